@@ -9,14 +9,80 @@
 //! supported token pairs, and swap limits.
 //!
 //! ## Callback Validation
-//! Uses nonce-based replay protection: each user has an incrementing nonce
-//! stored on-chain. Callbacks must present the expected nonce to be accepted.
+//! Uses nonce-based replay protection. Every operation routed to a protocol
+//! is issued the next nonce of the (protocol, user) sequence, starting at 1,
+//! and the protocol's callback must present it. Callbacks for any of the last
+//! `CALLBACK_NONCE_WINDOW` issued nonces are accepted in any order, so
+//! asynchronous AMMs may settle out of order, but each nonce only once.
+//! `get_next_nonce` returns the nonce the next operation will be issued.
+//!
+//! ## Hosted Pools
+//! Native constant-product pools and liquidity bootstrapping pools live in
+//! this contract and are registered under the built-in `stellarlend`
+//! protocol, whose address is the contract itself. Operations sent to that
+//! protocol are served by the native pool of the pair if there is one, and
+//! by the pair's LBP otherwise.
+//!
+//! ## Slippage Caps
+//! Every swap's `slippage_tolerance` is capped by `AmmSettings::max_slippage`.
+//! The admin can tighten the cap of individual pairs, such as illiquid ones,
+//! with `set_pair_max_slippage`, which keeps them in
+//! `AmmSettings::pair_max_slippage`. A pair cap never raises the limit above
+//! the global one, and pairs without one follow the global cap.
+//!
+//! ## Deadlines
+//! A swap deadline of 0 stands for `DEFAULT_DEADLINE_WINDOW` seconds from
+//! now, and explicit deadlines more than `AmmSettings::max_deadline_window`
+//! seconds out are rejected, so a signed swap cannot stay valid for long.
+//! Limit orders are exempt: their deadline is the order's expiry.
+//!
+//! ## Swap History
+//! Executed swaps are kept in a ring buffer of at most
+//! `AmmSettings::max_swap_history` records; records older than
+//! `AmmSettings::swap_history_retention` are dropped as new swaps arrive.
+//! Each record carries an increasing `id`, which `get_swap_history_page`
+//! uses as its cursor when paging from the newest record back.
+//!
+//! ## Swap Statistics
+//! Every executed swap adds to cumulative counters kept globally, per pair,
+//! per protocol and per user, read with `get_swap_stats`. Volumes are kept
+//! per token, as amounts of different tokens cannot be summed.
+//!
+//! ## Storage
+//! Settings, the protocol registry, the pair indexes of hosted pools and the
+//! global, pair and protocol counters are bounded by admin actions and live
+//! in instance storage, loaded with the contract. Per-user and per-pool data
+//! is persistent. This keeps the ledger entries a swap touches down to those
+//! of its user, pool and pair, leaving room for multi-swap calls such as
+//! leveraged position loops.
+//!
+//! ## Oracle Floor
+//! Swaps with `use_oracle_floor` derive their minimum output from the
+//! router's TWAP over `ORACLE_FLOOR_TWAP_WINDOW`, less the swap's slippage
+//! tolerance, so integrators that pass `min_amount_out: 0` are not exposed to
+//! sandwich attacks. Contracts embedding the router may resolve the floor
+//! from their own oracle with `oracle_floor` before submitting the swap.
 
 #![allow(unused)]
 use soroban_sdk::{
     contracterror, contractevent, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec,
 };
 
+/// Issued callback nonces of a (protocol, user) sequence that stay valid
+pub const CALLBACK_NONCE_WINDOW: u64 = 64;
+
+/// Swap records kept in the history by default
+pub const DEFAULT_MAX_SWAP_HISTORY: u32 = 1_000;
+
+/// Validity of a swap submitted with a deadline of 0 (seconds)
+pub const DEFAULT_DEADLINE_WINDOW: u64 = 300;
+
+/// Furthest a swap deadline may be set by default (seconds)
+pub const DEFAULT_MAX_DEADLINE_WINDOW: u64 = 3_600;
+
+/// TWAP window the oracle floor of a swap is priced over (seconds)
+pub const ORACLE_FLOOR_TWAP_WINDOW: u64 = 1_800;
+
 /// Errors that can occur during AMM operations
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -50,6 +116,28 @@ pub enum AmmError {
     MaxInputExceeded = 13,
     /// Contract has already been initialized
     AlreadyInitialized = 14,
+    /// Pool hosted by this contract not found
+    PoolNotFound = 15,
+    /// Invalid LBP weight schedule
+    InvalidWeightSchedule = 16,
+    /// Pool is not open for this operation
+    PoolNotActive = 17,
+    /// TWAP window must be greater than zero
+    InvalidTwapWindow = 18,
+    /// Not enough price history for the requested TWAP window
+    InsufficientObservations = 19,
+    /// Limit order not found
+    OrderNotFound = 20,
+    /// The routed price has not reached the order's limit price
+    LimitPriceNotReached = 21,
+    /// Limit order deadline has passed
+    OrderExpired = 22,
+    /// No route of registered pairs connects the two tokens
+    NoRouteFound = 23,
+    /// Not enough LP shares for the operation
+    InsufficientLpShares = 24,
+    /// Swap deadline is further out than the maximum deadline window
+    DeadlineTooFar = 25,
 }
 
 /// Storage keys for AMM-related data
@@ -65,10 +153,14 @@ pub enum AmmDataKey {
     SwapHistory,
     /// Liquidity operation history: Vec<LiquidityRecord>
     LiquidityHistory,
-    /// Callback validation nonces: Map<Address, u64>
-    CallbackNonces(Address),
+    /// Callback nonces of a (protocol, user) sequence: NonceWindow
+    CallbackNonces(Address, Address),
+    /// Cumulative swap statistics of a scope: SwapStats
+    SwapStats(SwapStatsScope),
     /// Admin address
     Admin,
+    /// Balance of a token held as reserves of hosted pools and LBPs: i128
+    HostedReserve(Address),
 }
 
 /// AMM protocol configuration
@@ -117,6 +209,16 @@ pub struct AmmSettings {
     pub liquidity_enabled: bool,
     /// Auto-swap threshold for collateral optimization
     pub auto_swap_threshold: i128,
+    /// Slippage caps of individual pairs, keyed in canonical token order
+    /// (in basis points, only tightening `max_slippage`)
+    pub pair_max_slippage: Map<(Option<Address>, Option<Address>), i128>,
+    /// Swap records kept in the history, oldest dropped first (at least one)
+    pub max_swap_history: u32,
+    /// Age in seconds after which swap records are dropped from the history
+    /// (0 = kept until `max_swap_history` is reached)
+    pub swap_history_retention: u64,
+    /// Furthest a swap deadline may be set from now, in seconds (0 = no limit)
+    pub max_deadline_window: u64,
 }
 
 /// Swap operation parameters
@@ -135,14 +237,20 @@ pub struct SwapParams {
     pub min_amount_out: i128,
     /// Maximum slippage tolerance (in basis points)
     pub slippage_tolerance: i128,
-    /// Deadline for the swap (timestamp)
+    /// Deadline for the swap (timestamp, 0 for `DEFAULT_DEADLINE_WINDOW`
+    /// from now)
     pub deadline: u64,
+    /// Raise `min_amount_out` to the oracle price less `slippage_tolerance`,
+    /// so callers may pass a `min_amount_out` of 0
+    pub use_oracle_floor: bool,
 }
 
 /// Swap operation record
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct SwapRecord {
+    /// Sequence number of the swap, increasing with every swap
+    pub id: u64,
     /// User who initiated the swap
     pub user: Address,
     /// AMM protocol used
@@ -163,6 +271,101 @@ pub struct SwapRecord {
     pub timestamp: u64,
     /// Transaction hash (for tracking)
     pub tx_hash: Symbol,
+    /// Per-protocol legs of a split swap (empty for single-protocol swaps)
+    pub splits: Vec<SwapSplit>,
+}
+
+/// Criteria selecting swap history records; unset fields match everything
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapHistoryFilter {
+    /// Only swaps initiated by this user
+    pub user: Option<Address>,
+    /// Only swaps routed to this AMM protocol
+    pub protocol: Option<Address>,
+    /// Only swaps at or after this timestamp
+    pub start_time: Option<u64>,
+    /// Only swaps at or before this timestamp
+    pub end_time: Option<u64>,
+}
+
+/// One page of swap history, newest record first
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapHistoryPage {
+    /// Matching records
+    pub records: Vec<SwapRecord>,
+    /// Cursor of the next page, if more records match
+    pub next_cursor: Option<u64>,
+}
+
+/// Set of swaps aggregated by a `SwapStats`
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SwapStatsScope {
+    /// Every swap
+    Global,
+    /// Swaps between two tokens, in either direction
+    Pair(Option<Address>, Option<Address>),
+    /// Swaps routed to an AMM protocol
+    Protocol(Address),
+    /// Swaps initiated by a user
+    User(Address),
+}
+
+/// Cumulative swap statistics
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapStats {
+    /// Number of swaps
+    pub swap_count: u64,
+    /// Amount of each token sold, keyed by token (None for native XLM)
+    pub volume_in: Map<Option<Address>, i128>,
+    /// Amount of each token bought, keyed by token (None for native XLM)
+    pub volume_out: Map<Option<Address>, i128>,
+    /// Protocol fees paid, keyed by the input token they were paid in
+    pub fees_paid: Map<Option<Address>, i128>,
+    /// Timestamp of the last swap (0 if none)
+    pub last_swap_at: u64,
+}
+
+/// Read-only quote of a swap
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapQuote {
+    /// AMM protocol quoted
+    pub protocol: Address,
+    /// Amount to swap
+    pub amount_in: i128,
+    /// Expected amount received
+    pub amount_out: i128,
+    /// Effective price (amount_out / amount_in * 10^18)
+    pub effective_price: i128,
+    /// Marginal price before the swap (10^18 scale)
+    pub spot_price: i128,
+    /// Shortfall of the effective price against the spot price (in basis
+    /// points), pool fees included
+    pub price_impact_bps: i128,
+    /// Protocol fee tier (in basis points)
+    pub fee_bps: i128,
+    /// Protocol fees charged on the input
+    pub fee_amount: i128,
+    /// Whether `amount_out` covers the requested `min_amount_out`
+    pub meets_min_amount_out: bool,
+}
+
+/// One protocol's share of a split swap
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapSplit {
+    /// AMM protocol that executed this leg
+    pub protocol: Address,
+    /// Share of the input routed to this protocol (in basis points)
+    pub ratio_bps: i128,
+    /// Amount swapped in on this protocol
+    pub amount_in: i128,
+    /// Amount received from this protocol
+    pub amount_out: i128,
 }
 
 /// Liquidity operation parameters
@@ -227,6 +430,16 @@ pub struct AmmCallbackData {
     pub deadline: u64,
 }
 
+/// Callback nonces issued for a (protocol, user) sequence
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct NonceWindow {
+    /// Nonce the next operation will be issued
+    pub next_nonce: u64,
+    /// Bit `i` is set once nonce `next_nonce - 1 - i` has been used
+    pub used: u64,
+}
+
 /// Execute a swap operation through AMM
 ///
 /// Performs token swaps using configured AMM protocols with slippage protection
@@ -242,9 +455,72 @@ pub struct AmmCallbackData {
 ///
 /// # Events
 /// Emits swap_executed, position_updated, and amm_operation events
-pub fn execute_swap(env: &Env, user: Address, params: SwapParams) -> Result<i128, AmmError> {
+pub fn execute_swap(env: &Env, user: Address, mut params: SwapParams) -> Result<i128, AmmError> {
+    params.deadline = resolve_deadline(env, params.deadline)?;
+    execute_swap_internal(env, user, params, false)
+}
+
+/// Execute a swap, optionally paying the input from tokens the router
+/// already holds on the user's behalf (e.g. a limit order's escrow)
+pub(crate) fn execute_swap_internal(
+    env: &Env,
+    user: Address,
+    params: SwapParams,
+    escrowed: bool,
+) -> Result<i128, AmmError> {
+    let params = apply_oracle_floor(env, params)?;
+    let (amount_out, fees_paid) = swap_on_protocol(env, &user, &params, escrowed)?;
+    complete_swap(env, &user, &params, amount_out, fees_paid, Vec::new(env))
+}
+
+/// Validate and execute a swap on `params.protocol` without recording it
+///
+/// # Returns
+/// Returns the amount received and the protocol fees paid
+pub(crate) fn swap_on_protocol(
+    env: &Env,
+    user: &Address,
+    params: &SwapParams,
+    escrowed: bool,
+) -> Result<(i128, i128), AmmError> {
+    let protocol_config = check_swap(env, params)?;
+
+    // Generate callback nonce for validation
+    let nonce = generate_callback_nonce(env, &params.protocol, user);
+
+    // Prepare callback data
+    let callback_data = AmmCallbackData {
+        nonce,
+        operation: Symbol::new(env, "swap"),
+        user: user.clone(),
+        expected_amounts: {
+            let mut amounts = Vec::new(env);
+            amounts.push_back(params.amount_in);
+            amounts.push_back(params.min_amount_out);
+            amounts
+        },
+        deadline: params.deadline,
+    };
+
+    // Execute the actual swap through AMM protocol
+    let amount_out = execute_amm_swap(env, params, &callback_data, escrowed)?;
+
+    // Validate minimum output
+    if amount_out < params.min_amount_out {
+        return Err(AmmError::MinOutputNotMet);
+    }
+
+    let fees_paid = calculate_swap_fees(&protocol_config, params.amount_in)?;
+    Ok((amount_out, fees_paid))
+}
+
+/// Run every check a swap must pass before execution
+///
+/// # Returns
+/// Returns the configuration of the protocol the swap is routed to
+fn check_swap(env: &Env, params: &SwapParams) -> Result<AmmProtocolConfig, AmmError> {
     // Validate swap parameters
-    validate_swap_params(env, &params)?;
+    validate_swap_params(env, params)?;
 
     // Check if swaps are enabled
     check_swap_enabled(env)?;
@@ -268,43 +544,97 @@ pub fn execute_swap(env: &Env, user: Address, params: SwapParams) -> Result<i128
     // Validate token pair is supported
     validate_token_pair(env, &protocol_config, &params.token_in, &params.token_out)?;
 
-    // Generate callback nonce for validation
-    let nonce = generate_callback_nonce(env, &user);
+    Ok(protocol_config)
+}
 
-    // Prepare callback data
-    let callback_data = AmmCallbackData {
-        nonce,
-        operation: Symbol::new(env, "swap"),
-        user: user.clone(),
-        expected_amounts: {
-            let mut amounts = Vec::new(env);
-            amounts.push_back(params.amount_in);
-            amounts.push_back(params.min_amount_out);
-            amounts
-        },
-        deadline: params.deadline,
-    };
+/// Quote a swap without executing it
+///
+/// Runs the same checks as `execute_swap` and prices the swap at the current
+/// state of the protocol, so callers can size trades before signing them.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `params` - Swap parameters as they would be passed to `execute_swap`
+///
+/// # Returns
+/// Returns the expected output, price impact and fees
+pub fn quote_swap(env: &Env, mut params: SwapParams) -> Result<SwapQuote, AmmError> {
+    params.deadline = resolve_deadline(env, params.deadline)?;
+    let params = apply_oracle_floor(env, params)?;
+    let protocol_config = check_swap(env, &params)?;
 
-    // Execute the actual swap through AMM protocol
-    let amount_out = execute_amm_swap(env, &params, &callback_data)?;
+    let amount_out = quote_amm_swap(env, &params)?;
+    let effective_price = calculate_effective_price(params.amount_in, amount_out)?;
+    let spot_price = quote_amm_spot_price(env, &params)?;
+    let price_impact_bps = if spot_price > effective_price {
+        (spot_price - effective_price)
+            .checked_mul(10_000)
+            .ok_or(AmmError::Overflow)?
+            / spot_price
+    } else {
+        0
+    };
 
-    // Validate minimum output
-    if amount_out < params.min_amount_out {
-        return Err(AmmError::MinOutputNotMet);
-    }
+    Ok(SwapQuote {
+        protocol: params.protocol,
+        amount_in: params.amount_in,
+        amount_out,
+        effective_price,
+        spot_price,
+        price_impact_bps,
+        fee_bps: protocol_config.fee_tier,
+        fee_amount: calculate_swap_fees(&protocol_config, params.amount_in)?,
+        meets_min_amount_out: amount_out >= params.min_amount_out,
+    })
+}
 
-    // Calculate effective price and fees
+/// Record an executed swap in the history and TWAP and emit its events
+///
+/// `splits` holds the per-protocol legs of a split swap and is empty for
+/// swaps executed on a single protocol.
+pub(crate) fn complete_swap(
+    env: &Env,
+    user: &Address,
+    params: &SwapParams,
+    amount_out: i128,
+    fees_paid: i128,
+    splits: Vec<SwapSplit>,
+) -> Result<i128, AmmError> {
+    // Calculate effective price
     let effective_price = calculate_effective_price(params.amount_in, amount_out)?;
-    let fees_paid = calculate_swap_fees(&protocol_config, params.amount_in)?;
 
     // Record swap in history
-    record_swap(env, &user, &params, amount_out, effective_price, fees_paid)?;
+    record_swap(
+        env,
+        user,
+        params,
+        amount_out,
+        effective_price,
+        fees_paid,
+        splits,
+    )?;
+    crate::twap::record_swap_price(
+        env,
+        &params.token_in,
+        &params.token_out,
+        params.amount_in,
+        amount_out,
+    )?;
+    let (token_a, token_b) = canonical_pair(&params.token_in, &params.token_out);
+    for scope in [
+        SwapStatsScope::Global,
+        SwapStatsScope::Pair(token_a, token_b),
+        SwapStatsScope::Protocol(params.protocol.clone()),
+        SwapStatsScope::User(user.clone()),
+    ] {
+        record_swap_stats(env, scope, params, amount_out, fees_paid)?;
+    }
 
     // Emit events
-    emit_swap_executed_event(env, &user, &params, amount_out, effective_price);
+    emit_swap_executed_event(env, user, params, amount_out, effective_price);
     emit_amm_operation_event(
         env,
-        &user,
+        user,
         Symbol::new(env, "swap"),
         params.amount_in,
         amount_out,
@@ -343,7 +673,7 @@ pub fn add_liquidity(env: &Env, user: Address, params: LiquidityParams) -> Resul
     validate_token_pair(env, &protocol_config, &params.token_a, &params.token_b)?;
 
     // Generate callback nonce
-    let nonce = generate_callback_nonce(env, &user);
+    let nonce = generate_callback_nonce(env, &params.protocol, &user);
 
     // Prepare callback data
     let callback_data = AmmCallbackData {
@@ -360,7 +690,12 @@ pub fn add_liquidity(env: &Env, user: Address, params: LiquidityParams) -> Resul
     };
 
     // Execute liquidity addition through AMM protocol
-    let lp_tokens = execute_amm_add_liquidity(env, &params, &callback_data)?;
+    let (lp_tokens, amount_a, amount_b) = execute_amm_add_liquidity(env, &params, &callback_data)?;
+    let params = LiquidityParams {
+        amount_a,
+        amount_b,
+        ..params
+    };
 
     // Record liquidity operation
     record_liquidity_operation(env, &user, Symbol::new(env, "add"), &params, lp_tokens)?;
@@ -427,7 +762,7 @@ pub fn remove_liquidity(
     validate_token_pair(env, &protocol_config, &token_a, &token_b)?;
 
     // Generate callback nonce
-    let nonce = generate_callback_nonce(env, &user);
+    let nonce = generate_callback_nonce(env, &protocol, &user);
 
     // Prepare callback data
     let callback_data = AmmCallbackData {
@@ -516,31 +851,19 @@ pub fn validate_amm_callback(
         return Err(AmmError::InvalidCallback);
     }
 
-    // Validate nonce to prevent replay attacks
-    let nonce_key = AmmDataKey::CallbackNonces(callback_data.user.clone());
-    let expected_nonce = env
-        .storage()
-        .persistent()
-        .get::<AmmDataKey, u64>(&nonce_key)
-        .unwrap_or(0);
-
-    if callback_data.nonce != expected_nonce {
+    // Accept each issued nonce of the window once, in any order
+    let nonce_key = AmmDataKey::CallbackNonces(caller.clone(), callback_data.user.clone());
+    let mut window = get_nonce_window(env, &caller, &callback_data.user);
+    let nonce = callback_data.nonce;
+    if nonce == 0 || nonce >= window.next_nonce {
         return Err(AmmError::InvalidCallback);
     }
-
-    // Increment nonce to prevent reuse
-    // Note: If called from execute_swap, the nonce was already incremented there.
-    // However, validate_amm_callback is intended for the AMM to CALL BACK into our contract.
-    // The current logic in execute_swap/add_liquidity/remove_liquidity ALREADY increments the nonce
-    // when preparing callback_data.
-    // Wait, let's look at generate_callback_nonce: it increments and returns NEW nonce.
-    // So if storage has 0, generate returns 1 and sets storage to 1.
-    // Then validate_amm_callback gets 1, compares with 1, and sets to 2.
-    // This is correct as it "consumes" the nonce for NEXT time.
-
-    env.storage()
-        .persistent()
-        .set(&nonce_key, &(expected_nonce + 1));
+    let age = window.next_nonce - 1 - nonce;
+    if age >= CALLBACK_NONCE_WINDOW || window.used & (1 << age) != 0 {
+        return Err(AmmError::InvalidCallback);
+    }
+    window.used |= 1 << age;
+    env.storage().persistent().set(&nonce_key, &window);
 
     // Emit callback validation event
     emit_callback_validated_event(env, &caller, &callback_data);
@@ -551,6 +874,8 @@ pub fn validate_amm_callback(
 /// Auto-swap for collateral optimization
 ///
 /// Automatically swaps assets to optimize collateral ratios during lending operations.
+/// Swaps follow the best route of registered pairs, which may chain up to
+/// `routing::MAX_ROUTE_HOPS` pairs when no direct pair pays more.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
@@ -577,22 +902,17 @@ pub fn auto_swap_for_collateral(
         return Err(AmmError::InvalidSwapParams);
     }
 
-    // Find best AMM protocol for this swap
-    let best_protocol = find_best_amm_protocol(env, &None, &target_token, amount)?;
-
-    // Create swap parameters with default slippage
-    let params = SwapParams {
-        protocol: best_protocol,
-        token_in: None, // Assume swapping from native XLM
-        token_out: target_token,
-        amount_in: amount,
-        min_amount_out: calculate_min_output_with_slippage(amount, settings.default_slippage)?,
-        slippage_tolerance: settings.default_slippage,
-        deadline: env.ledger().timestamp() + 300, // 5 minutes
-    };
+    // Find the best direct or multi-hop route, assuming a swap from native XLM
+    let route = crate::routing::quote_route(env, None, target_token, amount)?;
 
-    // Execute the swap
-    let amount_out = execute_swap(env, user, params)?;
+    // Execute the route with default slippage
+    let amount_out = crate::routing::execute_route(
+        env,
+        &user,
+        &route,
+        settings.default_slippage,
+        env.ledger().timestamp() + 300, // 5 minutes
+    )?;
 
     Ok(amount_out)
 }
@@ -600,7 +920,7 @@ pub fn auto_swap_for_collateral(
 // Helper functions
 
 /// Validate swap parameters
-fn validate_swap_params(env: &Env, params: &SwapParams) -> Result<(), AmmError> {
+pub(crate) fn validate_swap_params(env: &Env, params: &SwapParams) -> Result<(), AmmError> {
     if params.amount_in <= 0 {
         return Err(AmmError::InvalidSwapParams);
     }
@@ -613,14 +933,76 @@ fn validate_swap_params(env: &Env, params: &SwapParams) -> Result<(), AmmError>
         return Err(AmmError::InvalidTokenPair);
     }
 
-    let settings = get_amm_settings(env)?;
-    if params.slippage_tolerance > settings.max_slippage {
+    let max_slippage = get_pair_max_slippage(env, &params.token_in, &params.token_out)?;
+    if params.slippage_tolerance > max_slippage {
         return Err(AmmError::SlippageExceeded);
     }
 
     Ok(())
 }
 
+/// Minimum output of selling `amount_in` at `price` less `slippage_bps`
+///
+/// # Arguments
+/// * `amount_in` - Amount to swap
+/// * `price` - Units of the output token per unit of the input token (scaled by 10^18)
+/// * `slippage_bps` - Tolerated shortfall against `price` (in basis points)
+///
+/// # Returns
+/// Returns the floor, at least 1
+pub fn oracle_floor(amount_in: i128, price: i128, slippage_bps: i128) -> Result<i128, AmmError> {
+    if price <= 0 || !(0..10_000).contains(&slippage_bps) {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    let value =
+        amount_in.checked_mul(price).ok_or(AmmError::Overflow)? / 1_000_000_000_000_000_000i128;
+    Ok(calculate_min_output_with_slippage(value, slippage_bps)?.max(1))
+}
+
+/// Resolve the oracle floor of a swap that asks for one
+///
+/// The floor is priced at the router's TWAP of the pair and replaces
+/// `min_amount_out` when it is higher.
+fn apply_oracle_floor(env: &Env, mut params: SwapParams) -> Result<SwapParams, AmmError> {
+    if !params.use_oracle_floor {
+        return Ok(params);
+    }
+    let price = crate::twap::get_twap(
+        env,
+        &params.token_in,
+        &params.token_out,
+        ORACLE_FLOOR_TWAP_WINDOW,
+    )?;
+    let floor = oracle_floor(params.amount_in, price, params.slippage_tolerance)?;
+    params.min_amount_out = params.min_amount_out.max(floor);
+    params.use_oracle_floor = false;
+    Ok(params)
+}
+
+/// Resolve the deadline of a swap
+///
+/// A deadline of 0 becomes `DEFAULT_DEADLINE_WINDOW` seconds from now, or
+/// the maximum window if that is shorter.
+///
+/// # Errors
+/// * `AmmError::DeadlineTooFar` - If the deadline is more than
+///   `max_deadline_window` seconds from now
+pub(crate) fn resolve_deadline(env: &Env, deadline: u64) -> Result<u64, AmmError> {
+    let max_window = get_amm_settings(env)?.max_deadline_window;
+    let now = env.ledger().timestamp();
+    if deadline == 0 {
+        let window = match max_window {
+            0 => DEFAULT_DEADLINE_WINDOW,
+            max_window => DEFAULT_DEADLINE_WINDOW.min(max_window),
+        };
+        return Ok(now.saturating_add(window));
+    }
+    if max_window > 0 && deadline > now.saturating_add(max_window) {
+        return Err(AmmError::DeadlineTooFar);
+    }
+    Ok(deadline)
+}
+
 /// Validate liquidity parameters
 fn validate_liquidity_params(env: &Env, params: &LiquidityParams) -> Result<(), AmmError> {
     if params.amount_a <= 0 || params.amount_b <= 0 {
@@ -639,7 +1021,7 @@ fn validate_liquidity_params(env: &Env, params: &LiquidityParams) -> Result<(),
 }
 
 /// Check if swap operations are enabled
-fn check_swap_enabled(env: &Env) -> Result<(), AmmError> {
+pub(crate) fn check_swap_enabled(env: &Env) -> Result<(), AmmError> {
     let settings = get_amm_settings(env)?;
     if !settings.swap_enabled {
         return Err(AmmError::SwapPaused);
@@ -657,7 +1039,10 @@ fn check_liquidity_enabled(env: &Env) -> Result<(), AmmError> {
 }
 
 /// Get AMM protocol configuration
-fn get_amm_protocol_config(env: &Env, protocol: &Address) -> Result<AmmProtocolConfig, AmmError> {
+pub(crate) fn get_amm_protocol_config(
+    env: &Env,
+    protocol: &Address,
+) -> Result<AmmProtocolConfig, AmmError> {
     let protocols = get_amm_protocols(env)?;
     protocols
         .get(protocol.clone())
@@ -668,22 +1053,94 @@ fn get_amm_protocol_config(env: &Env, protocol: &Address) -> Result<AmmProtocolC
 pub fn get_amm_protocols(env: &Env) -> Result<Map<Address, AmmProtocolConfig>, AmmError> {
     let protocols_key = AmmDataKey::AmmProtocols;
     env.storage()
-        .persistent()
+        .instance()
         .get::<AmmDataKey, Map<Address, AmmProtocolConfig>>(&protocols_key)
         .ok_or(AmmError::UnsupportedProtocol)
 }
 
+/// Register a pair of a pool hosted by this contract under the built-in
+/// `stellarlend` protocol, whose protocol address is this contract
+pub(crate) fn register_hosted_pair(env: &Env, token_a: &Address, token_b: &Address, fee_bps: i128) {
+    let router = env.current_contract_address();
+    let mut protocols =
+        get_amm_protocols(env).unwrap_or_else(|_| Map::<Address, AmmProtocolConfig>::new(env));
+    let mut config = protocols
+        .get(router.clone())
+        .unwrap_or_else(|| AmmProtocolConfig {
+            protocol_address: router.clone(),
+            protocol_name: Symbol::new(env, "stellarlend"),
+            enabled: true,
+            fee_tier: fee_bps,
+            min_swap_amount: 1,
+            max_swap_amount: i128::MAX,
+            supported_pairs: Vec::new(env),
+        });
+
+    config.supported_pairs.push_back(TokenPair {
+        token_a: Some(token_a.clone()),
+        token_b: Some(token_b.clone()),
+        pool_address: router.clone(),
+    });
+    protocols.set(router, config);
+    env.storage()
+        .instance()
+        .set(&AmmDataKey::AmmProtocols, &protocols);
+}
+
+/// Get the balance of `token` this contract holds as reserves of hosted
+/// pools, native and LBP
+pub fn get_hosted_reserve(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get::<AmmDataKey, i128>(&AmmDataKey::HostedReserve(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Add `delta` to the hosted reserve of `token`, flooring at zero
+pub(crate) fn record_hosted_reserve_change(env: &Env, token: &Address, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+    let total = get_hosted_reserve(env, token).saturating_add(delta).max(0);
+    env.storage()
+        .instance()
+        .set(&AmmDataKey::HostedReserve(token.clone()), &total);
+}
+
+/// Remove a hosted pool's pair from the built-in `stellarlend` protocol
+pub(crate) fn unregister_hosted_pair(env: &Env, token_a: &Address, token_b: &Address) {
+    let router = env.current_contract_address();
+    let Ok(mut protocols) = get_amm_protocols(env) else {
+        return;
+    };
+    let Some(mut config) = protocols.get(router.clone()) else {
+        return;
+    };
+
+    let mut remaining = Vec::new(env);
+    for pair in config.supported_pairs.iter() {
+        if pair.token_a != Some(token_a.clone()) || pair.token_b != Some(token_b.clone()) {
+            remaining.push_back(pair);
+        }
+    }
+    config.supported_pairs = remaining;
+    protocols.set(router, config);
+    env.storage()
+        .instance()
+        .set(&AmmDataKey::AmmProtocols, &protocols);
+}
+
 /// Get AMM settings
 pub fn get_amm_settings(env: &Env) -> Result<AmmSettings, AmmError> {
     let settings_key = AmmDataKey::AmmSettings;
     env.storage()
-        .persistent()
+        .instance()
         .get::<AmmDataKey, AmmSettings>(&settings_key)
         .ok_or(AmmError::InvalidSwapParams)
 }
 
 /// Validate token pair is supported by protocol
-fn validate_token_pair(
+pub(crate) fn validate_token_pair(
     env: &Env,
     protocol_config: &AmmProtocolConfig,
     token_a: &Option<Address>,
@@ -699,18 +1156,42 @@ fn validate_token_pair(
     Err(AmmError::InvalidTokenPair)
 }
 
-/// Generate callback nonce for validation
-fn generate_callback_nonce(env: &Env, user: &Address) -> u64 {
-    let nonce_key = AmmDataKey::CallbackNonces(user.clone());
-    let current_nonce = env
-        .storage()
+/// Issue the next callback nonce of a (protocol, user) sequence
+pub(crate) fn generate_callback_nonce(env: &Env, protocol: &Address, user: &Address) -> u64 {
+    let mut window = get_nonce_window(env, protocol, user);
+    let nonce = window.next_nonce;
+    window.next_nonce += 1;
+    // The new nonce takes bit 0; the oldest nonce leaves the window
+    window.used <<= 1;
+    env.storage().persistent().set(
+        &AmmDataKey::CallbackNonces(protocol.clone(), user.clone()),
+        &window,
+    );
+    nonce
+}
+
+/// Get the callback nonces issued for a (protocol, user) sequence
+fn get_nonce_window(env: &Env, protocol: &Address, user: &Address) -> NonceWindow {
+    env.storage()
         .persistent()
-        .get::<AmmDataKey, u64>(&nonce_key)
-        .unwrap_or(0);
+        .get::<AmmDataKey, NonceWindow>(&AmmDataKey::CallbackNonces(protocol.clone(), user.clone()))
+        .unwrap_or(NonceWindow {
+            next_nonce: 1,
+            used: 0,
+        })
+}
 
-    let new_nonce = current_nonce + 1;
-    env.storage().persistent().set(&nonce_key, &new_nonce);
-    new_nonce
+/// Get the nonce the next operation of `user` on `protocol` will be issued
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `protocol` - The AMM protocol the operation is routed to
+/// * `user` - The user performing the operation
+///
+/// # Returns
+/// Returns the next nonce of the sequence, starting at 1
+pub fn get_next_nonce(env: &Env, protocol: &Address, user: &Address) -> u64 {
+    get_nonce_window(env, protocol, user).next_nonce
 }
 
 /// Calculate effective price
@@ -739,7 +1220,10 @@ fn calculate_swap_fees(
 }
 
 /// Calculate minimum output with slippage
-fn calculate_min_output_with_slippage(amount: i128, slippage_bps: i128) -> Result<i128, AmmError> {
+pub(crate) fn calculate_min_output_with_slippage(
+    amount: i128,
+    slippage_bps: i128,
+) -> Result<i128, AmmError> {
     let slippage_factor = 10_000 - slippage_bps;
     let min_output = (amount * slippage_factor)
         .checked_div(10_000)
@@ -747,52 +1231,86 @@ fn calculate_min_output_with_slippage(amount: i128, slippage_bps: i128) -> Resul
     Ok(min_output)
 }
 
-/// Find best AMM protocol for a swap
-fn find_best_amm_protocol(
-    env: &Env,
-    token_in: &Option<Address>,
-    token_out: &Option<Address>,
-    amount: i128,
-) -> Result<Address, AmmError> {
-    let protocols = get_amm_protocols(env)?;
-
-    let mut best_protocol: Option<Address> = None;
-    let mut best_output = 0i128;
+// Mock AMM protocol interaction functions
+// In a real implementation, these would call external AMM contracts
 
-    for (protocol_addr, config) in protocols.iter() {
-        if !config.enabled {
-            continue;
+/// Quote the output of a swap through AMM protocol without executing it
+pub(crate) fn quote_amm_swap(env: &Env, params: &SwapParams) -> Result<i128, AmmError> {
+    // Native pools and liquidity bootstrapping pools are hosted by this contract
+    if params.protocol == env.current_contract_address() {
+        if crate::pools::serves_pair(env, &params.token_in, &params.token_out) {
+            return crate::pools::quote_through_router(env, params);
         }
+        return crate::lbp::quote_through_router(env, params);
+    }
 
-        // Check if protocol supports this token pair
-        if validate_token_pair(env, &config, token_in, token_out).is_ok() {
-            // For simplicity, we'll use the first valid protocol
-            // In a real implementation, you'd query each protocol for quotes
-            if best_protocol.is_none() {
-                best_protocol = Some(protocol_addr);
-                best_output = amount; // Placeholder
-            }
+    // Mock implementation - in reality, this would query the AMM protocol contract
+    // For now, we'll simulate a successful swap with some slippage
+    let slippage_factor = 10_000 - params.slippage_tolerance;
+    (params.amount_in * slippage_factor)
+        .checked_div(10_000)
+        .ok_or(AmmError::Overflow)
+}
+
+/// Quote the marginal price of `params.token_in` in `params.token_out` (10^18 scale)
+pub(crate) fn quote_amm_spot_price(env: &Env, params: &SwapParams) -> Result<i128, AmmError> {
+    // Native pools and liquidity bootstrapping pools are hosted by this contract
+    if params.protocol == env.current_contract_address() {
+        if crate::pools::serves_pair(env, &params.token_in, &params.token_out) {
+            return crate::pools::spot_price_through_router(env, params);
         }
+        return crate::lbp::spot_price_through_router(env, params);
     }
 
-    best_protocol.ok_or(AmmError::UnsupportedProtocol)
+    // Mock implementation - the simulated rate does not depend on size
+    let slippage_factor = 10_000 - params.slippage_tolerance;
+    (slippage_factor * 1_000_000_000_000_000_000i128)
+        .checked_div(10_000)
+        .ok_or(AmmError::Overflow)
 }
 
-// Mock AMM protocol interaction functions
-// In a real implementation, these would call external AMM contracts
+/// Quote the depth a protocol offers for selling `params.token_in`
+///
+/// Depth is the input-side liquidity a swap is measured against: the larger
+/// it is, the less a given input moves the price.
+pub(crate) fn quote_amm_depth(
+    env: &Env,
+    protocol_config: &AmmProtocolConfig,
+    params: &SwapParams,
+) -> Result<i128, AmmError> {
+    // Native pools and liquidity bootstrapping pools are hosted by this contract
+    if protocol_config.protocol_address == env.current_contract_address() {
+        if crate::pools::serves_pair(env, &params.token_in, &params.token_out) {
+            return crate::pools::depth_through_router(env, params);
+        }
+        return crate::lbp::depth_through_router(env, params);
+    }
+
+    // Mock implementation - in reality, this would query the pool reserves
+    // For now, the configured swap capacity stands in for the depth
+    Ok(protocol_config.max_swap_amount)
+}
 
 /// Execute swap through AMM protocol
 fn execute_amm_swap(
     env: &Env,
     params: &SwapParams,
     callback_data: &AmmCallbackData,
+    escrowed: bool,
 ) -> Result<i128, AmmError> {
+    // Native pools and liquidity bootstrapping pools are hosted by this contract
+    if params.protocol == env.current_contract_address() {
+        let amount_out = if crate::pools::serves_pair(env, &params.token_in, &params.token_out) {
+            crate::pools::swap_through_router(env, &callback_data.user, params, escrowed)?
+        } else {
+            crate::lbp::swap_through_router(env, &callback_data.user, params, escrowed)?
+        };
+        validate_amm_callback(env, params.protocol.clone(), callback_data.clone())?;
+        return Ok(amount_out);
+    }
+
     // Mock implementation - in reality, this would call the AMM protocol contract
-    // For now, we'll simulate a successful swap with some slippage
-    let slippage_factor = 10_000 - params.slippage_tolerance;
-    let amount_out = (params.amount_in * slippage_factor)
-        .checked_div(10_000)
-        .ok_or(AmmError::Overflow)?;
+    let amount_out = quote_amm_swap(env, params)?;
 
     // Validate callback (this would be called by the AMM protocol)
     validate_amm_callback(env, params.protocol.clone(), callback_data.clone())?;
@@ -801,18 +1319,28 @@ fn execute_amm_swap(
 }
 
 /// Execute add liquidity through AMM protocol
+///
+/// # Returns
+/// Returns the LP tokens received and the (token_a, token_b) amounts deposited
 fn execute_amm_add_liquidity(
     env: &Env,
     params: &LiquidityParams,
     callback_data: &AmmCallbackData,
-) -> Result<i128, AmmError> {
+) -> Result<(i128, i128, i128), AmmError> {
+    // Native pools are hosted by this contract
+    if params.protocol == env.current_contract_address() {
+        let deposit = crate::pools::add_through_router(env, &callback_data.user, params)?;
+        validate_amm_callback(env, params.protocol.clone(), callback_data.clone())?;
+        return Ok(deposit);
+    }
+
     // Mock implementation
     let lp_tokens = (params.amount_a + params.amount_b) / 2; // Simplified calculation
 
     // Validate callback
     validate_amm_callback(env, params.protocol.clone(), callback_data.clone())?;
 
-    Ok(lp_tokens)
+    Ok((lp_tokens, params.amount_a, params.amount_b))
 }
 
 /// Execute remove liquidity through AMM protocol
@@ -827,6 +1355,19 @@ fn execute_amm_remove_liquidity(
     min_amount_b: i128,
     callback_data: &AmmCallbackData,
 ) -> Result<(i128, i128), AmmError> {
+    // Native pools are hosted by this contract
+    if *protocol == env.current_contract_address() {
+        let amounts = crate::pools::remove_through_router(
+            env,
+            &callback_data.user,
+            token_a,
+            token_b,
+            lp_tokens,
+        )?;
+        validate_amm_callback(env, protocol.clone(), callback_data.clone())?;
+        return Ok(amounts);
+    }
+
     // Mock implementation
     let amount_a = lp_tokens; // Simplified
     let amount_b = lp_tokens; // Simplified
@@ -845,6 +1386,7 @@ fn record_swap(
     amount_out: i128,
     effective_price: i128,
     fees_paid: i128,
+    splits: Vec<SwapSplit>,
 ) -> Result<(), AmmError> {
     let history_key = AmmDataKey::SwapHistory;
     let mut history = env
@@ -853,7 +1395,9 @@ fn record_swap(
         .get::<AmmDataKey, Vec<SwapRecord>>(&history_key)
         .unwrap_or_else(|| Vec::new(env));
 
+    let now = env.ledger().timestamp();
     let record = SwapRecord {
+        id: history.last().map_or(0, |last| last.id + 1),
         user: user.clone(),
         protocol: params.protocol.clone(),
         token_in: params.token_in.clone(),
@@ -862,16 +1406,26 @@ fn record_swap(
         amount_out,
         effective_price,
         fees_paid,
-        timestamp: env.ledger().timestamp(),
+        timestamp: now,
         tx_hash: Symbol::new(env, "mock_tx_hash"), // In reality, this would be the actual tx hash
+        splits,
     };
 
     history.push_back(record);
 
-    // Keep only last 1000 records
-    if history.len() > 1000 {
+    // Drop the oldest records beyond the size and age limits, always
+    // keeping the newest so ids keep increasing
+    let settings = get_amm_settings(env)?;
+    let max_records = settings.max_swap_history.max(1);
+    while history.len() > max_records {
         history.pop_front();
     }
+    if settings.swap_history_retention > 0 {
+        let cutoff = now.saturating_sub(settings.swap_history_retention);
+        while history.len() > 1 && history.first().is_some_and(|r| r.timestamp < cutoff) {
+            history.pop_front();
+        }
+    }
 
     env.storage().persistent().set(&history_key, &history);
     Ok(())
@@ -918,14 +1472,28 @@ fn record_liquidity_operation(
 // Event emission functions
 
 // Event structs
-#[contractevent]
+
+/// Published under the versioned topics `["swap_executed_event", "v1"]`
+/// with the fields as a map keyed by field name
+#[contractevent(topics = ["swap_executed_event", "v1"])]
 #[derive(Clone, Debug)]
 pub struct SwapExecutedEvent {
     pub user: Address,
     pub protocol: Address,
+    pub token_in: Option<Address>,
+    pub token_out: Option<Address>,
     pub amount_in: i128,
     pub amount_out: i128,
     pub effective_price: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PairSlippageCapUpdatedEvent {
+    pub token_a: Option<Address>,
+    pub token_b: Option<Address>,
+    pub max_slippage: Option<i128>,
 }
 
 #[contractevent]
@@ -976,9 +1544,12 @@ fn emit_swap_executed_event(
     SwapExecutedEvent {
         user: user.clone(),
         protocol: params.protocol.clone(),
+        token_in: params.token_in.clone(),
+        token_out: params.token_out.clone(),
         amount_in: params.amount_in,
         amount_out,
         effective_price,
+        timestamp: env.ledger().timestamp(),
     }
     .publish(env);
 }
@@ -1069,15 +1640,19 @@ pub fn initialize_amm_settings(
         swap_enabled: true,
         liquidity_enabled: true,
         auto_swap_threshold,
+        pair_max_slippage: Map::new(env),
+        max_swap_history: DEFAULT_MAX_SWAP_HISTORY,
+        swap_history_retention: 0,
+        max_deadline_window: DEFAULT_MAX_DEADLINE_WINDOW,
     };
 
     let settings_key = AmmDataKey::AmmSettings;
-    env.storage().persistent().set(&settings_key, &settings);
+    env.storage().instance().set(&settings_key, &settings);
 
     // Initialize empty protocols map
     let protocols_key = AmmDataKey::AmmProtocols;
     let protocols: Map<Address, AmmProtocolConfig> = Map::new(env);
-    env.storage().persistent().set(&protocols_key, &protocols);
+    env.storage().instance().set(&protocols_key, &protocols);
 
     Ok(())
 }
@@ -1094,12 +1669,12 @@ pub fn add_amm_protocol(
     let protocols_key = AmmDataKey::AmmProtocols;
     let mut protocols = env
         .storage()
-        .persistent()
+        .instance()
         .get::<AmmDataKey, Map<Address, AmmProtocolConfig>>(&protocols_key)
         .unwrap_or_else(|| Map::new(env));
 
     protocols.set(protocol_config.protocol_address.clone(), protocol_config);
-    env.storage().persistent().set(&protocols_key, &protocols);
+    env.storage().instance().set(&protocols_key, &protocols);
 
     Ok(())
 }
@@ -1114,13 +1689,89 @@ pub fn update_amm_settings(
     require_admin(env, &admin)?;
 
     let settings_key = AmmDataKey::AmmSettings;
-    env.storage().persistent().set(&settings_key, &settings);
+    env.storage().instance().set(&settings_key, &settings);
 
     Ok(())
 }
 
+/// Override the slippage cap of a token pair (admin only)
+///
+/// The override applies to both swap directions and only tightens the
+/// global `max_slippage`.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `admin` - The admin address
+/// * `token_a` - One token of the pair (None for native XLM)
+/// * `token_b` - The other token of the pair (None for native XLM)
+/// * `max_slippage` - Slippage cap of the pair in basis points, or None to
+///   remove the override
+pub fn set_pair_max_slippage(
+    env: &Env,
+    admin: Address,
+    token_a: Option<Address>,
+    token_b: Option<Address>,
+    max_slippage: Option<i128>,
+) -> Result<(), AmmError> {
+    admin.require_auth();
+    require_admin(env, &admin)?;
+
+    if token_a == token_b {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    let mut settings = get_amm_settings(env)?;
+    let pair = canonical_pair(&token_a, &token_b);
+    match max_slippage {
+        Some(cap) if !(0..10_000).contains(&cap) => return Err(AmmError::InvalidSwapParams),
+        Some(cap) => settings.pair_max_slippage.set(pair, cap),
+        None => {
+            settings.pair_max_slippage.remove(pair);
+        }
+    }
+    env.storage()
+        .instance()
+        .set(&AmmDataKey::AmmSettings, &settings);
+
+    PairSlippageCapUpdatedEvent {
+        token_a,
+        token_b,
+        max_slippage,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Largest slippage tolerance a swap between two tokens may use (basis points)
+///
+/// The pair's override if it is tighter than the global `max_slippage`,
+/// otherwise the global cap.
+pub fn get_pair_max_slippage(
+    env: &Env,
+    token_a: &Option<Address>,
+    token_b: &Option<Address>,
+) -> Result<i128, AmmError> {
+    let settings = get_amm_settings(env)?;
+    Ok(settings
+        .pair_max_slippage
+        .get(canonical_pair(token_a, token_b))
+        .map_or(settings.max_slippage, |cap| cap.min(settings.max_slippage)))
+}
+
+/// A token pair in canonical order
+fn canonical_pair(
+    token_a: &Option<Address>,
+    token_b: &Option<Address>,
+) -> (Option<Address>, Option<Address>) {
+    if token_a <= token_b {
+        (token_a.clone(), token_b.clone())
+    } else {
+        (token_b.clone(), token_a.clone())
+    }
+}
+
 /// Check if caller is admin
-fn require_admin(env: &Env, caller: &Address) -> Result<(), AmmError> {
+pub(crate) fn require_admin(env: &Env, caller: &Address) -> Result<(), AmmError> {
     let admin_key = AmmDataKey::Admin;
     let admin = env
         .storage()
@@ -1136,39 +1787,144 @@ fn require_admin(env: &Env, caller: &Address) -> Result<(), AmmError> {
 
 // Public query functions for analytics
 
+/// Add a swap to the statistics of a scope
+fn record_swap_stats(
+    env: &Env,
+    scope: SwapStatsScope,
+    params: &SwapParams,
+    amount_out: i128,
+    fees_paid: i128,
+) -> Result<(), AmmError> {
+    let add = |totals: &mut Map<Option<Address>, i128>, token: &Option<Address>, amount| {
+        let total = totals
+            .get(token.clone())
+            .unwrap_or(0)
+            .checked_add(amount)
+            .ok_or(AmmError::Overflow)?;
+        totals.set(token.clone(), total);
+        Ok::<(), AmmError>(())
+    };
+
+    let mut stats = get_swap_stats(env, scope.clone());
+    stats.swap_count += 1;
+    add(&mut stats.volume_in, &params.token_in, params.amount_in)?;
+    add(&mut stats.volume_out, &params.token_out, amount_out)?;
+    add(&mut stats.fees_paid, &params.token_in, fees_paid)?;
+    stats.last_swap_at = env.ledger().timestamp();
+
+    let key = AmmDataKey::SwapStats(scope.clone());
+    match scope {
+        SwapStatsScope::User(_) => env.storage().persistent().set(&key, &stats),
+        _ => env.storage().instance().set(&key, &stats),
+    }
+    Ok(())
+}
+
+/// Get the cumulative swap statistics of a scope
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `scope` - Global, or the pair, protocol or user to report on
+///
+/// # Returns
+/// Returns the statistics, all zero if no swap matched the scope yet
+pub fn get_swap_stats(env: &Env, scope: SwapStatsScope) -> SwapStats {
+    // Pairs are kept in canonical order
+    let scope = match scope {
+        SwapStatsScope::Pair(token_a, token_b) => {
+            let (token_a, token_b) = canonical_pair(&token_a, &token_b);
+            SwapStatsScope::Pair(token_a, token_b)
+        }
+        scope => scope,
+    };
+    let key = AmmDataKey::SwapStats(scope.clone());
+    let stats = match scope {
+        SwapStatsScope::User(_) => env.storage().persistent().get(&key),
+        _ => env.storage().instance().get(&key),
+    };
+    stats.unwrap_or_else(|| SwapStats {
+        swap_count: 0,
+        volume_in: Map::new(env),
+        volume_out: Map::new(env),
+        fees_paid: Map::new(env),
+        last_swap_at: 0,
+    })
+}
+
 /// Get swap history
 pub fn get_swap_history(
     env: &Env,
     user: Option<Address>,
     limit: u32,
 ) -> Result<Vec<SwapRecord>, AmmError> {
-    let history_key = AmmDataKey::SwapHistory;
+    let filter = SwapHistoryFilter {
+        user,
+        protocol: None,
+        start_time: None,
+        end_time: None,
+    };
+    Ok(get_swap_history_page(env, &filter, None, limit).records)
+}
+
+/// Get a page of swap history, newest record first
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `filter` - Criteria the records must match
+/// * `cursor` - `next_cursor` of the previous page, or None for the first page
+/// * `limit` - Maximum number of records to return
+///
+/// # Returns
+/// Returns the matching records with ids below the cursor and the cursor
+/// of the next page
+pub fn get_swap_history_page(
+    env: &Env,
+    filter: &SwapHistoryFilter,
+    cursor: Option<u64>,
+    limit: u32,
+) -> SwapHistoryPage {
     let history = env
         .storage()
         .persistent()
-        .get::<AmmDataKey, Vec<SwapRecord>>(&history_key)
+        .get::<AmmDataKey, Vec<SwapRecord>>(&AmmDataKey::SwapHistory)
         .unwrap_or_else(|| Vec::new(env));
 
-    let mut filtered_history = Vec::new(env);
-    let mut count = 0u32;
-
+    let mut records: Vec<SwapRecord> = Vec::new(env);
+    let mut next_cursor = None;
     for record in history.iter().rev() {
-        if count >= limit {
+        if cursor.is_some_and(|cursor| record.id >= cursor) {
+            continue;
+        }
+        // Records are in time order, so no older one can match either
+        if filter
+            .start_time
+            .is_some_and(|start| record.timestamp < start)
+        {
             break;
         }
-
-        if let Some(ref filter_user) = user {
-            if record.user == *filter_user {
-                filtered_history.push_back(record);
-                count += 1;
-            }
-        } else {
-            filtered_history.push_back(record);
-            count += 1;
+        if filter.end_time.is_some_and(|end| record.timestamp > end)
+            || filter
+                .user
+                .as_ref()
+                .is_some_and(|user| record.user != *user)
+            || filter
+                .protocol
+                .as_ref()
+                .is_some_and(|protocol| record.protocol != *protocol)
+        {
+            continue;
+        }
+        if records.len() >= limit {
+            next_cursor = records.last().map(|last| last.id);
+            break;
         }
+        records.push_back(record);
     }
 
-    Ok(filtered_history)
+    SwapHistoryPage {
+        records,
+        next_cursor,
+    }
 }
 
 /// Get liquidity history
@@ -0,0 +1,765 @@
+//! # Liquidity Bootstrapping Pools
+//!
+//! Two-token weighted pools whose weights shift linearly over an admin-set
+//! schedule. Starting with a heavy weight on the launched token and shifting
+//! it towards the reserve token lets price discovery start high and decay
+//! until demand meets supply, which makes LBPs suitable for fair launches of
+//! the protocol token or for seeding liquidity of new collateral assets.
+//!
+//! ## Pricing
+//! Swaps follow the weighted constant-product invariant
+//! `B_a^w_a * B_b^w_b = k`, so for an input `a` (after fees):
+//!
+//! `amount_out = B_out * (1 - (B_in / (B_in + a)) ^ (w_in / w_out))`
+//!
+//! Fractional powers are evaluated in 18-decimal fixed point via `ln`/`exp`.
+//!
+//! ## Router Integration
+//! Every live pool is registered as a pair of the built-in `stellarlend`
+//! protocol, whose protocol address is this contract. Swaps submitted to
+//! `execute_swap` with `protocol = <amm contract>` are routed to the active
+//! LBP for the requested pair and go through the usual slippage, deadline and
+//! callback checks.
+//!
+//! ## Invariants
+//! - Weights stay within `[MIN_WEIGHT_BPS, MAX_WEIGHT_BPS]` and the two
+//!   weights always sum to 10_000.
+//! - At most one live pool, LBP or native, exists per token pair.
+//! - Swaps are only possible between `start_time` and pool exit.
+//! - Only the admin can exit a pool, and only after `end_time`.
+//! - Pool reserves are counted in `amm::get_hosted_reserve` until exit.
+
+#![allow(unused)]
+use soroban_sdk::{contractevent, contracttype, Address, Env};
+
+use crate::amm::{
+    record_hosted_reserve_change, register_hosted_pair, require_admin, unregister_hosted_pair,
+    AmmError, SwapParams,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Minimum weight of either token (1%)
+pub const MIN_WEIGHT_BPS: i128 = 100;
+
+/// Maximum weight of either token (99%)
+pub const MAX_WEIGHT_BPS: i128 = 9_900;
+
+/// Maximum LBP swap fee (10%)
+pub const MAX_LBP_SWAP_FEE_BPS: i128 = 1_000;
+
+/// 18-decimal fixed point scale used for weighted math
+const WAD: i128 = 1_000_000_000_000_000_000;
+
+/// ln(2) in WAD
+const LN2_WAD: i128 = 693_147_180_559_945_309;
+
+/// Storage keys for liquidity bootstrapping pools
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum LbpDataKey {
+    /// Number of pools created so far (next pool id)
+    PoolCount,
+    /// Pool data: LbpPool
+    Pool(u32),
+    /// Live pool for a token pair, keyed in creation order: u32
+    PairPool(Address, Address),
+}
+
+/// Linear weight schedule for token A (token B weight is the complement)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WeightSchedule {
+    /// Timestamp at which swaps open and weights start moving
+    pub start_time: u64,
+    /// Timestamp at which weights reach their final value
+    pub end_time: u64,
+    /// Token A weight at `start_time` (bps)
+    pub start_weight_a: i128,
+    /// Token A weight at `end_time` (bps)
+    pub end_weight_a: i128,
+}
+
+/// Liquidity bootstrapping pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LbpPool {
+    /// Pool id
+    pub pool_id: u32,
+    /// Token being launched
+    pub token_a: Address,
+    /// Reserve token used for price discovery
+    pub token_b: Address,
+    /// Token A reserve
+    pub reserve_a: i128,
+    /// Token B reserve
+    pub reserve_b: i128,
+    /// Weight schedule
+    pub schedule: WeightSchedule,
+    /// Swap fee (bps), retained in the pool
+    pub swap_fee_bps: i128,
+    /// Whether the pool has been exited by the admin
+    pub exited: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LbpPoolCreatedEvent {
+    pub pool_id: u32,
+    pub token_a: Address,
+    pub token_b: Address,
+    pub reserve_a: i128,
+    pub reserve_b: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LbpScheduleUpdatedEvent {
+    pub pool_id: u32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub start_weight_a: i128,
+    pub end_weight_a: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LbpSwapEvent {
+    pub pool_id: u32,
+    pub user: Address,
+    pub token_in: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub weight_a: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LbpPoolExitedEvent {
+    pub pool_id: u32,
+    pub recipient: Address,
+    pub amount_a: i128,
+    pub amount_b: i128,
+}
+
+// Fixed point math
+
+/// Natural logarithm of a WAD value
+fn ln_wad(x: i128) -> Result<i128, AmmError> {
+    if x <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+
+    // Normalize x = m * 2^k with m in [1, 2)
+    let mut k: i128 = 0;
+    let mut m = x;
+    while m >= 2 * WAD {
+        m /= 2;
+        k += 1;
+    }
+    while m < WAD {
+        m *= 2;
+        k -= 1;
+    }
+
+    // ln(m) = 2 * atanh(z), z = (m - 1) / (m + 1) <= 1/3
+    let z = (m - WAD) * WAD / (m + WAD);
+    let z2 = z * z / WAD;
+    let mut term = z;
+    let mut sum = 0i128;
+    let mut n = 1i128;
+    while term != 0 {
+        sum += term / n;
+        term = term * z2 / WAD;
+        n += 2;
+    }
+
+    Ok(k * LN2_WAD + 2 * sum)
+}
+
+/// Exponential of a WAD value
+fn exp_wad(y: i128) -> Result<i128, AmmError> {
+    // e^-42 is below 1e-18 and rounds to zero
+    if y < -42 * WAD {
+        return Ok(0);
+    }
+    // e^46 * 1e18 is close to i128::MAX
+    if y > 46 * WAD {
+        return Err(AmmError::Overflow);
+    }
+
+    // y = k * ln(2) + r, |r| < ln(2)
+    let k = y / LN2_WAD;
+    let r = y - k * LN2_WAD;
+
+    let mut term = WAD;
+    let mut sum = WAD;
+    let mut i = 1i128;
+    while term != 0 {
+        term = term * r / WAD / i;
+        sum += term;
+        i += 1;
+    }
+
+    if k >= 0 {
+        sum.checked_mul(1i128 << k).ok_or(AmmError::Overflow)
+    } else {
+        Ok(sum >> (-k))
+    }
+}
+
+/// `base ^ exponent` for WAD values
+fn pow_wad(base: i128, exponent: i128) -> Result<i128, AmmError> {
+    if base == WAD || exponent == 0 {
+        return Ok(WAD);
+    }
+    let ln = ln_wad(base)?;
+    let y = ln.checked_mul(exponent).ok_or(AmmError::Overflow)? / WAD;
+    exp_wad(y)
+}
+
+// Pool helpers
+
+fn validate_schedule(env: &Env, schedule: &WeightSchedule) -> Result<(), AmmError> {
+    if schedule.start_time >= schedule.end_time {
+        return Err(AmmError::InvalidWeightSchedule);
+    }
+    if schedule.end_time <= env.ledger().timestamp() {
+        return Err(AmmError::InvalidWeightSchedule);
+    }
+    for weight in [schedule.start_weight_a, schedule.end_weight_a] {
+        if !(MIN_WEIGHT_BPS..=MAX_WEIGHT_BPS).contains(&weight) {
+            return Err(AmmError::InvalidWeightSchedule);
+        }
+    }
+    Ok(())
+}
+
+/// Save a pool, recording any change of its reserves in the hosted reserve
+/// totals
+fn save_pool(env: &Env, pool: &LbpPool) {
+    let (previous_a, previous_b) = get_lbp_pool(env, pool.pool_id)
+        .map(|previous| (previous.reserve_a, previous.reserve_b))
+        .unwrap_or((0, 0));
+    record_hosted_reserve_change(env, &pool.token_a, pool.reserve_a - previous_a);
+    record_hosted_reserve_change(env, &pool.token_b, pool.reserve_b - previous_b);
+    env.storage()
+        .persistent()
+        .set(&LbpDataKey::Pool(pool.pool_id), pool);
+}
+
+/// Get a liquidity bootstrapping pool
+pub fn get_lbp_pool(env: &Env, pool_id: u32) -> Result<LbpPool, AmmError> {
+    env.storage()
+        .persistent()
+        .get::<LbpDataKey, LbpPool>(&LbpDataKey::Pool(pool_id))
+        .ok_or(AmmError::PoolNotFound)
+}
+
+/// Find the live pool for a token pair (in either order)
+fn find_pair_pool(env: &Env, token_x: &Address, token_y: &Address) -> Option<u32> {
+    let storage = env.storage().instance();
+    storage
+        .get::<LbpDataKey, u32>(&LbpDataKey::PairPool(token_x.clone(), token_y.clone()))
+        .or_else(|| {
+            storage.get::<LbpDataKey, u32>(&LbpDataKey::PairPool(token_y.clone(), token_x.clone()))
+        })
+}
+
+/// Whether a token pair has a live pool
+pub(crate) fn has_live_pool(env: &Env, token_x: &Address, token_y: &Address) -> bool {
+    find_pair_pool(env, token_x, token_y).is_some()
+}
+
+/// Current (weight_a, weight_b) of a pool, interpolated along its schedule
+pub fn current_weights(env: &Env, pool: &LbpPool) -> (i128, i128) {
+    let schedule = &pool.schedule;
+    let now = env.ledger().timestamp();
+
+    let weight_a = if now <= schedule.start_time {
+        schedule.start_weight_a
+    } else if now >= schedule.end_time {
+        schedule.end_weight_a
+    } else {
+        let elapsed = (now - schedule.start_time) as i128;
+        let duration = (schedule.end_time - schedule.start_time) as i128;
+        schedule.start_weight_a
+            + (schedule.end_weight_a - schedule.start_weight_a) * elapsed / duration
+    };
+
+    (weight_a, BASIS_POINTS_SCALE - weight_a)
+}
+
+/// Weighted-pool output amount for a given input
+fn calculate_amount_out(
+    reserve_in: i128,
+    reserve_out: i128,
+    weight_in: i128,
+    weight_out: i128,
+    amount_in: i128,
+    swap_fee_bps: i128,
+) -> Result<i128, AmmError> {
+    if reserve_in <= 0 || reserve_out <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+
+    let amount_in_after_fee = amount_in
+        .checked_mul(BASIS_POINTS_SCALE - swap_fee_bps)
+        .ok_or(AmmError::Overflow)?
+        / BASIS_POINTS_SCALE;
+    let new_reserve_in = reserve_in
+        .checked_add(amount_in_after_fee)
+        .ok_or(AmmError::Overflow)?;
+
+    let base = reserve_in.checked_mul(WAD).ok_or(AmmError::Overflow)? / new_reserve_in;
+    let exponent = weight_in * WAD / weight_out;
+    let power = pow_wad(base, exponent)?;
+    if power >= WAD {
+        return Ok(0);
+    }
+
+    let amount_out = reserve_out
+        .checked_mul(WAD - power)
+        .ok_or(AmmError::Overflow)?
+        / WAD;
+    if amount_out >= reserve_out {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+    Ok(amount_out)
+}
+
+/// Create a liquidity bootstrapping pool (admin only)
+///
+/// Seeds the pool with `amount_a` of the launched token and `amount_b` of the
+/// reserve token, both transferred from the admin, and registers the pair
+/// with the router.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `admin` - The admin address
+/// * `token_a` - Token being launched
+/// * `token_b` - Reserve token
+/// * `amount_a` - Initial token A liquidity
+/// * `amount_b` - Initial token B liquidity
+/// * `schedule` - Weight schedule for token A
+/// * `swap_fee_bps` - Swap fee in basis points
+///
+/// # Returns
+/// Returns the id of the new pool
+#[allow(clippy::too_many_arguments)]
+pub fn create_lbp_pool(
+    env: &Env,
+    admin: Address,
+    token_a: Address,
+    token_b: Address,
+    amount_a: i128,
+    amount_b: i128,
+    schedule: WeightSchedule,
+    swap_fee_bps: i128,
+) -> Result<u32, AmmError> {
+    admin.require_auth();
+    require_admin(env, &admin)?;
+
+    if token_a == token_b {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    if find_pair_pool(env, &token_a, &token_b).is_some()
+        || crate::pools::get_pool_id(env, &token_a, &token_b).is_some()
+    {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    if amount_a <= 0 || amount_b <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if !(0..=MAX_LBP_SWAP_FEE_BPS).contains(&swap_fee_bps) {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    validate_schedule(env, &schedule)?;
+
+    let contract = env.current_contract_address();
+    soroban_sdk::token::Client::new(env, &token_a).transfer(&admin, &contract, &amount_a);
+    soroban_sdk::token::Client::new(env, &token_b).transfer(&admin, &contract, &amount_b);
+
+    let pool_id = env
+        .storage()
+        .persistent()
+        .get::<LbpDataKey, u32>(&LbpDataKey::PoolCount)
+        .unwrap_or(0);
+
+    let pool = LbpPool {
+        pool_id,
+        token_a: token_a.clone(),
+        token_b: token_b.clone(),
+        reserve_a: amount_a,
+        reserve_b: amount_b,
+        schedule: schedule.clone(),
+        swap_fee_bps,
+        exited: false,
+    };
+
+    save_pool(env, &pool);
+    env.storage()
+        .persistent()
+        .set(&LbpDataKey::PoolCount, &(pool_id + 1));
+    env.storage().instance().set(
+        &LbpDataKey::PairPool(token_a.clone(), token_b.clone()),
+        &pool_id,
+    );
+    register_hosted_pair(env, &pool.token_a, &pool.token_b, pool.swap_fee_bps);
+
+    LbpPoolCreatedEvent {
+        pool_id,
+        token_a,
+        token_b,
+        reserve_a: amount_a,
+        reserve_b: amount_b,
+        start_time: schedule.start_time,
+        end_time: schedule.end_time,
+    }
+    .publish(env);
+
+    Ok(pool_id)
+}
+
+/// Replace the weight schedule of a live pool (admin only)
+pub fn set_lbp_weight_schedule(
+    env: &Env,
+    admin: Address,
+    pool_id: u32,
+    schedule: WeightSchedule,
+) -> Result<(), AmmError> {
+    admin.require_auth();
+    require_admin(env, &admin)?;
+
+    let mut pool = get_lbp_pool(env, pool_id)?;
+    if pool.exited {
+        return Err(AmmError::PoolNotActive);
+    }
+    validate_schedule(env, &schedule)?;
+
+    pool.schedule = schedule.clone();
+    save_pool(env, &pool);
+
+    LbpScheduleUpdatedEvent {
+        pool_id,
+        start_time: schedule.start_time,
+        end_time: schedule.end_time,
+        start_weight_a: schedule.start_weight_a,
+        end_weight_a: schedule.end_weight_a,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Quote a swap against a pool at the current weights
+pub fn quote_lbp_swap(
+    env: &Env,
+    pool_id: u32,
+    token_in: Address,
+    amount_in: i128,
+) -> Result<i128, AmmError> {
+    let pool = get_lbp_pool(env, pool_id)?;
+    quote_pool(env, &pool, &token_in, amount_in)
+}
+
+fn quote_pool(
+    env: &Env,
+    pool: &LbpPool,
+    token_in: &Address,
+    amount_in: i128,
+) -> Result<i128, AmmError> {
+    if amount_in <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    let (weight_a, weight_b) = current_weights(env, pool);
+    if *token_in == pool.token_a {
+        calculate_amount_out(
+            pool.reserve_a,
+            pool.reserve_b,
+            weight_a,
+            weight_b,
+            amount_in,
+            pool.swap_fee_bps,
+        )
+    } else if *token_in == pool.token_b {
+        calculate_amount_out(
+            pool.reserve_b,
+            pool.reserve_a,
+            weight_b,
+            weight_a,
+            amount_in,
+            pool.swap_fee_bps,
+        )
+    } else {
+        Err(AmmError::InvalidTokenPair)
+    }
+}
+
+/// Swap against a pool, moving tokens and updating reserves
+///
+/// With `escrowed` the input is already held by this contract and only the
+/// output is transferred to `user`.
+fn swap_in_pool(
+    env: &Env,
+    user: &Address,
+    pool: &mut LbpPool,
+    token_in: &Address,
+    amount_in: i128,
+    min_amount_out: i128,
+    escrowed: bool,
+) -> Result<i128, AmmError> {
+    if pool.exited || env.ledger().timestamp() < pool.schedule.start_time {
+        return Err(AmmError::PoolNotActive);
+    }
+
+    let amount_out = quote_pool(env, pool, token_in, amount_in)?;
+    if amount_out <= 0 || amount_out < min_amount_out {
+        return Err(AmmError::MinOutputNotMet);
+    }
+
+    let token_out = if *token_in == pool.token_a {
+        pool.reserve_a = pool
+            .reserve_a
+            .checked_add(amount_in)
+            .ok_or(AmmError::Overflow)?;
+        pool.reserve_b -= amount_out;
+        pool.token_b.clone()
+    } else {
+        pool.reserve_b = pool
+            .reserve_b
+            .checked_add(amount_in)
+            .ok_or(AmmError::Overflow)?;
+        pool.reserve_a -= amount_out;
+        pool.token_a.clone()
+    };
+
+    let contract = env.current_contract_address();
+    if !escrowed {
+        soroban_sdk::token::Client::new(env, token_in).transfer(user, &contract, &amount_in);
+    }
+    soroban_sdk::token::Client::new(env, &token_out).transfer(&contract, user, &amount_out);
+    save_pool(env, pool);
+
+    let (weight_a, _) = current_weights(env, pool);
+    LbpSwapEvent {
+        pool_id: pool.pool_id,
+        user: user.clone(),
+        token_in: token_in.clone(),
+        amount_in,
+        amount_out,
+        weight_a,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+
+    Ok(amount_out)
+}
+
+/// Swap directly against a liquidity bootstrapping pool
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The user performing the swap
+/// * `pool_id` - The pool to swap against
+/// * `token_in` - Input token (either pool token)
+/// * `amount_in` - Amount of `token_in` to sell
+/// * `min_amount_out` - Minimum amount of the other token to receive
+/// * `deadline` - Swap deadline timestamp
+///
+/// # Returns
+/// Returns the amount of the other token received
+pub fn lbp_swap(
+    env: &Env,
+    user: Address,
+    pool_id: u32,
+    token_in: Address,
+    amount_in: i128,
+    min_amount_out: i128,
+    deadline: u64,
+) -> Result<i128, AmmError> {
+    user.require_auth();
+    if env.ledger().timestamp() > deadline {
+        return Err(AmmError::SlippageExceeded);
+    }
+    let mut pool = get_lbp_pool(env, pool_id)?;
+    let amount_out = swap_in_pool(
+        env,
+        &user,
+        &mut pool,
+        &token_in,
+        amount_in,
+        min_amount_out,
+        false,
+    )?;
+
+    // Router swaps are recorded by the router
+    let token_out = if token_in == pool.token_a {
+        pool.token_b.clone()
+    } else {
+        pool.token_a.clone()
+    };
+    crate::twap::record_swap_price(
+        env,
+        &Some(token_in),
+        &Some(token_out),
+        amount_in,
+        amount_out,
+    )?;
+    Ok(amount_out)
+}
+
+/// Find the live pool for the pair of a router swap
+fn find_router_pool(env: &Env, params: &SwapParams) -> Result<(LbpPool, Address), AmmError> {
+    let (Some(token_in), Some(token_out)) = (params.token_in.clone(), params.token_out.clone())
+    else {
+        return Err(AmmError::InvalidTokenPair);
+    };
+    let pool_id = find_pair_pool(env, &token_in, &token_out).ok_or(AmmError::InvalidTokenPair)?;
+    Ok((get_lbp_pool(env, pool_id)?, token_in))
+}
+
+/// Quote a router swap against the live pool for the requested pair
+pub(crate) fn quote_through_router(env: &Env, params: &SwapParams) -> Result<i128, AmmError> {
+    let (pool, token_in) = find_router_pool(env, params)?;
+    quote_pool(env, &pool, &token_in, params.amount_in)
+}
+
+/// Reserve of the input token in the live pool for a router swap
+pub(crate) fn depth_through_router(env: &Env, params: &SwapParams) -> Result<i128, AmmError> {
+    let (pool, token_in) = find_router_pool(env, params)?;
+    if token_in == pool.token_a {
+        Ok(pool.reserve_a)
+    } else {
+        Ok(pool.reserve_b)
+    }
+}
+
+/// Execute a router swap against the live pool for the requested pair
+///
+/// Called by the router when `params.protocol` is this contract. With
+/// `escrowed` the input was deposited with the router beforehand and the
+/// user does not sign the swap itself.
+pub(crate) fn swap_through_router(
+    env: &Env,
+    user: &Address,
+    params: &SwapParams,
+    escrowed: bool,
+) -> Result<i128, AmmError> {
+    // Router swaps against an LBP move real balances, so the user must sign
+    if !escrowed {
+        user.require_auth();
+    }
+
+    let (mut pool, token_in) = find_router_pool(env, params)?;
+    swap_in_pool(
+        env,
+        user,
+        &mut pool,
+        &token_in,
+        params.amount_in,
+        params.min_amount_out,
+        escrowed,
+    )
+}
+
+/// Spot price of token A in token B (WAD), `(B_b / w_b) / (B_a / w_a)`
+pub fn get_lbp_spot_price(env: &Env, pool_id: u32) -> Result<i128, AmmError> {
+    let pool = get_lbp_pool(env, pool_id)?;
+    let token_a = pool.token_a.clone();
+    spot_price(env, &pool, &token_a)
+}
+
+/// Spot price of the live pool's input token for a router swap (WAD)
+pub(crate) fn spot_price_through_router(env: &Env, params: &SwapParams) -> Result<i128, AmmError> {
+    let (pool, token_in) = find_router_pool(env, params)?;
+    spot_price(env, &pool, &token_in)
+}
+
+/// Spot price of `token_in` in the other pool token (WAD), before fees
+fn spot_price(env: &Env, pool: &LbpPool, token_in: &Address) -> Result<i128, AmmError> {
+    let (weight_a, weight_b) = current_weights(env, pool);
+    let (reserve_in, weight_in, reserve_out, weight_out) = if *token_in == pool.token_a {
+        (pool.reserve_a, weight_a, pool.reserve_b, weight_b)
+    } else {
+        (pool.reserve_b, weight_b, pool.reserve_a, weight_a)
+    };
+    if reserve_in <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+    reserve_out
+        .checked_mul(weight_in)
+        .and_then(|v| v.checked_mul(WAD))
+        .ok_or(AmmError::Overflow)?
+        .checked_div(
+            reserve_in
+                .checked_mul(weight_out)
+                .ok_or(AmmError::Overflow)?,
+        )
+        .ok_or(AmmError::Overflow)
+}
+
+/// Get the current (weight_a, weight_b) of a pool
+pub fn get_lbp_weights(env: &Env, pool_id: u32) -> Result<(i128, i128), AmmError> {
+    let pool = get_lbp_pool(env, pool_id)?;
+    Ok(current_weights(env, &pool))
+}
+
+/// Exit a pool after its schedule has ended (admin only)
+///
+/// Withdraws all remaining reserves to `recipient`, closes the pool and
+/// removes its pair from the router.
+///
+/// # Returns
+/// Returns the (token_a, token_b) amounts withdrawn
+pub fn exit_lbp_pool(
+    env: &Env,
+    admin: Address,
+    pool_id: u32,
+    recipient: Address,
+) -> Result<(i128, i128), AmmError> {
+    admin.require_auth();
+    require_admin(env, &admin)?;
+
+    let mut pool = get_lbp_pool(env, pool_id)?;
+    if pool.exited || env.ledger().timestamp() < pool.schedule.end_time {
+        return Err(AmmError::PoolNotActive);
+    }
+
+    let amount_a = pool.reserve_a;
+    let amount_b = pool.reserve_b;
+    let contract = env.current_contract_address();
+    if amount_a > 0 {
+        soroban_sdk::token::Client::new(env, &pool.token_a)
+            .transfer(&contract, &recipient, &amount_a);
+    }
+    if amount_b > 0 {
+        soroban_sdk::token::Client::new(env, &pool.token_b)
+            .transfer(&contract, &recipient, &amount_b);
+    }
+
+    pool.reserve_a = 0;
+    pool.reserve_b = 0;
+    pool.exited = true;
+    save_pool(env, &pool);
+    env.storage().instance().remove(&LbpDataKey::PairPool(
+        pool.token_a.clone(),
+        pool.token_b.clone(),
+    ));
+    unregister_hosted_pair(env, &pool.token_a, &pool.token_b);
+
+    LbpPoolExitedEvent {
+        pool_id,
+        recipient,
+        amount_a,
+        amount_b,
+    }
+    .publish(env);
+
+    Ok((amount_a, amount_b))
+}
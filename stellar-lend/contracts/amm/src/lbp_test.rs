@@ -0,0 +1,367 @@
+use super::*;
+use crate::lbp::{LbpPool, WeightSchedule};
+use soroban_sdk::{
+    testutils::Address as _, testutils::Ledger, token::StellarAssetClient, Address, Env,
+};
+
+const UNIT: i128 = 10_000_000;
+
+struct LbpTest<'a> {
+    env: Env,
+    contract_id: Address,
+    client: AmmContractClient<'a>,
+    admin: Address,
+    token_a: Address,
+    token_b: Address,
+}
+
+fn setup<'a>() -> LbpTest<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(AmmContract {}, ());
+    let client = AmmContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize_amm_settings(&admin, &100, &1000, &10000);
+
+    let token_a = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_b = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &token_a).mint(&admin, &(1_000_000 * UNIT));
+    StellarAssetClient::new(&env, &token_b).mint(&admin, &(1_000_000 * UNIT));
+
+    LbpTest {
+        env,
+        contract_id,
+        client,
+        admin,
+        token_a,
+        token_b,
+    }
+}
+
+fn schedule(start_weight_a: i128, end_weight_a: i128) -> WeightSchedule {
+    WeightSchedule {
+        start_time: 1_000,
+        end_time: 2_000,
+        start_weight_a,
+        end_weight_a,
+    }
+}
+
+fn create_pool(t: &LbpTest, schedule: &WeightSchedule, fee_bps: i128) -> u32 {
+    t.client.create_lbp_pool(
+        &t.admin,
+        &t.token_a,
+        &t.token_b,
+        &(1_000 * UNIT),
+        &(1_000 * UNIT),
+        schedule,
+        &fee_bps,
+    )
+}
+
+fn funded_user(t: &LbpTest, token: &Address, amount: i128) -> Address {
+    let user = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, token).mint(&user, &amount);
+    user
+}
+
+#[test]
+fn test_create_lbp_pool_registers_route() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(9_000, 5_000), 0);
+    assert_eq!(pool_id, 0);
+
+    let pool: LbpPool = t.client.get_lbp_pool(&pool_id).unwrap();
+    assert_eq!(pool.reserve_a, 1_000 * UNIT);
+    assert_eq!(pool.reserve_b, 1_000 * UNIT);
+    assert!(!pool.exited);
+
+    let protocols = t.client.get_amm_protocols().unwrap();
+    let route = protocols.get(t.contract_id.clone()).unwrap();
+    assert_eq!(route.supported_pairs.len(), 1);
+    assert_eq!(
+        route.supported_pairs.get(0).unwrap().token_a,
+        Some(t.token_a.clone())
+    );
+}
+
+#[test]
+fn test_create_lbp_pool_duplicate_pair() {
+    let t = setup();
+    create_pool(&t, &schedule(9_000, 5_000), 0);
+    let result = t.client.try_create_lbp_pool(
+        &t.admin,
+        &t.token_b,
+        &t.token_a,
+        &UNIT,
+        &UNIT,
+        &schedule(9_000, 5_000),
+        &0,
+    );
+    assert_eq!(result, Err(Ok(AmmError::InvalidTokenPair)));
+}
+
+#[test]
+fn test_create_lbp_pool_invalid_schedule() {
+    let t = setup();
+    let result = t.client.try_create_lbp_pool(
+        &t.admin,
+        &t.token_a,
+        &t.token_b,
+        &UNIT,
+        &UNIT,
+        &schedule(9_950, 5_000),
+        &0,
+    );
+    assert_eq!(result, Err(Ok(AmmError::InvalidWeightSchedule)));
+
+    let mut reversed = schedule(9_000, 5_000);
+    reversed.end_time = reversed.start_time;
+    let result = t.client.try_create_lbp_pool(
+        &t.admin, &t.token_a, &t.token_b, &UNIT, &UNIT, &reversed, &0,
+    );
+    assert_eq!(result, Err(Ok(AmmError::InvalidWeightSchedule)));
+}
+
+#[test]
+fn test_create_lbp_pool_unauthorized() {
+    let t = setup();
+    let attacker = Address::generate(&t.env);
+    let result = t.client.try_create_lbp_pool(
+        &attacker,
+        &t.token_a,
+        &t.token_b,
+        &UNIT,
+        &UNIT,
+        &schedule(9_000, 5_000),
+        &0,
+    );
+    assert_eq!(result, Err(Ok(AmmError::Unauthorized)));
+}
+
+#[test]
+fn test_weights_shift_over_time() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(9_000, 5_000), 0);
+
+    assert_eq!(t.client.get_lbp_weights(&pool_id), (9_000, 1_000));
+
+    t.env.ledger().with_mut(|li| li.timestamp = 1_500);
+    assert_eq!(t.client.get_lbp_weights(&pool_id), (7_000, 3_000));
+
+    t.env.ledger().with_mut(|li| li.timestamp = 5_000);
+    assert_eq!(t.client.get_lbp_weights(&pool_id), (5_000, 5_000));
+}
+
+#[test]
+fn test_spot_price_decays_without_demand() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(9_000, 5_000), 0);
+
+    let start_price = t.client.get_lbp_spot_price(&pool_id);
+    t.env.ledger().with_mut(|li| li.timestamp = 2_000);
+    let end_price = t.client.get_lbp_spot_price(&pool_id);
+
+    // 90/10 weights price token A at 9x token B, 50/50 at parity
+    assert_eq!(start_price, 9_000_000_000_000_000_000);
+    assert_eq!(end_price, 1_000_000_000_000_000_000);
+}
+
+#[test]
+fn test_equal_weight_swap_matches_constant_product() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(5_000, 5_000), 0);
+    let user = funded_user(&t, &t.token_b, 100 * UNIT);
+
+    // x * y = k: 1000 * 1000 / 1100 => 90.9090909 out
+    let quote = t.client.quote_lbp_swap(&pool_id, &t.token_b, &(100 * UNIT));
+    assert!((quote - 909_090_909).abs() <= 1);
+
+    let amount_out = t
+        .client
+        .lbp_swap(&user, &pool_id, &t.token_b, &(100 * UNIT), &0, &2_000);
+    assert_eq!(amount_out, quote);
+
+    let token_a = soroban_sdk::token::Client::new(&t.env, &t.token_a);
+    assert_eq!(token_a.balance(&user), amount_out);
+
+    let pool = t.client.get_lbp_pool(&pool_id).unwrap();
+    assert_eq!(pool.reserve_b, 1_100 * UNIT);
+    assert_eq!(pool.reserve_a, 1_000 * UNIT - amount_out);
+}
+
+#[test]
+fn test_swap_fee_reduces_output() {
+    let t = setup();
+    let no_fee = create_pool(&t, &schedule(5_000, 5_000), 0);
+    let quote_no_fee = t.client.quote_lbp_swap(&no_fee, &t.token_b, &(100 * UNIT));
+
+    let t2 = setup();
+    let with_fee = create_pool(&t2, &schedule(5_000, 5_000), 100);
+    let quote_with_fee = t2
+        .client
+        .quote_lbp_swap(&with_fee, &t2.token_b, &(100 * UNIT));
+
+    assert!(quote_with_fee < quote_no_fee);
+}
+
+#[test]
+fn test_swap_respects_min_output_and_start_time() {
+    let t = setup();
+    let mut sched = schedule(5_000, 5_000);
+    sched.start_time = 1_500;
+    let pool_id = create_pool(&t, &sched, 0);
+    let user = funded_user(&t, &t.token_b, 100 * UNIT);
+
+    let result = t
+        .client
+        .try_lbp_swap(&user, &pool_id, &t.token_b, &(10 * UNIT), &0, &2_000);
+    assert_eq!(result, Err(Ok(AmmError::PoolNotActive)));
+
+    t.env.ledger().with_mut(|li| li.timestamp = 1_500);
+    let result = t.client.try_lbp_swap(
+        &user,
+        &pool_id,
+        &t.token_b,
+        &(10 * UNIT),
+        &(10 * UNIT),
+        &2_000,
+    );
+    assert_eq!(result, Err(Ok(AmmError::MinOutputNotMet)));
+}
+
+#[test]
+fn test_router_swap_uses_lbp() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(5_000, 5_000), 0);
+    let user = funded_user(&t, &t.token_b, 100 * UNIT);
+    let quote = t.client.quote_lbp_swap(&pool_id, &t.token_b, &(100 * UNIT));
+
+    let params = SwapParams {
+        protocol: t.contract_id.clone(),
+        token_in: Some(t.token_b.clone()),
+        token_out: Some(t.token_a.clone()),
+        amount_in: 100 * UNIT,
+        min_amount_out: 90 * UNIT,
+        slippage_tolerance: 100,
+        deadline: 2_000,
+        use_oracle_floor: false,
+    };
+    let amount_out = t.client.execute_swap(&user, &params);
+    assert_eq!(amount_out, quote);
+
+    let history = t.client.get_swap_history(&Some(user.clone()), &10).unwrap();
+    assert_eq!(history.len(), 1);
+}
+
+#[test]
+fn test_quote_swap_reports_price_impact() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(5_000, 5_000), 0);
+    let params = |amount_in: i128| SwapParams {
+        protocol: t.contract_id.clone(),
+        token_in: Some(t.token_b.clone()),
+        token_out: Some(t.token_a.clone()),
+        amount_in,
+        min_amount_out: 1,
+        slippage_tolerance: 100,
+        deadline: 2_000,
+        use_oracle_floor: false,
+    };
+
+    let small = t.client.quote_swap(&params(UNIT));
+    let large = t.client.quote_swap(&params(100 * UNIT));
+    assert_eq!(
+        large.amount_out,
+        t.client.quote_lbp_swap(&pool_id, &t.token_b, &(100 * UNIT))
+    );
+    assert_eq!(large.spot_price, 1_000_000_000_000_000_000);
+    assert!(small.price_impact_bps < large.price_impact_bps);
+    // 100 into a 1_000/1_000 pool moves the price by about 100/1_100
+    assert!((900..=910).contains(&large.price_impact_bps));
+
+    let pool = t.client.get_lbp_pool(&pool_id).unwrap();
+    assert_eq!(pool.reserve_b, 1_000 * UNIT);
+}
+
+#[test]
+fn test_update_weight_schedule() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(9_000, 5_000), 0);
+
+    t.client
+        .set_lbp_weight_schedule(&t.admin, &pool_id, &schedule(8_000, 2_000));
+    assert_eq!(t.client.get_lbp_weights(&pool_id), (8_000, 2_000));
+
+    let result = t
+        .client
+        .try_set_lbp_weight_schedule(&t.admin, &pool_id, &schedule(8_000, 50));
+    assert_eq!(result, Err(Ok(AmmError::InvalidWeightSchedule)));
+}
+
+#[test]
+fn test_exit_pool_after_end() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(9_000, 5_000), 0);
+    let recipient = Address::generate(&t.env);
+
+    let result = t.client.try_exit_lbp_pool(&t.admin, &pool_id, &recipient);
+    assert_eq!(result, Err(Ok(AmmError::PoolNotActive)));
+
+    t.env.ledger().with_mut(|li| li.timestamp = 2_000);
+    let (amount_a, amount_b) = t.client.exit_lbp_pool(&t.admin, &pool_id, &recipient);
+    assert_eq!(amount_a, 1_000 * UNIT);
+    assert_eq!(amount_b, 1_000 * UNIT);
+
+    let token_a = soroban_sdk::token::Client::new(&t.env, &t.token_a);
+    assert_eq!(token_a.balance(&recipient), 1_000 * UNIT);
+
+    let pool = t.client.get_lbp_pool(&pool_id).unwrap();
+    assert!(pool.exited);
+
+    let route = t
+        .client
+        .get_amm_protocols()
+        .unwrap()
+        .get(t.contract_id.clone())
+        .unwrap();
+    assert_eq!(route.supported_pairs.len(), 0);
+
+    // Pair can be relaunched once the previous pool is closed
+    let mut relaunch = schedule(9_000, 5_000);
+    relaunch.start_time = 2_000;
+    relaunch.end_time = 3_000;
+    create_pool(&t, &relaunch, 0);
+}
+
+#[test]
+fn test_hosted_reserves_released_on_exit() {
+    let t = setup();
+    let pool_id = create_pool(&t, &schedule(9_000, 5_000), 0);
+    let hosted = |token: &Address| {
+        t.env.as_contract(&t.contract_id, || {
+            crate::amm::get_hosted_reserve(&t.env, token)
+        })
+    };
+    assert_eq!(hosted(&t.token_a), 1_000 * UNIT);
+    assert_eq!(hosted(&t.token_b), 1_000 * UNIT);
+
+    let user = funded_user(&t, &t.token_b, 10 * UNIT);
+    let amount_out = t
+        .client
+        .lbp_swap(&user, &pool_id, &t.token_b, &(10 * UNIT), &0, &2_000);
+    assert_eq!(hosted(&t.token_a), 1_000 * UNIT - amount_out);
+    assert_eq!(hosted(&t.token_b), 1_010 * UNIT);
+
+    t.env.ledger().with_mut(|li| li.timestamp = 2_000);
+    t.client
+        .exit_lbp_pool(&t.admin, &pool_id, &Address::generate(&t.env));
+    assert_eq!(hosted(&t.token_a), 0);
+    assert_eq!(hosted(&t.token_b), 0);
+}
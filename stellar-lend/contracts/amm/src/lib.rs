@@ -5,23 +5,48 @@
 //!
 //! ## Features
 //! - Multi-protocol AMM support with pluggable protocol configs
-//! - Slippage protection with configurable tolerances
+//! - Slippage protection with configurable tolerances and per-pair caps
+//! - Oracle-derived minimum outputs for swaps that opt in
 //! - Auto-swap for collateral optimization during lending operations
 //! - Callback validation with nonce-based replay protection
 //! - Swap and liquidity operation history for analytics
+//! - Cumulative swap volume per pair, protocol and user
+//! - Liquidity bootstrapping pools (LBPs) with time-shifting weights
+//! - Time-weighted average prices (TWAP) of traded pairs
+//! - Resting limit orders filled by keepers
+//! - Multi-hop route discovery across registered protocols
+//! - Best-execution splitting of large swaps across protocols
+//! - Read-only swap quotes with price impact and fees
+//! - Native constant-product pools with transferable LP shares
 
 #![no_std]
 #![allow(clippy::too_many_arguments)]
-use soroban_sdk::{contract, contractimpl, Address, Env, Map};
+use soroban_sdk::{contract, contractimpl, Address, Env, Map, Vec};
 
 pub mod amm;
 pub use crate::amm::{
     add_amm_protocol, add_liquidity, auto_swap_for_collateral, execute_swap,
-    initialize_amm_settings, remove_liquidity, update_amm_settings, validate_amm_callback,
-    AmmCallbackData, AmmError, AmmProtocolConfig, AmmSettings, LiquidityParams, SwapParams,
-    TokenPair,
+    initialize_amm_settings, oracle_floor, quote_swap, remove_liquidity, update_amm_settings,
+    validate_amm_callback, AmmCallbackData, AmmError, AmmProtocolConfig, AmmSettings,
+    LiquidityParams, SwapHistoryFilter, SwapHistoryPage, SwapParams, SwapQuote, SwapStats,
+    SwapStatsScope, TokenPair,
 };
 
+pub mod lbp;
+pub use crate::lbp::{LbpPool, WeightSchedule};
+
+pub mod twap;
+pub use crate::twap::{get_twap, PriceObservation};
+
+pub mod limit_orders;
+pub use crate::limit_orders::LimitOrder;
+
+pub mod routing;
+pub use crate::routing::{execute_split_swap, quote_route, RouteHop, SwapRoute};
+
+pub mod pools;
+pub use crate::pools::ConstantProductPool;
+
 #[contract]
 pub struct AmmContract;
 
@@ -91,10 +116,46 @@ impl AmmContract {
         update_amm_settings(&env, admin, settings)
     }
 
+    /// Override the slippage cap of a token pair (admin only)
+    ///
+    /// Tightens the global `max_slippage` for swaps between the two tokens,
+    /// in either direction.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `token_a` - One token of the pair (None for native XLM)
+    /// * `token_b` - The other token of the pair (None for native XLM)
+    /// * `max_slippage` - Slippage cap in basis points, or None to remove the override
+    pub fn set_pair_max_slippage(
+        env: Env,
+        admin: Address,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+        max_slippage: Option<i128>,
+    ) -> Result<(), AmmError> {
+        amm::set_pair_max_slippage(&env, admin, token_a, token_b, max_slippage)
+    }
+
+    /// Get the largest slippage tolerance accepted on a token pair
+    ///
+    /// # Returns
+    /// The pair's cap in basis points, or the global `max_slippage` if the
+    /// pair has no tighter override
+    pub fn get_pair_max_slippage(
+        env: Env,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+    ) -> Result<i128, AmmError> {
+        amm::get_pair_max_slippage(&env, &token_a, &token_b)
+    }
+
     /// Execute swap through AMM
     ///
     /// Performs token swaps using configured AMM protocols with slippage protection.
     /// Can be used within lending operations for collateral optimization.
+    /// With `params.use_oracle_floor` the minimum output is raised to the
+    /// pair's TWAP less `params.slippage_tolerance`. A `params.deadline` of 0
+    /// stands for `DEFAULT_DEADLINE_WINDOW` seconds from now.
     ///
     /// # Arguments
     /// * `user` - The user performing the swap
@@ -111,6 +172,21 @@ impl AmmContract {
         execute_swap(&env, user, params)
     }
 
+    /// Quote a swap without executing it
+    ///
+    /// Applies the same checks as `execute_swap` and returns the expected
+    /// output, the price impact against the current spot price and the fee
+    /// breakdown. Nothing is written and no signature is required.
+    ///
+    /// # Arguments
+    /// * `params` - Swap parameters as they would be passed to `execute_swap`
+    ///
+    /// # Returns
+    /// Returns the swap quote
+    pub fn quote_swap(env: Env, params: SwapParams) -> Result<SwapQuote, AmmError> {
+        quote_swap(&env, params)
+    }
+
     /// Add liquidity to AMM pool
     ///
     /// Adds liquidity to AMM pools for earning fees and supporting protocol operations.
@@ -184,6 +260,8 @@ impl AmmContract {
     ///
     /// Validates callbacks from AMM protocols to ensure they are legitimate
     /// and prevent replay attacks. This is called by AMM protocols during operations.
+    /// Any recently issued nonce of the (caller, user) sequence is accepted
+    /// once, so callbacks may arrive out of order.
     ///
     /// # Arguments
     /// * `caller` - The AMM protocol making the callback
@@ -202,10 +280,23 @@ impl AmmContract {
         validate_amm_callback(&env, caller, callback_data)
     }
 
+    /// Get the next callback nonce of a (protocol, user) sequence
+    ///
+    /// # Arguments
+    /// * `protocol` - The AMM protocol operations are routed to
+    /// * `user` - The user performing the operations
+    ///
+    /// # Returns
+    /// Returns the nonce the next operation will be issued
+    pub fn get_next_nonce(env: Env, protocol: Address, user: Address) -> u64 {
+        amm::get_next_nonce(&env, &protocol, &user)
+    }
+
     /// Auto-swap for collateral optimization
     ///
     /// Automatically swaps assets to optimize collateral ratios during lending operations.
     /// This is typically called internally during borrow/liquidation operations.
+    /// Pairs without a direct pool are routed through up to three hops.
     ///
     /// # Arguments
     /// * `user` - The user whose collateral to optimize
@@ -264,6 +355,38 @@ impl AmmContract {
         amm::get_swap_history(&env, user, limit).ok()
     }
 
+    /// Get a page of swap history
+    ///
+    /// Pages from the newest record back; pass the returned `next_cursor`
+    /// to get the following page.
+    ///
+    /// # Arguments
+    /// * `filter` - User, protocol and time range the records must match
+    /// * `cursor` - Cursor of the page, or None for the newest records
+    /// * `limit` - Maximum number of records to return
+    ///
+    /// # Returns
+    /// Returns the matching records, newest first, and the next page's cursor
+    pub fn get_swap_history_page(
+        env: Env,
+        filter: SwapHistoryFilter,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> SwapHistoryPage {
+        amm::get_swap_history_page(&env, &filter, cursor, limit)
+    }
+
+    /// Get cumulative swap statistics
+    ///
+    /// # Arguments
+    /// * `scope` - Global, or the pair, protocol or user to report on
+    ///
+    /// # Returns
+    /// Returns the swap count, per-token volumes and fees of the scope
+    pub fn get_swap_stats(env: Env, scope: SwapStatsScope) -> SwapStats {
+        amm::get_swap_stats(&env, scope)
+    }
+
     /// Get liquidity history
     ///
     /// Returns recent liquidity operations for analytics.
@@ -281,11 +404,340 @@ impl AmmContract {
     ) -> Option<soroban_sdk::Vec<amm::LiquidityRecord>> {
         amm::get_liquidity_history(&env, user, limit).ok()
     }
+
+    /// Create a liquidity bootstrapping pool (admin only)
+    ///
+    /// Seeds a weighted pool whose token A weight moves linearly along
+    /// `schedule`, and registers the pair with the router.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `token_a` - Token being launched
+    /// * `token_b` - Reserve token used for price discovery
+    /// * `amount_a` - Initial token A liquidity (transferred from admin)
+    /// * `amount_b` - Initial token B liquidity (transferred from admin)
+    /// * `schedule` - Weight schedule for token A
+    /// * `swap_fee_bps` - Swap fee in basis points
+    ///
+    /// # Returns
+    /// Returns the id of the new pool
+    ///
+    /// # Events
+    /// Emits `lbp_pool_created`
+    pub fn create_lbp_pool(
+        env: Env,
+        admin: Address,
+        token_a: Address,
+        token_b: Address,
+        amount_a: i128,
+        amount_b: i128,
+        schedule: WeightSchedule,
+        swap_fee_bps: i128,
+    ) -> Result<u32, AmmError> {
+        lbp::create_lbp_pool(
+            &env,
+            admin,
+            token_a,
+            token_b,
+            amount_a,
+            amount_b,
+            schedule,
+            swap_fee_bps,
+        )
+    }
+
+    /// Replace the weight schedule of a live LBP (admin only)
+    ///
+    /// # Events
+    /// Emits `lbp_schedule_updated_event`
+    pub fn set_lbp_weight_schedule(
+        env: Env,
+        admin: Address,
+        pool_id: u32,
+        schedule: WeightSchedule,
+    ) -> Result<(), AmmError> {
+        lbp::set_lbp_weight_schedule(&env, admin, pool_id, schedule)
+    }
+
+    /// Swap directly against a liquidity bootstrapping pool
+    ///
+    /// # Arguments
+    /// * `user` - The user performing the swap
+    /// * `pool_id` - The pool to swap against
+    /// * `token_in` - Input token (either pool token)
+    /// * `amount_in` - Amount to sell
+    /// * `min_amount_out` - Minimum amount of the other token to receive
+    /// * `deadline` - Swap deadline timestamp
+    ///
+    /// # Returns
+    /// Returns the amount received
+    ///
+    /// # Events
+    /// Emits `lbp_swap`
+    pub fn lbp_swap(
+        env: Env,
+        user: Address,
+        pool_id: u32,
+        token_in: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        deadline: u64,
+    ) -> Result<i128, AmmError> {
+        lbp::lbp_swap(
+            &env,
+            user,
+            pool_id,
+            token_in,
+            amount_in,
+            min_amount_out,
+            deadline,
+        )
+    }
+
+    /// Quote a swap against an LBP at its current weights
+    pub fn quote_lbp_swap(
+        env: Env,
+        pool_id: u32,
+        token_in: Address,
+        amount_in: i128,
+    ) -> Result<i128, AmmError> {
+        lbp::quote_lbp_swap(&env, pool_id, token_in, amount_in)
+    }
+
+    /// Get a liquidity bootstrapping pool
+    pub fn get_lbp_pool(env: Env, pool_id: u32) -> Option<LbpPool> {
+        lbp::get_lbp_pool(&env, pool_id).ok()
+    }
+
+    /// Get the current (weight_a, weight_b) of an LBP in basis points
+    pub fn get_lbp_weights(env: Env, pool_id: u32) -> Result<(i128, i128), AmmError> {
+        lbp::get_lbp_weights(&env, pool_id)
+    }
+
+    /// Get the spot price of token A in token B (scaled by 10^18)
+    pub fn get_lbp_spot_price(env: Env, pool_id: u32) -> Result<i128, AmmError> {
+        lbp::get_lbp_spot_price(&env, pool_id)
+    }
+
+    /// Exit an LBP after its schedule ended (admin only)
+    ///
+    /// Withdraws the remaining reserves to `recipient` and removes the pair
+    /// from the router.
+    ///
+    /// # Returns
+    /// Returns the (token_a, token_b) amounts withdrawn
+    pub fn exit_lbp_pool(
+        env: Env,
+        admin: Address,
+        pool_id: u32,
+        recipient: Address,
+    ) -> Result<(i128, i128), AmmError> {
+        lbp::exit_lbp_pool(&env, admin, pool_id, recipient)
+    }
+
+    /// Get the time-weighted average price of `token_a` in `token_b`
+    ///
+    /// Averages the effective prices of swaps between the two tokens over at
+    /// least the last `window` seconds.
+    ///
+    /// # Returns
+    /// Units of `token_b` per unit of `token_a` (scaled by 10^18)
+    pub fn get_twap(
+        env: Env,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+        window: u64,
+    ) -> Result<i128, AmmError> {
+        twap::get_twap(&env, &token_a, &token_b, window)
+    }
+
+    /// Get the stored price observations of a pair, oldest first
+    pub fn get_price_observations(
+        env: Env,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+    ) -> Vec<PriceObservation> {
+        twap::get_price_observations(&env, &token_a, &token_b)
+    }
+
+    /// Place a resting limit order
+    ///
+    /// The order sells `params.amount_in` for at least `params.min_amount_out`
+    /// once the routed price allows it, until `params.deadline`. Input routed
+    /// to pools hosted by this contract is escrowed until the order fills or
+    /// is cancelled.
+    ///
+    /// # Returns
+    /// Returns the id of the new order
+    ///
+    /// # Events
+    /// Emits `limit_order_placed_event`
+    pub fn place_limit_order(
+        env: Env,
+        owner: Address,
+        params: SwapParams,
+    ) -> Result<u64, AmmError> {
+        limit_orders::place_limit_order(&env, owner, params)
+    }
+
+    /// Cancel an open limit order and refund its escrow (owner only)
+    ///
+    /// # Events
+    /// Emits `limit_order_cancelled_event`
+    pub fn cancel_order(env: Env, owner: Address, order_id: u64) -> Result<(), AmmError> {
+        limit_orders::cancel_order(&env, owner, order_id)
+    }
+
+    /// Fill a limit order whose limit price is reached (any keeper)
+    ///
+    /// Executes the order's swap for its owner through the router.
+    ///
+    /// # Returns
+    /// Returns the amount received by the owner
+    ///
+    /// # Events
+    /// Emits `limit_order_filled_event` along with the swap events
+    pub fn fill_order(env: Env, keeper: Address, order_id: u64) -> Result<i128, AmmError> {
+        limit_orders::fill_order(&env, keeper, order_id)
+    }
+
+    /// Get an open limit order
+    pub fn get_limit_order(env: Env, order_id: u64) -> Option<LimitOrder> {
+        limit_orders::get_limit_order(&env, order_id).ok()
+    }
+
+    /// Quote the best route from `token_in` to `token_out`
+    ///
+    /// Chains up to three registered token pairs, across one or more enabled
+    /// protocols, and picks the path with the highest expected output.
+    ///
+    /// # Returns
+    /// Returns the route's hops and expected output
+    pub fn quote_route(
+        env: Env,
+        token_in: Option<Address>,
+        token_out: Option<Address>,
+        amount: i128,
+    ) -> Result<SwapRoute, AmmError> {
+        routing::quote_route(&env, token_in, token_out, amount)
+    }
+
+    /// Execute a swap split across every enabled protocol supporting the pair
+    ///
+    /// Each protocol receives a share of `amount_in` proportional to the
+    /// depth it quotes for `token_in`, which keeps the price impact of a
+    /// large swap low. The shares are recorded in the swap history entry.
+    ///
+    /// # Returns
+    /// Returns the total amount received
+    ///
+    /// # Events
+    /// Emits `swap_executed` and `amm_operation` events for the whole swap
+    pub fn execute_split_swap(
+        env: Env,
+        user: Address,
+        token_in: Option<Address>,
+        token_out: Option<Address>,
+        amount_in: i128,
+        min_amount_out: i128,
+        deadline: u64,
+    ) -> Result<i128, AmmError> {
+        routing::execute_split_swap(
+            &env,
+            user,
+            token_in,
+            token_out,
+            amount_in,
+            min_amount_out,
+            deadline,
+        )
+    }
+
+    /// Create a native constant-product pool (admin only)
+    ///
+    /// Seeds the pool from the admin, who receives the initial LP shares,
+    /// and registers the pair with the router. Swaps and liquidity
+    /// operations then reach the pool through `execute_swap`,
+    /// `add_liquidity` and `remove_liquidity` with this contract as the
+    /// protocol.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `token_a` - First pool token
+    /// * `token_b` - Second pool token
+    /// * `amount_a` - Initial token A liquidity (transferred from admin)
+    /// * `amount_b` - Initial token B liquidity (transferred from admin)
+    /// * `swap_fee_bps` - Swap fee in basis points, retained by the pool
+    ///
+    /// # Returns
+    /// Returns the id of the new pool
+    ///
+    /// # Events
+    /// Emits `pool_created_event`
+    pub fn create_pool(
+        env: Env,
+        admin: Address,
+        token_a: Address,
+        token_b: Address,
+        amount_a: i128,
+        amount_b: i128,
+        swap_fee_bps: i128,
+    ) -> Result<u32, AmmError> {
+        pools::create_pool(
+            &env,
+            admin,
+            token_a,
+            token_b,
+            amount_a,
+            amount_b,
+            swap_fee_bps,
+        )
+    }
+
+    /// Get a native pool
+    pub fn get_pool(env: Env, pool_id: u32) -> Option<ConstantProductPool> {
+        pools::get_pool(&env, pool_id).ok()
+    }
+
+    /// Get the id of the native pool for a token pair (in either order)
+    pub fn get_pool_id(env: Env, token_a: Address, token_b: Address) -> Option<u32> {
+        pools::get_pool_id(&env, &token_a, &token_b)
+    }
+
+    /// Get the LP shares `account` holds in a native pool
+    pub fn get_lp_shares(env: Env, pool_id: u32, account: Address) -> i128 {
+        pools::get_lp_shares(&env, pool_id, &account)
+    }
+
+    /// Transfer LP shares of a native pool to another account
+    ///
+    /// # Events
+    /// Emits `lp_shares_transferred_event`
+    pub fn transfer_lp_shares(
+        env: Env,
+        from: Address,
+        to: Address,
+        pool_id: u32,
+        amount: i128,
+    ) -> Result<(), AmmError> {
+        pools::transfer_lp_shares(&env, from, to, pool_id, amount)
+    }
 }
 
+#[cfg(test)]
+mod lbp_test;
+#[cfg(test)]
+mod limit_order_test;
+
 // Liquidation integration tests require lending crate; enable with feature "liquidate_integration"
 // when lending is available as a dependency.
 #[cfg(all(test, feature = "liquidate_integration"))]
 mod liquidate_test;
 #[cfg(test)]
+mod pool_test;
+#[cfg(test)]
+mod routing_test;
+#[cfg(test)]
 mod test;
+#[cfg(test)]
+mod twap_test;
@@ -0,0 +1,152 @@
+use super::*;
+use crate::lbp::WeightSchedule;
+use soroban_sdk::{
+    testutils::Address as _,
+    testutils::Ledger,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const UNIT: i128 = 10_000_000;
+
+struct OrderTest<'a> {
+    env: Env,
+    contract_id: Address,
+    client: AmmContractClient<'a>,
+    token_a: Address,
+    token_b: Address,
+}
+
+/// An LBP whose token A price in token B decays from 9 to 1 over [1_000, 2_000]
+fn setup<'a>() -> OrderTest<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(AmmContract {}, ());
+    let client = AmmContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize_amm_settings(&admin, &100, &1000, &10000);
+
+    let token_a = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_b = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &token_a).mint(&admin, &(1_000 * UNIT));
+    StellarAssetClient::new(&env, &token_b).mint(&admin, &(1_000 * UNIT));
+    client.create_lbp_pool(
+        &admin,
+        &token_a,
+        &token_b,
+        &(1_000 * UNIT),
+        &(1_000 * UNIT),
+        &WeightSchedule {
+            start_time: 1_000,
+            end_time: 2_000,
+            start_weight_a: 9_000,
+            end_weight_a: 5_000,
+        },
+        &0,
+    );
+
+    OrderTest {
+        env,
+        contract_id,
+        client,
+        token_a,
+        token_b,
+    }
+}
+
+/// Place an order buying at least 50 token A for 100 token B
+fn place_buy_order(t: &OrderTest, deadline: u64) -> (Address, u64) {
+    let owner = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.token_b).mint(&owner, &(100 * UNIT));
+    let params = SwapParams {
+        protocol: t.contract_id.clone(),
+        token_in: Some(t.token_b.clone()),
+        token_out: Some(t.token_a.clone()),
+        amount_in: 100 * UNIT,
+        min_amount_out: 50 * UNIT,
+        slippage_tolerance: 100,
+        deadline,
+        use_oracle_floor: false,
+    };
+    let order_id = t.client.place_limit_order(&owner, &params);
+    (owner, order_id)
+}
+
+#[test]
+fn test_limit_order_fills_when_price_reached() {
+    let t = setup();
+    let token_a = TokenClient::new(&t.env, &t.token_a);
+    let token_b = TokenClient::new(&t.env, &t.token_b);
+    let (owner, order_id) = place_buy_order(&t, 3_000);
+    assert_eq!(token_b.balance(&owner), 0);
+    assert!(t.client.get_limit_order(&order_id).unwrap().escrowed);
+
+    let keeper = Address::generate(&t.env);
+    assert_eq!(
+        t.client.try_fill_order(&keeper, &order_id),
+        Err(Ok(AmmError::LimitPriceNotReached))
+    );
+
+    t.env.ledger().with_mut(|li| li.timestamp = 2_000);
+    let quote = t.client.quote_lbp_swap(&0, &t.token_b, &(100 * UNIT));
+    let amount_out = t.client.fill_order(&keeper, &order_id);
+    assert_eq!(amount_out, quote);
+    assert!(amount_out >= 50 * UNIT);
+    assert_eq!(token_a.balance(&owner), amount_out);
+    assert_eq!(token_a.balance(&keeper), 0);
+    assert!(t.client.get_limit_order(&order_id).is_none());
+
+    let history = t.client.get_swap_history(&Some(owner), &10).unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(
+        t.client.try_fill_order(&keeper, &order_id),
+        Err(Ok(AmmError::OrderNotFound))
+    );
+}
+
+#[test]
+fn test_cancel_order_refunds_escrow() {
+    let t = setup();
+    let token_b = TokenClient::new(&t.env, &t.token_b);
+    let (owner, order_id) = place_buy_order(&t, 3_000);
+
+    let stranger = Address::generate(&t.env);
+    assert_eq!(
+        t.client.try_cancel_order(&stranger, &order_id),
+        Err(Ok(AmmError::Unauthorized))
+    );
+
+    t.client.cancel_order(&owner, &order_id);
+    assert_eq!(token_b.balance(&owner), 100 * UNIT);
+    assert!(t.client.get_limit_order(&order_id).is_none());
+    assert_eq!(
+        t.client.try_fill_order(&stranger, &order_id),
+        Err(Ok(AmmError::OrderNotFound))
+    );
+}
+
+#[test]
+fn test_limit_order_expires() {
+    let t = setup();
+    let (owner, order_id) = place_buy_order(&t, 1_500);
+
+    t.env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(
+        t.client
+            .try_fill_order(&Address::generate(&t.env), &order_id),
+        Err(Ok(AmmError::OrderExpired))
+    );
+
+    // Expired orders can still be cancelled for a refund
+    t.client.cancel_order(&owner, &order_id);
+    assert_eq!(
+        TokenClient::new(&t.env, &t.token_b).balance(&owner),
+        100 * UNIT
+    );
+}
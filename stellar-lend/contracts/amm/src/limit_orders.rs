@@ -0,0 +1,237 @@
+//! # Limit Orders
+//!
+//! Resting swap orders that execute once the routed price reaches the
+//! owner's limit. An order is an ordinary `SwapParams`: `amount_in` is sold
+//! for at least `min_amount_out`, so the limit price is
+//! `min_amount_out / amount_in`, and `deadline` is the order's expiry.
+//!
+//! ## Filling
+//! Any keeper can call `fill_order`. The order is quoted against its protocol
+//! at the current price; once the quote reaches `min_amount_out` the swap is
+//! executed for the owner through the router, with the same validation,
+//! callback nonce, history and TWAP recording as `execute_swap`.
+//!
+//! ## Escrow
+//! Orders routed to the pools hosted by this contract move real balances,
+//! so their input is escrowed with the router when the order is placed and
+//! refunded when it is cancelled. Orders on external protocols follow the
+//! same flow as direct swaps through those protocols.
+//!
+//! ## Invariants
+//! - Orders are validated like swaps when placed and again when filled.
+//! - An order fills at most once, in full, and only before its deadline.
+//! - Only the owner can cancel an order; expired orders stay cancellable.
+
+#![allow(unused)]
+use soroban_sdk::{contractevent, contracttype, Address, Env};
+
+use crate::amm::{
+    check_swap_enabled, execute_swap_internal, get_amm_protocol_config, quote_amm_swap,
+    validate_swap_params, validate_token_pair, AmmError, SwapParams,
+};
+
+/// Storage keys for limit orders
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum LimitOrderDataKey {
+    /// Next order id: u64
+    NextOrderId,
+    /// Open order: LimitOrder
+    Order(u64),
+}
+
+/// A resting limit order
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LimitOrder {
+    /// Order id
+    pub order_id: u64,
+    /// Account the order trades for
+    pub owner: Address,
+    /// Swap executed when the order fills
+    pub params: SwapParams,
+    /// Whether the input is held in escrow by the router
+    pub escrowed: bool,
+    /// Timestamp the order was placed
+    pub created_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LimitOrderPlacedEvent {
+    pub order_id: u64,
+    pub owner: Address,
+    pub token_in: Option<Address>,
+    pub token_out: Option<Address>,
+    pub amount_in: i128,
+    pub min_amount_out: i128,
+    pub deadline: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LimitOrderCancelledEvent {
+    pub order_id: u64,
+    pub owner: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LimitOrderFilledEvent {
+    pub order_id: u64,
+    pub owner: Address,
+    pub keeper: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub timestamp: u64,
+}
+
+/// Get an open limit order
+pub fn get_limit_order(env: &Env, order_id: u64) -> Result<LimitOrder, AmmError> {
+    env.storage()
+        .persistent()
+        .get::<LimitOrderDataKey, LimitOrder>(&LimitOrderDataKey::Order(order_id))
+        .ok_or(AmmError::OrderNotFound)
+}
+
+/// Place a resting limit order
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `owner` - The account the order trades for
+/// * `params` - Swap to execute; `min_amount_out` sets the limit price and
+///   `deadline` the expiry
+///
+/// # Returns
+/// Returns the id of the new order
+pub fn place_limit_order(env: &Env, owner: Address, params: SwapParams) -> Result<u64, AmmError> {
+    owner.require_auth();
+
+    validate_swap_params(env, &params)?;
+    check_swap_enabled(env)?;
+    if env.ledger().timestamp() >= params.deadline {
+        return Err(AmmError::OrderExpired);
+    }
+
+    let protocol_config = get_amm_protocol_config(env, &params.protocol)?;
+    if params.amount_in < protocol_config.min_swap_amount {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if params.amount_in > protocol_config.max_swap_amount {
+        return Err(AmmError::MaxInputExceeded);
+    }
+    validate_token_pair(env, &protocol_config, &params.token_in, &params.token_out)?;
+
+    let escrowed = params.protocol == env.current_contract_address();
+    if escrowed {
+        let token_in = params.token_in.clone().ok_or(AmmError::InvalidTokenPair)?;
+        let contract = env.current_contract_address();
+        soroban_sdk::token::Client::new(env, &token_in).transfer(
+            &owner,
+            &contract,
+            &params.amount_in,
+        );
+    }
+
+    let order_id = env
+        .storage()
+        .persistent()
+        .get::<LimitOrderDataKey, u64>(&LimitOrderDataKey::NextOrderId)
+        .unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&LimitOrderDataKey::NextOrderId, &(order_id + 1));
+
+    let order = LimitOrder {
+        order_id,
+        owner: owner.clone(),
+        params: params.clone(),
+        escrowed,
+        created_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&LimitOrderDataKey::Order(order_id), &order);
+
+    LimitOrderPlacedEvent {
+        order_id,
+        owner,
+        token_in: params.token_in,
+        token_out: params.token_out,
+        amount_in: params.amount_in,
+        min_amount_out: params.min_amount_out,
+        deadline: params.deadline,
+    }
+    .publish(env);
+
+    Ok(order_id)
+}
+
+/// Cancel an open limit order and refund its escrow (owner only)
+pub fn cancel_order(env: &Env, owner: Address, order_id: u64) -> Result<(), AmmError> {
+    owner.require_auth();
+
+    let order = get_limit_order(env, order_id)?;
+    if order.owner != owner {
+        return Err(AmmError::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&LimitOrderDataKey::Order(order_id));
+    if order.escrowed {
+        let token_in = order.params.token_in.ok_or(AmmError::InvalidTokenPair)?;
+        soroban_sdk::token::Client::new(env, &token_in).transfer(
+            &env.current_contract_address(),
+            &owner,
+            &order.params.amount_in,
+        );
+    }
+
+    LimitOrderCancelledEvent { order_id, owner }.publish(env);
+    Ok(())
+}
+
+/// Fill a limit order whose limit price is reached (any keeper)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `keeper` - The executing account
+/// * `order_id` - The order to fill
+///
+/// # Returns
+/// Returns the amount received by the owner
+pub fn fill_order(env: &Env, keeper: Address, order_id: u64) -> Result<i128, AmmError> {
+    keeper.require_auth();
+
+    let order = get_limit_order(env, order_id)?;
+    if env.ledger().timestamp() > order.params.deadline {
+        return Err(AmmError::OrderExpired);
+    }
+    if quote_amm_swap(env, &order.params)? < order.params.min_amount_out {
+        return Err(AmmError::LimitPriceNotReached);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&LimitOrderDataKey::Order(order_id));
+    let amount_out = execute_swap_internal(
+        env,
+        order.owner.clone(),
+        order.params.clone(),
+        order.escrowed,
+    )?;
+
+    LimitOrderFilledEvent {
+        order_id,
+        owner: order.owner,
+        keeper,
+        amount_in: order.params.amount_in,
+        amount_out,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+
+    Ok(amount_out)
+}
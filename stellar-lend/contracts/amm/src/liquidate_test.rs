@@ -60,6 +60,7 @@ fn test_liquidation_swap_path_success() {
         min_amount_out: 19_000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3_600,
+        use_oracle_floor: false,
     };
 
     let out = contract.execute_swap(&user, &params);
@@ -85,6 +86,7 @@ fn test_liquidation_swap_path_rejects_expired_deadline() {
         min_amount_out: 1,
         slippage_tolerance: 100,
         deadline: 999,
+        use_oracle_floor: false,
     };
 
     assert!(contract.try_execute_swap(&user, &params).is_err());
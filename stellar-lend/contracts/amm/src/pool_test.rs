@@ -0,0 +1,275 @@
+use super::*;
+use crate::lbp::WeightSchedule;
+use crate::pools::MINIMUM_LIQUIDITY;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const UNIT: i128 = 10_000_000;
+
+struct PoolTest<'a> {
+    env: Env,
+    contract_id: Address,
+    client: AmmContractClient<'a>,
+    admin: Address,
+    token_a: Address,
+    token_b: Address,
+    pool_id: u32,
+}
+
+/// A native pool holding 1_000 of each token with a 0.3% swap fee
+fn setup<'a>() -> PoolTest<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AmmContract {}, ());
+    let client = AmmContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize_amm_settings(&admin, &100, &1000, &10000);
+
+    let token_a = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_b = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &token_a).mint(&admin, &(1_000 * UNIT));
+    StellarAssetClient::new(&env, &token_b).mint(&admin, &(1_000 * UNIT));
+    let pool_id = client.create_pool(
+        &admin,
+        &token_a,
+        &token_b,
+        &(1_000 * UNIT),
+        &(1_000 * UNIT),
+        &30,
+    );
+
+    PoolTest {
+        env,
+        contract_id,
+        client,
+        admin,
+        token_a,
+        token_b,
+        pool_id,
+    }
+}
+
+fn liquidity_params(t: &PoolTest, amount_a: i128, amount_b: i128) -> LiquidityParams {
+    LiquidityParams {
+        protocol: t.contract_id.clone(),
+        token_a: Some(t.token_a.clone()),
+        token_b: Some(t.token_b.clone()),
+        amount_a,
+        amount_b,
+        min_amount_a: 0,
+        min_amount_b: 0,
+        deadline: t.env.ledger().timestamp() + 300,
+    }
+}
+
+#[test]
+fn test_create_pool_locks_minimum_liquidity() {
+    let t = setup();
+    let pool = t.client.get_pool(&t.pool_id).unwrap();
+    assert_eq!(pool.total_shares, 1_000 * UNIT);
+    assert_eq!(
+        t.client.get_lp_shares(&t.pool_id, &t.admin),
+        1_000 * UNIT - MINIMUM_LIQUIDITY
+    );
+    assert_eq!(
+        t.client.get_pool_id(&t.token_b, &t.token_a),
+        Some(t.pool_id)
+    );
+
+    // One hosted pool per pair, whichever kind it is
+    assert_eq!(
+        t.client
+            .try_create_pool(&t.admin, &t.token_b, &t.token_a, &UNIT, &UNIT, &30),
+        Err(Ok(AmmError::InvalidTokenPair))
+    );
+    assert_eq!(
+        t.client.try_create_lbp_pool(
+            &t.admin,
+            &t.token_a,
+            &t.token_b,
+            &UNIT,
+            &UNIT,
+            &WeightSchedule {
+                start_time: 0,
+                end_time: 1_000,
+                start_weight_a: 9_000,
+                end_weight_a: 5_000,
+            },
+            &0,
+        ),
+        Err(Ok(AmmError::InvalidTokenPair))
+    );
+}
+
+#[test]
+fn test_pool_swap_through_router() {
+    let t = setup();
+    let user = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.token_a).mint(&user, &(10 * UNIT));
+    let params = SwapParams {
+        protocol: t.contract_id.clone(),
+        token_in: Some(t.token_a.clone()),
+        token_out: Some(t.token_b.clone()),
+        amount_in: 10 * UNIT,
+        min_amount_out: 9 * UNIT,
+        slippage_tolerance: 100,
+        deadline: t.env.ledger().timestamp() + 300,
+        use_oracle_floor: false,
+    };
+
+    let quote = t.client.quote_swap(&params);
+    assert_eq!(quote.spot_price, 1_000_000_000_000_000_000);
+    let amount_out = t.client.execute_swap(&user, &params);
+    assert_eq!(amount_out, 98_715_803);
+    assert_eq!(amount_out, quote.amount_out);
+    assert_eq!(TokenClient::new(&t.env, &t.token_a).balance(&user), 0);
+    assert_eq!(
+        TokenClient::new(&t.env, &t.token_b).balance(&user),
+        amount_out
+    );
+
+    // The fee stays in the pool, so the invariant grows
+    let pool = t.client.get_pool(&t.pool_id).unwrap();
+    assert_eq!(pool.reserve_a, 1_010 * UNIT);
+    assert_eq!(pool.reserve_b, 1_000 * UNIT - amount_out);
+    assert!(pool.reserve_a * pool.reserve_b > 1_000 * UNIT * 1_000 * UNIT);
+    assert_eq!(
+        t.client.get_swap_history(&Some(user), &10).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_add_and_remove_liquidity() {
+    let t = setup();
+    let provider = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.token_a).mint(&provider, &(100 * UNIT));
+    StellarAssetClient::new(&t.env, &t.token_b).mint(&provider, &(200 * UNIT));
+
+    // Only the amount matching the pool ratio is deposited
+    let shares = t
+        .client
+        .add_liquidity(&provider, &liquidity_params(&t, 100 * UNIT, 200 * UNIT));
+    assert_eq!(shares, 100 * UNIT);
+    assert_eq!(t.client.get_lp_shares(&t.pool_id, &provider), shares);
+    assert_eq!(
+        TokenClient::new(&t.env, &t.token_b).balance(&provider),
+        100 * UNIT
+    );
+    let record = t
+        .client
+        .get_liquidity_history(&Some(provider.clone()), &10)
+        .unwrap()
+        .get(0)
+        .unwrap();
+    assert_eq!(record.amount_b, 100 * UNIT);
+
+    let mut params = liquidity_params(&t, 100 * UNIT, 200 * UNIT);
+    params.min_amount_b = 150 * UNIT;
+    assert_eq!(
+        t.client.try_add_liquidity(&provider, &params),
+        Err(Ok(AmmError::MinOutputNotMet))
+    );
+
+    // Shares are transferable and redeem pro rata, in the caller's token order
+    let holder = Address::generate(&t.env);
+    t.client
+        .transfer_lp_shares(&provider, &holder, &t.pool_id, &(40 * UNIT));
+    let deadline = t.env.ledger().timestamp() + 300;
+    assert_eq!(
+        t.client.try_remove_liquidity(
+            &provider,
+            &t.contract_id,
+            &Some(t.token_a.clone()),
+            &Some(t.token_b.clone()),
+            &(60 * UNIT + 1),
+            &0,
+            &0,
+            &deadline,
+        ),
+        Err(Ok(AmmError::InsufficientLpShares))
+    );
+    let (amount_b, amount_a) = t.client.remove_liquidity(
+        &holder,
+        &t.contract_id,
+        &Some(t.token_b.clone()),
+        &Some(t.token_a.clone()),
+        &(40 * UNIT),
+        &0,
+        &0,
+        &deadline,
+    );
+    assert_eq!((amount_a, amount_b), (40 * UNIT, 40 * UNIT));
+    assert_eq!(
+        TokenClient::new(&t.env, &t.token_a).balance(&holder),
+        40 * UNIT
+    );
+    assert_eq!(t.client.get_lp_shares(&t.pool_id, &holder), 0);
+
+    let pool = t.client.get_pool(&t.pool_id).unwrap();
+    assert_eq!(pool.total_shares, 1_060 * UNIT);
+    assert_eq!(pool.reserve_a, 1_060 * UNIT);
+}
+
+#[test]
+fn test_hosted_reserves_follow_pool_reserves() {
+    let t = setup();
+    let hosted = |token: &Address| {
+        t.env.as_contract(&t.contract_id, || {
+            crate::amm::get_hosted_reserve(&t.env, token)
+        })
+    };
+    assert_eq!(hosted(&t.token_a), 1_000 * UNIT);
+    assert_eq!(hosted(&t.token_b), 1_000 * UNIT);
+
+    let user = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.token_a).mint(&user, &(11 * UNIT));
+    StellarAssetClient::new(&t.env, &t.token_b).mint(&user, &(10 * UNIT));
+    t.client.execute_swap(
+        &user,
+        &SwapParams {
+            protocol: t.contract_id.clone(),
+            token_in: Some(t.token_a.clone()),
+            token_out: Some(t.token_b.clone()),
+            amount_in: 10 * UNIT,
+            min_amount_out: 9 * UNIT,
+            slippage_tolerance: 100,
+            deadline: t.env.ledger().timestamp() + 300,
+            use_oracle_floor: false,
+        },
+    );
+    let shares = t
+        .client
+        .add_liquidity(&user, &liquidity_params(&t, UNIT, 10 * UNIT));
+    t.client.remove_liquidity(
+        &user,
+        &t.contract_id,
+        &Some(t.token_a.clone()),
+        &Some(t.token_b.clone()),
+        &(shares / 2),
+        &0,
+        &0,
+        &(t.env.ledger().timestamp() + 300),
+    );
+
+    // Every token the contract holds belongs to the pool
+    let pool = t.client.get_pool(&t.pool_id).unwrap();
+    assert_eq!(hosted(&t.token_a), pool.reserve_a);
+    assert_eq!(hosted(&t.token_b), pool.reserve_b);
+    assert_eq!(
+        TokenClient::new(&t.env, &t.token_a).balance(&t.contract_id),
+        pool.reserve_a
+    );
+    assert_eq!(
+        TokenClient::new(&t.env, &t.token_b).balance(&t.contract_id),
+        pool.reserve_b
+    );
+}
@@ -0,0 +1,664 @@
+//! # Native Pools
+//!
+//! First-party constant-product pools hosted by this contract, so the
+//! protocol always has a venue to exit liquidated collateral into, even on
+//! pairs that external AMMs serve thinly.
+//!
+//! ## Pricing
+//! Swaps follow the constant-product invariant `B_a * B_b = k`, so for an
+//! input `a` (after fees):
+//!
+//! `amount_out = B_out * a / (B_in + a)`
+//!
+//! The swap fee stays in the pool and accrues to liquidity providers.
+//!
+//! ## LP Shares
+//! Liquidity providers receive pool shares tracked by this contract. Shares
+//! are minted in proportion to the reserves deposited, redeemed for the same
+//! proportion of both reserves, and can be transferred between accounts.
+//! `MINIMUM_LIQUIDITY` shares of the first deposit are locked forever so the
+//! share price cannot be inflated by donating to an empty pool.
+//!
+//! ## Router Integration
+//! Every pool is registered as a pair of the built-in `stellarlend`
+//! protocol, whose protocol address is this contract. `execute_swap`,
+//! `add_liquidity` and `remove_liquidity` called with
+//! `protocol = <amm contract>` are routed to the pool for the requested pair
+//! and go through the usual slippage, deadline and callback checks.
+//!
+//! ## Invariants
+//! - At most one live pool, LBP or native, exists per token pair.
+//! - `B_a * B_b` never decreases through a swap.
+//! - `total_shares` equals the sum of all LP balances plus
+//!   `MINIMUM_LIQUIDITY`.
+//! - Reserves only leave the pool through swaps and share redemptions.
+//! - Pool reserves are counted in `amm::get_hosted_reserve`, so a lending
+//!   contract hosting the pools can keep them out of its liquidity.
+
+#![allow(unused)]
+use soroban_sdk::{contractevent, contracttype, Address, Env};
+
+use crate::amm::{
+    record_hosted_reserve_change, register_hosted_pair, require_admin, AmmError, LiquidityParams,
+    SwapParams,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Maximum pool swap fee (10%)
+pub const MAX_POOL_SWAP_FEE_BPS: i128 = 1_000;
+
+/// Shares of the first deposit that are locked in the pool forever
+pub const MINIMUM_LIQUIDITY: i128 = 1_000;
+
+/// 18-decimal fixed point scale used for prices
+const WAD: i128 = 1_000_000_000_000_000_000;
+
+/// Storage keys for native pools
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum PoolDataKey {
+    /// Number of pools created so far (next pool id)
+    NativePoolCount,
+    /// Pool data: ConstantProductPool
+    NativePool(u32),
+    /// Pool of a token pair, keyed in sorted token order: u32
+    NativePairPool(Address, Address),
+    /// LP shares of an account in a pool: i128
+    LpShares(u32, Address),
+}
+
+/// Constant-product pool hosted by this contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConstantProductPool {
+    /// Pool id
+    pub pool_id: u32,
+    /// First pool token
+    pub token_a: Address,
+    /// Second pool token
+    pub token_b: Address,
+    /// Token A reserve
+    pub reserve_a: i128,
+    /// Token B reserve
+    pub reserve_b: i128,
+    /// Outstanding LP shares, locked minimum included
+    pub total_shares: i128,
+    /// Swap fee (bps), retained in the pool
+    pub swap_fee_bps: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PoolCreatedEvent {
+    pub pool_id: u32,
+    pub token_a: Address,
+    pub token_b: Address,
+    pub reserve_a: i128,
+    pub reserve_b: i128,
+    pub swap_fee_bps: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PoolSwapEvent {
+    pub pool_id: u32,
+    pub user: Address,
+    pub token_in: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PoolLiquidityEvent {
+    pub pool_id: u32,
+    pub user: Address,
+    pub amount_a: i128,
+    pub amount_b: i128,
+    /// Shares minted (positive) or burned (negative)
+    pub shares: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LpSharesTransferredEvent {
+    pub pool_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+// Pool helpers
+
+/// Save a pool, recording any change of its reserves in the hosted reserve
+/// totals
+fn save_pool(env: &Env, pool: &ConstantProductPool) {
+    let (previous_a, previous_b) = get_pool(env, pool.pool_id)
+        .map(|previous| (previous.reserve_a, previous.reserve_b))
+        .unwrap_or((0, 0));
+    record_hosted_reserve_change(env, &pool.token_a, pool.reserve_a - previous_a);
+    record_hosted_reserve_change(env, &pool.token_b, pool.reserve_b - previous_b);
+    env.storage()
+        .persistent()
+        .set(&PoolDataKey::NativePool(pool.pool_id), pool);
+}
+
+/// Get a native pool
+pub fn get_pool(env: &Env, pool_id: u32) -> Result<ConstantProductPool, AmmError> {
+    env.storage()
+        .persistent()
+        .get::<PoolDataKey, ConstantProductPool>(&PoolDataKey::NativePool(pool_id))
+        .ok_or(AmmError::PoolNotFound)
+}
+
+/// Index key of a token pair, with the tokens in sorted order
+fn pair_key(token_x: &Address, token_y: &Address) -> PoolDataKey {
+    if token_x < token_y {
+        PoolDataKey::NativePairPool(token_x.clone(), token_y.clone())
+    } else {
+        PoolDataKey::NativePairPool(token_y.clone(), token_x.clone())
+    }
+}
+
+/// Get the id of the pool for a token pair (in either order)
+pub fn get_pool_id(env: &Env, token_x: &Address, token_y: &Address) -> Option<u32> {
+    env.storage()
+        .instance()
+        .get::<PoolDataKey, u32>(&pair_key(token_x, token_y))
+}
+
+/// Get the LP shares `account` holds in a pool
+pub fn get_lp_shares(env: &Env, pool_id: u32, account: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<PoolDataKey, i128>(&PoolDataKey::LpShares(pool_id, account.clone()))
+        .unwrap_or(0)
+}
+
+fn set_lp_shares(env: &Env, pool_id: u32, account: &Address, shares: i128) {
+    let key = PoolDataKey::LpShares(pool_id, account.clone());
+    if shares == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &shares);
+    }
+}
+
+/// Integer square root (floor)
+fn sqrt(value: i128) -> i128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Constant-product output amount for a given input
+fn calculate_amount_out(
+    reserve_in: i128,
+    reserve_out: i128,
+    amount_in: i128,
+    swap_fee_bps: i128,
+) -> Result<i128, AmmError> {
+    if reserve_in <= 0 || reserve_out <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+
+    let amount_in_after_fee = amount_in
+        .checked_mul(BASIS_POINTS_SCALE - swap_fee_bps)
+        .ok_or(AmmError::Overflow)?
+        / BASIS_POINTS_SCALE;
+    let new_reserve_in = reserve_in
+        .checked_add(amount_in_after_fee)
+        .ok_or(AmmError::Overflow)?;
+
+    Ok(reserve_out
+        .checked_mul(amount_in_after_fee)
+        .ok_or(AmmError::Overflow)?
+        / new_reserve_in)
+}
+
+/// Reserves of a pool ordered as (`token`, other token)
+fn reserves_from(pool: &ConstantProductPool, token: &Address) -> Result<(i128, i128), AmmError> {
+    if *token == pool.token_a {
+        Ok((pool.reserve_a, pool.reserve_b))
+    } else if *token == pool.token_b {
+        Ok((pool.reserve_b, pool.reserve_a))
+    } else {
+        Err(AmmError::InvalidTokenPair)
+    }
+}
+
+/// Create a native constant-product pool (admin only)
+///
+/// Seeds the pool with `amount_a` and `amount_b`, both transferred from the
+/// admin, who receives the initial LP shares, and registers the pair with
+/// the router.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `admin` - The admin address
+/// * `token_a` - First pool token
+/// * `token_b` - Second pool token
+/// * `amount_a` - Initial token A liquidity
+/// * `amount_b` - Initial token B liquidity
+/// * `swap_fee_bps` - Swap fee in basis points
+///
+/// # Returns
+/// Returns the id of the new pool
+pub fn create_pool(
+    env: &Env,
+    admin: Address,
+    token_a: Address,
+    token_b: Address,
+    amount_a: i128,
+    amount_b: i128,
+    swap_fee_bps: i128,
+) -> Result<u32, AmmError> {
+    admin.require_auth();
+    require_admin(env, &admin)?;
+
+    if token_a == token_b {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    if get_pool_id(env, &token_a, &token_b).is_some()
+        || crate::lbp::has_live_pool(env, &token_a, &token_b)
+    {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    if amount_a <= 0 || amount_b <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if !(0..=MAX_POOL_SWAP_FEE_BPS).contains(&swap_fee_bps) {
+        return Err(AmmError::InvalidSwapParams);
+    }
+
+    let total_shares = sqrt(amount_a.checked_mul(amount_b).ok_or(AmmError::Overflow)?);
+    if total_shares <= MINIMUM_LIQUIDITY {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+
+    let contract = env.current_contract_address();
+    soroban_sdk::token::Client::new(env, &token_a).transfer(&admin, &contract, &amount_a);
+    soroban_sdk::token::Client::new(env, &token_b).transfer(&admin, &contract, &amount_b);
+
+    let pool_id = env
+        .storage()
+        .persistent()
+        .get::<PoolDataKey, u32>(&PoolDataKey::NativePoolCount)
+        .unwrap_or(0);
+
+    let pool = ConstantProductPool {
+        pool_id,
+        token_a: token_a.clone(),
+        token_b: token_b.clone(),
+        reserve_a: amount_a,
+        reserve_b: amount_b,
+        total_shares,
+        swap_fee_bps,
+    };
+
+    save_pool(env, &pool);
+    set_lp_shares(env, pool_id, &admin, total_shares - MINIMUM_LIQUIDITY);
+    env.storage()
+        .persistent()
+        .set(&PoolDataKey::NativePoolCount, &(pool_id + 1));
+    env.storage()
+        .instance()
+        .set(&pair_key(&token_a, &token_b), &pool_id);
+    register_hosted_pair(env, &token_a, &token_b, swap_fee_bps);
+
+    PoolCreatedEvent {
+        pool_id,
+        token_a,
+        token_b,
+        reserve_a: amount_a,
+        reserve_b: amount_b,
+        swap_fee_bps,
+    }
+    .publish(env);
+
+    Ok(pool_id)
+}
+
+/// Transfer LP shares of a pool to another account
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `from` - The account sending the shares
+/// * `to` - The receiving account
+/// * `pool_id` - The pool the shares belong to
+/// * `amount` - Number of shares to transfer
+pub fn transfer_lp_shares(
+    env: &Env,
+    from: Address,
+    to: Address,
+    pool_id: u32,
+    amount: i128,
+) -> Result<(), AmmError> {
+    from.require_auth();
+    move_lp_shares(env, from, to, pool_id, amount)
+}
+
+/// Move LP shares between accounts without checking authorization
+///
+/// For contracts embedding the library that hold shares on behalf of their
+/// users, such as LP collateral; they must authorize `from` themselves and
+/// never expose this as an entrypoint.
+pub fn move_lp_shares(
+    env: &Env,
+    from: Address,
+    to: Address,
+    pool_id: u32,
+    amount: i128,
+) -> Result<(), AmmError> {
+    get_pool(env, pool_id)?;
+    if amount <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+
+    let from_shares = get_lp_shares(env, pool_id, &from);
+    if from_shares < amount {
+        return Err(AmmError::InsufficientLpShares);
+    }
+    set_lp_shares(env, pool_id, &from, from_shares - amount);
+    let to_shares = get_lp_shares(env, pool_id, &to)
+        .checked_add(amount)
+        .ok_or(AmmError::Overflow)?;
+    set_lp_shares(env, pool_id, &to, to_shares);
+
+    LpSharesTransferredEvent {
+        pool_id,
+        from,
+        to,
+        amount,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Underlying (token_a, token_b) amounts redeemable for `shares` of a pool
+pub fn shares_value(env: &Env, pool_id: u32, shares: i128) -> Result<(i128, i128), AmmError> {
+    let pool = get_pool(env, pool_id)?;
+    if shares < 0 || shares > pool.total_shares {
+        return Err(AmmError::InsufficientLpShares);
+    }
+    Ok((
+        pool.reserve_a
+            .checked_mul(shares)
+            .ok_or(AmmError::Overflow)?
+            / pool.total_shares,
+        pool.reserve_b
+            .checked_mul(shares)
+            .ok_or(AmmError::Overflow)?
+            / pool.total_shares,
+    ))
+}
+
+/// Find the pool for a pair of router tokens
+fn find_pool(
+    env: &Env,
+    token_x: &Option<Address>,
+    token_y: &Option<Address>,
+) -> Result<ConstantProductPool, AmmError> {
+    let (Some(token_x), Some(token_y)) = (token_x, token_y) else {
+        return Err(AmmError::InvalidTokenPair);
+    };
+    let pool_id = get_pool_id(env, token_x, token_y).ok_or(AmmError::InvalidTokenPair)?;
+    get_pool(env, pool_id)
+}
+
+/// Whether a router swap's pair is served by a native pool
+pub(crate) fn serves_pair(env: &Env, token_x: &Option<Address>, token_y: &Option<Address>) -> bool {
+    find_pool(env, token_x, token_y).is_ok()
+}
+
+/// Quote a router swap against the pool for the requested pair
+pub(crate) fn quote_through_router(env: &Env, params: &SwapParams) -> Result<i128, AmmError> {
+    if params.amount_in <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    let pool = find_pool(env, &params.token_in, &params.token_out)?;
+    let token_in = params.token_in.clone().ok_or(AmmError::InvalidTokenPair)?;
+    let (reserve_in, reserve_out) = reserves_from(&pool, &token_in)?;
+    calculate_amount_out(reserve_in, reserve_out, params.amount_in, pool.swap_fee_bps)
+}
+
+/// Reserve of the input token in the pool for a router swap
+pub(crate) fn depth_through_router(env: &Env, params: &SwapParams) -> Result<i128, AmmError> {
+    let pool = find_pool(env, &params.token_in, &params.token_out)?;
+    let token_in = params.token_in.clone().ok_or(AmmError::InvalidTokenPair)?;
+    Ok(reserves_from(&pool, &token_in)?.0)
+}
+
+/// Spot price of the input token for a router swap (WAD), before fees
+pub(crate) fn spot_price_through_router(env: &Env, params: &SwapParams) -> Result<i128, AmmError> {
+    let pool = find_pool(env, &params.token_in, &params.token_out)?;
+    let token_in = params.token_in.clone().ok_or(AmmError::InvalidTokenPair)?;
+    let (reserve_in, reserve_out) = reserves_from(&pool, &token_in)?;
+    if reserve_in <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+    reserve_out
+        .checked_mul(WAD)
+        .ok_or(AmmError::Overflow)?
+        .checked_div(reserve_in)
+        .ok_or(AmmError::Overflow)
+}
+
+/// Execute a router swap against the pool for the requested pair
+///
+/// Called by the router when `params.protocol` is this contract. With
+/// `escrowed` the input was deposited with the router beforehand and the
+/// user does not sign the swap itself.
+pub(crate) fn swap_through_router(
+    env: &Env,
+    user: &Address,
+    params: &SwapParams,
+    escrowed: bool,
+) -> Result<i128, AmmError> {
+    // Router swaps against a native pool move real balances, so the user must sign
+    if !escrowed {
+        user.require_auth();
+    }
+
+    let mut pool = find_pool(env, &params.token_in, &params.token_out)?;
+    let token_in = params.token_in.clone().ok_or(AmmError::InvalidTokenPair)?;
+    let amount_out = quote_through_router(env, params)?;
+    if amount_out <= 0 || amount_out < params.min_amount_out {
+        return Err(AmmError::MinOutputNotMet);
+    }
+
+    let token_out = if token_in == pool.token_a {
+        pool.reserve_a = pool
+            .reserve_a
+            .checked_add(params.amount_in)
+            .ok_or(AmmError::Overflow)?;
+        pool.reserve_b -= amount_out;
+        pool.token_b.clone()
+    } else {
+        pool.reserve_b = pool
+            .reserve_b
+            .checked_add(params.amount_in)
+            .ok_or(AmmError::Overflow)?;
+        pool.reserve_a -= amount_out;
+        pool.token_a.clone()
+    };
+
+    let contract = env.current_contract_address();
+    if !escrowed {
+        soroban_sdk::token::Client::new(env, &token_in).transfer(
+            user,
+            &contract,
+            &params.amount_in,
+        );
+    }
+    soroban_sdk::token::Client::new(env, &token_out).transfer(&contract, user, &amount_out);
+    save_pool(env, &pool);
+
+    PoolSwapEvent {
+        pool_id: pool.pool_id,
+        user: user.clone(),
+        token_in,
+        amount_in: params.amount_in,
+        amount_out,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+
+    Ok(amount_out)
+}
+
+/// Deposit liquidity into the pool for a router liquidity operation
+///
+/// Deposits at the pool's current ratio: the side that would exceed the
+/// ratio is reduced, and each deposited amount must still reach its minimum.
+///
+/// # Returns
+/// Returns the shares minted and the deposited amounts, in the order of
+/// `params.token_a` and `params.token_b`
+pub(crate) fn add_through_router(
+    env: &Env,
+    user: &Address,
+    params: &LiquidityParams,
+) -> Result<(i128, i128, i128), AmmError> {
+    user.require_auth();
+
+    let mut pool = find_pool(env, &params.token_a, &params.token_b)?;
+    let flipped = params.token_a != Some(pool.token_a.clone());
+    let (desired_a, desired_b, min_a, min_b) = if flipped {
+        (
+            params.amount_b,
+            params.amount_a,
+            params.min_amount_b,
+            params.min_amount_a,
+        )
+    } else {
+        (
+            params.amount_a,
+            params.amount_b,
+            params.min_amount_a,
+            params.min_amount_b,
+        )
+    };
+    if pool.reserve_a <= 0 || pool.reserve_b <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+
+    let optimal_b = desired_a
+        .checked_mul(pool.reserve_b)
+        .ok_or(AmmError::Overflow)?
+        / pool.reserve_a;
+    let (amount_a, amount_b) = if optimal_b <= desired_b {
+        (desired_a, optimal_b)
+    } else {
+        let optimal_a = desired_b
+            .checked_mul(pool.reserve_a)
+            .ok_or(AmmError::Overflow)?
+            / pool.reserve_b;
+        (optimal_a, desired_b)
+    };
+    if amount_a < min_a || amount_b < min_b {
+        return Err(AmmError::MinOutputNotMet);
+    }
+
+    let shares = (amount_a
+        .checked_mul(pool.total_shares)
+        .ok_or(AmmError::Overflow)?
+        / pool.reserve_a)
+        .min(
+            amount_b
+                .checked_mul(pool.total_shares)
+                .ok_or(AmmError::Overflow)?
+                / pool.reserve_b,
+        );
+    if shares <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+
+    let contract = env.current_contract_address();
+    soroban_sdk::token::Client::new(env, &pool.token_a).transfer(user, &contract, &amount_a);
+    soroban_sdk::token::Client::new(env, &pool.token_b).transfer(user, &contract, &amount_b);
+
+    pool.reserve_a += amount_a;
+    pool.reserve_b += amount_b;
+    pool.total_shares += shares;
+    save_pool(env, &pool);
+    let user_shares = get_lp_shares(env, pool.pool_id, user) + shares;
+    set_lp_shares(env, pool.pool_id, user, user_shares);
+
+    PoolLiquidityEvent {
+        pool_id: pool.pool_id,
+        user: user.clone(),
+        amount_a,
+        amount_b,
+        shares,
+    }
+    .publish(env);
+
+    if flipped {
+        Ok((shares, amount_b, amount_a))
+    } else {
+        Ok((shares, amount_a, amount_b))
+    }
+}
+
+/// Redeem LP shares of the pool for a router liquidity operation
+///
+/// # Returns
+/// Returns the withdrawn amounts, in the order of `token_a` and `token_b`
+pub(crate) fn remove_through_router(
+    env: &Env,
+    user: &Address,
+    token_a: &Option<Address>,
+    token_b: &Option<Address>,
+    shares: i128,
+) -> Result<(i128, i128), AmmError> {
+    user.require_auth();
+
+    let mut pool = find_pool(env, token_a, token_b)?;
+    let user_shares = get_lp_shares(env, pool.pool_id, user);
+    if shares <= 0 || user_shares < shares {
+        return Err(AmmError::InsufficientLpShares);
+    }
+
+    let (amount_a, amount_b) = shares_value(env, pool.pool_id, shares)?;
+    pool.reserve_a -= amount_a;
+    pool.reserve_b -= amount_b;
+    pool.total_shares -= shares;
+    save_pool(env, &pool);
+    set_lp_shares(env, pool.pool_id, user, user_shares - shares);
+
+    let contract = env.current_contract_address();
+    if amount_a > 0 {
+        soroban_sdk::token::Client::new(env, &pool.token_a).transfer(&contract, user, &amount_a);
+    }
+    if amount_b > 0 {
+        soroban_sdk::token::Client::new(env, &pool.token_b).transfer(&contract, user, &amount_b);
+    }
+
+    PoolLiquidityEvent {
+        pool_id: pool.pool_id,
+        user: user.clone(),
+        amount_a,
+        amount_b,
+        shares: -shares,
+    }
+    .publish(env);
+
+    if *token_a == Some(pool.token_a.clone()) {
+        Ok((amount_a, amount_b))
+    } else {
+        Ok((amount_b, amount_a))
+    }
+}
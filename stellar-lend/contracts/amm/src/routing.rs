@@ -0,0 +1,390 @@
+//! # Swap Routing
+//!
+//! Route discovery across the token pairs of all enabled AMM protocols, so
+//! tokens without a direct pool can still be swapped through one or two
+//! intermediate tokens, possibly on different protocols, and best-execution
+//! splitting of large swaps across the protocols sharing a pair.
+//!
+//! ## Discovery
+//! Every supported pair is an edge usable in both directions. Routes of up
+//! to `MAX_ROUTE_HOPS` hops are explored depth-first from `token_in`; each
+//! hop is quoted with the output of the previous one at the default
+//! slippage, and the route with the highest final output wins. On equal
+//! output the route found first, i.e. the one using earlier registered
+//! protocols, is kept.
+//!
+//! ## Execution
+//! A route is executed hop by hop through `execute_swap`, feeding the amount
+//! received from one hop into the next. Each hop carries its own minimum
+//! output derived from its quote, so the usual slippage, deadline, callback
+//! and history handling applies to every hop.
+//!
+//! ## Split Swaps
+//! `execute_split_swap` divides a swap between every enabled protocol that
+//! supports the pair, proportionally to the depth each one quotes for the
+//! input token, so every leg moves its venue's price by about the same
+//! fraction. Protocols whose share would fall below their minimum swap
+//! amount are dropped, shallowest first. The legs are recorded as a single
+//! history entry listing each protocol's share.
+//!
+//! ## Invariants
+//! - A route never visits the same token twice.
+//! - Hops only use enabled protocols, within their swap amount limits.
+//! - The legs of a split swap add up to the full input.
+
+#![allow(unused)]
+use soroban_sdk::{contracttype, Address, Env, Map, Vec};
+
+use crate::amm::{
+    calculate_min_output_with_slippage, complete_swap, execute_swap, get_amm_protocols,
+    get_amm_settings, quote_amm_depth, quote_amm_swap, resolve_deadline, swap_on_protocol,
+    validate_token_pair, AmmError, AmmProtocolConfig, SwapParams, SwapSplit,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Maximum number of hops in a route
+pub const MAX_ROUTE_HOPS: u32 = 3;
+
+/// One swap of a route
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteHop {
+    /// AMM protocol used for this hop
+    pub protocol: Address,
+    /// Token sold (None for native XLM)
+    pub token_in: Option<Address>,
+    /// Token bought (None for native XLM)
+    pub token_out: Option<Address>,
+    /// Expected amount received from this hop
+    pub amount_out: i128,
+}
+
+/// A swap path and its expected output
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapRoute {
+    /// Hops in execution order
+    pub hops: Vec<RouteHop>,
+    /// Amount sold in the first hop
+    pub amount_in: i128,
+    /// Expected amount received from the last hop
+    pub amount_out: i128,
+}
+
+/// Find the route with the highest output from `token_in` to `token_out`
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `token_in` - Token to sell (None for native XLM)
+/// * `token_out` - Token to buy (None for native XLM)
+/// * `amount` - Amount of `token_in` to sell
+///
+/// # Returns
+/// Returns the best route of at most `MAX_ROUTE_HOPS` hops
+pub fn quote_route(
+    env: &Env,
+    token_in: Option<Address>,
+    token_out: Option<Address>,
+    amount: i128,
+) -> Result<SwapRoute, AmmError> {
+    if amount <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if token_in == token_out {
+        return Err(AmmError::InvalidTokenPair);
+    }
+
+    let protocols = get_amm_protocols(env)?;
+    let slippage = get_amm_settings(env)?.default_slippage;
+    let mut best: Option<SwapRoute> = None;
+    search_routes(
+        env,
+        &protocols,
+        slippage,
+        &token_in,
+        &token_out,
+        amount,
+        &Vec::new(env),
+        &mut best,
+    );
+
+    let mut route = best.ok_or(AmmError::NoRouteFound)?;
+    route.amount_in = amount;
+    Ok(route)
+}
+
+/// Extend `path` from `current` by one hop at a time, keeping the best route
+fn search_routes(
+    env: &Env,
+    protocols: &Map<Address, AmmProtocolConfig>,
+    slippage: i128,
+    current: &Option<Address>,
+    token_out: &Option<Address>,
+    amount: i128,
+    path: &Vec<RouteHop>,
+    best: &mut Option<SwapRoute>,
+) {
+    if path.len() >= MAX_ROUTE_HOPS {
+        return;
+    }
+
+    for (protocol, config) in protocols.iter() {
+        if !config.enabled || amount < config.min_swap_amount || amount > config.max_swap_amount {
+            continue;
+        }
+
+        for pair in config.supported_pairs.iter() {
+            let next = if pair.token_a == *current {
+                pair.token_b.clone()
+            } else if pair.token_b == *current {
+                pair.token_a.clone()
+            } else {
+                continue;
+            };
+            if visits(path, &next) {
+                continue;
+            }
+
+            let params = SwapParams {
+                protocol: protocol.clone(),
+                token_in: current.clone(),
+                token_out: next.clone(),
+                amount_in: amount,
+                min_amount_out: 1,
+                slippage_tolerance: slippage,
+                deadline: env.ledger().timestamp(),
+                use_oracle_floor: false,
+            };
+            let amount_out = match quote_amm_swap(env, &params) {
+                Ok(amount_out) if amount_out > 0 => amount_out,
+                _ => continue,
+            };
+
+            let mut extended = path.clone();
+            extended.push_back(RouteHop {
+                protocol: protocol.clone(),
+                token_in: current.clone(),
+                token_out: next.clone(),
+                amount_out,
+            });
+
+            if next == *token_out {
+                if best.as_ref().is_none_or(|b| amount_out > b.amount_out) {
+                    *best = Some(SwapRoute {
+                        hops: extended,
+                        amount_in: 0,
+                        amount_out,
+                    });
+                }
+            } else {
+                search_routes(
+                    env, protocols, slippage, &next, token_out, amount_out, &extended, best,
+                );
+            }
+        }
+    }
+}
+
+/// Whether a path already started at or passed through `token`
+fn visits(path: &Vec<RouteHop>, token: &Option<Address>) -> bool {
+    path.iter()
+        .any(|hop| hop.token_in == *token || hop.token_out == *token)
+}
+
+/// Execute a route hop by hop for `user`
+///
+/// Each hop must receive at least its quoted output less `slippage` (bps).
+///
+/// # Returns
+/// Returns the amount received from the last hop
+pub(crate) fn execute_route(
+    env: &Env,
+    user: &Address,
+    route: &SwapRoute,
+    slippage: i128,
+    deadline: u64,
+) -> Result<i128, AmmError> {
+    let mut amount = route.amount_in;
+    for hop in route.hops.iter() {
+        let params = SwapParams {
+            protocol: hop.protocol,
+            token_in: hop.token_in,
+            token_out: hop.token_out,
+            amount_in: amount,
+            min_amount_out: calculate_min_output_with_slippage(hop.amount_out, slippage)?,
+            slippage_tolerance: slippage,
+            deadline,
+            use_oracle_floor: false,
+        };
+        amount = execute_swap(env, user.clone(), params)?;
+    }
+    Ok(amount)
+}
+
+/// Execute a swap split across every enabled protocol supporting the pair
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The user performing the swap
+/// * `token_in` - Token to sell (None for native XLM)
+/// * `token_out` - Token to buy (None for native XLM)
+/// * `amount_in` - Total amount to sell
+/// * `min_amount_out` - Minimum total amount to receive
+/// * `deadline` - Swap deadline timestamp (0 for the default window)
+///
+/// # Returns
+/// Returns the total amount received
+pub fn execute_split_swap(
+    env: &Env,
+    user: Address,
+    token_in: Option<Address>,
+    token_out: Option<Address>,
+    amount_in: i128,
+    min_amount_out: i128,
+    deadline: u64,
+) -> Result<i128, AmmError> {
+    if amount_in <= 0 || min_amount_out <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    let deadline = resolve_deadline(env, deadline)?;
+    let slippage = get_amm_settings(env)?.default_slippage;
+    let legs = plan_split(env, &token_in, &token_out, amount_in)?;
+
+    let mut splits = Vec::new(env);
+    let mut main_protocol: Option<(Address, i128)> = None;
+    let mut amount_out = 0i128;
+    let mut fees_paid = 0i128;
+    for (protocol, leg_in) in legs.iter() {
+        let mut params = SwapParams {
+            protocol: protocol.clone(),
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_in: leg_in,
+            min_amount_out: 1,
+            slippage_tolerance: slippage,
+            deadline,
+            use_oracle_floor: false,
+        };
+        params.min_amount_out =
+            calculate_min_output_with_slippage(quote_amm_swap(env, &params)?, slippage)?.max(1);
+        let (leg_out, leg_fees) = swap_on_protocol(env, &user, &params, false)?;
+
+        amount_out = amount_out.checked_add(leg_out).ok_or(AmmError::Overflow)?;
+        fees_paid = fees_paid.checked_add(leg_fees).ok_or(AmmError::Overflow)?;
+        if main_protocol
+            .as_ref()
+            .is_none_or(|(_, main_in)| leg_in > *main_in)
+        {
+            main_protocol = Some((protocol.clone(), leg_in));
+        }
+        splits.push_back(SwapSplit {
+            protocol,
+            ratio_bps: leg_in * BASIS_POINTS_SCALE / amount_in,
+            amount_in: leg_in,
+            amount_out: leg_out,
+        });
+    }
+    if amount_out < min_amount_out {
+        return Err(AmmError::MinOutputNotMet);
+    }
+
+    // The history entry names the protocol that took the largest share
+    let (protocol, _) = main_protocol.ok_or(AmmError::UnsupportedProtocol)?;
+    let params = SwapParams {
+        protocol,
+        token_in,
+        token_out,
+        amount_in,
+        min_amount_out,
+        slippage_tolerance: slippage,
+        deadline,
+        use_oracle_floor: false,
+    };
+    complete_swap(env, &user, &params, amount_out, fees_paid, splits)
+}
+
+/// Divide `amount_in` between the protocols supporting a pair by their depth
+///
+/// # Returns
+/// Returns the (protocol, amount_in) legs, in protocol registration order
+fn plan_split(
+    env: &Env,
+    token_in: &Option<Address>,
+    token_out: &Option<Address>,
+    amount_in: i128,
+) -> Result<Vec<(Address, i128)>, AmmError> {
+    // (protocol, depth, min_swap_amount) of every protocol supporting the pair
+    let mut candidates: Vec<(Address, i128, i128)> = Vec::new(env);
+    for (protocol, config) in get_amm_protocols(env)?.iter() {
+        if !config.enabled || validate_token_pair(env, &config, token_in, token_out).is_err() {
+            continue;
+        }
+        let params = SwapParams {
+            protocol: protocol.clone(),
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_in,
+            min_amount_out: 1,
+            slippage_tolerance: 0,
+            deadline: env.ledger().timestamp(),
+            use_oracle_floor: false,
+        };
+        if let Ok(depth) = quote_amm_depth(env, &config, &params) {
+            if depth > 0 {
+                candidates.push_back((protocol, depth, config.min_swap_amount));
+            }
+        }
+    }
+    if candidates.is_empty() {
+        return Err(AmmError::UnsupportedProtocol);
+    }
+
+    loop {
+        let total_depth = total_depth(&candidates)?;
+
+        // Drop the shallowest protocol whose share is below its minimum
+        let mut drop: Option<(u32, i128)> = None;
+        for (index, (_, depth, min_swap_amount)) in candidates.iter().enumerate() {
+            if share(amount_in, depth, total_depth)? < min_swap_amount
+                && drop.is_none_or(|(_, shallowest)| depth < shallowest)
+            {
+                drop = Some((index as u32, depth));
+            }
+        }
+        match drop {
+            Some((index, _)) if candidates.len() > 1 => {
+                candidates.remove(index);
+            }
+            _ => break,
+        }
+    }
+
+    // Rounding dust goes to the deepest protocol
+    let total_depth = total_depth(&candidates)?;
+    let mut deepest = 0u32;
+    let mut allocated = 0i128;
+    let mut legs = Vec::new(env);
+    for (index, (protocol, depth, _)) in candidates.iter().enumerate() {
+        if depth > candidates.get_unchecked(deepest).1 {
+            deepest = index as u32;
+        }
+        let leg_in = share(amount_in, depth, total_depth)?;
+        allocated += leg_in;
+        legs.push_back((protocol, leg_in));
+    }
+    let (protocol, leg_in) = legs.get_unchecked(deepest);
+    legs.set(deepest, (protocol, leg_in + amount_in - allocated));
+    Ok(legs)
+}
+
+fn total_depth(candidates: &Vec<(Address, i128, i128)>) -> Result<i128, AmmError> {
+    candidates.iter().try_fold(0i128, |total, (_, depth, _)| {
+        total.checked_add(depth).ok_or(AmmError::Overflow)
+    })
+}
+
+fn share(amount: i128, depth: i128, total_depth: i128) -> Result<i128, AmmError> {
+    Ok(amount.checked_mul(depth).ok_or(AmmError::Overflow)? / total_depth)
+}
@@ -0,0 +1,196 @@
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol, Vec};
+
+fn setup(env: &Env) -> (AmmContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let contract = AmmContractClient::new(env, &env.register(AmmContract {}, ()));
+    let admin = Address::generate(env);
+    contract.initialize_amm_settings(&admin, &100, &1000, &10000);
+    (contract, admin)
+}
+
+/// Register a mock protocol supporting the given pairs; it pays 99% per swap
+fn add_protocol(
+    env: &Env,
+    contract: &AmmContractClient,
+    admin: &Address,
+    pairs: &[(Option<Address>, Option<Address>)],
+) -> Address {
+    add_protocol_with_depth(env, contract, admin, pairs, 1_000_000_000)
+}
+
+/// Register a mock protocol whose swap capacity (its quoted depth) is `depth`
+fn add_protocol_with_depth(
+    env: &Env,
+    contract: &AmmContractClient,
+    admin: &Address,
+    pairs: &[(Option<Address>, Option<Address>)],
+    depth: i128,
+) -> Address {
+    let protocol = Address::generate(env);
+    let mut supported_pairs = Vec::new(env);
+    for (token_a, token_b) in pairs {
+        supported_pairs.push_back(TokenPair {
+            token_a: token_a.clone(),
+            token_b: token_b.clone(),
+            pool_address: Address::generate(env),
+        });
+    }
+    contract.add_amm_protocol(
+        admin,
+        &AmmProtocolConfig {
+            protocol_address: protocol.clone(),
+            protocol_name: Symbol::new(env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1000,
+            max_swap_amount: depth,
+            supported_pairs,
+        },
+    );
+    protocol
+}
+
+#[test]
+fn test_quote_route_chains_protocols() {
+    let env = Env::default();
+    let (contract, admin) = setup(&env);
+    let usdc = Some(Address::generate(&env));
+    let eurc = Some(Address::generate(&env));
+    let first = add_protocol(&env, &contract, &admin, &[(None, usdc.clone())]);
+    let second = add_protocol(&env, &contract, &admin, &[(eurc.clone(), usdc.clone())]);
+
+    let route = contract.quote_route(&None, &eurc, &100_000);
+    assert_eq!(route.hops.len(), 2);
+    assert_eq!(route.amount_in, 100_000);
+    assert_eq!(route.amount_out, 98_010);
+
+    let hop = route.hops.get(0).unwrap();
+    assert_eq!(hop.protocol, first);
+    assert_eq!(hop.token_out, usdc);
+    assert_eq!(hop.amount_out, 99_000);
+    let hop = route.hops.get(1).unwrap();
+    assert_eq!(hop.protocol, second);
+    assert_eq!(hop.token_in, usdc);
+    assert_eq!(hop.token_out, eurc);
+
+    // Auto-swap follows the route hop by hop
+    let user = Address::generate(&env);
+    let amount_out = contract.auto_swap_for_collateral(&user, &eurc, &100_000);
+    assert_eq!(amount_out, 98_010);
+    let history = contract.get_swap_history(&Some(user), &10).unwrap();
+    assert_eq!(history.len(), 2);
+}
+
+#[test]
+fn test_quote_route_prefers_direct_pair() {
+    let env = Env::default();
+    let (contract, admin) = setup(&env);
+    let usdc = Some(Address::generate(&env));
+    let eurc = Some(Address::generate(&env));
+    add_protocol(
+        &env,
+        &contract,
+        &admin,
+        &[(None, usdc.clone()), (usdc.clone(), eurc.clone())],
+    );
+    let direct = add_protocol(&env, &contract, &admin, &[(None, eurc.clone())]);
+
+    let route = contract.quote_route(&None, &eurc, &100_000);
+    assert_eq!(route.hops.len(), 1);
+    assert_eq!(route.hops.get(0).unwrap().protocol, direct);
+    assert_eq!(route.amount_out, 99_000);
+}
+
+#[test]
+fn test_quote_route_limits_hops() {
+    let env = Env::default();
+    let (contract, admin) = setup(&env);
+    let tokens: [Option<Address>; 4] = core::array::from_fn(|_| Some(Address::generate(&env)));
+    add_protocol(
+        &env,
+        &contract,
+        &admin,
+        &[
+            (None, tokens[0].clone()),
+            (tokens[0].clone(), tokens[1].clone()),
+            (tokens[1].clone(), tokens[2].clone()),
+            (tokens[2].clone(), tokens[3].clone()),
+        ],
+    );
+
+    assert_eq!(
+        contract.quote_route(&None, &tokens[2], &100_000).hops.len(),
+        3
+    );
+    assert_eq!(
+        contract.try_quote_route(&None, &tokens[3], &100_000),
+        Err(Ok(AmmError::NoRouteFound))
+    );
+    assert_eq!(
+        contract.try_quote_route(&None, &Some(Address::generate(&env)), &100_000),
+        Err(Ok(AmmError::NoRouteFound))
+    );
+}
+
+#[test]
+fn test_split_swap_follows_depth() {
+    let env = Env::default();
+    let (contract, admin) = setup(&env);
+    let usdc = Some(Address::generate(&env));
+    let deep = add_protocol_with_depth(&env, &contract, &admin, &[(None, usdc.clone())], 3_000_000);
+    let shallow =
+        add_protocol_with_depth(&env, &contract, &admin, &[(None, usdc.clone())], 1_000_000);
+    let deadline = env.ledger().timestamp() + 300;
+    let user = Address::generate(&env);
+
+    assert_eq!(
+        contract.try_execute_split_swap(&user, &None, &usdc, &400_000, &396_001, &deadline),
+        Err(Ok(AmmError::MinOutputNotMet))
+    );
+    let amount_out =
+        contract.execute_split_swap(&user, &None, &usdc, &400_000, &396_000, &deadline);
+    assert_eq!(amount_out, 396_000);
+
+    let history = contract.get_swap_history(&Some(user), &10).unwrap();
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.protocol, deep);
+    assert_eq!(record.amount_in, 400_000);
+    assert_eq!(record.amount_out, 396_000);
+    assert_eq!(record.splits.len(), 2);
+
+    let leg = record.splits.get(0).unwrap();
+    assert_eq!(leg.protocol, deep);
+    assert_eq!(leg.ratio_bps, 7_500);
+    assert_eq!(leg.amount_in, 300_000);
+    assert_eq!(leg.amount_out, 297_000);
+    let leg = record.splits.get(1).unwrap();
+    assert_eq!(leg.protocol, shallow);
+    assert_eq!(leg.ratio_bps, 2_500);
+    assert_eq!(leg.amount_in, 100_000);
+}
+
+#[test]
+fn test_split_swap_drops_legs_below_minimum() {
+    let env = Env::default();
+    let (contract, admin) = setup(&env);
+    let usdc = Some(Address::generate(&env));
+    let deep = add_protocol_with_depth(&env, &contract, &admin, &[(None, usdc.clone())], 3_000_000);
+    add_protocol_with_depth(&env, &contract, &admin, &[(None, usdc.clone())], 1_000_000);
+    let user = Address::generate(&env);
+
+    // A quarter of 2_000 is below the 1_000 minimum swap amount
+    let deadline = env.ledger().timestamp() + 300;
+    contract.execute_split_swap(&user, &None, &usdc, &2_000, &1, &deadline);
+
+    let record = contract
+        .get_swap_history(&Some(user), &10)
+        .unwrap()
+        .get(0)
+        .unwrap();
+    assert_eq!(record.splits.len(), 1);
+    assert_eq!(record.splits.get(0).unwrap().protocol, deep);
+    assert_eq!(record.splits.get(0).unwrap().ratio_bps, 10_000);
+    assert_eq!(record.splits.get(0).unwrap().amount_in, 2_000);
+}
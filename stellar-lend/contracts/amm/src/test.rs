@@ -1,6 +1,8 @@
 use super::*;
 use crate::amm::*;
-use soroban_sdk::{testutils::Address as _, testutils::Ledger, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    testutils::Address as _, testutils::Ledger, vec, Address, Env, Map, Symbol, Vec,
+};
 
 fn create_amm_contract<'a>(env: &Env) -> AmmContractClient<'a> {
     AmmContractClient::new(env, &env.register(AmmContract {}, ()))
@@ -94,6 +96,10 @@ fn test_update_amm_settings() {
         swap_enabled: false,
         liquidity_enabled: true,
         auto_swap_threshold: 20000,
+        pair_max_slippage: Map::new(&env),
+        max_swap_history: 1000,
+        swap_history_retention: 0,
+        max_deadline_window: 3600,
     };
 
     contract.update_amm_settings(&admin, &new_settings);
@@ -107,6 +113,59 @@ fn test_update_amm_settings() {
     assert_eq!(settings.auto_swap_threshold, 20000);
 }
 
+#[test]
+fn test_pair_max_slippage_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract = create_amm_contract(&env);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let protocol_addr = Address::generate(&env);
+
+    contract.initialize_amm_settings(&admin, &100, &1000, &10000);
+    let protocol_config = create_test_protocol_config(&env, &protocol_addr);
+    contract.add_amm_protocol(&admin, &protocol_config);
+    let token_b = protocol_config.supported_pairs.get(0).unwrap().token_b;
+
+    // Set in either token order, the override caps both directions
+    contract.set_pair_max_slippage(&admin, &token_b, &None, &Some(50));
+    assert_eq!(contract.get_pair_max_slippage(&None, &token_b), 50);
+    assert_eq!(
+        contract.get_pair_max_slippage(&None, &Some(Address::generate(&env))),
+        1000
+    );
+
+    let mut params = SwapParams {
+        protocol: protocol_addr.clone(),
+        token_in: None,
+        token_out: token_b.clone(),
+        amount_in: 10000,
+        min_amount_out: 9000,
+        slippage_tolerance: 100,
+        deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
+    };
+    assert_eq!(
+        contract.try_execute_swap(&user, &params),
+        Err(Ok(AmmError::SlippageExceeded))
+    );
+    params.slippage_tolerance = 50;
+    assert_eq!(contract.execute_swap(&user, &params), 9950);
+
+    // An override never loosens the global cap
+    contract.set_pair_max_slippage(&admin, &None, &token_b, &Some(5000));
+    assert_eq!(contract.get_pair_max_slippage(&token_b, &None), 1000);
+    assert_eq!(
+        contract.try_set_pair_max_slippage(&admin, &None, &token_b, &Some(10_000)),
+        Err(Ok(AmmError::InvalidSwapParams))
+    );
+
+    contract.set_pair_max_slippage(&admin, &None, &token_b, &None);
+    params.slippage_tolerance = 100;
+    assert_eq!(contract.execute_swap(&user, &params), 9900);
+}
+
 #[test]
 fn test_successful_swap() {
     let env = Env::default();
@@ -149,6 +208,7 @@ fn test_successful_swap() {
         min_amount_out: 9000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let amount_out = contract.execute_swap(&user, &params);
@@ -184,6 +244,7 @@ fn test_swap_failure_insufficient_output() {
         min_amount_out: 10000, // Too high for 1% mock slippage
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let result = contract.try_execute_swap(&user, &params);
@@ -215,12 +276,75 @@ fn test_swap_failure_deadline_exceeded() {
         min_amount_out: 5000,
         slippage_tolerance: 100,
         deadline: 999, // Before current ledger timestamp (1000)
+        use_oracle_floor: false,
     };
 
     let result = contract.try_execute_swap(&user, &params);
     assert!(result.is_err());
 }
 
+#[test]
+fn test_swap_deadline_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let contract = create_amm_contract(&env);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let protocol_addr = Address::generate(&env);
+
+    contract.initialize_amm_settings(&admin, &100, &1000, &10000);
+    let protocol_config = create_test_protocol_config(&env, &protocol_addr);
+    contract.add_amm_protocol(&admin, &protocol_config);
+
+    let mut params = SwapParams {
+        protocol: protocol_addr.clone(),
+        token_in: None,
+        token_out: protocol_config.supported_pairs.get(0).unwrap().token_b,
+        amount_in: 10000,
+        min_amount_out: 5000,
+        slippage_tolerance: 100,
+        deadline: 0,
+        use_oracle_floor: false,
+    };
+
+    // A deadline of 0 stands for the default window
+    assert_eq!(contract.execute_swap(&user, &params), 9900);
+    assert_eq!(contract.quote_swap(&params).amount_out, 9900);
+
+    // Deadlines are capped at an hour out by default
+    params.deadline = 1000 + DEFAULT_MAX_DEADLINE_WINDOW + 1;
+    assert_eq!(
+        contract.try_execute_swap(&user, &params),
+        Err(Ok(AmmError::DeadlineTooFar))
+    );
+    assert_eq!(
+        contract.try_quote_swap(&params),
+        Err(Ok(AmmError::DeadlineTooFar))
+    );
+    params.deadline = 1000 + DEFAULT_MAX_DEADLINE_WINDOW;
+    assert_eq!(contract.execute_swap(&user, &params), 9900);
+
+    // A window shorter than the default also shortens deadlines of 0
+    let mut settings = contract.get_amm_settings().unwrap();
+    settings.max_deadline_window = 60;
+    contract.update_amm_settings(&admin, &settings);
+    params.deadline = 1061;
+    assert_eq!(
+        contract.try_execute_swap(&user, &params),
+        Err(Ok(AmmError::DeadlineTooFar))
+    );
+    params.deadline = 0;
+    assert_eq!(contract.execute_swap(&user, &params), 9900);
+
+    // A window of 0 lifts the cap
+    settings.max_deadline_window = 0;
+    contract.update_amm_settings(&admin, &settings);
+    params.deadline = u64::MAX;
+    assert_eq!(contract.execute_swap(&user, &params), 9900);
+}
+
 #[test]
 fn test_swap_failure_paused() {
     let env = Env::default();
@@ -247,6 +371,7 @@ fn test_swap_failure_paused() {
         min_amount_out: 5000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let result = contract.try_execute_swap(&user, &params);
@@ -379,6 +504,68 @@ fn test_callback_validation() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_quote_swap_matches_execution() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract = create_amm_contract(&env);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let protocol_addr = Address::generate(&env);
+
+    contract.initialize_amm_settings(&admin, &100, &1000, &10000);
+    let protocol_config = create_test_protocol_config(&env, &protocol_addr);
+    let token_out = protocol_config
+        .supported_pairs
+        .get(0)
+        .unwrap()
+        .token_b
+        .clone();
+    contract.add_amm_protocol(&admin, &protocol_config);
+
+    let params = SwapParams {
+        protocol: protocol_addr.clone(),
+        token_in: None,
+        token_out,
+        amount_in: 10000,
+        min_amount_out: 9950,
+        slippage_tolerance: 100,
+        deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
+    };
+
+    let quote = contract.quote_swap(&params);
+    assert_eq!(quote.protocol, protocol_addr);
+    assert_eq!(quote.amount_out, 9900);
+    assert_eq!(quote.effective_price, 990_000_000_000_000_000);
+    assert_eq!(quote.price_impact_bps, 0);
+    assert_eq!(quote.fee_bps, 30);
+    assert_eq!(quote.fee_amount, 30);
+    assert!(!quote.meets_min_amount_out);
+
+    // Quoting writes nothing
+    assert_eq!(
+        contract
+            .get_swap_history(&Some(user.clone()), &10)
+            .unwrap()
+            .len(),
+        0
+    );
+
+    let mut params = params;
+    params.min_amount_out = quote.amount_out;
+    assert!(contract.quote_swap(&params).meets_min_amount_out);
+    assert_eq!(contract.execute_swap(&user, &params), quote.amount_out);
+
+    // Quotes fail where the swap would
+    params.amount_in = 10;
+    assert_eq!(
+        contract.try_quote_swap(&params),
+        Err(Ok(AmmError::InvalidSwapParams))
+    );
+}
+
 #[test]
 fn test_auto_swap_for_collateral() {
     let env = Env::default();
@@ -433,6 +620,7 @@ fn test_swap_failure_unsupported_protocol() {
         min_amount_out: 5000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let result = contract.try_execute_swap(&user, &params);
@@ -461,6 +649,7 @@ fn test_swap_failure_invalid_token_pair() {
         min_amount_out: 5000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let result = contract.try_execute_swap(&user, &params);
@@ -524,6 +713,7 @@ fn test_get_history_with_limit() {
         min_amount_out: 5000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     contract.execute_swap(&user, &params);
@@ -535,6 +725,163 @@ fn test_get_history_with_limit() {
     assert_eq!(history.len(), 2);
 }
 
+fn swap_ids(env: &Env, records: &Vec<SwapRecord>) -> Vec<u64> {
+    let mut ids = Vec::new(env);
+    for record in records.iter() {
+        ids.push_back(record.id);
+    }
+    ids
+}
+
+#[test]
+fn test_swap_history_pagination_and_retention() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract = create_amm_contract(&env);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let protocol_a = Address::generate(&env);
+    let protocol_b = Address::generate(&env);
+
+    contract.initialize_amm_settings(&admin, &100, &1000, &10000);
+    let config_a = create_test_protocol_config(&env, &protocol_a);
+    let config_b = create_test_protocol_config(&env, &protocol_b);
+    contract.add_amm_protocol(&admin, &config_a);
+    contract.add_amm_protocol(&admin, &config_b);
+
+    let swap_at = |timestamp: u64, config: &AmmProtocolConfig| {
+        env.ledger().set_timestamp(timestamp);
+        let params = SwapParams {
+            protocol: config.protocol_address.clone(),
+            token_in: None,
+            token_out: config.supported_pairs.get(0).unwrap().token_b,
+            amount_in: 10000,
+            min_amount_out: 5000,
+            slippage_tolerance: 100,
+            deadline: timestamp + 3600,
+            use_oracle_floor: false,
+        };
+        contract.execute_swap(&user, &params);
+    };
+    swap_at(100, &config_a);
+    swap_at(200, &config_a);
+    swap_at(300, &config_a);
+    swap_at(400, &config_b);
+    swap_at(500, &config_a);
+
+    let mut filter = SwapHistoryFilter {
+        user: Some(user.clone()),
+        protocol: None,
+        start_time: None,
+        end_time: None,
+    };
+
+    // Pages run from the newest record back
+    let page = contract.get_swap_history_page(&filter, &None, &2);
+    assert_eq!(swap_ids(&env, &page.records), vec![&env, 4, 3]);
+    assert_eq!(page.next_cursor, Some(3));
+    let page = contract.get_swap_history_page(&filter, &page.next_cursor, &2);
+    assert_eq!(swap_ids(&env, &page.records), vec![&env, 2, 1]);
+    let page = contract.get_swap_history_page(&filter, &page.next_cursor, &2);
+    assert_eq!(swap_ids(&env, &page.records), vec![&env, 0]);
+    assert_eq!(page.next_cursor, None);
+
+    filter.protocol = Some(protocol_b.clone());
+    let page = contract.get_swap_history_page(&filter, &None, &10);
+    assert_eq!(swap_ids(&env, &page.records), vec![&env, 3]);
+
+    filter.protocol = None;
+    filter.start_time = Some(200);
+    filter.end_time = Some(300);
+    let page = contract.get_swap_history_page(&filter, &None, &10);
+    assert_eq!(swap_ids(&env, &page.records), vec![&env, 2, 1]);
+
+    // Other users' swaps are filtered out
+    filter.user = Some(Address::generate(&env));
+    let page = contract.get_swap_history_page(&filter, &None, &10);
+    assert_eq!(page.records.len(), 0);
+
+    // The history keeps at most max_swap_history records
+    let mut settings = contract.get_amm_settings().unwrap();
+    settings.max_swap_history = 3;
+    contract.update_amm_settings(&admin, &settings);
+    swap_at(600, &config_a);
+    let history = contract.get_swap_history(&None, &10).unwrap();
+    assert_eq!(swap_ids(&env, &history), vec![&env, 5, 4, 3]);
+
+    // Records older than the retention window are dropped, and ids go on
+    settings.swap_history_retention = 150;
+    contract.update_amm_settings(&admin, &settings);
+    swap_at(700, &config_a);
+    let history = contract.get_swap_history(&None, &10).unwrap();
+    assert_eq!(swap_ids(&env, &history), vec![&env, 6, 5]);
+}
+
+#[test]
+fn test_swap_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let contract = create_amm_contract(&env);
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let protocol_a = Address::generate(&env);
+    let protocol_b = Address::generate(&env);
+
+    contract.initialize_amm_settings(&admin, &100, &1000, &10000);
+    let config_a = create_test_protocol_config(&env, &protocol_a);
+    let config_b = create_test_protocol_config(&env, &protocol_b);
+    contract.add_amm_protocol(&admin, &config_a);
+    contract.add_amm_protocol(&admin, &config_b);
+    let token_a = config_a.supported_pairs.get(0).unwrap().token_b;
+    let token_b = config_b.supported_pairs.get(0).unwrap().token_b;
+
+    let swap = |user: &Address, config: &AmmProtocolConfig, amount_in: i128| {
+        let params = SwapParams {
+            protocol: config.protocol_address.clone(),
+            token_in: None,
+            token_out: config.supported_pairs.get(0).unwrap().token_b,
+            amount_in,
+            min_amount_out: 5000,
+            slippage_tolerance: 100,
+            deadline: env.ledger().timestamp() + 3600,
+            use_oracle_floor: false,
+        };
+        contract.execute_swap(user, &params);
+    };
+    swap(&alice, &config_a, 10000);
+    swap(&alice, &config_a, 20000);
+    swap(&bob, &config_b, 10000);
+
+    let global = contract.get_swap_stats(&SwapStatsScope::Global);
+    assert_eq!(global.swap_count, 3);
+    assert_eq!(global.volume_in.get(None), Some(40000));
+    assert_eq!(global.volume_out.get(token_a.clone()), Some(29700));
+    assert_eq!(global.volume_out.get(token_b.clone()), Some(9900));
+    assert_eq!(global.fees_paid.get(None), Some(120));
+    assert_eq!(global.last_swap_at, 1000);
+
+    // Pairs are found in either token order
+    let pair = contract.get_swap_stats(&SwapStatsScope::Pair(token_a.clone(), None));
+    assert_eq!(pair.swap_count, 2);
+    assert_eq!(pair.volume_in.get(None), Some(30000));
+
+    let protocol = contract.get_swap_stats(&SwapStatsScope::Protocol(protocol_b));
+    assert_eq!(protocol.swap_count, 1);
+    assert_eq!(protocol.volume_out.get(token_b), Some(9900));
+
+    let user = contract.get_swap_stats(&SwapStatsScope::User(alice));
+    assert_eq!(user.swap_count, 2);
+    assert_eq!(user.volume_out.get(token_a), Some(29700));
+
+    let idle = contract.get_swap_stats(&SwapStatsScope::User(admin));
+    assert_eq!(idle.swap_count, 0);
+    assert_eq!(idle.volume_in.len(), 0);
+}
+
 #[test]
 fn test_multiple_protocol_selection() {
     let env = Env::default();
@@ -606,6 +953,7 @@ fn test_swap_failure_max_input_exceeded() {
         min_amount_out: 5000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let result = contract.try_execute_swap(&user, &params);
@@ -634,6 +982,7 @@ fn test_swap_failure_zero_amount() {
         min_amount_out: 5000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let result = contract.try_execute_swap(&user, &params);
@@ -657,6 +1006,10 @@ fn test_admin_only_operations() {
         swap_enabled: true,
         liquidity_enabled: true,
         auto_swap_threshold: 20000,
+        pair_max_slippage: Map::new(&env),
+        max_swap_history: 1000,
+        swap_history_retention: 0,
+        max_deadline_window: 3600,
     };
 
     let result = contract.try_update_amm_settings(&non_admin, &new_settings);
@@ -730,51 +1083,63 @@ fn test_callback_validation_success() {
         min_amount_out: 100,
         slippage_tolerance: 100,
         deadline: 2000,
+        use_oracle_floor: false,
     };
     env.ledger().set_timestamp(1000);
     contract.execute_swap(&user, &params);
 
-    // The nonce should now be 1 in storage (since it starts at 0, incremented during swap)
-    // Wait, generate_callback_nonce returns current + 1.
-    // So after one swap, nonce in storage is 1.
-    // The callback_data sent TO the AMM during the swap has nonce 1.
-    // The AMM calls validate_amm_callback with nonce 1.
-    // validate_amm_callback checks if callback_data.nonce == expected (0 initially, wait...)
-
-    // Let's re-read generate_callback_nonce:
-    // fn generate_callback_nonce(env: &Env, user: &Address) -> u64 {
-    //    let current_nonce = storage.get(user).unwrap_or(0);
-    //    let new_nonce = current_nonce + 1;
-    //    storage.set(user, new_nonce);
-    //    new_nonce
-    // }
-    //
-    // And validate_amm_callback:
-    // pub fn validate_amm_callback(..., callback_data) {
-    //    let expected_nonce = storage.get(user).unwrap_or(0);
-    //    if callback_data.nonce != expected_nonce { error }
-    //    storage.set(user, expected_nonce + 1);
-    // }
-
-    // There is a BUG in the contract logic:
-    // execute_swap calls generate_callback_nonce which SETS the nonce to 1.
-    // then it calls execute_amm_swap with nonce 1.
-    // execute_amm_swap calls validate_amm_callback.
-    // validate_amm_callback GETS the nonce (which is now 1) and compares it to callback_data.nonce (which is 1).
-    // So it works, BUT then it increments it to 2.
-
-    // Wait, let me check the existing test `test_callback_validation`.
-    // It says nonce 999 is wrong.
-
-    let callback_data = AmmCallbackData {
-        nonce: 2, // Should be 2 now
+    // The swap was issued nonce 1, which its callback used
+    assert_eq!(contract.get_next_nonce(&protocol_addr, &user), 2);
+    let mut callback_data = AmmCallbackData {
+        nonce: 1,
         operation: Symbol::new(&env, "swap"),
         user: user.clone(),
         expected_amounts: Vec::new(&env),
         deadline: 2000,
     };
+    assert_eq!(
+        contract.try_validate_amm_callback(&protocol_addr, &callback_data),
+        Err(Ok(AmmError::InvalidCallback))
+    );
+
+    // Nonces not issued yet are rejected
+    callback_data.nonce = 2;
+    assert_eq!(
+        contract.try_validate_amm_callback(&protocol_addr, &callback_data),
+        Err(Ok(AmmError::InvalidCallback))
+    );
+
+    // Issued nonces are accepted once each, in any order
+    env.as_contract(&contract.address, || {
+        generate_callback_nonce(&env, &protocol_addr, &user);
+        generate_callback_nonce(&env, &protocol_addr, &user);
+    });
+    callback_data.nonce = 3;
+    contract.validate_amm_callback(&protocol_addr, &callback_data);
+    callback_data.nonce = 2;
+    contract.validate_amm_callback(&protocol_addr, &callback_data);
+    assert_eq!(
+        contract.try_validate_amm_callback(&protocol_addr, &callback_data),
+        Err(Ok(AmmError::InvalidCallback))
+    );
 
+    // Nonces issued before the last CALLBACK_NONCE_WINDOW expire unused
+    env.as_contract(&contract.address, || {
+        for _ in 0..=CALLBACK_NONCE_WINDOW {
+            generate_callback_nonce(&env, &protocol_addr, &user);
+        }
+    });
+    assert_eq!(contract.get_next_nonce(&protocol_addr, &user), 69);
+    callback_data.nonce = 4;
+    assert_eq!(
+        contract.try_validate_amm_callback(&protocol_addr, &callback_data),
+        Err(Ok(AmmError::InvalidCallback))
+    );
+    callback_data.nonce = 5;
     contract.validate_amm_callback(&protocol_addr, &callback_data);
+
+    // Sequences are kept per protocol
+    assert_eq!(contract.get_next_nonce(&Address::generate(&env), &user), 1);
 }
 
 #[test]
@@ -815,6 +1180,7 @@ fn test_edge_case_max_slippage() {
         min_amount_out: 1,        // High slippage tolerance
         slippage_tolerance: 2000, // 20%
         deadline: 2000,
+        use_oracle_floor: false,
     };
     env.ledger().set_timestamp(1000);
     let amount_out = contract.execute_swap(&user, &params);
@@ -844,6 +1210,7 @@ fn test_edge_case_min_swap_amount() {
         min_amount_out: 100,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let result = contract.try_execute_swap(&user, &params);
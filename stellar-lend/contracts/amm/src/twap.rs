@@ -0,0 +1,251 @@
+//! # Time-Weighted Average Prices
+//!
+//! Cumulative price observations for registered token pairs, from which a
+//! time-weighted average price (TWAP) over any recent window can be read.
+//! The lending pool uses it to cross-check spot oracle prices against prices
+//! actually traded on-chain.
+//!
+//! ## Accumulators
+//! Each pair keeps, in both directions, the price of its last swap and a
+//! running sum of `price * seconds`. A swap first advances the sums at the
+//! previous price up to the current timestamp, then records its own
+//! effective price (`amount_out / amount_in`, 18 decimals) for the time that
+//! follows. A price therefore only counts for as long as it stands, so moving
+//! the TWAP requires holding a manipulated price across many ledgers.
+//!
+//! ## Observations
+//! A snapshot of the sums is kept at most every `OBSERVATION_INTERVAL`
+//! seconds, up to `MAX_OBSERVATIONS` per pair. `get_twap` averages from the
+//! newest snapshot at least `window` seconds old to now, so the averaged
+//! period is between `window` and `window + OBSERVATION_INTERVAL` seconds
+//! while swaps are frequent.
+//!
+//! ## Invariants
+//! - Pairs are stored once, in canonical token order; both directions are
+//!   served from the same record.
+//! - Sums use wrapping arithmetic; differences between two snapshots are
+//!   exact as long as they are less than `i128::MAX` apart.
+//! - Observation timestamps strictly increase.
+
+#![allow(unused)]
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::amm::AmmError;
+
+/// Fixed-point scale of prices (18 decimals)
+pub const PRICE_SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// Minimum spacing between stored observations (seconds)
+pub const OBSERVATION_INTERVAL: u64 = 60;
+
+/// Observations kept per pair
+pub const MAX_OBSERVATIONS: u32 = 120;
+
+/// Storage keys for TWAP data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum TwapDataKey {
+    /// Accumulators of a pair, in canonical token order: PairAccumulator
+    PairAccumulator(Option<Address>, Option<Address>),
+    /// Snapshots of a pair's accumulators: Vec<PriceObservation>
+    PairObservations(Option<Address>, Option<Address>),
+}
+
+/// Running price sums of a token pair, in canonical token order
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PairAccumulator {
+    /// Price of the first token in the second, from the last swap (18 decimals)
+    pub price_0: i128,
+    /// Price of the second token in the first, from the last swap (18 decimals)
+    pub price_1: i128,
+    /// Sum of `price_0 * seconds` up to `last_update`
+    pub cumulative_0: i128,
+    /// Sum of `price_1 * seconds` up to `last_update`
+    pub cumulative_1: i128,
+    /// Timestamp the sums were last advanced to
+    pub last_update: u64,
+}
+
+/// Snapshot of a pair's running price sums
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceObservation {
+    /// Timestamp of the snapshot
+    pub timestamp: u64,
+    /// Sum of `price_0 * seconds` at `timestamp`
+    pub cumulative_0: i128,
+    /// Sum of `price_1 * seconds` at `timestamp`
+    pub cumulative_1: i128,
+}
+
+/// Order a pair canonically; returns whether the tokens were swapped
+fn canonical(
+    token_a: &Option<Address>,
+    token_b: &Option<Address>,
+) -> (Option<Address>, Option<Address>, bool) {
+    if token_a <= token_b {
+        (token_a.clone(), token_b.clone(), false)
+    } else {
+        (token_b.clone(), token_a.clone(), true)
+    }
+}
+
+/// Advance the sums to `now` at the standing prices
+fn advance(acc: &PairAccumulator, now: u64) -> PairAccumulator {
+    let elapsed = now.saturating_sub(acc.last_update) as i128;
+    PairAccumulator {
+        cumulative_0: acc
+            .cumulative_0
+            .wrapping_add(acc.price_0.wrapping_mul(elapsed)),
+        cumulative_1: acc
+            .cumulative_1
+            .wrapping_add(acc.price_1.wrapping_mul(elapsed)),
+        last_update: now.max(acc.last_update),
+        ..acc.clone()
+    }
+}
+
+/// Record the effective price of a swap between two tokens
+///
+/// Called after every router swap and direct LBP swap. Zero amounts are
+/// ignored.
+pub(crate) fn record_swap_price(
+    env: &Env,
+    token_in: &Option<Address>,
+    token_out: &Option<Address>,
+    amount_in: i128,
+    amount_out: i128,
+) -> Result<(), AmmError> {
+    if amount_in <= 0 || amount_out <= 0 {
+        return Ok(());
+    }
+    let price_in = amount_out
+        .checked_mul(PRICE_SCALE)
+        .ok_or(AmmError::Overflow)?
+        / amount_in;
+    let price_out = amount_in
+        .checked_mul(PRICE_SCALE)
+        .ok_or(AmmError::Overflow)?
+        / amount_out;
+    let (token_0, token_1, flipped) = canonical(token_in, token_out);
+    let (price_0, price_1) = if flipped {
+        (price_out, price_in)
+    } else {
+        (price_in, price_out)
+    };
+
+    let now = env.ledger().timestamp();
+    let acc_key = TwapDataKey::PairAccumulator(token_0.clone(), token_1.clone());
+    let mut acc = match env
+        .storage()
+        .persistent()
+        .get::<TwapDataKey, PairAccumulator>(&acc_key)
+    {
+        Some(acc) => advance(&acc, now),
+        None => PairAccumulator {
+            price_0,
+            price_1,
+            cumulative_0: 0,
+            cumulative_1: 0,
+            last_update: now,
+        },
+    };
+    acc.price_0 = price_0;
+    acc.price_1 = price_1;
+    env.storage().persistent().set(&acc_key, &acc);
+
+    let obs_key = TwapDataKey::PairObservations(token_0, token_1);
+    let mut observations = env
+        .storage()
+        .persistent()
+        .get::<TwapDataKey, Vec<PriceObservation>>(&obs_key)
+        .unwrap_or_else(|| Vec::new(env));
+    let due = match observations.last() {
+        Some(last) => now >= last.timestamp.saturating_add(OBSERVATION_INTERVAL),
+        None => true,
+    };
+    if due {
+        observations.push_back(PriceObservation {
+            timestamp: now,
+            cumulative_0: acc.cumulative_0,
+            cumulative_1: acc.cumulative_1,
+        });
+        if observations.len() > MAX_OBSERVATIONS {
+            observations.pop_front();
+        }
+        env.storage().persistent().set(&obs_key, &observations);
+    }
+    Ok(())
+}
+
+/// Get the stored observations of a pair, oldest first
+pub fn get_price_observations(
+    env: &Env,
+    token_a: &Option<Address>,
+    token_b: &Option<Address>,
+) -> Vec<PriceObservation> {
+    let (token_0, token_1, _) = canonical(token_a, token_b);
+    env.storage()
+        .persistent()
+        .get::<TwapDataKey, Vec<PriceObservation>>(&TwapDataKey::PairObservations(token_0, token_1))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Get the time-weighted average price of `token_a` in `token_b`
+///
+/// # Arguments
+/// * `token_a` - Token to price (None for native XLM)
+/// * `token_b` - Token to price it in (None for native XLM)
+/// * `window` - Minimum averaging period in seconds
+///
+/// # Returns
+/// Units of `token_b` per unit of `token_a`, with 18 decimals
+///
+/// # Errors
+/// * `AmmError::InvalidTwapWindow` - If `window` is zero
+/// * `AmmError::InsufficientObservations` - If the pair has no observation
+///   at least `window` seconds old
+pub fn get_twap(
+    env: &Env,
+    token_a: &Option<Address>,
+    token_b: &Option<Address>,
+    window: u64,
+) -> Result<i128, AmmError> {
+    if window == 0 {
+        return Err(AmmError::InvalidTwapWindow);
+    }
+    let (token_0, token_1, flipped) = canonical(token_a, token_b);
+    let acc = env
+        .storage()
+        .persistent()
+        .get::<TwapDataKey, PairAccumulator>(&TwapDataKey::PairAccumulator(
+            token_0.clone(),
+            token_1.clone(),
+        ))
+        .ok_or(AmmError::InsufficientObservations)?;
+
+    let now = env.ledger().timestamp();
+    let target = now
+        .checked_sub(window)
+        .ok_or(AmmError::InsufficientObservations)?;
+    let observations = get_price_observations(env, &token_0, &token_1);
+    let mut start: Option<PriceObservation> = None;
+    for obs in observations.iter() {
+        if obs.timestamp > target {
+            break;
+        }
+        start = Some(obs);
+    }
+    let start = start.ok_or(AmmError::InsufficientObservations)?;
+
+    let current = advance(&acc, now);
+    let elapsed = (now - start.timestamp) as i128;
+    let delta = if flipped {
+        current.cumulative_1.wrapping_sub(start.cumulative_1)
+    } else {
+        current.cumulative_0.wrapping_sub(start.cumulative_0)
+    };
+    Ok(delta / elapsed)
+}
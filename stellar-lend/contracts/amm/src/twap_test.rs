@@ -0,0 +1,160 @@
+use super::*;
+use crate::amm::ORACLE_FLOOR_TWAP_WINDOW;
+use crate::twap::PRICE_SCALE;
+use soroban_sdk::{testutils::Address as _, testutils::Ledger, Address, Env, Symbol, Vec};
+
+fn setup(env: &Env) -> (AmmContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let contract = AmmContractClient::new(env, &env.register(AmmContract {}, ()));
+    let admin = Address::generate(env);
+    contract.initialize_amm_settings(&admin, &100, &1000, &10000);
+
+    let protocol = Address::generate(env);
+    let token = Address::generate(env);
+    let mut supported_pairs = Vec::new(env);
+    supported_pairs.push_back(TokenPair {
+        token_a: None,
+        token_b: Some(token.clone()),
+        pool_address: Address::generate(env),
+    });
+    contract.add_amm_protocol(
+        &admin,
+        &AmmProtocolConfig {
+            protocol_address: protocol.clone(),
+            protocol_name: Symbol::new(env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1000,
+            max_swap_amount: 1_000_000_000,
+            supported_pairs,
+        },
+    );
+    (contract, protocol, token)
+}
+
+/// Sell 10_000 XLM; the mock protocol pays `10_000 - slippage` bps of it
+fn swap(
+    env: &Env,
+    contract: &AmmContractClient,
+    protocol: &Address,
+    token: &Address,
+    slippage: i128,
+) {
+    let params = SwapParams {
+        protocol: protocol.clone(),
+        token_in: None,
+        token_out: Some(token.clone()),
+        amount_in: 10_000,
+        min_amount_out: 1,
+        slippage_tolerance: slippage,
+        deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
+    };
+    contract.execute_swap(&Address::generate(env), &params);
+}
+
+#[test]
+fn test_twap_weights_prices_by_time() {
+    let env = Env::default();
+    let (contract, protocol, token) = setup(&env);
+    let xlm: Option<Address> = None;
+    let token = Some(token);
+
+    assert_eq!(
+        contract.try_get_twap(&xlm, &token, &600),
+        Err(Ok(AmmError::InsufficientObservations))
+    );
+
+    // 0.99 for 600 seconds, then 0.95 for 600 seconds
+    swap(&env, &contract, &protocol, token.as_ref().unwrap(), 100);
+    env.ledger().with_mut(|li| li.timestamp += 600);
+    swap(&env, &contract, &protocol, token.as_ref().unwrap(), 500);
+    env.ledger().with_mut(|li| li.timestamp += 600);
+
+    assert_eq!(contract.get_price_observations(&xlm, &token).len(), 2);
+    assert_eq!(
+        contract.get_twap(&xlm, &token, &1_200),
+        PRICE_SCALE * 97 / 100
+    );
+    assert_eq!(
+        contract.get_twap(&xlm, &token, &600),
+        PRICE_SCALE * 95 / 100
+    );
+
+    // The reverse direction averages the inverse prices
+    let inverse = (PRICE_SCALE * 10_000 / 9_900 + PRICE_SCALE * 10_000 / 9_500) / 2;
+    assert_eq!(contract.get_twap(&token, &xlm, &1_200), inverse);
+
+    assert_eq!(
+        contract.try_get_twap(&xlm, &token, &0),
+        Err(Ok(AmmError::InvalidTwapWindow))
+    );
+    assert_eq!(
+        contract.try_get_twap(&xlm, &token, &1_201),
+        Err(Ok(AmmError::InsufficientObservations))
+    );
+}
+
+#[test]
+fn test_burst_of_swaps_does_not_move_twap() {
+    let env = Env::default();
+    let (contract, protocol, token) = setup(&env);
+    let xlm: Option<Address> = None;
+    let token = Some(token);
+
+    swap(&env, &contract, &protocol, token.as_ref().unwrap(), 100);
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+
+    // A manipulated price that is reverted within the ledger never counts
+    swap(&env, &contract, &protocol, token.as_ref().unwrap(), 1_000);
+    swap(&env, &contract, &protocol, token.as_ref().unwrap(), 100);
+    assert_eq!(contract.get_price_observations(&xlm, &token).len(), 2);
+    assert_eq!(
+        contract.get_twap(&xlm, &token, &3_600),
+        PRICE_SCALE * 99 / 100
+    );
+}
+
+#[test]
+fn test_oracle_floor_uses_twap() {
+    let env = Env::default();
+    let (contract, protocol, token) = setup(&env);
+    let user = Address::generate(&env);
+    let mut params = SwapParams {
+        protocol: protocol.clone(),
+        token_in: None,
+        token_out: Some(token.clone()),
+        amount_in: 10_000,
+        min_amount_out: 0,
+        slippage_tolerance: 100,
+        deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: true,
+    };
+
+    // No floor can be derived before the pair has a price history
+    assert_eq!(
+        contract.try_execute_swap(&user, &params),
+        Err(Ok(AmmError::InsufficientObservations))
+    );
+
+    swap(&env, &contract, &protocol, &token, 0);
+    env.ledger()
+        .with_mut(|li| li.timestamp += ORACLE_FLOOR_TWAP_WINDOW);
+
+    // Floor is the TWAP value of the input less 1%
+    let quote = contract.quote_swap(&params);
+    assert!(quote.meets_min_amount_out);
+    assert_eq!(contract.execute_swap(&user, &params), 9_900);
+
+    // An explicit minimum above the floor still applies
+    params.min_amount_out = 9_901;
+    assert_eq!(
+        contract.try_execute_swap(&user, &params),
+        Err(Ok(AmmError::MinOutputNotMet))
+    );
+    assert_eq!(
+        oracle_floor(10_000, 0, 100),
+        Err(AmmError::InvalidSwapParams)
+    );
+}
@@ -0,0 +1,152 @@
+//! # StellarLend Debt Token
+//!
+//! Read-only view of the lending pool's variable debt through the Soroban
+//! token interface (SEP-41), so portfolio trackers and other protocols can
+//! read a borrower's debt with the standard `balance` call.
+//!
+//! ## Balances
+//! The token keeps no balances of its own: `balance` returns the borrower's
+//! debt from the pool, principal plus interest. It grows with the pool's
+//! borrow index without any transaction touching the position, and shrinks
+//! only when the debt is repaid or liquidated on the pool. Core positions do
+//! not record which asset was borrowed, so one debt token covers the pool.
+//!
+//! ## Invariants
+//! - Debt cannot be moved: `transfer`, `transfer_from` and `approve` always
+//!   fail and every allowance is 0.
+//! - Debt cannot be burned through the token; it is repaid on the pool.
+//! - Only the pool the token was initialized with is asked for balances.
+
+#![no_std]
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error,
+    token::TokenInterface, Address, Env, MuxedAddress, String,
+};
+
+/// Errors raised by the debt token
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DebtTokenError {
+    /// The token was already initialized
+    AlreadyInitialized = 1,
+    /// The token has not been initialized
+    NotInitialized = 2,
+    /// Debt cannot be transferred, approved or burned
+    NonTransferable = 3,
+}
+
+/// Pool entrypoint the debt token reads balances from
+#[contractclient(name = "DebtPoolClient")]
+pub trait DebtPool {
+    /// Debt of `user` including interest not yet booked
+    fn get_user_debt(env: Env, user: Address) -> i128;
+}
+
+/// Static token configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    /// Lending pool holding the debt positions
+    pub pool: Address,
+    pub decimals: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Storage keys of the debt token
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Token configuration: TokenMetadata
+    Metadata,
+}
+
+fn metadata(env: &Env) -> TokenMetadata {
+    env.storage()
+        .instance()
+        .get(&DataKey::Metadata)
+        .unwrap_or_else(|| panic_with_error!(env, DebtTokenError::NotInitialized))
+}
+
+#[contract]
+pub struct DebtToken;
+
+#[contractimpl]
+impl DebtToken {
+    /// Bind the token to a lending pool
+    ///
+    /// # Arguments
+    /// * `pool` - The lending pool contract
+    /// * `decimals` - Decimals of the debt amounts
+    /// * `name` - Token name
+    /// * `symbol` - Token symbol
+    pub fn initialize(env: Env, pool: Address, decimals: u32, name: String, symbol: String) {
+        if env.storage().instance().has(&DataKey::Metadata) {
+            panic_with_error!(&env, DebtTokenError::AlreadyInitialized);
+        }
+        env.storage().instance().set(
+            &DataKey::Metadata,
+            &TokenMetadata {
+                pool,
+                decimals,
+                name,
+                symbol,
+            },
+        );
+    }
+
+    /// Lending pool the token is bound to
+    pub fn pool(env: Env) -> Address {
+        metadata(&env).pool
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for DebtToken {
+    fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        0
+    }
+
+    fn approve(
+        env: Env,
+        _from: Address,
+        _spender: Address,
+        _amount: i128,
+        _expiration_ledger: u32,
+    ) {
+        panic_with_error!(&env, DebtTokenError::NonTransferable);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        DebtPoolClient::new(&env, &metadata(&env).pool).get_user_debt(&id)
+    }
+
+    fn transfer(env: Env, _from: Address, _to: MuxedAddress, _amount: i128) {
+        panic_with_error!(&env, DebtTokenError::NonTransferable);
+    }
+
+    fn transfer_from(env: Env, _spender: Address, _from: Address, _to: Address, _amount: i128) {
+        panic_with_error!(&env, DebtTokenError::NonTransferable);
+    }
+
+    fn burn(env: Env, _from: Address, _amount: i128) {
+        panic_with_error!(&env, DebtTokenError::NonTransferable);
+    }
+
+    fn burn_from(env: Env, _spender: Address, _from: Address, _amount: i128) {
+        panic_with_error!(&env, DebtTokenError::NonTransferable);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        metadata(&env).decimals
+    }
+
+    fn name(env: Env) -> String {
+        metadata(&env).name
+    }
+
+    fn symbol(env: Env) -> String {
+        metadata(&env).symbol
+    }
+}
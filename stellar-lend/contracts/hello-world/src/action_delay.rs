@@ -0,0 +1,125 @@
+//! # Action Delay Module
+//!
+//! Optional risk control against oracle manipulation financed by flash
+//! loans. While enabled, an account that deposits collateral cannot borrow or
+//! withdraw until `delay_ledgers` ledgers have passed, so a
+//! deposit → borrow → withdraw sequence cannot complete inside the single
+//! transaction a flash loan lives in.
+//!
+//! ## Delay
+//! A deposit at ledger `L` blocks borrows and withdrawals of the credited
+//! account until ledger `L + delay_ledgers`. A delay of 1 only rules out the
+//! same ledger; larger delays also cover multi-ledger manipulation. Repaying,
+//! depositing more and liquidations are never delayed.
+//!
+//! ## Invariants
+//! - `delay_ledgers == 0` disables the control; deposits are only recorded
+//!   while it is enabled.
+//! - Deposits credited on behalf of an account, including those made by the
+//!   protocol itself (e.g. the leverage loop), start its delay too.
+//! - Only the admin can configure the delay.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+use crate::events::{emit_action_delay_updated, ActionDelayUpdatedEvent};
+
+/// Longest delay that can be configured (~1 hour of ledgers)
+pub const MAX_ACTION_DELAY_LEDGERS: u32 = 720;
+
+/// Errors that can occur during action delay operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ActionDelayError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Delay is longer than `MAX_ACTION_DELAY_LEDGERS`
+    InvalidDelay = 2,
+}
+
+/// Storage keys for action delay data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum ActionDelayDataKey {
+    /// Ledgers a deposit blocks borrows and withdrawals for: u32
+    DelayLedgers,
+    /// Ledger of an account's last deposit: u32
+    LastDepositLedger(Address),
+}
+
+/// Configure the delay between a deposit and the next borrow or withdrawal
+/// (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `delay_ledgers` - Delay in ledgers (0 disables the control)
+///
+/// # Errors
+/// * `ActionDelayError::Unauthorized` - If caller is not the admin
+/// * `ActionDelayError::InvalidDelay` - If the delay is too long
+pub fn set_action_delay(
+    env: &Env,
+    caller: Address,
+    delay_ledgers: u32,
+) -> Result<(), ActionDelayError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| ActionDelayError::Unauthorized)?;
+
+    if delay_ledgers > MAX_ACTION_DELAY_LEDGERS {
+        return Err(ActionDelayError::InvalidDelay);
+    }
+    env.storage()
+        .persistent()
+        .set(&ActionDelayDataKey::DelayLedgers, &delay_ledgers);
+
+    emit_action_delay_updated(
+        env,
+        ActionDelayUpdatedEvent {
+            admin: caller,
+            delay_ledgers,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the delay between a deposit and the next borrow or withdrawal in
+/// ledgers (0 = disabled)
+pub fn get_action_delay(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<ActionDelayDataKey, u32>(&ActionDelayDataKey::DelayLedgers)
+        .unwrap_or(0)
+}
+
+/// Get the first ledger at which `user` may borrow or withdraw again
+///
+/// Returns 0 if the user is not delayed.
+pub fn get_action_unlock_ledger(env: &Env, user: &Address) -> u32 {
+    let delay = get_action_delay(env);
+    if delay == 0 {
+        return 0;
+    }
+    env.storage()
+        .persistent()
+        .get::<ActionDelayDataKey, u32>(&ActionDelayDataKey::LastDepositLedger(user.clone()))
+        .map_or(0, |ledger| ledger.saturating_add(delay))
+}
+
+/// Record a deposit credited to `user`
+pub(crate) fn record_deposit(env: &Env, user: &Address) {
+    if get_action_delay(env) == 0 {
+        return;
+    }
+    env.storage().persistent().set(
+        &ActionDelayDataKey::LastDepositLedger(user.clone()),
+        &env.ledger().sequence(),
+    );
+}
+
+/// Whether `user` is still inside the delay of their last deposit
+pub(crate) fn is_delayed(env: &Env, user: &Address) -> bool {
+    env.ledger().sequence() < get_action_unlock_ledger(env, user)
+}
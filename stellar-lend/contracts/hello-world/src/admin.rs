@@ -6,10 +6,20 @@
 //! ## Features
 //! - **Super Admin**: A single address with ultimate authority over the protocol.
 //! - **Roles**: Optional multi-admin functionality via specific roles (e.g., "oracle_admin").
+//!   Two roles split the admin's day-to-day powers:
+//!   - `guardian` can pause operations, but only the admin can unpause them
+//!   - `risk_manager` can tune reserve, risk and interest rate parameters and
+//!     AMM pools, within the same bounds the admin is held to
 //! - **Events**: Emits events for critical admin actions (admin changes, role grants/revocations).
 
 use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Symbol, Val, Vec};
 
+/// Role allowed to pause operations
+pub const GUARDIAN_ROLE: &str = "guardian";
+
+/// Role allowed to tune reserve, risk and interest rate parameters
+pub const RISK_MANAGER_ROLE: &str = "risk_manager";
+
 /// Errors that can occur during admin operations
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -32,6 +42,18 @@ pub enum AdminDataKey {
     Admin,
     /// Specific role assigned to an address: Role(RoleName, Address) -> bool
     Role(Symbol, Address),
+    /// Every current role assignment: Vec<RoleGrant>
+    RoleGrants,
+}
+
+/// A role assigned to an address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrant {
+    /// Role name
+    pub role: Symbol,
+    /// Address holding the role
+    pub account: Address,
 }
 
 /// Check if the super admin is set
@@ -101,6 +123,18 @@ pub fn grant_role(
     let key = AdminDataKey::Role(role.clone(), account.clone());
     env.storage().persistent().set(&key, &true);
 
+    let grant = RoleGrant {
+        role: role.clone(),
+        account: account.clone(),
+    };
+    let mut grants = get_role_grants(env);
+    if !grants.contains(&grant) {
+        grants.push_back(grant);
+        env.storage()
+            .persistent()
+            .set(&AdminDataKey::RoleGrants, &grants);
+    }
+
     // Emit event
     let topics = (
         Symbol::new(env, "role_granted"),
@@ -128,6 +162,18 @@ pub fn revoke_role(
     let key = AdminDataKey::Role(role.clone(), account.clone());
     env.storage().persistent().remove(&key);
 
+    let grant = RoleGrant {
+        role: role.clone(),
+        account: account.clone(),
+    };
+    let mut grants = get_role_grants(env);
+    if let Some(index) = grants.first_index_of(&grant) {
+        grants.remove(index);
+        env.storage()
+            .persistent()
+            .set(&AdminDataKey::RoleGrants, &grants);
+    }
+
     // Emit event
     let topics = (
         Symbol::new(env, "role_revoked"),
@@ -143,6 +189,14 @@ pub fn revoke_role(
     Ok(())
 }
 
+/// Get every current role assignment
+pub fn get_role_grants(env: &Env) -> Vec<RoleGrant> {
+    env.storage()
+        .persistent()
+        .get::<AdminDataKey, Vec<RoleGrant>>(&AdminDataKey::RoleGrants)
+        .unwrap_or(Vec::new(env))
+}
+
 /// Check if an address has a specific role
 #[allow(dead_code)]
 pub fn has_role(env: &Env, role: Symbol, account: Address) -> bool {
@@ -167,3 +221,13 @@ pub fn require_role_or_admin(
 
     Err(AdminError::Unauthorized)
 }
+
+/// Require that the caller is the super admin or a guardian
+pub fn require_guardian(env: &Env, caller: &Address) -> Result<(), AdminError> {
+    require_role_or_admin(env, caller, Symbol::new(env, GUARDIAN_ROLE))
+}
+
+/// Require that the caller is the super admin or a risk manager
+pub fn require_risk_manager(env: &Env, caller: &Address) -> Result<(), AdminError> {
+    require_role_or_admin(env, caller, Symbol::new(env, RISK_MANAGER_ROLE))
+}
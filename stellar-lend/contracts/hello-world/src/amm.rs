@@ -1,12 +1,12 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Symbol};
 use stellarlend_amm::{
     AmmError, AmmProtocolConfig, LiquidityParams, SwapParams,
 };
 
-/// Set AMM pool configuration (admin only)
+/// Set AMM pool configuration (admin or risk manager)
 pub fn set_amm_pool(
     env: Env,
-    admin: Address,
+    caller: Address,
     protocol_config: AmmProtocolConfig,
 ) -> Result<(), AmmError> {
     // In a real scenario, this would call the deployed AMM contract.
@@ -14,13 +14,95 @@ pub fn set_amm_pool(
     // However, to make it truly integrated as a wrapper, we might want to store the state here
     // or call another contract. 
     // For this implementation, we will use the library functions from stellarlend_amm.
-    
+    caller.require_auth();
+    crate::admin::require_risk_manager(&env, &caller).map_err(|_| AmmError::Unauthorized)?;
+
+    // The library checks against the admin it shares storage with
+    let admin = crate::admin::get_admin(&env).ok_or(AmmError::Unauthorized)?;
     stellarlend_amm::add_amm_protocol(&env, admin, protocol_config)
 }
 
+/// Override the AMM slippage cap of a token pair (admin only)
+pub fn set_amm_pair_slippage(
+    env: Env,
+    admin: Address,
+    token_a: Option<Address>,
+    token_b: Option<Address>,
+    max_slippage: Option<i128>,
+) -> Result<(), AmmError> {
+    stellarlend_amm::amm::set_pair_max_slippage(&env, admin, token_a, token_b, max_slippage)
+}
+
 /// Execute swap through AMM
-pub fn amm_swap(env: Env, user: Address, params: SwapParams) -> Result<i128, AmmError> {
-    stellarlend_amm::execute_swap(&env, user, params)
+///
+/// The protocol fee, if any, is taken from the input first (see `treasury`).
+/// Rejected while swaps are paused or either token is frozen. Swaps asking
+/// for an oracle floor must return at least the input's value at lending
+/// oracle prices, less their slippage tolerance (see `apply_oracle_floor`).
+pub fn amm_swap(env: Env, user: Address, mut params: SwapParams) -> Result<i128, AmmError> {
+    if crate::risk_management::check_operation_paused(&env, Symbol::new(&env, "pause_swap"))
+        || crate::risk_management::is_asset_frozen(&env, &params.token_in)
+        || crate::risk_management::is_asset_frozen(&env, &params.token_out)
+    {
+        return Err(AmmError::SwapPaused);
+    }
+    let fee = crate::treasury::amm_fee(&env, &params);
+    params.amount_in -= fee;
+    let params = apply_oracle_floor(&env, params)?;
+    let token_in = params.token_in.clone();
+    let amount_out = stellarlend_amm::execute_swap(&env, user.clone(), params)?;
+    crate::treasury::collect_amm_fee(&env, &user, &token_in, fee);
+    Ok(amount_out)
+}
+
+/// Raise `min_amount_out` to the oracle value of the input less slippage
+///
+/// Resolves the floor from the lending oracle so the router does not fall
+/// back to its own TWAP.
+fn apply_oracle_floor(env: &Env, mut params: SwapParams) -> Result<SwapParams, AmmError> {
+    if !params.use_oracle_floor {
+        return Ok(params);
+    }
+    let price_in = crate::liquidate::get_asset_price(env, &params.token_in)
+        .map_err(|_| AmmError::InvalidSwapParams)?;
+    let price_out = crate::liquidate::get_asset_price(env, &params.token_out)
+        .map_err(|_| AmmError::InvalidSwapParams)?;
+    if price_out <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    let price = price_in
+        .checked_mul(1_000_000_000_000_000_000)
+        .ok_or(AmmError::Overflow)?
+        / price_out;
+
+    let floor = stellarlend_amm::oracle_floor(params.amount_in, price, params.slippage_tolerance)?;
+    params.min_amount_out = params.min_amount_out.max(floor);
+    params.use_oracle_floor = false;
+    Ok(params)
+}
+
+/// Create a native constant-product pool (AMM admin only)
+///
+/// Reserves are tracked as hosted by the AMM module, which keeps them out of
+/// `reserve::available_liquidity`.
+pub fn amm_create_pool(
+    env: Env,
+    admin: Address,
+    token_a: Address,
+    token_b: Address,
+    amount_a: i128,
+    amount_b: i128,
+    swap_fee_bps: i128,
+) -> Result<u32, AmmError> {
+    stellarlend_amm::pools::create_pool(
+        &env,
+        admin,
+        token_a,
+        token_b,
+        amount_a,
+        amount_b,
+        swap_fee_bps,
+    )
 }
 
 /// Add liquidity to AMM pool
@@ -73,3 +155,27 @@ pub fn initialize_amm(
         auto_swap_threshold,
     )
 }
+
+/// Get the time-weighted average price of `token_a` in `token_b` (scaled by 10^18)
+pub fn amm_get_twap(
+    env: Env,
+    token_a: Option<Address>,
+    token_b: Option<Address>,
+    window: u64,
+) -> Result<i128, AmmError> {
+    stellarlend_amm::get_twap(&env, &token_a, &token_b, window)
+}
+
+/// Set the AMM slippage tolerances once a timelocked change is executed,
+/// keeping the other settings
+pub(crate) fn set_amm_slippage(
+    env: &Env,
+    default_slippage: i128,
+    max_slippage: i128,
+) -> Result<(), AmmError> {
+    let admin = crate::admin::get_admin(env).ok_or(AmmError::Unauthorized)?;
+    let mut settings = stellarlend_amm::amm::get_amm_settings(env)?;
+    settings.default_slippage = default_slippage;
+    settings.max_slippage = max_slippage;
+    stellarlend_amm::update_amm_settings(env, admin, settings)
+}
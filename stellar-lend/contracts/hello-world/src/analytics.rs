@@ -6,6 +6,9 @@
 //! - **Protocol metrics**: TVL, utilization, average borrow rate, total users/transactions
 //! - **User metrics**: collateral, debt, health factor, risk level, activity score
 //! - **Activity feed**: bounded log of recent protocol operations (max 10,000 entries)
+//! - **Account data**: collateral, debt, borrowing headroom and health factor in one read
+//! - **Market snapshots**: per-asset supplied, borrowed and reserve totals with rates
+//! - **Protocol overview**: valued TVL, debt and deficit with the pause state in one read
 //!
 //! ## Health Factor
 //! `health_factor = (collateral * 10000) / debt`
@@ -157,6 +160,67 @@ pub struct UserReport {
     pub timestamp: u64,
 }
 
+/// Borrowing and liquidation figures of a user's core position.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountData {
+    /// Collateral including settled and unsettled supply interest
+    pub total_collateral: i128,
+    /// Debt including accrued and pending interest
+    pub total_debt: i128,
+    /// Largest amount the user can borrow now
+    pub available_borrows: i128,
+    /// Share of the collateral counted against debt at liquidation (bps)
+    pub liquidation_threshold: i128,
+    /// Health factor (scaled by 10000, `i128::MAX` without debt); the
+    /// position can be liquidated below 10000
+    pub health_factor: i128,
+}
+
+/// Protocol-wide totals for dashboards.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtocolOverview {
+    /// Value of all supplied collateral in the oracle's base currency
+    pub total_value_locked: i128,
+    /// Value of all borrowed principal in the oracle's base currency
+    pub total_debt: i128,
+    /// Number of assets with a non-zero supply or borrow
+    pub active_reserves: u32,
+    /// Whether the emergency pause is active
+    pub paused: bool,
+    /// Value of the outstanding bad debt in the oracle's base currency
+    pub deficit: i128,
+    /// Overview timestamp
+    pub timestamp: u64,
+}
+
+/// Per-asset market totals and rates.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketSnapshot {
+    /// Asset the snapshot is for (None for native asset)
+    pub asset: Option<Address>,
+    /// Collateral principal supplied in the asset
+    pub total_supplied: i128,
+    /// Debt principal borrowed in the asset
+    pub total_borrowed: i128,
+    /// Protocol reserves accrued in the asset
+    pub total_reserves: i128,
+    /// Borrowed share of the supplied amount in basis points
+    pub utilization_rate: i128,
+    /// Annual borrow rate in basis points
+    pub borrow_rate: i128,
+    /// Annual supply rate in basis points
+    pub supply_rate: i128,
+    /// Supply cap of the asset (0 = unlimited)
+    pub supply_cap: i128,
+    /// Borrow cap of the asset (0 = unlimited)
+    pub borrow_cap: i128,
+    /// Snapshot timestamp
+    pub timestamp: u64,
+}
+
 const BASIS_POINTS: i128 = 10_000;
 const MAX_ACTIVITY_LOG_SIZE: u32 = 10_000;
 
@@ -321,6 +385,7 @@ pub fn get_protocol_stats(env: &Env) -> Result<ProtocolMetrics, AnalyticsError>
 /// # Errors
 /// Returns `AnalyticsError::DataNotFound` if the user has no position.
 pub fn get_user_position_summary(env: &Env, user: &Address) -> Result<Position, AnalyticsError> {
+    crate::storage_ttl::extend_position_ttl(env, user);
     let position = env
         .storage()
         .persistent()
@@ -679,3 +744,119 @@ pub fn generate_user_report(env: &Env, user: &Address) -> Result<UserReport, Ana
 
     Ok(report)
 }
+
+/// Get the borrowing and liquidation figures of a user's core position.
+///
+/// Combines the supply balance, the debt, the borrowing headroom of
+/// `borrow::get_max_borrowable` and the health factor liquidations are
+/// decided on. Core collateral is fungible, so the threshold is the native
+/// asset's liquidation threshold or, without one, the share implied by the
+/// global liquidation ratio, and `health_factor = collateral * threshold /
+/// debt` up to the rounding of the threshold.
+///
+/// # Arguments
+/// * `user` - The user's address
+///
+/// # Returns
+/// The user's `AccountData`; all zero and an infinite health factor
+/// without a position.
+///
+/// # Errors
+/// * `AnalyticsError::Overflow` - If the health factor overflows
+pub fn get_account_data(env: &Env, user: &Address) -> Result<AccountData, AnalyticsError> {
+    let total_collateral = crate::supply_interest::get_user_supply_balance(env, user);
+    let total_debt = crate::interest_index::get_user_debt(env, user);
+
+    let mut liquidation_threshold = crate::reserve::get_liquidation_threshold(env, &None);
+    if liquidation_threshold == 0 {
+        let ratio = crate::risk_params::get_risk_params(env)
+            .map(|params| params.liquidation_threshold)
+            .unwrap_or(BASIS_POINTS);
+        liquidation_threshold = (BASIS_POINTS * BASIS_POINTS)
+            .checked_div(ratio)
+            .ok_or(AnalyticsError::Overflow)?;
+    }
+    let health_factor = crate::liquidate::health_factor(env, &None, total_collateral, total_debt)
+        .map_err(|_| AnalyticsError::Overflow)?;
+
+    Ok(AccountData {
+        total_collateral,
+        total_debt,
+        available_borrows: crate::borrow::get_max_borrowable(env, user, None),
+        liquidation_threshold,
+        health_factor,
+    })
+}
+
+/// Get the protocol-wide totals in a single read.
+///
+/// Sums the per-asset supplied and borrowed totals of every reserve asset,
+/// each valued with its latest oracle price; assets without a price feed
+/// count at face value. The deficit is the total shortfall of the
+/// `bad_debt` module.
+///
+/// # Returns
+/// A `ProtocolOverview` at the current timestamp.
+pub fn get_protocol_overview(env: &Env) -> ProtocolOverview {
+    let mut total_value_locked: i128 = 0;
+    let mut total_debt: i128 = 0;
+    let mut active_reserves: u32 = 0;
+    for asset in crate::reserve::get_reserve_assets(env).iter() {
+        let supplied = crate::reserve::get_total_supplied(env, &asset);
+        let borrowed = crate::reserve::get_total_borrowed(env, &asset);
+        if supplied > 0 || borrowed > 0 {
+            active_reserves += 1;
+        }
+        total_value_locked =
+            total_value_locked.saturating_add(crate::bad_debt::value_of(env, &asset, supplied));
+        total_debt = total_debt.saturating_add(crate::bad_debt::value_of(env, &asset, borrowed));
+    }
+
+    ProtocolOverview {
+        total_value_locked,
+        total_debt,
+        active_reserves,
+        paused: crate::risk_management::is_emergency_paused(env),
+        deficit: crate::bad_debt::get_total_shortfall(env),
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+/// Get the totals and rates of a single asset's market.
+///
+/// Totals are the per-asset balances kept by the `reserve` module, updated
+/// by every deposit, withdrawal, borrow, repayment and liquidation. Core
+/// reserves share the pool's interest rate curve, so the rates are those of
+/// the pool.
+///
+/// # Arguments
+/// * `asset` - The asset address (None for native asset)
+///
+/// # Returns
+/// A `MarketSnapshot` of the asset at the current timestamp.
+pub fn get_market_snapshot(env: &Env, asset: &Option<Address>) -> MarketSnapshot {
+    let total_supplied = crate::reserve::get_total_supplied(env, asset);
+    let total_borrowed = crate::reserve::get_total_borrowed(env, asset);
+    let utilization_rate = if total_supplied > 0 {
+        total_borrowed
+            .saturating_mul(BASIS_POINTS)
+            .checked_div(total_supplied)
+            .unwrap_or(0)
+            .min(BASIS_POINTS)
+    } else {
+        0
+    };
+
+    MarketSnapshot {
+        asset: asset.clone(),
+        total_supplied,
+        total_borrowed,
+        total_reserves: crate::reserve::get_reserve_balance(env, asset.clone()),
+        utilization_rate,
+        borrow_rate: crate::interest_rate::calculate_borrow_rate(env).unwrap_or(0),
+        supply_rate: crate::interest_rate::calculate_supply_rate(env).unwrap_or(0),
+        supply_cap: crate::reserve::get_supply_cap(env, asset),
+        borrow_cap: crate::reserve::get_borrow_cap(env, asset),
+        timestamp: env.ledger().timestamp(),
+    }
+}
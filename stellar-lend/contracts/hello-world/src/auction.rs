@@ -0,0 +1,576 @@
+//! # Liquidation Auction Module
+//!
+//! Dutch-auction alternative to fixed-bonus liquidation. Instead of paying the
+//! full liquidation incentive to whoever liquidates first, the collateral
+//! backing a liquidatable debt is sold at a price that falls over time, so
+//! liquidators compete on price and the borrower keeps whatever part of the
+//! incentive is not needed to clear the debt.
+//!
+//! ## Flow
+//! 1. `start_auction` — anyone can start an auction on a position that
+//!    `liquidate` would accept. The tab is the debt repayable under the close
+//!    factor; the lot is the collateral a fixed-bonus liquidation of the tab
+//!    would seize. The lot is moved out of the borrower's collateral.
+//! 2. `bid` — bidders repay part or all of the remaining tab and receive
+//!    collateral at the current price. The price starts at the oracle price
+//!    (no bonus) and falls linearly until, at the end of the auction, the
+//!    whole lot goes for the whole tab (the full liquidation incentive).
+//! 3. `settle_auction` — once the tab is repaid, the lot is sold out or the
+//!    auction has run its duration, unsold collateral is returned to the
+//!    borrower.
+//!
+//! Insured positions and liquidation grace windows are handled as in
+//! `liquidate`: starting an auction that the insurance fund rescues, or that
+//! opens a grace window, returns `None` instead of an auction.
+//!
+//! ## Invariants
+//! - A borrower has at most one auction at a time.
+//! - Collateral sold never exceeds the lot and debt repaid never exceeds the
+//!   tab or the borrower's debt.
+//! - Bids are only accepted until the auction ends; afterwards it can only be
+//!   settled.
+//! - Only the admin can change the auction duration.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{
+    emit_auction_bid, emit_auction_settled, emit_auction_started, AuctionBidEvent,
+    AuctionSettledEvent, AuctionStartedEvent,
+};
+use crate::liquidate::LiquidationError;
+use crate::risk_management::{is_emergency_paused, require_operation_not_paused};
+
+/// Default auction duration (1 hour)
+pub const DEFAULT_AUCTION_DURATION: u64 = 3_600;
+
+/// Longest auction duration that can be configured (1 day)
+pub const MAX_AUCTION_DURATION: u64 = 86_400;
+
+/// Errors that can occur during liquidation auction operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AuctionError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Bid amount must be greater than zero
+    InvalidAmount = 2,
+    /// Asset address is invalid
+    InvalidAsset = 3,
+    /// Auction duration is zero or too long
+    InvalidDuration = 4,
+    /// Liquidations are currently paused
+    LiquidationPaused = 5,
+    /// Position is not undercollateralized
+    NotLiquidatable = 6,
+    /// Borrower already has an auction running
+    AuctionActive = 7,
+    /// Borrower has no auction
+    AuctionNotFound = 8,
+    /// Auction has ended and only accepts settlement
+    AuctionExpired = 9,
+    /// Auction still has collateral for sale and time left
+    AuctionNotFinished = 10,
+    /// Borrower is inside a liquidation grace or recovery window
+    GracePeriodActive = 11,
+    /// Price of an involved asset is older than its `max_price_age`
+    StalePrice = 12,
+    /// Price not available for asset
+    PriceNotAvailable = 13,
+    /// Overflow occurred during calculation
+    Overflow = 14,
+}
+
+/// Storage keys for liquidation auction data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum AuctionDataKey {
+    /// Running auction of a borrower: LiquidationAuction
+    LiquidationAuction(Address),
+    /// Duration of new auctions in seconds: u64
+    AuctionDuration,
+}
+
+/// Dutch auction of a borrower's collateral
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationAuction {
+    /// The borrower being liquidated
+    pub borrower: Address,
+    /// Asset bidders repay (None for native XLM)
+    pub debt_asset: Option<Address>,
+    /// Asset bidders receive (None for native XLM)
+    pub collateral_asset: Option<Address>,
+    /// Debt offered for repayment when the auction started
+    pub tab: i128,
+    /// Collateral worth `tab` at the oracle price when the auction started
+    pub base_collateral: i128,
+    /// Collateral locked when the auction started
+    pub lot: i128,
+    /// Debt still open for repayment
+    pub tab_remaining: i128,
+    /// Collateral still for sale
+    pub lot_remaining: i128,
+    /// Timestamp the auction started at
+    pub started_at: u64,
+    /// Timestamp after which bids are rejected
+    pub ends_at: u64,
+}
+
+fn map_liquidation_error(error: LiquidationError) -> AuctionError {
+    match error {
+        LiquidationError::StalePrice => AuctionError::StalePrice,
+        LiquidationError::PriceNotAvailable => AuctionError::PriceNotAvailable,
+        LiquidationError::NotLiquidatable => AuctionError::NotLiquidatable,
+        _ => AuctionError::Overflow,
+    }
+}
+
+fn require_liquidations_enabled(env: &Env) -> Result<(), AuctionError> {
+    if is_emergency_paused(env) {
+        return Err(AuctionError::LiquidationPaused);
+    }
+    require_operation_not_paused(env, Symbol::new(env, "pause_liquidate"))
+        .map_err(|_| AuctionError::LiquidationPaused)
+}
+
+/// Load a borrower's position with interest accrued up to now
+fn load_position(env: &Env, borrower: &Address) -> Result<Position, AuctionError> {
+    let mut position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(borrower.clone()))
+        .ok_or(AuctionError::NotLiquidatable)?;
+    if position.debt == 0 {
+        position.borrow_interest = 0;
+    }
+    crate::interest_index::accrue_position(env, borrower, &mut position)
+        .map_err(|_| AuctionError::Overflow)?;
+    Ok(position)
+}
+
+fn save_auction(env: &Env, auction: &LiquidationAuction) {
+    env.storage().persistent().set(
+        &AuctionDataKey::LiquidationAuction(auction.borrower.clone()),
+        auction,
+    );
+}
+
+/// Get the duration of new auctions in seconds
+pub fn get_auction_duration(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<AuctionDataKey, u64>(&AuctionDataKey::AuctionDuration)
+        .unwrap_or(DEFAULT_AUCTION_DURATION)
+}
+
+/// Set the duration of new auctions (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `duration` - Auction duration in seconds
+///
+/// # Errors
+/// * `AuctionError::Unauthorized` - If caller is not the admin
+/// * `AuctionError::InvalidDuration` - If `duration` is zero or above
+///   `MAX_AUCTION_DURATION`
+pub fn set_auction_duration(env: &Env, caller: Address, duration: u64) -> Result<(), AuctionError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| AuctionError::Unauthorized)?;
+    if duration == 0 || duration > MAX_AUCTION_DURATION {
+        return Err(AuctionError::InvalidDuration);
+    }
+    env.storage()
+        .persistent()
+        .set(&AuctionDataKey::AuctionDuration, &duration);
+    Ok(())
+}
+
+/// Get the running auction of a borrower
+pub fn get_auction(env: &Env, borrower: &Address) -> Option<LiquidationAuction> {
+    env.storage()
+        .persistent()
+        .get::<AuctionDataKey, LiquidationAuction>(&AuctionDataKey::LiquidationAuction(
+            borrower.clone(),
+        ))
+}
+
+/// Collateral paid for repaying `debt_amount` of an auction's tab at `now`
+///
+/// The collateral per unit of debt rises linearly from `base_collateral / tab`
+/// at the start to `lot / tab` at the end, capped by the collateral left.
+fn collateral_for(
+    auction: &LiquidationAuction,
+    debt_amount: i128,
+    now: u64,
+) -> Result<i128, AuctionError> {
+    let duration = (auction.ends_at - auction.started_at) as i128;
+    let elapsed = (now.min(auction.ends_at) - auction.started_at) as i128;
+    let bonus = auction
+        .lot
+        .checked_sub(auction.base_collateral)
+        .and_then(|spread| spread.checked_mul(elapsed))
+        .ok_or(AuctionError::Overflow)?
+        / duration;
+    let collateral = debt_amount
+        .checked_mul(auction.base_collateral + bonus)
+        .ok_or(AuctionError::Overflow)?
+        / auction.tab;
+    Ok(collateral.min(auction.lot_remaining))
+}
+
+/// Get the collateral a bid repaying `debt_amount` would receive now
+///
+/// # Errors
+/// * `AuctionError::AuctionNotFound` - If the borrower has no auction
+/// * `AuctionError::AuctionExpired` - If the auction has ended
+pub fn quote_bid(env: &Env, borrower: &Address, debt_amount: i128) -> Result<i128, AuctionError> {
+    let auction = get_auction(env, borrower).ok_or(AuctionError::AuctionNotFound)?;
+    let now = env.ledger().timestamp();
+    if now >= auction.ends_at {
+        return Err(AuctionError::AuctionExpired);
+    }
+    collateral_for(&auction, debt_amount.min(auction.tab_remaining), now)
+}
+
+/// Start a Dutch auction of a liquidatable borrower's collateral
+///
+/// # Arguments
+/// * `borrower` - The borrower to liquidate
+/// * `debt_asset` - The asset bidders repay (None for native XLM)
+/// * `collateral_asset` - The asset bidders receive (None for native XLM)
+///
+/// # Returns
+/// The auction, or None if the insurance fund rescued the position or a
+/// liquidation grace window was started instead
+///
+/// # Errors
+/// * `AuctionError::LiquidationPaused` - If liquidations are paused
+/// * `AuctionError::AuctionActive` - If the borrower already has an auction
+/// * `AuctionError::NotLiquidatable` - If the position is healthy
+/// * `AuctionError::GracePeriodActive` - If a grace or recovery window is running
+/// * `AuctionError::StalePrice` - If a price is older than its `max_price_age`
+pub fn start_auction(
+    env: &Env,
+    borrower: Address,
+    debt_asset: Option<Address>,
+    collateral_asset: Option<Address>,
+) -> Result<Option<LiquidationAuction>, AuctionError> {
+    require_liquidations_enabled(env)?;
+    for asset in [&debt_asset, &collateral_asset].into_iter().flatten() {
+        if asset == &env.current_contract_address() {
+            return Err(AuctionError::InvalidAsset);
+        }
+    }
+    if get_auction(env, &borrower).is_some() {
+        return Err(AuctionError::AuctionActive);
+    }
+    if crate::liquidation_grace::in_recovery_grace(env, &debt_asset, &collateral_asset) {
+        return Err(AuctionError::GracePeriodActive);
+    }
+
+    crate::supply_interest::settle_supply_interest(env, &borrower);
+    let mut position = load_position(env, &borrower)?;
+    crate::rate_stats::record_accrual(env, &debt_asset);
+    crate::liquidation_insurance::accrue_premium(env, &borrower, &mut position)
+        .map_err(|_| AuctionError::Overflow)?;
+    let position_key = DepositDataKey::Position(borrower.clone());
+
+    let collateral_key = DepositDataKey::CollateralBalance(borrower.clone());
+    let collateral_balance = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&collateral_key)
+        .unwrap_or(0);
+
+    // Value the collateral in debt terms, as `liquidate` does
+    let (debt_price, collateral_price) = if debt_asset.is_none() && collateral_asset.is_none() {
+        (1, 1)
+    } else {
+        (
+            crate::liquidate::get_asset_price(env, &debt_asset).map_err(map_liquidation_error)?,
+            crate::liquidate::get_asset_price(env, &collateral_asset)
+                .map_err(map_liquidation_error)?,
+        )
+    };
+    let collateral_value = crate::liquidate::calculate_collateral_value(
+        collateral_balance,
+        collateral_price,
+        debt_price,
+    )
+    .map_err(map_liquidation_error)?;
+    let mut total_debt = position
+        .debt
+        .checked_add(position.borrow_interest)
+        .ok_or(AuctionError::Overflow)?;
+    if !crate::liquidate::is_liquidatable(env, &collateral_asset, collateral_value, total_debt)
+        .map_err(map_liquidation_error)?
+    {
+        return Err(AuctionError::NotLiquidatable);
+    }
+
+    // Insured positions are rescued by the insurance fund first
+    let covered = crate::liquidation_insurance::apply_protection(
+        env,
+        &borrower,
+        &mut position,
+        &debt_asset,
+        collateral_value,
+    )
+    .map_err(|_| AuctionError::Overflow)?;
+    if covered > 0 {
+        total_debt = position
+            .debt
+            .checked_add(position.borrow_interest)
+            .ok_or(AuctionError::Overflow)?;
+        if !crate::liquidate::is_liquidatable(env, &collateral_asset, collateral_value, total_debt)
+            .map_err(map_liquidation_error)?
+        {
+            env.storage().persistent().set(&position_key, &position);
+            return Ok(None);
+        }
+    }
+
+    match crate::liquidation_grace::check_grace(env, &borrower, &debt_asset, total_debt) {
+        crate::liquidation_grace::GraceStatus::Started => {
+            env.storage().persistent().set(&position_key, &position);
+            return Ok(None);
+        }
+        crate::liquidation_grace::GraceStatus::Active => {
+            return Err(AuctionError::GracePeriodActive);
+        }
+        crate::liquidation_grace::GraceStatus::Expired => {}
+    }
+
+    // Tab under the close factor; lot as a fixed-bonus liquidation would seize
+    let health =
+        crate::liquidate::health_factor(env, &collateral_asset, collateral_value, total_debt)
+            .map_err(map_liquidation_error)?;
+    let close_factor = crate::reserve::effective_close_factor(env, &debt_asset, health);
+    let tab = total_debt
+        .checked_mul(close_factor)
+        .ok_or(AuctionError::Overflow)?
+        / 10000;
+    if tab <= 0 {
+        return Err(AuctionError::NotLiquidatable);
+    }
+    let incentive = crate::risk_params::get_liquidation_incentive(env).unwrap_or(1000);
+    let base_collateral =
+        crate::liquidate::calculate_collateral_value(tab, debt_price, collateral_price)
+            .map_err(map_liquidation_error)?;
+    let lot = base_collateral
+        .checked_mul(10000 + incentive)
+        .ok_or(AuctionError::Overflow)?
+        / 10000;
+    let lot = lot.min(collateral_balance);
+    let base_collateral = base_collateral.min(lot);
+    if lot <= 0 {
+        return Err(AuctionError::NotLiquidatable);
+    }
+
+    // Lock the lot
+    let new_collateral_balance = collateral_balance - lot;
+    env.storage()
+        .persistent()
+        .set(&collateral_key, &new_collateral_balance);
+    position.collateral = new_collateral_balance;
+    env.storage().persistent().set(&position_key, &position);
+
+    let now = env.ledger().timestamp();
+    let auction = LiquidationAuction {
+        borrower: borrower.clone(),
+        debt_asset: debt_asset.clone(),
+        collateral_asset: collateral_asset.clone(),
+        tab,
+        base_collateral,
+        lot,
+        tab_remaining: tab,
+        lot_remaining: lot,
+        started_at: now,
+        ends_at: now.saturating_add(get_auction_duration(env)),
+    };
+    save_auction(env, &auction);
+
+    emit_auction_started(
+        env,
+        AuctionStartedEvent {
+            borrower,
+            debt_asset,
+            collateral_asset,
+            tab,
+            lot,
+            ends_at: auction.ends_at,
+            timestamp: now,
+        },
+    );
+    Ok(Some(auction))
+}
+
+/// Repay part of an auction's tab in exchange for collateral at the current price
+///
+/// # Arguments
+/// * `bidder` - The bidder, who must have approved the debt asset
+/// * `borrower` - The borrower whose auction is bid on
+/// * `debt_amount` - Debt to repay; capped at the remaining tab and the
+///   borrower's debt
+///
+/// # Returns
+/// The collateral paid to the bidder
+///
+/// # Errors
+/// * `AuctionError::InvalidAmount` - If `debt_amount` is not positive
+/// * `AuctionError::AuctionNotFound` - If the borrower has no auction
+/// * `AuctionError::AuctionExpired` - If the auction has ended
+/// * `AuctionError::LiquidationPaused` - If liquidations are paused
+pub fn bid(
+    env: &Env,
+    bidder: Address,
+    borrower: Address,
+    debt_amount: i128,
+) -> Result<i128, AuctionError> {
+    bidder.require_auth();
+    if debt_amount <= 0 {
+        return Err(AuctionError::InvalidAmount);
+    }
+    require_liquidations_enabled(env)?;
+    let mut auction = get_auction(env, &borrower).ok_or(AuctionError::AuctionNotFound)?;
+    let now = env.ledger().timestamp();
+    if now >= auction.ends_at {
+        return Err(AuctionError::AuctionExpired);
+    }
+
+    let mut position = load_position(env, &borrower)?;
+    crate::rate_stats::record_accrual(env, &auction.debt_asset);
+    let total_debt = position
+        .debt
+        .checked_add(position.borrow_interest)
+        .ok_or(AuctionError::Overflow)?;
+    let repaid = debt_amount.min(auction.tab_remaining).min(total_debt);
+    if repaid <= 0 {
+        return Err(AuctionError::InvalidAmount);
+    }
+    let collateral_out = collateral_for(&auction, repaid, now)?;
+
+    if let Some(ref debt_addr) = auction.debt_asset {
+        soroban_sdk::token::Client::new(env, debt_addr).transfer_from(
+            &env.current_contract_address(),
+            &bidder,
+            &env.current_contract_address(),
+            &repaid,
+        );
+    }
+    if let Some(ref collateral_addr) = auction.collateral_asset {
+        soroban_sdk::token::Client::new(env, collateral_addr).transfer(
+            &env.current_contract_address(),
+            &bidder,
+            &collateral_out,
+        );
+    }
+
+    // Pay interest first, then principal
+    let interest_paid = repaid.min(position.borrow_interest);
+    let principal_paid = repaid - interest_paid;
+    position.borrow_interest -= interest_paid;
+    position.debt = position.debt.checked_sub(principal_paid).unwrap_or(0);
+    position.last_accrual_time = now;
+    env.storage()
+        .persistent()
+        .set(&DepositDataKey::Position(borrower.clone()), &position);
+    crate::reserve::record_borrow_change(env, &auction.debt_asset, -principal_paid);
+    crate::liquidation_insurance::collect_premium(
+        env,
+        &borrower,
+        &auction.debt_asset,
+        interest_paid,
+    )
+    .map_err(|_| AuctionError::Overflow)?;
+    crate::reserve::record_supply_change(env, &auction.collateral_asset, -collateral_out);
+    crate::reserve::clear_debt_assets_if_repaid(env, &borrower, &position);
+    crate::liquidation_index::sync_borrower(env, &borrower);
+
+    auction.tab_remaining -= repaid;
+    auction.lot_remaining -= collateral_out;
+    save_auction(env, &auction);
+
+    emit_auction_bid(
+        env,
+        AuctionBidEvent {
+            borrower,
+            bidder,
+            debt_repaid: repaid,
+            collateral_sold: collateral_out,
+            timestamp: now,
+        },
+    );
+    Ok(collateral_out)
+}
+
+/// Close a finished auction and return unsold collateral to the borrower
+///
+/// Anyone can settle an auction once its tab is repaid, its lot is sold out
+/// or it has ended. Debt left behind by a borrower with no collateral left
+/// becomes bad debt.
+///
+/// # Returns
+/// The collateral returned to the borrower
+///
+/// # Errors
+/// * `AuctionError::AuctionNotFound` - If the borrower has no auction
+/// * `AuctionError::AuctionNotFinished` - If the auction is still running
+pub fn settle_auction(env: &Env, borrower: Address) -> Result<i128, AuctionError> {
+    let auction = get_auction(env, &borrower).ok_or(AuctionError::AuctionNotFound)?;
+    let now = env.ledger().timestamp();
+    if auction.tab_remaining > 0 && auction.lot_remaining > 0 && now < auction.ends_at {
+        return Err(AuctionError::AuctionNotFinished);
+    }
+
+    let collateral_key = DepositDataKey::CollateralBalance(borrower.clone());
+    let collateral_balance = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&collateral_key)
+        .unwrap_or(0)
+        .checked_add(auction.lot_remaining)
+        .ok_or(AuctionError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&collateral_key, &collateral_balance);
+
+    let position_key = DepositDataKey::Position(borrower.clone());
+    if let Some(mut position) = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+    {
+        position.collateral = collateral_balance;
+        crate::bad_debt::realize_bad_debt(
+            env,
+            &borrower,
+            &auction.debt_asset,
+            &mut position,
+            Symbol::new(env, "auction"),
+        )
+        .map_err(|_| AuctionError::Overflow)?;
+        env.storage().persistent().set(&position_key, &position);
+        crate::reserve::clear_debt_assets_if_repaid(env, &borrower, &position);
+        crate::liquidation_index::sync_borrower(env, &borrower);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&AuctionDataKey::LiquidationAuction(borrower.clone()));
+
+    emit_auction_settled(
+        env,
+        AuctionSettledEvent {
+            borrower,
+            collateral_returned: auction.lot_remaining,
+            tab_unpaid: auction.tab_remaining,
+            timestamp: now,
+        },
+    );
+    Ok(auction.lot_remaining)
+}
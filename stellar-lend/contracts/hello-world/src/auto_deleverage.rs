@@ -0,0 +1,234 @@
+//! # Auto-Deleverage Module
+//!
+//! Opt-in stop-loss protection for borrowers. A borrower pre-authorizes the
+//! sale of one collateral asset to repay one debt asset; once the health
+//! factor of their position falls below their trigger, any keeper can sell
+//! part of the collateral through `repay_with_collateral` and earn a small
+//! fee. Deleveraging early costs the borrower at most
+//! `MAX_AUTO_DELEVERAGE_FEE_BPS` of the collateral sold, far less than the
+//! liquidation penalty.
+//!
+//! ## Execution
+//! The keeper chooses how much collateral to sell, up to the borrower's
+//! per-execution limit. The fee is taken from that amount and paid to the
+//! keeper in the collateral asset; the rest is sold and repays the debt with
+//! the oracle slippage bound of `repay_with_collateral`.
+//!
+//! ## Invariants
+//! - Only the borrower can enable, change or disable their protection.
+//! - A keeper can only act while the health factor is below the trigger.
+//! - Every execution must raise the health factor.
+//! - The fee never exceeds `MAX_AUTO_DELEVERAGE_FEE_BPS` of the amount sold.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+use crate::events::{
+    emit_auto_deleverage_disabled, emit_auto_deleverage_enabled, emit_auto_deleverage_executed,
+    AutoDeleverageDisabledEvent, AutoDeleverageEnabledEvent, AutoDeleverageExecutedEvent,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Highest keeper fee a borrower may grant (1%)
+pub const MAX_AUTO_DELEVERAGE_FEE_BPS: i128 = 100;
+
+/// Errors that can occur during auto-deleverage operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AutoDeleverageError {
+    /// Trigger, assets, limit or fee are invalid
+    InvalidConfig = 1,
+    /// The user has no auto-deleverage protection
+    ProtectionNotSet = 2,
+    /// Amount must be greater than zero and within the per-execution limit
+    InvalidAmount = 3,
+    /// The health factor is not below the trigger
+    TriggerNotMet = 4,
+    /// The collateral sale or repayment was rejected by the protocol
+    DeleverageFailed = 5,
+    /// The execution did not raise the health factor
+    HealthNotImproved = 6,
+}
+
+/// Storage keys for auto-deleverage data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum AutoDeleverageDataKey {
+    /// A user's protection: AutoDeleverageConfig
+    Protection(Address),
+}
+
+/// A borrower's auto-deleverage protection
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoDeleverageConfig {
+    /// Health factor (scaled by 10000) below which keepers may act
+    pub trigger_health_factor: i128,
+    /// The collateral asset sold
+    pub collateral_asset: Address,
+    /// The asset the debt is repaid in
+    pub debt_asset: Address,
+    /// Maximum collateral sold per execution, fee included
+    pub max_amount: i128,
+    /// Keeper fee in basis points of the collateral sold
+    pub fee_bps: i128,
+}
+
+/// Get a user's auto-deleverage protection
+pub fn get_auto_deleverage(env: &Env, user: &Address) -> Option<AutoDeleverageConfig> {
+    env.storage()
+        .persistent()
+        .get::<AutoDeleverageDataKey, AutoDeleverageConfig>(&AutoDeleverageDataKey::Protection(
+            user.clone(),
+        ))
+}
+
+/// Enable or replace a user's auto-deleverage protection
+///
+/// # Arguments
+/// * `user` - The borrower (must authorize)
+/// * `config` - The protection
+///
+/// # Errors
+/// * `AutoDeleverageError::InvalidConfig` - If the trigger is not above 1.0,
+///   the assets are the same, the limit is not positive or the fee is out of
+///   range
+pub fn enable_auto_deleverage(
+    env: &Env,
+    user: Address,
+    config: AutoDeleverageConfig,
+) -> Result<(), AutoDeleverageError> {
+    user.require_auth();
+
+    if config.trigger_health_factor <= BASIS_POINTS_SCALE
+        || config.collateral_asset == config.debt_asset
+        || config.max_amount <= 0
+        || !(0..=MAX_AUTO_DELEVERAGE_FEE_BPS).contains(&config.fee_bps)
+    {
+        return Err(AutoDeleverageError::InvalidConfig);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&AutoDeleverageDataKey::Protection(user.clone()), &config);
+
+    emit_auto_deleverage_enabled(
+        env,
+        AutoDeleverageEnabledEvent {
+            user,
+            trigger_health_factor: config.trigger_health_factor,
+            max_amount: config.max_amount,
+            fee_bps: config.fee_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Disable a user's auto-deleverage protection
+///
+/// # Arguments
+/// * `user` - The borrower (must authorize)
+///
+/// # Errors
+/// * `AutoDeleverageError::ProtectionNotSet` - If no protection is enabled
+pub fn disable_auto_deleverage(env: &Env, user: Address) -> Result<(), AutoDeleverageError> {
+    user.require_auth();
+
+    let key = AutoDeleverageDataKey::Protection(user.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(AutoDeleverageError::ProtectionNotSet);
+    }
+    env.storage().persistent().remove(&key);
+
+    emit_auto_deleverage_disabled(
+        env,
+        AutoDeleverageDisabledEvent {
+            user,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Sell part of a protected user's collateral to repay debt (any keeper)
+///
+/// # Arguments
+/// * `keeper` - The executing account (must authorize), paid the fee
+/// * `user` - The protected borrower
+/// * `amount` - Collateral to take from the position, fee included
+///
+/// # Returns
+/// Returns a tuple (remaining_debt, debt_repaid, fee)
+///
+/// # Errors
+/// * `AutoDeleverageError::ProtectionNotSet` - If the user is not protected
+/// * `AutoDeleverageError::InvalidAmount` - If the amount is not positive or
+///   above the per-execution limit
+/// * `AutoDeleverageError::TriggerNotMet` - If the health factor is not
+///   below the trigger
+/// * `AutoDeleverageError::DeleverageFailed` - If the sale or repayment fails
+/// * `AutoDeleverageError::HealthNotImproved` - If the health factor did not rise
+pub fn execute_auto_deleverage(
+    env: &Env,
+    keeper: Address,
+    user: Address,
+    amount: i128,
+) -> Result<(i128, i128, i128), AutoDeleverageError> {
+    keeper.require_auth();
+
+    let config = get_auto_deleverage(env, &user).ok_or(AutoDeleverageError::ProtectionNotSet)?;
+    if amount <= 0 || amount > config.max_amount {
+        return Err(AutoDeleverageError::InvalidAmount);
+    }
+    let health_before = crate::analytics::get_account_data(env, &user)
+        .map_err(|_| AutoDeleverageError::TriggerNotMet)?
+        .health_factor;
+    if health_before >= config.trigger_health_factor {
+        return Err(AutoDeleverageError::TriggerNotMet);
+    }
+
+    let fee = amount * config.fee_bps / BASIS_POINTS_SCALE;
+    let (remaining_debt, _, debt_repaid) = crate::repay::repay_with_collateral_internal(
+        env,
+        user.clone(),
+        config.debt_asset.clone(),
+        config.collateral_asset.clone(),
+        amount - fee,
+    )
+    .map_err(|_| AutoDeleverageError::DeleverageFailed)?;
+    if fee > 0 {
+        crate::repay::take_collateral(env, &user, &config.collateral_asset, fee)
+            .map_err(|_| AutoDeleverageError::DeleverageFailed)?;
+        soroban_sdk::token::Client::new(env, &config.collateral_asset).transfer(
+            &env.current_contract_address(),
+            &keeper,
+            &fee,
+        );
+    }
+
+    let health_after = crate::analytics::get_account_data(env, &user)
+        .map_err(|_| AutoDeleverageError::HealthNotImproved)?
+        .health_factor;
+    if health_after <= health_before {
+        return Err(AutoDeleverageError::HealthNotImproved);
+    }
+
+    emit_auto_deleverage_executed(
+        env,
+        AutoDeleverageExecutedEvent {
+            user,
+            keeper,
+            collateral_sold: amount - fee,
+            debt_repaid,
+            fee,
+            health_factor: health_after,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok((remaining_debt, debt_repaid, fee))
+}
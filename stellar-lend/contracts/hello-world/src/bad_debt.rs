@@ -0,0 +1,328 @@
+//! # Bad Debt Module
+//!
+//! Tracks realized bad debt per asset so insurers and stakers can see exactly
+//! what the backstop may be asked to cover.
+//!
+//! Bad debt is realized when a position is left with debt but no collateral:
+//! - automatically at liquidation time, once a liquidation seizes the last of
+//!   a borrower's collateral, and
+//! - by an admin write-off of any other position in that state.
+//!
+//! In both cases the residual debt is removed from the position and added to
+//! the bad debt of its asset, the protocol's deficit in that asset, so no
+//! phantom debt is left on the borrower. A CDP vault liquidated down to zero
+//! collateral is handled the same way, its residual debt recorded as bad debt
+//! of the protocol stablecoin.
+//!
+//! ## Deficit Coverage
+//! The admin or the treasury can burn the asset's protocol reserve against
+//! its deficit, either a chosen amount (`cover_bad_debt`) or as much as the
+//! reserve allows (`burn_reserves_for_deficit`). The safety module can also
+//! be slashed to cover it (see `safety_module`).
+//!
+//! ## Shortfall
+//! `get_total_shortfall` values every asset's bad debt with its latest oracle
+//! price feed, stale or not, so the figure never drops out while a feed lags.
+//! Assets without a feed (including native without a configured address) are
+//! counted at face value, matching the 1:1 default used by liquidations.
+//!
+//! Every change emits a `bad_debt_updated_event` carrying the asset's new bad
+//! debt and the new total shortfall.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol, Vec};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{emit_bad_debt_updated, BadDebtUpdatedEvent};
+use crate::oracle::{OracleDataKey, PriceFeed};
+
+/// Errors that can occur during bad debt operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BadDebtError {
+    /// Caller is not authorized (not admin)
+    Unauthorized = 1,
+    /// Amount must be greater than zero
+    InvalidAmount = 2,
+    /// Borrower has no position
+    NoPosition = 3,
+    /// Position still holds collateral
+    CollateralRemaining = 4,
+    /// Position has no debt to write off
+    NoDebt = 5,
+    /// Amount exceeds the asset's bad debt
+    ExceedsBadDebt = 6,
+    /// Protocol reserve is too small to cover the amount
+    InsufficientReserve = 7,
+    /// Overflow occurred during calculation
+    Overflow = 8,
+}
+
+/// Storage keys for bad debt data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum BadDebtDataKey {
+    /// Outstanding bad debt per asset: i128
+    BadDebt(Option<Address>),
+    /// Assets that have ever recorded bad debt: Vec<Option<Address>>
+    BadDebtAssets,
+}
+
+/// Get the outstanding bad debt of an asset
+pub fn get_bad_debt(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<BadDebtDataKey, i128>(&BadDebtDataKey::BadDebt(asset.clone()))
+        .unwrap_or(0)
+}
+
+fn get_bad_debt_assets(env: &Env) -> Vec<Option<Address>> {
+    env.storage()
+        .persistent()
+        .get::<BadDebtDataKey, Vec<Option<Address>>>(&BadDebtDataKey::BadDebtAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Value an amount of an asset with its latest price feed
+pub(crate) fn value_of(env: &Env, asset: &Option<Address>, amount: i128) -> i128 {
+    let priced_asset = match asset {
+        Some(address) => Some(address.clone()),
+        None => env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Address>(&DepositDataKey::NativeAssetAddress),
+    };
+    let feed = priced_asset.and_then(|address| {
+        env.storage()
+            .persistent()
+            .get::<OracleDataKey, PriceFeed>(&OracleDataKey::PriceFeed(address))
+    });
+
+    match feed {
+        Some(feed) => amount.saturating_mul(feed.price) / 10i128.saturating_pow(feed.decimals),
+        None => amount,
+    }
+}
+
+/// Get the oracle-valued bad debt across all assets
+pub fn get_total_shortfall(env: &Env) -> i128 {
+    let mut total: i128 = 0;
+    for asset in get_bad_debt_assets(env).iter() {
+        total = total.saturating_add(value_of(env, &asset, get_bad_debt(env, &asset)));
+    }
+    total
+}
+
+fn adjust_bad_debt(
+    env: &Env,
+    asset: &Option<Address>,
+    borrower: Option<Address>,
+    change: i128,
+    reason: Symbol,
+) -> Result<i128, BadDebtError> {
+    let bad_debt = get_bad_debt(env, asset)
+        .checked_add(change)
+        .ok_or(BadDebtError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&BadDebtDataKey::BadDebt(asset.clone()), &bad_debt);
+
+    let mut assets = get_bad_debt_assets(env);
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        env.storage()
+            .persistent()
+            .set(&BadDebtDataKey::BadDebtAssets, &assets);
+    }
+
+    emit_bad_debt_updated(
+        env,
+        BadDebtUpdatedEvent {
+            asset: asset.clone(),
+            borrower,
+            change,
+            bad_debt,
+            total_shortfall: get_total_shortfall(env),
+            reason,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(bad_debt)
+}
+
+/// Move the residual debt of a collateral-less position into bad debt
+///
+/// Does nothing while the position still holds collateral. The caller is
+/// responsible for persisting the position.
+///
+/// # Returns
+/// Returns the debt realized as bad debt
+pub(crate) fn realize_bad_debt(
+    env: &Env,
+    borrower: &Address,
+    debt_asset: &Option<Address>,
+    position: &mut Position,
+    reason: Symbol,
+) -> Result<i128, BadDebtError> {
+    if position.collateral > 0 {
+        return Ok(0);
+    }
+    let residual = position
+        .debt
+        .checked_add(position.borrow_interest)
+        .ok_or(BadDebtError::Overflow)?;
+    if residual <= 0 {
+        return Ok(0);
+    }
+
+    crate::reserve::record_borrow_change(env, debt_asset, -position.debt);
+    position.debt = 0;
+    position.borrow_interest = 0;
+    adjust_bad_debt(env, debt_asset, Some(borrower.clone()), residual, reason)?;
+    Ok(residual)
+}
+
+/// Record `amount` of debt written off outside the lending pool, such as a
+/// CDP vault left without collateral
+///
+/// # Returns
+/// Returns the asset's new bad debt
+pub(crate) fn record_bad_debt(
+    env: &Env,
+    asset: &Option<Address>,
+    borrower: &Address,
+    amount: i128,
+    reason: Symbol,
+) -> Result<i128, BadDebtError> {
+    if amount <= 0 {
+        return Err(BadDebtError::InvalidAmount);
+    }
+    adjust_bad_debt(env, asset, Some(borrower.clone()), amount, reason)
+}
+
+/// Write off the residual debt of a position with no collateral (admin only)
+///
+/// # Arguments
+/// * `caller` - The caller address (must be admin)
+/// * `borrower` - The borrower whose debt is written off
+/// * `debt_asset` - The asset the debt is denominated in (None = native)
+///
+/// # Returns
+/// Returns the debt written off
+pub fn write_off_bad_debt(
+    env: &Env,
+    caller: Address,
+    borrower: Address,
+    debt_asset: Option<Address>,
+) -> Result<i128, BadDebtError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| BadDebtError::Unauthorized)?;
+
+    let position_key = DepositDataKey::Position(borrower.clone());
+    let mut position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+        .ok_or(BadDebtError::NoPosition)?;
+    if position.collateral > 0 {
+        return Err(BadDebtError::CollateralRemaining);
+    }
+
+    let written_off = realize_bad_debt(
+        env,
+        &borrower,
+        &debt_asset,
+        &mut position,
+        Symbol::new(env, "write_off"),
+    )?;
+    if written_off == 0 {
+        return Err(BadDebtError::NoDebt);
+    }
+    position.last_accrual_time = env.ledger().timestamp();
+    env.storage().persistent().set(&position_key, &position);
+    crate::reserve::clear_debt_assets_if_repaid(env, &borrower, &position);
+    crate::liquidation_index::sync_borrower(env, &borrower);
+
+    Ok(written_off)
+}
+
+/// Require the caller to be the admin or the treasury address
+fn require_admin_or_treasury(env: &Env, caller: &Address) -> Result<(), BadDebtError> {
+    caller.require_auth();
+    if crate::reserve::get_treasury_address(env).as_ref() == Some(caller) {
+        return Ok(());
+    }
+    crate::admin::require_admin(env, caller).map_err(|_| BadDebtError::Unauthorized)
+}
+
+/// Cover bad debt out of the asset's protocol reserve (admin or treasury)
+///
+/// # Returns
+/// Returns the asset's remaining bad debt
+pub fn cover_bad_debt(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<i128, BadDebtError> {
+    require_admin_or_treasury(env, &caller)?;
+    if amount <= 0 {
+        return Err(BadDebtError::InvalidAmount);
+    }
+    if amount > get_bad_debt(env, &asset) {
+        return Err(BadDebtError::ExceedsBadDebt);
+    }
+
+    let reserve = crate::reserve::get_reserve_balance(env, asset.clone());
+    if amount > reserve {
+        return Err(BadDebtError::InsufficientReserve);
+    }
+    env.storage().persistent().set(
+        &DepositDataKey::ProtocolReserve(asset.clone()),
+        &(reserve - amount),
+    );
+
+    adjust_bad_debt(env, &asset, None, -amount, Symbol::new(env, "covered"))
+}
+
+/// Clear deficit covered by a safety module slash
+///
+/// # Returns
+/// Returns the asset's remaining bad debt
+pub(crate) fn cover_from_safety_module(
+    env: &Env,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<i128, BadDebtError> {
+    adjust_bad_debt(env, asset, None, -amount, Symbol::new(env, "slashed"))
+}
+
+/// Burn as much of the asset's protocol reserve as needed to clear its
+/// deficit (admin or treasury)
+///
+/// # Returns
+/// Returns the amount burned, at most the asset's deficit and reserve
+///
+/// # Errors
+/// * `BadDebtError::Unauthorized` - If caller is neither admin nor treasury
+/// * `BadDebtError::NoDebt` - If the asset has no deficit
+/// * `BadDebtError::InsufficientReserve` - If the asset has no reserve
+pub fn burn_reserves_for_deficit(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+) -> Result<i128, BadDebtError> {
+    let deficit = get_bad_debt(env, &asset);
+    if deficit <= 0 {
+        return Err(BadDebtError::NoDebt);
+    }
+    let burned = deficit.min(crate::reserve::get_reserve_balance(env, asset.clone()));
+    if burned <= 0 {
+        return Err(BadDebtError::InsufficientReserve);
+    }
+    cover_bad_debt(env, caller, asset, burned)?;
+    Ok(burned)
+}
@@ -0,0 +1,147 @@
+//! # Batch Actions Module
+//!
+//! Executes several core actions of one user as a unit, so flows such as
+//! "repay + withdraw" or "deposit + borrow" need a single signature and a
+//! single transaction.
+//!
+//! `execute` authorizes the user once and applies the actions in order
+//! through the same code paths as the standalone entrypoints: deposits and
+//! repayments are pulled from the user, withdrawals and borrows are paid to
+//! the user, and swaps trade the user's own tokens through the AMM.
+//!
+//! ## Health Check
+//! Withdrawals and borrows skip their own collateral ratio check. Once the
+//! last action has run, the position must meet the minimum collateral ratio
+//! each of them would have enforced; otherwise the whole batch reverts. The
+//! order of the actions therefore does not matter for the final check, e.g.
+//! a withdrawal may come before the repayment that makes room for it.
+//!
+//! ## Invariants
+//! - A batch holds between 1 and `MAX_BATCH_ACTIONS` actions.
+//! - Any failing action reverts every action of the batch.
+//! - Every other check of an action (amounts, pause switches, caps, action
+//!   delay, liquidity, slippage) still applies to the action itself.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+use stellarlend_amm::SwapParams;
+
+use crate::events::{emit_batch_executed, BatchExecutedEvent};
+
+/// Maximum actions per batch
+pub const MAX_BATCH_ACTIONS: u32 = 10;
+
+/// Errors that can occur during batch execution
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BatchActionError {
+    /// The batch has no actions
+    EmptyBatch = 1,
+    /// The batch has more than `MAX_BATCH_ACTIONS` actions
+    TooManyActions = 2,
+    /// An action was rejected by the protocol
+    ActionFailed = 3,
+    /// The position ends below the minimum collateral ratio
+    InsufficientCollateralRatio = 4,
+}
+
+/// A core action executed as part of a batch
+// Contract types cannot hold a `Box`, so `Swap` carries its parameters inline
+#[allow(clippy::large_enum_variant)]
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum BatchAction {
+    /// Deposit collateral: (asset, amount)
+    Deposit(Option<Address>, i128),
+    /// Withdraw collateral: (asset, amount)
+    Withdraw(Option<Address>, i128),
+    /// Borrow against the collateral: (asset, amount)
+    Borrow(Option<Address>, i128),
+    /// Repay debt: (asset, amount)
+    Repay(Option<Address>, i128),
+    /// Swap the user's tokens through the AMM
+    Swap(SwapParams),
+}
+
+/// Execute a batch of actions on behalf of a user
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `actions` - Actions to execute, in order
+///
+/// # Returns
+/// The result of each action: the collateral after a deposit or withdrawal,
+/// the total debt after a borrow, the remaining debt after a repayment, or
+/// the amount received from a swap
+///
+/// # Errors
+/// * `BatchActionError::EmptyBatch` - If there are no actions
+/// * `BatchActionError::TooManyActions` - If there are too many actions
+/// * `BatchActionError::ActionFailed` - If an action is rejected
+/// * `BatchActionError::InsufficientCollateralRatio` - If the position ends
+///   below the minimum collateral ratio
+pub fn execute(
+    env: &Env,
+    user: Address,
+    actions: Vec<BatchAction>,
+) -> Result<Vec<i128>, BatchActionError> {
+    if actions.is_empty() {
+        return Err(BatchActionError::EmptyBatch);
+    }
+    if actions.len() > MAX_BATCH_ACTIONS {
+        return Err(BatchActionError::TooManyActions);
+    }
+    user.require_auth();
+
+    let mut results = Vec::new(env);
+    for action in actions.iter() {
+        let result = match action {
+            BatchAction::Deposit(asset, amount) => {
+                crate::deposit::deposit_collateral(env, user.clone(), asset, amount)
+                    .map_err(|_| BatchActionError::ActionFailed)?
+            }
+            BatchAction::Withdraw(asset, amount) => {
+                crate::withdraw::withdraw_internal(env, user.clone(), asset, amount, &user, false)
+                    .map_err(|_| BatchActionError::ActionFailed)?
+            }
+            BatchAction::Borrow(asset, amount) => {
+                crate::borrow::borrow_internal(env, user.clone(), asset, amount, true, false)
+                    .map_err(|_| BatchActionError::ActionFailed)?
+                    .0
+            }
+            BatchAction::Repay(asset, amount) => {
+                crate::repay::repay_debt(env, user.clone(), asset, amount)
+                    .map_err(|_| BatchActionError::ActionFailed)?
+                    .0
+            }
+            BatchAction::Swap(params) => crate::amm::amm_swap(env.clone(), user.clone(), params)
+                .map_err(|_| BatchActionError::ActionFailed)?,
+        };
+        results.push_back(result);
+    }
+
+    for action in actions.iter() {
+        match action {
+            BatchAction::Withdraw(asset, _) => {
+                crate::withdraw::check_collateral_ratio(env, &user, asset)
+                    .map_err(|_| BatchActionError::InsufficientCollateralRatio)?
+            }
+            BatchAction::Borrow(asset, _) => {
+                crate::borrow::check_collateral_ratio(env, &user, asset)
+                    .map_err(|_| BatchActionError::InsufficientCollateralRatio)?
+            }
+            _ => {}
+        }
+    }
+
+    emit_batch_executed(
+        env,
+        BatchExecutedEvent {
+            user,
+            action_count: actions.len(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(results)
+}
@@ -0,0 +1,120 @@
+//! # Batch Query Module
+//!
+//! Answers many read-only queries in one invocation so indexers and wallets
+//! can load a whole portfolio with a single simulated call instead of one
+//! RPC round trip per view.
+//!
+//! Each `QueryRequest` maps to an existing view:
+//!
+//! | Request          | View                                    |
+//! |------------------|-----------------------------------------|
+//! | `AccountData`    | `analytics::get_account_data` (core)    |
+//! | `AssetPosition`  | `cross_asset::get_user_asset_position`  |
+//! | `HealthFactor`   | `cross_asset::get_health_factor`        |
+//! | `Price`          | reserve price used by cross-asset health|
+//! | `ReserveConfig`  | `reserve::get_reserve_config`           |
+//!
+//! ## Invariants
+//! - Results are returned in request order, one per request.
+//! - A query that fails yields `QueryResult::Unavailable` and does not fail
+//!   the batch.
+//! - At most `MAX_QUERIES` requests are answered per call, keeping the
+//!   footprint of a simulated call within network limits.
+//! - Nothing is written.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::analytics::AccountData;
+use crate::cross_asset::{AssetKey, AssetPosition};
+use crate::reserve::ReserveConfig;
+
+/// Maximum requests per batch
+pub const MAX_QUERIES: u32 = 20;
+
+/// Errors that can occur during batch queries
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BatchQueryError {
+    /// More than `MAX_QUERIES` requests
+    TooManyQueries = 1,
+}
+
+/// A read-only query
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QueryRequest {
+    /// Collateral, debt and health of a user's core position
+    AccountData(Address),
+    /// A user's cross-asset position in an asset (None for native)
+    AssetPosition(Address, Option<Address>),
+    /// A user's cross-asset health factor
+    HealthFactor(Address),
+    /// Current price of a cross-asset reserve (7 decimals)
+    Price(Option<Address>),
+    /// Reserve configuration of an asset
+    ReserveConfig(Option<Address>),
+}
+
+/// The answer to a `QueryRequest`
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryResult {
+    /// Answer to `QueryRequest::AccountData`
+    AccountData(AccountData),
+    /// Answer to `QueryRequest::AssetPosition`
+    AssetPosition(AssetPosition),
+    /// Answer to `QueryRequest::HealthFactor` (`i128::MAX` without debt)
+    HealthFactor(i128),
+    /// Answer to `QueryRequest::Price`
+    Price(i128),
+    /// Answer to `QueryRequest::ReserveConfig`
+    ReserveConfig(ReserveConfig),
+    /// The query failed, e.g. on a stale or missing price
+    Unavailable,
+}
+
+fn answer(env: &Env, request: QueryRequest) -> QueryResult {
+    match request {
+        QueryRequest::AccountData(user) => crate::analytics::get_account_data(env, &user)
+            .map_or(QueryResult::Unavailable, QueryResult::AccountData),
+        QueryRequest::AssetPosition(user, asset) => QueryResult::AssetPosition(
+            crate::cross_asset::get_user_asset_position(env, &user, asset),
+        ),
+        QueryRequest::HealthFactor(user) => crate::cross_asset::get_health_factor(env, &user)
+            .map_or(QueryResult::Unavailable, QueryResult::HealthFactor),
+        QueryRequest::Price(asset) => {
+            crate::cross_asset::get_asset_config(env, &AssetKey::from_option(asset.clone()))
+                .and_then(|config| crate::cross_asset::get_reserve_price(env, &asset, &config))
+                .map_or(QueryResult::Unavailable, QueryResult::Price)
+        }
+        QueryRequest::ReserveConfig(asset) => {
+            QueryResult::ReserveConfig(crate::reserve::get_reserve_config(env, asset))
+        }
+    }
+}
+
+/// Answer a batch of read-only queries
+///
+/// # Arguments
+/// * `requests` - Queries to answer (at most `MAX_QUERIES`)
+///
+/// # Returns
+/// One result per request, in request order
+///
+/// # Errors
+/// * `BatchQueryError::TooManyQueries` - If more than `MAX_QUERIES` requests
+pub fn query_many(
+    env: &Env,
+    requests: Vec<QueryRequest>,
+) -> Result<Vec<QueryResult>, BatchQueryError> {
+    if requests.len() > MAX_QUERIES {
+        return Err(BatchQueryError::TooManyQueries);
+    }
+    let mut results = Vec::new(env);
+    for request in requests.iter() {
+        results.push_back(answer(env, request));
+    }
+    Ok(results)
+}
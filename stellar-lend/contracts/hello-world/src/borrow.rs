@@ -5,6 +5,8 @@
 //! Users can borrow assets against their deposited collateral, subject to:
 //! - Minimum collateral ratio requirements (150% default)
 //! - Maximum borrow limits based on collateral value
+//! - The asset's borrow cap, set per reserve
+//! - Siloed assets, which are only borrowed in isolation
 //! - Pause switch checks
 //!
 //! ## Interest Accrual
@@ -14,8 +16,15 @@
 //!
 //! ## Invariants
 //! - A user must have collateral deposited before borrowing.
-//! - The collateral ratio must remain at or above the minimum after the borrow.
+//! - The collateral ratio must remain at or above the minimum after the borrow,
+//!   measured against the debt including interest accrued up to the borrow.
+//!   Since the minimum ratio is never below the liquidation threshold, a
+//!   borrow can never make a position liquidatable.
 //! - Borrow amount must not exceed the maximum borrowable given current collateral.
+//! - Total principal borrowed of an asset never exceeds its borrow cap.
+//! - A user with debt in a siloed asset owes no other asset.
+//! - Native XLM is paid out through the configured native asset contract;
+//!   borrowing that contract's address books the debt as native XLM.
 
 #![allow(unused)]
 use soroban_sdk::{contracterror, Address, Env, IntoVal, Map, Symbol, Val, Vec};
@@ -50,73 +59,38 @@ pub enum BorrowError {
     MaxBorrowExceeded = 8,
     /// Asset is not enabled for borrowing
     AssetNotEnabled = 9,
+    /// AMM swap of the borrowed asset failed
+    SwapFailed = 10,
+    /// AMM swap returned less than the minimum output
+    SlippageExceeded = 11,
+    /// Borrow would exceed the asset's borrow cap
+    BorrowCapExceeded = 12,
+    /// Borrow would mix a siloed asset with other debt
+    SiloedBorrowViolation = 13,
+    /// Borrows are blocked for a few ledgers after a deposit
+    ActionDelayActive = 14,
+    /// Asset is frozen (see `risk_management`)
+    AssetFrozen = 15,
 }
 
-/// Minimum collateral ratio (in basis points, e.g., 15000 = 150%)
-/// This is the minimum ratio required: collateral_value / debt_value >= 1.5
+/// Slippage tolerance passed to the AMM router for borrow-and-swap (1%)
+const SWAP_SLIPPAGE_TOLERANCE_BPS: i128 = 100;
+
+// Minimum collateral ratio (in basis points, e.g., 15000 = 150%)
+// This is the minimum ratio required: collateral_value / debt_value >= 1.5
 // Minimum collateral ratio is now managed by the risk_params module
 // const MIN_COLLATERAL_RATIO_BPS: i128 = 15000; // 150% (Legacy)
 
-/// Annual interest rate in basis points (e.g., 500 = 5% per year)
-/// This is a simple constant rate model - in production, this would be more sophisticated
-// Interest rate is now calculated dynamically based on utilization
-// See interest_rate module for details
-/// Calculate interest accrued since last accrual time
-/// Uses simple interest: interest = principal * rate * time
-/// Calculate accrued interest using dynamic interest rate
-/// Uses the current borrow rate based on protocol utilization
-fn calculate_accrued_interest(
-    env: &Env,
-    principal: i128,
-    last_accrual_time: u64,
-    current_time: u64,
-) -> Result<i128, BorrowError> {
-    if principal == 0 {
-        return Ok(0);
-    }
-
-    if current_time <= last_accrual_time {
-        return Ok(0);
-    }
-
-    // Get current borrow rate (in basis points)
-    let rate_bps =
-        crate::interest_rate::calculate_borrow_rate(env).map_err(|_| BorrowError::Overflow)?;
-
-    // Calculate interest using the dynamic rate
-    crate::interest_rate::calculate_accrued_interest(
-        principal,
-        last_accrual_time,
-        current_time,
-        rate_bps,
-    )
-    .map_err(|_| BorrowError::Overflow)
-}
-
 /// Accrue interest on a position
-/// Updates the position's borrow_interest and last_accrual_time
-fn accrue_interest(env: &Env, position: &mut Position) -> Result<(), BorrowError> {
-    let current_time = env.ledger().timestamp();
-
+///
+/// Books the interest accrued since the last accrual through the pool's
+/// borrow index (see `interest_index`).
+fn accrue_interest(env: &Env, user: &Address, position: &mut Position) -> Result<(), BorrowError> {
     if position.debt == 0 {
         position.borrow_interest = 0;
-        position.last_accrual_time = current_time;
-        return Ok(());
     }
-
-    // Calculate new interest accrued using dynamic rate
-    let new_interest =
-        calculate_accrued_interest(env, position.debt, position.last_accrual_time, current_time)?;
-
-    // Add to existing interest
-    position.borrow_interest = position
-        .borrow_interest
-        .checked_add(new_interest)
-        .ok_or(BorrowError::Overflow)?;
-
-    // Update last accrual time
-    position.last_accrual_time = current_time;
-
+    crate::interest_index::accrue_position(env, user, position)
+        .map_err(|_| BorrowError::Overflow)?;
     Ok(())
 }
 
@@ -183,20 +157,15 @@ fn calculate_max_borrowable(
 }
 
 /// Validate that borrow would maintain minimum collateral ratio
+///
+/// `position` must already have its interest accrued.
 fn validate_collateral_ratio_after_borrow(
     env: &Env,
     user: &Address,
+    position: &Position,
     borrow_amount: i128,
     collateral_factor: i128,
 ) -> Result<(), BorrowError> {
-    // Get user position
-    let position_key = DepositDataKey::Position(user.clone());
-    let position = env
-        .storage()
-        .persistent()
-        .get::<DepositDataKey, Position>(&position_key)
-        .ok_or(BorrowError::InsufficientCollateral)?;
-
     // Get current collateral balance
     let collateral_key = DepositDataKey::CollateralBalance(user.clone());
     let current_collateral = env
@@ -235,6 +204,41 @@ fn validate_collateral_ratio_after_borrow(
     Ok(())
 }
 
+/// Check that a user's position meets the minimum collateral ratio of
+/// borrowing `asset`
+///
+/// Used after borrows booked without their own check (see `borrow_internal`).
+///
+/// # Errors
+/// * `BorrowError::InsufficientCollateral` - If the user has debt but no collateral
+/// * `BorrowError::InsufficientCollateralRatio` - If the ratio is below the minimum
+pub(crate) fn check_collateral_ratio(
+    env: &Env,
+    user: &Address,
+    asset: Option<Address>,
+) -> Result<(), BorrowError> {
+    let asset = crate::deposit::canonical_asset(env, asset);
+    let position = match env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    {
+        Some(position) if position.debt > 0 || position.borrow_interest > 0 => position,
+        _ => return Ok(()),
+    };
+    let collateral_factor = asset
+        .as_ref()
+        .and_then(|asset_addr| {
+            env.storage()
+                .persistent()
+                .get::<DepositDataKey, AssetParams>(&DepositDataKey::AssetParams(
+                    asset_addr.clone(),
+                ))
+        })
+        .map_or(10000, |params| params.collateral_factor);
+    validate_collateral_ratio_after_borrow(env, user, &position, 0, collateral_factor)
+}
+
 /// Borrow assets from the protocol
 pub fn borrow_asset(
     env: &Env,
@@ -242,6 +246,175 @@ pub fn borrow_asset(
     asset: Option<Address>,
     amount: i128,
 ) -> Result<i128, BorrowError> {
+    borrow_internal(env, user, asset, amount, true, true).map(|(total_debt, _)| total_debt)
+}
+
+/// Get the largest amount a user can currently borrow
+///
+/// Mirrors the checks of `borrow_asset`: the debt after the borrow, including
+/// interest and insurance premium accrued up to now, must keep the collateral
+/// ratio at or above the minimum collateral ratio, and the reserve must hold
+/// enough liquidity. The amount is in debt terms; the borrow fee is deducted
+/// from what the user receives. Pause switches are not taken into account.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The address of the user
+/// * `asset` - The asset to borrow (None for native XLM)
+///
+/// # Returns
+/// The maximum borrowable amount; zero if the user has no collateral, the
+/// asset is disabled or the position has no headroom left
+pub fn get_max_borrowable(env: &Env, user: &Address, asset: Option<Address>) -> i128 {
+    let asset = crate::deposit::canonical_asset(env, asset);
+    let params = asset.as_ref().and_then(|asset_addr| {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, AssetParams>(&DepositDataKey::AssetParams(asset_addr.clone()))
+    });
+    if params
+        .as_ref()
+        .is_some_and(|params| !params.deposit_enabled)
+    {
+        return 0;
+    }
+    let collateral_factor = params.map_or(10000, |params| params.collateral_factor);
+
+    let collateral = crate::supply_interest::get_user_supply_balance(env, user);
+    if collateral <= 0 {
+        return 0;
+    }
+
+    let timestamp = env.ledger().timestamp();
+    let mut position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+        .unwrap_or(Position {
+            collateral: 0,
+            debt: 0,
+            borrow_interest: 0,
+            last_accrual_time: timestamp,
+        });
+    let pending = match crate::interest_index::pending_interest(env, user, &position) {
+        Ok(pending) => pending,
+        Err(_) => return 0,
+    };
+    position.borrow_interest = position.borrow_interest.saturating_add(pending);
+    let premium = match crate::liquidation_insurance::pending_premium(env, user, &position) {
+        Ok(premium) => premium,
+        Err(_) => return 0,
+    };
+    let interest = position.borrow_interest.saturating_add(premium);
+
+    let min_ratio = crate::risk_params::get_min_collateral_ratio(env).unwrap_or(15000);
+    let max_borrowable = calculate_max_borrowable(
+        collateral,
+        position.debt,
+        interest,
+        collateral_factor,
+        min_ratio,
+    )
+    .unwrap_or(0)
+    .min(crate::reserve::get_remaining_borrow_capacity(env, &asset));
+
+    // Cap by the liquidity held by the reserve
+    // Skipped in unit tests, like the balance check of the borrow itself
+    #[cfg(not(test))]
+    {
+        if let Some(token_addr) = crate::deposit::resolve_token_address(env, &asset) {
+            let liquidity = crate::reserve::available_liquidity(env, &asset, &token_addr);
+            return max_borrowable.min(liquidity);
+        }
+    }
+
+    max_borrowable
+}
+
+/// Borrow `debt_asset` and swap it into `receive_asset` through the AMM router
+///
+/// The debt is booked in the borrowed asset; the borrowed amount (net of the
+/// borrow fee) is swapped by this contract against its hosted pools and only
+/// the swap output is paid out to the user. The whole call reverts if the
+/// swap fails or returns less than `min_out`.
+///
+/// # Arguments
+/// * `user` - The borrower
+/// * `debt_asset` - The asset borrowed and owed
+/// * `amount` - The amount to borrow
+/// * `receive_asset` - The asset delivered to the user
+/// * `min_out` - Minimum amount of `receive_asset` to receive
+///
+/// # Returns
+/// Returns a tuple (total_debt, amount_received)
+pub fn borrow_and_swap(
+    env: &Env,
+    user: Address,
+    debt_asset: Address,
+    amount: i128,
+    receive_asset: Address,
+    min_out: i128,
+) -> Result<(i128, i128), BorrowError> {
+    user.require_auth();
+    if receive_asset == debt_asset {
+        return Err(BorrowError::InvalidAsset);
+    }
+    if min_out <= 0 {
+        return Err(BorrowError::InvalidAmount);
+    }
+
+    let (total_debt, borrowed) = borrow_internal(
+        env,
+        user.clone(),
+        Some(debt_asset.clone()),
+        amount,
+        false,
+        true,
+    )?;
+
+    let params = stellarlend_amm::SwapParams {
+        protocol: env.current_contract_address(),
+        token_in: Some(debt_asset),
+        token_out: Some(receive_asset.clone()),
+        amount_in: borrowed,
+        min_amount_out: min_out,
+        slippage_tolerance: SWAP_SLIPPAGE_TOLERANCE_BPS,
+        deadline: env.ledger().timestamp(),
+        use_oracle_floor: false,
+    };
+    let amount_out = stellarlend_amm::execute_swap(env, env.current_contract_address(), params)
+        .map_err(|e| match e {
+            stellarlend_amm::AmmError::MinOutputNotMet
+            | stellarlend_amm::AmmError::SlippageExceeded => BorrowError::SlippageExceeded,
+            _ => BorrowError::SwapFailed,
+        })?;
+
+    soroban_sdk::token::Client::new(env, &receive_asset).transfer(
+        &env.current_contract_address(),
+        &user,
+        &amount_out,
+    );
+
+    Ok((total_debt, amount_out))
+}
+
+/// Book a borrow and, when `deliver` is set, pay the borrowed amount out to
+/// the user
+///
+/// Without `check_ratio` the collateral checks are skipped; the caller must
+/// run `check_collateral_ratio` once it is done with the position.
+///
+/// # Returns
+/// Returns a tuple (total_debt, amount_received) where `amount_received` is
+/// the borrowed amount net of the borrow fee
+pub(crate) fn borrow_internal(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+    deliver: bool,
+    check_ratio: bool,
+) -> Result<(i128, i128), BorrowError> {
     // Validate amount
     if amount <= 0 {
         return Err(BorrowError::InvalidAmount);
@@ -260,10 +433,24 @@ pub fn borrow_asset(
             }
         }
     }
+    if crate::risk_management::is_operation_paused(env, Symbol::new(env, "pause_borrow")) {
+        return Err(BorrowError::BorrowPaused);
+    }
+
+    // No borrowing against collateral deposited within the action delay
+    if crate::action_delay::is_delayed(env, &user) {
+        return Err(BorrowError::ActionDelayActive);
+    }
 
     // Get current timestamp
     let timestamp = env.ledger().timestamp();
 
+    // Wrapped native XLM is booked as native XLM
+    let asset = crate::deposit::canonical_asset(env, asset);
+    if crate::risk_management::is_asset_frozen(env, &asset) {
+        return Err(BorrowError::AssetFrozen);
+    }
+
     // Validate asset if provided
     if let Some(ref asset_addr) = asset {
         // Validate asset address - ensure it's not the contract itself
@@ -284,6 +471,9 @@ pub fn borrow_asset(
         }
     }
 
+    // Credit supply interest earned so far; it backs the borrow too
+    crate::supply_interest::settle_supply_interest(env, &user);
+
     // Get user position
     let position_key = DepositDataKey::Position(user.clone());
     #[allow(clippy::unnecessary_lazy_evaluations)]
@@ -299,7 +489,10 @@ pub fn borrow_asset(
         });
 
     // Accrue interest on existing debt before borrowing
-    accrue_interest(env, &mut position)?;
+    accrue_interest(env, &user, &mut position)?;
+    crate::rate_stats::record_accrual(env, &asset);
+    crate::liquidation_insurance::accrue_premium(env, &user, &mut position)
+        .map_err(|_| BorrowError::Overflow)?;
 
     // Get current collateral balance
     let collateral_key = DepositDataKey::CollateralBalance(user.clone());
@@ -310,7 +503,7 @@ pub fn borrow_asset(
         .unwrap_or(0);
 
     // Check if user has collateral
-    if current_collateral == 0 {
+    if check_ratio && current_collateral == 0 {
         return Err(BorrowError::InsufficientCollateral);
     }
 
@@ -358,13 +551,23 @@ pub fn borrow_asset(
         min_ratio,
     )?;
 
-    // Check if borrow amount exceeds maximum
-    if amount > max_borrowable {
-        return Err(BorrowError::MaxBorrowExceeded);
+    if check_ratio {
+        // Check if borrow amount exceeds maximum
+        if amount > max_borrowable {
+            return Err(BorrowError::MaxBorrowExceeded);
+        }
+
+        // Validate collateral ratio after borrow
+        validate_collateral_ratio_after_borrow(env, &user, &position, amount, collateral_factor)?;
     }
 
-    // Validate collateral ratio after borrow
-    validate_collateral_ratio_after_borrow(env, &user, amount, collateral_factor)?;
+    // Enforce the asset's borrow cap, however much collateral backs the borrow
+    crate::reserve::check_borrow_cap(env, &asset, amount)
+        .map_err(|_| BorrowError::BorrowCapExceeded)?;
+
+    // Siloed assets cannot share an account's debt with other assets
+    crate::reserve::check_siloed_borrow(env, &user, &asset)
+        .map_err(|_| BorrowError::SiloedBorrowViolation)?;
 
     // Calculate new debt
     let new_debt = position
@@ -390,27 +593,31 @@ pub fn borrow_asset(
     position.debt = new_debt;
     position.last_accrual_time = timestamp;
     env.storage().persistent().set(&position_key, &position);
+    crate::reserve::record_borrow_change(env, &asset, amount);
+    crate::reserve::record_debt_asset(env, &user, &asset);
+    crate::storage_ttl::extend_position_ttl(env, &user);
+    crate::storage_ttl::extend_reserve_ttl(env, &asset);
+
+    // Handle asset transfer - contract sends tokens to user; native XLM goes
+    // through its Stellar Asset Contract
+    // Skip actual token transfers in unit tests to avoid Storage error with non-existent contracts
+    #[cfg(not(test))]
+    {
+        if let Some(token_addr) = crate::deposit::resolve_token_address(env, &asset) {
+            let token_client = soroban_sdk::token::Client::new(env, &token_addr);
 
-    // Handle asset transfer - contract sends tokens to user
-    if let Some(ref asset_addr) = asset {
-        // Skip actual token transfers in unit tests to avoid Storage error with non-existent contracts
-        #[cfg(not(test))]
-        {
-            let token_client = soroban_sdk::token::Client::new(env, asset_addr);
-
-            // Check contract balance
-            let contract_balance = token_client.balance(&env.current_contract_address());
-            if contract_balance < amount {
+            // Check the liquidity left after escrowed balances
+            if crate::reserve::available_liquidity(env, &asset, &token_addr) < amount {
                 return Err(BorrowError::InsufficientCollateral);
             }
 
-            token_client.transfer(
-                &env.current_contract_address(),
-                &user,
-                &receive_amount,
-            );
+            if deliver {
+                token_client.transfer(&env.current_contract_address(), &user, &receive_amount);
+            }
         }
+    }
 
+    if asset.is_some() {
         // Credit fee to protocol reserve
         if fee_amount > 0 {
             let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
@@ -423,6 +630,12 @@ pub fn borrow_asset(
                 &reserve_key,
                 &(current_reserve.checked_add(fee_amount).ok_or(BorrowError::Overflow)?),
             );
+            crate::treasury::record_revenue(
+                env,
+                &asset,
+                crate::treasury::RevenueSource::BorrowFee,
+                fee_amount,
+            );
         }
     }
 
@@ -445,6 +658,8 @@ pub fn borrow_asset(
         crate::deposit::DepositError::Overflow => BorrowError::Overflow,
         _ => BorrowError::Overflow,
     })?;
+    crate::health_history::record_health_snapshot(env, &user, "borrow");
+    crate::liquidation_index::sync_borrower(env, &user);
 
     // Emit borrow event
     emit_borrow(
@@ -464,7 +679,7 @@ pub fn borrow_asset(
 
     // Return total debt
     let total_debt = position.debt.checked_add(position.borrow_interest).ok_or(BorrowError::Overflow)?;
-    Ok(total_debt)
+    Ok((total_debt, receive_amount))
 }
 
 /// Update user analytics after borrow
@@ -0,0 +1,984 @@
+//! # CDP Stablecoin Module
+//!
+//! Collateralized debt positions that mint the protocol stablecoin.
+//!
+//! Users lock an admin-approved collateral asset in a vault and mint the
+//! protocol stablecoin against it. The stablecoin is a Stellar asset whose
+//! admin is this contract: it is minted when debt is drawn and burned when
+//! debt is repaid or liquidated.
+//!
+//! ## Collateral Types
+//! Each approved collateral has its own minimum collateral ratio, liquidation
+//! ratio, liquidation penalty, stability fee and debt ceiling. A global debt
+//! ceiling caps the total stablecoin debt across all collateral types.
+//!
+//! ## Stability Fee
+//! Vault debt grows at the collateral type's annual `stability_fee_bps`. Fees
+//! are accrued whenever a vault is touched and count towards the debt ceilings.
+//! Repayments and liquidations settle a vault's fees before its principal; fees
+//! settled this way are counted as collected.
+//!
+//! ## Liquidation
+//! Vaults whose collateral ratio falls below the liquidation ratio can be
+//! liquidated: the liquidator burns stablecoin to cover up to the protocol
+//! close factor of the vault debt and receives collateral worth the covered
+//! debt plus the liquidation penalty. Collateral is valued with the protocol
+//! oracle against the stablecoin's own oracle price. Debt left once a
+//! liquidation seizes the last of a vault's collateral is written off as bad
+//! debt of the stablecoin (see `bad_debt`).
+//!
+//! ## AMM Deleveraging
+//! Vault owners can repay debt by selling part of their collateral for
+//! stablecoin on a deployed AMM contract registered with the router. The
+//! vault is charged the collateral that actually left this contract and only
+//! the stablecoin that actually arrived is burned.
+//!
+//! ## Pausing
+//! CDP operations honour the protocol's emergency pause and operation
+//! switches: deposits follow `pause_deposit`, minting `pause_borrow`,
+//! withdrawals `pause_withdraw`, repayments `pause_repay` and liquidations
+//! `pause_liquidate`. Frozen collateral accepts no new deposits or debt.
+//!
+//! ## Invariants
+//! - `liquidation_ratio_bps >= 10_000` and `min_collateral_ratio_bps >= liquidation_ratio_bps`.
+//! - Minting and withdrawing can never leave a vault below its minimum collateral ratio.
+//! - Total debt never exceeds the per-collateral or global debt ceilings through minting.
+//! - Liquidations can only cover up to the close factor of a vault's debt.
+//! - Vault collateral is escrowed in `reserve`, so the lending pool never
+//!   lends it out.
+//! - No vault is left holding debt without collateral.
+
+#![allow(unused)]
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{contracterror, contracttype, vec, Address, Env, IntoVal, Symbol};
+
+use crate::events::{
+    emit_cdp_collateral_deposited, emit_cdp_collateral_withdrawn, emit_cdp_liquidation,
+    emit_cdp_mint, emit_cdp_repay, CdpCollateralDepositedEvent, CdpCollateralWithdrawnEvent,
+    CdpLiquidationEvent, CdpMintEvent, CdpRepayEvent,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Maximum annual stability fee (50%)
+pub const MAX_STABILITY_FEE_BPS: i128 = 5_000;
+
+/// Maximum liquidation penalty (50%)
+pub const MAX_LIQUIDATION_PENALTY_BPS: i128 = 5_000;
+
+/// Default slippage tolerance passed to the AMM router (1%)
+const AMM_SLIPPAGE_TOLERANCE_BPS: i128 = 100;
+
+/// Errors that can occur during CDP operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CdpError {
+    /// Caller is not authorized (not admin)
+    Unauthorized = 1,
+    /// Amount must be greater than zero
+    InvalidAmount = 2,
+    /// Configuration is invalid
+    InvalidConfig = 3,
+    /// CDP module has not been initialized
+    NotInitialized = 4,
+    /// CDP module has already been initialized
+    AlreadyInitialized = 5,
+    /// Collateral asset is not approved for CDPs
+    CollateralNotSupported = 6,
+    /// Collateral type is disabled for new debt
+    CollateralDisabled = 7,
+    /// Minting would exceed a debt ceiling
+    DebtCeilingExceeded = 8,
+    /// Operation would leave the vault below its minimum collateral ratio
+    Undercollateralized = 9,
+    /// Vault is above its liquidation ratio
+    NotLiquidatable = 10,
+    /// Liquidation exceeds the close factor
+    ExceedsCloseFactor = 11,
+    /// Vault does not hold enough collateral
+    InsufficientCollateral = 12,
+    /// Oracle price unavailable for the collateral or stablecoin
+    PriceUnavailable = 13,
+    /// AMM swap failed or returned too little stablecoin
+    SwapFailed = 14,
+    /// Overflow occurred during calculation
+    Overflow = 15,
+    /// The operation is paused
+    OperationPaused = 16,
+    /// Collateral asset is frozen (see `risk_management`)
+    AssetFrozen = 17,
+}
+
+/// Storage keys for CDP data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum CdpDataKey {
+    /// Global CDP configuration: CdpConfig
+    CdpConfig,
+    /// Parameters of an approved collateral: CdpCollateralType
+    CollateralType(Address),
+    /// Outstanding debt minted against a collateral: i128
+    CollateralDebt(Address),
+    /// Outstanding debt across all collaterals: i128
+    TotalDebt,
+    /// Cumulative stability fees accrued: i128
+    FeesAccrued,
+    /// Cumulative stability fees repaid: i128
+    FeesCollected,
+    /// Vault of an owner for a collateral: CdpVault
+    Vault(Address, Address),
+}
+
+/// Global CDP configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CdpConfig {
+    /// Protocol stablecoin (a Stellar asset administered by this contract)
+    pub stablecoin: Address,
+    /// Maximum stablecoin debt across all collateral types
+    pub debt_ceiling: i128,
+}
+
+/// Risk parameters of an approved collateral
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CdpCollateralType {
+    /// Collateral ratio required to mint or withdraw (bps)
+    pub min_collateral_ratio_bps: i128,
+    /// Collateral ratio below which a vault can be liquidated (bps)
+    pub liquidation_ratio_bps: i128,
+    /// Bonus collateral awarded to liquidators (bps)
+    pub liquidation_penalty_bps: i128,
+    /// Annual stability fee charged on vault debt (bps)
+    pub stability_fee_bps: i128,
+    /// Maximum stablecoin debt against this collateral
+    pub debt_ceiling: i128,
+    /// Whether new debt can be minted against this collateral
+    pub enabled: bool,
+}
+
+/// A user's vault for one collateral type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CdpVault {
+    /// Locked collateral, in collateral asset units
+    pub collateral: i128,
+    /// Outstanding stablecoin debt including accrued stability fees
+    pub debt: i128,
+    /// Part of `debt` that is stability fees not yet repaid
+    pub fees: i128,
+    /// Last time the stability fee was accrued
+    pub last_accrual_time: u64,
+}
+
+/// Get the CDP configuration
+pub fn get_cdp_config(env: &Env) -> Option<CdpConfig> {
+    env.storage()
+        .persistent()
+        .get::<CdpDataKey, CdpConfig>(&CdpDataKey::CdpConfig)
+}
+
+fn require_config(env: &Env) -> Result<CdpConfig, CdpError> {
+    get_cdp_config(env).ok_or(CdpError::NotInitialized)
+}
+
+/// Get the parameters of an approved collateral
+pub fn get_cdp_collateral_type(env: &Env, collateral: &Address) -> Option<CdpCollateralType> {
+    env.storage()
+        .persistent()
+        .get::<CdpDataKey, CdpCollateralType>(&CdpDataKey::CollateralType(collateral.clone()))
+}
+
+fn require_collateral_type(env: &Env, collateral: &Address) -> Result<CdpCollateralType, CdpError> {
+    get_cdp_collateral_type(env, collateral).ok_or(CdpError::CollateralNotSupported)
+}
+
+fn get_i128(env: &Env, key: &CdpDataKey) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<CdpDataKey, i128>(key)
+        .unwrap_or(0)
+}
+
+/// Get the outstanding stablecoin debt minted against a collateral
+pub fn get_cdp_collateral_debt(env: &Env, collateral: &Address) -> i128 {
+    get_i128(env, &CdpDataKey::CollateralDebt(collateral.clone()))
+}
+
+/// Get the outstanding stablecoin debt across all collaterals
+pub fn get_cdp_total_debt(env: &Env) -> i128 {
+    get_i128(env, &CdpDataKey::TotalDebt)
+}
+
+/// Add `delta` (which may be negative) to the per-collateral and total debt
+fn adjust_debt(env: &Env, collateral: &Address, delta: i128) -> Result<(), CdpError> {
+    let collateral_key = CdpDataKey::CollateralDebt(collateral.clone());
+    let collateral_debt = get_i128(env, &collateral_key)
+        .checked_add(delta)
+        .ok_or(CdpError::Overflow)?
+        .max(0);
+    env.storage()
+        .persistent()
+        .set(&collateral_key, &collateral_debt);
+
+    let total_debt = get_i128(env, &CdpDataKey::TotalDebt)
+        .checked_add(delta)
+        .ok_or(CdpError::Overflow)?
+        .max(0);
+    env.storage()
+        .persistent()
+        .set(&CdpDataKey::TotalDebt, &total_debt);
+    Ok(())
+}
+
+fn load_vault(env: &Env, owner: &Address, collateral: &Address) -> CdpVault {
+    env.storage()
+        .persistent()
+        .get::<CdpDataKey, CdpVault>(&CdpDataKey::Vault(owner.clone(), collateral.clone()))
+        .unwrap_or(CdpVault {
+            collateral: 0,
+            debt: 0,
+            fees: 0,
+            last_accrual_time: env.ledger().timestamp(),
+        })
+}
+
+/// Persist a vault, escrowing the change in its collateral so the lending
+/// pool does not lend it out
+fn save_vault(env: &Env, owner: &Address, collateral: &Address, vault: &CdpVault) {
+    let key = CdpDataKey::Vault(owner.clone(), collateral.clone());
+    let previous = env
+        .storage()
+        .persistent()
+        .get::<CdpDataKey, CdpVault>(&key)
+        .map_or(0, |v| v.collateral);
+    crate::reserve::record_escrow_change(
+        env,
+        &Some(collateral.clone()),
+        vault.collateral - previous,
+    );
+    if vault.collateral == 0 && vault.debt == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, vault);
+    }
+}
+
+/// Reject an operation while the protocol is emergency paused or
+/// `operation` is paused, through the legacy or the risk management switches
+fn require_not_paused(env: &Env, operation: &str) -> Result<(), CdpError> {
+    let operation = Symbol::new(env, operation);
+    let legacy_paused = env
+        .storage()
+        .persistent()
+        .get::<crate::deposit::DepositDataKey, soroban_sdk::Map<Symbol, bool>>(
+            &crate::deposit::DepositDataKey::PauseSwitches,
+        )
+        .and_then(|switches| switches.get(operation.clone()))
+        .unwrap_or(false);
+    if legacy_paused || crate::risk_management::check_operation_paused(env, operation) {
+        return Err(CdpError::OperationPaused);
+    }
+    Ok(())
+}
+
+/// Reject new deposits or debt against a frozen collateral
+fn require_not_frozen(env: &Env, collateral: &Address) -> Result<(), CdpError> {
+    if crate::risk_management::is_asset_frozen(env, &Some(collateral.clone())) {
+        return Err(CdpError::AssetFrozen);
+    }
+    Ok(())
+}
+
+/// Stability fee owed on a vault since its last accrual
+fn pending_fee(
+    env: &Env,
+    vault: &CdpVault,
+    collateral_type: &CdpCollateralType,
+) -> Result<i128, CdpError> {
+    crate::interest_rate::calculate_accrued_interest(
+        vault.debt,
+        vault.last_accrual_time,
+        env.ledger().timestamp(),
+        collateral_type.stability_fee_bps,
+    )
+    .map_err(|_| CdpError::Overflow)
+}
+
+/// Accrue the stability fee on a vault and the collateral's debt totals
+fn accrue_vault(
+    env: &Env,
+    collateral: &Address,
+    vault: &mut CdpVault,
+    collateral_type: &CdpCollateralType,
+) -> Result<(), CdpError> {
+    let fee = pending_fee(env, vault, collateral_type)?;
+    if fee > 0 {
+        vault.debt = vault.debt.checked_add(fee).ok_or(CdpError::Overflow)?;
+        vault.fees = vault.fees.checked_add(fee).ok_or(CdpError::Overflow)?;
+        adjust_debt(env, collateral, fee)?;
+        let fees = get_i128(env, &CdpDataKey::FeesAccrued)
+            .checked_add(fee)
+            .ok_or(CdpError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&CdpDataKey::FeesAccrued, &fees);
+    }
+    vault.last_accrual_time = env.ledger().timestamp();
+    Ok(())
+}
+
+/// Book a repayment of `amount` against a vault, fees first
+fn apply_repayment(env: &Env, vault: &mut CdpVault, amount: i128) -> Result<(), CdpError> {
+    let fees_paid = amount.min(vault.fees);
+    if fees_paid > 0 {
+        vault.fees -= fees_paid;
+        let collected = get_i128(env, &CdpDataKey::FeesCollected)
+            .checked_add(fees_paid)
+            .ok_or(CdpError::Overflow)?;
+        env.storage()
+            .persistent()
+            .set(&CdpDataKey::FeesCollected, &collected);
+    }
+    vault.debt -= amount;
+    Ok(())
+}
+
+/// Oracle prices of (collateral, stablecoin)
+fn get_prices(
+    env: &Env,
+    config: &CdpConfig,
+    collateral: &Address,
+) -> Result<(i128, i128), CdpError> {
+    let collateral_price =
+        crate::oracle::get_price(env, collateral).map_err(|_| CdpError::PriceUnavailable)?;
+    let stable_price = crate::oracle::get_price(env, &config.stablecoin)
+        .map_err(|_| CdpError::PriceUnavailable)?;
+    if collateral_price <= 0 || stable_price <= 0 {
+        return Err(CdpError::PriceUnavailable);
+    }
+    Ok((collateral_price, stable_price))
+}
+
+/// Collateral ratio of a vault in basis points (`i128::MAX` when debt-free)
+fn collateral_ratio(
+    env: &Env,
+    config: &CdpConfig,
+    collateral: &Address,
+    vault: &CdpVault,
+) -> Result<i128, CdpError> {
+    if vault.debt == 0 {
+        return Ok(i128::MAX);
+    }
+    let (collateral_price, stable_price) = get_prices(env, config, collateral)?;
+    vault
+        .collateral
+        .checked_mul(collateral_price)
+        .ok_or(CdpError::Overflow)?
+        .checked_mul(BASIS_POINTS_SCALE)
+        .ok_or(CdpError::Overflow)?
+        .checked_div(stable_price)
+        .ok_or(CdpError::Overflow)?
+        .checked_div(vault.debt)
+        .ok_or(CdpError::Overflow)
+}
+
+fn require_safe(
+    env: &Env,
+    config: &CdpConfig,
+    collateral: &Address,
+    vault: &CdpVault,
+    collateral_type: &CdpCollateralType,
+) -> Result<(), CdpError> {
+    if collateral_ratio(env, config, collateral, vault)? < collateral_type.min_collateral_ratio_bps
+    {
+        return Err(CdpError::Undercollateralized);
+    }
+    Ok(())
+}
+
+fn validate_collateral_type(collateral_type: &CdpCollateralType) -> Result<(), CdpError> {
+    if collateral_type.liquidation_ratio_bps < BASIS_POINTS_SCALE
+        || collateral_type.min_collateral_ratio_bps < collateral_type.liquidation_ratio_bps
+    {
+        return Err(CdpError::InvalidConfig);
+    }
+    if !(0..=MAX_LIQUIDATION_PENALTY_BPS).contains(&collateral_type.liquidation_penalty_bps)
+        || !(0..=MAX_STABILITY_FEE_BPS).contains(&collateral_type.stability_fee_bps)
+        || collateral_type.debt_ceiling < 0
+    {
+        return Err(CdpError::InvalidConfig);
+    }
+    Ok(())
+}
+
+/// Initialize the CDP module with the protocol stablecoin (admin only)
+///
+/// # Arguments
+/// * `caller` - The caller address (must be admin)
+/// * `stablecoin` - Stellar asset whose admin is this contract
+/// * `debt_ceiling` - Maximum stablecoin debt across all collateral types
+///
+/// # Errors
+/// * `CdpError::Unauthorized` - If caller is not admin
+/// * `CdpError::AlreadyInitialized` - If the module is already initialized
+/// * `CdpError::InvalidConfig` - If the debt ceiling is negative
+pub fn initialize_cdp(
+    env: &Env,
+    caller: Address,
+    stablecoin: Address,
+    debt_ceiling: i128,
+) -> Result<(), CdpError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| CdpError::Unauthorized)?;
+    if get_cdp_config(env).is_some() {
+        return Err(CdpError::AlreadyInitialized);
+    }
+    if debt_ceiling < 0 {
+        return Err(CdpError::InvalidConfig);
+    }
+
+    env.storage().persistent().set(
+        &CdpDataKey::CdpConfig,
+        &CdpConfig {
+            stablecoin,
+            debt_ceiling,
+        },
+    );
+    Ok(())
+}
+
+/// Update the global stablecoin debt ceiling (admin only)
+pub fn set_cdp_debt_ceiling(
+    env: &Env,
+    caller: Address,
+    debt_ceiling: i128,
+) -> Result<(), CdpError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| CdpError::Unauthorized)?;
+    if debt_ceiling < 0 {
+        return Err(CdpError::InvalidConfig);
+    }
+
+    let mut config = require_config(env)?;
+    config.debt_ceiling = debt_ceiling;
+    env.storage()
+        .persistent()
+        .set(&CdpDataKey::CdpConfig, &config);
+    Ok(())
+}
+
+/// Approve or update a collateral type (admin only)
+///
+/// # Errors
+/// * `CdpError::Unauthorized` - If caller is not admin
+/// * `CdpError::NotInitialized` - If the module is not initialized
+/// * `CdpError::InvalidConfig` - If the parameters are out of range
+pub fn set_cdp_collateral_type(
+    env: &Env,
+    caller: Address,
+    collateral: Address,
+    collateral_type: CdpCollateralType,
+) -> Result<(), CdpError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| CdpError::Unauthorized)?;
+    let config = require_config(env)?;
+    if collateral == config.stablecoin {
+        return Err(CdpError::InvalidConfig);
+    }
+    validate_collateral_type(&collateral_type)?;
+
+    env.storage()
+        .persistent()
+        .set(&CdpDataKey::CollateralType(collateral), &collateral_type);
+    Ok(())
+}
+
+/// Lock collateral in the caller's vault
+///
+/// # Errors
+/// * `CdpError::OperationPaused` - If deposits are paused
+/// * `CdpError::AssetFrozen` - If the collateral is frozen
+///
+/// # Returns
+/// Returns the vault's new collateral balance
+pub fn cdp_deposit(
+    env: &Env,
+    owner: Address,
+    collateral: Address,
+    amount: i128,
+) -> Result<i128, CdpError> {
+    owner.require_auth();
+    if amount <= 0 {
+        return Err(CdpError::InvalidAmount);
+    }
+    require_not_paused(env, "pause_deposit")?;
+    require_not_frozen(env, &collateral)?;
+    require_config(env)?;
+    let collateral_type = require_collateral_type(env, &collateral)?;
+
+    let mut vault = load_vault(env, &owner, &collateral);
+    accrue_vault(env, &collateral, &mut vault, &collateral_type)?;
+
+    let token_client = soroban_sdk::token::Client::new(env, &collateral);
+    token_client.transfer(&owner, env.current_contract_address(), &amount);
+
+    vault.collateral = vault
+        .collateral
+        .checked_add(amount)
+        .ok_or(CdpError::Overflow)?;
+    save_vault(env, &owner, &collateral, &vault);
+
+    emit_cdp_collateral_deposited(
+        env,
+        CdpCollateralDepositedEvent {
+            owner,
+            collateral,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(vault.collateral)
+}
+
+/// Withdraw collateral from the caller's vault
+///
+/// # Errors
+/// * `CdpError::OperationPaused` - If withdrawals are paused
+/// * `CdpError::InsufficientCollateral` - If the vault holds less than `amount`
+/// * `CdpError::Undercollateralized` - If the vault would fall below its minimum ratio
+///
+/// # Returns
+/// Returns the vault's remaining collateral
+pub fn cdp_withdraw(
+    env: &Env,
+    owner: Address,
+    collateral: Address,
+    amount: i128,
+) -> Result<i128, CdpError> {
+    owner.require_auth();
+    if amount <= 0 {
+        return Err(CdpError::InvalidAmount);
+    }
+    require_not_paused(env, "pause_withdraw")?;
+    let config = require_config(env)?;
+    let collateral_type = require_collateral_type(env, &collateral)?;
+
+    let mut vault = load_vault(env, &owner, &collateral);
+    accrue_vault(env, &collateral, &mut vault, &collateral_type)?;
+    if amount > vault.collateral {
+        return Err(CdpError::InsufficientCollateral);
+    }
+
+    vault.collateral -= amount;
+    require_safe(env, &config, &collateral, &vault, &collateral_type)?;
+    save_vault(env, &owner, &collateral, &vault);
+
+    let token_client = soroban_sdk::token::Client::new(env, &collateral);
+    token_client.transfer(&env.current_contract_address(), &owner, &amount);
+
+    emit_cdp_collateral_withdrawn(
+        env,
+        CdpCollateralWithdrawnEvent {
+            owner,
+            collateral,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(vault.collateral)
+}
+
+/// Mint stablecoin against the caller's vault
+///
+/// # Errors
+/// * `CdpError::OperationPaused` - If borrowing is paused
+/// * `CdpError::AssetFrozen` - If the collateral is frozen
+/// * `CdpError::CollateralDisabled` - If the collateral type is disabled
+/// * `CdpError::DebtCeilingExceeded` - If a debt ceiling would be exceeded
+/// * `CdpError::Undercollateralized` - If the vault would fall below its minimum ratio
+///
+/// # Returns
+/// Returns the vault's new debt
+pub fn cdp_mint(
+    env: &Env,
+    owner: Address,
+    collateral: Address,
+    amount: i128,
+) -> Result<i128, CdpError> {
+    owner.require_auth();
+    if amount <= 0 {
+        return Err(CdpError::InvalidAmount);
+    }
+    require_not_paused(env, "pause_borrow")?;
+    require_not_frozen(env, &collateral)?;
+    let config = require_config(env)?;
+    let collateral_type = require_collateral_type(env, &collateral)?;
+    if !collateral_type.enabled {
+        return Err(CdpError::CollateralDisabled);
+    }
+
+    let mut vault = load_vault(env, &owner, &collateral);
+    accrue_vault(env, &collateral, &mut vault, &collateral_type)?;
+
+    let collateral_debt = get_cdp_collateral_debt(env, &collateral)
+        .checked_add(amount)
+        .ok_or(CdpError::Overflow)?;
+    let total_debt = get_cdp_total_debt(env)
+        .checked_add(amount)
+        .ok_or(CdpError::Overflow)?;
+    if collateral_debt > collateral_type.debt_ceiling || total_debt > config.debt_ceiling {
+        return Err(CdpError::DebtCeilingExceeded);
+    }
+
+    vault.debt = vault.debt.checked_add(amount).ok_or(CdpError::Overflow)?;
+    require_safe(env, &config, &collateral, &vault, &collateral_type)?;
+    save_vault(env, &owner, &collateral, &vault);
+    adjust_debt(env, &collateral, amount)?;
+
+    soroban_sdk::token::StellarAssetClient::new(env, &config.stablecoin).mint(&owner, &amount);
+
+    emit_cdp_mint(
+        env,
+        CdpMintEvent {
+            owner,
+            collateral,
+            amount,
+            debt: vault.debt,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(vault.debt)
+}
+
+/// Repay stablecoin debt on the caller's vault
+///
+/// Repayments above the outstanding debt are capped to the debt.
+///
+/// # Errors
+/// * `CdpError::OperationPaused` - If repayments are paused
+///
+/// # Returns
+/// Returns the vault's remaining debt
+pub fn cdp_repay(
+    env: &Env,
+    owner: Address,
+    collateral: Address,
+    amount: i128,
+) -> Result<i128, CdpError> {
+    owner.require_auth();
+    if amount <= 0 {
+        return Err(CdpError::InvalidAmount);
+    }
+    require_not_paused(env, "pause_repay")?;
+    let config = require_config(env)?;
+    let collateral_type = require_collateral_type(env, &collateral)?;
+
+    let mut vault = load_vault(env, &owner, &collateral);
+    accrue_vault(env, &collateral, &mut vault, &collateral_type)?;
+
+    let repaid = amount.min(vault.debt);
+    if repaid == 0 {
+        return Err(CdpError::InvalidAmount);
+    }
+    soroban_sdk::token::Client::new(env, &config.stablecoin).burn(&owner, &repaid);
+
+    apply_repayment(env, &mut vault, repaid)?;
+    save_vault(env, &owner, &collateral, &vault);
+    adjust_debt(env, &collateral, -repaid)?;
+
+    emit_cdp_repay(
+        env,
+        CdpRepayEvent {
+            payer: owner.clone(),
+            owner,
+            collateral,
+            amount: repaid,
+            debt: vault.debt,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(vault.debt)
+}
+
+/// Repay debt by selling vault collateral for stablecoin through the AMM router
+///
+/// This contract swaps on the deployed AMM contract `protocol`, which must be
+/// registered and enabled with the router. The vault is charged the
+/// collateral that left this contract; the stablecoin that arrived is burned
+/// against the vault debt and any excess is paid out to the owner.
+///
+/// # Arguments
+/// * `owner` - The vault owner
+/// * `collateral` - The vault's collateral asset
+/// * `collateral_amount` - Collateral to sell
+/// * `protocol` - Deployed AMM contract to swap on
+/// * `min_stable_out` - Minimum stablecoin to receive
+/// * `deadline` - Swap deadline
+///
+/// # Returns
+/// Returns the vault's remaining debt
+///
+/// # Errors
+/// * `CdpError::OperationPaused` - If repayments are paused
+/// * `CdpError::SwapFailed` - If `protocol` is not a registered AMM, the swap
+///   fails, or the balances moved do not match the swap
+#[allow(clippy::too_many_arguments)]
+pub fn cdp_repay_with_collateral(
+    env: &Env,
+    owner: Address,
+    collateral: Address,
+    collateral_amount: i128,
+    protocol: Address,
+    min_stable_out: i128,
+    deadline: u64,
+) -> Result<i128, CdpError> {
+    owner.require_auth();
+    if collateral_amount <= 0 {
+        return Err(CdpError::InvalidAmount);
+    }
+    require_not_paused(env, "pause_repay")?;
+    let config = require_config(env)?;
+    let collateral_type = require_collateral_type(env, &collateral)?;
+
+    let mut vault = load_vault(env, &owner, &collateral);
+    accrue_vault(env, &collateral, &mut vault, &collateral_type)?;
+    if collateral_amount > vault.collateral {
+        return Err(CdpError::InsufficientCollateral);
+    }
+
+    let registered = stellarlend_amm::amm::get_amm_protocols(env)
+        .ok()
+        .and_then(|protocols| protocols.get(protocol.clone()))
+        .is_some_and(|p| p.enabled);
+    if !registered || protocol == env.current_contract_address() {
+        return Err(CdpError::SwapFailed);
+    }
+
+    let contract = env.current_contract_address();
+    let collateral_client = soroban_sdk::token::Client::new(env, &collateral);
+    let stable_client = soroban_sdk::token::Client::new(env, &config.stablecoin);
+    let collateral_before = collateral_client.balance(&contract);
+    let stable_before = stable_client.balance(&contract);
+
+    // The AMM pulls the collateral from this contract
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: collateral.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (contract.clone(), protocol.clone(), collateral_amount).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+    let params = stellarlend_amm::SwapParams {
+        protocol: protocol.clone(),
+        token_in: Some(collateral.clone()),
+        token_out: Some(config.stablecoin.clone()),
+        amount_in: collateral_amount,
+        min_amount_out: min_stable_out,
+        slippage_tolerance: AMM_SLIPPAGE_TOLERANCE_BPS,
+        deadline,
+        use_oracle_floor: false,
+    };
+    match stellarlend_amm::AmmContractClient::new(env, &protocol)
+        .try_execute_swap(&contract, &params)
+    {
+        Ok(Ok(_)) => {}
+        _ => return Err(CdpError::SwapFailed),
+    }
+
+    // Book what actually moved, not what the AMM reported
+    let collateral_sold = collateral_before - collateral_client.balance(&contract);
+    let stable_out = stable_client.balance(&contract) - stable_before;
+    if collateral_sold <= 0
+        || collateral_sold > collateral_amount
+        || stable_out <= 0
+        || stable_out < min_stable_out
+    {
+        return Err(CdpError::SwapFailed);
+    }
+
+    let repaid = stable_out.min(vault.debt);
+    if repaid > 0 {
+        stable_client.burn(&env.current_contract_address(), &repaid);
+    }
+    if stable_out > repaid {
+        stable_client.transfer(
+            &env.current_contract_address(),
+            &owner,
+            &(stable_out - repaid),
+        );
+    }
+
+    vault.collateral -= collateral_sold;
+    apply_repayment(env, &mut vault, repaid)?;
+    require_safe(env, &config, &collateral, &vault, &collateral_type)?;
+    save_vault(env, &owner, &collateral, &vault);
+    adjust_debt(env, &collateral, -repaid)?;
+
+    emit_cdp_repay(
+        env,
+        CdpRepayEvent {
+            payer: env.current_contract_address(),
+            owner,
+            collateral,
+            amount: repaid,
+            debt: vault.debt,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(vault.debt)
+}
+
+/// Liquidate a vault below its liquidation ratio
+///
+/// The liquidator burns `debt_to_cover` stablecoin and receives collateral
+/// worth the covered debt plus the liquidation penalty, capped at the vault's
+/// collateral. If the seizure empties the vault, its remaining debt is
+/// written off as bad debt of the stablecoin.
+///
+/// # Errors
+/// * `CdpError::OperationPaused` - If liquidations are paused
+/// * `CdpError::NotLiquidatable` - If the vault is at or above its liquidation ratio
+/// * `CdpError::ExceedsCloseFactor` - If `debt_to_cover` exceeds the close factor
+///
+/// # Returns
+/// Returns a tuple (debt_covered, collateral_seized)
+pub fn liquidate_cdp(
+    env: &Env,
+    liquidator: Address,
+    owner: Address,
+    collateral: Address,
+    debt_to_cover: i128,
+) -> Result<(i128, i128), CdpError> {
+    liquidator.require_auth();
+    if debt_to_cover <= 0 {
+        return Err(CdpError::InvalidAmount);
+    }
+    require_not_paused(env, "pause_liquidate")?;
+    let config = require_config(env)?;
+    let collateral_type = require_collateral_type(env, &collateral)?;
+
+    let mut vault = load_vault(env, &owner, &collateral);
+    accrue_vault(env, &collateral, &mut vault, &collateral_type)?;
+    if vault.debt == 0
+        || collateral_ratio(env, &config, &collateral, &vault)?
+            >= collateral_type.liquidation_ratio_bps
+    {
+        return Err(CdpError::NotLiquidatable);
+    }
+
+    let max_cover = crate::risk_params::get_max_liquidatable_amount(env, vault.debt)
+        .map_err(|_| CdpError::Overflow)?;
+    if debt_to_cover > max_cover {
+        return Err(CdpError::ExceedsCloseFactor);
+    }
+
+    // Covered debt converted to collateral, plus the liquidation penalty
+    let (collateral_price, stable_price) = get_prices(env, &config, &collateral)?;
+    let collateral_seized = debt_to_cover
+        .checked_mul(stable_price)
+        .ok_or(CdpError::Overflow)?
+        .checked_mul(BASIS_POINTS_SCALE + collateral_type.liquidation_penalty_bps)
+        .ok_or(CdpError::Overflow)?
+        .checked_div(collateral_price)
+        .ok_or(CdpError::Overflow)?
+        .checked_div(BASIS_POINTS_SCALE)
+        .ok_or(CdpError::Overflow)?
+        .min(vault.collateral);
+
+    soroban_sdk::token::Client::new(env, &config.stablecoin).burn(&liquidator, &debt_to_cover);
+    soroban_sdk::token::Client::new(env, &collateral).transfer(
+        &env.current_contract_address(),
+        &liquidator,
+        &collateral_seized,
+    );
+
+    apply_repayment(env, &mut vault, debt_to_cover)?;
+    vault.collateral -= collateral_seized;
+    adjust_debt(env, &collateral, -debt_to_cover)?;
+
+    // Nothing backs the debt left once the last collateral is seized
+    if vault.collateral == 0 && vault.debt > 0 {
+        let residual = vault.debt;
+        adjust_debt(env, &collateral, -residual)?;
+        vault.debt = 0;
+        vault.fees = 0;
+        crate::bad_debt::record_bad_debt(
+            env,
+            &Some(config.stablecoin.clone()),
+            &owner,
+            residual,
+            Symbol::new(env, "cdp_liquidation"),
+        )
+        .map_err(|_| CdpError::Overflow)?;
+    }
+    save_vault(env, &owner, &collateral, &vault);
+
+    emit_cdp_liquidation(
+        env,
+        CdpLiquidationEvent {
+            liquidator,
+            owner,
+            collateral,
+            debt_covered: debt_to_cover,
+            collateral_seized,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok((debt_to_cover, collateral_seized))
+}
+
+/// Get a vault with the stability fee accrued up to now
+pub fn get_cdp_vault(env: &Env, owner: &Address, collateral: &Address) -> Option<CdpVault> {
+    let mut vault = env
+        .storage()
+        .persistent()
+        .get::<CdpDataKey, CdpVault>(&CdpDataKey::Vault(owner.clone(), collateral.clone()))?;
+    if let Some(collateral_type) = get_cdp_collateral_type(env, collateral) {
+        if let Ok(fee) = pending_fee(env, &vault, &collateral_type) {
+            vault.debt = vault.debt.saturating_add(fee);
+            vault.fees = vault.fees.saturating_add(fee);
+            vault.last_accrual_time = env.ledger().timestamp();
+        }
+    }
+    Some(vault)
+}
+
+/// Get a vault's collateral ratio in basis points (`i128::MAX` when debt-free)
+pub fn get_cdp_collateral_ratio(
+    env: &Env,
+    owner: &Address,
+    collateral: &Address,
+) -> Result<i128, CdpError> {
+    let config = require_config(env)?;
+    let vault = get_cdp_vault(env, owner, collateral).unwrap_or(CdpVault {
+        collateral: 0,
+        debt: 0,
+        fees: 0,
+        last_accrual_time: env.ledger().timestamp(),
+    });
+    collateral_ratio(env, &config, collateral, &vault)
+}
+
+/// Get the cumulative stability fees accrued
+pub fn get_cdp_fees_accrued(env: &Env) -> i128 {
+    get_i128(env, &CdpDataKey::FeesAccrued)
+}
+
+/// Get the cumulative stability fees repaid by vault owners and liquidators
+pub fn get_cdp_fees_collected(env: &Env) -> i128 {
+    get_i128(env, &CdpDataKey::FeesCollected)
+}
@@ -0,0 +1,206 @@
+//! # Config Export Module
+//!
+//! Exports the full protocol configuration as structured data and imports it
+//! into a fresh deployment in one call, keeping testnet and mainnet in parity.
+//!
+//! The configuration covers:
+//! - reserves (cross-asset configs, including caps and prices),
+//! - the native asset address,
+//! - risk parameters and the interest rate model,
+//! - oracle parameters and per-asset fallback oracles,
+//! - AMM settings and protocols,
+//! - role assignments.
+//!
+//! ## Invariants
+//! - `import_config` is admin only and refuses deployments that already have
+//!   reserves configured.
+//! - Every imported section is validated with the same rules as its setter;
+//!   any failure reverts the whole import.
+//! - The exported admin is informational; the importing admin stays in charge.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol, Vec};
+
+use crate::admin::RoleGrant;
+use crate::cross_asset::{AssetConfig, AssetKey};
+use crate::deposit::DepositDataKey;
+use crate::events::{emit_config_imported, ConfigImportedEvent};
+use crate::interest_rate::{InterestRateConfig, InterestRateDataKey};
+use crate::oracle::{OracleConfig, OracleDataKey};
+use crate::protocol_init::InitConfig;
+use crate::risk_params::{RiskParams, RiskParamsDataKey};
+use stellarlend_amm::{AmmProtocolConfig, AmmSettings};
+
+/// Errors that can occur during config export and import
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ConfigExportError {
+    /// Caller is not authorized (not admin)
+    Unauthorized = 1,
+    /// Deployment already has reserves configured
+    AlreadyConfigured = 2,
+    /// A section of the imported config failed validation
+    InvalidConfig = 3,
+    /// Protocol has not been initialized
+    NotInitialized = 4,
+}
+
+/// Fallback oracle of a priced asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleSourceConfig {
+    /// Priced asset
+    pub asset: Address,
+    /// Fallback oracle, if one is set
+    pub fallback_oracle: Option<Address>,
+}
+
+/// Full protocol configuration
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtocolConfigExport {
+    /// Protocol admin at export time (not imported)
+    pub admin: Option<Address>,
+    /// Native asset contract address
+    pub native_asset: Option<Address>,
+    /// Reserve configs, in registration order
+    pub reserves: Vec<AssetConfig>,
+    /// Liquidation risk parameters
+    pub risk_params: RiskParams,
+    /// Interest rate model
+    pub interest_rate_config: InterestRateConfig,
+    /// Oracle safety parameters
+    pub oracle_config: OracleConfig,
+    /// Oracle sources of every reserve with a priced asset
+    pub oracle_sources: Vec<OracleSourceConfig>,
+    /// AMM operation settings: empty if never initialized, otherwise one entry
+    pub amm_settings: Vec<AmmSettings>,
+    /// Registered AMM protocols
+    pub amm_protocols: Vec<AmmProtocolConfig>,
+    /// Role assignments
+    pub roles: Vec<RoleGrant>,
+}
+
+/// Export the full protocol configuration
+///
+/// # Errors
+/// * `ConfigExportError::NotInitialized` - If risk parameters or the interest rate model are unset
+pub fn export_config(env: &Env) -> Result<ProtocolConfigExport, ConfigExportError> {
+    let risk_params =
+        crate::risk_params::get_risk_params(env).ok_or(ConfigExportError::NotInitialized)?;
+    let interest_rate_config = crate::interest_rate::get_interest_rate_config(env)
+        .ok_or(ConfigExportError::NotInitialized)?;
+    let native_asset = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Address>(&DepositDataKey::NativeAssetAddress);
+
+    let mut reserves = Vec::new(env);
+    let mut oracle_sources = Vec::new(env);
+    for asset_key in crate::cross_asset::get_asset_list(env).iter() {
+        if let Ok(config) = crate::cross_asset::get_asset_config(env, &asset_key) {
+            reserves.push_back(config);
+        }
+        let priced_asset = match asset_key {
+            AssetKey::Token(address) => Some(address),
+            AssetKey::Native => native_asset.clone(),
+        };
+        if let Some(asset) = priced_asset {
+            let fallback_oracle = env
+                .storage()
+                .persistent()
+                .get::<OracleDataKey, Address>(&OracleDataKey::FallbackOracle(asset.clone()));
+            oracle_sources.push_back(OracleSourceConfig {
+                asset,
+                fallback_oracle,
+            });
+        }
+    }
+
+    let mut amm_settings = Vec::new(env);
+    if let Ok(settings) = stellarlend_amm::amm::get_amm_settings(env) {
+        amm_settings.push_back(settings);
+    }
+    let amm_protocols = match stellarlend_amm::amm::get_amm_protocols(env) {
+        Ok(protocols) => protocols.values(),
+        Err(_) => Vec::new(env),
+    };
+
+    Ok(ProtocolConfigExport {
+        admin: crate::admin::get_admin(env),
+        native_asset,
+        reserves,
+        risk_params,
+        interest_rate_config,
+        oracle_config: crate::oracle::get_oracle_config(env),
+        oracle_sources,
+        amm_settings,
+        amm_protocols,
+        roles: crate::admin::get_role_grants(env),
+    })
+}
+
+/// Import a protocol configuration into a fresh deployment (admin only)
+///
+/// # Arguments
+/// * `caller` - The caller address (must be admin)
+/// * `config` - The configuration to apply
+///
+/// # Errors
+/// * `ConfigExportError::AlreadyConfigured` - If reserves are already configured
+/// * `ConfigExportError::InvalidConfig` - If any section fails validation
+pub fn import_config(
+    env: &Env,
+    caller: Address,
+    config: ProtocolConfigExport,
+) -> Result<(), ConfigExportError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| ConfigExportError::Unauthorized)?;
+    if !crate::cross_asset::get_asset_list(env).is_empty() {
+        return Err(ConfigExportError::AlreadyConfigured);
+    }
+    let timestamp = env.ledger().timestamp();
+
+    let mut risk_params = config.risk_params;
+    crate::risk_params::validate_risk_params(&risk_params)
+        .map_err(|_| ConfigExportError::InvalidConfig)?;
+    risk_params.last_update = timestamp;
+    env.storage()
+        .persistent()
+        .set(&RiskParamsDataKey::RiskParamsConfig, &risk_params);
+
+    let mut rate_config = config.interest_rate_config;
+    crate::interest_rate::validate_interest_rate_config(&rate_config)
+        .map_err(|_| ConfigExportError::InvalidConfig)?;
+    rate_config.last_update = timestamp;
+    env.storage()
+        .persistent()
+        .set(&InterestRateDataKey::InterestRateConfig, &rate_config);
+
+    let init_config = InitConfig {
+        admin: caller.clone(),
+        native_asset: config.native_asset,
+        reserves: config.reserves.clone(),
+        oracle_config: config.oracle_config,
+        oracle_sources: config.oracle_sources,
+        amm_settings: config.amm_settings,
+        amm_protocols: config.amm_protocols.clone(),
+        roles: config.roles.clone(),
+    };
+    crate::protocol_init::apply_config(env, &init_config)
+        .map_err(|_| ConfigExportError::InvalidConfig)?;
+
+    emit_config_imported(
+        env,
+        ConfigImportedEvent {
+            admin: caller,
+            reserves: config.reserves.len(),
+            amm_protocols: config.amm_protocols.len(),
+            roles: config.roles.len(),
+            timestamp,
+        },
+    );
+
+    Ok(())
+}
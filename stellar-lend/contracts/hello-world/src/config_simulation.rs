@@ -0,0 +1,151 @@
+//! # Config Simulation Module
+//!
+//! Dry-runs a reserve config change against the live cross-asset positions so
+//! risk admins can measure its blast radius before queueing it.
+//!
+//! `simulate_config_change` values every account twice, once with the current
+//! configs and once with `new_config` substituted for the target reserve, and
+//! reports the accounts that are healthy now but would be liquidatable after
+//! the change. Nothing is written.
+//!
+//! ## Invariants
+//! - Health factors follow `get_user_position_summary`: liquidation-threshold
+//!   weighted collateral over debt value, liquidatable below 1.0x.
+//! - Accounts that are already liquidatable are not counted.
+//! - Reserve prices are used as stored; the simulation does not reject stale
+//!   prices, since it only compares two valuations of the same moment.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Map};
+
+use crate::cross_asset::{AssetConfig, AssetKey};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Reserve price scale (7 decimals)
+const PRICE_SCALE: i128 = 10_000_000;
+
+/// Errors that can occur during config simulation
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ConfigSimulationError {
+    /// Asset is not a registered reserve
+    AssetNotConfigured = 1,
+    /// New config names a different asset
+    AssetMismatch = 2,
+    /// New config fails reserve config validation
+    InvalidConfig = 3,
+}
+
+/// Impact of a simulated reserve config change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigChangeImpact {
+    /// Reserve the change targets (None = native)
+    pub asset: Option<Address>,
+    /// Accounts holding cross-asset positions
+    pub accounts_evaluated: u32,
+    /// Healthy accounts that would become liquidatable
+    pub newly_liquidatable: u32,
+    /// Collateral value of the newly liquidatable accounts, after the change
+    pub affected_collateral_value: i128,
+    /// Debt value of the newly liquidatable accounts, after the change
+    pub affected_debt_value: i128,
+}
+
+/// Value every account: account -> (collateral value, weighted collateral value, debt value)
+fn account_values(
+    env: &Env,
+    overridden: Option<(&AssetKey, &AssetConfig)>,
+) -> Map<Address, (i128, i128, i128)> {
+    let positions = crate::cross_asset::get_user_asset_positions(env);
+
+    let mut accounts: Map<Address, (i128, i128, i128)> = Map::new(env);
+    for (key, position) in positions.iter() {
+        let config = match overridden {
+            Some((asset_key, config)) if *asset_key == key.asset => config.clone(),
+            _ => match crate::cross_asset::get_asset_config(env, &key.asset) {
+                Ok(config) => config,
+                Err(_) => continue,
+            },
+        };
+
+        let collateral_value = position.collateral.saturating_mul(config.price) / PRICE_SCALE;
+        let weighted_value = if config.can_collateralize {
+            collateral_value.saturating_mul(config.liquidation_threshold) / BASIS_POINTS_SCALE
+        } else {
+            0
+        };
+        let debt_value = position
+            .debt_principal
+            .saturating_add(position.accrued_interest)
+            .saturating_mul(config.price)
+            / PRICE_SCALE;
+
+        let (collateral, weighted, debt) = accounts.get(key.user.clone()).unwrap_or((0, 0, 0));
+        accounts.set(
+            key.user,
+            (
+                collateral.saturating_add(collateral_value),
+                weighted.saturating_add(weighted_value),
+                debt.saturating_add(debt_value),
+            ),
+        );
+    }
+    accounts
+}
+
+fn is_liquidatable(weighted_collateral: i128, debt: i128) -> bool {
+    debt > 0 && weighted_collateral.saturating_mul(BASIS_POINTS_SCALE) / debt < BASIS_POINTS_SCALE
+}
+
+/// Simulate applying a reserve config now
+///
+/// # Arguments
+/// * `asset` - The reserve to change (None = native)
+/// * `new_config` - The proposed config, including price
+///
+/// # Returns
+/// Returns the accounts that would newly become liquidatable and their value
+pub fn simulate_config_change(
+    env: &Env,
+    asset: Option<Address>,
+    new_config: AssetConfig,
+) -> Result<ConfigChangeImpact, ConfigSimulationError> {
+    let asset_key = AssetKey::from_option(asset.clone());
+    crate::cross_asset::get_asset_config(env, &asset_key)
+        .map_err(|_| ConfigSimulationError::AssetNotConfigured)?;
+    if new_config.asset != asset {
+        return Err(ConfigSimulationError::AssetMismatch);
+    }
+    crate::cross_asset::require_valid_config(&new_config)
+        .map_err(|_| ConfigSimulationError::InvalidConfig)?;
+
+    let before = account_values(env, None);
+    let after = account_values(env, Some((&asset_key, &new_config)));
+
+    let mut impact = ConfigChangeImpact {
+        asset,
+        accounts_evaluated: before.len(),
+        newly_liquidatable: 0,
+        affected_collateral_value: 0,
+        affected_debt_value: 0,
+    };
+    for (user, (_, weighted, debt)) in before.iter() {
+        if is_liquidatable(weighted, debt) {
+            continue;
+        }
+        let (collateral_after, weighted_after, debt_after) = after.get(user).unwrap_or((0, 0, 0));
+        if is_liquidatable(weighted_after, debt_after) {
+            impact.newly_liquidatable += 1;
+            impact.affected_collateral_value = impact
+                .affected_collateral_value
+                .saturating_add(collateral_after);
+            impact.affected_debt_value = impact.affected_debt_value.saturating_add(debt_after);
+        }
+    }
+
+    Ok(impact)
+}
@@ -0,0 +1,193 @@
+//! # Credit Delegation Module
+//!
+//! Lets a depositor extend an uncollateralized credit line to another
+//! address. The delegator approves a borrowing allowance per asset with
+//! `approve_credit`; the delegatee then borrows with `borrow_with_credit`
+//! against the delegator's collateral and receives the funds, while the debt
+//! is booked on the delegator's position and counts against their health
+//! factor.
+//!
+//! Repayment is not restricted: the delegatee (or anyone else) settles the
+//! delegator's debt with the regular repay entrypoints. Any agreement on
+//! repayment between the two parties lives off-chain.
+//!
+//! ## Invariants
+//! - Only the delegator can set an allowance; setting it to 0 revokes it.
+//! - A delegated borrow consumes the allowance of its asset and passes every
+//!   regular borrow check on the delegator's position.
+//! - Allowances only decrease through borrowing; re-approving replaces them.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+use crate::events::{
+    emit_credit_approved, emit_credit_borrowed, CreditApprovedEvent, CreditBorrowedEvent,
+};
+
+/// Errors that can occur during credit delegation operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CreditDelegationError {
+    /// Amount must be positive (or zero to revoke an allowance)
+    InvalidAmount = 1,
+    /// A delegator cannot delegate credit to themselves
+    InvalidDelegatee = 2,
+    /// Amount exceeds the remaining allowance
+    InsufficientAllowance = 3,
+    /// The borrow was rejected on the delegator's position
+    BorrowFailed = 4,
+}
+
+/// Storage keys for credit delegation data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum CreditDelegationDataKey {
+    /// Remaining allowance of (delegator, delegatee, asset): i128
+    CreditAllowance(Address, Address, Option<Address>),
+}
+
+/// Get the remaining credit a delegator has extended to a delegatee
+pub fn get_credit_allowance(
+    env: &Env,
+    delegator: &Address,
+    delegatee: &Address,
+    asset: Option<Address>,
+) -> i128 {
+    let asset = crate::deposit::canonical_asset(env, asset);
+    env.storage()
+        .persistent()
+        .get::<CreditDelegationDataKey, i128>(&CreditDelegationDataKey::CreditAllowance(
+            delegator.clone(),
+            delegatee.clone(),
+            asset,
+        ))
+        .unwrap_or(0)
+}
+
+/// Approve a borrowing allowance for a delegatee, replacing any previous one
+///
+/// # Arguments
+/// * `delegator` - The depositor whose collateral backs the credit (must authorize)
+/// * `delegatee` - The address allowed to borrow
+/// * `asset` - The asset that may be borrowed (None for native XLM)
+/// * `amount` - The allowance (0 revokes it)
+///
+/// # Errors
+/// * `CreditDelegationError::InvalidDelegatee` - If delegator and delegatee match
+/// * `CreditDelegationError::InvalidAmount` - If the amount is negative
+pub fn approve_credit(
+    env: &Env,
+    delegator: Address,
+    delegatee: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(), CreditDelegationError> {
+    delegator.require_auth();
+
+    if delegatee == delegator {
+        return Err(CreditDelegationError::InvalidDelegatee);
+    }
+    if amount < 0 {
+        return Err(CreditDelegationError::InvalidAmount);
+    }
+
+    let asset = crate::deposit::canonical_asset(env, asset);
+    let key = CreditDelegationDataKey::CreditAllowance(
+        delegator.clone(),
+        delegatee.clone(),
+        asset.clone(),
+    );
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+    }
+
+    emit_credit_approved(
+        env,
+        CreditApprovedEvent {
+            delegator,
+            delegatee,
+            asset,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Borrow against a delegator's collateral within the approved allowance
+///
+/// The debt is booked on the delegator's position; the borrowed amount, net
+/// of the borrow fee, is paid to the delegatee.
+///
+/// # Arguments
+/// * `delegatee` - The borrower of the funds (must authorize)
+/// * `delegator` - The account whose position takes on the debt
+/// * `asset` - The asset to borrow (None for native XLM)
+/// * `amount` - The amount to borrow
+///
+/// # Returns
+/// Returns the delegator's total debt after the borrow
+///
+/// # Errors
+/// * `CreditDelegationError::InvalidAmount` - If the amount is not positive
+/// * `CreditDelegationError::InsufficientAllowance` - If the allowance is too low
+/// * `CreditDelegationError::BorrowFailed` - If the delegator's position
+///   cannot take on the debt
+pub fn borrow_with_credit(
+    env: &Env,
+    delegatee: Address,
+    delegator: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<i128, CreditDelegationError> {
+    delegatee.require_auth();
+
+    if amount <= 0 {
+        return Err(CreditDelegationError::InvalidAmount);
+    }
+    let asset = crate::deposit::canonical_asset(env, asset);
+    let allowance = get_credit_allowance(env, &delegator, &delegatee, asset.clone());
+    if amount > allowance {
+        return Err(CreditDelegationError::InsufficientAllowance);
+    }
+
+    let (total_debt, received) =
+        crate::borrow::borrow_internal(env, delegator.clone(), asset.clone(), amount, false, true)
+            .map_err(|_| CreditDelegationError::BorrowFailed)?;
+
+    let key = CreditDelegationDataKey::CreditAllowance(
+        delegator.clone(),
+        delegatee.clone(),
+        asset.clone(),
+    );
+    if allowance == amount {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &(allowance - amount));
+    }
+
+    // The funds go to the delegatee rather than the position owner
+    if let Some(token_addr) = crate::deposit::resolve_token_address(env, &asset) {
+        soroban_sdk::token::Client::new(env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &delegatee,
+            &received,
+        );
+    }
+
+    emit_credit_borrowed(
+        env,
+        CreditBorrowedEvent {
+            delegator,
+            delegatee,
+            asset,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(total_debt)
+}
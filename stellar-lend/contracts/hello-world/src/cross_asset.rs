@@ -10,12 +10,40 @@
 //! - Supply and borrow cap enforcement per asset
 //!
 //! ## Health Factor
-//! Computed as `weighted_collateral_value / weighted_debt_value * 10000`.
+//! A user may post several collateral assets and borrow several debt assets.
+//! Each asset with a position is one leg of the user's portfolio, and the
+//! health factor is computed over all legs at once:
+//!
+//! `sum(collateral_value * liquidation_threshold) / sum(debt_value) * 10000`
+//!
 //! A health factor below 10,000 (1.0x) makes the position liquidatable.
+//! Borrowing power uses the collateral factors instead of the liquidation
+//! thresholds, leaving a buffer between the two.
+//!
+//! LP shares of native AMM pools pledged as collateral (see `lp_collateral`)
+//! add one leg per pool token, valued at fair value less the token's LP
+//! haircut and weighted by the token's own factors.
+//!
+//! ## Liquidation
+//! A liquidatable portfolio is liquidated one debt/collateral pair at a
+//! time: the liquidator picks the debt asset to repay and the collateral
+//! asset to seize. Repayment is bounded by the debt asset's close factor
+//! (see `reserve::effective_close_factor`), and the collateral seized is
+//! worth the repaid debt plus the liquidation incentive from `risk_params`,
+//! capped at the borrower's balance of that collateral. Seized collateral is
+//! credited to the liquidator's own position. `get_best_liquidation`
+//! suggests the pair with the largest bonus.
 //!
 //! ## Invariants
-//! - Withdrawals and borrows are rejected if they would lower health factor below 1.0.
-//! - Prices must not be stale (> 1 hour old) for position calculations.
+//! - Withdrawals, borrows and outgoing collateral transfers (supply token
+//!   transfers) are rejected if they would lower health factor below 1.0.
+//! - Borrows are rejected if total debt value would exceed
+//!   `sum(collateral_value * collateral_factor)`.
+//! - Assets disabled as collateral add debt but no collateral weight.
+//! - Prices must not be older than the asset's `max_price_age` (1 hour by
+//!   default) for position calculations.
+//! - A user's reward emissions (see `rewards`) are credited, and their
+//!   reward boost recomputed, on each change to their balance in an asset.
 
 #![allow(dead_code)]
 use soroban_sdk::{contracterror, contracttype, symbol_short, Address, Env, Map, Symbol, Vec};
@@ -67,7 +95,7 @@ pub struct AssetPosition {
 pub struct UserPositionSummary {
     /// Total collateral value in USD (7 decimals)
     pub total_collateral_value: i128,
-    /// Total weighted collateral (considering collateral factors)
+    /// Total collateral weighted by liquidation thresholds
     pub weighted_collateral_value: i128,
     /// Total debt value in USD (7 decimals)
     pub total_debt_value: i128,
@@ -77,10 +105,47 @@ pub struct UserPositionSummary {
     pub health_factor: i128,
     /// Whether position can be liquidated
     pub is_liquidatable: bool,
-    /// Maximum additional borrow capacity in USD
+    /// Maximum additional borrow capacity in USD (collateral factor weighted)
     pub borrow_capacity: i128,
 }
 
+/// One asset of a user's cross-asset portfolio, valued at its current price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PortfolioLeg {
+    /// Asset (None for native XLM)
+    pub asset: Option<Address>,
+    /// Price used for valuation (7 decimals)
+    pub price: i128,
+    /// Collateral value in USD (7 decimals)
+    pub collateral_value: i128,
+    /// Debt value, principal plus interest, in USD (7 decimals)
+    pub debt_value: i128,
+    /// Collateral factor applied to borrowing power (0 if not collateral)
+    pub collateral_factor: i128,
+    /// Liquidation threshold applied to the health factor (0 if not collateral)
+    pub liquidation_threshold: i128,
+    /// Native AMM pool whose pledged LP shares this leg values (None for the
+    /// asset's own position)
+    pub lp_pool: Option<u32>,
+}
+
+/// Debt/collateral pair suggested for liquidating a portfolio
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationSuggestion {
+    /// Debt asset to repay (None for native XLM)
+    pub debt_asset: Option<Address>,
+    /// Collateral asset to seize (None for native XLM)
+    pub collateral_asset: Option<Address>,
+    /// Largest repayable debt in the pair, in the debt asset's units
+    pub debt_amount: i128,
+    /// Collateral seized for it, in the collateral asset's units
+    pub collateral_amount: i128,
+    /// Liquidation bonus in USD (7 decimals)
+    pub bonus_value: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AssetKey {
@@ -94,7 +159,8 @@ pub enum AssetKey {
 pub enum CrossAssetError {
     /// The specified asset has no configuration registered
     AssetNotConfigured = 1,
-    /// The asset is configured but disabled for the requested operation
+    /// The asset is configured but disabled for the requested operation, or
+    /// frozen
     AssetDisabled = 2,
     /// Insufficient collateral for the requested withdrawal or borrow
     InsufficientCollateral = 3,
@@ -108,10 +174,22 @@ pub enum CrossAssetError {
     BorrowCapExceeded = 7,
     /// Price is zero or negative
     InvalidPrice = 8,
-    /// Asset price is older than the staleness threshold (1 hour)
+    /// Asset price is older than the asset's `max_price_age`
     PriceStale = 9,
     /// Caller is not authorized (not admin)
     NotAuthorized = 10,
+    /// Portfolio is healthy or has no debt in the asset
+    NotLiquidatable = 11,
+    /// Repayment exceeds the debt asset's close factor
+    ExceedsCloseFactor = 12,
+    /// Amount must be greater than zero
+    InvalidAmount = 13,
+    /// Collateral factors must be changed through the parameter timelock
+    TimelockActive = 14,
+    /// The operation is paused (see `risk_management`)
+    OperationPaused = 15,
+    /// The native AMM pool does not exist
+    LpPoolNotFound = 16,
 }
 
 /// Admin address authorized for protocol management
@@ -151,6 +229,23 @@ pub fn initialize(env: &Env, admin: Address) -> Result<(), CrossAssetError> {
     Ok(())
 }
 
+/// Set the module admin if none is set, without requiring its auth again.
+///
+/// For callers that already authorized `admin` as the protocol admin.
+pub(crate) fn set_admin_if_unset(env: &Env, admin: &Address) {
+    if !env.storage().persistent().has(&ADMIN) {
+        env.storage().persistent().set(&ADMIN, admin);
+    }
+}
+
+/// Reject operations stopped by their pause switch
+pub(crate) fn require_not_paused(env: &Env, operation: &str) -> Result<(), CrossAssetError> {
+    if crate::risk_management::is_operation_paused(env, Symbol::new(env, operation)) {
+        return Err(CrossAssetError::OperationPaused);
+    }
+    Ok(())
+}
+
 fn require_admin(env: &Env) -> Result<(), CrossAssetError> {
     let admin: Address = env
         .storage()
@@ -184,6 +279,15 @@ pub fn initialize_asset(
 ) -> Result<(), CrossAssetError> {
     require_admin(env)?;
 
+    register_asset(env, asset, config)
+}
+
+/// Validate and register an asset config, for callers that already checked admin auth.
+pub(crate) fn register_asset(
+    env: &Env,
+    asset: Option<Address>,
+    config: AssetConfig,
+) -> Result<(), CrossAssetError> {
     require_valid_config(&config)?;
 
     let asset_key = AssetKey::from_option(asset.clone());
@@ -228,6 +332,8 @@ pub fn initialize_asset(
 /// # Errors
 /// * `NotAuthorized` - Caller is not the admin
 /// * `AssetNotConfigured` - Asset has not been initialized or factor out of range
+/// * `TimelockActive` - The collateral factor is changed while the parameter
+///   timelock is enabled
 #[allow(clippy::too_many_arguments)]
 pub fn update_asset_config(
     env: &Env,
@@ -240,6 +346,9 @@ pub fn update_asset_config(
     can_borrow: Option<bool>,
 ) -> Result<(), CrossAssetError> {
     require_admin(env)?;
+    if collateral_factor.is_some() && crate::param_timelock::is_param_timelock_active(env) {
+        return Err(CrossAssetError::TimelockActive);
+    }
 
     let asset_key = AssetKey::from_option(asset);
     let mut config = get_asset_config(env, &asset_key)?;
@@ -283,6 +392,28 @@ pub fn update_asset_config(
     Ok(())
 }
 
+/// Set an asset's collateral factor once a timelocked change is executed
+pub(crate) fn apply_collateral_factor(
+    env: &Env,
+    asset: Option<Address>,
+    collateral_factor: i128,
+) -> Result<(), CrossAssetError> {
+    require_valid_basis_points(collateral_factor)?;
+    let asset_key = AssetKey::from_option(asset);
+    let mut config = get_asset_config(env, &asset_key)?;
+    config.collateral_factor = collateral_factor;
+
+    let mut configs: Map<AssetKey, AssetConfig> = env
+        .storage()
+        .persistent()
+        .get(&ASSET_CONFIGS)
+        .unwrap_or(Map::new(env));
+    configs.set(asset_key, config);
+    env.storage().persistent().set(&ASSET_CONFIGS, &configs);
+
+    Ok(())
+}
+
 /// Update the oracle price for an asset.
 ///
 /// Records the new price and the current ledger timestamp for staleness checks.
@@ -318,9 +449,11 @@ pub fn update_asset_price(
         .get(&ASSET_CONFIGS)
         .unwrap_or(Map::new(env));
 
-    configs.set(asset_key, config);
+    configs.set(asset_key.clone(), config);
     env.storage().persistent().set(&ASSET_CONFIGS, &configs);
 
+    crate::health_history::record_threshold_crossings(env, &asset_key);
+
     Ok(())
 }
 
@@ -335,18 +468,35 @@ pub fn update_asset_price(
 /// Asset position or default empty position
 pub fn get_user_asset_position(env: &Env, user: &Address, asset: Option<Address>) -> AssetPosition {
     let key = UserAssetKey::new(user.clone(), asset);
+    extend_user_positions_ttl(env);
     let positions: Map<UserAssetKey, AssetPosition> = env
         .storage()
         .persistent()
         .get(&USER_POSITIONS)
         .unwrap_or(Map::new(env));
 
-    positions.get(key).unwrap_or(AssetPosition {
+    positions.get(key).unwrap_or(empty_position(env))
+}
+
+/// Position of a user with nothing in an asset
+fn empty_position(env: &Env) -> AssetPosition {
+    AssetPosition {
         collateral: 0,
         debt_principal: 0,
         accrued_interest: 0,
         last_updated: env.ledger().timestamp(),
-    })
+    }
+}
+
+/// Get every user-asset position
+///
+/// Used by read-only risk monitoring to aggregate across positions.
+pub(crate) fn get_user_asset_positions(env: &Env) -> Map<UserAssetKey, AssetPosition> {
+    extend_user_positions_ttl(env);
+    env.storage()
+        .persistent()
+        .get(&USER_POSITIONS)
+        .unwrap_or(Map::new(env))
 }
 
 /// Update user's position for a specific asset
@@ -369,29 +519,58 @@ fn set_user_asset_position(
         .get(&USER_POSITIONS)
         .unwrap_or(Map::new(env));
 
+    crate::rewards::checkpoint_user_rewards(env, user, &key.asset, &position);
     positions.set(key, position);
     env.storage().persistent().set(&USER_POSITIONS, &positions);
+    extend_user_positions_ttl(env);
 }
 
-/// Calculate a unified position summary across all registered assets.
+/// Keep the user-asset position map from being archived
+pub(crate) fn extend_user_positions_ttl(env: &Env) {
+    crate::storage_ttl::extend_persistent(env, &USER_POSITIONS);
+}
+
+/// Price of an asset for valuation (7 decimals).
 ///
-/// Iterates over all configured assets, aggregates collateral and debt values
-/// weighted by their respective factors, and computes the health factor.
-/// Prices older than 1 hour are rejected.
+/// Uses the asset's SEP-40 price feed when one is configured (see
+/// `price_oracle`), otherwise the price stored by `update_asset_price`.
+///
+/// # Errors
+/// * `PriceStale` - The price is older than the asset's `max_price_age`
+/// * `InvalidPrice` - The feed has no usable price for the asset
+pub(crate) fn get_reserve_price(
+    env: &Env,
+    asset: &Option<Address>,
+    config: &AssetConfig,
+) -> Result<i128, CrossAssetError> {
+    if let Some(feed_price) = crate::price_oracle::get_feed_price(env, asset) {
+        return feed_price.map_err(|e| match e {
+            crate::price_oracle::PriceOracleError::StalePrice => CrossAssetError::PriceStale,
+            _ => CrossAssetError::InvalidPrice,
+        });
+    }
+
+    crate::price_oracle::check_price_age(env, asset, config.price_updated_at)
+        .map_err(|_| CrossAssetError::PriceStale)?;
+    Ok(config.price)
+}
+
+/// Value every asset a user has a position in.
+///
+/// Prices come from the asset's price feed when configured; prices older
+/// than the asset's `max_price_age` are rejected.
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `user` - User address
 ///
 /// # Returns
-/// [`UserPositionSummary`] with health factor, liquidation status, and borrow capacity.
+/// One [`PortfolioLeg`] per asset with collateral or debt, in registration order.
 ///
 /// # Errors
-/// * `PriceStale` - Any asset with a non-zero position has a price older than 1 hour
-pub fn get_user_position_summary(
-    env: &Env,
-    user: &Address,
-) -> Result<UserPositionSummary, CrossAssetError> {
+/// * `PriceStale` - Any asset with a non-zero position has a stale price
+/// * `InvalidPrice` - A price feed has no usable price for such an asset
+pub fn get_portfolio(env: &Env, user: &Address) -> Result<Vec<PortfolioLeg>, CrossAssetError> {
     let asset_list: Vec<AssetKey> = env
         .storage()
         .persistent()
@@ -404,49 +583,87 @@ pub fn get_user_position_summary(
         .get(&ASSET_CONFIGS)
         .unwrap_or(Map::new(env));
 
-    let mut total_collateral_value: i128 = 0;
-    let mut weighted_collateral_value: i128 = 0;
-    let mut total_debt_value: i128 = 0;
-    let mut weighted_debt_value: i128 = 0;
-
-    for i in 0..asset_list.len() {
-        let asset_key = asset_list.get(i).unwrap();
-
-        if let Some(config) = configs.get(asset_key.clone()) {
-            let asset_option = asset_key.to_option();
-            let position = get_user_asset_position(env, user, asset_option);
-
-            if position.collateral == 0 && position.debt_principal == 0 {
-                continue;
-            }
-
-            let current_time = env.ledger().timestamp();
-            if current_time > config.price_updated_at
-                && current_time - config.price_updated_at > 3600
-            {
-                return Err(CrossAssetError::PriceStale);
-            }
+    let mut legs = Vec::new(env);
+    for asset_key in asset_list.iter() {
+        let Some(config) = configs.get(asset_key.clone()) else {
+            continue;
+        };
+        let asset_option = asset_key.to_option();
+        let position = get_user_asset_position(env, user, asset_option.clone());
+        if position.collateral == 0 && position.debt_principal == 0 {
+            continue;
+        }
 
-            let collateral_value = (position.collateral * config.price) / 10_000_000;
-            total_collateral_value += collateral_value;
+        let price = get_reserve_price(env, &asset_option, &config)?;
+        let total_debt = position
+            .debt_principal
+            .saturating_add(position.accrued_interest);
+        let (collateral_factor, liquidation_threshold) = if config.can_collateralize {
+            (config.collateral_factor, config.liquidation_threshold)
+        } else {
+            (0, 0)
+        };
+        legs.push_back(PortfolioLeg {
+            asset: asset_option,
+            price,
+            collateral_value: position.collateral.saturating_mul(price) / 10_000_000,
+            debt_value: total_debt.saturating_mul(price) / 10_000_000,
+            collateral_factor,
+            liquidation_threshold,
+            lp_pool: None,
+        });
+    }
+    crate::lp_collateral::append_portfolio_legs(env, user, &mut legs)?;
+    Ok(legs)
+}
 
-            if config.can_collateralize {
-                weighted_collateral_value +=
-                    (collateral_value * config.liquidation_threshold) / 10_000;
-            }
+/// Debt value a portfolio's collateral supports at collateral factors
+fn borrow_limit(legs: &Vec<PortfolioLeg>) -> i128 {
+    legs.iter().fold(0i128, |limit, leg| {
+        limit.saturating_add(leg.collateral_value.saturating_mul(leg.collateral_factor) / 10_000)
+    })
+}
 
-            let total_debt = position.debt_principal + position.accrued_interest;
-            let debt_value = (total_debt * config.price) / 10_000_000;
-            total_debt_value += debt_value;
+/// Calculate a unified position summary across all registered assets.
+///
+/// Aggregates the user's portfolio legs (see [`get_portfolio`]): collateral
+/// weighted by liquidation thresholds for the health factor, and by
+/// collateral factors for the borrow capacity.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `user` - User address
+///
+/// # Returns
+/// [`UserPositionSummary`] with health factor, liquidation status, and borrow capacity.
+///
+/// # Errors
+/// * `PriceStale` - Any asset with a non-zero position has a stale price
+/// * `InvalidPrice` - A price feed has no usable price for such an asset
+pub fn get_user_position_summary(
+    env: &Env,
+    user: &Address,
+) -> Result<UserPositionSummary, CrossAssetError> {
+    let legs = get_portfolio(env, user)?;
+    let mut total_collateral_value: i128 = 0;
+    let mut weighted_collateral_value: i128 = 0;
+    let mut total_debt_value: i128 = 0;
 
-            weighted_debt_value += debt_value;
-        }
+    for leg in legs.iter() {
+        total_collateral_value = total_collateral_value.saturating_add(leg.collateral_value);
+        weighted_collateral_value = weighted_collateral_value.saturating_add(
+            leg.collateral_value
+                .saturating_mul(leg.liquidation_threshold)
+                / 10_000,
+        );
+        total_debt_value = total_debt_value.saturating_add(leg.debt_value);
     }
+    let weighted_debt_value = total_debt_value;
 
     // Calculate health factor (weighted_collateral / weighted_debt * 10000)
     // Health factor of 1.0 = 10000, below 1.0 can be liquidated
     let health_factor = if weighted_debt_value > 0 {
-        (weighted_collateral_value * 10_000) / weighted_debt_value
+        weighted_collateral_value.saturating_mul(10_000) / weighted_debt_value
     } else {
         i128::MAX // No debt = infinite health
     };
@@ -454,12 +671,8 @@ pub fn get_user_position_summary(
     // Position is liquidatable if health factor < 1.0 (10000)
     let is_liquidatable = health_factor < 10_000 && weighted_debt_value > 0;
 
-    // Calculate remaining borrow capacity
-    let borrow_capacity = if weighted_collateral_value > weighted_debt_value {
-        weighted_collateral_value - weighted_debt_value
-    } else {
-        0
-    };
+    // Remaining borrowing power at collateral factors
+    let borrow_capacity = (borrow_limit(&legs) - weighted_debt_value).max(0);
 
     Ok(UserPositionSummary {
         total_collateral_value,
@@ -472,6 +685,86 @@ pub fn get_user_position_summary(
     })
 }
 
+/// Health factor of a user's whole cross-asset portfolio.
+///
+/// Scaled by 10_000 (1.0x); `i128::MAX` without debt.
+///
+/// # Errors
+/// * `PriceStale` - Any asset with a non-zero position has a stale price
+/// * `InvalidPrice` - A price feed has no usable price for such an asset
+pub fn get_health_factor(env: &Env, user: &Address) -> Result<i128, CrossAssetError> {
+    Ok(get_user_position_summary(env, user)?.health_factor)
+}
+
+/// Price of a collateral asset at which a user's health factor crosses 1.0.
+///
+/// Every other asset, and LP shares pledged in any pool, is held at its
+/// current price. When the user also owes
+/// the asset, both sides move with its price; if the debt outweighs the
+/// threshold-weighted collateral, the position is liquidated when the price
+/// rises to the returned value rather than falls to it.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `user` - User address
+/// * `collateral_asset` - Collateral asset (`None` for XLM)
+///
+/// # Returns
+/// The price (7 decimals), or 0 if no price of the asset makes the position
+/// liquidatable.
+///
+/// # Errors
+/// * `AssetNotConfigured` - Asset is not registered
+/// * `InsufficientCollateral` - The user holds no collateral in the asset
+/// * `PriceStale` - Any asset with a non-zero position has a stale price
+/// * `InvalidPrice` - A price feed has no usable price for such an asset
+pub fn get_liquidation_price(
+    env: &Env,
+    user: &Address,
+    collateral_asset: Option<Address>,
+) -> Result<i128, CrossAssetError> {
+    let config = get_asset_config(env, &AssetKey::from_option(collateral_asset.clone()))?;
+    let position = get_user_asset_position(env, user, collateral_asset.clone());
+    if position.collateral <= 0 {
+        return Err(CrossAssetError::InsufficientCollateral);
+    }
+
+    // Threshold-weighted collateral and debt of the other assets
+    let mut other_weighted_collateral: i128 = 0;
+    let mut other_debt: i128 = 0;
+    for leg in get_portfolio(env, user)?.iter() {
+        if leg.asset == collateral_asset && leg.lp_pool.is_none() {
+            continue;
+        }
+        other_weighted_collateral = other_weighted_collateral.saturating_add(
+            leg.collateral_value
+                .saturating_mul(leg.liquidation_threshold)
+                / 10_000,
+        );
+        other_debt = other_debt.saturating_add(leg.debt_value);
+    }
+
+    // At price p the health factor is 1.0 when
+    // other_weighted_collateral + p * net_weight / 10^7 = other_debt
+    let liquidation_threshold = if config.can_collateralize {
+        config.liquidation_threshold
+    } else {
+        0
+    };
+    let net_weight = (position.collateral.saturating_mul(liquidation_threshold) / 10_000)
+        .saturating_sub(
+            position
+                .debt_principal
+                .saturating_add(position.accrued_interest),
+        );
+    let shortfall = other_debt.saturating_sub(other_weighted_collateral);
+    if (net_weight > 0 && shortfall > 0) || (net_weight < 0 && shortfall < 0) {
+        Ok(shortfall.saturating_mul(10_000_000) / net_weight)
+    } else {
+        Ok(0)
+    }
+}
+
 /// Deposit collateral for a specific asset.
 ///
 /// Requires user authorization. Validates the asset is enabled for collateral
@@ -488,8 +781,9 @@ pub fn get_user_position_summary(
 ///
 /// # Errors
 /// * `AssetNotConfigured` - Asset is not registered
-/// * `AssetDisabled` - Asset is not enabled for collateral
+/// * `AssetDisabled` - Asset is not enabled for collateral or is frozen
 /// * `SupplyCapExceeded` - Deposit would exceed the asset's supply cap
+/// * `OperationPaused` - Deposits are paused
 pub fn cross_asset_deposit(
     env: &Env,
     user: Address,
@@ -497,11 +791,12 @@ pub fn cross_asset_deposit(
     amount: i128,
 ) -> Result<AssetPosition, CrossAssetError> {
     user.require_auth();
+    require_not_paused(env, "pause_deposit")?;
 
     let asset_key = AssetKey::from_option(asset.clone());
     let config = get_asset_config(env, &asset_key)?;
 
-    if !config.can_collateralize {
+    if !config.can_collateralize || crate::risk_management::is_asset_frozen(env, &asset) {
         return Err(CrossAssetError::AssetDisabled);
     }
 
@@ -519,6 +814,7 @@ pub fn cross_asset_deposit(
 
     set_user_asset_position(env, &user, asset, position.clone());
     update_total_supply(env, &asset_key, amount);
+    crate::health_history::record_cross_asset_snapshot(env, &user, "ca_deposit");
 
     Ok(position)
 }
@@ -542,6 +838,8 @@ pub fn cross_asset_deposit(
 /// * `InsufficientCollateral` - User's collateral balance is below `amount`
 /// * `UnhealthyPosition` - Withdrawal would drop health factor below 1.0
 /// * `PriceStale` - Stale price prevents health factor calculation
+/// * `OperationPaused` - Withdrawals are paused, unless the protocol is in
+///   emergency withdrawal mode and the user has no debt
 pub fn cross_asset_withdraw(
     env: &Env,
     user: Address,
@@ -549,6 +847,12 @@ pub fn cross_asset_withdraw(
     amount: i128,
 ) -> Result<AssetPosition, CrossAssetError> {
     user.require_auth();
+    // In emergency withdrawal mode suppliers without debt can always exit
+    let debt_free = !has_debt(env, &user);
+    let emergency_exit = debt_free && crate::risk_management::is_emergency_withdrawal_mode(env);
+    if !emergency_exit {
+        require_not_paused(env, "pause_withdraw")?;
+    }
 
     let asset_key = AssetKey::from_option(asset.clone());
 
@@ -563,24 +867,130 @@ pub fn cross_asset_withdraw(
 
     set_user_asset_position(env, &user, asset.clone(), position.clone());
 
-    let summary = get_user_position_summary(env, &user)?;
+    if !debt_free {
+        let summary = get_user_position_summary(env, &user)?;
 
-    if summary.total_debt_value > 0 && summary.health_factor < 10_000 {
-        position.collateral += amount;
-        set_user_asset_position(env, &user, asset, position);
-        return Err(CrossAssetError::UnhealthyPosition);
+        if summary.total_debt_value > 0 && summary.health_factor < 10_000 {
+            position.collateral += amount;
+            set_user_asset_position(env, &user, asset, position);
+            return Err(CrossAssetError::UnhealthyPosition);
+        }
     }
 
     update_total_supply(env, &asset_key, -amount);
+    crate::health_history::record_cross_asset_snapshot(env, &user, "ca_withdraw");
 
     Ok(position)
 }
 
+/// Whether a user owes principal or interest in any asset
+pub(crate) fn has_debt(env: &Env, user: &Address) -> bool {
+    get_asset_list(env).iter().any(|asset_key| {
+        let position = get_user_asset_position(env, user, asset_key.to_option());
+        position.debt_principal > 0 || position.accrued_interest > 0
+    })
+}
+
+/// Move collateral of one asset from one user's position to another's.
+///
+/// Backs transfers of the asset's supply token (see `supply_token`); the
+/// caller is responsible for authorization. The asset's total supply is
+/// unchanged. If the asset is enabled as collateral and the sender has debt,
+/// the sender's health factor must stay at or above 1.0, otherwise the
+/// transfer is rolled back.
+///
+/// # Errors
+/// * `InvalidAmount` - Amount is not positive
+/// * `InsufficientCollateral` - Sender's collateral balance is below `amount`
+/// * `UnhealthyPosition` - Transfer would drop the sender's health factor below 1.0
+/// * `PriceStale` - Stale price prevents health factor calculation
+pub(crate) fn transfer_collateral(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(), CrossAssetError> {
+    if amount <= 0 {
+        return Err(CrossAssetError::InvalidAmount);
+    }
+
+    let mut from_position = get_user_asset_position(env, from, asset.clone());
+    if from_position.collateral < amount {
+        return Err(CrossAssetError::InsufficientCollateral);
+    }
+    if from == to {
+        return Ok(());
+    }
+
+    from_position.collateral -= amount;
+    from_position.last_updated = env.ledger().timestamp();
+    set_user_asset_position(env, from, asset.clone(), from_position.clone());
+
+    let used_as_collateral = get_asset_config(env, &AssetKey::from_option(asset.clone()))
+        .map(|config| config.can_collateralize)
+        .unwrap_or(false);
+    if used_as_collateral {
+        let summary = get_user_position_summary(env, from)?;
+        if summary.total_debt_value > 0 && summary.health_factor < 10_000 {
+            from_position.collateral += amount;
+            set_user_asset_position(env, from, asset, from_position);
+            return Err(CrossAssetError::UnhealthyPosition);
+        }
+    }
+
+    let mut to_position = get_user_asset_position(env, to, asset.clone());
+    to_position.collateral += amount;
+    to_position.last_updated = env.ledger().timestamp();
+    set_user_asset_position(env, to, asset, to_position);
+
+    crate::health_history::record_cross_asset_snapshot(env, from, "ca_transfer");
+    crate::health_history::record_cross_asset_snapshot(env, to, "ca_transfer");
+
+    Ok(())
+}
+
+/// Whether a user holds collateral or debt in any asset, or pledged LP shares
+pub(crate) fn has_asset_positions(env: &Env, user: &Address) -> bool {
+    let holds_asset = get_user_asset_positions(env).iter().any(|(key, position)| {
+        key.user == *user
+            && (position.collateral > 0
+                || position.debt_principal > 0
+                || position.accrued_interest > 0)
+    });
+    holds_asset || !crate::lp_collateral::get_pledged_pools(env, user).is_empty()
+}
+
+/// Move every asset position and pledged LP share of one user to another.
+///
+/// Backs transfers of whole account positions (see `position_token`); the
+/// caller must make sure `to` holds no positions. Asset totals are unchanged.
+pub(crate) fn move_user_positions(env: &Env, from: &Address, to: &Address) {
+    let mut positions = get_user_asset_positions(env);
+    for (key, position) in positions.clone().iter() {
+        if key.user == *from {
+            crate::rewards::checkpoint_user_rewards(env, from, &key.asset, &empty_position(env));
+            crate::rewards::checkpoint_user_rewards(env, to, &key.asset, &position);
+            positions.remove(key.clone());
+            positions.set(
+                UserAssetKey {
+                    user: to.clone(),
+                    asset: key.asset,
+                },
+                position,
+            );
+        }
+    }
+    env.storage().persistent().set(&USER_POSITIONS, &positions);
+    crate::lp_collateral::move_pledges(env, from, to);
+}
+
 /// Borrow a specific asset against cross-asset collateral.
 ///
 /// Requires user authorization. Validates the asset is enabled for borrowing,
-/// checks the borrow cap, and verifies the post-borrow health factor stays
-/// above 1.0. If the health check fails, the borrow is rolled back.
+/// checks the borrow cap, and verifies the post-borrow debt value stays
+/// within the collateral-factor weighted value of all the user's collateral.
+/// If the check fails, the borrow is rolled back.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -593,9 +1003,10 @@ pub fn cross_asset_withdraw(
 ///
 /// # Errors
 /// * `AssetNotConfigured` - Asset is not registered
-/// * `AssetDisabled` - Asset is not enabled for borrowing
+/// * `AssetDisabled` - Asset is not enabled for borrowing or is frozen
 /// * `BorrowCapExceeded` - Borrow would exceed the asset's borrow cap
-/// * `ExceedsBorrowCapacity` - Health factor would drop below 1.0
+/// * `OperationPaused` - Borrows are paused
+/// * `ExceedsBorrowCapacity` - Debt would exceed the portfolio's borrowing power
 /// * `PriceStale` - Stale price prevents health factor calculation
 pub fn cross_asset_borrow(
     env: &Env,
@@ -604,11 +1015,12 @@ pub fn cross_asset_borrow(
     amount: i128,
 ) -> Result<AssetPosition, CrossAssetError> {
     user.require_auth();
+    require_not_paused(env, "pause_borrow")?;
 
     let asset_key = AssetKey::from_option(asset.clone());
     let config = get_asset_config(env, &asset_key)?;
 
-    if !config.can_borrow {
+    if !config.can_borrow || crate::risk_management::is_asset_frozen(env, &asset) {
         return Err(CrossAssetError::AssetDisabled);
     }
 
@@ -626,15 +1038,19 @@ pub fn cross_asset_borrow(
 
     set_user_asset_position(env, &user, asset.clone(), position.clone());
 
-    let summary = get_user_position_summary(env, &user)?;
+    let legs = get_portfolio(env, &user)?;
+    let debt_value = legs
+        .iter()
+        .fold(0i128, |total, leg| total.saturating_add(leg.debt_value));
 
-    if summary.health_factor < 10_000 {
+    if debt_value > borrow_limit(&legs) {
         position.debt_principal -= amount;
         set_user_asset_position(env, &user, asset, position);
         return Err(CrossAssetError::ExceedsBorrowCapacity);
     }
 
     update_total_borrow(env, &asset_key, amount);
+    crate::health_history::record_cross_asset_snapshot(env, &user, "ca_borrow");
 
     Ok(position)
 }
@@ -659,6 +1075,7 @@ pub fn cross_asset_repay(
     amount: i128,
 ) -> Result<AssetPosition, CrossAssetError> {
     user.require_auth();
+    require_not_paused(env, "pause_repay")?;
 
     let asset_key = AssetKey::from_option(asset.clone());
 
@@ -682,10 +1099,232 @@ pub fn cross_asset_repay(
     // Update storage
     set_user_asset_position(env, &user, asset, position.clone());
     update_total_borrow(env, &asset_key, -repay_amount);
+    crate::health_history::record_cross_asset_snapshot(env, &user, "ca_repay");
 
     Ok(position)
 }
 
+/// Collateral seized for repaying `debt_amount`, before capping at the balance
+fn collateral_for_debt(
+    debt_amount: i128,
+    debt_price: i128,
+    collateral_price: i128,
+    incentive_bps: i128,
+) -> i128 {
+    debt_amount
+        .saturating_mul(debt_price)
+        .saturating_mul(10_000 + incentive_bps)
+        / collateral_price.saturating_mul(10_000)
+}
+
+pub(crate) fn liquidation_incentive(env: &Env) -> i128 {
+    crate::risk_params::get_liquidation_incentive(env).unwrap_or(1_000)
+}
+
+/// Repay `debt_amount` of a liquidated borrower's debt, interest first
+pub(crate) fn repay_liquidated_debt(
+    env: &Env,
+    borrower: &Address,
+    debt_asset: &Option<Address>,
+    mut debt_position: AssetPosition,
+    debt_amount: i128,
+) {
+    if debt_amount <= debt_position.accrued_interest {
+        debt_position.accrued_interest -= debt_amount;
+    } else {
+        debt_position.debt_principal -= debt_amount - debt_position.accrued_interest;
+        debt_position.accrued_interest = 0;
+    }
+    debt_position.last_updated = env.ledger().timestamp();
+    set_user_asset_position(env, borrower, debt_asset.clone(), debt_position);
+    update_total_borrow(
+        env,
+        &AssetKey::from_option(debt_asset.clone()),
+        -debt_amount,
+    );
+}
+
+/// Liquidate one debt/collateral pair of an unhealthy portfolio.
+///
+/// Requires liquidator authorization. Repays `debt_amount` of the borrower's
+/// `debt_asset` debt (interest first) and moves the matching collateral plus
+/// the liquidation incentive from the borrower's `collateral_asset` position
+/// to the liquidator's, capped at the borrower's balance.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `liquidator` - Liquidator (must authorize)
+/// * `borrower` - Owner of the unhealthy portfolio
+/// * `debt_asset` - Asset whose debt is repaid (`None` for XLM)
+/// * `collateral_asset` - Asset whose collateral is seized (`None` for XLM)
+/// * `debt_amount` - Debt to repay, in the debt asset's units
+///
+/// # Returns
+/// `(debt_repaid, collateral_seized)`
+///
+/// # Errors
+/// * `InvalidAmount` - `debt_amount` is not positive
+/// * `NotAuthorized` - The liquidator is the borrower
+/// * `NotLiquidatable` - The portfolio is healthy or owes no `debt_asset`
+/// * `AssetDisabled` - `collateral_asset` is not enabled for collateral
+/// * `InsufficientCollateral` - The borrower has no `collateral_asset` collateral
+/// * `ExceedsCloseFactor` - `debt_amount` exceeds the close factor
+/// * `PriceStale` - Stale price prevents health factor calculation
+/// * `OperationPaused` - Liquidations are paused
+pub fn cross_asset_liquidate(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_asset: Option<Address>,
+    collateral_asset: Option<Address>,
+    debt_amount: i128,
+) -> Result<(i128, i128), CrossAssetError> {
+    liquidator.require_auth();
+    require_not_paused(env, "pause_liquidate")?;
+    if debt_amount <= 0 {
+        return Err(CrossAssetError::InvalidAmount);
+    }
+    if liquidator == borrower {
+        return Err(CrossAssetError::NotAuthorized);
+    }
+
+    let summary = get_user_position_summary(env, &borrower)?;
+    if !summary.is_liquidatable {
+        return Err(CrossAssetError::NotLiquidatable);
+    }
+
+    let debt_key = AssetKey::from_option(debt_asset.clone());
+    let debt_config = get_asset_config(env, &debt_key)?;
+    let collateral_config =
+        get_asset_config(env, &AssetKey::from_option(collateral_asset.clone()))?;
+    if !collateral_config.can_collateralize {
+        return Err(CrossAssetError::AssetDisabled);
+    }
+
+    let debt_position = get_user_asset_position(env, &borrower, debt_asset.clone());
+    let total_debt = debt_position
+        .debt_principal
+        .saturating_add(debt_position.accrued_interest);
+    if total_debt == 0 {
+        return Err(CrossAssetError::NotLiquidatable);
+    }
+    let close_factor =
+        crate::reserve::effective_close_factor(env, &debt_asset, summary.health_factor);
+    if debt_amount > total_debt.saturating_mul(close_factor) / 10_000 {
+        return Err(CrossAssetError::ExceedsCloseFactor);
+    }
+
+    let collateral_balance =
+        get_user_asset_position(env, &borrower, collateral_asset.clone()).collateral;
+    if collateral_balance == 0 {
+        return Err(CrossAssetError::InsufficientCollateral);
+    }
+    let debt_price = get_reserve_price(env, &debt_asset, &debt_config)?;
+    let collateral_price = get_reserve_price(env, &collateral_asset, &collateral_config)?;
+    let collateral_seized = collateral_for_debt(
+        debt_amount,
+        debt_price,
+        collateral_price,
+        liquidation_incentive(env),
+    )
+    .min(collateral_balance);
+
+    repay_liquidated_debt(env, &borrower, &debt_asset, debt_position, debt_amount);
+
+    // Re-read in case the debt and collateral legs are the same asset
+    let now = env.ledger().timestamp();
+    let mut collateral_position = get_user_asset_position(env, &borrower, collateral_asset.clone());
+    collateral_position.collateral -= collateral_seized;
+    collateral_position.last_updated = now;
+    set_user_asset_position(
+        env,
+        &borrower,
+        collateral_asset.clone(),
+        collateral_position,
+    );
+
+    let mut liquidator_position =
+        get_user_asset_position(env, &liquidator, collateral_asset.clone());
+    liquidator_position.collateral += collateral_seized;
+    liquidator_position.last_updated = now;
+    set_user_asset_position(env, &liquidator, collateral_asset, liquidator_position);
+
+    crate::health_history::record_cross_asset_snapshot(env, &borrower, "ca_liquidate");
+
+    Ok((debt_amount, collateral_seized))
+}
+
+/// Suggest the debt/collateral pair that maximizes a liquidator's bonus.
+///
+/// Every pair of a debt leg and a collateral leg of the portfolio is sized
+/// at the largest repayment allowed by the close factor and the collateral
+/// available; the pair with the largest bonus wins. Pledged LP shares are
+/// seized through `lp_collateral::liquidate_lp_collateral` instead and are
+/// not suggested.
+///
+/// # Returns
+/// `None` if the portfolio is not liquidatable.
+///
+/// # Errors
+/// * `PriceStale` - Any asset with a non-zero position has a stale price
+/// * `InvalidPrice` - A price feed has no usable price for such an asset
+pub fn get_best_liquidation(
+    env: &Env,
+    borrower: &Address,
+) -> Result<Option<LiquidationSuggestion>, CrossAssetError> {
+    let summary = get_user_position_summary(env, borrower)?;
+    if !summary.is_liquidatable {
+        return Ok(None);
+    }
+
+    let legs = get_portfolio(env, borrower)?;
+    let incentive_bps = liquidation_incentive(env);
+    let mut best: Option<LiquidationSuggestion> = None;
+    for debt_leg in legs.iter() {
+        if debt_leg.debt_value == 0 {
+            continue;
+        }
+        let close_factor =
+            crate::reserve::effective_close_factor(env, &debt_leg.asset, summary.health_factor);
+        let max_repay_value = debt_leg.debt_value.saturating_mul(close_factor) / 10_000;
+        for collateral_leg in legs.iter() {
+            if collateral_leg.collateral_value == 0
+                || collateral_leg.liquidation_threshold == 0
+                || collateral_leg.lp_pool.is_some()
+            {
+                continue;
+            }
+            let repay_value = max_repay_value.min(
+                collateral_leg.collateral_value.saturating_mul(10_000) / (10_000 + incentive_bps),
+            );
+            let bonus_value = repay_value.saturating_mul(incentive_bps) / 10_000;
+            if best
+                .as_ref()
+                .is_some_and(|best| best.bonus_value >= bonus_value)
+            {
+                continue;
+            }
+            let debt_amount = repay_value.saturating_mul(10_000_000) / debt_leg.price;
+            let collateral =
+                get_user_asset_position(env, borrower, collateral_leg.asset.clone()).collateral;
+            best = Some(LiquidationSuggestion {
+                debt_asset: debt_leg.asset.clone(),
+                collateral_asset: collateral_leg.asset.clone(),
+                debt_amount,
+                collateral_amount: collateral_for_debt(
+                    debt_amount,
+                    debt_leg.price,
+                    collateral_leg.price,
+                    incentive_bps,
+                )
+                .min(collateral),
+                bonus_value,
+            });
+        }
+    }
+    Ok(best)
+}
+
 /// Return the list of all registered asset keys.
 ///
 /// Returns an empty vector if no assets have been configured.
@@ -717,7 +1356,7 @@ pub fn get_asset_config_by_address(
 
 // Helper functions
 
-fn get_asset_config(env: &Env, asset_key: &AssetKey) -> Result<AssetConfig, CrossAssetError> {
+pub(crate) fn get_asset_config(env: &Env, asset_key: &AssetKey) -> Result<AssetConfig, CrossAssetError> {
     let configs: Map<AssetKey, AssetConfig> = env
         .storage()
         .persistent()
@@ -729,7 +1368,7 @@ fn get_asset_config(env: &Env, asset_key: &AssetKey) -> Result<AssetConfig, Cros
         .ok_or(CrossAssetError::AssetNotConfigured)
 }
 
-fn require_valid_config(config: &AssetConfig) -> Result<(), CrossAssetError> {
+pub(crate) fn require_valid_config(config: &AssetConfig) -> Result<(), CrossAssetError> {
     require_valid_basis_points(config.collateral_factor)?;
     require_valid_basis_points(config.liquidation_threshold)?;
     require_valid_basis_points(config.reserve_factor)?;
@@ -753,7 +1392,8 @@ fn require_valid_basis_points(value: i128) -> Result<(), CrossAssetError> {
     Ok(())
 }
 
-fn get_total_supply(env: &Env, asset_key: &AssetKey) -> i128 {
+pub(crate) fn get_total_supply(env: &Env, asset_key: &AssetKey) -> i128 {
+    crate::storage_ttl::extend_persistent(env, &TOTAL_SUPPLIES);
     let supplies: Map<AssetKey, i128> = env
         .storage()
         .persistent()
@@ -773,9 +1413,11 @@ fn update_total_supply(env: &Env, asset_key: &AssetKey, delta: i128) {
     let current = supplies.get(asset_key.clone()).unwrap_or(0);
     supplies.set(asset_key.clone(), current + delta);
     env.storage().persistent().set(&TOTAL_SUPPLIES, &supplies);
+    crate::storage_ttl::extend_persistent(env, &TOTAL_SUPPLIES);
 }
 
-fn get_total_borrow(env: &Env, asset_key: &AssetKey) -> i128 {
+pub(crate) fn get_total_borrow(env: &Env, asset_key: &AssetKey) -> i128 {
+    crate::storage_ttl::extend_persistent(env, &TOTAL_BORROWS);
     let borrows: Map<AssetKey, i128> = env
         .storage()
         .persistent()
@@ -795,6 +1437,7 @@ fn update_total_borrow(env: &Env, asset_key: &AssetKey, delta: i128) {
     let current = borrows.get(asset_key.clone()).unwrap_or(0);
     borrows.set(asset_key.clone(), current + delta);
     env.storage().persistent().set(&TOTAL_BORROWS, &borrows);
+    crate::storage_ttl::extend_persistent(env, &TOTAL_BORROWS);
 }
 
 /// Combined key for user-asset position lookups
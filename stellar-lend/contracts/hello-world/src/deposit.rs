@@ -21,13 +21,22 @@
 //! - Deposit amount must be strictly positive.
 //! - Deposits are rejected when the protocol or deposit operation is paused.
 //! - Token transfers use `transfer_from`, requiring prior user approval.
+//! - Deposits on behalf of another user pull tokens from the payer, who must
+//!   authorize the call; the credited user's position is the only one changed.
+//!
+//! ## Native XLM
+//! Native XLM (`asset = None`) is transferred through the Stellar Asset
+//! Contract configured with `set_native_asset_address`. Passing that contract's
+//! address as the asset is treated exactly like `None`, so native and wrapped
+//! XLM are never booked under two keys.
 
 #![allow(unused)]
 use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
 use crate::events::{
-    emit_analytics_updated, emit_deposit, emit_position_updated, emit_user_activity_tracked,
-    AnalyticsUpdatedEvent, DepositEvent, PositionUpdatedEvent, UserActivityTrackedEvent,
+    emit_analytics_updated, emit_deposit, emit_deposit_on_behalf, emit_position_updated,
+    emit_user_activity_tracked, AnalyticsUpdatedEvent, DepositEvent, DepositOnBehalfEvent,
+    PositionUpdatedEvent, UserActivityTrackedEvent,
 };
 
 /// Errors that can occur during deposit operations
@@ -49,6 +58,10 @@ pub enum DepositError {
     Overflow = 6,
     /// Reentrancy detected
     Reentrancy = 7,
+    /// Deposit would exceed the asset's supply cap
+    SupplyCapExceeded = 8,
+    /// Asset is frozen (see `risk_management`)
+    AssetFrozen = 9,
 }
 
 /// Storage keys for deposit-related data
@@ -208,6 +221,81 @@ pub fn deposit_collateral(
     user: Address,
     asset: Option<Address>,
     amount: i128,
+) -> Result<i128, DepositError> {
+    deposit_internal(env, Some(&user.clone()), user, asset, amount)
+}
+
+/// Deposit collateral paid by one account and credited to another
+///
+/// Lets exchanges and smart wallets fund user positions from an omnibus
+/// account. The tokens are pulled from `payer`; the collateral, analytics and
+/// deposit event belong to `on_behalf_of`.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `payer` - The account the tokens are pulled from (must authorize)
+/// * `on_behalf_of` - The user whose position is credited
+/// * `asset` - The address of the asset contract to deposit (None for native XLM)
+/// * `amount` - The amount to deposit
+///
+/// # Returns
+/// Returns the updated collateral balance of `on_behalf_of`
+///
+/// # Errors
+/// Same as `deposit_collateral`; `DepositError::InsufficientBalance` refers
+/// to the payer's balance
+pub fn deposit_collateral_for(
+    env: &Env,
+    payer: Address,
+    on_behalf_of: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<i128, DepositError> {
+    payer.require_auth();
+
+    let new_collateral = deposit_internal(
+        env,
+        Some(&payer),
+        on_behalf_of.clone(),
+        asset.clone(),
+        amount,
+    )?;
+    if payer != on_behalf_of {
+        emit_deposit_on_behalf(
+            env,
+            DepositOnBehalfEvent {
+                payer,
+                user: on_behalf_of,
+                asset,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+    Ok(new_collateral)
+}
+
+/// Deposit collateral the contract already holds into `user`'s position
+///
+/// Used when the tokens were produced on-chain, e.g. by an AMM swap of
+/// borrowed funds, so there is nothing to pull from an account.
+pub(crate) fn deposit_held_collateral(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<i128, DepositError> {
+    deposit_internal(env, None, user, asset, amount)
+}
+
+/// Deposit `amount` paid by `payer` into `user`'s position; `None` credits
+/// tokens already held by the contract
+fn deposit_internal(
+    env: &Env,
+    payer: Option<&Address>,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
 ) -> Result<i128, DepositError> {
     // Validate amount
     if amount <= 0 {
@@ -215,9 +303,7 @@ pub fn deposit_collateral(
     }
 
     // Check if deposits are paused
-    // Note: The risk management system provides pause functionality through the public API.
-    // This check maintains backward compatibility with the old pause switch system.
-    // The risk management pause switches are checked at the contract level in lib.rs.
+    // Note: This check maintains backward compatibility with the old pause switch system.
     let pause_switches_key = DepositDataKey::PauseSwitches;
     if let Some(pause_map) = env
         .storage()
@@ -232,13 +318,18 @@ pub fn deposit_collateral(
     }
 
     // Check risk management emergency pause and operation pause
-    // We access the risk management storage directly to check pause status
     check_risk_management_pause(env)?;
 
     // Get current timestamp
     let timestamp = env.ledger().timestamp();
 
-    // Handle asset transfer
+    // Wrapped native XLM is booked as native XLM
+    let asset = canonical_asset(env, asset);
+    if crate::risk_management::is_asset_frozen(env, &asset) {
+        return Err(DepositError::AssetFrozen);
+    }
+
+    // Validate asset
     if let Some(ref asset_addr) = asset {
         // Validate asset address - ensure it's not the contract itself
         if asset_addr == &env.current_contract_address() {
@@ -261,32 +352,38 @@ pub fn deposit_collateral(
                 return Err(DepositError::InvalidAmount);
             }
         }
+    }
+
+    // Enforce the asset's supply cap
+    crate::reserve::check_supply_cap(env, &asset, amount)
+        .map_err(|_| DepositError::SupplyCapExceeded)?;
 
-        // Transfer tokens from user to contract using token contract
+    // Handle asset transfer; native XLM goes through its Stellar Asset Contract
+    if let (Some(token_addr), Some(payer)) = (resolve_token_address(env, &asset), payer) {
+        // Transfer tokens from payer to contract using token contract
         // Use the token contract's transfer_from method
-        let token_client = soroban_sdk::token::Client::new(env, asset_addr);
+        let token_client = soroban_sdk::token::Client::new(env, &token_addr);
 
-        // Check user balance
-        let user_balance = token_client.balance(&user);
-        if user_balance < amount {
+        // Check payer balance
+        let payer_balance = token_client.balance(payer);
+        if payer_balance < amount {
             return Err(DepositError::InsufficientBalance);
         }
 
-        // Transfer tokens from user to contract
-        // The user must have approved the contract to spend their tokens
-        // transfer_from requires: spender (contract), from (user), to (contract), amount
+        // Transfer tokens from payer to contract
+        // The payer must have approved the contract to spend their tokens
+        // transfer_from requires: spender (contract), from (payer), to (contract), amount
         token_client.transfer_from(
             &env.current_contract_address(), // spender (this contract)
-            &user,                           // from (user)
+            payer,                           // from (payer)
             &env.current_contract_address(), // to (this contract)
             &amount,
         );
-    } else {
-        // Native XLM deposit - in Soroban, native assets are handled differently
-        // For now, we'll track it but actual XLM handling depends on Soroban's native asset support
-        // This is a placeholder for native asset handling
     }
 
+    // Credit supply interest earned so far before adding to the collateral
+    crate::supply_interest::settle_supply_interest(env, &user);
+
     // Get or create user position
     let position_key = DepositDataKey::Position(user.clone());
     #[allow(clippy::unnecessary_lazy_evaluations)]
@@ -323,12 +420,16 @@ pub fn deposit_collateral(
     position.collateral = new_collateral;
     position.last_accrual_time = timestamp;
     env.storage().persistent().set(&position_key, &position);
+    crate::action_delay::record_deposit(env, &user);
 
     // Update user analytics
     update_user_analytics(env, &user, amount, timestamp, true)?;
 
     // Update protocol analytics
     update_protocol_analytics(env, amount, true)?;
+    crate::reserve::record_supply_change(env, &asset, amount);
+    crate::storage_ttl::extend_position_ttl(env, &user);
+    crate::storage_ttl::extend_reserve_ttl(env, &asset);
 
     // Add to activity log
     add_activity_log(
@@ -339,6 +440,8 @@ pub fn deposit_collateral(
         asset.clone(),
         timestamp,
     )?;
+    crate::health_history::record_health_snapshot(env, &user, "deposit");
+    crate::liquidation_grace::clear_if_healthy(env, &user);
 
     // Emit deposit event
     emit_deposit(
@@ -363,6 +466,37 @@ pub fn deposit_collateral(
     Ok(new_collateral)
 }
 
+/// Get the native asset (XLM) Stellar Asset Contract address, if configured
+pub(crate) fn get_native_asset_address(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<DepositDataKey, Address>(&DepositDataKey::NativeAssetAddress)
+}
+
+/// Map the native asset contract to `None` so native XLM has a single key
+///
+/// Deposits, balances, reserves and parameters of native XLM are all booked
+/// under `None`, whether the caller names the asset as `None` or by its
+/// Stellar Asset Contract address.
+pub(crate) fn canonical_asset(env: &Env, asset: Option<Address>) -> Option<Address> {
+    match asset {
+        Some(addr) if get_native_asset_address(env).as_ref() == Some(&addr) => None,
+        other => other,
+    }
+}
+
+/// Token contract that settles transfers of `asset`
+///
+/// Native XLM (`None`) settles through its Stellar Asset Contract. Returns
+/// `None` for native XLM while no native asset address is configured, in
+/// which case native amounts are only booked.
+pub(crate) fn resolve_token_address(env: &Env, asset: &Option<Address>) -> Option<Address> {
+    match asset {
+        Some(addr) => Some(addr.clone()),
+        None => get_native_asset_address(env),
+    }
+}
+
 /// Set the native asset address (admin only).
 /// Required for deposit/borrow/repay with asset = None. Must be called before using None as asset.
 pub fn set_native_asset_address(
@@ -464,7 +598,7 @@ pub fn update_protocol_analytics(
     Ok(())
 }
 
-/// Add entry to activity log
+/// Add entry to activity log and to the user's activity history
 pub fn add_activity_log(
     env: &Env,
     user: &Address,
@@ -480,6 +614,15 @@ pub fn add_activity_log(
         .get::<DepositDataKey, Vec<Activity>>(&log_key)
         .unwrap_or_else(|| Vec::new(env));
 
+    crate::user_activity::record_user_action(
+        env,
+        user,
+        activity_type.clone(),
+        asset.clone(),
+        amount,
+        timestamp,
+    );
+
     let activity = Activity {
         user: user.clone(),
         activity_type,
@@ -551,35 +694,10 @@ pub fn emit_user_activity_tracked_event(
     );
 }
 
-#[contracttype]
-enum RiskDataKey {
-    RiskConfig,
-    EmergencyPause,
-}
-
-/// Check risk management pause status
-/// This function checks the risk management system's pause switches
-/// by accessing the storage directly to avoid module dependency issues
+/// Check the emergency pause and the risk management deposit switch
 fn check_risk_management_pause(env: &Env) -> Result<(), DepositError> {
-    // Define risk management storage keys locally to avoid dependency
-
-    // Check emergency pause first
-    let emergency_key = RiskDataKey::EmergencyPause;
-    if let Some(emergency_paused) = env
-        .storage()
-        .persistent()
-        .get::<RiskDataKey, bool>(&emergency_key)
-    {
-        if emergency_paused {
-            return Err(DepositError::DepositPaused);
-        }
+    if crate::risk_management::check_operation_paused(env, Symbol::new(env, "pause_deposit")) {
+        return Err(DepositError::DepositPaused);
     }
-
-    // Check operation-specific pause in risk config
-    // Note: We need to access the RiskConfig struct, but to avoid circular dependencies,
-    // we'll check if the config exists and try to read pause_switches
-    // For now, we'll skip this check and rely on the old pause switch system
-    // The risk management pause switches should be checked at the contract API level
-
     Ok(())
 }
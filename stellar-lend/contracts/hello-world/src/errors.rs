@@ -1,5 +1,15 @@
 use soroban_sdk::contracterror;
 
+use crate::borrow::BorrowError;
+use crate::deposit::DepositError;
+use crate::flash_loan::FlashLoanError;
+use crate::leverage::LeverageError;
+use crate::liquidate::LiquidationError;
+use crate::operators::OperatorError;
+use crate::oracle::OracleError;
+use crate::repay::RepayError;
+use crate::withdraw::WithdrawError;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum GovernanceError {
@@ -38,3 +48,256 @@ pub enum GovernanceError {
     AlreadyInitialized = 132,
     NotInitialized = 133,
 }
+
+/// Errors returned by the core lending entrypoints
+///
+/// Deposit, withdraw, borrow, repay and liquidation entrypoints (including
+/// their AMM-routed and operator variants), flash loans, oracle and leverage
+/// entrypoints report failures with these codes, so clients can match on one
+/// set of errors whichever operation failed. The modules keep their own error
+/// enums; `From` maps them here.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProtocolError {
+    /// Amount is zero, negative or otherwise out of range
+    InvalidAmount = 1,
+    /// Asset is unknown or not valid for the operation
+    InvalidAsset = 2,
+    /// Caller's token balance is too low
+    InsufficientBalance = 3,
+    /// Position holds too little collateral for the operation
+    InsufficientCollateral = 4,
+    /// The operation would leave the position below the minimum ratio
+    InsufficientCollateralRatio = 5,
+    /// Amount exceeds what the position can borrow
+    MaxBorrowExceeded = 6,
+    /// The asset's supply cap would be exceeded
+    SupplyCapExceeded = 7,
+    /// The asset's borrow cap would be exceeded
+    BorrowCapExceeded = 8,
+    /// The operation is paused
+    Paused = 9,
+    /// The asset is not enabled for the operation
+    AssetNotEnabled = 10,
+    /// The asset is frozen (see `risk_management`)
+    AssetFrozen = 11,
+    /// Oracle price is older than allowed
+    StaleOracle = 12,
+    /// No usable oracle price
+    PriceUnavailable = 13,
+    /// Arithmetic overflow
+    Overflow = 14,
+    /// Reentrant call
+    Reentrancy = 15,
+    /// The position has no debt to repay
+    NoDebt = 16,
+    /// The position is not liquidatable
+    NotLiquidatable = 17,
+    /// Amount exceeds the close factor
+    ExceedsCloseFactor = 18,
+    /// Liquidation amount is below the minimum
+    InsufficientLiquidation = 19,
+    /// The position is in its liquidation grace period
+    GracePeriodActive = 20,
+    /// The AMM swap failed
+    SwapFailed = 21,
+    /// The AMM swap returned less than the minimum
+    SlippageExceeded = 22,
+    /// Siloed debt cannot be mixed with other debt
+    SiloedBorrowViolation = 23,
+    /// The position was funded too recently (see `action_delay`)
+    ActionDelayActive = 24,
+    /// Recipient is not valid
+    InvalidRecipient = 25,
+    /// The pool holds too little of the asset
+    InsufficientLiquidity = 26,
+    /// A flash loan was not repaid with its fee
+    FlashLoanNotRepaid = 27,
+    /// The flash loan receiver is invalid or its callback failed
+    FlashLoanCallbackFailed = 28,
+    /// Submitted price is zero, negative or otherwise invalid
+    InvalidPrice = 29,
+    /// Submitted price deviates too far from the last one
+    PriceDeviationExceeded = 30,
+    /// Oracle address is not valid for the asset
+    InvalidOracle = 31,
+    /// Caller is not allowed to perform the operation
+    Unauthorized = 32,
+    /// Target leverage is out of range
+    InvalidLeverage = 33,
+    /// A deposit or borrow step of the leverage loop was rejected
+    LeverageStepFailed = 34,
+    /// The operator is not approved for the operation
+    OperatorNotApproved = 35,
+    /// The operator's approval has expired
+    OperatorApprovalExpired = 36,
+    /// Amount exceeds the operator's remaining cap
+    OperatorCapExceeded = 37,
+}
+
+impl From<DepositError> for ProtocolError {
+    fn from(error: DepositError) -> Self {
+        match error {
+            DepositError::InvalidAmount => ProtocolError::InvalidAmount,
+            DepositError::InvalidAsset => ProtocolError::InvalidAsset,
+            DepositError::InsufficientBalance => ProtocolError::InsufficientBalance,
+            DepositError::DepositPaused => ProtocolError::Paused,
+            DepositError::AssetNotEnabled => ProtocolError::AssetNotEnabled,
+            DepositError::Overflow => ProtocolError::Overflow,
+            DepositError::Reentrancy => ProtocolError::Reentrancy,
+            DepositError::SupplyCapExceeded => ProtocolError::SupplyCapExceeded,
+            DepositError::AssetFrozen => ProtocolError::AssetFrozen,
+        }
+    }
+}
+
+impl From<WithdrawError> for ProtocolError {
+    fn from(error: WithdrawError) -> Self {
+        match error {
+            WithdrawError::InvalidAmount => ProtocolError::InvalidAmount,
+            WithdrawError::InvalidAsset => ProtocolError::InvalidAsset,
+            WithdrawError::InsufficientCollateral => ProtocolError::InsufficientCollateral,
+            WithdrawError::WithdrawPaused => ProtocolError::Paused,
+            WithdrawError::InsufficientCollateralRatio | WithdrawError::Undercollateralized => {
+                ProtocolError::InsufficientCollateralRatio
+            }
+            WithdrawError::Overflow => ProtocolError::Overflow,
+            WithdrawError::Reentrancy => ProtocolError::Reentrancy,
+            WithdrawError::InvalidRecipient => ProtocolError::InvalidRecipient,
+            WithdrawError::ActionDelayActive => ProtocolError::ActionDelayActive,
+        }
+    }
+}
+
+impl From<BorrowError> for ProtocolError {
+    fn from(error: BorrowError) -> Self {
+        match error {
+            BorrowError::InvalidAmount => ProtocolError::InvalidAmount,
+            BorrowError::InvalidAsset => ProtocolError::InvalidAsset,
+            BorrowError::InsufficientCollateral => ProtocolError::InsufficientCollateral,
+            BorrowError::BorrowPaused => ProtocolError::Paused,
+            BorrowError::InsufficientCollateralRatio => ProtocolError::InsufficientCollateralRatio,
+            BorrowError::Overflow => ProtocolError::Overflow,
+            BorrowError::Reentrancy => ProtocolError::Reentrancy,
+            BorrowError::MaxBorrowExceeded => ProtocolError::MaxBorrowExceeded,
+            BorrowError::AssetNotEnabled => ProtocolError::AssetNotEnabled,
+            BorrowError::SwapFailed => ProtocolError::SwapFailed,
+            BorrowError::SlippageExceeded => ProtocolError::SlippageExceeded,
+            BorrowError::BorrowCapExceeded => ProtocolError::BorrowCapExceeded,
+            BorrowError::SiloedBorrowViolation => ProtocolError::SiloedBorrowViolation,
+            BorrowError::ActionDelayActive => ProtocolError::ActionDelayActive,
+            BorrowError::AssetFrozen => ProtocolError::AssetFrozen,
+        }
+    }
+}
+
+impl From<RepayError> for ProtocolError {
+    fn from(error: RepayError) -> Self {
+        match error {
+            RepayError::InvalidAmount => ProtocolError::InvalidAmount,
+            RepayError::InvalidAsset => ProtocolError::InvalidAsset,
+            RepayError::InsufficientBalance => ProtocolError::InsufficientBalance,
+            RepayError::RepayPaused => ProtocolError::Paused,
+            RepayError::NoDebt => ProtocolError::NoDebt,
+            RepayError::Overflow => ProtocolError::Overflow,
+            RepayError::Reentrancy => ProtocolError::Reentrancy,
+            RepayError::InsufficientCollateral => ProtocolError::InsufficientCollateral,
+            RepayError::SwapFailed => ProtocolError::SwapFailed,
+            RepayError::SlippageExceeded => ProtocolError::SlippageExceeded,
+            RepayError::UnhealthyPosition => ProtocolError::InsufficientCollateralRatio,
+        }
+    }
+}
+
+impl From<LiquidationError> for ProtocolError {
+    fn from(error: LiquidationError) -> Self {
+        match error {
+            LiquidationError::InvalidAmount => ProtocolError::InvalidAmount,
+            LiquidationError::InvalidAsset
+            | LiquidationError::InvalidCollateralAsset
+            | LiquidationError::InvalidDebtAsset => ProtocolError::InvalidAsset,
+            LiquidationError::NotLiquidatable => ProtocolError::NotLiquidatable,
+            LiquidationError::LiquidationPaused => ProtocolError::Paused,
+            LiquidationError::ExceedsCloseFactor => ProtocolError::ExceedsCloseFactor,
+            LiquidationError::InsufficientBalance => ProtocolError::InsufficientBalance,
+            LiquidationError::Overflow => ProtocolError::Overflow,
+            LiquidationError::PriceNotAvailable => ProtocolError::PriceUnavailable,
+            LiquidationError::InsufficientLiquidation => ProtocolError::InsufficientLiquidation,
+            LiquidationError::GracePeriodActive => ProtocolError::GracePeriodActive,
+            LiquidationError::StalePrice => ProtocolError::StaleOracle,
+            LiquidationError::SwapFailed => ProtocolError::SwapFailed,
+            LiquidationError::SlippageExceeded => ProtocolError::SlippageExceeded,
+        }
+    }
+}
+
+impl From<FlashLoanError> for ProtocolError {
+    fn from(error: FlashLoanError) -> Self {
+        match error {
+            FlashLoanError::InvalidAmount => ProtocolError::InvalidAmount,
+            FlashLoanError::InvalidAsset => ProtocolError::InvalidAsset,
+            FlashLoanError::InsufficientLiquidity => ProtocolError::InsufficientLiquidity,
+            FlashLoanError::FlashLoanPaused => ProtocolError::Paused,
+            FlashLoanError::NotRepaid | FlashLoanError::InsufficientRepayment => {
+                ProtocolError::FlashLoanNotRepaid
+            }
+            FlashLoanError::Overflow => ProtocolError::Overflow,
+            FlashLoanError::Reentrancy => ProtocolError::Reentrancy,
+            FlashLoanError::InvalidCallback | FlashLoanError::CallbackFailed => {
+                ProtocolError::FlashLoanCallbackFailed
+            }
+            FlashLoanError::Unauthorized => ProtocolError::Unauthorized,
+        }
+    }
+}
+
+impl From<OracleError> for ProtocolError {
+    fn from(error: OracleError) -> Self {
+        match error {
+            OracleError::InvalidPrice => ProtocolError::InvalidPrice,
+            OracleError::StalePrice => ProtocolError::StaleOracle,
+            OracleError::PriceDeviationExceeded => ProtocolError::PriceDeviationExceeded,
+            OracleError::InvalidOracle => ProtocolError::InvalidOracle,
+            OracleError::OraclePaused => ProtocolError::Paused,
+            OracleError::Overflow => ProtocolError::Overflow,
+            OracleError::Unauthorized => ProtocolError::Unauthorized,
+            OracleError::AssetNotSupported => ProtocolError::InvalidAsset,
+            OracleError::FallbackNotConfigured => ProtocolError::PriceUnavailable,
+        }
+    }
+}
+
+impl From<LeverageError> for ProtocolError {
+    fn from(error: LeverageError) -> Self {
+        match error {
+            LeverageError::InvalidAmount => ProtocolError::InvalidAmount,
+            LeverageError::InvalidAsset => ProtocolError::InvalidAsset,
+            LeverageError::InvalidLeverage => ProtocolError::InvalidLeverage,
+            LeverageError::DepositFailed | LeverageError::BorrowFailed => {
+                ProtocolError::LeverageStepFailed
+            }
+            LeverageError::SwapFailed => ProtocolError::SwapFailed,
+            LeverageError::SlippageExceeded => ProtocolError::SlippageExceeded,
+            LeverageError::PriceNotAvailable => ProtocolError::PriceUnavailable,
+            LeverageError::Overflow => ProtocolError::Overflow,
+        }
+    }
+}
+
+impl From<OperatorError> for ProtocolError {
+    fn from(error: OperatorError) -> Self {
+        match error {
+            OperatorError::InvalidAmount => ProtocolError::InvalidAmount,
+            OperatorError::ApprovalExpired => ProtocolError::OperatorApprovalExpired,
+            OperatorError::CapExceeded => ProtocolError::OperatorCapExceeded,
+            OperatorError::InvalidOperations
+            | OperatorError::InvalidCap
+            | OperatorError::InvalidExpiry
+            | OperatorError::InvalidOperator
+            | OperatorError::TooManyOperators
+            | OperatorError::ApprovalNotFound
+            | OperatorError::OperationNotApproved => ProtocolError::OperatorNotApproved,
+        }
+    }
+}
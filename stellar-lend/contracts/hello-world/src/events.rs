@@ -1,14 +1,21 @@
 #![allow(unused_variables)]
 
-use soroban_sdk::{contractevent, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol, Vec};
 
+use crate::param_timelock::ParamChange;
+use crate::safety_module::SafetyModuleConfig;
 use crate::types::{AssetStatus, ProposalType, VoteType};
 
 // ============================================================================
 // Core Lending Events (Existing)
 // ============================================================================
+//
+// Core lending events are published under versioned topics
+// `[<event_name>, "v1"]` with their fields as a map keyed by field name.
+// Adding a field keeps the version, so indexers decoding by name keep
+// working; removing, renaming or retyping a field bumps it.
 
-#[contractevent]
+#[contractevent(topics = ["deposit_event", "v1"])]
 #[derive(Clone, Debug)]
 pub struct DepositEvent {
     pub user: Address,
@@ -19,6 +26,16 @@ pub struct DepositEvent {
 
 #[contractevent]
 #[derive(Clone, Debug)]
+pub struct DepositOnBehalfEvent {
+    pub payer: Address,
+    pub user: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["withdrawal_event", "v1"])]
+#[derive(Clone, Debug)]
 pub struct WithdrawalEvent {
     pub user: Address,
     pub asset: Option<Address>,
@@ -28,6 +45,16 @@ pub struct WithdrawalEvent {
 
 #[contractevent]
 #[derive(Clone, Debug)]
+pub struct WithdrawToEvent {
+    pub user: Address,
+    pub recipient: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["borrow_event", "v1"])]
+#[derive(Clone, Debug)]
 pub struct BorrowEvent {
     pub user: Address,
     pub asset: Option<Address>,
@@ -35,18 +62,20 @@ pub struct BorrowEvent {
     pub timestamp: u64,
 }
 
-#[contractevent]
+#[contractevent(topics = ["repay_event", "v1"])]
 #[derive(Clone, Debug)]
 pub struct RepayEvent {
     pub user: Address,
     pub asset: Option<Address>,
     pub amount: i128,
+    pub principal_paid: i128,
+    pub interest_paid: i128,
     pub timestamp: u64,
 }
 
-#[contractevent]
+#[contractevent(topics = ["liquidation_executed_event", "v1"])]
 #[derive(Clone, Debug)]
-pub struct LiquidationEvent {
+pub struct LiquidationExecutedEvent {
     pub liquidator: Address,
     pub borrower: Address,
     pub debt_asset: Option<Address>,
@@ -54,6 +83,8 @@ pub struct LiquidationEvent {
     pub debt_liquidated: i128,
     pub collateral_seized: i128,
     pub incentive_amount: i128,
+    pub liquidator_bonus: i128,
+    pub protocol_fee: i128,
     pub timestamp: u64,
 }
 
@@ -376,179 +407,1150 @@ pub struct RecoveryExecutedEvent {
 }
 
 // ============================================================================
-// Core Lending Emitter Helpers
+// Strategy Vault Events
 // ============================================================================
 
-pub fn emit_deposit(e: &Env, event: DepositEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StrategyVaultCreatedEvent {
+    pub vault_id: u32,
+    pub collateral_asset: Address,
+    pub debt_asset: Address,
+    pub target_leverage_bps: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_withdrawal(e: &Env, event: WithdrawalEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct VaultDepositEvent {
+    pub vault_id: u32,
+    pub user: Address,
+    pub amount: i128,
+    pub shares: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_borrow(e: &Env, event: BorrowEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct VaultWithdrawEvent {
+    pub vault_id: u32,
+    pub user: Address,
+    pub shares: i128,
+    pub amount: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_repay(e: &Env, event: RepayEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct VaultRebalancedEvent {
+    pub vault_id: u32,
+    pub keeper: Address,
+    pub old_leverage_bps: i128,
+    pub new_leverage_bps: i128,
+    pub collateral: i128,
+    pub debt: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_liquidation(e: &Env, event: LiquidationEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct VaultFeeChargedEvent {
+    pub vault_id: u32,
+    pub recipient: Address,
+    pub fee_value: i128,
+    pub fee_shares: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_flash_loan_initiated(e: &Env, event: FlashLoanInitiatedEvent) {
-    event.publish(e);
-}
+// ============================================================================
+// Liquidation Insurance Events
+// ============================================================================
 
-pub fn emit_flash_loan_repaid(e: &Env, event: FlashLoanRepaidEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InsurancePolicyPurchasedEvent {
+    pub user: Address,
+    pub debt_asset: Option<Address>,
+    pub coverage: i128,
+    pub premium_bps: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_admin_action(e: &Env, event: AdminActionEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InsurancePolicyCancelledEvent {
+    pub user: Address,
+    pub premiums_paid: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_price_updated(e: &Env, event: PriceUpdatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InsuranceFundDepositEvent {
+    pub funder: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub balance: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_risk_params_updated(e: &Env, event: RiskParamsUpdatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InsuranceClaimedEvent {
+    pub borrower: Address,
+    pub debt_asset: Option<Address>,
+    pub amount: i128,
+    pub remaining_coverage: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_pause_state_changed(e: &Env, event: PauseStateChangedEvent) {
-    event.publish(e);
-}
+// ============================================================================
+// CDP Stablecoin Events
+// ============================================================================
 
-pub fn emit_position_updated(e: &Env, event: PositionUpdatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CdpCollateralDepositedEvent {
+    pub owner: Address,
+    pub collateral: Address,
+    pub amount: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_analytics_updated(e: &Env, event: AnalyticsUpdatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CdpCollateralWithdrawnEvent {
+    pub owner: Address,
+    pub collateral: Address,
+    pub amount: i128,
+    pub timestamp: u64,
 }
 
-pub fn emit_user_activity_tracked(e: &Env, event: UserActivityTrackedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CdpMintEvent {
+    pub owner: Address,
+    pub collateral: Address,
+    pub amount: i128,
+    pub debt: i128,
+    pub timestamp: u64,
 }
 
-// ============================================================================
-// Asset-Specific Emitter Helpers
-// ============================================================================
-
-#[allow(dead_code)]
-pub fn emit_mint(e: &Env, event: MintEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CdpRepayEvent {
+    pub payer: Address,
+    pub owner: Address,
+    pub collateral: Address,
+    pub amount: i128,
+    pub debt: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_transfer(e: &Env, event: TransferEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CdpLiquidationEvent {
+    pub liquidator: Address,
+    pub owner: Address,
+    pub collateral: Address,
+    pub debt_covered: i128,
+    pub collateral_seized: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_status_change(e: &Env, event: StatusChangeEvent) {
-    event.publish(e);
-}
+// ============================================================================
+// Savings Rate Events
+// ============================================================================
 
-#[allow(dead_code)]
-pub fn emit_quality_score_updated(e: &Env, event: QualityScoreUpdatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SavingsRateUpdatedEvent {
+    pub asset: Address,
+    pub old_rate_bps: i128,
+    pub new_rate_bps: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_approve(e: &Env, event: ApproveEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SavingsDepositEvent {
+    pub user: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub shares: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_sep41_transfer(e: &Env, event: Sep41TransferEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SavingsWithdrawEvent {
+    pub user: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub shares: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_sep41_burn(e: &Env, event: Sep41BurnEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SavingsFundedEvent {
+    pub asset: Address,
+    pub source: Address,
+    pub amount: i128,
+    pub funding: i128,
+    pub timestamp: u64,
 }
 
 // ============================================================================
-// Governance Emitter Helpers
+// Liquidation Hedge Marketplace Events
 // ============================================================================
 
-#[allow(dead_code)]
-pub fn emit_governance_initialized(e: &Env, event: GovernanceInitializedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HedgeOfferCreatedEvent {
+    pub offer_id: u64,
+    pub writer: Address,
+    pub debt_asset: Option<Address>,
+    pub strike_hf_bps: i128,
+    pub premium_bps: i128,
+    pub amount: i128,
+    pub expiry: u64,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_proposal_created(e: &Env, event: ProposalCreatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HedgeOfferCancelledEvent {
+    pub offer_id: u64,
+    pub writer: Address,
+    pub refunded: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_vote_cast(e: &Env, event: VoteCastEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HfPutPurchasedEvent {
+    pub put_id: u64,
+    pub offer_id: u64,
+    pub holder: Address,
+    pub repay_amount: i128,
+    pub premium: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_proposal_queued(e: &Env, event: ProposalQueuedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HfPutExercisedEvent {
+    pub put_id: u64,
+    pub holder: Address,
+    pub keeper: Address,
+    pub repaid: i128,
+    pub health_factor: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_proposal_executed(e: &Env, event: ProposalExecutedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HfPutExpiredEvent {
+    pub put_id: u64,
+    pub writer: Address,
+    pub released: i128,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_proposal_failed(e: &Env, event: ProposalFailedEvent) {
-    event.publish(e);
-}
+// ============================================================================
+// Risk Monitor Events
+// ============================================================================
 
-#[allow(dead_code)]
-pub fn emit_proposal_cancelled(e: &Env, event: ProposalCancelledEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HeartbeatEvent {
+    pub ledger: u32,
+    pub total_supplied_value: i128,
+    pub total_borrowed_value: i128,
+    pub critical_accounts: u32,
+    pub high_risk_accounts: u32,
+    pub elevated_risk_accounts: u32,
+    pub emergency_paused: bool,
+    pub paused_operations: Vec<Symbol>,
+    pub oldest_oracle_update: u64,
+    pub newest_oracle_update: u64,
+    pub stale_oracle_feeds: u32,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_proposal_approved(e: &Env, event: ProposalApprovedEvent) {
-    event.publish(e);
-}
+// ============================================================================
+// Bad Debt Events
+// ============================================================================
 
-#[allow(dead_code)]
-pub fn emit_governance_config_updated(e: &Env, event: GovernanceConfigUpdatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BadDebtUpdatedEvent {
+    pub asset: Option<Address>,
+    pub borrower: Option<Address>,
+    pub change: i128,
+    pub bad_debt: i128,
+    pub total_shortfall: i128,
+    pub reason: Symbol,
+    pub timestamp: u64,
 }
 
 // ============================================================================
-// Multisig Emitter Helpers
+// Config Export Events
 // ============================================================================
 
-#[allow(dead_code)]
-pub fn emit_multisig_config_updated(e: &Env, event: MultisigConfigUpdatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ConfigImportedEvent {
+    pub admin: Address,
+    pub reserves: u32,
+    pub amm_protocols: u32,
+    pub roles: u32,
+    pub timestamp: u64,
 }
 
 // ============================================================================
-// Guardian & Recovery Emitter Helpers
+// Protocol Init Events
 // ============================================================================
 
-#[allow(dead_code)]
-pub fn emit_guardian_added(e: &Env, event: GuardianAddedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ProtocolInitializedEvent {
+    pub admin: Address,
+    pub reserves: u32,
+    pub amm_protocols: u32,
+    pub roles: u32,
+    pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub fn emit_guardian_removed(e: &Env, event: GuardianRemovedEvent) {
-    event.publish(e);
-}
+// ============================================================================
+// Shadow Oracle Events
+// ============================================================================
 
-#[allow(dead_code)]
-pub fn emit_guardian_threshold_updated(e: &Env, event: GuardianThresholdUpdatedEvent) {
-    event.publish(e);
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ShadowPriceObservedEvent {
+    pub asset: Address,
+    pub shadow_oracle: Address,
+    pub shadow_price: i128,
+    pub primary_price: i128,
+    pub deviation_bps: i128,
+    pub breached: bool,
+    pub timestamp: u64,
+}
+
+// ============================================================================
+// Intent Events
+// ============================================================================
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct IntentCancelledEvent {
+    pub user: Address,
+    pub nonce: u64,
+    pub timestamp: u64,
+}
+
+// ============================================================================
+// Liquidation Grace Events
+// ============================================================================
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct GracePeriodStartedEvent {
+    pub borrower: Address,
+    pub started_ledger: u32,
+    pub ends_ledger: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RecoveryGraceStartedEvent {
+    pub asset: Option<Address>,
+    pub reason: Symbol,
+    pub ends_ledger: u32,
+    pub timestamp: u64,
+}
+
+// ============================================================================
+// Operator Events
+// ============================================================================
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OperatorApprovedEvent {
+    pub user: Address,
+    pub operator: Address,
+    pub operations: u32,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OperatorRevokedEvent {
+    pub user: Address,
+    pub operator: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OperatorActionEvent {
+    pub user: Address,
+    pub operator: Address,
+    pub operation: u32,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+// ============================================================================
+// Supply Interest Events
+// ============================================================================
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SupplyInterestAccruedEvent {
+    pub user: Address,
+    pub amount: i128,
+    pub supply_index: i128,
+    pub timestamp: u64,
+}
+
+// ============================================================================
+// Liquidation Auction Events
+// ============================================================================
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AuctionStartedEvent {
+    pub borrower: Address,
+    pub debt_asset: Option<Address>,
+    pub collateral_asset: Option<Address>,
+    pub tab: i128,
+    pub lot: i128,
+    pub ends_at: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AuctionBidEvent {
+    pub borrower: Address,
+    pub bidder: Address,
+    pub debt_repaid: i128,
+    pub collateral_sold: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AuctionSettledEvent {
+    pub borrower: Address,
+    pub collateral_returned: i128,
+    pub tab_unpaid: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CreditApprovedEvent {
+    pub delegator: Address,
+    pub delegatee: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CreditBorrowedEvent {
+    pub delegator: Address,
+    pub delegatee: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PermitExecutedEvent {
+    pub user: Address,
+    pub operation: u32,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub nonce: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PermitSignerUpdatedEvent {
+    pub user: Address,
+    pub public_key: BytesN<32>,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PositionWrappedEvent {
+    pub user: Address,
+    pub token_id: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PositionUnwrappedEvent {
+    pub user: Address,
+    pub token_id: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PositionTransferredEvent {
+    pub from: Address,
+    pub to: Address,
+    pub token_id: u64,
+    pub collateral: i128,
+    pub debt: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PositionMigratedEvent {
+    pub user: Address,
+    pub new_pool: Address,
+    pub collateral: i128,
+    pub debt: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PositionReceivedEvent {
+    pub user: Address,
+    pub source: Address,
+    pub collateral: i128,
+    pub debt: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MigrationTargetUpdatedEvent {
+    pub admin: Address,
+    pub new_pool: Option<Address>,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MigrationSourceUpdatedEvent {
+    pub admin: Address,
+    pub source: Address,
+    pub allowed: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RewardEmissionUpdatedEvent {
+    pub asset: Option<Address>,
+    pub reward_token: Address,
+    pub supply_per_second: i128,
+    pub borrow_per_second: i128,
+    pub end_time: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RewardsClaimedEvent {
+    pub user: Address,
+    pub to: Address,
+    pub reward_token: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StakeLockedEvent {
+    pub user: Address,
+    pub amount: i128,
+    pub total_amount: i128,
+    pub unlock_time: u64,
+    pub weight: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StakeUnlockedEvent {
+    pub user: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SafetyStakedEvent {
+    pub user: Address,
+    pub amount: i128,
+    pub shares: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SafetyCooldownStartedEvent {
+    pub user: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SafetyUnstakedEvent {
+    pub user: Address,
+    pub shares: i128,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SafetyModuleSlashedEvent {
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub covered: i128,
+    pub treasury: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SafetyYieldPaidEvent {
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub total_shares: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SafetyYieldClaimedEvent {
+    pub user: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SafetyConfigUpdatedEvent {
+    pub admin: Address,
+    pub config: SafetyModuleConfig,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RevenueDisbursedEvent {
+    pub asset: Option<Address>,
+    pub to: Address,
+    pub amount: i128,
+    pub remaining_reserve: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AmmProtocolFeeUpdatedEvent {
+    pub admin: Address,
+    pub fee_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ParamChangeQueuedEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub proposer: Address,
+    pub eta: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ParamChangeExecutedEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub executor: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ParamChangeCancelledEvent {
+    pub id: u64,
+    pub change: ParamChange,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["param_timelock_delay_updated"])]
+#[derive(Clone, Debug)]
+pub struct ParamTimelockDelayUpdatedEvent {
+    pub admin: Address,
+    pub delay: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AssetFreezeChangedEvent {
+    pub actor: Address,
+    pub asset: Option<Address>,
+    pub frozen: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct EmergencyWithdrawalModeEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UpgradeScheduledEvent {
+    pub wasm_hash: BytesN<32>,
+    pub proposer: Address,
+    pub eta: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UpgradeCancelledEvent {
+    pub wasm_hash: BytesN<32>,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ContractUpgradedEvent {
+    pub wasm_hash: BytesN<32>,
+    pub admin: Address,
+    pub from_version: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StorageMigratedEvent {
+    pub admin: Address,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PositionRestoredEvent {
+    pub user: Address,
+    pub supply_interest: i128,
+    pub borrow_interest: i128,
+    pub timestamp: u64,
+}
+
+/// Pool indexes advanced to a new timestamp
+///
+/// `borrow_rate` and `supply_rate` are the annual rates (bps) the indexes grew
+/// at over the `elapsed` seconds, at `utilization` (bps).
+#[contractevent(topics = ["accrue"])]
+#[derive(Clone, Debug)]
+pub struct AccrueEvent {
+    pub borrow_index: i128,
+    pub supply_index: i128,
+    pub borrow_rate: i128,
+    pub supply_rate: i128,
+    pub utilization: i128,
+    pub elapsed: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BatchExecutedEvent {
+    pub user: Address,
+    pub action_count: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InstructionCreatedEvent {
+    pub id: u64,
+    pub owner: Address,
+    pub operation: u32,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub bounty: i128,
+    pub executions: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InstructionExecutedEvent {
+    pub id: u64,
+    pub owner: Address,
+    pub keeper: Address,
+    pub amount: i128,
+    pub bounty: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InstructionCancelledEvent {
+    pub id: u64,
+    pub owner: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AutoDeleverageEnabledEvent {
+    pub user: Address,
+    pub trigger_health_factor: i128,
+    pub max_amount: i128,
+    pub fee_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AutoDeleverageDisabledEvent {
+    pub user: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AutoDeleverageExecutedEvent {
+    pub user: Address,
+    pub keeper: Address,
+    pub collateral_sold: i128,
+    pub debt_repaid: i128,
+    pub fee: i128,
+    pub health_factor: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LeveragedPositionOpenedEvent {
+    pub user: Address,
+    pub collateral_asset: Address,
+    pub debt_asset: Address,
+    pub initial_amount: i128,
+    pub collateral_added: i128,
+    pub debt_added: i128,
+    pub leverage: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ActionDelayUpdatedEvent {
+    pub admin: Address,
+    pub delay_ledgers: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SupplyTokenUpdatedEvent {
+    pub admin: Address,
+    pub asset: Option<Address>,
+    pub token: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SupplyTokenRedeemedEvent {
+    pub user: Address,
+    pub asset: Option<Address>,
+    pub stoken_amount: i128,
+    pub underlying: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SupplyCapUpdatedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub supply_cap: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BorrowCapUpdatedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub borrow_cap: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SiloedUpdatedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub siloed: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["liquidation_threshold_updated"])]
+#[derive(Clone, Debug)]
+pub struct LiquidationThresholdUpdatedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub liquidation_threshold_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CloseFactorUpdatedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub close_factor_bps: i128,
+    pub deep_distress_hf_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["liq_protocol_fee_updated"])]
+#[derive(Clone, Debug)]
+pub struct LiqProtocolFeeUpdatedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub fee_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LpHaircutUpdatedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub haircut_bps: i128,
+    pub timestamp: u64,
+}
+
+// ============================================================================
+// Core Lending Emitter Helpers
+// ============================================================================
+
+pub fn emit_deposit(e: &Env, event: DepositEvent) {
+    event.publish(e);
+}
+
+pub fn emit_deposit_on_behalf(e: &Env, event: DepositOnBehalfEvent) {
+    event.publish(e);
+}
+
+pub fn emit_withdrawal(e: &Env, event: WithdrawalEvent) {
+    event.publish(e);
+}
+
+pub fn emit_withdraw_to(e: &Env, event: WithdrawToEvent) {
+    event.publish(e);
+}
+
+pub fn emit_borrow(e: &Env, event: BorrowEvent) {
+    event.publish(e);
+}
+
+pub fn emit_repay(e: &Env, event: RepayEvent) {
+    event.publish(e);
+}
+
+pub fn emit_liquidation(e: &Env, event: LiquidationExecutedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_flash_loan_initiated(e: &Env, event: FlashLoanInitiatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_flash_loan_repaid(e: &Env, event: FlashLoanRepaidEvent) {
+    event.publish(e);
+}
+
+pub fn emit_admin_action(e: &Env, event: AdminActionEvent) {
+    event.publish(e);
+}
+
+pub fn emit_price_updated(e: &Env, event: PriceUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_risk_params_updated(e: &Env, event: RiskParamsUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_pause_state_changed(e: &Env, event: PauseStateChangedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_asset_freeze_changed(e: &Env, event: AssetFreezeChangedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_emergency_withdrawal_mode(e: &Env, event: EmergencyWithdrawalModeEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_updated(e: &Env, event: PositionUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_analytics_updated(e: &Env, event: AnalyticsUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_user_activity_tracked(e: &Env, event: UserActivityTrackedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Asset-Specific Emitter Helpers
+// ============================================================================
+
+#[allow(dead_code)]
+pub fn emit_mint(e: &Env, event: MintEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_transfer(e: &Env, event: TransferEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_status_change(e: &Env, event: StatusChangeEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_quality_score_updated(e: &Env, event: QualityScoreUpdatedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_approve(e: &Env, event: ApproveEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_sep41_transfer(e: &Env, event: Sep41TransferEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_sep41_burn(e: &Env, event: Sep41BurnEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Governance Emitter Helpers
+// ============================================================================
+
+#[allow(dead_code)]
+pub fn emit_governance_initialized(e: &Env, event: GovernanceInitializedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_proposal_created(e: &Env, event: ProposalCreatedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_vote_cast(e: &Env, event: VoteCastEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_proposal_queued(e: &Env, event: ProposalQueuedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_proposal_executed(e: &Env, event: ProposalExecutedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_proposal_failed(e: &Env, event: ProposalFailedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_proposal_cancelled(e: &Env, event: ProposalCancelledEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_proposal_approved(e: &Env, event: ProposalApprovedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_governance_config_updated(e: &Env, event: GovernanceConfigUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Multisig Emitter Helpers
+// ============================================================================
+
+#[allow(dead_code)]
+pub fn emit_multisig_config_updated(e: &Env, event: MultisigConfigUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Guardian & Recovery Emitter Helpers
+// ============================================================================
+
+#[allow(dead_code)]
+pub fn emit_guardian_added(e: &Env, event: GuardianAddedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_guardian_removed(e: &Env, event: GuardianRemovedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_guardian_threshold_updated(e: &Env, event: GuardianThresholdUpdatedEvent) {
+    event.publish(e);
 }
 
 #[allow(dead_code)]
@@ -556,12 +1558,508 @@ pub fn emit_recovery_started(e: &Env, event: RecoveryStartedEvent) {
     event.publish(e);
 }
 
-#[allow(dead_code)]
-pub fn emit_recovery_approved(e: &Env, event: RecoveryApprovedEvent) {
+#[allow(dead_code)]
+pub fn emit_recovery_approved(e: &Env, event: RecoveryApprovedEvent) {
+    event.publish(e);
+}
+
+#[allow(dead_code)]
+pub fn emit_recovery_executed(e: &Env, event: RecoveryExecutedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Strategy Vault Emitter Helpers
+// ============================================================================
+
+pub fn emit_strategy_vault_created(e: &Env, event: StrategyVaultCreatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_vault_deposit(e: &Env, event: VaultDepositEvent) {
+    event.publish(e);
+}
+
+pub fn emit_vault_withdraw(e: &Env, event: VaultWithdrawEvent) {
+    event.publish(e);
+}
+
+pub fn emit_vault_rebalanced(e: &Env, event: VaultRebalancedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_vault_fee_charged(e: &Env, event: VaultFeeChargedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Liquidation Insurance Emitter Helpers
+// ============================================================================
+
+pub fn emit_insurance_policy_purchased(e: &Env, event: InsurancePolicyPurchasedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_insurance_policy_cancelled(e: &Env, event: InsurancePolicyCancelledEvent) {
+    event.publish(e);
+}
+
+pub fn emit_insurance_fund_deposit(e: &Env, event: InsuranceFundDepositEvent) {
+    event.publish(e);
+}
+
+pub fn emit_insurance_claimed(e: &Env, event: InsuranceClaimedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// CDP Stablecoin Emitter Helpers
+// ============================================================================
+
+pub fn emit_cdp_collateral_deposited(e: &Env, event: CdpCollateralDepositedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_cdp_collateral_withdrawn(e: &Env, event: CdpCollateralWithdrawnEvent) {
+    event.publish(e);
+}
+
+pub fn emit_cdp_mint(e: &Env, event: CdpMintEvent) {
+    event.publish(e);
+}
+
+pub fn emit_cdp_repay(e: &Env, event: CdpRepayEvent) {
+    event.publish(e);
+}
+
+pub fn emit_cdp_liquidation(e: &Env, event: CdpLiquidationEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Savings Rate Emitter Helpers
+// ============================================================================
+
+pub fn emit_savings_rate_updated(e: &Env, event: SavingsRateUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_savings_deposit(e: &Env, event: SavingsDepositEvent) {
+    event.publish(e);
+}
+
+pub fn emit_savings_withdraw(e: &Env, event: SavingsWithdrawEvent) {
+    event.publish(e);
+}
+
+pub fn emit_savings_funded(e: &Env, event: SavingsFundedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Liquidation Hedge Marketplace Emitter Helpers
+// ============================================================================
+
+pub fn emit_hedge_offer_created(e: &Env, event: HedgeOfferCreatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_hedge_offer_cancelled(e: &Env, event: HedgeOfferCancelledEvent) {
+    event.publish(e);
+}
+
+pub fn emit_hf_put_purchased(e: &Env, event: HfPutPurchasedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_hf_put_exercised(e: &Env, event: HfPutExercisedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_hf_put_expired(e: &Env, event: HfPutExpiredEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Risk Monitor Emitter Helpers
+// ============================================================================
+
+pub fn emit_heartbeat(e: &Env, event: HeartbeatEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Bad Debt Emitter Helpers
+// ============================================================================
+
+pub fn emit_bad_debt_updated(e: &Env, event: BadDebtUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Config Export Emitter Helpers
+// ============================================================================
+
+pub fn emit_config_imported(e: &Env, event: ConfigImportedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Protocol Init Emitter Helpers
+// ============================================================================
+
+pub fn emit_protocol_initialized(e: &Env, event: ProtocolInitializedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Shadow Oracle Emitter Helpers
+// ============================================================================
+
+pub fn emit_shadow_price_observed(e: &Env, event: ShadowPriceObservedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Intent Emitter Helpers
+// ============================================================================
+
+pub fn emit_intent_cancelled(e: &Env, event: IntentCancelledEvent) {
     event.publish(e);
 }
 
-#[allow(dead_code)]
-pub fn emit_recovery_executed(e: &Env, event: RecoveryExecutedEvent) {
+// ============================================================================
+// Liquidation Grace Emitter Helpers
+// ============================================================================
+
+pub fn emit_grace_period_started(e: &Env, event: GracePeriodStartedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_recovery_grace_started(e: &Env, event: RecoveryGraceStartedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Operator Emitter Helpers
+// ============================================================================
+
+pub fn emit_operator_approved(e: &Env, event: OperatorApprovedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_operator_revoked(e: &Env, event: OperatorRevokedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_operator_action(e: &Env, event: OperatorActionEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Supply Interest Emitter Helpers
+// ============================================================================
+
+pub fn emit_supply_interest_accrued(e: &Env, event: SupplyInterestAccruedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Liquidation Auction Emitter Helpers
+// ============================================================================
+
+pub fn emit_auction_started(e: &Env, event: AuctionStartedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_auction_bid(e: &Env, event: AuctionBidEvent) {
+    event.publish(e);
+}
+
+pub fn emit_auction_settled(e: &Env, event: AuctionSettledEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Leverage Emitter Helpers
+// ============================================================================
+
+pub fn emit_leveraged_position_opened(e: &Env, event: LeveragedPositionOpenedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Credit Delegation Emitter Helpers
+// ============================================================================
+
+pub fn emit_credit_approved(e: &Env, event: CreditApprovedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_credit_borrowed(e: &Env, event: CreditBorrowedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Permit Emitter Helpers
+// ============================================================================
+
+pub fn emit_permit_executed(e: &Env, event: PermitExecutedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_permit_signer_updated(e: &Env, event: PermitSignerUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Position Token Emitter Helpers
+// ============================================================================
+
+pub fn emit_position_wrapped(e: &Env, event: PositionWrappedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_unwrapped(e: &Env, event: PositionUnwrappedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_transferred(e: &Env, event: PositionTransferredEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Position Migration Emitter Helpers
+// ============================================================================
+
+pub fn emit_position_migrated(e: &Env, event: PositionMigratedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_received(e: &Env, event: PositionReceivedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_migration_target_updated(e: &Env, event: MigrationTargetUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_migration_source_updated(e: &Env, event: MigrationSourceUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Rewards Emitter Helpers
+// ============================================================================
+
+pub fn emit_reward_emission_updated(e: &Env, event: RewardEmissionUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_rewards_claimed(e: &Env, event: RewardsClaimedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Staking Emitter Helpers
+// ============================================================================
+
+pub fn emit_stake_locked(e: &Env, event: StakeLockedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_stake_unlocked(e: &Env, event: StakeUnlockedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Safety Module Emitter Helpers
+// ============================================================================
+
+pub fn emit_safety_staked(e: &Env, event: SafetyStakedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_safety_cooldown_started(e: &Env, event: SafetyCooldownStartedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_safety_unstaked(e: &Env, event: SafetyUnstakedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_safety_module_slashed(e: &Env, event: SafetyModuleSlashedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_safety_yield_paid(e: &Env, event: SafetyYieldPaidEvent) {
+    event.publish(e);
+}
+
+pub fn emit_safety_yield_claimed(e: &Env, event: SafetyYieldClaimedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_safety_config_updated(e: &Env, event: SafetyConfigUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Treasury Emitter Helpers
+// ============================================================================
+
+pub fn emit_revenue_disbursed(e: &Env, event: RevenueDisbursedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_amm_protocol_fee_updated(e: &Env, event: AmmProtocolFeeUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Parameter Timelock Emitter Helpers
+// ============================================================================
+
+pub fn emit_param_change_queued(e: &Env, event: ParamChangeQueuedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_param_change_executed(e: &Env, event: ParamChangeExecutedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_param_change_cancelled(e: &Env, event: ParamChangeCancelledEvent) {
+    event.publish(e);
+}
+
+pub fn emit_param_timelock_delay_updated(e: &Env, event: ParamTimelockDelayUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Contract Upgrade Emitter Helpers
+// ============================================================================
+
+pub fn emit_upgrade_scheduled(e: &Env, event: UpgradeScheduledEvent) {
+    event.publish(e);
+}
+
+pub fn emit_upgrade_cancelled(e: &Env, event: UpgradeCancelledEvent) {
+    event.publish(e);
+}
+
+pub fn emit_contract_upgraded(e: &Env, event: ContractUpgradedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_storage_migrated(e: &Env, event: StorageMigratedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Storage TTL Emitter Helpers
+// ============================================================================
+
+pub fn emit_position_restored(e: &Env, event: PositionRestoredEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Interest Index Emitter Helpers
+// ============================================================================
+
+pub fn emit_accrue(e: &Env, event: AccrueEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Batch Action Emitter Helpers
+// ============================================================================
+
+pub fn emit_batch_executed(e: &Env, event: BatchExecutedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Standing Instruction Emitter Helpers
+// ============================================================================
+
+pub fn emit_instruction_created(e: &Env, event: InstructionCreatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_instruction_executed(e: &Env, event: InstructionExecutedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_instruction_cancelled(e: &Env, event: InstructionCancelledEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Auto-Deleverage Emitter Helpers
+// ============================================================================
+
+pub fn emit_auto_deleverage_enabled(e: &Env, event: AutoDeleverageEnabledEvent) {
+    event.publish(e);
+}
+
+pub fn emit_auto_deleverage_disabled(e: &Env, event: AutoDeleverageDisabledEvent) {
+    event.publish(e);
+}
+
+pub fn emit_auto_deleverage_executed(e: &Env, event: AutoDeleverageExecutedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Action Delay Emitter Helpers
+// ============================================================================
+
+pub fn emit_action_delay_updated(e: &Env, event: ActionDelayUpdatedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Supply Token Emitter Helpers
+// ============================================================================
+
+pub fn emit_supply_token_updated(e: &Env, event: SupplyTokenUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_supply_token_redeemed(e: &Env, event: SupplyTokenRedeemedEvent) {
+    event.publish(e);
+}
+
+// ============================================================================
+// Reserve Configuration Emitter Helpers
+// ============================================================================
+
+pub fn emit_supply_cap_updated(e: &Env, event: SupplyCapUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_borrow_cap_updated(e: &Env, event: BorrowCapUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_siloed_updated(e: &Env, event: SiloedUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_liquidation_threshold_updated(e: &Env, event: LiquidationThresholdUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_close_factor_updated(e: &Env, event: CloseFactorUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_liq_protocol_fee_updated(e: &Env, event: LiqProtocolFeeUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_lp_haircut_updated(e: &Env, event: LpHaircutUpdatedEvent) {
     event.publish(e);
 }
@@ -9,6 +9,17 @@
 //! - Default fee: 9 basis points (0.09%) of the borrowed amount.
 //! - Fee is configurable by the admin.
 //!
+//! ## Atomic Flash Loans
+//! `flash_loan` transfers the funds to a receiver contract, invokes its
+//! `on_flash_loan` callback (see `FlashLoanReceiver`) and, once the callback
+//! returns, requires the contract's balance of the asset to have grown by the
+//! fee. The receiver repays by transferring principal plus fee back to the
+//! lender passed to the callback; anything short reverts the invocation. The
+//! fee is credited to the asset's protocol reserve.
+//!
+//! `execute_flash_loan` / `repay_flash_loan` remain for callers that settle
+//! the loan with two calls in the same transaction.
+//!
 //! ## Reentrancy Protection
 //! An active flash loan is recorded per (user, asset) pair. A second flash loan
 //! for the same pair is rejected until the first is repaid, preventing reentrancy.
@@ -23,7 +34,10 @@ use crate::events::{
     emit_flash_loan_initiated, emit_flash_loan_repaid, FlashLoanInitiatedEvent,
     FlashLoanRepaidEvent,
 };
-use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
+use soroban_sdk::{
+    contractclient, contracterror, contracttype, Address, Bytes, Env, IntoVal, Map, Symbol, Val,
+    Vec,
+};
 
 use crate::deposit::DepositDataKey;
 
@@ -52,6 +66,8 @@ pub enum FlashLoanError {
     InvalidCallback = 9,
     /// Callback execution failed
     CallbackFailed = 10,
+    /// Caller is not the admin
+    Unauthorized = 11,
 }
 
 /// Storage keys for flash loan-related data
@@ -71,6 +87,22 @@ pub enum FlashLoanDataKey {
     PauseSwitches,
 }
 
+/// Interface of contracts receiving an atomic flash loan
+#[contractclient(name = "FlashLoanReceiverClient")]
+pub trait FlashLoanReceiver {
+    /// Called with the borrowed funds already transferred to the receiver;
+    /// must transfer `amount + fee` of `asset` back to `lender` and return
+    /// `true`
+    fn on_flash_loan(
+        env: Env,
+        lender: Address,
+        asset: Address,
+        amount: i128,
+        fee: i128,
+        data: Bytes,
+    ) -> bool;
+}
+
 /// Flash loan record
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -170,35 +202,38 @@ fn clear_flash_loan(env: &Env, user: &Address, asset: &Address) {
     env.storage().persistent().remove(&loan_key);
 }
 
-/// Execute flash loan
-///
-/// Allows users to borrow assets without collateral for a single transaction.
-/// The loan must be repaid (with fee) within the same transaction via callback.
-///
-/// # Arguments
-/// * `env` - The Soroban environment
-/// * `user` - The address borrowing the flash loan
-/// * `asset` - The address of the asset contract to borrow
-/// * `amount` - The amount to borrow
-/// * `callback` - The callback contract address that will handle repayment
-///
-/// # Returns
-/// Returns the total amount to repay (principal + fee)
-///
-/// # Errors
-/// * `FlashLoanError::InvalidAmount` - If amount is zero, negative, or outside limits
-/// * `FlashLoanError::InvalidAsset` - If asset address is invalid
-/// * `FlashLoanError::InsufficientLiquidity` - If contract doesn't have enough liquidity
-/// * `FlashLoanError::FlashLoanPaused` - If flash loans are paused
-/// * `FlashLoanError::Reentrancy` - If flash loan is already active for this user/asset
-/// * `FlashLoanError::InvalidCallback` - If callback address is invalid
-/// * `FlashLoanError::Overflow` - If calculation overflow occurs
-pub fn execute_flash_loan(
+/// Credit a flash loan fee to the asset's protocol reserve
+fn credit_fee_to_reserve(env: &Env, asset: &Address, fee: i128) -> Result<(), FlashLoanError> {
+    if fee > 0 {
+        let reserve_key = DepositDataKey::ProtocolReserve(Some(asset.clone()));
+        let current_reserve = env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, i128>(&reserve_key)
+            .unwrap_or(0);
+        env.storage().persistent().set(
+            &reserve_key,
+            &(current_reserve
+                .checked_add(fee)
+                .ok_or(FlashLoanError::Overflow)?),
+        );
+        crate::treasury::record_revenue(
+            env,
+            &Some(asset.clone()),
+            crate::treasury::RevenueSource::FlashLoanFee,
+            fee,
+        );
+    }
+    Ok(())
+}
+
+/// Validate a flash loan request and compute its fee
+fn validate_flash_loan(
     env: &Env,
-    user: Address,
-    asset: Address,
+    borrower: &Address,
+    asset: &Address,
     amount: i128,
-    callback: Address,
+    callback: &Address,
 ) -> Result<i128, FlashLoanError> {
     // Validate amount
     if amount <= 0 {
@@ -220,12 +255,12 @@ pub fn execute_flash_loan(
     }
 
     // Validate asset address
-    if asset == env.current_contract_address() {
+    if asset == &env.current_contract_address() {
         return Err(FlashLoanError::InvalidAsset);
     }
 
     // Validate callback address
-    if callback == env.current_contract_address() {
+    if callback == &env.current_contract_address() {
         return Err(FlashLoanError::InvalidCallback);
     }
 
@@ -236,12 +271,132 @@ pub fn execute_flash_loan(
     }
 
     // Check for reentrancy (active flash loan)
-    if is_flash_loan_active(env, &user, &asset) {
+    if is_flash_loan_active(env, borrower, asset) {
         return Err(FlashLoanError::Reentrancy);
     }
 
-    // Calculate fee
-    let fee = calculate_flash_loan_fee(env, amount)?;
+    calculate_flash_loan_fee(env, amount)
+}
+
+/// Execute an atomic flash loan
+///
+/// Transfers `amount` to `receiver`, invokes its `on_flash_loan` callback
+/// and requires principal plus fee to be back in the contract when the
+/// callback returns. The receiver must authorize the loan.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `receiver` - The receiver contract (implements `FlashLoanReceiver`)
+/// * `asset` - The address of the asset contract to borrow
+/// * `amount` - The amount to borrow
+/// * `data` - Opaque data forwarded to the callback
+///
+/// # Returns
+/// Returns the fee paid
+///
+/// # Errors
+/// Same as `execute_flash_loan`, plus:
+/// * `FlashLoanError::CallbackFailed` - If the callback returns `false`
+/// * `FlashLoanError::NotRepaid` - If principal plus fee was not returned
+pub fn flash_loan(
+    env: &Env,
+    receiver: Address,
+    asset: Address,
+    amount: i128,
+    data: Bytes,
+) -> Result<i128, FlashLoanError> {
+    receiver.require_auth();
+    let fee = validate_flash_loan(env, &receiver, &asset, amount, &receiver)?;
+
+    // Check contract balance
+    let token_client = soroban_sdk::token::Client::new(env, &asset);
+    let balance_before = token_client.balance(&env.current_contract_address());
+    if balance_before < amount {
+        return Err(FlashLoanError::InsufficientLiquidity);
+    }
+    let required_balance = balance_before
+        .checked_add(fee)
+        .ok_or(FlashLoanError::Overflow)?;
+
+    // Record flash loan before transfer
+    record_flash_loan(env, &receiver, &asset, amount, fee, &receiver);
+    token_client.transfer(&env.current_contract_address(), &receiver, &amount);
+    emit_flash_loan_initiated(
+        env,
+        FlashLoanInitiatedEvent {
+            user: receiver.clone(),
+            asset: asset.clone(),
+            amount,
+            fee,
+            callback: receiver.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    let accepted = FlashLoanReceiverClient::new(env, &receiver).on_flash_loan(
+        &env.current_contract_address(),
+        &asset,
+        &amount,
+        &fee,
+        &data,
+    );
+    if !accepted {
+        return Err(FlashLoanError::CallbackFailed);
+    }
+
+    // Principal plus fee must be back by the end of the callback
+    if token_client.balance(&env.current_contract_address()) < required_balance {
+        return Err(FlashLoanError::NotRepaid);
+    }
+
+    credit_fee_to_reserve(env, &asset, fee)?;
+    clear_flash_loan(env, &receiver, &asset);
+    emit_flash_loan_repaid(
+        env,
+        FlashLoanRepaidEvent {
+            user: receiver,
+            asset,
+            amount,
+            fee,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(fee)
+}
+
+/// Execute flash loan
+///
+/// Allows users to borrow assets without collateral for a single transaction.
+/// The loan must be repaid (with fee) within the same transaction via callback.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The address borrowing the flash loan
+/// * `asset` - The address of the asset contract to borrow
+/// * `amount` - The amount to borrow
+/// * `callback` - The callback contract address that will handle repayment
+///
+/// # Returns
+/// Returns the total amount to repay (principal + fee)
+///
+/// # Errors
+/// * `FlashLoanError::InvalidAmount` - If amount is zero, negative, or outside limits
+/// * `FlashLoanError::InvalidAsset` - If asset address is invalid
+/// * `FlashLoanError::InsufficientLiquidity` - If contract doesn't have enough liquidity
+/// * `FlashLoanError::FlashLoanPaused` - If flash loans are paused
+/// * `FlashLoanError::Reentrancy` - If flash loan is already active for this user/asset
+/// * `FlashLoanError::InvalidCallback` - If callback address is invalid
+/// * `FlashLoanError::Overflow` - If calculation overflow occurs
+pub fn execute_flash_loan(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+    callback: Address,
+) -> Result<i128, FlashLoanError> {
+    // Validate the request and calculate the fee
+    let fee = validate_flash_loan(env, &user, &asset, amount, &callback)?;
     let total_repayment = amount.checked_add(fee).ok_or(FlashLoanError::Overflow)?;
 
     // Check contract balance
@@ -337,18 +492,7 @@ pub fn repay_flash_loan(
     );
 
     // Credit fee to protocol reserve
-    if record.fee > 0 {
-        let reserve_key = DepositDataKey::ProtocolReserve(Some(asset.clone()));
-        let current_reserve = env
-            .storage()
-            .persistent()
-            .get::<DepositDataKey, i128>(&reserve_key)
-            .unwrap_or(0);
-        env.storage().persistent().set(
-            &reserve_key,
-            &(current_reserve.checked_add(record.fee).ok_or(FlashLoanError::Overflow)?),
-        );
-    }
+    credit_fee_to_reserve(env, &asset, record.fee)?;
 
     // Clear flash loan record
     clear_flash_loan(env, &user, &asset);
@@ -376,7 +520,7 @@ pub fn repay_flash_loan(
 /// * `fee_bps` - The new fee in basis points
 pub fn set_flash_loan_fee(env: &Env, caller: Address, fee_bps: i128) -> Result<(), FlashLoanError> {
     // Check authorization
-    crate::admin::require_admin(env, &caller).map_err(|_| FlashLoanError::InvalidCallback)?;
+    crate::admin::require_admin(env, &caller).map_err(|_| FlashLoanError::Unauthorized)?;
 
     // Validate fee (must be between 0 and 10000 basis points)
     if !(0..=10000).contains(&fee_bps) {
@@ -404,7 +548,7 @@ pub fn configure_flash_loan(
     config: FlashLoanConfig,
 ) -> Result<(), FlashLoanError> {
     // Check authorization
-    crate::admin::require_admin(env, &caller).map_err(|_| FlashLoanError::InvalidCallback)?;
+    crate::admin::require_admin(env, &caller).map_err(|_| FlashLoanError::Unauthorized)?;
 
     // Validate configuration
     if !(0..=10000).contains(&config.fee_bps) {
@@ -1,6 +1,6 @@
 #![allow(unused_variables)]
 
-use soroban_sdk::{token::TokenClient, Address, Env, String, Vec};
+use soroban_sdk::{token::TokenClient, Address, Env, String, Symbol, Vec};
 
 use crate::errors::GovernanceError;
 use crate::storage::{GovernanceDataKey, GuardianConfig};
@@ -560,6 +560,9 @@ pub fn set_multisig_config(
         .instance()
         .set(&GovernanceDataKey::MultisigConfig, &config);
 
+    Ok(())
+}
+
 /// Return the list of admins who have approved a proposal, or `None` if not found.
 pub fn get_proposal_approvals(env: &Env, proposal_id: u64) -> Option<Vec<Address>> {
     let approvals_key = GovernanceDataKey::ProposalApprovals(proposal_id);
@@ -570,26 +573,6 @@ pub fn get_proposal_approvals(env: &Env, proposal_id: u64) -> Option<Vec<Address
 // Events
 // ============================================================================
 
-fn emit_proposal_created_event(env: &Env, proposal_id: &u64, proposer: &Address) {
-    let topics = (
-        Symbol::new(env, "proposal_created"),
-        *proposal_id,
-        proposer.clone(),
-    );
-    env.events().publish(topics, ());
-}
-
-fn emit_vote_cast_event(
-    env: &Env,
-    proposal_id: &u64,
-    voter: &Address,
-    vote: &Vote,
-    voting_power: &i128,
-) {
-    let topics = (Symbol::new(env, "vote_cast"), *proposal_id, voter.clone());
-    env.events().publish(topics, (vote.clone(), *voting_power));
-}
-
 pub fn emit_proposal_executed_event(env: &Env, proposal_id: &u64, executor: &Address) {
     let topics = (
         Symbol::new(env, "proposal_executed"),
@@ -599,11 +582,6 @@ pub fn emit_proposal_executed_event(env: &Env, proposal_id: &u64, executor: &Add
     env.events().publish(topics, ());
 }
 
-fn emit_proposal_failed_event(env: &Env, proposal_id: &u64) {
-    let topics = (Symbol::new(env, "proposal_failed"), *proposal_id);
-    env.events().publish(topics, ());
-}
-
 pub fn emit_approval_event(env: &Env, proposal_id: &u64, approver: &Address) {
     let topics = (
         Symbol::new(env, "proposal_approved"),
@@ -937,6 +915,24 @@ pub fn get_multisig_config(env: &Env) -> Option<MultisigConfig> {
         .get(&GovernanceDataKey::MultisigConfig)
 }
 
+pub fn get_guardian_config(env: &Env) -> Option<GuardianConfig> {
+    env.storage()
+        .instance()
+        .get(&GovernanceDataKey::GuardianConfig)
+}
+
+pub fn get_recovery_request(env: &Env) -> Option<RecoveryRequest> {
+    env.storage()
+        .persistent()
+        .get(&GovernanceDataKey::RecoveryRequest)
+}
+
+pub fn get_recovery_approvals(env: &Env) -> Option<Vec<Address>> {
+    env.storage()
+        .persistent()
+        .get(&GovernanceDataKey::RecoveryApprovals)
+}
+
 pub fn emit_guardian_added_event(env: &Env, guardian: &Address) {
     let topics = (Symbol::new(env, "guardian_added"), guardian.clone());
     env.events().publish(topics, ());
@@ -0,0 +1,217 @@
+//! # Health History Module
+//!
+//! Keeps a bounded, per-user history of health factor snapshots so disputes
+//! ("I was liquidated while healthy") and near-miss analytics can be settled
+//! from on-chain data.
+//!
+//! ## Snapshots
+//! A snapshot is recorded:
+//! - after every state-changing interaction: deposit, withdraw, borrow, repay
+//!   and liquidation, on both the core and the cross-asset positions;
+//! - right before a liquidation executes, with the health factor the
+//!   liquidation check used (`liq_check`);
+//! - when a reserve price update moves a cross-asset position into a
+//!   different risk level band (`price_cross`).
+//!
+//! Core snapshots use `analytics::calculate_health_factor`; cross-asset
+//! snapshots use `get_user_position_summary` and are flagged `cross_asset`.
+//! Risk level bands follow `analytics::calculate_user_risk_level`.
+//!
+//! ## Invariants
+//! - At most `MAX_HEALTH_SNAPSHOTS` snapshots are kept per user; the oldest
+//!   is dropped first.
+//! - Recording never fails the interaction that triggers it; positions whose
+//!   health factor cannot be computed (e.g. stale prices) are skipped.
+
+#![allow(unused)]
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+use crate::cross_asset::AssetKey;
+
+/// Snapshots kept per user
+pub const MAX_HEALTH_SNAPSHOTS: u32 = 50;
+
+/// Maximum snapshots returned per history page
+pub const MAX_HISTORY_PAGE: u32 = 50;
+
+/// Storage keys for health history data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum HealthHistoryDataKey {
+    /// Health factor snapshots of a user, oldest first: Vec<HealthSnapshot>
+    HealthHistory(Address),
+}
+
+/// Health factor of a position at a point in time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthSnapshot {
+    /// Health factor (scaled by 10000, `i128::MAX` without debt)
+    pub health_factor: i128,
+    /// Risk level band (1 = low, 5 = critical)
+    pub risk_level: i128,
+    /// Collateral (value, for cross-asset positions) backing the health factor
+    pub collateral: i128,
+    /// Debt (value, for cross-asset positions) backing the health factor
+    pub debt: i128,
+    /// Whether the snapshot is of the cross-asset position
+    pub cross_asset: bool,
+    /// What triggered the snapshot
+    pub reason: Symbol,
+    /// Ledger timestamp
+    pub timestamp: u64,
+    /// Ledger sequence
+    pub ledger: u32,
+}
+
+fn get_history(env: &Env, user: &Address) -> Vec<HealthSnapshot> {
+    env.storage()
+        .persistent()
+        .get::<HealthHistoryDataKey, Vec<HealthSnapshot>>(&HealthHistoryDataKey::HealthHistory(
+            user.clone(),
+        ))
+        .unwrap_or(Vec::new(env))
+}
+
+fn push_snapshot(
+    env: &Env,
+    user: &Address,
+    health_factor: i128,
+    collateral: i128,
+    debt: i128,
+    cross_asset: bool,
+    reason: Symbol,
+) {
+    let mut history = get_history(env, user);
+    if history.len() >= MAX_HEALTH_SNAPSHOTS {
+        history.pop_front();
+    }
+    history.push_back(HealthSnapshot {
+        health_factor,
+        risk_level: crate::analytics::calculate_user_risk_level(health_factor),
+        collateral,
+        debt,
+        cross_asset,
+        reason,
+        timestamp: env.ledger().timestamp(),
+        ledger: env.ledger().sequence(),
+    });
+    env.storage()
+        .persistent()
+        .set(&HealthHistoryDataKey::HealthHistory(user.clone()), &history);
+}
+
+/// Record the health of a user's core position
+pub(crate) fn record_health_snapshot(env: &Env, user: &Address, reason: &str) {
+    let position = match crate::analytics::get_user_position_summary(env, user) {
+        Ok(position) => position,
+        Err(_) => return,
+    };
+    let health_factor = match crate::analytics::calculate_health_factor(env, user) {
+        Ok(health_factor) => health_factor,
+        Err(_) => return,
+    };
+    push_snapshot(
+        env,
+        user,
+        health_factor,
+        position.collateral,
+        position.debt,
+        false,
+        Symbol::new(env, reason),
+    );
+}
+
+/// Record the health factor a liquidation check was decided on
+pub(crate) fn record_liquidation_check(
+    env: &Env,
+    borrower: &Address,
+    collateral_value: i128,
+    total_debt: i128,
+) {
+    let health_factor = if total_debt > 0 {
+        collateral_value.saturating_mul(10_000) / total_debt
+    } else {
+        i128::MAX
+    };
+    push_snapshot(
+        env,
+        borrower,
+        health_factor,
+        collateral_value,
+        total_debt,
+        false,
+        Symbol::new(env, "liq_check"),
+    );
+}
+
+/// Record the health of a user's cross-asset position
+pub(crate) fn record_cross_asset_snapshot(env: &Env, user: &Address, reason: &str) {
+    if let Ok(summary) = crate::cross_asset::get_user_position_summary(env, user) {
+        push_snapshot(
+            env,
+            user,
+            summary.health_factor,
+            summary.total_collateral_value,
+            summary.total_debt_value,
+            true,
+            Symbol::new(env, reason),
+        );
+    }
+}
+
+/// Record a snapshot for every cross-asset position in `asset_key` whose risk
+/// level band changed since its last cross-asset snapshot
+///
+/// Called after a reserve price update; cost grows with the number of positions.
+pub(crate) fn record_threshold_crossings(env: &Env, asset_key: &AssetKey) {
+    for (key, position) in crate::cross_asset::get_user_asset_positions(env).iter() {
+        if key.asset != *asset_key || (position.collateral == 0 && position.debt_principal == 0) {
+            continue;
+        }
+        let summary = match crate::cross_asset::get_user_position_summary(env, &key.user) {
+            Ok(summary) => summary,
+            Err(_) => continue,
+        };
+        let risk_level = crate::analytics::calculate_user_risk_level(summary.health_factor);
+        let last_risk_level = get_history(env, &key.user)
+            .iter()
+            .rev()
+            .find(|snapshot| snapshot.cross_asset)
+            .map(|snapshot| snapshot.risk_level);
+        if last_risk_level != Some(risk_level) {
+            push_snapshot(
+                env,
+                &key.user,
+                summary.health_factor,
+                summary.total_collateral_value,
+                summary.total_debt_value,
+                true,
+                Symbol::new(env, "price_cross"),
+            );
+        }
+    }
+}
+
+/// Get a page of a user's health history, oldest first
+///
+/// # Arguments
+/// * `user` - The user address
+/// * `start` - Index of the first snapshot to return
+/// * `limit` - Maximum snapshots to return (capped at `MAX_HISTORY_PAGE`)
+pub fn get_health_history(
+    env: &Env,
+    user: &Address,
+    start: u32,
+    limit: u32,
+) -> Vec<HealthSnapshot> {
+    let history = get_history(env, user);
+    let end = start
+        .saturating_add(limit.min(MAX_HISTORY_PAGE))
+        .min(history.len());
+    if start >= end {
+        return Vec::new(env);
+    }
+    history.slice(start..end)
+}
@@ -0,0 +1,109 @@
+//! # Health Simulation Module
+//!
+//! Dry-runs a sequence of core actions against a user's position so UIs can
+//! show the health factor a transaction would leave behind before it is sent.
+//!
+//! `simulate_health_factor` starts from the collateral and debt of
+//! `analytics::get_account_data`, applies each action in order and returns
+//! the health factor liquidations would be decided on. Nothing is written.
+//!
+//! ## Invariants
+//! - Core collateral is fungible, so actions carry amounts only.
+//! - A borrow adds its full amount to the debt; the borrow fee is deducted
+//!   from what the user receives, not added on top.
+//! - A repayment larger than the debt only clears the debt.
+//! - Caps, pause switches and liquidity are not checked; a sequence that
+//!   withdraws more collateral than the position holds is rejected.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+/// Errors that can occur during health factor simulation
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum HealthSimulationError {
+    /// An action amount is zero or negative
+    InvalidAmount = 1,
+    /// A withdrawal exceeds the collateral at that point of the sequence
+    InsufficientCollateral = 2,
+    /// Overflow occurred during calculation
+    Overflow = 3,
+}
+
+/// A proposed action on a core position
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SimAction {
+    /// Deposit collateral
+    Deposit(i128),
+    /// Withdraw collateral
+    Withdraw(i128),
+    /// Borrow against the collateral
+    Borrow(i128),
+    /// Repay debt
+    Repay(i128),
+}
+
+/// Get the health factor of a user's position after a sequence of actions
+///
+/// # Arguments
+/// * `user` - The position owner
+/// * `actions` - Actions to apply, in order
+///
+/// # Returns
+/// The health factor after the actions (scaled by 10000, `i128::MAX`
+/// without debt)
+///
+/// # Errors
+/// * `HealthSimulationError::InvalidAmount` - If an amount is not positive
+/// * `HealthSimulationError::InsufficientCollateral` - If a withdrawal
+///   exceeds the collateral
+/// * `HealthSimulationError::Overflow` - If a balance overflows
+pub fn simulate_health_factor(
+    env: &Env,
+    user: &Address,
+    actions: Vec<SimAction>,
+) -> Result<i128, HealthSimulationError> {
+    let account = crate::analytics::get_account_data(env, user)
+        .map_err(|_| HealthSimulationError::Overflow)?;
+    let mut collateral = account.total_collateral;
+    let mut debt = account.total_debt;
+
+    for action in actions.iter() {
+        let amount = match &action {
+            SimAction::Deposit(amount)
+            | SimAction::Withdraw(amount)
+            | SimAction::Borrow(amount)
+            | SimAction::Repay(amount) => *amount,
+        };
+        if amount <= 0 {
+            return Err(HealthSimulationError::InvalidAmount);
+        }
+
+        match action {
+            SimAction::Deposit(_) => {
+                collateral = collateral
+                    .checked_add(amount)
+                    .ok_or(HealthSimulationError::Overflow)?;
+            }
+            SimAction::Withdraw(_) => {
+                if amount > collateral {
+                    return Err(HealthSimulationError::InsufficientCollateral);
+                }
+                collateral -= amount;
+            }
+            SimAction::Borrow(_) => {
+                debt = debt
+                    .checked_add(amount)
+                    .ok_or(HealthSimulationError::Overflow)?;
+            }
+            SimAction::Repay(_) => {
+                debt -= amount.min(debt);
+            }
+        }
+    }
+
+    crate::liquidate::health_factor(env, &None, collateral, debt)
+        .map_err(|_| HealthSimulationError::Overflow)
+}
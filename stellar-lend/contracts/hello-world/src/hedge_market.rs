@@ -0,0 +1,603 @@
+//! # Liquidation Hedge Marketplace
+//!
+//! A small options-style market for "HF puts".
+//!
+//! Liquidity providers (writers) post offers that escrow debt-asset liquidity
+//! and quote a health factor strike and a premium. A borrower buys a put from
+//! an offer by paying the premium up front to the writer; in return, a fixed
+//! amount of their debt is reserved out of the writer's escrow.
+//!
+//! If the borrower's health factor falls below the strike before expiry, a
+//! keeper exercises the put and the reserved liquidity repays the borrower's
+//! debt, pulling the position back from liquidation range during price
+//! spikes. Puts that expire unexercised return their reserved liquidity to the
+//! writer.
+//!
+//! Health factor is the one liquidations use (`liquidate::health_factor`):
+//! collateral valued in the debt asset at oracle prices, weighted by the
+//! liquidation threshold, over debt including interest, with 10_000 at the
+//! liquidation point. Each put prices the collateral asset its holder named
+//! when buying it.
+//!
+//! ## Invariants
+//! - A put can only be bought while the position is healthier than the strike.
+//! - A put only repays debt in an asset its holder actually owes.
+//! - Strikes sit above the liquidation point so puts settle before
+//!   liquidations do.
+//! - Reserved liquidity is either paid out once on exercise or returned once on
+//!   expiry; never both.
+//! - Exercised puts repay debt through the same bookkeeping as `repay_debt`,
+//!   so insurance premiums, the reserve factor and the liquidation index see
+//!   the repayment.
+//! - Offer and put liquidity is escrowed in `reserve` until it repays debt, so
+//!   the lending pool never lends it out.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{
+    emit_hedge_offer_cancelled, emit_hedge_offer_created, emit_hf_put_exercised,
+    emit_hf_put_expired, emit_hf_put_purchased, HedgeOfferCancelledEvent, HedgeOfferCreatedEvent,
+    HfPutExercisedEvent, HfPutExpiredEvent, HfPutPurchasedEvent,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Health factor at which a position becomes liquidatable
+const LIQUIDATION_HF_BPS: i128 = 10_000;
+
+/// Highest strike a writer may quote (3.0x)
+pub const MAX_STRIKE_HF_BPS: i128 = 30_000;
+
+/// Errors that can occur in the hedge marketplace
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum HedgeMarketError {
+    /// Caller is not authorized
+    Unauthorized = 1,
+    /// Amount must be greater than zero
+    InvalidAmount = 2,
+    /// Offer parameters are invalid
+    InvalidOffer = 3,
+    /// Offer does not exist
+    OfferNotFound = 4,
+    /// Offer is cancelled or expired
+    OfferUnavailable = 5,
+    /// Offer has too little unreserved liquidity
+    InsufficientCapacity = 6,
+    /// Borrower has no open debt position
+    NoPosition = 7,
+    /// Position health factor is already at or below the strike
+    StrikeReached = 8,
+    /// Put does not exist
+    PutNotFound = 9,
+    /// Put was already exercised or expired
+    PutNotActive = 10,
+    /// Put has expired
+    PutExpired = 11,
+    /// Position health factor is still above the strike
+    StrikeNotReached = 12,
+    /// Put has not expired yet
+    PutNotExpired = 13,
+    /// Native asset address is not configured
+    AssetNotConfigured = 14,
+    /// Overflow occurred during calculation
+    Overflow = 15,
+    /// Holder does not owe the put's debt asset
+    DebtAssetNotOwed = 16,
+    /// Asset price is not available
+    PriceNotAvailable = 17,
+    /// Repaying the holder's debt failed (e.g. repayments are paused)
+    RepayFailed = 18,
+}
+
+/// Storage keys for hedge marketplace data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum HedgeDataKey {
+    /// Next offer id: u64
+    NextHedgeOfferId,
+    /// Offer by id: HedgeOffer
+    HedgeOffer(u64),
+    /// Next put id: u64
+    NextHfPutId,
+    /// Put by id: HfPut
+    HfPut(u64),
+}
+
+/// Liquidity posted by a writer to underwrite HF puts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HedgeOffer {
+    /// Liquidity provider
+    pub writer: Address,
+    /// Debt asset the liquidity repays (None = native)
+    pub debt_asset: Option<Address>,
+    /// Health factor below which puts can be exercised (bps)
+    pub strike_hf_bps: i128,
+    /// Premium charged on the reserved repay amount (bps)
+    pub premium_bps: i128,
+    /// Escrowed liquidity not yet reserved by puts
+    pub available: i128,
+    /// Expiry of the offer and of every put bought from it
+    pub expiry: u64,
+    /// Whether new puts can be bought
+    pub active: bool,
+}
+
+/// Status of an HF put
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HfPutStatus {
+    Active,
+    Exercised,
+    Expired,
+}
+
+/// Right to have `repay_amount` of debt repaid once HF crosses the strike
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HfPut {
+    /// Offer the put was bought from
+    pub offer_id: u64,
+    /// Borrower protected by the put
+    pub holder: Address,
+    /// Writer who receives unused liquidity back
+    pub writer: Address,
+    /// Debt asset repaid on exercise (None = native)
+    pub debt_asset: Option<Address>,
+    /// Collateral asset the holder's health factor is priced with
+    pub collateral_asset: Option<Address>,
+    /// Health factor strike (bps)
+    pub strike_hf_bps: i128,
+    /// Debt repaid on exercise
+    pub repay_amount: i128,
+    /// Premium paid to the writer
+    pub premium_paid: i128,
+    /// Last time the put can be exercised
+    pub expiry: u64,
+    /// Current status
+    pub status: HfPutStatus,
+}
+
+fn resolve_asset(env: &Env, debt_asset: &Option<Address>) -> Result<Address, HedgeMarketError> {
+    match debt_asset {
+        Some(asset) => Ok(asset.clone()),
+        None => env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Address>(&DepositDataKey::NativeAssetAddress)
+            .ok_or(HedgeMarketError::AssetNotConfigured),
+    }
+}
+
+fn next_id(env: &Env, key: HedgeDataKey) -> u64 {
+    let id = env
+        .storage()
+        .persistent()
+        .get::<HedgeDataKey, u64>(&key)
+        .unwrap_or(0);
+    env.storage().persistent().set(&key, &(id + 1));
+    id
+}
+
+fn load_offer(env: &Env, offer_id: u64) -> Result<HedgeOffer, HedgeMarketError> {
+    env.storage()
+        .persistent()
+        .get::<HedgeDataKey, HedgeOffer>(&HedgeDataKey::HedgeOffer(offer_id))
+        .ok_or(HedgeMarketError::OfferNotFound)
+}
+
+fn save_offer(env: &Env, offer_id: u64, offer: &HedgeOffer) {
+    env.storage()
+        .persistent()
+        .set(&HedgeDataKey::HedgeOffer(offer_id), offer);
+}
+
+fn load_put(env: &Env, put_id: u64) -> Result<HfPut, HedgeMarketError> {
+    env.storage()
+        .persistent()
+        .get::<HedgeDataKey, HfPut>(&HedgeDataKey::HfPut(put_id))
+        .ok_or(HedgeMarketError::PutNotFound)
+}
+
+fn save_put(env: &Env, put_id: u64, put: &HfPut) {
+    env.storage()
+        .persistent()
+        .set(&HedgeDataKey::HfPut(put_id), put);
+}
+
+/// Health factor of a put holder, priced as liquidations price it
+fn health_factor(
+    env: &Env,
+    position: &Position,
+    collateral_asset: &Option<Address>,
+    debt_asset: &Option<Address>,
+) -> Result<i128, HedgeMarketError> {
+    let total_debt = position
+        .debt
+        .checked_add(position.borrow_interest)
+        .ok_or(HedgeMarketError::Overflow)?;
+    crate::liquidate::priced_health_factor(
+        env,
+        collateral_asset,
+        debt_asset,
+        position.collateral,
+        total_debt,
+    )
+    .map_err(|e| match e {
+        crate::liquidate::LiquidationError::Overflow => HedgeMarketError::Overflow,
+        _ => HedgeMarketError::PriceNotAvailable,
+    })
+}
+
+/// Check that `user` owes `debt_asset`
+fn require_debt_asset(
+    env: &Env,
+    user: &Address,
+    debt_asset: &Option<Address>,
+) -> Result<(), HedgeMarketError> {
+    let asset = crate::deposit::canonical_asset(env, debt_asset.clone());
+    if !crate::reserve::get_user_debt_assets(env, user).contains(&asset) {
+        return Err(HedgeMarketError::DebtAssetNotOwed);
+    }
+    Ok(())
+}
+
+/// Load a position with interest and insurance premium booked up to now, as
+/// liquidations do, and store it
+fn load_position(env: &Env, user: &Address) -> Result<Position, HedgeMarketError> {
+    let key = DepositDataKey::Position(user.clone());
+    let mut position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&key)
+        .ok_or(HedgeMarketError::NoPosition)?;
+    if position.debt == 0 {
+        position.borrow_interest = 0;
+    }
+    crate::interest_index::accrue_position(env, user, &mut position)
+        .map_err(|_| HedgeMarketError::Overflow)?;
+    crate::liquidation_insurance::accrue_premium(env, user, &mut position)
+        .map_err(|_| HedgeMarketError::Overflow)?;
+    env.storage().persistent().set(&key, &position);
+    Ok(position)
+}
+
+/// Post liquidity to underwrite HF puts
+///
+/// # Arguments
+/// * `writer` - The liquidity provider
+/// * `debt_asset` - Debt asset the liquidity repays (None = native)
+/// * `strike_hf_bps` - Health factor strike, above the liquidation threshold
+/// * `premium_bps` - Premium charged on each reserved repay amount
+/// * `amount` - Liquidity escrowed with the offer
+/// * `expiry` - Expiry timestamp of the offer and its puts
+///
+/// # Returns
+/// Returns the offer id
+pub fn create_hedge_offer(
+    env: &Env,
+    writer: Address,
+    debt_asset: Option<Address>,
+    strike_hf_bps: i128,
+    premium_bps: i128,
+    amount: i128,
+    expiry: u64,
+) -> Result<u64, HedgeMarketError> {
+    writer.require_auth();
+    if amount <= 0 {
+        return Err(HedgeMarketError::InvalidAmount);
+    }
+    if strike_hf_bps <= LIQUIDATION_HF_BPS
+        || strike_hf_bps > MAX_STRIKE_HF_BPS
+        || !(0..=BASIS_POINTS_SCALE).contains(&premium_bps)
+        || expiry <= env.ledger().timestamp()
+    {
+        return Err(HedgeMarketError::InvalidOffer);
+    }
+
+    let token = resolve_asset(env, &debt_asset)?;
+    soroban_sdk::token::Client::new(env, &token).transfer(
+        &writer,
+        env.current_contract_address(),
+        &amount,
+    );
+    crate::reserve::record_escrow_change(env, &debt_asset, amount);
+
+    let offer_id = next_id(env, HedgeDataKey::NextHedgeOfferId);
+    save_offer(
+        env,
+        offer_id,
+        &HedgeOffer {
+            writer: writer.clone(),
+            debt_asset: debt_asset.clone(),
+            strike_hf_bps,
+            premium_bps,
+            available: amount,
+            expiry,
+            active: true,
+        },
+    );
+
+    emit_hedge_offer_created(
+        env,
+        HedgeOfferCreatedEvent {
+            offer_id,
+            writer,
+            debt_asset,
+            strike_hf_bps,
+            premium_bps,
+            amount,
+            expiry,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(offer_id)
+}
+
+/// Cancel an offer and withdraw its unreserved liquidity
+///
+/// Puts already bought from the offer stay in force.
+///
+/// # Returns
+/// Returns the liquidity refunded to the writer
+pub fn cancel_hedge_offer(
+    env: &Env,
+    writer: Address,
+    offer_id: u64,
+) -> Result<i128, HedgeMarketError> {
+    writer.require_auth();
+    let mut offer = load_offer(env, offer_id)?;
+    if offer.writer != writer {
+        return Err(HedgeMarketError::Unauthorized);
+    }
+    if !offer.active {
+        return Err(HedgeMarketError::OfferUnavailable);
+    }
+
+    let refunded = offer.available;
+    offer.available = 0;
+    offer.active = false;
+    save_offer(env, offer_id, &offer);
+
+    if refunded > 0 {
+        crate::reserve::record_escrow_change(env, &offer.debt_asset, -refunded);
+        let token = resolve_asset(env, &offer.debt_asset)?;
+        soroban_sdk::token::Client::new(env, &token).transfer(
+            &env.current_contract_address(),
+            &writer,
+            &refunded,
+        );
+    }
+
+    emit_hedge_offer_cancelled(
+        env,
+        HedgeOfferCancelledEvent {
+            offer_id,
+            writer,
+            refunded,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(refunded)
+}
+
+/// Buy an HF put from an offer
+///
+/// The premium is paid in the debt asset straight to the writer. The holder
+/// must owe the offer's debt asset.
+///
+/// # Arguments
+/// * `holder` - The borrower buying protection
+/// * `offer_id` - The offer to buy from
+/// * `collateral_asset` - Collateral asset the holder's health factor is
+///   priced with (None for native XLM)
+/// * `repay_amount` - Debt to be repaid if the strike is crossed
+///
+/// # Returns
+/// Returns the put id
+pub fn buy_hf_put(
+    env: &Env,
+    holder: Address,
+    offer_id: u64,
+    collateral_asset: Option<Address>,
+    repay_amount: i128,
+) -> Result<u64, HedgeMarketError> {
+    holder.require_auth();
+    if repay_amount <= 0 {
+        return Err(HedgeMarketError::InvalidAmount);
+    }
+
+    let mut offer = load_offer(env, offer_id)?;
+    let now = env.ledger().timestamp();
+    if !offer.active || now >= offer.expiry {
+        return Err(HedgeMarketError::OfferUnavailable);
+    }
+    if repay_amount > offer.available {
+        return Err(HedgeMarketError::InsufficientCapacity);
+    }
+
+    let position = load_position(env, &holder)?;
+    if position.debt == 0 {
+        return Err(HedgeMarketError::NoPosition);
+    }
+    require_debt_asset(env, &holder, &offer.debt_asset)?;
+    if health_factor(env, &position, &collateral_asset, &offer.debt_asset)? <= offer.strike_hf_bps {
+        return Err(HedgeMarketError::StrikeReached);
+    }
+
+    let premium = repay_amount
+        .checked_mul(offer.premium_bps)
+        .ok_or(HedgeMarketError::Overflow)?
+        / BASIS_POINTS_SCALE;
+    if premium > 0 {
+        let token = resolve_asset(env, &offer.debt_asset)?;
+        soroban_sdk::token::Client::new(env, &token).transfer(&holder, &offer.writer, &premium);
+    }
+
+    offer.available -= repay_amount;
+    save_offer(env, offer_id, &offer);
+
+    let put_id = next_id(env, HedgeDataKey::NextHfPutId);
+    save_put(
+        env,
+        put_id,
+        &HfPut {
+            offer_id,
+            holder: holder.clone(),
+            writer: offer.writer.clone(),
+            debt_asset: offer.debt_asset.clone(),
+            collateral_asset,
+            strike_hf_bps: offer.strike_hf_bps,
+            repay_amount,
+            premium_paid: premium,
+            expiry: offer.expiry,
+            status: HfPutStatus::Active,
+        },
+    );
+
+    emit_hf_put_purchased(
+        env,
+        HfPutPurchasedEvent {
+            put_id,
+            offer_id,
+            holder,
+            repay_amount,
+            premium,
+            timestamp: now,
+        },
+    );
+
+    Ok(put_id)
+}
+
+/// Exercise an HF put whose holder has crossed the strike (keeper or admin)
+///
+/// Repays up to the put's repay amount of the holder's debt, interest first,
+/// as long as the holder still owes the put's debt asset. The repayment is
+/// booked like `repay_debt`. Any part of the reserved liquidity not needed
+/// because the debt is smaller goes back to the writer.
+///
+/// # Returns
+/// Returns the debt repaid
+pub fn exercise_hf_put(env: &Env, keeper: Address, put_id: u64) -> Result<i128, HedgeMarketError> {
+    keeper.require_auth();
+    crate::admin::require_role_or_admin(env, &keeper, Symbol::new(env, "keeper"))
+        .map_err(|_| HedgeMarketError::Unauthorized)?;
+
+    let mut put = load_put(env, put_id)?;
+    if put.status != HfPutStatus::Active {
+        return Err(HedgeMarketError::PutNotActive);
+    }
+    if env.ledger().timestamp() > put.expiry {
+        return Err(HedgeMarketError::PutExpired);
+    }
+
+    require_debt_asset(env, &put.holder, &put.debt_asset)?;
+    let position = load_position(env, &put.holder)?;
+    if health_factor(env, &position, &put.collateral_asset, &put.debt_asset)? >= put.strike_hf_bps {
+        return Err(HedgeMarketError::StrikeNotReached);
+    }
+
+    let (_, interest_paid, principal_paid) = crate::repay::repay_from_contract(
+        env,
+        put.holder.clone(),
+        put.debt_asset.clone(),
+        put.repay_amount,
+    )
+    .map_err(|e| match e {
+        crate::repay::RepayError::Overflow => HedgeMarketError::Overflow,
+        _ => HedgeMarketError::RepayFailed,
+    })?;
+    let repaid = interest_paid + principal_paid;
+    let position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(put.holder.clone()))
+        .ok_or(HedgeMarketError::NoPosition)?;
+
+    // Repaid liquidity joins the pool's; the rest goes back
+    crate::reserve::record_escrow_change(env, &put.debt_asset, -put.repay_amount);
+    let unused = put.repay_amount - repaid;
+    if unused > 0 {
+        let token = resolve_asset(env, &put.debt_asset)?;
+        soroban_sdk::token::Client::new(env, &token).transfer(
+            &env.current_contract_address(),
+            &put.writer,
+            &unused,
+        );
+    }
+
+    put.status = HfPutStatus::Exercised;
+    save_put(env, put_id, &put);
+
+    emit_hf_put_exercised(
+        env,
+        HfPutExercisedEvent {
+            put_id,
+            holder: put.holder,
+            keeper,
+            repaid,
+            health_factor: health_factor(env, &position, &put.collateral_asset, &put.debt_asset)?,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(repaid)
+}
+
+/// Release the reserved liquidity of an expired put back to its writer
+///
+/// Anyone can call this once the put has expired.
+///
+/// # Returns
+/// Returns the liquidity returned to the writer
+pub fn expire_hf_put(env: &Env, put_id: u64) -> Result<i128, HedgeMarketError> {
+    let mut put = load_put(env, put_id)?;
+    if put.status != HfPutStatus::Active {
+        return Err(HedgeMarketError::PutNotActive);
+    }
+    if env.ledger().timestamp() <= put.expiry {
+        return Err(HedgeMarketError::PutNotExpired);
+    }
+
+    put.status = HfPutStatus::Expired;
+    save_put(env, put_id, &put);
+    crate::reserve::record_escrow_change(env, &put.debt_asset, -put.repay_amount);
+
+    let token = resolve_asset(env, &put.debt_asset)?;
+    soroban_sdk::token::Client::new(env, &token).transfer(
+        &env.current_contract_address(),
+        &put.writer,
+        &put.repay_amount,
+    );
+
+    emit_hf_put_expired(
+        env,
+        HfPutExpiredEvent {
+            put_id,
+            writer: put.writer,
+            released: put.repay_amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(put.repay_amount)
+}
+
+/// Get an offer by id
+pub fn get_hedge_offer(env: &Env, offer_id: u64) -> Option<HedgeOffer> {
+    load_offer(env, offer_id).ok()
+}
+
+/// Get a put by id
+pub fn get_hf_put(env: &Env, put_id: u64) -> Option<HfPut> {
+    load_put(env, put_id).ok()
+}
@@ -0,0 +1,116 @@
+//! # Intents Module
+//!
+//! Per-user nonces for off-chain signed intents (approvals, queued swaps,
+//! commit-reveal commitments). Every intent carries a nonce; executing it
+//! consumes the nonce, and a user can cancel a nonce up front so the intent
+//! signed with it can never execute.
+//!
+//! Nonces only move forward: consuming nonce `n` sets the user's next nonce
+//! to `n + 1`, so gaps left by cancelled intents do not block later ones.
+//!
+//! ## Invariants
+//! - A nonce below the user's next nonce is spent and can be neither
+//!   consumed nor cancelled again.
+//! - A cancelled nonce can never be consumed.
+//! - Only the user can cancel their own intents.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+use crate::events::{emit_intent_cancelled, IntentCancelledEvent};
+
+/// Errors that can occur during intent operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum IntentError {
+    /// Nonce was already consumed
+    NonceAlreadyUsed = 1,
+    /// Intent with this nonce was cancelled
+    IntentCancelled = 2,
+    /// Overflow occurred during calculation
+    Overflow = 3,
+}
+
+/// Storage keys for intent data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum IntentDataKey {
+    /// Next unused nonce of a user: u64
+    UserNonce(Address),
+    /// Whether a user's intent nonce was cancelled: bool
+    CancelledIntent(Address, u64),
+}
+
+/// Get the next unused nonce of a user
+pub fn get_user_nonce(env: &Env, user: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<IntentDataKey, u64>(&IntentDataKey::UserNonce(user.clone()))
+        .unwrap_or(0)
+}
+
+/// Check whether a user cancelled the intent signed with `nonce`
+pub fn is_intent_cancelled(env: &Env, user: &Address, nonce: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get::<IntentDataKey, bool>(&IntentDataKey::CancelledIntent(user.clone(), nonce))
+        .unwrap_or(false)
+}
+
+/// Cancel the intent signed with `nonce`
+///
+/// # Arguments
+/// * `user` - The intent signer (must authorize)
+/// * `nonce` - The nonce of the intent to invalidate
+///
+/// # Errors
+/// * `IntentError::NonceAlreadyUsed` - If the nonce was already consumed
+/// * `IntentError::IntentCancelled` - If the nonce was already cancelled
+pub fn cancel_intent(env: &Env, user: Address, nonce: u64) -> Result<(), IntentError> {
+    user.require_auth();
+
+    if nonce < get_user_nonce(env, &user) {
+        return Err(IntentError::NonceAlreadyUsed);
+    }
+    if is_intent_cancelled(env, &user, nonce) {
+        return Err(IntentError::IntentCancelled);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&IntentDataKey::CancelledIntent(user.clone(), nonce), &true);
+
+    emit_intent_cancelled(
+        env,
+        IntentCancelledEvent {
+            user,
+            nonce,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Consume `nonce` for an intent being executed on behalf of `user`
+///
+/// Callers are responsible for authenticating the intent itself.
+///
+/// # Errors
+/// * `IntentError::NonceAlreadyUsed` - If the nonce was already consumed
+/// * `IntentError::IntentCancelled` - If the user cancelled the nonce
+pub(crate) fn consume_nonce(env: &Env, user: &Address, nonce: u64) -> Result<(), IntentError> {
+    if nonce < get_user_nonce(env, user) {
+        return Err(IntentError::NonceAlreadyUsed);
+    }
+    if is_intent_cancelled(env, user, nonce) {
+        return Err(IntentError::IntentCancelled);
+    }
+
+    let next = nonce.checked_add(1).ok_or(IntentError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&IntentDataKey::UserNonce(user.clone()), &next);
+    Ok(())
+}
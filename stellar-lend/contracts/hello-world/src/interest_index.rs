@@ -0,0 +1,231 @@
+//! # Interest Index Module
+//!
+//! Cumulative borrow and supply indexes of the core lending pool, updated
+//! lazily on every interaction instead of per-user rate-times-time accrual.
+//!
+//! ## Indexes
+//! Both indexes start at `INDEX_SCALE` and are advanced by `accrue_interest`
+//! at the rates in force since the last update:
+//! - the borrow index at the borrow rate of the `interest_rate` module;
+//! - the supply index at the supply rate scaled by utilization (see
+//!   `supply_interest`).
+//!
+//! Core positions share one utilization curve and do not record which asset
+//! was borrowed, so the pool has a single pair of indexes.
+//!
+//! ## Positions
+//! A borrower's interest since their last accrual is
+//! `(debt + borrow_interest) * (borrow_index / snapshot - 1)`, where the
+//! snapshot is the borrow index at that accrual. Reading a user's debt therefore needs no loop over
+//! past periods or users; `get_user_debt` includes interest not yet booked.
+//!
+//! ## Invariants
+//! - Indexes never decrease and are advanced before utilization changes, so
+//!   each period accrues at the utilization it actually had.
+//! - Positions without a snapshot (opened before indexes existed) accrue
+//!   once from `last_accrual_time` at the current borrow rate, then switch to
+//!   the index.
+//! - Interest compounds on booked interest as on principal, so a position
+//!   owes the same whether or not it is touched in between.
+
+#![allow(unused)]
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{emit_accrue, AccrueEvent};
+use crate::interest_rate::InterestRateError;
+
+/// Fixed-point scale of the indexes (1.0)
+pub const INDEX_SCALE: i128 = 1_000_000_000_000_000_000;
+
+const BASIS_POINTS_SCALE: i128 = 10_000;
+const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+
+/// Storage keys for interest index data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum InterestIndexDataKey {
+    /// Cumulative indexes of the pool: InterestIndexes
+    InterestIndexes,
+    /// Borrow index at a user's last debt accrual: i128
+    BorrowSnapshot(Address),
+}
+
+/// Cumulative borrow and supply indexes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestIndexes {
+    /// Borrow index, scaled by `INDEX_SCALE`
+    pub borrow_index: i128,
+    /// Supply index, scaled by `INDEX_SCALE`
+    pub supply_index: i128,
+    /// Timestamp the indexes were last advanced to
+    pub last_update: u64,
+}
+
+/// Annual rate at which deposits grow (bps): supply rate times utilization
+pub(crate) fn effective_supply_rate(env: &Env) -> i128 {
+    let supply_rate = crate::interest_rate::calculate_supply_rate(env).unwrap_or(0);
+    let utilization = crate::interest_rate::calculate_utilization(env).unwrap_or(0);
+    supply_rate.saturating_mul(utilization) / BASIS_POINTS_SCALE
+}
+
+fn load_indexes(env: &Env) -> InterestIndexes {
+    env.storage()
+        .persistent()
+        .get::<InterestIndexDataKey, InterestIndexes>(&InterestIndexDataKey::InterestIndexes)
+        .unwrap_or(InterestIndexes {
+            borrow_index: INDEX_SCALE,
+            supply_index: INDEX_SCALE,
+            last_update: env.ledger().timestamp(),
+        })
+}
+
+/// Grow `index` by `rate_bps` per year over `elapsed` seconds
+fn grow(index: i128, rate_bps: i128, elapsed: i128) -> i128 {
+    let growth = index.saturating_mul(rate_bps).saturating_mul(elapsed)
+        / (BASIS_POINTS_SCALE * SECONDS_PER_YEAR);
+    index.saturating_add(growth.max(0))
+}
+
+/// Advance the indexes to the current timestamp at the current rates
+fn advance(env: &Env, state: &InterestIndexes) -> InterestIndexes {
+    let now = env.ledger().timestamp();
+    if now <= state.last_update {
+        return state.clone();
+    }
+    let elapsed = (now - state.last_update) as i128;
+    let borrow_rate = crate::interest_rate::calculate_borrow_rate(env).unwrap_or(0);
+    InterestIndexes {
+        borrow_index: grow(state.borrow_index, borrow_rate, elapsed),
+        supply_index: grow(state.supply_index, effective_supply_rate(env), elapsed),
+        last_update: now,
+    }
+}
+
+/// Get the indexes as of the current timestamp, without storing them
+pub fn get_interest_indexes(env: &Env) -> InterestIndexes {
+    advance(env, &load_indexes(env))
+}
+
+/// Advance the stored indexes to the current timestamp
+///
+/// Must be called before any change to deposits or borrows. Calling it more
+/// than once in a ledger is a no-op. Each advance emits an `accrue` event
+/// with the new indexes and the rates they grew at.
+pub fn accrue_interest(env: &Env) -> InterestIndexes {
+    let previous = load_indexes(env);
+    let state = advance(env, &previous);
+    env.storage()
+        .persistent()
+        .set(&InterestIndexDataKey::InterestIndexes, &state);
+
+    if state.last_update > previous.last_update {
+        emit_accrue(
+            env,
+            AccrueEvent {
+                borrow_index: state.borrow_index,
+                supply_index: state.supply_index,
+                borrow_rate: crate::interest_rate::calculate_borrow_rate(env).unwrap_or(0),
+                supply_rate: effective_supply_rate(env),
+                utilization: crate::interest_rate::calculate_utilization(env).unwrap_or(0),
+                elapsed: state.last_update - previous.last_update,
+                timestamp: state.last_update,
+            },
+        );
+    }
+    state
+}
+
+fn get_borrow_snapshot(env: &Env, user: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get::<InterestIndexDataKey, i128>(&InterestIndexDataKey::BorrowSnapshot(user.clone()))
+}
+
+/// Interest on a position's debt between its snapshot and `borrow_index`
+fn interest_to(
+    env: &Env,
+    user: &Address,
+    position: &Position,
+    borrow_index: i128,
+) -> Result<i128, InterestRateError> {
+    if position.debt <= 0 {
+        return Ok(0);
+    }
+    match get_borrow_snapshot(env, user) {
+        Some(snapshot) if snapshot > 0 && borrow_index > snapshot => position
+            .debt
+            .checked_add(position.borrow_interest)
+            .and_then(|owed| owed.checked_mul(borrow_index - snapshot))
+            .map(|scaled| scaled / snapshot)
+            .ok_or(InterestRateError::Overflow),
+        Some(_) => Ok(0),
+        None if env.ledger().timestamp() <= position.last_accrual_time => Ok(0),
+        None => {
+            let rate_bps = crate::interest_rate::calculate_borrow_rate(env)?;
+            crate::interest_rate::calculate_accrued_interest(
+                position.debt,
+                position.last_accrual_time,
+                env.ledger().timestamp(),
+                rate_bps,
+            )
+        }
+    }
+}
+
+/// Interest a position has accrued since its last accrual, without booking it
+pub fn pending_interest(
+    env: &Env,
+    user: &Address,
+    position: &Position,
+) -> Result<i128, InterestRateError> {
+    interest_to(env, user, position, get_interest_indexes(env).borrow_index)
+}
+
+/// Book the interest a position has accrued and move its snapshot
+///
+/// Advances the indexes first. The caller must store the position.
+///
+/// # Returns
+/// The interest added to `position.borrow_interest`
+pub(crate) fn accrue_position(
+    env: &Env,
+    user: &Address,
+    position: &mut Position,
+) -> Result<i128, InterestRateError> {
+    let indexes = accrue_interest(env);
+    let interest = interest_to(env, user, position, indexes.borrow_index)?;
+    position.borrow_interest = position
+        .borrow_interest
+        .checked_add(interest)
+        .ok_or(InterestRateError::Overflow)?;
+    position.last_accrual_time = indexes.last_update;
+    env.storage().persistent().set(
+        &InterestIndexDataKey::BorrowSnapshot(user.clone()),
+        &indexes.borrow_index,
+    );
+    Ok(interest)
+}
+
+/// Get a user's total debt, including interest not yet booked
+///
+/// # Arguments
+/// * `user` - The address of the user
+///
+/// # Returns
+/// Principal plus accrued interest; zero without a position
+pub fn get_user_debt(env: &Env, user: &Address) -> i128 {
+    match env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    {
+        Some(position) => position
+            .debt
+            .saturating_add(position.borrow_interest)
+            .saturating_add(pending_interest(env, user, &position).unwrap_or(0)),
+        None => 0,
+    }
+}
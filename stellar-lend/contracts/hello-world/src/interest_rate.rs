@@ -43,6 +43,8 @@ pub enum InterestRateError {
     DivisionByZero = 5,
     /// Contract has already been initialized
     AlreadyInitialized = 6,
+    /// The model must be changed through the parameter timelock
+    TimelockActive = 7,
 }
 
 /// Storage keys for interest rate data
@@ -94,6 +96,35 @@ pub struct InterestRateConfig {
 const BASIS_POINTS_SCALE: i128 = 10_000; // 100% = 10,000 basis points
 const SECONDS_PER_YEAR: u64 = 365 * 86400; // 31,536,000 seconds
 
+/// Fixed-point scale of the rate views (7 decimals: 10_000_000 = 100%)
+pub const RATE_VIEW_SCALE: i128 = 10_000_000;
+
+/// Convert a rate or utilization in basis points to the rate view scale
+pub fn to_rate_view(bps: i128) -> i128 {
+    bps.saturating_mul(RATE_VIEW_SCALE / BASIS_POINTS_SCALE)
+}
+
+/// Get the borrow rate of a reserve in the rate view scale
+///
+/// Core reserves share the pool's utilization curve, so every asset reports
+/// the pool rate and `asset` does not change the result.
+pub fn get_borrow_rate_view(env: &Env, asset: &Option<Address>) -> i128 {
+    to_rate_view(calculate_borrow_rate(env).unwrap_or(0))
+}
+
+/// Get the supply rate of a reserve in the rate view scale
+///
+/// Reports the rate deposits actually earn, the supply rate scaled by
+/// utilization (`interest_index::effective_supply_rate`).
+pub fn get_supply_rate_view(env: &Env, asset: &Option<Address>) -> i128 {
+    to_rate_view(crate::interest_index::effective_supply_rate(env))
+}
+
+/// Get the utilization of a reserve in the rate view scale
+pub fn get_utilization_view(env: &Env, asset: &Option<Address>) -> i128 {
+    to_rate_view(calculate_utilization(env).unwrap_or(0))
+}
+
 /// Default interest rate configuration
 fn get_default_config() -> InterestRateConfig {
     InterestRateConfig {
@@ -136,6 +167,32 @@ pub fn initialize_interest_rate_config(env: &Env, admin: Address) -> Result<(),
     Ok(())
 }
 
+/// Validate a full interest rate configuration
+///
+/// Applies the same bounds as `update_interest_rate_config`.
+pub(crate) fn validate_interest_rate_config(
+    config: &InterestRateConfig,
+) -> Result<(), InterestRateError> {
+    let in_range = |value: i128| (0..=BASIS_POINTS_SCALE).contains(&value);
+    if !in_range(config.base_rate_bps)
+        || !in_range(config.rate_floor_bps)
+        || !in_range(config.rate_ceiling_bps)
+        || !in_range(config.spread_bps)
+    {
+        return Err(InterestRateError::InvalidParameter);
+    }
+    if config.kink_utilization_bps <= 0 || config.kink_utilization_bps >= BASIS_POINTS_SCALE {
+        return Err(InterestRateError::InvalidParameter);
+    }
+    if config.multiplier_bps < 0 || config.jump_multiplier_bps < 0 {
+        return Err(InterestRateError::InvalidParameter);
+    }
+    if config.rate_floor_bps > config.rate_ceiling_bps {
+        return Err(InterestRateError::InvalidParameter);
+    }
+    Ok(())
+}
+
 /// Calculate protocol utilization
 /// Utilization = total_borrows / total_deposits (in basis points)
 /// Returns utilization in basis points (0-10000)
@@ -298,7 +355,7 @@ pub fn calculate_accrued_interest(
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin or risk manager)
 /// * `base_rate_bps` - New base rate (None to keep current)
 /// * `kink_utilization_bps` - New kink utilization (None to keep current)
 /// * `multiplier_bps` - New multiplier (None to keep current)
@@ -306,6 +363,8 @@ pub fn calculate_accrued_interest(
 /// * `rate_floor_bps` - New rate floor (None to keep current)
 /// * `rate_ceiling_bps` - New rate ceiling (None to keep current)
 /// * `spread_bps` - New spread (None to keep current)
+///
+/// Rejected with `TimelockActive` while the parameter timelock is enabled.
 #[allow(clippy::too_many_arguments)]
 pub fn update_interest_rate_config(
     env: &Env,
@@ -319,8 +378,39 @@ pub fn update_interest_rate_config(
     spread_bps: Option<i128>,
 ) -> Result<(), InterestRateError> {
     // Check authorization
-    crate::admin::require_admin(env, &caller).map_err(|_| InterestRateError::Unauthorized)?;
+    crate::admin::require_risk_manager(env, &caller)
+        .map_err(|_| InterestRateError::Unauthorized)?;
+    if crate::param_timelock::is_param_timelock_active(env) {
+        return Err(InterestRateError::TimelockActive);
+    }
 
+    apply_interest_rate_config(
+        env,
+        base_rate_bps,
+        kink_utilization_bps,
+        multiplier_bps,
+        jump_multiplier_bps,
+        rate_floor_bps,
+        rate_ceiling_bps,
+        spread_bps,
+    )
+}
+
+/// Validate and store interest rate configuration changes
+///
+/// Shared by `update_interest_rate_config` and executed timelocked changes;
+/// callers check authorization.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn apply_interest_rate_config(
+    env: &Env,
+    base_rate_bps: Option<i128>,
+    kink_utilization_bps: Option<i128>,
+    multiplier_bps: Option<i128>,
+    jump_multiplier_bps: Option<i128>,
+    rate_floor_bps: Option<i128>,
+    rate_ceiling_bps: Option<i128>,
+    spread_bps: Option<i128>,
+) -> Result<(), InterestRateError> {
     let config_key = InterestRateDataKey::InterestRateConfig;
     let mut config = get_interest_rate_config(env).ok_or(InterestRateError::InvalidParameter)?;
 
@@ -0,0 +1,243 @@
+//! # Leverage Module
+//!
+//! One-click leverage loop: `open_leveraged_position` deposits the user's
+//! initial collateral, then repeatedly borrows the debt asset, swaps it into
+//! the collateral asset through the AMM router and deposits the output, until
+//! the position holds `target_leverage` times the initial amount.
+//!
+//! ## Leverage
+//! Leverage is expressed in basis points of the initial amount (20000 = 2x):
+//! the collateral added by the loop, initial deposit included, over the
+//! initial deposit. With a maximum loan-to-value `ltv` the loop converges to
+//! `1 / (1 - ltv)`; `get_max_leverage` returns that bound, derived from the
+//! debt asset's collateral factor and the minimum collateral ratio.
+//!
+//! ## Loop
+//! Each step borrows the collateral still missing (valued at oracle prices),
+//! capped at the position's borrowable headroom, and stops once the target is
+//! within `LEVERAGE_TOLERANCE_BPS`, the headroom is exhausted or
+//! `MAX_LEVERAGE_ITERATIONS` steps have run. Every swap must return at least
+//! the oracle value of the borrowed amount less `SWAP_SLIPPAGE_TOLERANCE_BPS`.
+//!
+//! ## Invariants
+//! - Every borrow goes through the regular borrow checks, so the position
+//!   never exceeds the maximum loan-to-value.
+//! - The whole call reverts if any deposit, borrow or swap fails.
+//! - Borrowed funds never leave the contract; only swap output is deposited.
+//! - Unavailable while the action delay is enabled (see `action_delay`):
+//!   the initial deposit blocks the loop's borrows.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, Address, Env};
+
+use crate::deposit::{AssetParams, DepositDataKey};
+use crate::events::{emit_leveraged_position_opened, LeveragedPositionOpenedEvent};
+
+/// Maximum borrow-swap-deposit steps per call
+pub const MAX_LEVERAGE_ITERATIONS: u32 = 10;
+
+/// Distance to the target collateral at which the loop stops (0.5%)
+const LEVERAGE_TOLERANCE_BPS: i128 = 50;
+
+/// Slippage tolerance against the oracle price for each swap (1%)
+const SWAP_SLIPPAGE_TOLERANCE_BPS: i128 = 100;
+
+/// Errors that can occur while opening a leveraged position
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LeverageError {
+    /// Initial amount must be greater than zero
+    InvalidAmount = 1,
+    /// Collateral and debt assets must be distinct tokens
+    InvalidAsset = 2,
+    /// Target leverage must be above 1x and below the maximum leverage
+    InvalidLeverage = 3,
+    /// Depositing collateral failed
+    DepositFailed = 4,
+    /// Borrowing the debt asset failed
+    BorrowFailed = 5,
+    /// AMM swap of the borrowed asset failed
+    SwapFailed = 6,
+    /// AMM swap returned less than the minimum output
+    SlippageExceeded = 7,
+    /// Asset price is not available
+    PriceNotAvailable = 8,
+    /// Overflow occurred during calculation
+    Overflow = 9,
+}
+
+/// Get the maximum leverage reachable when borrowing `debt_asset`
+///
+/// # Returns
+/// The leverage bound in basis points; about 30000 (3x) for a 150% minimum
+/// collateral ratio and a 100% collateral factor
+pub fn get_max_leverage(env: &Env, debt_asset: &Address) -> i128 {
+    let collateral_factor = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, AssetParams>(&DepositDataKey::AssetParams(debt_asset.clone()))
+        .map_or(10000, |params| params.collateral_factor);
+    let min_ratio = crate::risk_params::get_min_collateral_ratio(env).unwrap_or(15000);
+    if min_ratio <= 0 {
+        return 10000;
+    }
+
+    let max_ltv = collateral_factor.saturating_mul(10000) / min_ratio;
+    if max_ltv >= 10000 {
+        return i128::MAX;
+    }
+    100_000_000 / (10000 - max_ltv.max(0))
+}
+
+/// Open a leveraged position in one transaction
+///
+/// # Arguments
+/// * `user` - The user opening the position
+/// * `collateral_asset` - The asset deposited and bought with borrowed funds
+/// * `debt_asset` - The asset borrowed
+/// * `initial_amount` - Collateral pulled from the user (requires approval)
+/// * `target_leverage` - Target leverage in basis points (20000 = 2x)
+///
+/// # Returns
+/// Returns a tuple (collateral_added, debt_added, leverage) where
+/// `collateral_added` includes the initial deposit and `leverage` is the
+/// leverage reached, in basis points
+///
+/// # Errors
+/// * `LeverageError::InvalidAmount` - If the initial amount is not positive
+/// * `LeverageError::InvalidAsset` - If both assets are the same
+/// * `LeverageError::InvalidLeverage` - If the target is at most 1x or not
+///   below `get_max_leverage`
+/// * `LeverageError::DepositFailed` / `BorrowFailed` - If a step is rejected
+/// * `LeverageError::SlippageExceeded` - If a swap output is too low
+pub fn open_leveraged_position(
+    env: &Env,
+    user: Address,
+    collateral_asset: Address,
+    debt_asset: Address,
+    initial_amount: i128,
+    target_leverage: i128,
+) -> Result<(i128, i128, i128), LeverageError> {
+    user.require_auth();
+    if initial_amount <= 0 {
+        return Err(LeverageError::InvalidAmount);
+    }
+    if collateral_asset == debt_asset {
+        return Err(LeverageError::InvalidAsset);
+    }
+    if target_leverage <= 10000 || target_leverage >= get_max_leverage(env, &debt_asset) {
+        return Err(LeverageError::InvalidLeverage);
+    }
+
+    let target_collateral = initial_amount
+        .checked_mul(target_leverage)
+        .map(|v| v / 10000)
+        .ok_or(LeverageError::Overflow)?;
+    let collateral_price = crate::liquidate::get_asset_price(env, &Some(collateral_asset.clone()))
+        .map_err(|_| LeverageError::PriceNotAvailable)?;
+    let debt_price = crate::liquidate::get_asset_price(env, &Some(debt_asset.clone()))
+        .map_err(|_| LeverageError::PriceNotAvailable)?;
+    if collateral_price <= 0 || debt_price <= 0 {
+        return Err(LeverageError::PriceNotAvailable);
+    }
+
+    crate::deposit::deposit_collateral(
+        env,
+        user.clone(),
+        Some(collateral_asset.clone()),
+        initial_amount,
+    )
+    .map_err(|_| LeverageError::DepositFailed)?;
+
+    let mut collateral_added = initial_amount;
+    let mut debt_added = 0i128;
+    for _ in 0..MAX_LEVERAGE_ITERATIONS {
+        let remaining = target_collateral - collateral_added;
+        if remaining.saturating_mul(10000)
+            <= target_collateral.saturating_mul(LEVERAGE_TOLERANCE_BPS)
+        {
+            break;
+        }
+
+        // Borrow the oracle value of the missing collateral, within headroom
+        let needed = remaining
+            .checked_mul(collateral_price)
+            .map(|v| v / debt_price)
+            .ok_or(LeverageError::Overflow)?;
+        let headroom = crate::borrow::get_max_borrowable(env, &user, Some(debt_asset.clone()));
+        let borrow_amount = needed.min(headroom);
+        if borrow_amount <= 0 {
+            break;
+        }
+        let (_, borrowed) = crate::borrow::borrow_internal(
+            env,
+            user.clone(),
+            Some(debt_asset.clone()),
+            borrow_amount,
+            false,
+            true,
+        )
+        .map_err(|_| LeverageError::BorrowFailed)?;
+
+        let min_amount_out = borrowed
+            .checked_mul(debt_price)
+            .and_then(|v| v.checked_mul(10000 - SWAP_SLIPPAGE_TOLERANCE_BPS))
+            .and_then(|v| v.checked_div(collateral_price.checked_mul(10000)?))
+            .ok_or(LeverageError::Overflow)?
+            .max(1);
+        let params = stellarlend_amm::SwapParams {
+            protocol: env.current_contract_address(),
+            token_in: Some(debt_asset.clone()),
+            token_out: Some(collateral_asset.clone()),
+            amount_in: borrowed,
+            min_amount_out,
+            slippage_tolerance: SWAP_SLIPPAGE_TOLERANCE_BPS,
+            deadline: env.ledger().timestamp(),
+            use_oracle_floor: false,
+        };
+        let amount_out = stellarlend_amm::execute_swap(env, env.current_contract_address(), params)
+            .map_err(|e| match e {
+                stellarlend_amm::AmmError::MinOutputNotMet
+                | stellarlend_amm::AmmError::SlippageExceeded => LeverageError::SlippageExceeded,
+                _ => LeverageError::SwapFailed,
+            })?;
+        if amount_out < min_amount_out {
+            return Err(LeverageError::SlippageExceeded);
+        }
+
+        crate::deposit::deposit_held_collateral(
+            env,
+            user.clone(),
+            Some(collateral_asset.clone()),
+            amount_out,
+        )
+        .map_err(|_| LeverageError::DepositFailed)?;
+        collateral_added = collateral_added
+            .checked_add(amount_out)
+            .ok_or(LeverageError::Overflow)?;
+        debt_added = debt_added
+            .checked_add(borrow_amount)
+            .ok_or(LeverageError::Overflow)?;
+    }
+
+    let leverage = collateral_added
+        .checked_mul(10000)
+        .map(|v| v / initial_amount)
+        .ok_or(LeverageError::Overflow)?;
+    emit_leveraged_position_opened(
+        env,
+        LeveragedPositionOpenedEvent {
+            user,
+            collateral_asset,
+            debt_asset,
+            initial_amount,
+            collateral_added,
+            debt_added,
+            leverage,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok((collateral_added, debt_added, leverage))
+}
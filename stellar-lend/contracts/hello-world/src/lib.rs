@@ -1,9 +1,25 @@
-use soroban_sdk::{contract, contractimpl, Address, Env, Map, Symbol, Vec};
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+//! # StellarLend Core Contract
+//!
+//! Single deployed entrypoint for the StellarLend protocol. Every method of
+//! [`StellarLend`] delegates to the module implementing it: core lending
+//! (deposit, withdraw, borrow, repay, liquidate), cross-asset positions,
+//! risk and rate configuration, oracles, analytics views, governance and the
+//! protocol extensions.
+
+use soroban_sdk::{
+    contract, contractimpl, Address, Bytes, BytesN, Env, Map, String, Symbol, Val, Vec,
+};
 
+pub mod admin;
 pub mod analytics;
 pub mod borrow;
+pub mod bridge;
+pub mod config;
 pub mod cross_asset;
 pub mod deposit;
+pub mod errors;
 pub mod events;
 pub mod flash_loan;
 pub mod governance;
@@ -11,158 +27,151 @@ pub mod interest_rate;
 pub mod liquidate;
 pub mod oracle;
 pub mod repay;
+pub mod reserve;
 pub mod risk_management;
+pub mod risk_params;
+pub mod storage;
+pub mod types;
 pub mod withdraw;
 
-#[cfg(test)]
-mod tests;
-
-use crate::deposit::{AssetParams, DepositDataKey, ProtocolAnalytics};
-use crate::oracle::OracleConfig;
-use crate::risk_management::{RiskConfig, RiskManagementError};
-
-/// Helper function to require admin authorization
-fn require_admin(env: &Env, caller: &Address) -> Result<(), RiskManagementError> {
-    caller.require_auth();
-    let admin_key = DepositDataKey::Admin;
-    let admin = env
-        .storage()
-        .persistent()
-        .get::<DepositDataKey, Address>(&admin_key)
-        .ok_or(RiskManagementError::Unauthorized)?;
-
-    if caller != &admin {
-        return Err(RiskManagementError::Unauthorized);
-    }
-    Ok(())
-}
-
-mod admin;
-mod borrow;
-mod deposit;
-mod errors;
-mod events;
-mod repay;
-mod reserve;
-mod risk_management;
-mod risk_params;
-mod withdraw;
-pub mod recovery;
-pub mod multisig;
-
-use borrow::borrow_asset;
-use deposit::deposit_collateral;
-use repay::repay_debt;
-
-use risk_management::{
-    initialize_risk_management, is_emergency_paused, is_operation_paused,
-    set_pause_switch, set_pause_switches, check_emergency_pause, require_admin,
-    RiskConfig, RiskManagementError,
-};
-use risk_params::{
-    can_be_liquidated,
-    get_liquidation_incentive_amount, get_max_liquidatable_amount,
-    initialize_risk_params, require_min_collateral_ratio,
-    RiskParamsError
-};
-use withdraw::withdraw_collateral;
-
-mod analytics;
-
 use analytics::{
-    generate_protocol_report, generate_user_report, get_recent_activity, get_user_activity_feed,
-    AnalyticsError, ProtocolReport, UserReport,
+    AccountData, AnalyticsError, MarketSnapshot, ProtocolMetrics, ProtocolOverview, ProtocolReport,
+    UserMetrics, UserReport,
 };
-
-mod cross_asset;
+use bridge::{BridgeConfig, BridgeError};
+use config::ConfigError;
 use cross_asset::{
-    get_asset_config_by_address, get_asset_list, get_user_asset_position,
-    get_user_position_summary, initialize_asset, update_asset_config,
-    update_asset_price, AssetConfig, AssetKey, AssetPosition, CrossAssetError, UserPositionSummary,
+    AssetConfig, AssetKey, AssetPosition, CrossAssetError, LiquidationSuggestion, PortfolioLeg,
+    UserPositionSummary,
 };
-
-mod oracle;
-use oracle::{
-    configure_oracle, get_price, set_fallback_oracle, set_primary_oracle, update_price_feed,
-    OracleConfig,
+use deposit::{DepositDataKey, DepositError, Position};
+use flash_loan::FlashLoanConfig;
+use interest_rate::InterestRateError;
+use oracle::OracleConfig;
+use reserve::{ReserveConfig, ReserveError};
+use risk_management::RiskManagementError;
+use risk_params::{RiskParams, RiskParamsError};
+use stellarlend_amm::{
+    AmmError, AmmProtocolConfig, ConstantProductPool, LiquidityParams, SwapParams, SwapStats,
+    SwapStatsScope,
 };
 
-mod config;
-use config::{config_backup, config_get, config_restore, config_set, ConfigError};
+mod amm;
 
-mod flash_loan;
-use flash_loan::{
-    configure_flash_loan, execute_flash_loan, repay_flash_loan, set_flash_loan_fee, FlashLoanConfig,
-};
+mod strategy_vault;
+use strategy_vault::{StrategyVaultConfig, StrategyVaultError, StrategyVaultView};
 
-mod bridge;
-#[allow(unused_imports)]
-use bridge::{
-    bridge_deposit, bridge_withdraw, get_bridge_config, list_bridges, register_bridge,
-    set_bridge_fee, BridgeConfig, BridgeError,
+mod liquidation_insurance;
+use liquidation_insurance::{
+    InsurancePolicy, LiquidationInsuranceConfig, LiquidationInsuranceError,
 };
 
-mod liquidate;
-use liquidate::liquidate;
-
-mod interest_rate;
-#[allow(unused_imports)]
-use interest_rate::{
-    get_current_borrow_rate, get_current_supply_rate, get_current_utilization,
-    initialize_interest_rate_config, set_emergency_rate_adjustment, update_interest_rate_config,
-    InterestRateError,
+mod cdp;
+use cdp::{CdpCollateralType, CdpConfig, CdpError, CdpVault};
+mod savings;
+use savings::{SavingsError, SavingsMarket};
+mod hedge_market;
+use hedge_market::{HedgeMarketError, HedgeOffer, HfPut};
+mod risk_monitor;
+use risk_monitor::{
+    HeartbeatDigest, OracleStaleness, ReserveConcentration, RiskMonitorError, RiskMonitorReport,
+    RiskSnapshot, SolvencySnapshot,
 };
-
-mod governance;
-
+mod bad_debt;
+use bad_debt::BadDebtError;
+mod config_simulation;
+use config_simulation::{ConfigChangeImpact, ConfigSimulationError};
+mod config_export;
+use config_export::{ConfigExportError, ProtocolConfigExport};
+mod protocol_init;
+use protocol_init::{InitConfig, ProtocolInitError};
+mod shadow_oracle;
+use shadow_oracle::{ShadowOracleError, ShadowOracleStats};
+mod health_history;
+use health_history::HealthSnapshot;
+mod user_activity;
+use user_activity::UserAction;
+mod intents;
+use intents::IntentError;
+mod liquidation_grace;
+use liquidation_grace::{LiquidationGraceConfig, LiquidationGraceError};
+mod operators;
+use operators::{OperatorApproval, OperatorCap, OperatorError};
+mod rate_stats;
+use rate_stats::{RateStats, RateStatsError};
+mod interest_index;
+use interest_index::InterestIndexes;
+mod price_oracle;
+use price_oracle::{PriceFeedSource, PriceOracleError, Sep40Asset};
+mod auction;
+use auction::{AuctionError, LiquidationAuction};
+mod liquidation_index;
+use liquidation_index::LiquidatablePositionsPage;
+mod action_delay;
+mod leverage;
+use action_delay::ActionDelayError;
+mod credit_delegation;
+use credit_delegation::CreditDelegationError;
+mod supply_token;
+use supply_token::SupplyTokenError;
+mod permit;
+use permit::{Permit, PermitError};
+mod position_token;
+use position_token::PositionTokenError;
+mod migration;
+use migration::MigrationError;
+mod rewards;
+use rewards::{RewardEmission, RewardIndexes, RewardsError};
+mod safety_module;
+use safety_module::{SafetyModuleConfig, SafetyModuleError};
+mod staking;
+use staking::{StakeLock, StakingError};
+mod supply_interest;
+mod treasury;
+use treasury::{RevenueBreakdown, TreasuryError};
+mod param_timelock;
+use param_timelock::{ParamChange, ParamTimelockError, QueuedParamChange};
+mod upgrade;
+use upgrade::{ScheduledUpgrade, UpgradeError};
+mod storage_ttl;
+use storage_ttl::StorageTtlError;
+mod health_simulation;
+use health_simulation::{HealthSimulationError, SimAction};
+mod batch_query;
+use batch_query::{BatchQueryError, QueryRequest, QueryResult};
+mod batch_actions;
+use batch_actions::{BatchAction, BatchActionError};
+mod standing_instructions;
+use standing_instructions::{InstructionTrigger, StandingInstruction, StandingInstructionError};
+mod auto_deleverage;
+use auto_deleverage::{AutoDeleverageConfig, AutoDeleverageError};
+mod lp_collateral;
+
+use errors::{GovernanceError, ProtocolError};
 use storage::GuardianConfig;
-
-// Governance module
-use crate::types::{
+use types::{
     GovernanceConfig, MultisigConfig, Proposal, ProposalOutcome, ProposalType, RecoveryRequest,
     VoteInfo, VoteType,
 };
-// use crate::governance::self;
 
 /// The StellarLend core contract.
 ///
 /// Provides the public API for all lending protocol operations. Each method
-/// delegates to the corresponding module implementation and converts internal
-/// errors into panics for Soroban's contract-call semantics.
+/// delegates to the corresponding module implementation and returns its
+/// error type so callers can match on contract errors.
 #[contract]
-pub struct HelloContract;
-
-#[contractimpl]
-impl HelloContract {
-    /// Initialize the contract with an admin address
-    pub fn initialize(env: Env, admin: Address) {
-        let admin_key = DepositDataKey::Admin;
-        if env.storage().persistent().has(&admin_key) {
-            panic!("Already initialized");
-        }
-        env.storage().persistent().set(&admin_key, &admin);
-
-        // Initialize protocol analytics
-        let analytics_key = DepositDataKey::ProtocolAnalytics;
-        let analytics = ProtocolAnalytics {
-            total_deposits: 0,
-            total_borrows: 0,
-            total_value_locked: 0,
-        };
-        env.storage().persistent().set(&analytics_key, &analytics);
+pub struct StellarLend;
 
-        // Initialize other modules
-        interest_rate::initialize_interest_rate_config(&env, admin.clone()).unwrap();
-        risk_management::initialize_risk_management(&env, admin).unwrap();
-    }
+/// Name of the contract before it was consolidated, kept for integrations
+/// and tests written against it.
+pub use StellarLend as HelloContract;
+pub use StellarLendClient as HelloContractClient;
 
-    /// Deposit assets into the protocol
-    /// Health-check endpoint.
-    ///
-    /// Returns the string `"Hello"` to verify the contract is deployed and callable.
-    pub fn hello(env: Env) -> String {
-        String::from_str(&env, "Hello")
-    }
+#[contractimpl]
+impl StellarLend {
+    // ============================================================================
+    // Initialization and Admin Entrypoints
+    // ============================================================================
 
     /// Initialize the contract with admin address.
     ///
@@ -176,22 +185,24 @@ impl HelloContract {
     /// Returns Ok(()) on success
     pub fn initialize(env: Env, admin: Address) -> Result<(), RiskManagementError> {
         // Prevent double initialization
-        if crate::admin::has_admin(&env) {
-            return Err(RiskManagementError::Unauthorized); // or a specific error
+        if admin::has_admin(&env) {
+            return Err(RiskManagementError::Unauthorized);
         }
 
-        crate::admin::set_admin(&env, admin.clone(), None)
+        admin::set_admin(&env, admin.clone(), None)
             .map_err(|_| RiskManagementError::Unauthorized)?;
-        initialize_risk_management(&env, admin.clone())?;
-        initialize_risk_params(&env).map_err(|_| RiskManagementError::InvalidParameter)?;
+        risk_management::initialize_risk_management(&env, admin.clone())?;
+        risk_params::initialize_risk_params(&env)
+            .map_err(|_| RiskManagementError::InvalidParameter)?;
         // Initialize interest rate config with default parameters
-        initialize_interest_rate_config(&env, admin.clone()).map_err(|e| {
+        interest_rate::initialize_interest_rate_config(&env, admin.clone()).map_err(|e| {
             if e == InterestRateError::AlreadyInitialized {
                 RiskManagementError::AlreadyInitialized
             } else {
                 RiskManagementError::Unauthorized
             }
         })?;
+        upgrade::initialize_version(&env);
         Ok(())
     }
 
@@ -204,8 +215,9 @@ impl HelloContract {
         env: Env,
         caller: Address,
         new_admin: Address,
-    ) -> Result<(), crate::admin::AdminError> {
-        crate::admin::set_admin(&env, new_admin, Some(caller))
+    ) -> Result<(), admin::AdminError> {
+        caller.require_auth();
+        admin::set_admin(&env, new_admin, Some(caller))
     }
 
     /// Grant a role to an address (admin only)
@@ -214,8 +226,8 @@ impl HelloContract {
         caller: Address,
         role: Symbol,
         account: Address,
-    ) -> Result<(), crate::admin::AdminError> {
-        crate::admin::grant_role(&env, caller, role, account)
+    ) -> Result<(), admin::AdminError> {
+        admin::grant_role(&env, caller, role, account)
     }
 
     /// Revoke a role from an address (admin only)
@@ -224,10 +236,23 @@ impl HelloContract {
         caller: Address,
         role: Symbol,
         account: Address,
-    ) -> Result<(), crate::admin::AdminError> {
-        crate::admin::revoke_role(&env, caller, role, account)
+    ) -> Result<(), admin::AdminError> {
+        admin::revoke_role(&env, caller, role, account)
+    }
+
+    /// Set native asset address (admin only). Required before using asset = None for deposit/borrow/repay.
+    pub fn set_native_asset_address(
+        env: Env,
+        caller: Address,
+        native_asset: Address,
+    ) -> Result<(), DepositError> {
+        deposit::set_native_asset_address(&env, caller, native_asset)
     }
 
+    // ============================================================================
+    // Core Lending Entrypoints
+    // ============================================================================
+
     /// Deposit collateral into the protocol
     ///
     /// Allows users to deposit assets as collateral in the protocol.
@@ -252,1258 +277,4394 @@ impl HelloContract {
         user: Address,
         asset: Option<Address>,
         amount: i128,
-    ) -> Result<i128, crate::deposit::DepositError> {
-        deposit::deposit_collateral(&env, user, asset, amount)
-    }
-
-    /// Set native asset address (admin only). Required before using asset = None for deposit/borrow/repay.
-    pub fn set_native_asset_address(
-        env: Env,
-        caller: Address,
-        native_asset: Address,
-    ) -> Result<(), deposit::DepositError> {
-        deposit::set_native_asset_address(&env, caller, native_asset)
+    ) -> Result<i128, ProtocolError> {
+        deposit::deposit_collateral(&env, user, asset, amount).map_err(ProtocolError::from)
     }
 
-    /// Set risk parameters (admin only)
-    ///
-    /// Updates risk parameters with validation and change limits.
+    /// Deposit collateral paid by one account and credited to another
     ///
     /// # Arguments
-    /// * `caller` - The caller address (must be admin)
-    /// * `min_collateral_ratio` - Optional new minimum collateral ratio (in basis points)
-    /// * `liquidation_threshold` - Optional new liquidation threshold (in basis points)
-    /// * `close_factor` - Optional new close factor (in basis points)
-    /// * `liquidation_incentive` - Optional new liquidation incentive (in basis points)
+    /// * `payer` - The account the tokens are pulled from (must authorize)
+    /// * `on_behalf_of` - The user whose position is credited
+    /// * `asset` - The address of the asset contract to deposit (None for native XLM)
+    /// * `amount` - The amount to deposit
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn set_risk_params(
+    /// Returns the updated collateral balance of `on_behalf_of`
+    pub fn deposit_collateral_for(
         env: Env,
-        caller: Address,
-        min_collateral_ratio: Option<i128>,
-        liquidation_threshold: Option<i128>,
-        close_factor: Option<i128>,
-        liquidation_incentive: Option<i128>,
-    ) -> Result<(), RiskManagementError> {
-        require_admin(&env, &caller)?;
-        check_emergency_pause(&env)?;
-        risk_params::set_risk_params(
-            &env,
-            min_collateral_ratio,
-            liquidation_threshold,
-            close_factor,
-            liquidation_incentive,
-        ).map_err(|e| match e {
-            RiskParamsError::ParameterChangeTooLarge => RiskManagementError::ParameterChangeTooLarge,
-            RiskParamsError::InvalidCollateralRatio => RiskManagementError::InvalidCollateralRatio,
-            RiskParamsError::InvalidLiquidationThreshold => RiskManagementError::InvalidLiquidationThreshold,
-            RiskParamsError::InvalidCloseFactor => RiskManagementError::InvalidCloseFactor,
-            RiskParamsError::InvalidLiquidationIncentive => RiskManagementError::InvalidLiquidationIncentive,
-            _ => RiskManagementError::InvalidParameter,
-        })
+        payer: Address,
+        on_behalf_of: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, ProtocolError> {
+        deposit::deposit_collateral_for(&env, payer, on_behalf_of, asset, amount)
+            .map_err(ProtocolError::from)
     }
 
-
-    pub fn set_guardians(
-    env: Env,
-    caller: Address,
-    guardians: soroban_sdk::Vec<Address>,
-    threshold: u32,
-) -> Result<(), governance::GovernanceError> {
-    recovery::set_guardians(&env, caller, guardians, threshold)
-}
-
-pub fn start_recovery(
-    env: Env,
-    initiator: Address,
-    old_admin: Address,
-    new_admin: Address,
-) -> Result<(), governance::GovernanceError> {
-    recovery::start_recovery(&env, initiator, old_admin, new_admin)
-}
-
-pub fn approve_recovery(
-    env: Env,
-    approver: Address,
-) -> Result<(), governance::GovernanceError> {
-    recovery::approve_recovery(&env, approver)
-}
-
-pub fn execute_recovery(
-    env: Env,
-    executor: Address,
-) -> Result<(), governance::GovernanceError> {
-    recovery::execute_recovery(&env, executor)
-}
-
-pub fn ms_set_admins(
-    env: Env,
-    caller: Address,
-    admins: soroban_sdk::Vec<Address>,
-    threshold: u32,
-) -> Result<(), governance::GovernanceError> {
-    multisig::ms_set_admins(&env, caller, admins, threshold)
-}
-
-pub fn ms_propose_set_min_cr(
-    env: Env,
-    proposer: Address,
-    new_ratio: i128,
-) -> Result<u64, governance::GovernanceError> {
-    multisig::ms_propose_set_min_cr(&env, proposer, new_ratio)
-}
-
-pub fn ms_approve(
-    env: Env,
-    approver: Address,
-    proposal_id: u64,
-) -> Result<(), governance::GovernanceError> {
-    multisig::ms_approve(&env, approver, proposal_id)
-}
-
-pub fn ms_execute(
-    env: Env,
-    executor: Address,
-    proposal_id: u64,
-) -> Result<(), governance::GovernanceError> {
-    multisig::ms_execute(&env, executor, proposal_id)
-}
-
-    /// Set pause switch for an operation (admin only)
+    /// Withdraw collateral from the protocol
     ///
     /// # Arguments
-    /// * `caller` - The caller address (must be admin)
-    /// * `operation` - The operation symbol (e.g., "pause_deposit", "pause_borrow")
-    /// * `paused` - Whether to pause (true) or unpause (false)
+    /// * `user` - The address of the user withdrawing collateral
+    /// * `asset` - The address of the asset contract to withdraw (None for native XLM)
+    /// * `amount` - The amount to withdraw
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn set_pause_switch(
+    /// Returns the remaining collateral balance for the user
+    pub fn withdraw_collateral(
         env: Env,
         user: Address,
         asset: Option<Address>,
         amount: i128,
-    ) -> Result<i128, crate::borrow::BorrowError> {
-        borrow::borrow_asset(&env, user, asset, amount)
+    ) -> Result<i128, ProtocolError> {
+        withdraw::withdraw_collateral(&env, user, asset, amount).map_err(ProtocolError::from)
     }
 
-    /// Repay borrowed assets
-    pub fn repay_debt(
+    /// Withdraw collateral to another address
+    ///
+    /// # Arguments
+    /// * `user` - The address of the user withdrawing collateral (must authorize)
+    /// * `asset` - The address of the asset contract to withdraw (None for native XLM)
+    /// * `amount` - The amount to withdraw
+    /// * `recipient` - The address receiving the tokens
+    ///
+    /// # Returns
+    /// Returns the remaining collateral balance for the user
+    pub fn withdraw_to(
         env: Env,
         user: Address,
         asset: Option<Address>,
         amount: i128,
-    ) -> Result<(i128, i128, i128), crate::repay::RepayError> {
-        repay::repay_debt(&env, user, asset, amount)
-    }
-
-    /// Liquidate an undercollateralized position
-    pub fn liquidate(
-        env: Env,
-        caller: Address,
-        paused: bool,
-    ) -> Result<(), RiskManagementError> {
-        risk_management::set_emergency_pause(&env, caller, paused)
+        recipient: Address,
+    ) -> Result<i128, ProtocolError> {
+        withdraw::withdraw_to(&env, user, asset, amount, recipient).map_err(ProtocolError::from)
     }
 
-    /// Get current risk configuration
+    /// Get the largest amount a user can currently withdraw
     ///
-    /// # Returns
-    /// Returns the current risk configuration or None if not initialized
-    pub fn get_risk_config(env: Env) -> Option<RiskConfig> {
-        risk_management::get_risk_config(&env)
-    }
-
-    /// Get minimum collateral ratio
+    /// Keeps the collateral ratio at or above the minimum collateral ratio,
+    /// with the debt including interest accrued up to now.
     ///
-    /// # Returns
-    /// Returns the minimum collateral ratio in basis points
-    pub fn get_min_collateral_ratio(env: Env) -> Result<i128, RiskManagementError> {
-        risk_params::get_min_collateral_ratio(&env).map_err(|_| RiskManagementError::InvalidParameter)
+    /// # Arguments
+    /// * `user` - The address of the user
+    /// * `asset` - The asset to withdraw (None for native XLM)
+    pub fn get_max_withdrawable(env: Env, user: Address, asset: Option<Address>) -> i128 {
+        withdraw::get_max_withdrawable(&env, &user, asset)
     }
 
-    /// Get liquidation threshold
+    /// Borrow assets against deposited collateral
+    ///
+    /// # Arguments
+    /// * `user` - The address of the borrower
+    /// * `asset` - The address of the asset contract to borrow (None for native XLM)
+    /// * `amount` - The amount to borrow
     ///
     /// # Returns
-    /// Returns the liquidation threshold in basis points
-    pub fn get_liquidation_threshold(env: Env) -> Result<i128, RiskManagementError> {
-        risk_params::get_liquidation_threshold(&env).map_err(|_| RiskManagementError::InvalidParameter)
+    /// Returns the user's total debt after the borrow
+    pub fn borrow_asset(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, ProtocolError> {
+        borrow::borrow_asset(&env, user, asset, amount).map_err(ProtocolError::from)
     }
 
-    /// Get close factor
+    /// Get the largest amount a user can currently borrow
     ///
-    /// # Returns
-    /// Returns the close factor in basis points
-    pub fn get_close_factor(env: Env) -> Result<i128, RiskManagementError> {
-        risk_params::get_close_factor(&env).map_err(|_| RiskManagementError::InvalidParameter)
+    /// Accounts for collateral, the collateral factor, the debt including
+    /// accrued interest, the minimum collateral ratio, the asset's borrow cap
+    /// and reserve liquidity.
+    ///
+    /// # Arguments
+    /// * `user` - The address of the user
+    /// * `asset` - The asset to borrow (None for native XLM)
+    pub fn get_max_borrowable(env: Env, user: Address, asset: Option<Address>) -> i128 {
+        borrow::get_max_borrowable(&env, &user, asset)
     }
 
-    /// Get liquidation incentive
+    /// Borrow one asset and receive another, swapped through the AMM router
+    ///
+    /// The debt is booked in `debt_asset`; the call reverts if the swap
+    /// returns less than `min_out`.
+    ///
+    /// # Arguments
+    /// * `user` - The address of the borrower
+    /// * `debt_asset` - The asset borrowed and owed
+    /// * `amount` - The amount to borrow
+    /// * `receive_asset` - The asset delivered to the user
+    /// * `min_out` - Minimum amount of `receive_asset` to receive
     ///
     /// # Returns
-    /// Returns the liquidation incentive in basis points
-    pub fn get_liquidation_incentive(env: Env) -> Result<i128, RiskManagementError> {
-        risk_params::get_liquidation_incentive(&env).map_err(|_| RiskManagementError::InvalidParameter)
-    }
-
-    /// Get current borrow rate (in basis points)
-    pub fn get_borrow_rate(env: Env) -> i128 {
-        interest_rate::calculate_borrow_rate(&env).unwrap_or(0)
-    }
-
-    /// Get current supply rate (in basis points)
-    pub fn get_supply_rate(env: Env) -> i128 {
-        interest_rate::calculate_supply_rate(&env).unwrap_or(0)
-    }
-
-    /// Update interest rate model configuration (admin only)
-    #[allow(clippy::too_many_arguments)]
-    pub fn update_interest_rate_config(
+    /// Returns a tuple (total_debt, amount_received)
+    pub fn borrow_and_swap(
         env: Env,
-        admin: Address,
-        base_rate: Option<i128>,
-        kink: Option<i128>,
-        multiplier: Option<i128>,
-        jump_multiplier: Option<i128>,
-        rate_floor: Option<i128>,
-        rate_ceiling: Option<i128>,
-        spread: Option<i128>,
-    ) -> Result<(), RiskManagementError> {
-        require_min_collateral_ratio(&env, collateral_value, debt_value).map_err(|_| RiskManagementError::InsufficientCollateralRatio)
+        user: Address,
+        debt_asset: Address,
+        amount: i128,
+        receive_asset: Address,
+        min_out: i128,
+    ) -> Result<(i128, i128), ProtocolError> {
+        borrow::borrow_and_swap(&env, user, debt_asset, amount, receive_asset, min_out)
+            .map_err(ProtocolError::from)
     }
 
-    /// Check if position can be liquidated
+    /// Repay borrowed assets
     ///
     /// # Arguments
-    /// * `collateral_value` - Total collateral value (in base units)
-    /// * `debt_value` - Total debt value (in base units)
+    /// * `user` - The address of the user repaying debt
+    /// * `asset` - The address of the asset contract to repay (None for native XLM)
+    /// * `amount` - The amount to repay (capped at the outstanding debt)
     ///
     /// # Returns
-    /// Returns true if position can be liquidated
-    pub fn can_be_liquidated(
-        env: Env,
-        collateral_value: i128,
-        debt_value: i128,
-    ) -> Result<bool, RiskManagementError> {
-        can_be_liquidated(&env, collateral_value, debt_value).map_err(|_| RiskManagementError::InvalidParameter)
-    }
-
-    /// Manual emergency interest rate adjustment (admin only)
-    pub fn set_emergency_rate_adjustment(
+    /// Returns a tuple (remaining_debt, interest_paid, principal_paid)
+    pub fn repay_debt(
         env: Env,
-        debt_value: i128,
-    ) -> Result<i128, RiskManagementError> {
-        get_max_liquidatable_amount(&env, debt_value).map_err(|_| RiskManagementError::Overflow)
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<(i128, i128, i128), ProtocolError> {
+        repay::repay_debt(&env, user, asset, amount).map_err(ProtocolError::from)
     }
 
-    /// Calculate liquidation incentive amount
+    /// Repay debt by selling the user's own collateral through the AMM router
+    ///
+    /// The proceeds of the swap repay the debt; any excess is paid out to the
+    /// user. Reverts if the swap returns less than the oracle value of the
+    /// collateral minus the slippage tolerance.
     ///
     /// # Arguments
-    /// * `liquidated_amount` - Amount being liquidated (in base units)
+    /// * `user` - The borrower
+    /// * `debt_asset` - The asset the debt is repaid in
+    /// * `collateral_asset` - The collateral asset sold
+    /// * `amount` - The amount of collateral to sell
     ///
     /// # Returns
-    /// Liquidation incentive amount
-    pub fn get_liquidation_incentive_amount(
+    /// Returns a tuple (remaining_debt, collateral_sold, debt_repaid)
+    pub fn repay_with_collateral(
         env: Env,
-        liquidated_amount: i128,
-    ) -> Result<i128, RiskManagementError> {
-        get_liquidation_incentive_amount(&env, liquidated_amount).map_err(|_| RiskManagementError::Overflow)
-    }
-
-    /// Refresh analytics for a user
-    pub fn refresh_user_analytics(_env: Env, _user: Address) -> Result<(), RiskManagementError> {
-        Ok(())
-    }
-
-    /// Claim accumulated protocol reserves (admin only)
-    pub fn claim_reserves(env: Env, caller: Address, asset: Option<Address>, to: Address, amount: i128) -> Result<(), RiskManagementError> {
-        require_admin(&env, &caller)?;
-        
-        let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
-        let mut reserve_balance = env.storage().persistent()
-            .get::<DepositDataKey, i128>(&reserve_key)
-            .unwrap_or(0);
-            
-        if amount > reserve_balance {
-            return Err(RiskManagementError::InvalidParameter);
-        }
-        
-        if let Some(_asset_addr) = asset {
-            #[cfg(not(test))]
-            {
-                let token_client = soroban_sdk::token::Client::new(&env, &_asset_addr);
-                token_client.transfer(&env.current_contract_address(), &to, &amount);
-            }
-        }
-        
-        reserve_balance -= amount;
-        env.storage().persistent().set(&reserve_key, &reserve_balance);
-        Ok(())
-    }
-
-    /// Get current protocol reserve balance for an asset
-    pub fn get_reserve_balance(env: Env, asset: Option<Address>) -> i128 {
-        let reserve_key = DepositDataKey::ProtocolReserve(asset);
-        env.storage().persistent()
-            .get::<DepositDataKey, i128>(&reserve_key)
-            .unwrap_or(0)
+        user: Address,
+        debt_asset: Address,
+        collateral_asset: Address,
+        amount: i128,
+    ) -> Result<(i128, i128, i128), ProtocolError> {
+        repay::repay_with_collateral(&env, user, debt_asset, collateral_asset, amount)
+            .map_err(ProtocolError::from)
     }
 
-    /// Generate a comprehensive protocol report.
+    /// Liquidate an undercollateralized position
     ///
-    /// Aggregates TVL, utilization, average borrow rate, and user/transaction counts
-    /// into a single [`ProtocolReport`] snapshot.
+    /// # Arguments
+    /// * `liquidator` - The address performing the liquidation
+    /// * `borrower` - The address of the position being liquidated
+    /// * `debt_asset` - The debt asset repaid by the liquidator (None for native XLM)
+    /// * `collateral_asset` - The collateral asset seized (None for native XLM)
+    /// * `debt_amount` - The amount of debt to repay
     ///
     /// # Returns
-    /// A `ProtocolReport` containing current protocol metrics and timestamp.
-    ///
-    /// # Errors
-    /// Returns `AnalyticsError` if protocol data is not initialized or computation overflows.
-    pub fn get_protocol_report(env: Env) -> Result<ProtocolReport, AnalyticsError> {
-        generate_protocol_report(&env)
+    /// Returns a tuple (debt_liquidated, collateral_seized, incentive)
+    pub fn liquidate(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        debt_asset: Option<Address>,
+        collateral_asset: Option<Address>,
+        debt_amount: i128,
+    ) -> Result<(i128, i128, i128), ProtocolError> {
+        liquidate::liquidate(
+            &env,
+            liquidator,
+            borrower,
+            debt_asset,
+            collateral_asset,
+            debt_amount,
+        )
+        .map_err(ProtocolError::from)
     }
 
-    /// Generate a comprehensive report for a specific user.
+    /// Liquidate a position without supplying the debt asset up front
     ///
-    /// Includes the user's position, health factor, risk level, activity history,
-    /// and cumulative transaction metrics.
+    /// The seized collateral is swapped into the debt asset through the AMM
+    /// router, the proceeds repay the debt and the surplus is paid to the
+    /// liquidator. The call reverts if the surplus would be below `min_surplus`.
     ///
     /// # Arguments
-    /// * `user` - The address of the user to report on
+    /// * `liquidator` - The address performing the liquidation
+    /// * `borrower` - The address of the position being liquidated
+    /// * `debt_asset` - The debt asset repaid from the swap proceeds
+    /// * `collateral_asset` - The collateral asset seized and sold
+    /// * `debt_amount` - The amount of debt to repay
+    /// * `min_surplus` - Minimum amount of `debt_asset` paid to the liquidator
     ///
     /// # Returns
-    /// A `UserReport` with the user's metrics, position, and recent activities.
-    ///
-    /// # Errors
-    /// Returns `AnalyticsError::DataNotFound` if the user has no recorded activity.
-    pub fn get_user_report(env: Env, user: Address) -> Result<UserReport, AnalyticsError> {
-        generate_user_report(&env, &user)
+    /// Returns a tuple (debt_liquidated, collateral_seized, surplus)
+    pub fn liquidate_with_swap(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        debt_asset: Address,
+        collateral_asset: Address,
+        debt_amount: i128,
+        min_surplus: i128,
+    ) -> Result<(i128, i128, i128), ProtocolError> {
+        liquidate::liquidate_with_swap(
+            &env,
+            liquidator,
+            borrower,
+            debt_asset,
+            collateral_asset,
+            debt_amount,
+            min_surplus,
+        )
+        .map_err(ProtocolError::from)
     }
 
-    /// Retrieve recent protocol activity entries.
+    /// Open a position: deposit collateral, then borrow against it
     ///
-    /// Returns a paginated list of the most recent protocol activities in
-    /// reverse chronological order.
+    /// Runs under a single user authorization; the collateral ratio is
+    /// checked once, by the borrow, against the post-deposit collateral. If
+    /// the borrow fails the deposit is reverted too.
     ///
     /// # Arguments
-    /// * `limit` - Maximum number of entries to return
-    /// * `offset` - Number of entries to skip from the most recent
+    /// * `user` - The address of the user opening the position
+    /// * `collateral_asset` - The collateral asset (None for native XLM)
+    /// * `collateral_amount` - The amount of collateral to deposit
+    /// * `debt_asset` - The asset to borrow (None for native XLM)
+    /// * `borrow_amount` - The amount to borrow
     ///
     /// # Returns
-    /// A vector of `ActivityEntry` records.
-    pub fn get_recent_activity(
+    /// Returns a tuple (collateral_balance, total_debt)
+    pub fn open_position(
         env: Env,
-        limit: u32,
-        offset: u32,
-    ) -> Result<soroban_sdk::Vec<analytics::ActivityEntry>, AnalyticsError> {
-        get_recent_activity(&env, limit, offset)
+        user: Address,
+        collateral_asset: Option<Address>,
+        collateral_amount: i128,
+        debt_asset: Option<Address>,
+        borrow_amount: i128,
+    ) -> Result<(i128, i128), ProtocolError> {
+        user.require_auth();
+        let collateral =
+            deposit::deposit_collateral(&env, user.clone(), collateral_asset, collateral_amount)?;
+        let total_debt = borrow::borrow_asset(&env, user, debt_asset, borrow_amount)?;
+        Ok((collateral, total_debt))
     }
 
-    /// Retrieve activity entries for a specific user.
-    ///
-    /// Returns a paginated list of the user's activities in reverse
-    /// chronological order.
+    /// Withdraw the user's entire collateral balance
     ///
     /// # Arguments
-    /// * `user` - The address of the user
-    /// * `limit` - Maximum number of entries to return
-    /// * `offset` - Number of entries to skip from the most recent
+    /// * `user` - The address of the user withdrawing collateral
+    /// * `asset` - The asset contract to withdraw (None for native XLM)
     ///
     /// # Returns
-    /// A vector of `ActivityEntry` records for the specified user.
-    pub fn get_user_activity(
+    /// Returns the amount withdrawn
+    pub fn withdraw_all(
         env: Env,
         user: Address,
-        limit: u32,
+        asset: Option<Address>,
+    ) -> Result<i128, ProtocolError> {
+        withdraw::withdraw_all(&env, user, asset).map_err(ProtocolError::from)
+    }
+
+    /// Close the user's position: repay all debt, then withdraw all collateral
+    ///
+    /// The amounts are computed on-chain, accrued interest included, so no
+    /// dust is left behind. The user must have approved the contract for the
+    /// outstanding debt.
+    ///
+    /// # Arguments
+    /// * `user` - The address of the user closing the position
+    /// * `debt_asset` - The asset the debt is repaid in (None for native XLM)
+    /// * `collateral_asset` - The collateral asset withdrawn (None for native XLM)
+    ///
+    /// # Returns
+    /// Returns a tuple (debt_repaid, collateral_withdrawn)
+    pub fn close_position(
+        env: Env,
+        user: Address,
+        debt_asset: Option<Address>,
+        collateral_asset: Option<Address>,
+    ) -> Result<(i128, i128), ProtocolError> {
+        let debt_repaid = match repay::repay_all(&env, user.clone(), debt_asset) {
+            Ok((_, interest_paid, principal_paid)) => interest_paid + principal_paid,
+            Err(repay::RepayError::NoDebt) => 0,
+            Err(e) => return Err(e.into()),
+        };
+        let collateral_withdrawn = match withdraw::withdraw_all(&env, user, collateral_asset) {
+            Ok(amount) => amount,
+            Err(withdraw::WithdrawError::InsufficientCollateral) => 0,
+            Err(e) => return Err(e.into()),
+        };
+        Ok((debt_repaid, collateral_withdrawn))
+    }
+
+    // ============================================================================
+    // Flash Loan Entrypoints
+    // ============================================================================
+
+    /// Execute a flash loan
+    ///
+    /// # Arguments
+    /// * `user` - The address receiving the loan
+    /// * `asset` - The asset to borrow
+    /// * `amount` - The amount to borrow
+    /// * `callback` - The receiver contract invoked with the funds
+    ///
+    /// # Returns
+    /// Returns the total amount (principal + fee) to repay
+    pub fn execute_flash_loan(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+        callback: Address,
+    ) -> Result<i128, ProtocolError> {
+        flash_loan::execute_flash_loan(&env, user, asset, amount, callback)
+            .map_err(ProtocolError::from)
+    }
+
+    /// Execute an atomic flash loan
+    ///
+    /// Transfers the funds to `receiver`, invokes its `on_flash_loan`
+    /// callback and reverts unless principal plus fee is returned before the
+    /// callback ends. The fee accrues to the asset's protocol reserve.
+    ///
+    /// # Arguments
+    /// * `receiver` - The receiver contract (must authorize)
+    /// * `asset` - The asset to borrow
+    /// * `amount` - The amount to borrow
+    /// * `data` - Opaque data forwarded to the callback
+    ///
+    /// # Returns
+    /// Returns the fee paid
+    pub fn flash_loan(
+        env: Env,
+        receiver: Address,
+        asset: Address,
+        amount: i128,
+        data: Bytes,
+    ) -> Result<i128, ProtocolError> {
+        flash_loan::flash_loan(&env, receiver, asset, amount, data).map_err(ProtocolError::from)
+    }
+
+    /// Repay an active flash loan
+    pub fn repay_flash_loan(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), ProtocolError> {
+        flash_loan::repay_flash_loan(&env, user, asset, amount).map_err(ProtocolError::from)
+    }
+
+    /// Set the flash loan fee in basis points (admin only)
+    pub fn set_flash_loan_fee(
+        env: Env,
+        caller: Address,
+        fee_bps: i128,
+    ) -> Result<(), ProtocolError> {
+        flash_loan::set_flash_loan_fee(&env, caller, fee_bps).map_err(ProtocolError::from)
+    }
+
+    /// Configure flash loan limits (admin only)
+    pub fn configure_flash_loan(
+        env: Env,
+        caller: Address,
+        config: FlashLoanConfig,
+    ) -> Result<(), ProtocolError> {
+        flash_loan::configure_flash_loan(&env, caller, config).map_err(ProtocolError::from)
+    }
+
+    // ============================================================================
+    // Risk Management Entrypoints
+    // ============================================================================
+
+    /// Set risk parameters (admin or risk manager)
+    ///
+    /// Updates risk parameters with validation and change limits. While the
+    /// parameter timelock is enabled, changes must be queued instead.
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin or risk manager)
+    /// * `min_collateral_ratio` - Optional new minimum collateral ratio (in basis points)
+    /// * `liquidation_threshold` - Optional new liquidation threshold (in basis points)
+    /// * `close_factor` - Optional new close factor (in basis points)
+    /// * `liquidation_incentive` - Optional new liquidation incentive (in basis points)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_risk_params(
+        env: Env,
+        caller: Address,
+        min_collateral_ratio: Option<i128>,
+        liquidation_threshold: Option<i128>,
+        close_factor: Option<i128>,
+        liquidation_incentive: Option<i128>,
+    ) -> Result<(), RiskManagementError> {
+        caller.require_auth();
+        risk_management::require_risk_manager(&env, &caller)?;
+        risk_management::check_emergency_pause(&env)?;
+        if param_timelock::is_param_timelock_active(&env) {
+            return Err(RiskManagementError::TimelockActive);
+        }
+        risk_params::set_risk_params(
+            &env,
+            min_collateral_ratio,
+            liquidation_threshold,
+            close_factor,
+            liquidation_incentive,
+        )
+        .map_err(|e| match e {
+            RiskParamsError::ParameterChangeTooLarge => {
+                RiskManagementError::ParameterChangeTooLarge
+            }
+            RiskParamsError::InvalidCollateralRatio => RiskManagementError::InvalidCollateralRatio,
+            RiskParamsError::InvalidLiquidationThreshold => {
+                RiskManagementError::InvalidLiquidationThreshold
+            }
+            RiskParamsError::InvalidCloseFactor => RiskManagementError::InvalidCloseFactor,
+            RiskParamsError::InvalidLiquidationIncentive => {
+                RiskManagementError::InvalidLiquidationIncentive
+            }
+            _ => RiskManagementError::InvalidParameter,
+        })
+    }
+
+    /// Set pause switch for an operation (admin, or guardian to pause)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin, or guardian when pausing)
+    /// * `operation` - The operation symbol: "pause_deposit", "pause_withdraw",
+    ///   "pause_borrow", "pause_repay", "pause_liquidate" or "pause_swap"
+    /// * `paused` - Whether to pause (true) or unpause (false)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_pause_switch(
+        env: Env,
+        caller: Address,
+        operation: Symbol,
+        paused: bool,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_pause_switch(&env, caller, operation, paused)
+    }
+
+    /// Set multiple pause switches at once (admin, or guardian to pause)
+    pub fn set_pause_switches(
+        env: Env,
+        caller: Address,
+        switches: Map<Symbol, bool>,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_pause_switches(&env, caller, switches)
+    }
+
+    /// Check if an operation is paused
+    pub fn is_operation_paused(env: Env, operation: Symbol) -> bool {
+        risk_management::is_operation_paused(&env, operation)
+    }
+
+    /// Set emergency pause (admin, or guardian to pause)
+    pub fn set_emergency_pause(
+        env: Env,
+        caller: Address,
+        paused: bool,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_emergency_pause(&env, caller, paused)
+    }
+
+    /// Check if emergency pause is active
+    pub fn is_emergency_paused(env: Env) -> bool {
+        risk_management::is_emergency_paused(&env)
+    }
+
+    /// Freeze or unfreeze an asset (admin, or guardian to freeze)
+    ///
+    /// A frozen asset takes no new deposits, borrows or AMM swaps, while
+    /// withdrawals, repayments and liquidations stay open.
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin, or guardian when freezing)
+    /// * `asset` - The asset (None for native XLM)
+    /// * `frozen` - Whether to freeze (true) or unfreeze (false)
+    pub fn set_asset_frozen(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        frozen: bool,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_asset_frozen(&env, caller, asset, frozen)
+    }
+
+    /// Check if an asset is frozen
+    pub fn is_asset_frozen(env: Env, asset: Option<Address>) -> bool {
+        risk_management::is_asset_frozen(&env, &asset)
+    }
+
+    /// Enter emergency withdrawal mode (admin only)
+    ///
+    /// A terminal state: the emergency pause is turned on and borrows are
+    /// paused, nothing can be unpaused afterwards, and suppliers without debt
+    /// can withdraw even while withdrawals are paused.
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    pub fn enter_emergency_withdrawal_mode(
+        env: Env,
+        caller: Address,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::enter_emergency_withdrawal_mode(&env, caller)
+    }
+
+    /// Check if the protocol is in emergency withdrawal mode
+    pub fn is_emergency_withdrawal_mode(env: Env) -> bool {
+        risk_management::is_emergency_withdrawal_mode(&env)
+    }
+
+    /// Get current risk parameters
+    ///
+    /// # Returns
+    /// Returns the current risk parameters or None if not initialized
+    pub fn get_risk_config(env: Env) -> Option<RiskParams> {
+        risk_params::get_risk_params(&env)
+    }
+
+    /// Get minimum collateral ratio
+    ///
+    /// # Returns
+    /// Returns the minimum collateral ratio in basis points
+    pub fn get_min_collateral_ratio(env: Env) -> Result<i128, RiskManagementError> {
+        risk_params::get_min_collateral_ratio(&env)
+            .map_err(|_| RiskManagementError::InvalidParameter)
+    }
+
+    /// Get liquidation threshold
+    ///
+    /// # Returns
+    /// Returns the liquidation threshold in basis points
+    pub fn get_liquidation_threshold(env: Env) -> Result<i128, RiskManagementError> {
+        risk_params::get_liquidation_threshold(&env)
+            .map_err(|_| RiskManagementError::InvalidParameter)
+    }
+
+    /// Get close factor
+    ///
+    /// # Returns
+    /// Returns the close factor in basis points
+    pub fn get_close_factor(env: Env) -> Result<i128, RiskManagementError> {
+        risk_params::get_close_factor(&env).map_err(|_| RiskManagementError::InvalidParameter)
+    }
+
+    /// Get liquidation incentive
+    ///
+    /// # Returns
+    /// Returns the liquidation incentive in basis points
+    pub fn get_liquidation_incentive(env: Env) -> Result<i128, RiskManagementError> {
+        risk_params::get_liquidation_incentive(&env)
+            .map_err(|_| RiskManagementError::InvalidParameter)
+    }
+
+    /// Check that a position meets the minimum collateral ratio
+    ///
+    /// # Arguments
+    /// * `collateral_value` - Total collateral value (in base units)
+    /// * `debt_value` - Total debt value (in base units)
+    pub fn require_min_collateral_ratio(
+        env: Env,
+        collateral_value: i128,
+        debt_value: i128,
+    ) -> Result<(), RiskManagementError> {
+        risk_params::require_min_collateral_ratio(&env, collateral_value, debt_value)
+            .map_err(|_| RiskManagementError::InsufficientCollateralRatio)
+    }
+
+    /// Check if position can be liquidated
+    ///
+    /// # Arguments
+    /// * `collateral_value` - Total collateral value (in base units)
+    /// * `debt_value` - Total debt value (in base units)
+    ///
+    /// # Returns
+    /// Returns true if position can be liquidated
+    pub fn can_be_liquidated(
+        env: Env,
+        collateral_value: i128,
+        debt_value: i128,
+    ) -> Result<bool, RiskManagementError> {
+        risk_params::can_be_liquidated(&env, collateral_value, debt_value)
+            .map_err(|_| RiskManagementError::InvalidParameter)
+    }
+
+    /// Get the maximum debt that can be liquidated in one call (close factor)
+    ///
+    /// # Arguments
+    /// * `debt_value` - Total debt value (in base units)
+    pub fn get_max_liquidatable_amount(
+        env: Env,
+        debt_value: i128,
+    ) -> Result<i128, RiskManagementError> {
+        risk_params::get_max_liquidatable_amount(&env, debt_value)
+            .map_err(|_| RiskManagementError::Overflow)
+    }
+
+    /// Calculate liquidation incentive amount
+    ///
+    /// # Arguments
+    /// * `liquidated_amount` - Amount being liquidated (in base units)
+    ///
+    /// # Returns
+    /// Liquidation incentive amount
+    pub fn get_liquidation_incentive_amount(
+        env: Env,
+        liquidated_amount: i128,
+    ) -> Result<i128, RiskManagementError> {
+        risk_params::get_liquidation_incentive_amount(&env, liquidated_amount)
+            .map_err(|_| RiskManagementError::Overflow)
+    }
+
+    // ============================================================================
+    // Interest Rate Entrypoints
+    // ============================================================================
+
+    /// Get the current annual borrow rate of an asset
+    ///
+    /// All reserves share the pool's utilization curve, so this is the pool
+    /// rate whichever asset is passed.
+    ///
+    /// # Arguments
+    /// * `asset` - The reserve (None for native XLM); does not change the result
+    ///
+    /// # Returns
+    /// The rate with 7 decimals (10_000_000 = 100%)
+    pub fn get_borrow_rate(env: Env, asset: Option<Address>) -> i128 {
+        interest_rate::get_borrow_rate_view(&env, &asset)
+    }
+
+    /// Get the current annual supply rate of an asset (7 decimals, 10_000_000 = 100%)
+    ///
+    /// The rate deposits earn: the pool supply rate scaled by utilization.
+    /// `asset` does not change the result; all reserves share the pool curve.
+    pub fn get_supply_rate(env: Env, asset: Option<Address>) -> i128 {
+        interest_rate::get_supply_rate_view(&env, &asset)
+    }
+
+    /// Get the current utilization of an asset (7 decimals, 10_000_000 = 100%)
+    ///
+    /// Pool-wide utilization; `asset` does not change the result.
+    pub fn get_utilization(env: Env, asset: Option<Address>) -> i128 {
+        interest_rate::get_utilization_view(&env, &asset)
+    }
+
+    /// Update interest rate model configuration (admin or risk manager)
+    ///
+    /// Must be queued instead while the parameter timelock is enabled.
+    pub fn update_interest_rate_config(
+        env: Env,
+        caller: Address,
+        base_rate: Option<i128>,
+        kink: Option<i128>,
+        multiplier: Option<i128>,
+        jump_multiplier: Option<i128>,
+        rate_floor: Option<i128>,
+        rate_ceiling: Option<i128>,
+        spread: Option<i128>,
+    ) -> Result<(), InterestRateError> {
+        interest_rate::update_interest_rate_config(
+            &env,
+            caller,
+            base_rate,
+            kink,
+            multiplier,
+            jump_multiplier,
+            rate_floor,
+            rate_ceiling,
+            spread,
+        )
+    }
+
+    /// Manual emergency interest rate adjustment (admin only)
+    pub fn set_emergency_rate_adjustment(
+        env: Env,
+        caller: Address,
+        adjustment_bps: i128,
+    ) -> Result<(), InterestRateError> {
+        interest_rate::set_emergency_rate_adjustment(&env, caller, adjustment_bps)
+    }
+
+    // ============================================================================
+    // Reserve Entrypoints
+    // ============================================================================
+
+    /// Claim accumulated protocol reserves (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `asset` - The reserve asset (None for native XLM)
+    /// * `to` - The recipient of the claimed reserves
+    /// * `amount` - The amount to claim
+    pub fn claim_reserves(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), RiskManagementError> {
+        caller.require_auth();
+        risk_management::require_admin(&env, &caller)?;
+
+        let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
+        let reserve_balance = env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, i128>(&reserve_key)
+            .unwrap_or(0);
+        if amount <= 0 || amount > reserve_balance {
+            return Err(RiskManagementError::InvalidParameter);
+        }
+
+        let token_addr = deposit::resolve_token_address(&env, &asset)
+            .ok_or(RiskManagementError::InvalidParameter)?;
+        let token_client = soroban_sdk::token::Client::new(&env, &token_addr);
+        token_client.transfer(&env.current_contract_address(), &to, &amount);
+
+        env.storage()
+            .persistent()
+            .set(&reserve_key, &(reserve_balance - amount));
+        treasury::record_disbursement(&env, &asset, amount);
+        Ok(())
+    }
+
+    /// Get current protocol reserve balance for an asset
+    pub fn get_reserve_balance(env: Env, asset: Option<Address>) -> i128 {
+        reserve::get_reserve_balance(&env, asset)
+    }
+
+    /// Set the share of repaid borrower interest kept as reserves (admin or
+    /// risk manager)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin or risk manager)
+    /// * `asset` - The reserve asset (None for native XLM)
+    /// * `reserve_factor_bps` - Reserve factor in basis points (0-5000)
+    pub fn set_reserve_factor(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        reserve_factor_bps: i128,
+    ) -> Result<(), ReserveError> {
+        reserve::set_reserve_factor(&env, caller, asset, reserve_factor_bps)
+    }
+
+    /// Set the treasury address that receives reserve withdrawals (admin only)
+    pub fn set_treasury_address(
+        env: Env,
+        caller: Address,
+        treasury: Address,
+    ) -> Result<(), ReserveError> {
+        reserve::set_treasury_address(&env, caller, treasury)
+    }
+
+    /// Get the reserve parameters of an asset and the treasury address
+    pub fn get_reserve_config(env: Env, asset: Option<Address>) -> ReserveConfig {
+        reserve::get_reserve_config(&env, asset)
+    }
+
+    /// Set the maximum total deposits of an asset (admin or risk manager, 0 =
+    /// unlimited)
+    pub fn set_supply_cap(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        supply_cap: i128,
+    ) -> Result<(), ReserveError> {
+        reserve::set_supply_cap(&env, caller, asset, supply_cap)
+    }
+
+    /// Get how much more of an asset can be deposited before its supply cap
+    ///
+    /// Returns `i128::MAX` if the asset has no cap.
+    pub fn get_remaining_supply_capacity(env: Env, asset: Option<Address>) -> i128 {
+        reserve::get_remaining_supply_capacity(&env, &asset)
+    }
+
+    /// Set the maximum total principal borrowed of an asset (admin or risk
+    /// manager, 0 = unlimited)
+    pub fn set_borrow_cap(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        borrow_cap: i128,
+    ) -> Result<(), ReserveError> {
+        reserve::set_borrow_cap(&env, caller, asset, borrow_cap)
+    }
+
+    /// Get how much more of an asset can be borrowed before its borrow cap
+    ///
+    /// Returns `i128::MAX` if the asset has no cap.
+    pub fn get_remaining_borrow_capacity(env: Env, asset: Option<Address>) -> i128 {
+        reserve::get_remaining_borrow_capacity(&env, &asset)
+    }
+
+    /// Mark an asset as only borrowable in isolation (admin or risk manager)
+    pub fn set_siloed(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        siloed: bool,
+    ) -> Result<(), ReserveError> {
+        reserve::set_siloed(&env, caller, asset, siloed)
+    }
+
+    /// Set the share of a collateral asset's value counted against debt when
+    /// deciding liquidations (admin or risk manager, basis points, 0 = global
+    /// ratio)
+    ///
+    /// Must not be below the asset's collateral factor, which keeps a buffer
+    /// between the maximum LTV and the liquidation point.
+    pub fn set_liquidation_threshold(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        liquidation_threshold_bps: i128,
+    ) -> Result<(), ReserveError> {
+        reserve::set_liquidation_threshold(&env, caller, asset, liquidation_threshold_bps)
+    }
+
+    /// Set the close factor of a debt asset (admin or risk manager, basis
+    /// points, 0 = global close factor) and the health factor below which a liquidation
+    /// can repay all of the debt (basis points, 0 = never)
+    pub fn set_reserve_close_factor(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        close_factor_bps: i128,
+        deep_distress_hf_bps: i128,
+    ) -> Result<(), ReserveError> {
+        reserve::set_close_factor(&env, caller, asset, close_factor_bps, deep_distress_hf_bps)
+    }
+
+    /// Set the share of the liquidation bonus on a collateral asset credited
+    /// to protocol reserves (admin or risk manager, basis points)
+    pub fn set_liquidation_protocol_fee(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        fee_bps: i128,
+    ) -> Result<(), ReserveError> {
+        reserve::set_liquidation_protocol_fee(&env, caller, asset, fee_bps)
+    }
+
+    /// Set the extra haircut on an asset's side of LP shares pledged as
+    /// collateral (admin or risk manager, basis points)
+    pub fn set_lp_haircut(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        haircut_bps: i128,
+    ) -> Result<(), ReserveError> {
+        reserve::set_lp_haircut(&env, caller, asset, haircut_bps)
+    }
+
+    /// Get the assets a user has borrowed since their debt was last zero
+    pub fn get_user_debt_assets(env: Env, user: Address) -> Vec<Option<Address>> {
+        reserve::get_user_debt_assets(&env, &user)
+    }
+
+    /// Get the protocol-owned reserves of an asset
+    ///
+    /// Includes the reserve share of repaid interest, borrow fees and flash
+    /// loan fees, less withdrawals.
+    pub fn get_protocol_reserves(env: Env, asset: Option<Address>) -> i128 {
+        reserve::get_reserve_balance(&env, asset)
+    }
+
+    /// Withdraw protocol reserves to the treasury address (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `asset` - The reserve asset (None for native XLM)
+    /// * `amount` - The amount to withdraw
+    ///
+    /// # Returns
+    /// The amount withdrawn
+    pub fn withdraw_reserves(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, ReserveError> {
+        reserve::withdraw_reserve_to_treasury(&env, caller, asset, amount)
+    }
+
+    // ============================================================================
+    // Analytics Entrypoints
+    // ============================================================================
+
+    /// Generate a comprehensive protocol report.
+    ///
+    /// Aggregates TVL, utilization, average borrow rate, and user/transaction counts
+    /// into a single [`ProtocolReport`] snapshot.
+    ///
+    /// # Returns
+    /// A `ProtocolReport` containing current protocol metrics and timestamp.
+    ///
+    /// # Errors
+    /// Returns `AnalyticsError` if protocol data is not initialized or computation overflows.
+    pub fn get_protocol_report(env: Env) -> Result<ProtocolReport, AnalyticsError> {
+        analytics::generate_protocol_report(&env)
+    }
+
+    /// Generate a comprehensive report for a specific user.
+    ///
+    /// Includes the user's position, health factor, risk level, activity history,
+    /// and cumulative transaction metrics.
+    ///
+    /// # Arguments
+    /// * `user` - The address of the user to report on
+    ///
+    /// # Returns
+    /// A `UserReport` with the user's metrics, position, and recent activities.
+    ///
+    /// # Errors
+    /// Returns `AnalyticsError::DataNotFound` if the user has no recorded activity.
+    pub fn get_user_report(env: Env, user: Address) -> Result<UserReport, AnalyticsError> {
+        analytics::generate_user_report(&env, &user)
+    }
+
+    /// Get the borrowing and liquidation figures of a user's core position.
+    ///
+    /// Returns the collateral, debt, available borrows, liquidation threshold
+    /// and health factor in one call.
+    ///
+    /// # Arguments
+    /// * `user` - The user address
+    ///
+    /// # Returns
+    /// The user's `AccountData`.
+    ///
+    /// # Errors
+    /// Returns `AnalyticsError::Overflow` if the health factor overflows.
+    pub fn get_account_data(env: Env, user: Address) -> Result<AccountData, AnalyticsError> {
+        analytics::get_account_data(&env, &user)
+    }
+
+    /// Get the protocol-wide totals in a single read.
+    ///
+    /// Returns the oracle-valued TVL and debt, the number of active reserves,
+    /// the emergency pause state and the bad debt deficit.
+    ///
+    /// # Returns
+    /// A `ProtocolOverview` of the protocol.
+    pub fn get_protocol_overview(env: Env) -> ProtocolOverview {
+        analytics::get_protocol_overview(&env)
+    }
+
+    /// Get the totals and rates of a single asset's market.
+    ///
+    /// Returns the supplied, borrowed and reserve totals of the asset with its
+    /// utilization, rates and caps in one call.
+    ///
+    /// # Arguments
+    /// * `asset` - The asset address (None for native asset)
+    ///
+    /// # Returns
+    /// A `MarketSnapshot` of the asset.
+    pub fn get_market_snapshot(env: Env, asset: Option<Address>) -> MarketSnapshot {
+        analytics::get_market_snapshot(&env, &asset)
+    }
+
+    /// Retrieve recent protocol activity entries.
+    ///
+    /// Returns a paginated list of the most recent protocol activities in
+    /// reverse chronological order.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of entries to return
+    /// * `offset` - Number of entries to skip from the most recent
+    ///
+    /// # Returns
+    /// A vector of `ActivityEntry` records.
+    pub fn get_recent_activity(
+        env: Env,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<analytics::ActivityEntry>, AnalyticsError> {
+        analytics::get_recent_activity(&env, limit, offset)
+    }
+
+    /// Retrieve activity entries for a specific user.
+    ///
+    /// Returns a paginated list of the user's activities in reverse
+    /// chronological order, filtered from the protocol activity log.
+    ///
+    /// # Arguments
+    /// * `user` - The address of the user
+    /// * `limit` - Maximum number of entries to return
+    /// * `offset` - Number of entries to skip from the most recent
+    ///
+    /// # Returns
+    /// A vector of `ActivityEntry` records for the specified user.
+    pub fn get_user_activity_feed(
+        env: Env,
+        user: Address,
+        limit: u32,
         offset: u32,
-    ) -> Result<soroban_sdk::Vec<analytics::ActivityEntry>, AnalyticsError> {
-        get_user_activity_feed(&env, &user, limit, offset)
+    ) -> Result<Vec<analytics::ActivityEntry>, AnalyticsError> {
+        analytics::get_user_activity_feed(&env, &user, limit, offset)
+    }
+
+    /// Get a page of a user's recent actions, newest first
+    ///
+    /// Reads the user's own bounded history (last 50 actions), so older
+    /// actions do not depend on the protocol-wide activity log.
+    ///
+    /// # Arguments
+    /// * `user` - The user address
+    /// * `cursor` - Id of the last action of the previous page (None for the
+    ///   most recent actions)
+    /// * `limit` - Maximum actions to return (capped at 50)
+    pub fn get_user_activity(
+        env: Env,
+        user: Address,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> Vec<UserAction> {
+        user_activity::get_user_activity(&env, &user, cursor, limit)
+    }
+
+    /// Get user analytics metrics
+    pub fn get_user_analytics(env: Env, user: Address) -> Result<UserMetrics, AnalyticsError> {
+        analytics::get_user_activity_summary(&env, &user)
+    }
+
+    /// Get protocol analytics metrics
+    pub fn get_protocol_analytics(env: Env) -> Result<ProtocolMetrics, AnalyticsError> {
+        analytics::get_protocol_stats(&env)
+    }
+
+    // ============================================================================
+    // Oracle Entrypoints
+    // ============================================================================
+
+    /// Update price feed from oracle
+    ///
+    /// # Returns
+    /// Returns the stored price
+    pub fn update_price_feed(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        price: i128,
+        decimals: u32,
+        oracle: Address,
+    ) -> Result<i128, ProtocolError> {
+        oracle::update_price_feed(&env, caller, asset, price, decimals, oracle)
+            .map_err(ProtocolError::from)
+    }
+
+    /// Get current price for an asset
+    pub fn get_price(env: Env, asset: Address) -> Result<i128, ProtocolError> {
+        oracle::get_price(&env, &asset).map_err(ProtocolError::from)
+    }
+
+    /// Configure oracle parameters (admin only)
+    pub fn configure_oracle(
+        env: Env,
+        caller: Address,
+        config: OracleConfig,
+    ) -> Result<(), ProtocolError> {
+        oracle::configure_oracle(&env, caller, config).map_err(ProtocolError::from)
+    }
+
+    /// Set primary oracle for an asset (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `asset` - The asset address
+    /// * `primary_oracle` - The primary oracle address
+    pub fn set_primary_oracle(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        primary_oracle: Address,
+    ) -> Result<(), ProtocolError> {
+        oracle::set_primary_oracle(&env, caller, asset, primary_oracle).map_err(ProtocolError::from)
+    }
+
+    /// Set fallback oracle for an asset (admin only)
+    pub fn set_fallback_oracle(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        fallback_oracle: Address,
+    ) -> Result<(), ProtocolError> {
+        oracle::set_fallback_oracle(&env, caller, asset, fallback_oracle)
+            .map_err(ProtocolError::from)
+    }
+
+    // ============================================================================
+    // AMM Entrypoints
+    // ============================================================================
+
+    /// Initialize AMM settings (admin only)
+    pub fn initialize_amm(
+        env: Env,
+        admin: Address,
+        default_slippage: i128,
+        max_slippage: i128,
+        auto_swap_threshold: i128,
+    ) -> Result<(), AmmError> {
+        amm::initialize_amm(
+            env,
+            admin,
+            default_slippage,
+            max_slippage,
+            auto_swap_threshold,
+        )
+    }
+
+    /// Set AMM pool configuration (admin or risk manager)
+    pub fn set_amm_pool(
+        env: Env,
+        caller: Address,
+        protocol_config: AmmProtocolConfig,
+    ) -> Result<(), AmmError> {
+        amm::set_amm_pool(env, caller, protocol_config)
+    }
+
+    /// Override the AMM slippage cap of a token pair (admin only)
+    ///
+    /// The cap only tightens the global maximum slippage; None removes the
+    /// override.
+    pub fn set_amm_pair_slippage(
+        env: Env,
+        admin: Address,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+        max_slippage: Option<i128>,
+    ) -> Result<(), AmmError> {
+        amm::set_amm_pair_slippage(env, admin, token_a, token_b, max_slippage)
+    }
+
+    /// Execute swap through AMM
+    pub fn amm_swap(env: Env, user: Address, params: SwapParams) -> Result<i128, AmmError> {
+        amm::amm_swap(env, user, params)
+    }
+
+    /// Create a native constant-product pool (AMM admin only)
+    ///
+    /// The pool's reserves sit in this contract's token balances but are
+    /// never lent out; borrows only draw on the balance beyond them.
+    ///
+    /// # Returns
+    /// Returns the id of the new pool
+    pub fn amm_create_pool(
+        env: Env,
+        admin: Address,
+        token_a: Address,
+        token_b: Address,
+        amount_a: i128,
+        amount_b: i128,
+        swap_fee_bps: i128,
+    ) -> Result<u32, AmmError> {
+        amm::amm_create_pool(
+            env,
+            admin,
+            token_a,
+            token_b,
+            amount_a,
+            amount_b,
+            swap_fee_bps,
+        )
+    }
+
+    /// Get a native AMM pool
+    pub fn amm_get_pool(env: Env, pool_id: u32) -> Option<ConstantProductPool> {
+        stellarlend_amm::pools::get_pool(&env, pool_id).ok()
+    }
+
+    /// Get the LP shares an account holds in a native AMM pool, pledged
+    /// shares excluded
+    pub fn amm_get_lp_shares(env: Env, pool_id: u32, account: Address) -> i128 {
+        stellarlend_amm::pools::get_lp_shares(&env, pool_id, &account)
+    }
+
+    /// Get cumulative AMM swap statistics, globally or of a pair, protocol
+    /// or user
+    pub fn amm_get_swap_stats(env: Env, scope: SwapStatsScope) -> SwapStats {
+        stellarlend_amm::amm::get_swap_stats(&env, scope)
+    }
+
+    /// Add liquidity to an AMM pool
+    ///
+    /// # Returns
+    /// Returns the LP tokens minted
+    pub fn amm_add_liquidity(
+        env: Env,
+        user: Address,
+        params: LiquidityParams,
+    ) -> Result<i128, AmmError> {
+        amm::amm_add_liquidity(env, user, params)
+    }
+
+    /// Remove liquidity from an AMM pool
+    ///
+    /// # Returns
+    /// Returns a tuple (amount_a, amount_b) received
+    pub fn amm_remove_liquidity(
+        env: Env,
+        user: Address,
+        protocol: Address,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+        lp_tokens: i128,
+        min_amount_a: i128,
+        min_amount_b: i128,
+        deadline: u64,
+    ) -> Result<(i128, i128), AmmError> {
+        amm::amm_remove_liquidity(
+            env,
+            user,
+            protocol,
+            token_a,
+            token_b,
+            lp_tokens,
+            min_amount_a,
+            min_amount_b,
+            deadline,
+        )
+    }
+
+    /// Get the time-weighted average price of `token_a` in `token_b`
+    ///
+    /// Averages the effective prices of AMM swaps between the two tokens over
+    /// at least the last `window` seconds, for cross-checking oracle prices.
+    ///
+    /// # Returns
+    /// Units of `token_b` per unit of `token_a` (scaled by 10^18)
+    pub fn get_twap(
+        env: Env,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+        window: u64,
+    ) -> Result<i128, AmmError> {
+        amm::amm_get_twap(env, token_a, token_b, window)
+    }
+
+    // ============================================================================
+    // Bridge Entrypoints
+    // ============================================================================
+
+    /// Register a bridge (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - Admin address for authorization
+    /// * `network_id` - ID of the remote network
+    /// * `bridge` - Address of the bridge contract
+    /// * `fee_bps` - Fee in basis points
+    pub fn register_bridge(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        bridge: Address,
+        fee_bps: i128,
+    ) -> Result<(), BridgeError> {
+        bridge::register_bridge(&env, caller, network_id, bridge, fee_bps)
+    }
+
+    /// Set bridge fee (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - Admin address for authorization
+    /// * `network_id` - ID of the remote network
+    /// * `fee_bps` - New fee in basis points
+    pub fn set_bridge_fee(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        fee_bps: i128,
+    ) -> Result<(), BridgeError> {
+        bridge::set_bridge_fee(&env, caller, network_id, fee_bps)
+    }
+
+    /// Deposit through a bridge
+    ///
+    /// # Arguments
+    /// * `user` - User depositing collateral
+    /// * `network_id` - Remote network ID
+    /// * `asset` - Asset to deposit
+    /// * `amount` - Amount to deposit
+    pub fn bridge_deposit(
+        env: Env,
+        user: Address,
+        network_id: u32,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, BridgeError> {
+        bridge::bridge_deposit(&env, user, network_id, asset, amount)
+    }
+
+    /// Withdraw through a bridge
+    ///
+    /// # Arguments
+    /// * `user` - User withdrawing collateral
+    /// * `network_id` - Remote network ID
+    /// * `asset` - Asset to withdraw
+    /// * `amount` - Amount to withdraw
+    pub fn bridge_withdraw(
+        env: Env,
+        user: Address,
+        network_id: u32,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, BridgeError> {
+        bridge::bridge_withdraw(&env, user, network_id, asset, amount)
+    }
+
+    /// List all bridges
+    pub fn list_bridges(env: Env) -> Map<u32, BridgeConfig> {
+        bridge::list_bridges(&env)
+    }
+
+    /// Get configuration of a specific bridge
+    pub fn get_bridge_config(env: Env, network_id: u32) -> Result<BridgeConfig, BridgeError> {
+        bridge::get_bridge_config(&env, network_id)
+    }
+
+    // ============================================================================
+    // Configuration Entrypoints
+    // ============================================================================
+
+    /// Set a configuration value (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `key` - The configuration key
+    /// * `value` - The configuration value
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn config_set(
+        env: Env,
+        caller: Address,
+        key: Symbol,
+        value: Val,
+    ) -> Result<(), ConfigError> {
+        config::config_set(&env, caller, key, value)
+    }
+
+    /// Get a configuration value
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key
+    ///
+    /// # Returns
+    /// Returns Some(value) if the key exists, None otherwise
+    pub fn config_get(env: Env, key: Symbol) -> Option<Val> {
+        config::config_get(&env, key)
+    }
+
+    /// Backup configuration parameters (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `keys` - A vector of configuration keys to backup
+    ///
+    /// # Returns
+    /// Returns a vector of key-value pairs representing the backup
+    pub fn config_backup(
+        env: Env,
+        caller: Address,
+        keys: Vec<Symbol>,
+    ) -> Result<Vec<(Symbol, Val)>, ConfigError> {
+        config::config_backup(&env, caller, keys)
+    }
+
+    /// Restore configuration parameters (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `backup` - A vector of key-value pairs to restore
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn config_restore(
+        env: Env,
+        caller: Address,
+        backup: Vec<(Symbol, Val)>,
+    ) -> Result<(), ConfigError> {
+        config::config_restore(&env, caller, backup)
+    }
+
+    // ============================================================================
+    // Cross-Asset / Asset Configuration Methods
+    // ============================================================================
+
+    /// Initialize cross-asset lending module (admin only)
+    ///
+    /// Sets up the cross-asset lending system with an admin.
+    /// Must be called before any asset configuration.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn initialize_ca(env: Env, admin: Address) -> Result<(), CrossAssetError> {
+        cross_asset::initialize(&env, admin)
+    }
+
+    /// Initialize/register a new asset with configuration
+    ///
+    /// Registers an asset with its collateral factor (LTV), liquidation threshold,
+    /// debt ceiling, and other parameters. Admin only.
+    ///
+    /// # Arguments
+    /// * `asset` - Asset address (None for native XLM)
+    /// * `config` - Complete asset configuration
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn initialize_asset(
+        env: Env,
+        asset: Option<Address>,
+        config: AssetConfig,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::initialize_asset(&env, asset, config)
+    }
+
+    /// Update asset configuration (admin only)
+    ///
+    /// Updates asset parameters including LTV, liquidation threshold, debt ceiling,
+    /// and collateral/borrow enablement. Only provided fields are updated.
+    /// While the parameter timelock is enabled, the LTV must be changed by
+    /// queuing instead.
+    ///
+    /// # Arguments
+    /// * `asset` - Asset address (None for XLM)
+    /// * `collateral_factor` - Optional new LTV in basis points
+    /// * `liquidation_threshold` - Optional new liquidation threshold in basis points
+    /// * `max_supply` - Optional new supply cap
+    /// * `max_borrow` - Optional new debt ceiling
+    /// * `can_collateralize` - Optional flag to enable/disable as collateral
+    /// * `can_borrow` - Optional flag to enable/disable borrowing
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn update_asset_config(
+        env: Env,
+        asset: Option<Address>,
+        collateral_factor: Option<i128>,
+        liquidation_threshold: Option<i128>,
+        max_supply: Option<i128>,
+        max_borrow: Option<i128>,
+        can_collateralize: Option<bool>,
+        can_borrow: Option<bool>,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::update_asset_config(
+            &env,
+            asset,
+            collateral_factor,
+            liquidation_threshold,
+            max_supply,
+            max_borrow,
+            can_collateralize,
+            can_borrow,
+        )
+    }
+
+    /// Update asset price (admin/oracle only)
+    ///
+    /// Updates the price for an asset used in health factor calculations.
+    ///
+    /// # Arguments
+    /// * `asset` - Asset address (None for XLM)
+    /// * `price` - New price in base units (7 decimals)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn update_asset_price(
+        env: Env,
+        asset: Option<Address>,
+        price: i128,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::update_asset_price(&env, asset, price)
+    }
+
+    /// Get asset configuration
+    ///
+    /// Returns the configuration for a specific asset including LTV,
+    /// liquidation threshold, debt ceiling, etc.
+    ///
+    /// # Arguments
+    /// * `asset` - Asset address (None for XLM)
+    ///
+    /// # Returns
+    /// Asset configuration
+    pub fn get_asset_config(
+        env: Env,
+        asset: Option<Address>,
+    ) -> Result<AssetConfig, CrossAssetError> {
+        cross_asset::get_asset_config_by_address(&env, asset)
+    }
+
+    /// Get list of all configured assets
+    ///
+    /// Returns all assets that have been registered in the system.
+    ///
+    /// # Returns
+    /// Vector of asset keys
+    pub fn get_asset_list(env: Env) -> Vec<AssetKey> {
+        cross_asset::get_asset_list(&env)
+    }
+
+    /// Deposit collateral for cross-asset lending
+    ///
+    /// Deposits collateral that can be used across multiple assets.
+    /// Asset must be enabled for collateral.
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    /// * `asset` - Asset address (None for XLM)
+    /// * `amount` - Amount to deposit
+    ///
+    /// # Returns
+    /// Updated asset position
+    pub fn cross_asset_deposit(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<AssetPosition, CrossAssetError> {
+        cross_asset::cross_asset_deposit(&env, user, asset, amount)
+    }
+
+    /// Withdraw collateral from cross-asset lending
+    ///
+    /// Withdraws collateral while maintaining healthy position.
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    /// * `asset` - Asset address (None for XLM)
+    /// * `amount` - Amount to withdraw
+    ///
+    /// # Returns
+    /// Updated asset position
+    pub fn cross_asset_withdraw(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<AssetPosition, CrossAssetError> {
+        cross_asset::cross_asset_withdraw(&env, user, asset, amount)
+    }
+
+    /// Borrow asset in cross-asset lending
+    ///
+    /// Borrows against cross-asset collateral. Respects LTV and debt ceiling.
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    /// * `asset` - Asset address (None for XLM)
+    /// * `amount` - Amount to borrow
+    ///
+    /// # Returns
+    /// Updated asset position
+    pub fn cross_asset_borrow(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<AssetPosition, CrossAssetError> {
+        cross_asset::cross_asset_borrow(&env, user, asset, amount)
+    }
+
+    /// Repay borrowed asset
+    ///
+    /// Repays debt for a specific asset.
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    /// * `asset` - Asset address (None for XLM)
+    /// * `amount` - Amount to repay
+    ///
+    /// # Returns
+    /// Updated asset position
+    pub fn cross_asset_repay(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<AssetPosition, CrossAssetError> {
+        cross_asset::cross_asset_repay(&env, user, asset, amount)
+    }
+
+    /// Liquidate one debt/collateral pair of an unhealthy cross-asset portfolio
+    ///
+    /// The liquidator picks which of the borrower's collateral assets to
+    /// seize; see `get_best_liquidation` for the most profitable pair.
+    ///
+    /// # Arguments
+    /// * `liquidator` - Liquidator address
+    /// * `borrower` - Owner of the unhealthy portfolio
+    /// * `debt_asset` - Asset whose debt is repaid (None for XLM)
+    /// * `collateral_asset` - Asset whose collateral is seized (None for XLM)
+    /// * `debt_amount` - Debt to repay
+    ///
+    /// # Returns
+    /// Returns a tuple (debt_repaid, collateral_seized)
+    pub fn cross_asset_liquidate(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        debt_asset: Option<Address>,
+        collateral_asset: Option<Address>,
+        debt_amount: i128,
+    ) -> Result<(i128, i128), CrossAssetError> {
+        cross_asset::cross_asset_liquidate(
+            &env,
+            liquidator,
+            borrower,
+            debt_asset,
+            collateral_asset,
+            debt_amount,
+        )
+    }
+
+    /// Suggest the debt/collateral pair maximizing the liquidation bonus
+    ///
+    /// Returns None if the borrower's cross-asset portfolio is healthy.
+    pub fn get_best_liquidation(
+        env: Env,
+        borrower: Address,
+    ) -> Result<Option<LiquidationSuggestion>, CrossAssetError> {
+        cross_asset::get_best_liquidation(&env, &borrower)
+    }
+
+    /// Pledge LP shares of a native AMM pool as cross-asset collateral
+    ///
+    /// Both pool tokens must be enabled as collateral. Each side counts at
+    /// fair value less the token's LP haircut.
+    ///
+    /// # Returns
+    /// The user's pledged shares of the pool
+    pub fn deposit_lp_collateral(
+        env: Env,
+        user: Address,
+        pool_id: u32,
+        shares: i128,
+    ) -> Result<i128, CrossAssetError> {
+        lp_collateral::deposit_lp_collateral(&env, user, pool_id, shares)
+    }
+
+    /// Withdraw pledged LP shares, keeping the health factor above 1.0
+    ///
+    /// # Returns
+    /// The user's remaining pledged shares of the pool
+    pub fn withdraw_lp_collateral(
+        env: Env,
+        user: Address,
+        pool_id: u32,
+        shares: i128,
+    ) -> Result<i128, CrossAssetError> {
+        lp_collateral::withdraw_lp_collateral(&env, user, pool_id, shares)
+    }
+
+    /// Liquidate pledged LP shares of an unhealthy cross-asset portfolio
+    ///
+    /// # Arguments
+    /// * `liquidator` - Liquidator address
+    /// * `borrower` - Owner of the unhealthy portfolio
+    /// * `debt_asset` - Asset whose debt is repaid (None for XLM)
+    /// * `pool_id` - Native pool whose pledged shares are seized
+    /// * `debt_amount` - Debt to repay
+    ///
+    /// # Returns
+    /// Returns a tuple (debt_repaid, shares_seized)
+    pub fn liquidate_lp_collateral(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        debt_asset: Option<Address>,
+        pool_id: u32,
+        debt_amount: i128,
+    ) -> Result<(i128, i128), CrossAssetError> {
+        lp_collateral::liquidate_lp_collateral(
+            &env,
+            liquidator,
+            borrower,
+            debt_asset,
+            pool_id,
+            debt_amount,
+        )
+    }
+
+    /// Get the LP shares of a native pool a user has pledged as collateral
+    pub fn get_pledged_lp_shares(env: Env, user: Address, pool_id: u32) -> i128 {
+        lp_collateral::get_pledged_shares(&env, &user, pool_id)
+    }
+
+    /// Get the fair value of LP shares of a native pool, before haircuts
+    ///
+    /// # Returns
+    /// The value in USD (7 decimals), from the pool invariant and the oracle
+    /// prices of both tokens
+    pub fn get_lp_fair_value(
+        env: Env,
+        pool_id: u32,
+        shares: i128,
+    ) -> Result<i128, CrossAssetError> {
+        lp_collateral::get_lp_fair_value(&env, pool_id, shares)
+    }
+
+    /// Get user's position for a specific asset
+    ///
+    /// Returns collateral and debt for a user in a specific asset.
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    /// * `asset` - Asset address (None for XLM)
+    ///
+    /// # Returns
+    /// Asset position
+    pub fn get_user_asset_position(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+    ) -> AssetPosition {
+        cross_asset::get_user_asset_position(&env, &user, asset)
+    }
+
+    /// Get user's unified position summary across all assets
+    ///
+    /// Calculates health factor, liquidation status, and borrow capacity
+    /// across all assets.
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    ///
+    /// # Returns
+    /// Position summary with health factor
+    pub fn get_user_position_summary(
+        env: Env,
+        user: Address,
+    ) -> Result<UserPositionSummary, CrossAssetError> {
+        cross_asset::get_user_position_summary(&env, &user)
+    }
+
+    /// Get a user's health factor over all collateral and debt assets
+    ///
+    /// Liquidation-threshold weighted collateral value of every asset over
+    /// the value of every debt, scaled by 10000 (1.0x).
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    ///
+    /// # Returns
+    /// The health factor; `i128::MAX` without debt
+    pub fn get_health_factor(env: Env, user: Address) -> Result<i128, CrossAssetError> {
+        cross_asset::get_health_factor(&env, &user)
+    }
+
+    /// Get the value of each asset a user has collateral or debt in
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    ///
+    /// # Returns
+    /// One leg per asset, with the price and factors used for health
+    pub fn get_portfolio(env: Env, user: Address) -> Result<Vec<PortfolioLeg>, CrossAssetError> {
+        cross_asset::get_portfolio(&env, &user)
+    }
+
+    /// Get the price of a collateral asset at which a user's health factor
+    /// crosses 1.0, with every other price unchanged
+    ///
+    /// # Arguments
+    /// * `user` - User address
+    /// * `collateral_asset` - Collateral asset (None for XLM)
+    ///
+    /// # Returns
+    /// The liquidation price (7 decimals); 0 if no price of the asset makes
+    /// the position liquidatable
+    pub fn get_liquidation_price(
+        env: Env,
+        user: Address,
+        collateral_asset: Option<Address>,
+    ) -> Result<i128, CrossAssetError> {
+        cross_asset::get_liquidation_price(&env, &user, collateral_asset)
+    }
+
+    // ============================================================================
+    // Governance Entrypoints
+    // ============================================================================
+
+    /// Initialize governance module
+    ///
+    /// Sets up the governance system with voting token and parameters.
+    /// Must be called after contract initialization.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `vote_token` - Token contract address used for voting
+    /// * `voting_period` - Optional voting duration in seconds
+    /// * `execution_delay` - Optional delay before execution in seconds
+    /// * `quorum_bps` - Optional quorum requirement in basis points
+    /// * `proposal_threshold` - Optional minimum tokens to create proposal
+    /// * `timelock_duration` - Optional timelock duration in seconds
+    /// * `default_voting_threshold` - Optional default voting threshold in basis points
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_initialize(
+        env: Env,
+        admin: Address,
+        vote_token: Address,
+        voting_period: Option<u64>,
+        execution_delay: Option<u64>,
+        quorum_bps: Option<u32>,
+        proposal_threshold: Option<i128>,
+        timelock_duration: Option<u64>,
+        default_voting_threshold: Option<i128>,
+    ) -> Result<(), GovernanceError> {
+        governance::initialize(
+            &env,
+            admin,
+            vote_token,
+            voting_period,
+            execution_delay,
+            quorum_bps,
+            proposal_threshold,
+            timelock_duration,
+            default_voting_threshold,
+        )
+    }
+
+    /// Create a new governance proposal
+    ///
+    /// # Arguments
+    /// * `proposer` - Address creating the proposal
+    /// * `proposal_type` - Type of proposal (parameter change, pause, etc.)
+    /// * `description` - Description of the proposal
+    /// * `voting_threshold` - Optional custom voting threshold
+    ///
+    /// # Returns
+    /// Returns the new proposal ID
+    pub fn gov_create_proposal(
+        env: Env,
+        proposer: Address,
+        proposal_type: ProposalType,
+        description: String,
+        voting_threshold: Option<i128>,
+    ) -> Result<u64, GovernanceError> {
+        governance::create_proposal(&env, proposer, proposal_type, description, voting_threshold)
+    }
+
+    /// Cast a vote on a proposal
+    ///
+    /// # Arguments
+    /// * `voter` - Address casting the vote
+    /// * `proposal_id` - ID of the proposal to vote on
+    /// * `vote_type` - Vote choice (For, Against, Abstain)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        vote_type: VoteType,
+    ) -> Result<(), GovernanceError> {
+        governance::vote(&env, voter, proposal_id, vote_type)
+    }
+
+    /// Queue a successful proposal for execution
+    ///
+    /// # Arguments
+    /// * `caller` - Address queuing the proposal
+    /// * `proposal_id` - ID of the proposal to queue
+    ///
+    /// # Returns
+    /// Returns the proposal outcome
+    pub fn gov_queue_proposal(
+        env: Env,
+        caller: Address,
+        proposal_id: u64,
+    ) -> Result<ProposalOutcome, GovernanceError> {
+        governance::queue_proposal(&env, caller, proposal_id)
+    }
+
+    /// Execute a queued proposal
+    ///
+    /// # Arguments
+    /// * `executor` - Address executing the proposal
+    /// * `proposal_id` - ID of the proposal to execute
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_execute_proposal(
+        env: Env,
+        executor: Address,
+        proposal_id: u64,
+    ) -> Result<(), GovernanceError> {
+        governance::execute_proposal(&env, executor, proposal_id)
+    }
+
+    /// Cancel a proposal
+    ///
+    /// Only proposer or admin can cancel.
+    ///
+    /// # Arguments
+    /// * `caller` - Address cancelling the proposal
+    /// * `proposal_id` - ID of the proposal to cancel
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_cancel_proposal(
+        env: Env,
+        caller: Address,
+        proposal_id: u64,
+    ) -> Result<(), GovernanceError> {
+        governance::cancel_proposal(&env, caller, proposal_id)
+    }
+
+    /// Approve a proposal as multisig admin
+    ///
+    /// # Arguments
+    /// * `approver` - Admin address approving the proposal
+    /// * `proposal_id` - ID of the proposal to approve
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_approve_proposal(
+        env: Env,
+        approver: Address,
+        proposal_id: u64,
+    ) -> Result<(), GovernanceError> {
+        governance::approve_proposal(&env, approver, proposal_id)
+    }
+
+    /// Set multisig configuration
+    ///
+    /// # Arguments
+    /// * `caller` - Caller address (must be admin)
+    /// * `admins` - Vector of admin addresses
+    /// * `threshold` - Number of approvals required
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_set_multisig_config(
+        env: Env,
+        caller: Address,
+        admins: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), GovernanceError> {
+        governance::set_multisig_config(&env, caller, admins, threshold)
+    }
+
+    /// Add a guardian
+    ///
+    /// # Arguments
+    /// * `caller` - Caller address (must be admin)
+    /// * `guardian` - Guardian address to add
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_add_guardian(
+        env: Env,
+        caller: Address,
+        guardian: Address,
+    ) -> Result<(), GovernanceError> {
+        governance::add_guardian(&env, caller, guardian)
+    }
+
+    /// Remove a guardian
+    ///
+    /// # Arguments
+    /// * `caller` - Caller address (must be admin)
+    /// * `guardian` - Guardian address to remove
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_remove_guardian(
+        env: Env,
+        caller: Address,
+        guardian: Address,
+    ) -> Result<(), GovernanceError> {
+        governance::remove_guardian(&env, caller, guardian)
+    }
+
+    /// Set guardian threshold
+    ///
+    /// # Arguments
+    /// * `caller` - Caller address (must be admin)
+    /// * `threshold` - Number of guardian approvals required for recovery
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_set_guardian_threshold(
+        env: Env,
+        caller: Address,
+        threshold: u32,
+    ) -> Result<(), GovernanceError> {
+        governance::set_guardian_threshold(&env, caller, threshold)
+    }
+
+    /// Start recovery process
+    ///
+    /// # Arguments
+    /// * `initiator` - Guardian initiating recovery
+    /// * `old_admin` - Current admin to replace
+    /// * `new_admin` - New admin address
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_start_recovery(
+        env: Env,
+        initiator: Address,
+        old_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), GovernanceError> {
+        governance::start_recovery(&env, initiator, old_admin, new_admin)
+    }
+
+    /// Approve recovery
+    ///
+    /// # Arguments
+    /// * `approver` - Guardian approving recovery
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_approve_recovery(env: Env, approver: Address) -> Result<(), GovernanceError> {
+        governance::approve_recovery(&env, approver)
+    }
+
+    /// Execute recovery
+    ///
+    /// # Arguments
+    /// * `executor` - Address executing recovery
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_execute_recovery(env: Env, executor: Address) -> Result<(), GovernanceError> {
+        governance::execute_recovery(&env, executor)
+    }
+
+    // ============================================================================
+    // Governance Query Functions
+    // ============================================================================
+
+    /// Get proposal by ID
+    pub fn gov_get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
+        governance::get_proposal(&env, proposal_id)
+    }
+
+    /// Get vote information
+    pub fn gov_get_vote(env: Env, proposal_id: u64, voter: Address) -> Option<VoteInfo> {
+        governance::get_vote(&env, proposal_id, voter)
+    }
+
+    /// Get governance configuration
+    pub fn gov_get_config(env: Env) -> Option<GovernanceConfig> {
+        governance::get_config(&env)
+    }
+
+    /// Get governance admin
+    pub fn gov_get_admin(env: Env) -> Option<Address> {
+        governance::get_admin(&env)
+    }
+
+    /// Get multisig configuration
+    pub fn gov_get_multisig_config(env: Env) -> Option<MultisigConfig> {
+        governance::get_multisig_config(&env)
+    }
+
+    /// Get guardian configuration
+    pub fn gov_get_guardian_config(env: Env) -> Option<GuardianConfig> {
+        governance::get_guardian_config(&env)
+    }
+
+    /// Get proposal approvals
+    pub fn gov_get_proposal_approvals(env: Env, proposal_id: u64) -> Option<Vec<Address>> {
+        governance::get_proposal_approvals(&env, proposal_id)
+    }
+
+    /// Get current recovery request
+    pub fn gov_get_recovery_request(env: Env) -> Option<RecoveryRequest> {
+        governance::get_recovery_request(&env)
+    }
+
+    /// Get recovery approvals
+    pub fn gov_get_recovery_approvals(env: Env) -> Option<Vec<Address>> {
+        governance::get_recovery_approvals(&env)
+    }
+
+    // ============================================================================
+    // Strategy Vault Entrypoints
+    // ============================================================================
+
+    /// Create a leveraged strategy vault (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `config` - Vault pair, target leverage, rebalancing band, deposit cap and fee
+    ///
+    /// # Returns
+    /// Returns the id of the new vault
+    pub fn create_strategy_vault(
+        env: Env,
+        caller: Address,
+        config: StrategyVaultConfig,
+    ) -> Result<u32, StrategyVaultError> {
+        strategy_vault::create_vault(&env, caller, config)
+    }
+
+    /// Update a strategy vault's configuration (admin only)
+    ///
+    /// The collateral and debt assets of a vault cannot be changed.
+    pub fn update_strategy_vault(
+        env: Env,
+        caller: Address,
+        vault_id: u32,
+        config: StrategyVaultConfig,
+    ) -> Result<(), StrategyVaultError> {
+        strategy_vault::update_vault_config(&env, caller, vault_id, config)
+    }
+
+    /// Deposit the vault's collateral asset in exchange for vault shares
+    ///
+    /// # Arguments
+    /// * `user` - The depositor
+    /// * `vault_id` - The vault id
+    /// * `amount` - Amount of collateral to deposit
+    ///
+    /// # Returns
+    /// Returns the number of shares minted
+    pub fn vault_deposit(
+        env: Env,
+        user: Address,
+        vault_id: u32,
+        amount: i128,
+    ) -> Result<i128, StrategyVaultError> {
+        strategy_vault::vault_deposit(&env, user, vault_id, amount)
+    }
+
+    /// Redeem vault shares, deleveraging pro rata
+    ///
+    /// # Arguments
+    /// * `user` - The share holder
+    /// * `vault_id` - The vault id
+    /// * `shares` - Number of shares to redeem
+    ///
+    /// # Returns
+    /// Returns the collateral amount paid out
+    pub fn vault_withdraw(
+        env: Env,
+        user: Address,
+        vault_id: u32,
+        shares: i128,
+    ) -> Result<i128, StrategyVaultError> {
+        strategy_vault::vault_withdraw(&env, user, vault_id, shares)
+    }
+
+    /// Rebalance a vault back to its target leverage (keeper or admin)
+    ///
+    /// Only allowed while the vault's leverage is outside its rebalancing band.
+    ///
+    /// # Returns
+    /// Returns the new leverage in basis points
+    pub fn rebalance_vault(
+        env: Env,
+        keeper: Address,
+        vault_id: u32,
+    ) -> Result<i128, StrategyVaultError> {
+        strategy_vault::rebalance_vault(&env, keeper, vault_id)
+    }
+
+    /// Get a vault's configuration, state, equity, leverage and share price
+    pub fn get_strategy_vault(
+        env: Env,
+        vault_id: u32,
+    ) -> Result<StrategyVaultView, StrategyVaultError> {
+        strategy_vault::get_vault(&env, vault_id)
+    }
+
+    /// Get the shares held by a user in a vault
+    pub fn get_vault_shares(env: Env, vault_id: u32, user: Address) -> i128 {
+        strategy_vault::get_vault_shares(&env, vault_id, &user)
+    }
+
+    /// Get the number of strategy vaults created
+    pub fn get_vault_count(env: Env) -> u32 {
+        strategy_vault::get_vault_count(&env)
+    }
+
+    // ============================================================================
+    // Liquidation Insurance Entrypoints
+    // ============================================================================
+
+    /// Configure liquidation insurance (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `premium_bps` - Annual premium on insured principal, added to the borrow rate
+    /// * `max_coverage` - Maximum coverage a single policy can carry
+    /// * `enabled` - Whether new policies can be bought
+    pub fn set_insurance_config(
+        env: Env,
+        caller: Address,
+        premium_bps: i128,
+        max_coverage: i128,
+        enabled: bool,
+    ) -> Result<(), LiquidationInsuranceError> {
+        liquidation_insurance::set_insurance_config(
+            &env,
+            caller,
+            premium_bps,
+            max_coverage,
+            enabled,
+        )
+    }
+
+    /// Add funds to the liquidation insurance fund of an asset
+    ///
+    /// # Returns
+    /// Returns the new fund balance
+    pub fn fund_insurance(
+        env: Env,
+        funder: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, LiquidationInsuranceError> {
+        liquidation_insurance::fund_insurance(&env, funder, asset, amount)
+    }
+
+    /// Buy liquidation protection for the caller's position
+    ///
+    /// # Arguments
+    /// * `user` - The borrower
+    /// * `debt_asset` - The asset the policy pays out in (None for native XLM)
+    /// * `coverage` - Maximum debt the fund will repay before liquidators can act
+    pub fn buy_insurance(
+        env: Env,
+        user: Address,
+        debt_asset: Option<Address>,
+        coverage: i128,
+    ) -> Result<InsurancePolicy, LiquidationInsuranceError> {
+        liquidation_insurance::buy_insurance(&env, user, debt_asset, coverage)
+    }
+
+    /// Cancel the caller's liquidation protection
+    pub fn cancel_insurance(env: Env, user: Address) -> Result<(), LiquidationInsuranceError> {
+        liquidation_insurance::cancel_insurance(&env, user)
+    }
+
+    /// Get the liquidation protection held by a borrower
+    pub fn get_insurance_policy(env: Env, user: Address) -> Option<InsurancePolicy> {
+        liquidation_insurance::get_insurance_policy(&env, &user)
+    }
+
+    /// Get the liquidation insurance fund balance for an asset
+    pub fn get_insurance_fund(env: Env, asset: Option<Address>) -> i128 {
+        liquidation_insurance::get_insurance_fund(&env, &asset)
+    }
+
+    /// Get the liquidation insurance configuration
+    pub fn get_insurance_config(env: Env) -> Option<LiquidationInsuranceConfig> {
+        liquidation_insurance::get_insurance_config(&env)
+    }
+
+    // ============================================================================
+    // CDP Stablecoin Entrypoints
+    // ============================================================================
+
+    /// Initialize the CDP module with the protocol stablecoin (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `stablecoin` - Stellar asset whose admin is this contract
+    /// * `debt_ceiling` - Maximum stablecoin debt across all collateral types
+    pub fn initialize_cdp(
+        env: Env,
+        caller: Address,
+        stablecoin: Address,
+        debt_ceiling: i128,
+    ) -> Result<(), CdpError> {
+        cdp::initialize_cdp(&env, caller, stablecoin, debt_ceiling)
+    }
+
+    /// Update the global stablecoin debt ceiling (admin only)
+    pub fn set_cdp_debt_ceiling(
+        env: Env,
+        caller: Address,
+        debt_ceiling: i128,
+    ) -> Result<(), CdpError> {
+        cdp::set_cdp_debt_ceiling(&env, caller, debt_ceiling)
+    }
+
+    /// Approve or update a CDP collateral type (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `collateral` - The collateral asset
+    /// * `collateral_type` - Collateral ratios, penalty, stability fee and debt ceiling
+    pub fn set_cdp_collateral_type(
+        env: Env,
+        caller: Address,
+        collateral: Address,
+        collateral_type: CdpCollateralType,
+    ) -> Result<(), CdpError> {
+        cdp::set_cdp_collateral_type(&env, caller, collateral, collateral_type)
+    }
+
+    /// Lock collateral in the caller's CDP vault
+    ///
+    /// # Returns
+    /// Returns the vault's new collateral balance
+    pub fn cdp_deposit(
+        env: Env,
+        owner: Address,
+        collateral: Address,
+        amount: i128,
+    ) -> Result<i128, CdpError> {
+        cdp::cdp_deposit(&env, owner, collateral, amount)
+    }
+
+    /// Withdraw collateral from the caller's CDP vault
+    ///
+    /// # Returns
+    /// Returns the vault's remaining collateral
+    pub fn cdp_withdraw(
+        env: Env,
+        owner: Address,
+        collateral: Address,
+        amount: i128,
+    ) -> Result<i128, CdpError> {
+        cdp::cdp_withdraw(&env, owner, collateral, amount)
+    }
+
+    /// Mint stablecoin against the caller's CDP vault
+    ///
+    /// # Returns
+    /// Returns the vault's new debt
+    pub fn cdp_mint(
+        env: Env,
+        owner: Address,
+        collateral: Address,
+        amount: i128,
+    ) -> Result<i128, CdpError> {
+        cdp::cdp_mint(&env, owner, collateral, amount)
+    }
+
+    /// Burn stablecoin to repay the caller's CDP debt
+    ///
+    /// # Returns
+    /// Returns the vault's remaining debt
+    pub fn cdp_repay(
+        env: Env,
+        owner: Address,
+        collateral: Address,
+        amount: i128,
+    ) -> Result<i128, CdpError> {
+        cdp::cdp_repay(&env, owner, collateral, amount)
+    }
+
+    /// Repay CDP debt by selling vault collateral through the AMM router
+    ///
+    /// # Returns
+    /// Returns the vault's remaining debt
+    pub fn cdp_repay_with_collateral(
+        env: Env,
+        owner: Address,
+        collateral: Address,
+        collateral_amount: i128,
+        protocol: Address,
+        min_stable_out: i128,
+        deadline: u64,
+    ) -> Result<i128, CdpError> {
+        cdp::cdp_repay_with_collateral(
+            &env,
+            owner,
+            collateral,
+            collateral_amount,
+            protocol,
+            min_stable_out,
+            deadline,
+        )
+    }
+
+    /// Liquidate a CDP vault below its liquidation ratio
+    ///
+    /// # Returns
+    /// Returns a tuple (debt_covered, collateral_seized)
+    pub fn liquidate_cdp(
+        env: Env,
+        liquidator: Address,
+        owner: Address,
+        collateral: Address,
+        debt_to_cover: i128,
+    ) -> Result<(i128, i128), CdpError> {
+        cdp::liquidate_cdp(&env, liquidator, owner, collateral, debt_to_cover)
+    }
+
+    /// Get a CDP vault with the stability fee accrued up to now
+    pub fn get_cdp_vault(env: Env, owner: Address, collateral: Address) -> Option<CdpVault> {
+        cdp::get_cdp_vault(&env, &owner, &collateral)
+    }
+
+    /// Get a CDP vault's collateral ratio in basis points
+    pub fn get_cdp_collateral_ratio(
+        env: Env,
+        owner: Address,
+        collateral: Address,
+    ) -> Result<i128, CdpError> {
+        cdp::get_cdp_collateral_ratio(&env, &owner, &collateral)
+    }
+
+    /// Get the CDP module configuration
+    pub fn get_cdp_config(env: Env) -> Option<CdpConfig> {
+        cdp::get_cdp_config(&env)
+    }
+
+    /// Get the parameters of a CDP collateral type
+    pub fn get_cdp_collateral_type(env: Env, collateral: Address) -> Option<CdpCollateralType> {
+        cdp::get_cdp_collateral_type(&env, &collateral)
+    }
+
+    /// Get the outstanding stablecoin debt across all collaterals
+    pub fn get_cdp_total_debt(env: Env) -> i128 {
+        cdp::get_cdp_total_debt(&env)
+    }
+
+    /// Get the cumulative stability fees accrued
+    pub fn get_cdp_fees_accrued(env: Env) -> i128 {
+        cdp::get_cdp_fees_accrued(&env)
+    }
+
+    /// Get the cumulative stability fees repaid
+    pub fn get_cdp_fees_collected(env: Env) -> i128 {
+        cdp::get_cdp_fees_collected(&env)
+    }
+
+    // ============================================================================
+    // Savings Rate Entrypoints
+    // ============================================================================
+
+    /// Create or update the savings market of an asset (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `asset` - The protocol stablecoin or another approved asset
+    /// * `rate_bps` - Annual savings rate (max 2000 = 20%)
+    /// * `enabled` - Whether new deposits are accepted
+    pub fn configure_savings_market(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        rate_bps: i128,
+        enabled: bool,
+    ) -> Result<(), SavingsError> {
+        savings::configure_savings_market(&env, caller, asset, rate_bps, enabled)
+    }
+
+    /// Deposit into a savings market, returning the user's savings balance
+    pub fn savings_deposit(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        savings::savings_deposit(&env, user, asset, amount)
+    }
+
+    /// Withdraw instantly from a savings market, returning the remaining balance
+    pub fn savings_withdraw(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        savings::savings_withdraw(&env, user, asset, amount)
+    }
+
+    /// Top up the interest funding pool of a savings market
+    pub fn fund_savings(
+        env: Env,
+        funder: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        savings::fund_savings(&env, funder, asset, amount)
+    }
+
+    /// Mint accrued CDP stability fees into the stablecoin savings pool
+    pub fn harvest_stability_fees(env: Env) -> Result<i128, SavingsError> {
+        savings::harvest_stability_fees(&env)
+    }
+
+    /// Get the savings market of an asset
+    pub fn get_savings_market(env: Env, asset: Address) -> Option<SavingsMarket> {
+        savings::get_savings_market(&env, &asset)
+    }
+
+    /// Get a user's savings balance including accrued interest
+    pub fn get_savings_balance(env: Env, asset: Address, user: Address) -> i128 {
+        savings::get_savings_balance(&env, &asset, &user)
+    }
+
+    // ============================================================================
+    // Liquidation Hedge Marketplace Entrypoints
+    // ============================================================================
+
+    /// Post liquidity to underwrite HF puts
+    ///
+    /// # Arguments
+    /// * `writer` - The liquidity provider
+    /// * `debt_asset` - Debt asset the liquidity repays (None = native)
+    /// * `strike_hf_bps` - Health factor strike, above the liquidation threshold
+    /// * `premium_bps` - Premium charged on each reserved repay amount
+    /// * `amount` - Liquidity escrowed with the offer
+    /// * `expiry` - Expiry timestamp of the offer and its puts
+    pub fn create_hedge_offer(
+        env: Env,
+        writer: Address,
+        debt_asset: Option<Address>,
+        strike_hf_bps: i128,
+        premium_bps: i128,
+        amount: i128,
+        expiry: u64,
+    ) -> Result<u64, HedgeMarketError> {
+        hedge_market::create_hedge_offer(
+            &env,
+            writer,
+            debt_asset,
+            strike_hf_bps,
+            premium_bps,
+            amount,
+            expiry,
+        )
+    }
+
+    /// Cancel an offer and withdraw its unreserved liquidity
+    pub fn cancel_hedge_offer(
+        env: Env,
+        writer: Address,
+        offer_id: u64,
+    ) -> Result<i128, HedgeMarketError> {
+        hedge_market::cancel_hedge_offer(&env, writer, offer_id)
+    }
+
+    /// Buy an HF put covering `repay_amount` of debt from an offer
+    pub fn buy_hf_put(
+        env: Env,
+        holder: Address,
+        offer_id: u64,
+        collateral_asset: Option<Address>,
+        repay_amount: i128,
+    ) -> Result<u64, HedgeMarketError> {
+        hedge_market::buy_hf_put(&env, holder, offer_id, collateral_asset, repay_amount)
+    }
+
+    /// Exercise an HF put whose holder has crossed the strike (keeper or admin)
+    pub fn exercise_hf_put(
+        env: Env,
+        keeper: Address,
+        put_id: u64,
+    ) -> Result<i128, HedgeMarketError> {
+        hedge_market::exercise_hf_put(&env, keeper, put_id)
+    }
+
+    /// Return the reserved liquidity of an expired put to its writer
+    pub fn expire_hf_put(env: Env, put_id: u64) -> Result<i128, HedgeMarketError> {
+        hedge_market::expire_hf_put(&env, put_id)
+    }
+
+    /// Get a hedge offer by id
+    pub fn get_hedge_offer(env: Env, offer_id: u64) -> Option<HedgeOffer> {
+        hedge_market::get_hedge_offer(&env, offer_id)
+    }
+
+    /// Get an HF put by id
+    pub fn get_hf_put(env: Env, put_id: u64) -> Option<HfPut> {
+        hedge_market::get_hf_put(&env, put_id)
+    }
+
+    // ============================================================================
+    // Risk Monitor Entrypoints
+    // ============================================================================
+
+    /// Get protocol-wide collateral, debt, bad debt and solvency ratio
+    pub fn get_solvency_snapshot(env: Env) -> SolvencySnapshot {
+        risk_monitor::get_solvency_snapshot(&env)
+    }
+
+    /// Get the protocol solvency ratio in basis points
+    pub fn get_solvency_ratio(env: Env) -> i128 {
+        risk_monitor::get_solvency_ratio(&env)
+    }
+
+    /// Get the aggregated bad debt value across all accounts
+    pub fn get_aggregate_bad_debt(env: Env) -> i128 {
+        risk_monitor::get_aggregate_bad_debt(&env)
+    }
+
+    /// Get the largest-position concentration of a reserve
+    pub fn get_reserve_concentration(env: Env, asset: Option<Address>) -> ReserveConcentration {
+        risk_monitor::get_reserve_concentration(&env, asset)
+    }
+
+    /// Get the freshness of an asset's price feed
+    pub fn get_oracle_staleness(env: Env, asset: Address) -> OracleStaleness {
+        risk_monitor::get_oracle_staleness(&env, asset)
+    }
+
+    /// Get every risk monitoring metric in a single call
+    pub fn get_risk_monitor_report(env: Env) -> RiskMonitorReport {
+        risk_monitor::get_risk_monitor_report(&env)
+    }
+
+    /// Emit a heartbeat digest event (permissionless, rate limited by ledger)
+    ///
+    /// The digest carries totals, worst health factor bucket counts, pause
+    /// flags and oracle freshness.
+    pub fn heartbeat(env: Env) -> Result<HeartbeatDigest, RiskMonitorError> {
+        risk_monitor::heartbeat(&env)
+    }
+
+    /// Get the heartbeat digest for the current ledger without emitting it
+    pub fn get_heartbeat_digest(env: Env) -> HeartbeatDigest {
+        risk_monitor::get_heartbeat_digest(&env)
+    }
+
+    /// Set the minimum number of ledgers between heartbeats (admin only)
+    pub fn set_heartbeat_interval(
+        env: Env,
+        caller: Address,
+        ledgers: u32,
+    ) -> Result<(), RiskMonitorError> {
+        risk_monitor::set_heartbeat_interval(&env, caller, ledgers)
+    }
+
+    /// Get the heartbeat interval and the ledger of the last heartbeat
+    pub fn get_heartbeat_status(env: Env) -> (u32, Option<u32>) {
+        (
+            risk_monitor::get_heartbeat_interval(&env),
+            risk_monitor::get_last_heartbeat_ledger(&env),
+        )
+    }
+
+    /// Get a risk dashboard snapshot: health factor buckets, a page of
+    /// reserve utilization and cap usage, and trailing liquidation volume
+    ///
+    /// # Arguments
+    /// * `start` - Index of the first reserve to return
+    /// * `limit` - Reserves to return (capped at 20)
+    pub fn get_risk_snapshot(env: Env, start: u32, limit: u32) -> RiskSnapshot {
+        risk_monitor::get_risk_snapshot(&env, start, limit)
+    }
+
+    // ============================================================================
+    // Bad Debt Entrypoints
+    // ============================================================================
+
+    /// Get the outstanding bad debt of an asset (None = native)
+    pub fn get_bad_debt(env: Env, asset: Option<Address>) -> i128 {
+        bad_debt::get_bad_debt(&env, &asset)
+    }
+
+    /// Get the protocol deficit of an asset: debt left behind by positions
+    /// with no collateral and not yet covered by reserves (None = native)
+    pub fn get_deficit(env: Env, asset: Option<Address>) -> i128 {
+        bad_debt::get_bad_debt(&env, &asset)
+    }
+
+    /// Get the oracle-valued bad debt across all assets
+    pub fn get_total_shortfall(env: Env) -> i128 {
+        bad_debt::get_total_shortfall(&env)
+    }
+
+    /// Write off the residual debt of a position with no collateral (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `borrower` - The borrower whose debt is written off
+    /// * `debt_asset` - The asset the debt is denominated in (None = native)
+    pub fn write_off_bad_debt(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+        debt_asset: Option<Address>,
+    ) -> Result<i128, BadDebtError> {
+        bad_debt::write_off_bad_debt(&env, caller, borrower, debt_asset)
+    }
+
+    /// Cover bad debt out of the asset's protocol reserve (admin or treasury)
+    pub fn cover_bad_debt(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, BadDebtError> {
+        bad_debt::cover_bad_debt(&env, caller, asset, amount)
+    }
+
+    /// Burn the asset's protocol reserve against its deficit, up to the
+    /// smaller of the two (admin or treasury)
+    ///
+    /// # Returns
+    /// The amount burned
+    pub fn burn_reserves_for_deficit(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+    ) -> Result<i128, BadDebtError> {
+        bad_debt::burn_reserves_for_deficit(&env, caller, asset)
+    }
+
+    // ============================================================================
+    // Config Simulation Entrypoints
+    // ============================================================================
+
+    /// Dry-run a reserve config change against the current positions
+    ///
+    /// # Arguments
+    /// * `asset` - The reserve to change (None = native)
+    /// * `new_config` - The proposed config, including price
+    ///
+    /// # Returns
+    /// Returns how many healthy accounts would become liquidatable and their value
+    pub fn simulate_config_change(
+        env: Env,
+        asset: Option<Address>,
+        new_config: AssetConfig,
+    ) -> Result<ConfigChangeImpact, ConfigSimulationError> {
+        config_simulation::simulate_config_change(&env, asset, new_config)
+    }
+
+    // ============================================================================
+    // Config Export Entrypoints
+    // ============================================================================
+
+    /// Export the full protocol configuration: reserves, oracles, rates, caps,
+    /// AMM protocols and roles
+    pub fn export_config(env: Env) -> Result<ProtocolConfigExport, ConfigExportError> {
+        config_export::export_config(&env)
+    }
+
+    /// Import a protocol configuration into a fresh deployment (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `config` - The configuration, as returned by `export_config`
+    pub fn import_config(
+        env: Env,
+        caller: Address,
+        config: ProtocolConfigExport,
+    ) -> Result<(), ConfigExportError> {
+        config_export::import_config(&env, caller, config)
+    }
+
+    // ============================================================================
+    // Protocol Init Entrypoints
+    // ============================================================================
+
+    /// Initialize the protocol with its full configuration in one call
+    ///
+    /// Sets the admin, roles, reserves, oracles and AMM settings atomically.
+    /// Rejected once the protocol, or any of its sections, is initialized.
+    ///
+    /// # Arguments
+    /// * `config` - The deployment configuration; `config.admin` must authorize
+    pub fn initialize_protocol(env: Env, config: InitConfig) -> Result<(), ProtocolInitError> {
+        protocol_init::initialize_protocol(&env, config)
+    }
+
+    /// Check whether `initialize_protocol` has completed
+    pub fn is_protocol_initialized(env: Env) -> bool {
+        protocol_init::is_protocol_initialized(&env)
+    }
+
+    // ============================================================================
+    // Shadow Oracle Entrypoints
+    // ============================================================================
+
+    /// Register or replace the shadow oracle of an asset (admin only)
+    ///
+    /// Shadow prices are compared against the primary feed but never used by
+    /// the protocol.
+    pub fn set_shadow_oracle(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        shadow_oracle: Address,
+    ) -> Result<(), ShadowOracleError> {
+        shadow_oracle::set_shadow_oracle(&env, caller, asset, shadow_oracle)
+    }
+
+    /// Remove the shadow oracle of an asset and its stats (admin only)
+    pub fn remove_shadow_oracle(
+        env: Env,
+        caller: Address,
+        asset: Address,
+    ) -> Result<(), ShadowOracleError> {
+        shadow_oracle::remove_shadow_oracle(&env, caller, asset)
+    }
+
+    /// Promote the shadow oracle of an asset to primary oracle (admin only)
+    ///
+    /// # Returns
+    /// Returns the promoted oracle address
+    pub fn promote_shadow_oracle(
+        env: Env,
+        caller: Address,
+        asset: Address,
+    ) -> Result<Address, ShadowOracleError> {
+        shadow_oracle::promote_shadow_oracle(&env, caller, asset)
+    }
+
+    /// Submit a shadow price for comparison against the primary (shadow oracle only)
+    ///
+    /// # Returns
+    /// Returns the deviation from the primary price in basis points
+    pub fn submit_shadow_price(
+        env: Env,
+        oracle: Address,
+        asset: Address,
+        price: i128,
+        decimals: u32,
+    ) -> Result<i128, ShadowOracleError> {
+        shadow_oracle::submit_shadow_price(&env, oracle, asset, price, decimals)
+    }
+
+    /// Get the rolling comparison stats of an asset's shadow feed
+    pub fn get_shadow_oracle_stats(
+        env: Env,
+        asset: Address,
+    ) -> Result<ShadowOracleStats, ShadowOracleError> {
+        shadow_oracle::get_shadow_oracle_stats(&env, asset)
+    }
+
+    // ============================================================================
+    // Health History Entrypoints
+    // ============================================================================
+
+    /// Get a page of a user's health factor snapshots, oldest first
+    ///
+    /// # Arguments
+    /// * `user` - The user address
+    /// * `start` - Index of the first snapshot to return
+    /// * `limit` - Maximum snapshots to return (capped at 50)
+    pub fn get_health_history(
+        env: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<HealthSnapshot> {
+        health_history::get_health_history(&env, &user, start, limit)
+    }
+
+    // ============================================================================
+    // Intent Entrypoints
+    // ============================================================================
+
+    /// Get the next unused nonce of a user's signed intents
+    pub fn get_user_nonce(env: Env, user: Address) -> u64 {
+        intents::get_user_nonce(&env, &user)
+    }
+
+    /// Check whether a user cancelled the intent signed with `nonce`
+    pub fn is_intent_cancelled(env: Env, user: Address, nonce: u64) -> bool {
+        intents::is_intent_cancelled(&env, &user, nonce)
+    }
+
+    /// Cancel a signed intent so it can never execute (user only)
+    ///
+    /// # Arguments
+    /// * `user` - The intent signer
+    /// * `nonce` - The nonce of the intent to invalidate
+    pub fn cancel_intent(env: Env, user: Address, nonce: u64) -> Result<(), IntentError> {
+        intents::cancel_intent(&env, user, nonce)
+    }
+
+    // ============================================================================
+    // Liquidation Grace Entrypoints
+    // ============================================================================
+
+    /// Configure the liquidation grace window of a debt reserve (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `asset` - The debt reserve (None for native XLM)
+    /// * `grace_ledgers` - Length of the grace window in ledgers (0 disables it)
+    /// * `max_debt` - Largest total debt eligible for a grace window
+    pub fn set_liquidation_grace(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        grace_ledgers: u32,
+        max_debt: i128,
+    ) -> Result<(), LiquidationGraceError> {
+        liquidation_grace::set_liquidation_grace(&env, caller, asset, grace_ledgers, max_debt)
+    }
+
+    /// Get the liquidation grace window configuration of a debt reserve
+    pub fn get_liquidation_grace(
+        env: Env,
+        asset: Option<Address>,
+    ) -> Option<LiquidationGraceConfig> {
+        liquidation_grace::get_liquidation_grace(&env, &asset)
+    }
+
+    /// Get the ledger a borrower's running grace window started at, if any
+    pub fn get_grace_started(env: Env, borrower: Address) -> Option<u32> {
+        liquidation_grace::get_grace_started(&env, &borrower)
+    }
+
+    /// Configure the post-incident recovery window (admin only)
+    ///
+    /// After liquidations are unpaused or an oracle recovers from an outage,
+    /// liquidations are blocked for `recovery_ledgers` ledgers.
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `recovery_ledgers` - Length of the recovery window in ledgers (0 disables it)
+    pub fn set_recovery_grace(
+        env: Env,
+        caller: Address,
+        recovery_ledgers: u32,
+    ) -> Result<(), LiquidationGraceError> {
+        liquidation_grace::set_recovery_grace(&env, caller, recovery_ledgers)
+    }
+
+    /// Get the length of the post-incident recovery window in ledgers
+    pub fn get_recovery_grace(env: Env) -> u32 {
+        liquidation_grace::get_recovery_grace(&env)
+    }
+
+    /// Get the ledger until which liquidations touching an asset are blocked
+    pub fn get_recovery_grace_until(env: Env, asset: Option<Address>) -> u32 {
+        liquidation_grace::get_recovery_grace_until(&env, &asset)
+    }
+
+    // ============================================================================
+    // Operator Entrypoints
+    // ============================================================================
+
+    /// Approve an operator to act on the caller's position
+    ///
+    /// Replaces any previous approval of the same operator.
+    ///
+    /// # Arguments
+    /// * `user` - The position owner
+    /// * `operator` - The contract or account being approved
+    /// * `operations` - Approved operations (bit flags: 1 deposit, 2 withdraw,
+    ///   4 borrow, 8 repay)
+    /// * `caps` - Per-asset caps on the amounts the operator may move
+    /// * `expires_at` - Timestamp after which the approval is void
+    pub fn approve_operator(
+        env: Env,
+        user: Address,
+        operator: Address,
+        operations: u32,
+        caps: Vec<OperatorCap>,
+        expires_at: u64,
+    ) -> Result<(), OperatorError> {
+        operators::approve_operator(&env, user, operator, operations, caps, expires_at)
+    }
+
+    /// Revoke an operator's approval (user only)
+    pub fn revoke_operator(
+        env: Env,
+        user: Address,
+        operator: Address,
+    ) -> Result<(), OperatorError> {
+        operators::revoke_operator(&env, user, operator)
+    }
+
+    /// Get a user's approval of an operator
+    pub fn get_operator_approval(
+        env: Env,
+        user: Address,
+        operator: Address,
+    ) -> Option<OperatorApproval> {
+        operators::get_operator_approval(&env, &user, &operator)
+    }
+
+    /// Get all operator approvals of a user, including expired ones
+    pub fn get_operator_approvals(env: Env, user: Address) -> Vec<OperatorApproval> {
+        operators::get_operator_approvals(&env, &user)
+    }
+
+    /// Deposit collateral on behalf of a user, pulled from the user's account
+    ///
+    /// # Arguments
+    /// * `operator` - The approved operator
+    /// * `user` - The position owner
+    /// * `asset` - The collateral asset (None for native XLM)
+    /// * `amount` - The amount to deposit
+    pub fn operator_deposit(
+        env: Env,
+        operator: Address,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, ProtocolError> {
+        operators::consume_approval(
+            &env,
+            &operator,
+            &user,
+            operators::OP_DEPOSIT,
+            &asset,
+            amount,
+        )?;
+        deposit::deposit_collateral(&env, user, asset, amount).map_err(ProtocolError::from)
+    }
+
+    /// Withdraw collateral on behalf of a user, paid to the user
+    ///
+    /// # Arguments
+    /// * `operator` - The approved operator
+    /// * `user` - The position owner
+    /// * `asset` - The collateral asset (None for native XLM)
+    /// * `amount` - The amount to withdraw
+    pub fn operator_withdraw(
+        env: Env,
+        operator: Address,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, ProtocolError> {
+        operators::consume_approval(
+            &env,
+            &operator,
+            &user,
+            operators::OP_WITHDRAW,
+            &asset,
+            amount,
+        )?;
+        withdraw::withdraw_collateral(&env, user, asset, amount).map_err(ProtocolError::from)
+    }
+
+    /// Borrow on behalf of a user, paid to the user
+    ///
+    /// # Arguments
+    /// * `operator` - The approved operator
+    /// * `user` - The position owner
+    /// * `asset` - The asset to borrow (None for native XLM)
+    /// * `amount` - The amount to borrow
+    pub fn operator_borrow(
+        env: Env,
+        operator: Address,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, ProtocolError> {
+        operators::consume_approval(&env, &operator, &user, operators::OP_BORROW, &asset, amount)?;
+        borrow::borrow_asset(&env, user, asset, amount).map_err(ProtocolError::from)
+    }
+
+    /// Repay debt on behalf of a user, pulled from the user's account
+    ///
+    /// # Arguments
+    /// * `operator` - The approved operator
+    /// * `user` - The position owner
+    /// * `asset` - The debt asset (None for native XLM)
+    /// * `amount` - The amount to repay
+    pub fn operator_repay(
+        env: Env,
+        operator: Address,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<(i128, i128, i128), ProtocolError> {
+        operators::consume_approval(&env, &operator, &user, operators::OP_REPAY, &asset, amount)?;
+        repay::repay_debt(&env, user, asset, amount).map_err(ProtocolError::from)
+    }
+
+    // ============================================================================
+    // Rate Statistics Entrypoints
+    // ============================================================================
+
+    /// Configure the rate statistics averaging windows (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `windows` - Window lengths in seconds (1d/7d/30d by default)
+    pub fn set_rate_windows(
+        env: Env,
+        caller: Address,
+        windows: Vec<u64>,
+    ) -> Result<(), RateStatsError> {
+        rate_stats::set_rate_windows(&env, caller, windows)
+    }
+
+    /// Get the configured rate statistics windows in seconds
+    pub fn get_rate_windows(env: Env) -> Vec<u64> {
+        rate_stats::get_rate_windows(&env)
+    }
+
+    /// Get the time-weighted utilization and borrow rate of a reserve over a window
+    ///
+    /// # Arguments
+    /// * `asset` - The reserve (None for native XLM)
+    /// * `window` - A configured window length in seconds
+    pub fn get_rate_stats(env: Env, asset: Option<Address>, window: u64) -> Option<RateStats> {
+        rate_stats::get_rate_stats(&env, &asset, window)
+    }
+
+    /// Get the time-weighted utilization and borrow rate of a reserve over every window
+    pub fn get_all_rate_stats(env: Env, asset: Option<Address>) -> Vec<RateStats> {
+        rate_stats::get_all_rate_stats(&env, &asset)
+    }
+
+    // ============================================================================
+    // Supply Interest Entrypoints
+    // ============================================================================
+
+    /// Get a user's collateral including accrued supply interest
+    ///
+    /// # Arguments
+    /// * `user` - The address of the user
+    ///
+    /// # Returns
+    /// The collateral principal plus supply interest not yet settled
+    pub fn get_user_supply_balance(env: Env, user: Address) -> i128 {
+        supply_interest::get_user_supply_balance(&env, &user)
+    }
+
+    /// Get the cumulative supply index (scaled by 1e18)
+    pub fn get_supply_index(env: Env) -> i128 {
+        supply_interest::get_supply_index(&env)
+    }
+
+    // ============================================================================
+    // Interest Index Entrypoints
+    // ============================================================================
+
+    /// Advance the cumulative borrow and supply indexes to the current time
+    ///
+    /// Core positions share one pool, so there is a single pair of indexes.
+    /// Every deposit, withdrawal, borrow, repayment and liquidation already
+    /// accrues; this lets keepers accrue during quiet periods.
+    ///
+    /// # Returns
+    /// The updated indexes
+    pub fn accrue_interest(env: Env) -> InterestIndexes {
+        interest_index::accrue_interest(&env)
+    }
+
+    /// Get the cumulative borrow and supply indexes as of the current time
+    pub fn get_interest_indexes(env: Env) -> InterestIndexes {
+        interest_index::get_interest_indexes(&env)
+    }
+
+    /// Get a user's total debt, including interest accrued since their last interaction
+    ///
+    /// # Arguments
+    /// * `user` - The address of the user
+    ///
+    /// # Returns
+    /// Principal plus accrued interest
+    pub fn get_user_debt(env: Env, user: Address) -> i128 {
+        interest_index::get_user_debt(&env, &user)
+    }
+
+    // ============================================================================
+    // Price Oracle Entrypoints
+    // ============================================================================
+
+    /// Read a reserve's price from a SEP-40 price feed (admin only)
+    ///
+    /// Health factors and liquidations use the feed's price instead of the
+    /// price stored by `update_asset_price` from then on.
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `asset` - The reserve (None for native XLM)
+    /// * `feed` - The SEP-40 feed contract (e.g. Reflector)
+    /// * `feed_asset` - The reserve's asset as named by the feed
+    pub fn set_price_feed(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        feed: Address,
+        feed_asset: Sep40Asset,
+    ) -> Result<(), PriceOracleError> {
+        price_oracle::set_price_feed(&env, caller, asset, feed, feed_asset)
     }
 
-    /// Update price feed from oracle
-    pub fn update_price_feed(
+    /// Stop reading a reserve's price from a price feed (admin only)
+    pub fn remove_price_feed(
         env: Env,
         caller: Address,
-        asset: Address,
-        price: i128,
-        decimals: u32,
-        oracle: Address,
-    ) -> i128 {
-        oracle::update_price_feed(&env, caller, asset, price, decimals, oracle)
-            .expect("Oracle error")
+        asset: Option<Address>,
+    ) -> Result<(), PriceOracleError> {
+        price_oracle::remove_price_feed(&env, caller, asset)
     }
 
-    /// Get current price for an asset
-    pub fn get_price(env: Env, asset: Address) -> i128 {
-        oracle::get_price(&env, &asset).expect("Oracle error")
+    /// Get the price feed configured for a reserve
+    pub fn get_price_feed(env: Env, asset: Option<Address>) -> Option<PriceFeedSource> {
+        price_oracle::get_price_feed(&env, &asset)
     }
 
-    /// Configure oracle parameters (admin only)
-    pub fn configure_oracle(
-        env: Env,
-        caller: Address,
-        config: OracleConfig,
-    ) {
-        oracle::configure_oracle(&env, caller, config).expect("Oracle error")
+    /// Fetch a reserve's price from its feed, bypassing the cache
+    ///
+    /// # Returns
+    /// The price with 7 decimals
+    pub fn refresh_asset_price(env: Env, asset: Option<Address>) -> Result<i128, PriceOracleError> {
+        price_oracle::refresh_price(&env, &asset)
     }
 
-    /// Set primary oracle for an asset (admin only)
+    /// Set the oldest price, in seconds, the protocol acts on for a reserve (admin only)
+    ///
+    /// Health factors and liquidations involving the reserve fail with a
+    /// stale-price error once its latest price is older than this.
     ///
     /// # Arguments
-    /// * `caller` - The caller address (must be admin)
-    /// * `asset` - The asset address
-    /// * `primary_oracle` - The primary oracle address
-    pub fn set_primary_oracle(env: Env, caller: Address, asset: Address, primary_oracle: Address) {
-        set_primary_oracle(&env, caller, asset, primary_oracle)
-            .unwrap_or_else(|e| panic!("Oracle error: {:?}", e))
-    }
-
-    /// Set fallback oracle for an asset (admin only)
-    pub fn set_fallback_oracle(
+    /// * `caller` - The admin address
+    /// * `asset` - The reserve (None for native XLM)
+    /// * `max_age` - Maximum price age in seconds
+    pub fn set_max_price_age(
         env: Env,
         caller: Address,
-        asset: Address,
-        fallback_oracle: Address,
-    ) {
-        oracle::set_fallback_oracle(&env, caller, asset, fallback_oracle).expect("Oracle error")
+        asset: Option<Address>,
+        max_age: u64,
+    ) -> Result<(), PriceOracleError> {
+        price_oracle::set_max_price_age(&env, caller, asset, max_age)
     }
 
-    /// Get recent activity from analytics
-    pub fn get_recent_activity(env: Env, limit: u32, offset: u32) -> Result<Vec<crate::analytics::ActivityEntry>, crate::analytics::AnalyticsError> {
-        analytics::get_recent_activity(&env, limit, offset)
+    /// Get the oldest price, in seconds, the protocol acts on for a reserve
+    pub fn get_max_price_age(env: Env, asset: Option<Address>) -> u64 {
+        price_oracle::get_max_price_age(&env, &asset)
     }
 
-    /// Initialize risk management (admin only)
-    pub fn initialize_risk_management(env: Env, admin: Address) -> Result<(), RiskManagementError> {
-        risk_management::initialize_risk_management(&env, admin)
+    /// Get a reserve's price with 7 decimals, as used for health factors
+    ///
+    /// # Errors
+    /// * `PriceOracleError::FeedNotConfigured` - If the reserve has no feed
+    /// * `PriceOracleError::StalePrice` - If the feed's price is too old
+    pub fn get_asset_price(env: Env, asset: Option<Address>) -> Result<i128, PriceOracleError> {
+        price_oracle::get_feed_price(&env, &asset)
+            .unwrap_or(Err(PriceOracleError::FeedNotConfigured))
     }
 
-    /// Get current risk configuration
-    pub fn get_risk_config(env: Env) -> Option<RiskConfig> {
-        risk_management::get_risk_config(&env)
-    }
+    // ============================================================================
+    // Liquidation Auction Entrypoints
+    // ============================================================================
 
-    /// Set risk management parameters (admin only)
-    pub fn set_risk_params(
-        env: Env, 
-        admin: Address, 
-        min_collateral_ratio: Option<i128>,
-        liquidation_threshold: Option<i128>,
-        close_factor: Option<i128>,
-        liquidation_incentive: Option<i128>,
-    ) -> Result<(), RiskManagementError> {
-        risk_management::set_risk_params(&env, admin, min_collateral_ratio, liquidation_threshold, close_factor, liquidation_incentive)
+    /// Start a Dutch auction of a liquidatable borrower's collateral
+    ///
+    /// Alternative to `liquidate`: the collateral a fixed-bonus liquidation
+    /// would seize is sold at a price falling from the oracle price to the
+    /// full liquidation incentive over the auction duration.
+    ///
+    /// # Returns
+    /// The auction, or None if the insurance fund rescued the position or a
+    /// liquidation grace window was started instead
+    pub fn start_liquidation_auction(
+        env: Env,
+        borrower: Address,
+        debt_asset: Option<Address>,
+        collateral_asset: Option<Address>,
+    ) -> Result<Option<LiquidationAuction>, AuctionError> {
+        auction::start_auction(&env, borrower, debt_asset, collateral_asset)
     }
 
-    /// Set a pause switch for an operation (admin only)
-    pub fn set_pause_switch(env: Env, admin: Address, operation: Symbol, paused: bool) -> Result<(), RiskManagementError> {
-        risk_management::set_pause_switch(&env, admin, operation, paused)
+    /// Repay part of an auction's debt in exchange for collateral at the current price
+    ///
+    /// # Returns
+    /// The collateral paid to the bidder
+    pub fn bid_liquidation_auction(
+        env: Env,
+        bidder: Address,
+        borrower: Address,
+        debt_amount: i128,
+    ) -> Result<i128, AuctionError> {
+        auction::bid(&env, bidder, borrower, debt_amount)
     }
 
-    /// Check if an operation is paused
-    pub fn is_operation_paused(env: Env, operation: Symbol) -> bool {
-        risk_management::is_operation_paused(&env, operation)
+    /// Close a finished auction and return unsold collateral to the borrower
+    ///
+    /// # Returns
+    /// The collateral returned to the borrower
+    pub fn settle_liquidation_auction(env: Env, borrower: Address) -> Result<i128, AuctionError> {
+        auction::settle_auction(&env, borrower)
     }
 
-    /// Check if emergency pause is active
-    pub fn is_emergency_paused(env: Env) -> bool {
-        risk_management::is_emergency_paused(&env)
+    /// Get the running auction of a borrower
+    pub fn get_liquidation_auction(env: Env, borrower: Address) -> Option<LiquidationAuction> {
+        auction::get_auction(&env, &borrower)
     }
 
-    /// Set emergency pause (admin only)
-    pub fn set_emergency_pause(env: Env, admin: Address, paused: bool) -> Result<(), RiskManagementError> {
-        risk_management::set_emergency_pause(&env, admin, paused)
+    /// Get the collateral a bid repaying `debt_amount` would receive now
+    pub fn quote_auction_bid(
+        env: Env,
+        borrower: Address,
+        debt_amount: i128,
+    ) -> Result<i128, AuctionError> {
+        auction::quote_bid(&env, &borrower, debt_amount)
     }
 
-    /// Get user analytics metrics
-    pub fn get_user_analytics(env: Env, user: Address) -> Result<crate::analytics::UserMetrics, crate::analytics::AnalyticsError> {
-        analytics::get_user_activity_summary(&env, &user)
+    /// Set the duration of new liquidation auctions in seconds (admin only)
+    pub fn set_auction_duration(
+        env: Env,
+        caller: Address,
+        duration: u64,
+    ) -> Result<(), AuctionError> {
+        auction::set_auction_duration(&env, caller, duration)
     }
 
-    /// Get protocol analytics metrics
-    pub fn get_protocol_analytics(env: Env) -> Result<crate::analytics::ProtocolMetrics, crate::analytics::AnalyticsError> {
-        analytics::get_protocol_stats(&env)
+    /// Get the duration of new liquidation auctions in seconds
+    pub fn get_auction_duration(env: Env) -> u64 {
+        auction::get_auction_duration(&env)
     }
 
-    /// Initialize AMM settings (admin only)
-    pub fn initialize_amm(
-        env: Env,
-        admin: Address,
-        default_slippage: i128,
-        max_slippage: i128,
-        auto_swap_threshold: i128,
-    ) -> Result<(), AmmError> {
-        initialize_amm(
-            env,
-            admin,
-            default_slippage,
-            max_slippage,
-            auto_swap_threshold,
-        )
-    }
+    // ============================================================================
+    // Liquidation Index Entrypoints
+    // ============================================================================
 
-    /// Set AMM pool configuration (admin only)
-    pub fn set_amm_pool(
+    /// Get a page of liquidatable core positions
+    ///
+    /// # Arguments
+    /// * `cursor` - Registry slot to start at (0 for the first page)
+    /// * `limit` - Registry slots to inspect (0 = the maximum)
+    ///
+    /// # Returns
+    /// The liquidatable positions among the inspected slots and the cursor of
+    /// the next page
+    pub fn get_liquidatable_positions(
         env: Env,
-        admin: Address,
-        protocol_config: AmmProtocolConfig,
-    ) -> Result<(), AmmError> {
-        set_amm_pool(env, admin, protocol_config)
+        cursor: u32,
+        limit: u32,
+    ) -> LiquidatablePositionsPage {
+        liquidation_index::get_liquidatable_positions(&env, cursor, limit)
     }
 
-    /// Execute swap through AMM
-    pub fn amm_swap(env: Env, user: Address, params: SwapParams) -> Result<i128, AmmError> {
-        amm_swap(env, user, params)
+    /// Get the number of borrowers with outstanding core debt
+    pub fn get_indexed_borrower_count(env: Env) -> u32 {
+        liquidation_index::get_indexed_borrower_count(&env)
     }
 
-    /// Register a bridge 
+    // ============================================================================
+    // Leverage Entrypoints
+    // ============================================================================
+
+    /// Open a leveraged position in one transaction
+    ///
+    /// Deposits `initial_amount`, then loops borrow, swap through the AMM
+    /// router and deposit until the target leverage is reached, bounded by
+    /// the maximum loan-to-value.
     ///
     /// # Arguments
-    /// * `caller` - Admin address for authorization
-    /// * `network_id` - ID of the remote network
-    /// * `bridge` - Address of the bridge contract
-    /// * `fee_bps` - Fee in basis points
-    pub fn register_bridge(
+    /// * `user` - The user opening the position
+    /// * `collateral_asset` - The asset deposited and bought with borrowed funds
+    /// * `debt_asset` - The asset borrowed
+    /// * `initial_amount` - Collateral pulled from the user (requires approval)
+    /// * `target_leverage` - Target leverage in basis points (20000 = 2x)
+    ///
+    /// # Returns
+    /// Returns a tuple (collateral_added, debt_added, leverage)
+    pub fn open_leveraged_position(
         env: Env,
-        caller: Address,
-        network_id: u32,
-        bridge: Address,
-        fee_bps: i128,
-    ) -> Result<(), BridgeError> {
-        bridge::register_bridge(&env, caller, network_id, bridge, fee_bps)
+        user: Address,
+        collateral_asset: Address,
+        debt_asset: Address,
+        initial_amount: i128,
+        target_leverage: i128,
+    ) -> Result<(i128, i128, i128), ProtocolError> {
+        leverage::open_leveraged_position(
+            &env,
+            user,
+            collateral_asset,
+            debt_asset,
+            initial_amount,
+            target_leverage,
+        )
+        .map_err(ProtocolError::from)
+    }
+
+    /// Get the maximum leverage reachable when borrowing `debt_asset`, in
+    /// basis points
+    pub fn get_max_leverage(env: Env, debt_asset: Address) -> i128 {
+        leverage::get_max_leverage(&env, &debt_asset)
     }
 
-    /// Set bridge fee
-    /// 
+    // ============================================================================
+    // Action Delay Entrypoints
+    // ============================================================================
+
+    /// Configure the delay between a deposit and the next borrow or
+    /// withdrawal of the same account (admin only)
+    ///
+    /// Blunts oracle manipulation financed by flash loans, which needs
+    /// deposit, borrow and withdraw to happen in one transaction.
+    ///
     /// # Arguments
-    /// * `caller` - Admin address for authorization
-    /// * `network_id` - ID of the remote network
-    /// * `fee_bps` - New fee in basis points
-    pub fn set_bridge_fee(
+    /// * `caller` - The caller address (must be admin)
+    /// * `delay_ledgers` - Delay in ledgers (0 disables it, 1 = next ledger)
+    pub fn set_action_delay(
         env: Env,
         caller: Address,
-        network_id: u32,
-        fee_bps: i128,
-    ) -> Result<(), BridgeError> {
-        bridge::set_bridge_fee(&env, caller, network_id, fee_bps)
+        delay_ledgers: u32,
+    ) -> Result<(), ActionDelayError> {
+        action_delay::set_action_delay(&env, caller, delay_ledgers)
     }
 
-    /// Deposit through a bridge
+    /// Get the delay between a deposit and the next borrow or withdrawal
+    pub fn get_action_delay(env: Env) -> u32 {
+        action_delay::get_action_delay(&env)
+    }
+
+    /// Get the first ledger at which a user may borrow or withdraw again
+    /// (0 if not delayed)
+    pub fn get_action_unlock_ledger(env: Env, user: Address) -> u32 {
+        action_delay::get_action_unlock_ledger(&env, &user)
+    }
+
+    // ============================================================================
+    // Credit Delegation Entrypoints
+    // ============================================================================
+
+    /// Approve a borrowing allowance against the caller's collateral
     ///
     /// # Arguments
-    /// * `user` - User depositing collateral
-    /// * `network_id` - Remote network ID
-    /// * `asset` - Asset to deposit
-    /// * `amount` - Amount to deposit
-    pub fn bridge_deposit(
+    /// * `delegator` - The depositor extending credit (must authorize)
+    /// * `delegatee` - The address allowed to borrow
+    /// * `asset` - The asset that may be borrowed (None for native XLM)
+    /// * `amount` - The allowance (0 revokes it)
+    pub fn approve_credit(
         env: Env,
-        user: Address,
-        network_id: u32,
+        delegator: Address,
+        delegatee: Address,
         asset: Option<Address>,
         amount: i128,
-    ) -> Result<i128, BridgeError> {
-        bridge::bridge_deposit(&env, user, network_id, asset, amount)
+    ) -> Result<(), CreditDelegationError> {
+        credit_delegation::approve_credit(&env, delegator, delegatee, asset, amount)
     }
 
-    /// Withdraw through a bridge
+    /// Borrow against a delegator's collateral within the approved allowance
+    ///
+    /// The debt is booked on the delegator's position and the funds are paid
+    /// to the delegatee.
     ///
     /// # Arguments
-    /// * `user` - User withdrawing collateral
-    /// * `network_id` - Remote network ID
-    /// * `asset` - Asset to withdraw
-    /// * `amount` - Amount to withdraw
-    pub fn bridge_withdraw(
+    /// * `delegatee` - The borrower of the funds (must authorize)
+    /// * `delegator` - The account whose position takes on the debt
+    /// * `asset` - The asset to borrow (None for native XLM)
+    /// * `amount` - The amount to borrow
+    ///
+    /// # Returns
+    /// Returns the delegator's total debt after the borrow
+    pub fn borrow_with_credit(
         env: Env,
-        user: Address,
-        network_id: u32,
+        delegatee: Address,
+        delegator: Address,
         asset: Option<Address>,
         amount: i128,
-    ) -> Result<i128, BridgeError> {
-        bridge::bridge_withdraw(&env, user, network_id, asset, amount)
+    ) -> Result<i128, CreditDelegationError> {
+        credit_delegation::borrow_with_credit(&env, delegatee, delegator, asset, amount)
     }
 
-    /// List all bridges
-    pub fn list_bridges(env: Env) -> Map<u32, BridgeConfig> {
-        bridge::list_bridges(&env)
+    /// Get the remaining credit a delegator has extended to a delegatee
+    pub fn get_credit_allowance(
+        env: Env,
+        delegator: Address,
+        delegatee: Address,
+        asset: Option<Address>,
+    ) -> i128 {
+        credit_delegation::get_credit_allowance(&env, &delegator, &delegatee, asset)
     }
 
-    /// Get configuration of a specific bridge
-    pub fn get_bridge_config(env: Env, network_id: u32) -> Result<BridgeConfig, BridgeError> {
-        bridge::get_bridge_config(&env, network_id)
-    }
+    // ============================================================================
+    // Supply Token Entrypoints
+    // ============================================================================
 
-    /// Set a configuration value (admin only)
+    /// Register the supply token (sToken) of an asset (admin only)
     ///
     /// # Arguments
-    /// * `caller` - The caller address (must be admin)
-    /// * `key` - The configuration key
-    /// * `value` - The configuration value
-    ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    pub fn config_set(
+    /// * `caller` - The admin address
+    /// * `asset` - The reserve asset (None for native XLM)
+    /// * `token` - The supply token contract, bound to this pool and `asset`
+    pub fn set_supply_token(
         env: Env,
         caller: Address,
-        key: soroban_sdk::Symbol,
-        value: soroban_sdk::Val,
-    ) -> Result<(), ConfigError> {
-        config_set(&env, caller, key, value)
+        asset: Option<Address>,
+        token: Address,
+    ) -> Result<(), SupplyTokenError> {
+        supply_token::set_supply_token(&env, caller, asset, token)
     }
 
-    /// Get a configuration value
+    /// Get the supply token registered for an asset
+    pub fn get_supply_token(env: Env, asset: Option<Address>) -> Option<Address> {
+        supply_token::get_supply_token(&env, asset)
+    }
+
+    /// Supplied balance of a user in an asset (read by the supply token)
+    pub fn supply_token_balance(env: Env, asset: Option<Address>, user: Address) -> i128 {
+        supply_token::supply_token_balance(&env, asset, &user)
+    }
+
+    /// Move supplied balance between holders (callable by the supply token only)
     ///
     /// # Arguments
-    /// * `key` - The configuration key
+    /// * `asset` - The reserve asset (None for native XLM)
+    /// * `from` - The sender
+    /// * `to` - The recipient
+    /// * `amount` - The amount of supply to move
+    pub fn supply_token_transfer(
+        env: Env,
+        asset: Option<Address>,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), SupplyTokenError> {
+        supply_token::supply_token_transfer(&env, asset, from, to, amount)
+    }
+
+    /// Get the underlying amount one supply token unit redeems for
     ///
     /// # Returns
-    /// Returns Some(value) if the key exists, None otherwise
-    pub fn config_get(env: Env, key: soroban_sdk::Symbol) -> Option<soroban_sdk::Val> {
-        config_get(&env, key)
+    /// The exchange rate, scaled by 10_000_000
+    pub fn get_exchange_rate(env: Env, asset: Option<Address>) -> Result<i128, SupplyTokenError> {
+        supply_token::get_exchange_rate(&env, asset)
     }
 
-    /// Backup configuration parameters (admin only)
+    /// Redeem supply tokens for the underlying asset
     ///
     /// # Arguments
-    /// * `caller` - The caller address (must be admin)
-    /// * `keys` - A vector of configuration keys to backup
+    /// * `user` - The holder redeeming
+    /// * `asset` - The reserve asset (None for native XLM)
+    /// * `stoken_amount` - The amount of supply tokens to redeem
     ///
     /// # Returns
-    /// Returns a vector of key-value pairs representing the backup
-    pub fn config_backup(
+    /// The underlying amount withdrawn
+    pub fn redeem(
         env: Env,
-        caller: Address,
-        keys: soroban_sdk::Vec<soroban_sdk::Symbol>,
-    ) -> Result<soroban_sdk::Vec<(soroban_sdk::Symbol, soroban_sdk::Val)>, ConfigError> {
-        config_backup(&env, caller, keys)
+        user: Address,
+        asset: Option<Address>,
+        stoken_amount: i128,
+    ) -> Result<i128, SupplyTokenError> {
+        supply_token::redeem(&env, user, asset, stoken_amount)
     }
 
-    /// Restore configuration parameters (admin only)
+    // ============================================================================
+    // Permit Entrypoints
+    // ============================================================================
+
+    /// Register or replace the ed25519 key that signs the caller's permits
     ///
     /// # Arguments
-    /// * `caller` - The caller address (must be admin)
-    /// * `backup` - A vector of key-value pairs to restore
+    /// * `user` - The position owner
+    /// * `public_key` - The ed25519 public key
+    pub fn set_permit_signer(env: Env, user: Address, public_key: BytesN<32>) {
+        permit::set_permit_signer(&env, user, public_key)
+    }
+
+    /// Get the key that signs a user's permits
+    pub fn get_permit_signer(env: Env, user: Address) -> Option<BytesN<32>> {
+        permit::get_permit_signer(&env, &user)
+    }
+
+    /// Get the nonce a user's next permit must carry
+    pub fn get_permit_nonce(env: Env, user: Address) -> u64 {
+        permit::get_permit_nonce(&env, &user)
+    }
+
+    /// Get the digest a user signs to authorize a permit
+    pub fn get_permit_digest(env: Env, permit: Permit) -> BytesN<32> {
+        permit::get_permit_digest(&env, &permit)
+    }
+
+    /// Execute a deposit, withdrawal or repayment signed off-chain by its user
+    ///
+    /// Any account may submit the permit and pay the fee.
+    ///
+    /// # Arguments
+    /// * `permit` - The signed operation
+    /// * `signature` - The user's ed25519 signature of the permit digest
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn config_restore(
+    /// The user's collateral after a deposit or withdrawal, or the remaining
+    /// debt after a repayment
+    pub fn execute_permit(
         env: Env,
-        caller: Address,
-        backup: soroban_sdk::Vec<(soroban_sdk::Symbol, soroban_sdk::Val)>,
-    ) -> Result<(), ConfigError> {
-        config_restore(&env, caller, backup)
+        permit: Permit,
+        signature: BytesN<64>,
+    ) -> Result<i128, PermitError> {
+        permit::execute_permit(&env, permit, signature)
     }
 
     // ============================================================================
-    // Cross-Asset / Asset Configuration Methods
+    // Position Token Entrypoints
     // ============================================================================
 
-    /// Initialize cross-asset lending module (admin only)
-    ///
-    /// Sets up the cross-asset lending system with an admin.
-    /// Must be called before any asset configuration.
+    /// Wrap the caller's whole position into a transferable position token
     ///
     /// # Arguments
-    /// * `admin` - The admin address
+    /// * `user` - The position owner
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn initialize_ca(env: Env, admin: Address) -> Result<(), CrossAssetError> {
-        initialize(&env, admin)
+    /// Returns the id of the new token
+    pub fn wrap_position(env: Env, user: Address) -> Result<u64, PositionTokenError> {
+        position_token::wrap_position(&env, user)
+    }
+
+    /// Burn a position token, leaving the position with its owner
+    pub fn unwrap_position(
+        env: Env,
+        owner: Address,
+        token_id: u64,
+    ) -> Result<(), PositionTokenError> {
+        position_token::unwrap_position(&env, owner, token_id)
+    }
+
+    /// Transfer a position token with all collateral and debt behind it
+    ///
+    /// # Arguments
+    /// * `from` - The token owner
+    /// * `to` - The new owner, who assumes the debt (must authorize)
+    /// * `token_id` - The position token
+    pub fn transfer_position(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_id: u64,
+    ) -> Result<(), PositionTokenError> {
+        position_token::transfer_position(&env, from, to, token_id)
+    }
+
+    /// Get the owner of a position token
+    pub fn get_position_token_owner(env: Env, token_id: u64) -> Option<Address> {
+        position_token::get_position_token_owner(&env, token_id)
+    }
+
+    /// Get the position token owned by an account
+    pub fn get_position_token(env: Env, user: Address) -> Option<u64> {
+        position_token::get_position_token(&env, &user)
+    }
+
+    // ============================================================================
+    // Position Migration Entrypoints
+    // ============================================================================
+
+    /// Set the upgraded pool positions may migrate to (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `new_pool` - The upgraded pool (None disables migration)
+    pub fn set_migration_target(
+        env: Env,
+        caller: Address,
+        new_pool: Option<Address>,
+    ) -> Result<(), MigrationError> {
+        migration::set_migration_target(&env, caller, new_pool)
+    }
+
+    /// Get the upgraded pool positions may migrate to
+    pub fn get_migration_target(env: Env) -> Option<Address> {
+        migration::get_migration_target(&env)
+    }
+
+    /// Accept or stop accepting positions migrated from a pool (admin only)
+    pub fn set_migration_source(
+        env: Env,
+        caller: Address,
+        source: Address,
+        allowed: bool,
+    ) -> Result<(), MigrationError> {
+        migration::set_migration_source(&env, caller, source, allowed)
     }
 
-    /// Initialize/register a new asset with configuration
-    ///
-    /// Registers an asset with its collateral factor (LTV), liquidation threshold,
-    /// debt ceiling, and other parameters. Admin only.
+    /// Whether positions are accepted from a pool
+    pub fn is_migration_source(env: Env, source: Address) -> bool {
+        migration::is_migration_source(&env, &source)
+    }
+
+    /// Move the caller's collateral and debt to the upgraded pool
     ///
     /// # Arguments
-    /// * `asset` - Asset address (None for native XLM)
-    /// * `config` - Complete asset configuration
+    /// * `user` - The position owner
+    /// * `new_pool` - The configured migration target
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn initialize_asset(
+    /// Returns a tuple (collateral, debt) moved
+    pub fn migrate_position(
         env: Env,
-        asset: Option<Address>,
-        config: AssetConfig,
-    ) -> Result<(), CrossAssetError> {
-        initialize_asset(&env, asset, config)
+        user: Address,
+        new_pool: Address,
+    ) -> Result<(i128, i128), MigrationError> {
+        migration::migrate_position(&env, user, new_pool)
     }
 
-    /// Update asset configuration (admin only)
+    /// Re-create a position migrated from another pool
     ///
-    /// Updates asset parameters including LTV, liquidation threshold, debt ceiling,
-    /// and collateral/borrow enablement. Only provided fields are updated.
+    /// Called by the source pool during `migrate_position`.
+    pub fn receive_migrated_position(
+        env: Env,
+        source: Address,
+        user: Address,
+        collateral: i128,
+        debt: i128,
+        borrow_interest: i128,
+    ) -> Result<(), MigrationError> {
+        migration::receive_migrated_position(&env, source, user, collateral, debt, borrow_interest)
+    }
+
+    // ============================================================================
+    // Rewards Entrypoints
+    // ============================================================================
+
+    /// Fund and set a reserve's emission schedule for a reward token (admin only)
     ///
     /// # Arguments
-    /// * `asset` - Asset address (None for XLM)
-    /// * `collateral_factor` - Optional new LTV in basis points
-    /// * `liquidation_threshold` - Optional new liquidation threshold in basis points
-    /// * `max_supply` - Optional new supply cap
-    /// * `max_borrow` - Optional new debt ceiling
-    /// * `can_collateralize` - Optional flag to enable/disable as collateral
-    /// * `can_borrow` - Optional flag to enable/disable borrowing
-    ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    #[allow(clippy::too_many_arguments)]
-    pub fn update_asset_config(
+    /// * `caller` - The admin address, funding the schedule
+    /// * `asset` - The reserve (None for native XLM)
+    /// * `reward_token` - The token the schedule pays out
+    /// * `supply_per_second` - Rewards per second for the reserve's suppliers
+    /// * `borrow_per_second` - Rewards per second for the reserve's borrowers
+    /// * `duration` - Seconds the schedule runs from now
+    pub fn set_reward_emission(
         env: Env,
+        caller: Address,
         asset: Option<Address>,
-        collateral_factor: Option<i128>,
-        liquidation_threshold: Option<i128>,
-        max_supply: Option<i128>,
-        max_borrow: Option<i128>,
-        can_collateralize: Option<bool>,
-        can_borrow: Option<bool>,
-    ) -> Result<(), CrossAssetError> {
-        update_asset_config(
+        reward_token: Address,
+        supply_per_second: i128,
+        borrow_per_second: i128,
+        duration: u64,
+    ) -> Result<RewardEmission, RewardsError> {
+        rewards::set_reward_emission(
             &env,
+            caller,
             asset,
-            collateral_factor,
-            liquidation_threshold,
-            max_supply,
-            max_borrow,
-            can_collateralize,
-            can_borrow,
+            reward_token,
+            supply_per_second,
+            borrow_per_second,
+            duration,
         )
     }
 
-    /// Update asset price (admin/oracle only)
-    ///
-    /// Updates the price for an asset used in health factor calculations.
-    ///
-    /// # Arguments
-    /// * `asset` - Asset address (None for XLM)
-    /// * `price` - New price in base units (7 decimals)
-    ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    pub fn update_asset_price(
+    /// Get the reward tokens a reserve streams or has streamed
+    pub fn get_reward_tokens(env: Env, asset: Option<Address>) -> Vec<Address> {
+        rewards::get_reward_tokens(&env, &asset)
+    }
+
+    /// Get a reserve's emission schedule for a reward token
+    pub fn get_reward_emission(
         env: Env,
         asset: Option<Address>,
-        price: i128,
-    ) -> Result<(), CrossAssetError> {
-        update_asset_price(&env, asset, price)
+        reward_token: Address,
+    ) -> Option<RewardEmission> {
+        rewards::get_reward_emission(&env, &asset, &reward_token)
     }
 
-    /// Get asset configuration
-    ///
-    /// Returns the configuration for a specific asset including LTV,
-    /// liquidation threshold, debt ceiling, etc.
-    ///
-    /// # Arguments
-    /// * `asset` - Asset address (None for XLM)
-    ///
-    /// # Returns
-    /// Asset configuration
-    pub fn get_asset_config(
+    /// Get a reserve's reward indexes for a reward token as of now
+    pub fn get_reward_indexes(
         env: Env,
         asset: Option<Address>,
-    ) -> Result<AssetConfig, CrossAssetError> {
-        get_asset_config_by_address(&env, asset)
+        reward_token: Address,
+    ) -> RewardIndexes {
+        rewards::get_reward_indexes(&env, &asset, &reward_token)
     }
 
-    /// Get list of all configured assets
-    ///
-    /// Returns all assets that have been registered in the system.
-    ///
-    /// # Returns
-    /// Vector of asset keys
-    pub fn get_asset_list(env: Env) -> soroban_sdk::Vec<AssetKey> {
-        get_asset_list(&env)
+    /// Get the rewards a user can claim from the given reserves, per reward token
+    pub fn get_claimable_rewards(
+        env: Env,
+        user: Address,
+        assets: Vec<Option<Address>>,
+    ) -> Map<Address, i128> {
+        rewards::get_claimable_rewards(&env, &user, &assets)
     }
 
-    /// Deposit collateral for cross-asset lending
-    ///
-    /// Deposits collateral that can be used across multiple assets.
-    /// Asset must be enabled for collateral.
+    /// Get the rewards a user can claim from every reserve, per reward token
+    pub fn get_pending_rewards(env: Env, user: Address) -> Map<Address, i128> {
+        rewards::get_pending_rewards(&env, &user)
+    }
+
+    /// Claim a user's rewards from the given reserves
     ///
     /// # Arguments
-    /// * `user` - User address
-    /// * `asset` - Asset address (None for XLM)
-    /// * `amount` - Amount to deposit
+    /// * `user` - The reward earner
+    /// * `assets` - The reserves to claim from (None for native XLM)
+    /// * `to` - The recipient of the rewards
     ///
     /// # Returns
-    /// Updated asset position
-    pub fn cross_asset_deposit(
+    /// Returns the amount paid per reward token
+    pub fn claim_rewards(
         env: Env,
         user: Address,
-        asset: Option<Address>,
-        amount: i128,
-    ) -> Result<AssetPosition, CrossAssetError> {
-        cross_asset_deposit(&env, user, asset, amount)
+        assets: Vec<Option<Address>>,
+        to: Address,
+    ) -> Result<Map<Address, i128>, RewardsError> {
+        rewards::claim_rewards(&env, user, assets, to)
     }
 
-    /// Withdraw collateral from cross-asset lending
+    /// Get a user's reward boost in a reserve
     ///
-    /// Withdraws collateral while maintaining healthy position.
+    /// # Returns
+    /// Returns a tuple (supply boost, borrow boost) in basis points
+    pub fn get_reward_boost(env: Env, user: Address, asset: Option<Address>) -> (i128, i128) {
+        rewards::get_reward_boost(&env, &user, &asset)
+    }
+
+    /// Recompute a user's reward boost in every reserve
+    pub fn refresh_boosts(env: Env, user: Address) {
+        rewards::refresh_boosts(&env, &user)
+    }
+
+    // ============================================================================
+    // Staking Entrypoints
+    // ============================================================================
+
+    /// Lock governance tokens to boost lending rewards, or top up and extend a lock
     ///
     /// # Arguments
-    /// * `user` - User address
-    /// * `asset` - Asset address (None for XLM)
-    /// * `amount` - Amount to withdraw
+    /// * `user` - The staker
+    /// * `amount` - Governance tokens to add to the lock
+    /// * `unlock_time` - Timestamp the lock ends at
     ///
     /// # Returns
-    /// Updated asset position
-    pub fn cross_asset_withdraw(
+    /// Returns the updated lock
+    pub fn lock_stake(
         env: Env,
         user: Address,
-        asset: Option<Address>,
         amount: i128,
-    ) -> Result<AssetPosition, CrossAssetError> {
-        cross_asset_withdraw(&env, user, asset, amount)
+        unlock_time: u64,
+    ) -> Result<StakeLock, StakingError> {
+        staking::lock_stake(&env, user, amount, unlock_time)
     }
 
-    /// Borrow asset in cross-asset lending
-    ///
-    /// Borrows against cross-asset collateral. Respects LTV and debt ceiling.
+    /// Withdraw a user's locked governance tokens after the unlock time
+    pub fn unlock_stake(env: Env, user: Address) -> Result<i128, StakingError> {
+        staking::unlock_stake(&env, user)
+    }
+
+    /// Get a user's locked stake
+    pub fn get_stake(env: Env, user: Address) -> Option<StakeLock> {
+        staking::get_stake(&env, &user)
+    }
+
+    /// Get the sum of the weights of all locks
+    pub fn get_total_stake_weight(env: Env) -> i128 {
+        staking::get_total_stake_weight(&env)
+    }
+
+    // ============================================================================
+    // Safety Module Entrypoints
+    // ============================================================================
+
+    /// Set the safety module slash cap, cooldown and auto-slash switch (admin only)
+    pub fn set_safety_module_config(
+        env: Env,
+        caller: Address,
+        config: SafetyModuleConfig,
+    ) -> Result<(), SafetyModuleError> {
+        safety_module::set_safety_module_config(&env, caller, config)
+    }
+
+    /// Get the safety module parameters
+    pub fn get_safety_module_config(env: Env) -> SafetyModuleConfig {
+        safety_module::get_safety_module_config(&env)
+    }
+
+    /// Stake protocol tokens in the safety module
     ///
-    /// # Arguments
-    /// * `user` - User address
-    /// * `asset` - Asset address (None for XLM)
-    /// * `amount` - Amount to borrow
+    /// # Returns
+    /// Returns the shares issued
+    pub fn stake_safety(env: Env, user: Address, amount: i128) -> Result<i128, SafetyModuleError> {
+        safety_module::stake_safety(&env, user, amount)
+    }
+
+    /// Start the cooldown required before withdrawing from the safety module
+    pub fn start_safety_cooldown(env: Env, user: Address) -> Result<u64, SafetyModuleError> {
+        safety_module::start_safety_cooldown(&env, user)
+    }
+
+    /// Redeem safety module shares for protocol tokens after the cooldown
     ///
     /// # Returns
-    /// Updated asset position
-    pub fn cross_asset_borrow(
+    /// Returns the protocol tokens returned
+    pub fn unstake_safety(
         env: Env,
         user: Address,
-        asset: Option<Address>,
-        amount: i128,
-    ) -> Result<AssetPosition, CrossAssetError> {
-        cross_asset_borrow(&env, user, asset, amount)
+        shares: i128,
+    ) -> Result<i128, SafetyModuleError> {
+        safety_module::unstake_safety(&env, user, shares)
     }
 
-    /// Repay borrowed asset
-    ///
-    /// Repays debt for a specific asset.
+    /// Slash the safety module to cover an asset's deficit (admin or treasury)
     ///
     /// # Arguments
-    /// * `user` - User address
-    /// * `asset` - Asset address (None for XLM)
-    /// * `amount` - Amount to repay
+    /// * `caller` - The admin or treasury address
+    /// * `asset` - The asset whose deficit is covered (None = native)
+    /// * `amount` - Protocol tokens to slash, sent to the treasury
+    /// * `covered` - Deficit the slashed tokens cover
     ///
     /// # Returns
-    /// Updated asset position
-    pub fn cross_asset_repay(
+    /// Returns the asset's remaining bad debt
+    pub fn slash_safety_module(
         env: Env,
-        user: Address,
+        caller: Address,
         asset: Option<Address>,
         amount: i128,
-    ) -> Result<AssetPosition, CrossAssetError> {
-        cross_asset_repay(&env, user, asset, amount)
+        covered: i128,
+    ) -> Result<i128, SafetyModuleError> {
+        safety_module::slash_safety_module(&env, caller, asset, amount, covered)
     }
 
-    /// Get user's position for a specific asset
-    ///
-    /// Returns collateral and debt for a user in a specific asset.
-    ///
-    /// # Arguments
-    /// * `user` - User address
-    /// * `asset` - Asset address (None for XLM)
+    /// Slash the safety module against an asset's deficit at oracle prices
+    /// (anyone, while auto-slash is enabled)
     ///
     /// # Returns
-    /// Asset position
-    pub fn get_user_asset_position(
+    /// Returns the asset's remaining bad debt
+    pub fn trigger_safety_slash(
         env: Env,
-        user: Address,
         asset: Option<Address>,
-    ) -> AssetPosition {
-        get_user_asset_position(&env, &user, asset)
+    ) -> Result<i128, SafetyModuleError> {
+        safety_module::trigger_safety_slash(&env, asset)
     }
 
-    /// Get user's unified position summary across all assets
-    ///
-    /// Calculates health factor, liquidation status, and borrow capacity
-    /// across all assets.
-    ///
-    /// # Arguments
-    /// * `user` - User address
+    /// Pay safety module stakers a yield out of a protocol reserve (admin or treasury)
+    pub fn pay_safety_yield(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<(), SafetyModuleError> {
+        safety_module::pay_safety_yield(&env, caller, asset, amount)
+    }
+
+    /// Claim a staker's safety module yield in an asset
     ///
     /// # Returns
-    /// Position summary with health factor
-    pub fn get_user_position_summary(
+    /// Returns the amount claimed
+    pub fn claim_safety_yield(
         env: Env,
         user: Address,
-    ) -> Result<UserPositionSummary, CrossAssetError> {
-        get_user_position_summary(&env, &user)
+        asset: Option<Address>,
+    ) -> Result<i128, SafetyModuleError> {
+        safety_module::claim_safety_yield(&env, user, asset)
+    }
+
+    /// Get the protocol tokens held by the safety module
+    pub fn get_safety_fund(env: Env) -> i128 {
+        safety_module::get_safety_fund(&env)
+    }
+
+    /// Get a staker's safety module shares
+    pub fn get_safety_shares(env: Env, user: Address) -> i128 {
+        safety_module::get_safety_shares(&env, &user)
+    }
+
+    /// Get the protocol tokens a staker's safety module shares are worth
+    pub fn get_safety_balance(env: Env, user: Address) -> i128 {
+        safety_module::get_safety_balance(&env, &user)
+    }
+
+    /// Get when a staker started their safety module cooldown, if they have
+    pub fn get_safety_cooldown(env: Env, user: Address) -> Option<u64> {
+        safety_module::get_safety_cooldown(&env, &user)
+    }
+
+    /// Get the largest safety module slash currently allowed
+    pub fn get_safety_slash_cap(env: Env) -> i128 {
+        safety_module::get_safety_slash_cap(&env)
+    }
+
+    /// Get a staker's unclaimed safety module yield in an asset
+    pub fn get_safety_yield(env: Env, user: Address, asset: Option<Address>) -> i128 {
+        safety_module::get_safety_yield(&env, &user, &asset)
     }
 
     // ============================================================================
-    // Governance Entrypoints
+    // Treasury Entrypoints
     // ============================================================================
 
-    /// Initialize governance module
-    ///
-    /// Sets up the governance system with voting token and parameters.
-    /// Must be called after contract initialization.
-    ///
-    /// # Arguments
-    /// * `admin` - The admin address
-    /// * `vote_token` - Token contract address used for voting
-    /// * `voting_period` - Optional voting duration in seconds
-    /// * `execution_delay` - Optional delay before execution in seconds
-    /// * `quorum_bps` - Optional quorum requirement in basis points
-    /// * `proposal_threshold` - Optional minimum tokens to create proposal
-    /// * `timelock_duration` - Optional timelock duration in seconds
-    /// * `default_voting_threshold` - Optional default voting threshold in basis points
-    ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_initialize(
-        env: Env,
-        admin: Address,
-        vote_token: Address,
-        voting_period: Option<u64>,
-        execution_delay: Option<u64>,
-        quorum_bps: Option<u32>,
-        proposal_threshold: Option<i128>,
-        timelock_duration: Option<u64>,
-        default_voting_threshold: Option<i128>,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::initialize(
-            &env,
-            admin,
-            vote_token,
-            voting_period,
-            execution_delay,
-            quorum_bps,
-            proposal_threshold,
-            timelock_duration,
-            default_voting_threshold,
-        )
+    /// Get an asset's cumulative protocol revenue by source
+    pub fn get_revenue_breakdown(env: Env, asset: Option<Address>) -> RevenueBreakdown {
+        treasury::get_revenue_breakdown(&env, &asset)
     }
-
-    /// Create a new governance proposal
-    ///
-    /// # Arguments
-    /// * `proposer` - Address creating the proposal
-    /// * `proposal_type` - Type of proposal (parameter change, pause, etc.)
-    /// * `description` - Description of the proposal
-    /// * `voting_threshold` - Optional custom voting threshold
-    ///
-    /// # Returns
-    /// Returns the new proposal ID
-    pub fn gov_create_proposal(
-        env: Env,
-        proposer: Address,
-        proposal_type: ProposalType,
-        description: String,
-        voting_threshold: Option<i128>,
-    ) -> Result<u64, errors::GovernanceError> {
-        governance::create_proposal(&env, proposer, proposal_type, description, voting_threshold)
+
+    /// Get the assets that have earned protocol revenue
+    pub fn get_revenue_assets(env: Env) -> Vec<Option<Address>> {
+        treasury::get_revenue_assets(&env)
     }
 
-    /// Cast a vote on a proposal
+    /// Pay protocol revenue out of an asset's reserve (admin only)
     ///
     /// # Arguments
-    /// * `voter` - Address casting the vote
-    /// * `proposal_id` - ID of the proposal to vote on
-    /// * `vote_type` - Vote choice (For, Against, Abstain)
+    /// * `caller` - The caller address (must be admin)
+    /// * `asset` - The reserve asset (None = native)
+    /// * `to` - The recipient
+    /// * `amount` - The amount to pay
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_vote(
+    /// Returns the asset's remaining protocol reserve
+    pub fn disburse_revenue(
         env: Env,
-        voter: Address,
-        proposal_id: u64,
-        vote_type: VoteType,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::vote(&env, voter, proposal_id, vote_type)
+        caller: Address,
+        asset: Option<Address>,
+        to: Address,
+        amount: i128,
+    ) -> Result<i128, TreasuryError> {
+        treasury::disburse_revenue(&env, caller, asset, to, amount)
     }
 
-    /// Queue a successful proposal for execution
-    ///
-    /// # Arguments
-    /// * `caller` - Address queuing the proposal
-    /// * `proposal_id` - ID of the proposal to queue
-    ///
-    /// # Returns
-    /// Returns the proposal outcome
-    pub fn gov_queue_proposal(
+    /// Set the protocol fee taken from AMM swaps, in basis points (admin only)
+    pub fn set_amm_protocol_fee(
         env: Env,
         caller: Address,
-        proposal_id: u64,
-    ) -> Result<ProposalOutcome, errors::GovernanceError> {
-        governance::queue_proposal(&env, caller, proposal_id)
+        fee_bps: i128,
+    ) -> Result<(), TreasuryError> {
+        treasury::set_amm_protocol_fee(&env, caller, fee_bps)
     }
 
-    /// Execute a queued proposal
-    ///
-    /// # Arguments
-    /// * `executor` - Address executing the proposal
-    /// * `proposal_id` - ID of the proposal to execute
-    ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_execute_proposal(
-        env: Env,
-        executor: Address,
-        proposal_id: u64,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::execute_proposal(&env, executor, proposal_id)
+    /// Get the protocol fee taken from AMM swaps, in basis points
+    pub fn get_amm_protocol_fee(env: Env) -> i128 {
+        treasury::get_amm_protocol_fee(&env)
     }
 
-    /// Cancel a proposal
+    // ============================================================================
+    // Parameter Timelock Entrypoints
+    // ============================================================================
+
+    /// Set the delay for risk-parameter changes (admin only)
     ///
-    /// Only proposer or admin can cancel.
+    /// While the delay is non-zero, collateral factors, risk parameters, the
+    /// interest rate model and AMM slippage can only be changed by queuing
+    /// them. The delay can be raised directly; lowering it must be queued.
     ///
     /// # Arguments
-    /// * `caller` - Address cancelling the proposal
-    /// * `proposal_id` - ID of the proposal to cancel
-    ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_cancel_proposal(
+    /// * `caller` - The admin address
+    /// * `delay` - Delay in seconds (0 disables the timelock)
+    pub fn set_param_timelock_delay(
         env: Env,
         caller: Address,
-        proposal_id: u64,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::cancel_proposal(&env, caller, proposal_id)
+        delay: u64,
+    ) -> Result<(), ParamTimelockError> {
+        param_timelock::set_param_timelock_delay(&env, caller, delay)
     }
 
-    /// Approve a proposal as multisig admin
+    /// Get the delay for risk-parameter changes in seconds
+    pub fn get_param_timelock_delay(env: Env) -> u64 {
+        param_timelock::get_param_timelock_delay(&env)
+    }
+
+    /// Queue a risk-parameter change (admin or risk manager)
     ///
     /// # Arguments
-    /// * `approver` - Admin address approving the proposal
-    /// * `proposal_id` - ID of the proposal to approve
+    /// * `caller` - The proposer (admin for `ParamChange::TimelockDelay`)
+    /// * `change` - The change to apply once the delay has elapsed
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_approve_proposal(
+    /// Returns the id of the queued change
+    pub fn queue_param_change(
         env: Env,
-        approver: Address,
-        proposal_id: u64,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::approve_proposal(&env, approver, proposal_id)
+        caller: Address,
+        change: ParamChange,
+    ) -> Result<u64, ParamTimelockError> {
+        param_timelock::queue_param_change(&env, caller, change)
     }
 
-    /// Set multisig configuration
-    ///
-    /// # Arguments
-    /// * `caller` - Caller address (must be admin)
-    /// * `admins` - Vector of admin addresses
-    /// * `threshold` - Number of approvals required
-    ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_set_multisig_config(
+    /// Apply a queued change after its delay (admin or risk manager)
+    pub fn execute_param_change(
         env: Env,
         caller: Address,
-        admins: Vec<Address>,
-        threshold: u32,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::set_multisig_config(&env, caller, admins, threshold)
+        id: u64,
+    ) -> Result<(), ParamTimelockError> {
+        param_timelock::execute_param_change(&env, caller, id)
     }
 
-    /// Add a guardian
-    ///
-    /// # Arguments
-    /// * `caller` - Caller address (must be admin)
-    /// * `guardian` - Guardian address to add
-    ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_add_guardian(
+    /// Cancel a queued change (admin, risk manager or guardian)
+    pub fn cancel_param_change(
         env: Env,
         caller: Address,
-        guardian: Address,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::add_guardian(&env, caller, guardian)
+        id: u64,
+    ) -> Result<(), ParamTimelockError> {
+        param_timelock::cancel_param_change(&env, caller, id)
     }
 
-    /// Remove a guardian
+    /// Get a queued change
+    pub fn get_param_change(env: Env, id: u64) -> Option<QueuedParamChange> {
+        param_timelock::get_param_change(&env, id)
+    }
+
+    /// Get the ids of changes that are still queued
+    pub fn get_pending_param_changes(env: Env) -> Vec<u64> {
+        param_timelock::get_pending_param_changes(&env)
+    }
+
+    // ============================================================================
+    // Contract Upgrade Entrypoints
+    // ============================================================================
+
+    /// Schedule an upgrade to uploaded code (admin only)
+    ///
+    /// The upgrade can be installed once the parameter timelock delay has
+    /// elapsed.
     ///
     /// # Arguments
-    /// * `caller` - Caller address (must be admin)
-    /// * `guardian` - Guardian address to remove
+    /// * `caller` - The admin address
+    /// * `new_wasm_hash` - Hash of the uploaded code
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_remove_guardian(
+    /// Returns the earliest time the upgrade can be installed
+    pub fn schedule_upgrade(
         env: Env,
         caller: Address,
-        guardian: Address,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::remove_guardian(&env, caller, guardian)
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<u64, UpgradeError> {
+        upgrade::schedule_upgrade(&env, caller, new_wasm_hash)
     }
 
-    /// Set guardian threshold
+    /// Cancel the scheduled upgrade (admin or guardian)
+    pub fn cancel_upgrade(env: Env, caller: Address) -> Result<(), UpgradeError> {
+        upgrade::cancel_upgrade(&env, caller)
+    }
+
+    /// Get the scheduled upgrade
+    pub fn get_scheduled_upgrade(env: Env) -> Option<ScheduledUpgrade> {
+        upgrade::get_scheduled_upgrade(&env)
+    }
+
+    /// Install the scheduled code (admin only)
     ///
-    /// # Arguments
-    /// * `caller` - Caller address (must be admin)
-    /// * `threshold` - Number of guardian approvals required for recovery
+    /// Call `migrate_storage` afterwards to bring the stored data to the new
+    /// code's layout.
     ///
-    /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_set_guardian_threshold(
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `new_wasm_hash` - Hash of the scheduled code
+    pub fn upgrade(
         env: Env,
         caller: Address,
-        threshold: u32,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::set_guardian_threshold(&env, caller, threshold)
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), UpgradeError> {
+        upgrade::upgrade(&env, caller, new_wasm_hash)
     }
 
-    /// Start recovery process
-    ///
-    /// # Arguments
-    /// * `initiator` - Guardian initiating recovery
-    /// * `old_admin` - Current admin to replace
-    /// * `new_admin` - New admin address
+    /// Run the migrations from the stored version to this build's (admin only)
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_start_recovery(
-        env: Env,
-        initiator: Address,
-        old_admin: Address,
-        new_admin: Address,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::start_recovery(&env, initiator, old_admin, new_admin)
+    /// Returns the new version
+    pub fn migrate_storage(env: Env, caller: Address) -> Result<u32, UpgradeError> {
+        upgrade::migrate_storage(&env, caller)
     }
 
-    /// Approve recovery
+    /// Get the storage layout version of the stored data
+    pub fn version(env: Env) -> u32 {
+        upgrade::get_version(&env)
+    }
+
+    // ============================================================================
+    // Storage TTL Entrypoints
+    // ============================================================================
+
+    /// Keep a user's position from being archived (callable by anyone)
+    ///
+    /// Extends the time-to-live of the user's position entries and of the
+    /// contract instance without changing any balance.
     ///
     /// # Arguments
-    /// * `approver` - Guardian approving recovery
+    /// * `user` - The position owner
+    pub fn bump_position(env: Env, user: Address) {
+        storage_ttl::bump_position(&env, &user)
+    }
+
+    /// Book the interest of a restored position and keep it live
+    ///
+    /// Call after restoring an archived position's entries (see
+    /// `storage_ttl` for the keys). Interest since the last accrual is
+    /// back-filled from the user's index snapshots. Callable by anyone.
+    ///
+    /// # Arguments
+    /// * `user` - The position owner
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_approve_recovery(
-        env: Env,
-        approver: Address,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::approve_recovery(&env, approver)
+    /// The restored position
+    pub fn restore_position(env: Env, user: Address) -> Result<Position, StorageTtlError> {
+        storage_ttl::restore_position(&env, &user)
     }
 
-    /// Execute recovery
+    // ============================================================================
+    // Health Simulation Entrypoints
+    // ============================================================================
+
+    /// Get the health factor a user's position would have after a sequence
+    /// of deposits, withdrawals, borrows and repayments, without executing
+    /// them
     ///
     /// # Arguments
-    /// * `executor` - Address executing recovery
+    /// * `user` - The position owner
+    /// * `actions` - Actions to apply, in order
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_execute_recovery(
+    /// The simulated health factor (scaled by 10000)
+    pub fn simulate_health_factor(
         env: Env,
         user: Address,
-    ) -> Result<UserPositionSummary, CrossAssetError> {
-        get_user_position_summary(&env, &user)
+        actions: Vec<SimAction>,
+    ) -> Result<i128, HealthSimulationError> {
+        health_simulation::simulate_health_factor(&env, &user, actions)
     }
 
     // ============================================================================
-    // Governance Query Functions
+    // Batch Query Entrypoints
     // ============================================================================
 
-    /// Get proposal by ID
-    pub fn gov_get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
-        governance::get_proposal(&env, proposal_id)
-    }
-
-    /// Get vote information
-    pub fn gov_get_vote(env: Env, proposal_id: u64, voter: Address) -> Option<VoteInfo> {
-        governance::get_vote(&env, proposal_id, voter)
-    }
-
-    /// Get governance configuration
-    pub fn gov_get_config(env: Env) -> Option<GovernanceConfig> {
-        governance::get_config(&env)
+    /// Answer many read-only queries in one call
+    ///
+    /// Covers core account data, cross-asset positions and health factors,
+    /// reserve prices and reserve configs. A failing query yields
+    /// `QueryResult::Unavailable` instead of failing the batch.
+    ///
+    /// # Arguments
+    /// * `requests` - Queries to answer (at most 20)
+    ///
+    /// # Returns
+    /// One result per request, in request order
+    pub fn query_many(
+        env: Env,
+        requests: Vec<QueryRequest>,
+    ) -> Result<Vec<QueryResult>, BatchQueryError> {
+        batch_query::query_many(&env, requests)
     }
 
-    /// Get governance admin
-    pub fn gov_get_admin(env: Env) -> Option<Address> {
-        governance::get_admin(&env)
-    }
+    // ============================================================================
+    // Batch Action Entrypoints
+    // ============================================================================
 
-    /// Get multisig configuration
-    pub fn gov_get_multisig_config(env: Env) -> Option<MultisigConfig> {
-        governance::get_multisig_config(&env)
+    /// Execute several deposits, withdrawals, borrows, repayments and swaps
+    /// of a user as a unit
+    ///
+    /// The collateral ratio is checked once, after the last action; any
+    /// failure reverts the whole batch.
+    ///
+    /// # Arguments
+    /// * `user` - The position owner (must authorize)
+    /// * `actions` - Actions to execute, in order (at most 10)
+    ///
+    /// # Returns
+    /// The result of each action, in action order
+    pub fn execute(
+        env: Env,
+        user: Address,
+        actions: Vec<BatchAction>,
+    ) -> Result<Vec<i128>, BatchActionError> {
+        batch_actions::execute(&env, user, actions)
     }
 
-    /// Get guardian configuration
-    pub fn gov_get_guardian_config(env: Env) -> Option<GuardianConfig> {
-        governance::get_guardian_config(&env)
-    }
+    // ============================================================================
+    // Standing Instruction Entrypoints
+    // ============================================================================
 
-    /// Get proposal approvals
-    pub fn gov_get_proposal_approvals(env: Env, proposal_id: u64) -> Option<Vec<Address>> {
-        governance::get_proposal_approvals(&env, proposal_id)
+    /// Register a standing deposit or repayment that keepers execute when its
+    /// trigger is met
+    ///
+    /// The amount and the bounty are pulled from the owner's wallet through
+    /// their token allowance to this contract on every execution.
+    ///
+    /// # Arguments
+    /// * `owner` - The position owner
+    /// * `operation` - `OP_DEPOSIT` or `OP_REPAY`
+    /// * `asset` - The asset (None for native XLM)
+    /// * `amount` - Amount deposited or repaid per execution
+    /// * `trigger` - When the instruction may be executed
+    /// * `bounty` - Amount of the asset paid to the keeper per execution
+    /// * `executions` - Number of times the instruction may be executed
+    ///
+    /// # Returns
+    /// The id of the new instruction
+    pub fn create_standing_instruction(
+        env: Env,
+        owner: Address,
+        operation: u32,
+        asset: Option<Address>,
+        amount: i128,
+        trigger: InstructionTrigger,
+        bounty: i128,
+        executions: u32,
+    ) -> Result<u64, StandingInstructionError> {
+        standing_instructions::create_standing_instruction(
+            &env, owner, operation, asset, amount, trigger, bounty, executions,
+        )
     }
 
-    /// Get current recovery request
-    pub fn gov_get_recovery_request(env: Env) -> Option<RecoveryRequest> {
-        governance::get_recovery_request(&env)
+    /// Cancel a standing instruction (owner only)
+    pub fn cancel_standing_instruction(
+        env: Env,
+        owner: Address,
+        id: u64,
+    ) -> Result<(), StandingInstructionError> {
+        standing_instructions::cancel_standing_instruction(&env, owner, id)
     }
 
-    /// Get recovery approvals
-    pub fn gov_get_recovery_approvals(env: Env) -> Option<Vec<Address>> {
-        governance::get_recovery_approvals(&env)
+    /// Execute a standing instruction whose trigger is met and collect its
+    /// bounty (any keeper)
+    ///
+    /// # Returns
+    /// The owner's collateral after a deposit, or the remaining debt after a
+    /// repayment
+    pub fn execute_standing_instruction(
+        env: Env,
+        keeper: Address,
+        id: u64,
+    ) -> Result<i128, StandingInstructionError> {
+        standing_instructions::execute_standing_instruction(&env, keeper, id)
     }
 
-    /// Get paginated list of proposals
-    pub fn gov_get_proposals(env: Env, start_id: u64, limit: u32) -> Vec<Proposal> {
-        governance::get_proposals(&env, start_id, limit)
+    /// Get a standing instruction by id
+    pub fn get_standing_instruction(env: Env, id: u64) -> Option<StandingInstruction> {
+        standing_instructions::get_standing_instruction(&env, id)
     }
 
-    /// Check if an address can vote on a proposal
-    pub fn gov_can_vote(env: Env, voter: Address, proposal_id: u64) -> bool {
-        governance::can_vote(&env, voter, proposal_id)
+    /// Get all standing instructions of a user
+    pub fn get_standing_instructions(env: Env, user: Address) -> Vec<StandingInstruction> {
+        standing_instructions::get_standing_instructions(&env, &user)
     }
 
-    // --- Bridge ---
-
-    /// Register a new bridge (admin only)
-    pub fn register_bridge(
-        env: Env,
-        caller: Address,
-        network_id: u32,
-        bridge: Address,
-        fee_bps: i128,
-    ) -> Result<(), BridgeError> {
-        register_bridge(&env, caller, network_id, bridge, fee_bps)
-    }
+    // ============================================================================
+    // Auto-Deleverage Entrypoints
+    // ============================================================================
 
-    /// Set fee for a bridge (admin only)
-    pub fn set_bridge_fee(
+    /// Let keepers sell collateral to repay debt once the health factor falls
+    /// below a trigger, for a fee of at most 1% of the collateral sold
+    ///
+    /// # Arguments
+    /// * `user` - The borrower
+    /// * `config` - Trigger, assets, per-execution limit and keeper fee
+    pub fn enable_auto_deleverage(
         env: Env,
-        caller: Address,
-        network_id: u32,
-        fee_bps: i128,
-    ) -> Result<(), BridgeError> {
-        set_bridge_fee(&env, caller, network_id, fee_bps)
-    }
-
-    /// List all registered bridges
-    pub fn list_bridges(env: Env) -> Map<u32, BridgeConfig> {
-        list_bridges(&env)
+        user: Address,
+        config: AutoDeleverageConfig,
+    ) -> Result<(), AutoDeleverageError> {
+        auto_deleverage::enable_auto_deleverage(&env, user, config)
     }
 
-    /// Get configuration for a bridge by network id
-    pub fn get_bridge_config(env: Env, network_id: u32) -> Result<BridgeConfig, BridgeError> {
-        get_bridge_config(&env, network_id)
+    /// Disable a user's auto-deleverage protection
+    pub fn disable_auto_deleverage(env: Env, user: Address) -> Result<(), AutoDeleverageError> {
+        auto_deleverage::disable_auto_deleverage(&env, user)
     }
 
-    /// Deposit into protocol via a bridge
-    pub fn bridge_deposit(
-        env: Env,
-        user: Address,
-        network_id: u32,
-        asset: Option<Address>,
-        amount: i128,
-    ) -> Result<i128, BridgeError> {
-        bridge_deposit(&env, user, network_id, asset, amount)
+    /// Get a user's auto-deleverage protection
+    pub fn get_auto_deleverage(env: Env, user: Address) -> Option<AutoDeleverageConfig> {
+        auto_deleverage::get_auto_deleverage(&env, &user)
     }
 
-    /// Withdraw from protocol via a bridge
-    pub fn bridge_withdraw(
+    /// Deleverage a protected position whose health factor is below its
+    /// trigger (any keeper)
+    ///
+    /// # Arguments
+    /// * `keeper` - The executing account, paid the fee
+    /// * `user` - The protected borrower
+    /// * `amount` - Collateral to take from the position, fee included
+    ///
+    /// # Returns
+    /// Returns a tuple (remaining_debt, debt_repaid, fee)
+    pub fn execute_auto_deleverage(
         env: Env,
+        keeper: Address,
         user: Address,
-        network_id: u32,
-        asset: Option<Address>,
         amount: i128,
-    ) -> Result<i128, BridgeError> {
-        bridge_withdraw(&env, user, network_id, asset, amount)
+    ) -> Result<(i128, i128, i128), AutoDeleverageError> {
+        auto_deleverage::execute_auto_deleverage(&env, keeper, user, amount)
     }
 }
 
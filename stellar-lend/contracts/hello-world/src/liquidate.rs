@@ -6,22 +6,37 @@
 //! collateral plus a liquidation incentive (bonus). This module uses the risk
 //! management system to determine:
 //! - Whether a position is eligible for liquidation (below liquidation threshold)
-//! - The maximum liquidatable amount (controlled by the close factor)
-//! - The liquidation incentive awarded to the liquidator
+//!   unless the collateral asset has its own liquidation threshold, in which
+//!   case the position is liquidatable once its debt exceeds the collateral
+//!   value weighted by that threshold
+//! - The maximum liquidatable amount (controlled by the close factor of the
+//!   debt reserve, or the global one, and 100% below the reserve's deep
+//!   distress health factor)
+//! - The liquidation incentive, split between the liquidator and the
+//!   collateral reserve by the reserve's liquidation protocol fee
 //!
 //! ## Cross-Asset Liquidation
 //! When debt and collateral are different assets, oracle prices are used to
 //! convert between asset values. A default price of 1.0 (8 decimals) is used
-//! as fallback when oracle prices are not configured.
+//! as fallback when oracle prices are not configured, but a configured price
+//! older than the asset's `max_price_age` fails with `StalePrice`.
 //!
 //! ## Invariants
 //! - Only undercollateralized positions (below liquidation threshold) can be liquidated.
 //! - Liquidation amount cannot exceed the close factor percentage of total debt.
+//!   The health factor used to pick the close factor is 1.0 (10000) exactly
+//!   at the liquidation point.
 //! - Collateral seized cannot exceed the borrower's available collateral.
 //! - Interest is accrued on the borrower's position before liquidation.
+//! - Positions with liquidation insurance are first topped up by the insurance
+//!   fund; if that lifts them out of liquidation range nothing is liquidated
+//!   and `(0, 0, 0)` is returned.
+//! - Small positions of reserves with a liquidation grace window are not
+//!   liquidated on the first attempt: the window is started, `(0, 0, 0)` is
+//!   returned, and liquidations fail until it elapses.
 
 #![allow(unused)]
-use crate::events::{emit_liquidation, LiquidationEvent};
+use crate::events::{emit_liquidation, LiquidationExecutedEvent};
 use soroban_sdk::{contracterror, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
 use crate::deposit::{
@@ -29,13 +44,14 @@ use crate::deposit::{
     emit_user_activity_tracked_event, update_protocol_analytics, AssetParams, DepositDataKey,
     Position, ProtocolAnalytics, UserAnalytics,
 };
-use crate::oracle::get_price;
+use crate::oracle::{get_price, OracleError};
+use crate::price_oracle::PriceOracleError;
 use crate::risk_management::{
     is_emergency_paused, is_operation_paused, require_operation_not_paused, RiskManagementError,
 };
 use crate::risk_params::{
     can_be_liquidated, get_close_factor, get_liquidation_incentive,
-    get_liquidation_incentive_amount, get_max_liquidatable_amount,
+    get_liquidation_incentive_amount,
 };
 
 /// Errors that can occur during liquidation operations
@@ -65,81 +81,67 @@ pub enum LiquidationError {
     PriceNotAvailable = 10,
     /// Liquidation would leave position undercollateralized
     InsufficientLiquidation = 11,
+    /// Borrower is inside a liquidation grace or recovery window
+    GracePeriodActive = 12,
+    /// Price of an involved asset is older than its `max_price_age`
+    StalePrice = 13,
+    /// AMM swap of the seized collateral failed
+    SwapFailed = 14,
+    /// AMM swap returned less than the debt repaid plus the minimum surplus
+    SlippageExceeded = 15,
 }
 
-/// Annual interest rate in basis points (e.g., 500 = 5% per year)
-/// This matches the rate used in borrow.rs and repay.rs
-// Interest rate is now calculated dynamically based on utilization
-// See interest_rate module for details
-/// Calculate interest accrued since last accrual time
-/// Calculate accrued interest using dynamic interest rate
-/// Uses the current borrow rate based on protocol utilization
-fn calculate_accrued_interest(
-    env: &Env,
-    principal: i128,
-    last_accrual_time: u64,
-    current_time: u64,
-) -> Result<i128, LiquidationError> {
-    if principal == 0 {
-        return Ok(0);
-    }
-
-    if current_time <= last_accrual_time {
-        return Ok(0);
-    }
-
-    // Get current borrow rate (in basis points)
-    let rate_bps =
-        crate::interest_rate::calculate_borrow_rate(env).map_err(|_| LiquidationError::Overflow)?;
-
-    // Calculate interest using the dynamic rate
-    crate::interest_rate::calculate_accrued_interest(
-        principal,
-        last_accrual_time,
-        current_time,
-        rate_bps,
-    )
-    .map_err(|_| LiquidationError::Overflow)
-}
+/// Slippage tolerance passed to the AMM router for liquidate-with-swap (1%)
+const SWAP_SLIPPAGE_TOLERANCE_BPS: i128 = 100;
 
 /// Accrue interest on a position
-fn accrue_interest(env: &Env, position: &mut Position) -> Result<(), LiquidationError> {
-    let current_time = env.ledger().timestamp();
-
+///
+/// Books the interest accrued since the last accrual through the pool's
+/// borrow index (see `interest_index`).
+fn accrue_interest(
+    env: &Env,
+    user: &Address,
+    position: &mut Position,
+) -> Result<(), LiquidationError> {
     if position.debt == 0 {
         position.borrow_interest = 0;
-        position.last_accrual_time = current_time;
-        return Ok(());
     }
-
-    // Calculate new interest accrued using dynamic rate
-    let new_interest =
-        calculate_accrued_interest(env, position.debt, position.last_accrual_time, current_time)?;
-
-    // Add to existing interest
-    position.borrow_interest = position
-        .borrow_interest
-        .checked_add(new_interest)
-        .ok_or(LiquidationError::Overflow)?;
-
-    // Update last accrual time
-    position.last_accrual_time = current_time;
-
+    crate::interest_index::accrue_position(env, user, position)
+        .map_err(|_| LiquidationError::Overflow)?;
     Ok(())
 }
 
-/// Get asset price from oracle
-/// Returns price in base units (scaled by decimals)
-/// Falls back to default price if oracle doesn't have a price set
-fn get_asset_price(env: &Env, asset: &Address) -> i128 {
-    // Try to get price from oracle, but fallback to default if not available
-    // This allows liquidation to work even when prices aren't set up in tests
-    get_price(env, asset).unwrap_or(1_00000000i128) // Default: 1 XLM with 8 decimals
+/// Get asset price for liquidation math
+/// Uses the asset's SEP-40 price feed when configured; a feed without a
+/// usable price blocks the liquidation rather than falling back
+/// Otherwise returns the pushed oracle price, falling back to a default
+/// Prices older than the asset's `max_price_age` are never used
+pub(crate) fn get_asset_price(
+    env: &Env,
+    asset: &Option<Address>,
+) -> Result<i128, LiquidationError> {
+    if let Some(feed_price) = crate::price_oracle::get_feed_price(env, asset) {
+        return feed_price.map_err(|e| match e {
+            PriceOracleError::StalePrice => LiquidationError::StalePrice,
+            _ => LiquidationError::PriceNotAvailable,
+        });
+    }
+    match asset {
+        Some(addr) => match get_price(env, addr) {
+            Ok(price) => Ok(price),
+            Err(OracleError::StalePrice) => Err(LiquidationError::StalePrice),
+            // Fallback to default if the oracle has no price
+            // This allows liquidation to work even when prices aren't set up in tests
+            Err(_) => Ok(1_00000000i128), // Default: 1 XLM with 8 decimals
+        },
+        // Default price for native XLM (1:1, no decimals)
+        None => Ok(1i128),
+    }
 }
 
 /// Calculate collateral value in debt asset terms
 /// Returns collateral_value = collateral_amount * collateral_price / debt_price
-fn calculate_collateral_value(
+pub(crate) fn calculate_collateral_value(
     collateral_amount: i128,
     collateral_price: i128,
     debt_price: i128,
@@ -156,6 +158,84 @@ fn calculate_collateral_value(
         .ok_or(LiquidationError::Overflow)
 }
 
+/// Check whether a position can be liquidated
+/// Uses the collateral asset's liquidation threshold when one is set:
+/// liquidatable when collateral_value * threshold / 10000 < debt_value
+/// Otherwise uses the global minimum collateral ratio from risk params
+pub(crate) fn is_liquidatable(
+    env: &Env,
+    collateral_asset: &Option<Address>,
+    collateral_value: i128,
+    debt_value: i128,
+) -> Result<bool, LiquidationError> {
+    let liquidation_threshold = crate::reserve::get_liquidation_threshold(env, collateral_asset);
+    if liquidation_threshold == 0 {
+        return can_be_liquidated(env, collateral_value, debt_value)
+            .map_err(|_| LiquidationError::NotLiquidatable);
+    }
+    if debt_value == 0 {
+        return Ok(false);
+    }
+    let weighted_collateral = collateral_value
+        .checked_mul(liquidation_threshold)
+        .ok_or(LiquidationError::Overflow)?
+        / 10000;
+    Ok(weighted_collateral < debt_value)
+}
+
+/// Health factor of a position in basis points, 10000 at the liquidation point
+/// With a collateral liquidation threshold: collateral_value * threshold / debt_value
+/// Otherwise: (collateral_value / debt_value) / global liquidation threshold ratio
+pub(crate) fn health_factor(
+    env: &Env,
+    collateral_asset: &Option<Address>,
+    collateral_value: i128,
+    debt_value: i128,
+) -> Result<i128, LiquidationError> {
+    if debt_value == 0 {
+        return Ok(i128::MAX);
+    }
+    let liquidation_threshold = crate::reserve::get_liquidation_threshold(env, collateral_asset);
+    if liquidation_threshold > 0 {
+        return collateral_value
+            .checked_mul(liquidation_threshold)
+            .ok_or(LiquidationError::Overflow)?
+            .checked_div(debt_value)
+            .ok_or(LiquidationError::Overflow);
+    }
+    let ratio_threshold = crate::risk_params::get_risk_params(env)
+        .map(|params| params.liquidation_threshold)
+        .unwrap_or(10000);
+    collateral_value
+        .checked_mul(10000 * 10000)
+        .ok_or(LiquidationError::Overflow)?
+        .checked_div(debt_value)
+        .ok_or(LiquidationError::Overflow)?
+        .checked_div(ratio_threshold)
+        .ok_or(LiquidationError::Overflow)
+}
+
+/// Health factor of a position priced the way liquidations price it
+///
+/// Values `collateral_balance` of `collateral_asset` in `debt_asset` terms at
+/// oracle prices (1:1 when both are native XLM) and applies `health_factor`.
+pub(crate) fn priced_health_factor(
+    env: &Env,
+    collateral_asset: &Option<Address>,
+    debt_asset: &Option<Address>,
+    collateral_balance: i128,
+    debt_value: i128,
+) -> Result<i128, LiquidationError> {
+    let collateral_value = if debt_asset.is_none() && collateral_asset.is_none() {
+        collateral_balance
+    } else {
+        let debt_price = get_asset_price(env, debt_asset)?;
+        let collateral_price = get_asset_price(env, collateral_asset)?;
+        calculate_collateral_value(collateral_balance, collateral_price, debt_price)?
+    };
+    health_factor(env, collateral_asset, collateral_value, debt_value)
+}
+
 /// Calculate debt value
 /// Returns debt_value = debt_amount + interest
 fn calculate_debt_value(debt: i128, interest: i128) -> Result<i128, LiquidationError> {
@@ -206,6 +286,125 @@ pub fn liquidate(
     collateral_asset: Option<Address>,
     debt_amount: i128,
 ) -> Result<(i128, i128, i128), LiquidationError> {
+    let (debt_liquidated, collateral_seized, incentive_amount, _) = liquidate_internal(
+        env,
+        liquidator,
+        borrower,
+        debt_asset,
+        collateral_asset,
+        debt_amount,
+        true,
+    )?;
+    Ok((debt_liquidated, collateral_seized, incentive_amount))
+}
+
+/// Liquidate a position without supplying the debt asset up front
+///
+/// The liquidator's share of the seized collateral is swapped into the debt
+/// asset by this contract against its hosted AMM pools. The proceeds repay
+/// the liquidated debt and only the surplus is paid out to the liquidator.
+/// The whole call reverts if the swap fails or does not cover the debt plus
+/// `min_surplus`.
+///
+/// # Arguments
+/// * `liquidator` - The address of the liquidator
+/// * `borrower` - The address of the borrower being liquidated
+/// * `debt_asset` - The debt asset repaid from the swap proceeds
+/// * `collateral_asset` - The collateral asset seized and sold
+/// * `debt_amount` - The amount of debt to liquidate
+/// * `min_surplus` - Minimum amount of `debt_asset` paid to the liquidator
+///
+/// # Returns
+/// Returns a tuple (debt_liquidated, collateral_seized, surplus)
+///
+/// # Errors
+/// * `LiquidationError::InvalidCollateralAsset` - If both assets are the same
+/// * `LiquidationError::InvalidAmount` - If `min_surplus` is negative
+/// * `LiquidationError::SwapFailed` - If the AMM swap fails
+/// * `LiquidationError::SlippageExceeded` - If the swap output is too low
+/// * Any error of `liquidate`
+pub fn liquidate_with_swap(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_asset: Address,
+    collateral_asset: Address,
+    debt_amount: i128,
+    min_surplus: i128,
+) -> Result<(i128, i128, i128), LiquidationError> {
+    liquidator.require_auth();
+    if collateral_asset == debt_asset {
+        return Err(LiquidationError::InvalidCollateralAsset);
+    }
+    if min_surplus < 0 {
+        return Err(LiquidationError::InvalidAmount);
+    }
+
+    let (debt_liquidated, collateral_seized, _, collateral_to_liquidator) = liquidate_internal(
+        env,
+        liquidator.clone(),
+        borrower,
+        Some(debt_asset.clone()),
+        Some(collateral_asset.clone()),
+        debt_amount,
+        false,
+    )?;
+    // Rescued by insurance or a grace window was started
+    if debt_liquidated == 0 {
+        return Ok((0, 0, 0));
+    }
+
+    let min_amount_out = debt_liquidated
+        .checked_add(min_surplus)
+        .ok_or(LiquidationError::Overflow)?;
+    let params = stellarlend_amm::SwapParams {
+        protocol: env.current_contract_address(),
+        token_in: Some(collateral_asset),
+        token_out: Some(debt_asset.clone()),
+        amount_in: collateral_to_liquidator,
+        min_amount_out,
+        slippage_tolerance: SWAP_SLIPPAGE_TOLERANCE_BPS,
+        deadline: env.ledger().timestamp(),
+        use_oracle_floor: false,
+    };
+    let amount_out = stellarlend_amm::execute_swap(env, env.current_contract_address(), params)
+        .map_err(|e| match e {
+            stellarlend_amm::AmmError::MinOutputNotMet
+            | stellarlend_amm::AmmError::SlippageExceeded => LiquidationError::SlippageExceeded,
+            _ => LiquidationError::SwapFailed,
+        })?;
+    if amount_out < min_amount_out {
+        return Err(LiquidationError::SlippageExceeded);
+    }
+
+    // The debt is repaid from the proceeds; the rest goes to the liquidator
+    let surplus = amount_out - debt_liquidated;
+    if surplus > 0 {
+        soroban_sdk::token::Client::new(env, &debt_asset).transfer(
+            &env.current_contract_address(),
+            &liquidator,
+            &surplus,
+        );
+    }
+
+    Ok((debt_liquidated, collateral_seized, surplus))
+}
+
+/// Liquidate a position and, when `settle` is set, pull the debt asset from
+/// the liquidator and pay out their collateral
+///
+/// # Returns
+/// Returns a tuple (debt_liquidated, collateral_seized, incentive_amount,
+/// collateral_to_liquidator)
+fn liquidate_internal(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_asset: Option<Address>,
+    collateral_asset: Option<Address>,
+    debt_amount: i128,
+    settle: bool,
+) -> Result<(i128, i128, i128, i128), LiquidationError> {
     // Validate amount
     if debt_amount <= 0 {
         return Err(LiquidationError::InvalidAmount);
@@ -238,9 +437,17 @@ pub fn liquidate(
         }
     }
 
+    // Liquidations stay blocked for a while after an unpause or oracle outage
+    if crate::liquidation_grace::in_recovery_grace(env, &debt_asset, &collateral_asset) {
+        return Err(LiquidationError::GracePeriodActive);
+    }
+
     // Get current timestamp
     let timestamp = env.ledger().timestamp();
 
+    // Credit the borrower's supply interest before collateral is seized
+    crate::supply_interest::settle_supply_interest(env, &borrower);
+
     // Get borrower position
     let position_key = DepositDataKey::Position(borrower.clone());
     let mut position = env
@@ -250,7 +457,12 @@ pub fn liquidate(
         .ok_or(LiquidationError::NotLiquidatable)?;
 
     // Accrue interest before liquidation
-    accrue_interest(env, &mut position)?;
+    accrue_interest(env, &borrower, &mut position)?;
+    crate::rate_stats::record_accrual(env, &debt_asset);
+
+    // Charge any liquidation insurance premium owed
+    crate::liquidation_insurance::accrue_premium(env, &borrower, &mut position)
+        .map_err(|_| LiquidationError::Overflow)?;
 
     // Get collateral balance
     let collateral_key = DepositDataKey::CollateralBalance(borrower.clone());
@@ -271,35 +483,64 @@ pub fn liquidate(
         collateral_balance
     } else {
         // Need to convert between different assets using prices
-        let debt_price = if let Some(ref debt_addr) = debt_asset {
-            get_asset_price(env, debt_addr)
-        } else {
-            // Default price for native XLM (1:1, no decimals)
-            1i128
-        };
-
-        let collateral_price = if let Some(ref collateral_addr) = collateral_asset {
-            get_asset_price(env, collateral_addr)
-        } else {
-            // Default price for native XLM (1:1, no decimals)
-            1i128
-        };
+        let debt_price = get_asset_price(env, &debt_asset)?;
+        let collateral_price = get_asset_price(env, &collateral_asset)?;
 
         // Calculate collateral value in debt asset terms
         calculate_collateral_value(collateral_balance, collateral_price, debt_price)?
     };
 
     // Check if position can be liquidated
-    let can_liquidate = can_be_liquidated(env, collateral_value, total_debt)
-        .map_err(|_| LiquidationError::NotLiquidatable)?;
+    let can_liquidate = is_liquidatable(env, &collateral_asset, collateral_value, total_debt)?;
 
     if !can_liquidate {
         return Err(LiquidationError::NotLiquidatable);
     }
 
-    // Get maximum liquidatable amount (close factor)
-    let max_liquidatable =
-        get_max_liquidatable_amount(env, total_debt).map_err(|_| LiquidationError::Overflow)?;
+    // Insured positions are rescued by the insurance fund before liquidators can act
+    let covered = crate::liquidation_insurance::apply_protection(
+        env,
+        &borrower,
+        &mut position,
+        &debt_asset,
+        collateral_value,
+    )
+    .map_err(|_| LiquidationError::Overflow)?;
+    let total_debt = if covered > 0 {
+        let total_debt = calculate_debt_value(position.debt, position.borrow_interest)?;
+        let still_liquidatable =
+            is_liquidatable(env, &collateral_asset, collateral_value, total_debt)?;
+        if !still_liquidatable {
+            env.storage().persistent().set(&position_key, &position);
+            emit_position_updated_event(env, &borrower, &position);
+            return Ok((0, 0, 0, 0));
+        }
+        total_debt
+    } else {
+        total_debt
+    };
+
+    // Small positions may get a grace window to recover before liquidation
+    match crate::liquidation_grace::check_grace(env, &borrower, &debt_asset, total_debt) {
+        crate::liquidation_grace::GraceStatus::Started => {
+            env.storage().persistent().set(&position_key, &position);
+            emit_position_updated_event(env, &borrower, &position);
+            return Ok((0, 0, 0, 0));
+        }
+        crate::liquidation_grace::GraceStatus::Active => {
+            return Err(LiquidationError::GracePeriodActive);
+        }
+        crate::liquidation_grace::GraceStatus::Expired => {}
+    }
+    crate::health_history::record_liquidation_check(env, &borrower, collateral_value, total_debt);
+
+    // Get maximum liquidatable amount (close factor, 100% in deep distress)
+    let health = health_factor(env, &collateral_asset, collateral_value, total_debt)?;
+    let close_factor = crate::reserve::effective_close_factor(env, &debt_asset, health);
+    let max_liquidatable = total_debt
+        .checked_mul(close_factor)
+        .ok_or(LiquidationError::Overflow)?
+        / 10000;
 
     // Validate liquidation amount doesn't exceed close factor
     if debt_amount > max_liquidatable {
@@ -328,17 +569,8 @@ pub fn liquidate(
         actual_debt_liquidated
     } else {
         // Need to convert between different assets using prices
-        let debt_price = if let Some(ref debt_addr) = debt_asset {
-            get_asset_price(env, debt_addr)
-        } else {
-            1i128 // Native XLM
-        };
-
-        let collateral_price = if let Some(ref collateral_addr) = collateral_asset {
-            get_asset_price(env, collateral_addr)
-        } else {
-            1i128 // Native XLM
-        };
+        let debt_price = get_asset_price(env, &debt_asset)?;
+        let collateral_price = get_asset_price(env, &collateral_asset)?;
 
         actual_debt_liquidated
             .checked_mul(debt_price)
@@ -361,8 +593,20 @@ pub fn liquidate(
         collateral_seized
     };
 
-    // Check liquidator has sufficient balance to repay debt
-    if let Some(ref debt_addr) = debt_asset {
+    // Split the bonus between the liquidator and the collateral reserve
+    let bonus = actual_collateral_seized
+        .checked_sub(collateral_value_liquidated)
+        .ok_or(LiquidationError::Overflow)?;
+    let (liquidator_bonus, protocol_fee) =
+        crate::reserve::split_liquidation_bonus(env, &collateral_asset, bonus)
+            .map_err(|_| LiquidationError::Overflow)?;
+    let collateral_to_liquidator = actual_collateral_seized
+        .checked_sub(protocol_fee)
+        .ok_or(LiquidationError::Overflow)?;
+
+    // Check liquidator has sufficient balance to repay debt (unless it is
+    // repaid from a swap of the collateral)
+    if let Some(debt_addr) = debt_asset.as_ref().filter(|_| settle) {
         let token_client = soroban_sdk::token::Client::new(env, debt_addr);
         let liquidator_balance = token_client.balance(&liquidator);
         if liquidator_balance < actual_debt_liquidated {
@@ -388,12 +632,15 @@ pub fn liquidate(
             return Err(LiquidationError::InsufficientBalance);
         }
 
-        // Transfer collateral asset from contract to liquidator (with incentive)
-        token_client.transfer(
-            &env.current_contract_address(), // from (this contract)
-            &liquidator,                     // to (liquidator)
-            &actual_collateral_seized,
-        );
+        // Transfer collateral asset from contract to liquidator (with its
+        // share of the incentive; the protocol's cut stays in reserves)
+        if settle {
+            token_client.transfer(
+                &env.current_contract_address(), // from (this contract)
+                &liquidator,                     // to (liquidator)
+                &collateral_to_liquidator,
+            );
+        }
     } else {
         // Native XLM handling - placeholder for now
     }
@@ -415,6 +662,9 @@ pub fn liquidate(
         .unwrap_or(0);
     position.debt = position.debt.checked_sub(principal_to_pay).unwrap_or(0);
     position.last_accrual_time = timestamp;
+    crate::reserve::record_borrow_change(env, &debt_asset, -principal_to_pay);
+    crate::liquidation_insurance::collect_premium(env, &borrower, &debt_asset, interest_to_pay)
+        .map_err(|_| LiquidationError::Overflow)?;
 
     // Update borrower's collateral balance
     let new_collateral_balance = collateral_balance
@@ -424,11 +674,28 @@ pub fn liquidate(
         .persistent()
         .set(&collateral_key, &new_collateral_balance);
 
+    crate::reserve::record_supply_change(env, &collateral_asset, -actual_collateral_seized);
+
     // Update position collateral
     position.collateral = new_collateral_balance;
 
+    // Debt left behind once the last collateral is seized becomes bad debt
+    crate::bad_debt::realize_bad_debt(
+        env,
+        &borrower,
+        &debt_asset,
+        &mut position,
+        Symbol::new(env, "liquidation"),
+    )
+    .map_err(|_| LiquidationError::Overflow)?;
+
     // Save updated position
     env.storage().persistent().set(&position_key, &position);
+    crate::reserve::clear_debt_assets_if_repaid(env, &borrower, &position);
+    crate::storage_ttl::extend_position_ttl(env, &borrower);
+    crate::storage_ttl::extend_reserve_ttl(env, &debt_asset);
+    crate::storage_ttl::extend_reserve_ttl(env, &collateral_asset);
+    crate::liquidation_index::sync_borrower(env, &borrower);
 
     // Update analytics
     update_liquidation_analytics(
@@ -453,11 +720,13 @@ pub fn liquidate(
         crate::deposit::DepositError::Overflow => LiquidationError::Overflow,
         _ => LiquidationError::Overflow,
     })?;
+    crate::health_history::record_health_snapshot(env, &borrower, "liquidate");
+    crate::liquidation_grace::clear_if_healthy(env, &borrower);
 
     // Emit liquidation event
     emit_liquidation(
         env,
-        LiquidationEvent {
+        LiquidationExecutedEvent {
             liquidator: liquidator.clone(),
             borrower: borrower.clone(),
             debt_asset: debt_asset.clone(),
@@ -465,6 +734,8 @@ pub fn liquidate(
             debt_liquidated: actual_debt_liquidated,
             collateral_seized: actual_collateral_seized,
             incentive_amount,
+            liquidator_bonus,
+            protocol_fee,
             timestamp,
         },
     );
@@ -494,6 +765,7 @@ pub fn liquidate(
         actual_debt_liquidated,
         actual_collateral_seized,
         incentive_amount,
+        collateral_to_liquidator,
     ))
 }
 
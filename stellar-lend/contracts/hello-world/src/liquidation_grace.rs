@@ -0,0 +1,329 @@
+//! # Liquidation Grace Module
+//!
+//! Optional, per-reserve grace window for small positions that have just
+//! become liquidatable. Sharp but transient price wicks can push retail
+//! borrowers under the liquidation threshold for a few ledgers; the grace
+//! window gives them time to top up collateral or repay before liquidators
+//! can act.
+//!
+//! ## Flow
+//! 1. The first liquidation attempt on an in-scope position starts the grace
+//!    window instead of liquidating: the start ledger is recorded and the
+//!    liquidation returns `(0, 0, 0)`.
+//! 2. Liquidation attempts inside the window fail with
+//!    `LiquidationError::GracePeriodActive`.
+//! 3. Once `grace_ledgers` ledgers have passed, the position is liquidatable
+//!    as usual.
+//!
+//! A position is in scope when the grace window of its debt reserve is
+//! enabled and its total debt does not exceed the reserve's `max_debt`.
+//!
+//! ## Recovery Grace
+//! Independently of position size, the admin can configure a protocol-wide
+//! recovery window. When liquidations are unpaused (switch or emergency
+//! pause) or an oracle pushes a price after its previous one went stale,
+//! liquidations are blocked for `recovery_ledgers` ledgers so borrowers can
+//! top up collateral before liquidators act on the post-incident prices.
+//! An unpause blocks every liquidation; an oracle recovery blocks those
+//! using the recovered asset as debt or collateral.
+//!
+//! ## Invariants
+//! - A grace window is started at most once per unhealthy spell; the marker
+//!   is cleared as soon as the position is back out of liquidation range
+//!   after a deposit, repayment or liquidation.
+//! - Positions above the reserve's size threshold never get a grace window.
+//! - `grace_ledgers == 0` disables the grace window for the reserve.
+//! - Only the admin can configure grace windows.
+//! - Recovery windows only ever extend; a new incident never shortens a
+//!   running window.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+
+use crate::events::{
+    emit_grace_period_started, emit_recovery_grace_started, GracePeriodStartedEvent,
+    RecoveryGraceStartedEvent,
+};
+use crate::risk_params::can_be_liquidated;
+
+/// Longest grace window that can be configured (~1 day of ledgers)
+pub const MAX_GRACE_LEDGERS: u32 = 17_280;
+
+/// Errors that can occur during liquidation grace operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LiquidationGraceError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Grace window configuration is invalid
+    InvalidConfig = 2,
+}
+
+/// Storage keys for liquidation grace data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum LiquidationGraceDataKey {
+    /// Grace window configuration of a debt reserve: LiquidationGraceConfig
+    GraceConfig(Option<Address>),
+    /// Ledger the current grace window of a borrower started at: u32
+    GraceStarted(Address),
+    /// Length of the post-incident recovery window in ledgers: u32
+    RecoveryGraceLedgers,
+    /// Ledger the protocol-wide recovery window ends at: u32
+    ProtocolRecoveryUntil,
+    /// Ledger the recovery window of an asset's oracle ends at: u32
+    OracleRecoveryUntil(Option<Address>),
+}
+
+/// Grace window configuration of a debt reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationGraceConfig {
+    /// Length of the grace window in ledgers (0 = disabled)
+    pub grace_ledgers: u32,
+    /// Largest total debt (principal + interest) eligible for a grace window
+    pub max_debt: i128,
+}
+
+/// Outcome of the grace window check of a liquidation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum GraceStatus {
+    /// No grace window applies; liquidation may proceed
+    Expired,
+    /// A grace window was just started
+    Started,
+    /// The borrower is inside a running grace window
+    Active,
+}
+
+/// Configure the grace window of a debt reserve
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `asset` - The debt reserve (None for native XLM)
+/// * `grace_ledgers` - Length of the grace window in ledgers (0 disables it)
+/// * `max_debt` - Largest total debt eligible for a grace window
+///
+/// # Errors
+/// * `LiquidationGraceError::Unauthorized` - If caller is not the admin
+/// * `LiquidationGraceError::InvalidConfig` - If the window is too long or
+///   `max_debt` is not positive
+pub fn set_liquidation_grace(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    grace_ledgers: u32,
+    max_debt: i128,
+) -> Result<(), LiquidationGraceError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| LiquidationGraceError::Unauthorized)?;
+
+    if grace_ledgers > MAX_GRACE_LEDGERS || max_debt <= 0 {
+        return Err(LiquidationGraceError::InvalidConfig);
+    }
+
+    let config = LiquidationGraceConfig {
+        grace_ledgers,
+        max_debt,
+    };
+    env.storage()
+        .persistent()
+        .set(&LiquidationGraceDataKey::GraceConfig(asset), &config);
+    Ok(())
+}
+
+/// Get the grace window configuration of a debt reserve
+pub fn get_liquidation_grace(env: &Env, asset: &Option<Address>) -> Option<LiquidationGraceConfig> {
+    env.storage()
+        .persistent()
+        .get::<LiquidationGraceDataKey, LiquidationGraceConfig>(
+            &LiquidationGraceDataKey::GraceConfig(asset.clone()),
+        )
+}
+
+/// Get the ledger the current grace window of a borrower started at
+pub fn get_grace_started(env: &Env, borrower: &Address) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get::<LiquidationGraceDataKey, u32>(&LiquidationGraceDataKey::GraceStarted(
+            borrower.clone(),
+        ))
+}
+
+/// Check, and start if needed, the grace window of a liquidatable position
+///
+/// # Arguments
+/// * `borrower` - The borrower being liquidated
+/// * `debt_asset` - The debt reserve of the liquidation
+/// * `total_debt` - The borrower's total debt (principal + interest)
+pub(crate) fn check_grace(
+    env: &Env,
+    borrower: &Address,
+    debt_asset: &Option<Address>,
+    total_debt: i128,
+) -> GraceStatus {
+    let config = match get_liquidation_grace(env, debt_asset) {
+        Some(config) if config.grace_ledgers > 0 && total_debt <= config.max_debt => config,
+        _ => return GraceStatus::Expired,
+    };
+
+    let current = env.ledger().sequence();
+    match get_grace_started(env, borrower) {
+        Some(started) if current < started.saturating_add(config.grace_ledgers) => {
+            GraceStatus::Active
+        }
+        Some(_) => GraceStatus::Expired,
+        None => {
+            env.storage().persistent().set(
+                &LiquidationGraceDataKey::GraceStarted(borrower.clone()),
+                &current,
+            );
+            emit_grace_period_started(
+                env,
+                GracePeriodStartedEvent {
+                    borrower: borrower.clone(),
+                    started_ledger: current,
+                    ends_ledger: current.saturating_add(config.grace_ledgers),
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+            GraceStatus::Started
+        }
+    }
+}
+
+/// Clear a borrower's grace window once their position is out of liquidation range
+pub(crate) fn clear_if_healthy(env: &Env, borrower: &Address) {
+    let key = LiquidationGraceDataKey::GraceStarted(borrower.clone());
+    if !env.storage().persistent().has(&key) {
+        return;
+    }
+    let position = match crate::analytics::get_user_position_summary(env, borrower) {
+        Ok(position) => position,
+        Err(_) => return,
+    };
+    let total_debt = position.debt.saturating_add(position.borrow_interest);
+    if let Ok(false) = can_be_liquidated(env, position.collateral, total_debt) {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Configure the post-incident recovery window (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `recovery_ledgers` - Length of the recovery window in ledgers (0 disables it)
+///
+/// # Errors
+/// * `LiquidationGraceError::Unauthorized` - If caller is not the admin
+/// * `LiquidationGraceError::InvalidConfig` - If the window is too long
+pub fn set_recovery_grace(
+    env: &Env,
+    caller: Address,
+    recovery_ledgers: u32,
+) -> Result<(), LiquidationGraceError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| LiquidationGraceError::Unauthorized)?;
+
+    if recovery_ledgers > MAX_GRACE_LEDGERS {
+        return Err(LiquidationGraceError::InvalidConfig);
+    }
+    env.storage().persistent().set(
+        &LiquidationGraceDataKey::RecoveryGraceLedgers,
+        &recovery_ledgers,
+    );
+    Ok(())
+}
+
+/// Get the length of the post-incident recovery window in ledgers (0 = disabled)
+pub fn get_recovery_grace(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<LiquidationGraceDataKey, u32>(&LiquidationGraceDataKey::RecoveryGraceLedgers)
+        .unwrap_or(0)
+}
+
+/// Get the ledger until which liquidations touching an asset are blocked
+///
+/// Returns the later of the protocol-wide and the asset's oracle recovery
+/// window, or 0 if neither was ever started.
+pub fn get_recovery_grace_until(env: &Env, asset: &Option<Address>) -> u32 {
+    let protocol = env
+        .storage()
+        .persistent()
+        .get::<LiquidationGraceDataKey, u32>(&LiquidationGraceDataKey::ProtocolRecoveryUntil)
+        .unwrap_or(0);
+    let oracle = env
+        .storage()
+        .persistent()
+        .get::<LiquidationGraceDataKey, u32>(&LiquidationGraceDataKey::OracleRecoveryUntil(
+            asset.clone(),
+        ))
+        .unwrap_or(0);
+    protocol.max(oracle)
+}
+
+/// Whether a recovery window blocks a liquidation between two assets
+pub(crate) fn in_recovery_grace(
+    env: &Env,
+    debt_asset: &Option<Address>,
+    collateral_asset: &Option<Address>,
+) -> bool {
+    let current = env.ledger().sequence();
+    current < get_recovery_grace_until(env, debt_asset)
+        || current < get_recovery_grace_until(env, collateral_asset)
+}
+
+/// Extend a recovery window to `recovery_ledgers` from now, if configured
+fn start_recovery_grace(
+    env: &Env,
+    key: LiquidationGraceDataKey,
+    asset: Option<Address>,
+    reason: &str,
+) {
+    let recovery_ledgers = get_recovery_grace(env);
+    if recovery_ledgers == 0 {
+        return;
+    }
+    let ends_ledger = env.ledger().sequence().saturating_add(recovery_ledgers);
+    let current_end = env
+        .storage()
+        .persistent()
+        .get::<LiquidationGraceDataKey, u32>(&key)
+        .unwrap_or(0);
+    if ends_ledger <= current_end {
+        return;
+    }
+    env.storage().persistent().set(&key, &ends_ledger);
+    emit_recovery_grace_started(
+        env,
+        RecoveryGraceStartedEvent {
+            asset,
+            reason: Symbol::new(env, reason),
+            ends_ledger,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+/// Start the protocol-wide recovery window after liquidations are unpaused
+pub(crate) fn start_unpause_recovery(env: &Env) {
+    start_recovery_grace(
+        env,
+        LiquidationGraceDataKey::ProtocolRecoveryUntil,
+        None,
+        "unpause",
+    );
+}
+
+/// Start the recovery window of an asset after its oracle comes back from an outage
+pub(crate) fn start_oracle_recovery(env: &Env, asset: &Option<Address>) {
+    start_recovery_grace(
+        env,
+        LiquidationGraceDataKey::OracleRecoveryUntil(asset.clone()),
+        asset.clone(),
+        "oracle",
+    );
+}
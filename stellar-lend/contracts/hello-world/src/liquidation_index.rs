@@ -0,0 +1,178 @@
+//! # Liquidation Index Module
+//!
+//! On-chain registry of core positions with outstanding debt, so liquidation
+//! bots can page through underwater accounts with
+//! `get_liquidatable_positions(cursor, limit)` instead of rebuilding the
+//! borrower set from events off-chain.
+//!
+//! ## Registry
+//! A borrower is added when a borrow leaves them with debt and removed once a
+//! repayment, liquidation, auction or bad debt write-off clears it. Removal
+//! swaps the last borrower into the freed slot, so the registry stays dense.
+//!
+//! ## Paging
+//! Prices move without touching positions, so health is evaluated when the
+//! page is read rather than stored. Each call inspects at most `limit`
+//! registry slots starting at `cursor` (capped at `MAX_INDEX_PAGE`) and
+//! returns the liquidatable ones among them, so a page may hold fewer than
+//! `limit` positions while `next_cursor` is set. Each position carries its
+//! risk level band (see `analytics::calculate_user_risk_level`).
+//!
+//! ## Invariants
+//! - Every indexed borrower occupies exactly one slot, recorded in
+//!   `BorrowerSlot`.
+//! - Borrowers whose debt (principal + interest) is zero are not indexed.
+//! - A removal while a bot is paging can move the last borrower behind the
+//!   bot's cursor; bots should restart from 0 once they reach the end.
+
+#![allow(unused)]
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::deposit::{DepositDataKey, Position};
+
+/// Maximum registry slots inspected per page
+pub const MAX_INDEX_PAGE: u32 = 50;
+
+/// Storage keys for the liquidation index
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum LiquidationIndexDataKey {
+    /// Borrowers with outstanding core debt: Vec<Address>
+    IndexedBorrowers,
+    /// Slot of a borrower in `IndexedBorrowers`: u32
+    BorrowerSlot(Address),
+}
+
+/// Liquidatable core position found by the index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidatablePosition {
+    /// The borrower
+    pub borrower: Address,
+    /// Collateral balance of the position
+    pub collateral: i128,
+    /// Total debt (principal + interest) of the position
+    pub debt: i128,
+    /// Health factor (collateral over debt, scaled by 10000)
+    pub health_factor: i128,
+    /// Risk level band (1 = low, 5 = critical)
+    pub risk_level: i128,
+}
+
+/// Page of liquidatable positions
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidatablePositionsPage {
+    /// Liquidatable positions among the inspected slots
+    pub positions: Vec<LiquidatablePosition>,
+    /// Cursor of the next page, if slots are left
+    pub next_cursor: Option<u32>,
+    /// Number of indexed borrowers
+    pub indexed_borrowers: u32,
+}
+
+fn get_indexed_borrowers(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get::<LiquidationIndexDataKey, Vec<Address>>(&LiquidationIndexDataKey::IndexedBorrowers)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn get_position(env: &Env, user: &Address) -> Option<Position> {
+    env.storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+}
+
+/// Add or remove a borrower according to their current debt
+///
+/// Called after every interaction that can open or clear core debt.
+pub(crate) fn sync_borrower(env: &Env, user: &Address) {
+    let has_debt = get_position(env, user)
+        .map(|position| position.debt > 0 || position.borrow_interest > 0)
+        .unwrap_or(false);
+    let slot_key = LiquidationIndexDataKey::BorrowerSlot(user.clone());
+    let slot = env
+        .storage()
+        .persistent()
+        .get::<LiquidationIndexDataKey, u32>(&slot_key);
+
+    match (has_debt, slot) {
+        (true, None) => {
+            let mut borrowers = get_indexed_borrowers(env);
+            env.storage().persistent().set(&slot_key, &borrowers.len());
+            borrowers.push_back(user.clone());
+            env.storage()
+                .persistent()
+                .set(&LiquidationIndexDataKey::IndexedBorrowers, &borrowers);
+        }
+        (false, Some(slot)) => {
+            let mut borrowers = get_indexed_borrowers(env);
+            let last = borrowers.pop_back();
+            if let Some(last) = last.filter(|last| last != user) {
+                borrowers.set(slot, last.clone());
+                env.storage()
+                    .persistent()
+                    .set(&LiquidationIndexDataKey::BorrowerSlot(last), &slot);
+            }
+            env.storage().persistent().remove(&slot_key);
+            env.storage()
+                .persistent()
+                .set(&LiquidationIndexDataKey::IndexedBorrowers, &borrowers);
+        }
+        _ => {}
+    }
+}
+
+/// Get the number of borrowers in the index
+pub fn get_indexed_borrower_count(env: &Env) -> u32 {
+    get_indexed_borrowers(env).len()
+}
+
+/// Get a page of liquidatable core positions
+///
+/// # Arguments
+/// * `cursor` - Registry slot to start at (0 for the first page)
+/// * `limit` - Slots to inspect (0 or above `MAX_INDEX_PAGE` = the maximum)
+pub fn get_liquidatable_positions(env: &Env, cursor: u32, limit: u32) -> LiquidatablePositionsPage {
+    let limit = if limit == 0 || limit > MAX_INDEX_PAGE {
+        MAX_INDEX_PAGE
+    } else {
+        limit
+    };
+
+    let borrowers = get_indexed_borrowers(env);
+    let count = borrowers.len();
+    let end = cursor.saturating_add(limit).min(count);
+    let mut positions = Vec::new(env);
+    for i in cursor..end {
+        let borrower = borrowers.get(i).unwrap();
+        let position = match get_position(env, &borrower) {
+            Some(position) => position,
+            None => continue,
+        };
+        let debt = position.debt.saturating_add(position.borrow_interest);
+        if !matches!(
+            crate::risk_params::can_be_liquidated(env, position.collateral, debt),
+            Ok(true)
+        ) {
+            continue;
+        }
+        let health_factor = position.collateral.saturating_mul(10_000) / debt;
+        positions.push_back(LiquidatablePosition {
+            borrower,
+            collateral: position.collateral,
+            debt,
+            health_factor,
+            risk_level: crate::analytics::calculate_user_risk_level(health_factor),
+        });
+    }
+    let next_cursor = if end < count { Some(end) } else { None };
+
+    LiquidatablePositionsPage {
+        positions,
+        next_cursor,
+        indexed_borrowers: count,
+    }
+}
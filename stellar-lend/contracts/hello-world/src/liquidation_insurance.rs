@@ -0,0 +1,517 @@
+//! # Liquidation Insurance Module
+//!
+//! Optional per-position protection against liquidation.
+//!
+//! A borrower buys a policy covering up to `coverage` units of their debt asset.
+//! While the policy is active an insurance premium accrues on their outstanding
+//! principal at `premium_bps` per year, on top of the protocol borrow rate. The
+//! premium is added to the position's borrow interest and only credited to the
+//! insurance fund of the debt asset once it is repaid: repaid interest settles
+//! the premium owed first, whether it comes from the borrower, a liquidator or
+//! an auction bidder.
+//!
+//! ## Claims
+//! When a liquidator targets an insured position that has fallen into
+//! liquidation range, the insurance fund first repays the borrower's debt —
+//! just enough to lift the position back above the liquidation threshold, and
+//! never more than the remaining coverage or the fund balance. Only if the
+//! position is still liquidatable after the claim does the liquidation proceed.
+//!
+//! ## Invariants
+//! - Coverage never exceeds the admin-configured `max_coverage`.
+//! - Claims are bounded by the remaining coverage and the fund balance.
+//! - The fund only holds top-ups and premiums actually repaid.
+//! - A policy only protects debt in the asset it was bought for.
+//! - Premiums stop accruing once a policy is cancelled or its coverage is used up.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{
+    emit_insurance_claimed, emit_insurance_fund_deposit, emit_insurance_policy_cancelled,
+    emit_insurance_policy_purchased, InsuranceClaimedEvent, InsuranceFundDepositEvent,
+    InsurancePolicyCancelledEvent, InsurancePolicyPurchasedEvent,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Maximum annual premium (20%)
+pub const MAX_PREMIUM_BPS: i128 = 2_000;
+
+/// Errors that can occur during liquidation insurance operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LiquidationInsuranceError {
+    /// Caller is not authorized (not admin)
+    Unauthorized = 1,
+    /// Amount must be greater than zero
+    InvalidAmount = 2,
+    /// Insurance configuration is invalid
+    InvalidConfig = 3,
+    /// Liquidation insurance is not enabled
+    InsuranceDisabled = 4,
+    /// Borrower has no open position
+    NoPosition = 5,
+    /// Requested coverage exceeds the maximum allowed
+    CoverageExceeded = 6,
+    /// Borrower has no active policy
+    PolicyNotFound = 7,
+    /// Overflow occurred during calculation
+    Overflow = 8,
+}
+
+/// Storage keys for liquidation insurance data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum InsuranceDataKey {
+    /// Global insurance configuration: LiquidationInsuranceConfig
+    InsuranceConfig,
+    /// Policy held by a borrower: InsurancePolicy
+    Policy(Address),
+    /// Insurance fund balance per asset: i128
+    Fund(Option<Address>),
+}
+
+/// Liquidation insurance configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationInsuranceConfig {
+    /// Annual premium charged on insured principal (bps)
+    pub premium_bps: i128,
+    /// Maximum coverage a single policy can carry, in debt asset units
+    pub max_coverage: i128,
+    /// Whether new policies can be bought
+    pub enabled: bool,
+}
+
+/// Liquidation protection held by a borrower
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsurancePolicy {
+    /// Debt asset the policy pays out in (None for native XLM)
+    pub debt_asset: Option<Address>,
+    /// Coverage bought, in debt asset units
+    pub coverage: i128,
+    /// Coverage not yet paid out
+    pub remaining_coverage: i128,
+    /// Annual premium locked in when the policy was bought (bps)
+    pub premium_bps: i128,
+    /// Cumulative premiums charged to the position
+    pub premiums_paid: i128,
+    /// Premiums charged but not yet repaid; credited to the fund on repayment
+    pub premiums_owed: i128,
+    /// Last time the premium was accrued
+    pub last_premium_time: u64,
+    /// Whether the policy still protects the position
+    pub active: bool,
+}
+
+/// Get the liquidation insurance configuration
+pub fn get_insurance_config(env: &Env) -> Option<LiquidationInsuranceConfig> {
+    env.storage()
+        .persistent()
+        .get::<InsuranceDataKey, LiquidationInsuranceConfig>(&InsuranceDataKey::InsuranceConfig)
+}
+
+/// Get the policy held by a borrower
+pub fn get_insurance_policy(env: &Env, user: &Address) -> Option<InsurancePolicy> {
+    env.storage()
+        .persistent()
+        .get::<InsuranceDataKey, InsurancePolicy>(&InsuranceDataKey::Policy(user.clone()))
+}
+
+/// Get the insurance fund balance for an asset
+pub fn get_insurance_fund(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<InsuranceDataKey, i128>(&InsuranceDataKey::Fund(asset.clone()))
+        .unwrap_or(0)
+}
+
+fn set_insurance_fund(env: &Env, asset: &Option<Address>, balance: i128) {
+    env.storage()
+        .persistent()
+        .set(&InsuranceDataKey::Fund(asset.clone()), &balance);
+}
+
+fn save_policy(env: &Env, user: &Address, policy: &InsurancePolicy) {
+    env.storage()
+        .persistent()
+        .set(&InsuranceDataKey::Policy(user.clone()), policy);
+}
+
+/// Configure liquidation insurance (admin only)
+///
+/// # Arguments
+/// * `caller` - The caller address (must be admin)
+/// * `premium_bps` - Annual premium on insured principal (0 - MAX_PREMIUM_BPS)
+/// * `max_coverage` - Maximum coverage per policy
+/// * `enabled` - Whether new policies can be bought
+///
+/// # Errors
+/// * `LiquidationInsuranceError::Unauthorized` - If caller is not admin
+/// * `LiquidationInsuranceError::InvalidConfig` - If premium or coverage is out of range
+pub fn set_insurance_config(
+    env: &Env,
+    caller: Address,
+    premium_bps: i128,
+    max_coverage: i128,
+    enabled: bool,
+) -> Result<(), LiquidationInsuranceError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller)
+        .map_err(|_| LiquidationInsuranceError::Unauthorized)?;
+
+    if !(0..=MAX_PREMIUM_BPS).contains(&premium_bps) || max_coverage <= 0 {
+        return Err(LiquidationInsuranceError::InvalidConfig);
+    }
+
+    let config = LiquidationInsuranceConfig {
+        premium_bps,
+        max_coverage,
+        enabled,
+    };
+    env.storage()
+        .persistent()
+        .set(&InsuranceDataKey::InsuranceConfig, &config);
+    Ok(())
+}
+
+/// Add funds to the insurance fund of an asset
+///
+/// Anyone can top up the fund; premiums are credited to it as they are repaid.
+/// The amount is always transferred from `funder`; native XLM settles through
+/// its Stellar Asset Contract.
+///
+/// # Returns
+/// Returns the new fund balance
+///
+/// # Errors
+/// * `LiquidationInsuranceError::InvalidAmount` - If amount is not positive,
+///   or the asset is native XLM and no native asset address is configured
+pub fn fund_insurance(
+    env: &Env,
+    funder: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<i128, LiquidationInsuranceError> {
+    funder.require_auth();
+    if amount <= 0 {
+        return Err(LiquidationInsuranceError::InvalidAmount);
+    }
+
+    let asset = crate::deposit::canonical_asset(env, asset);
+    let token_addr = crate::deposit::resolve_token_address(env, &asset)
+        .ok_or(LiquidationInsuranceError::InvalidAmount)?;
+    let token_client = soroban_sdk::token::Client::new(env, &token_addr);
+    token_client.transfer(&funder, env.current_contract_address(), &amount);
+
+    let balance = get_insurance_fund(env, &asset)
+        .checked_add(amount)
+        .ok_or(LiquidationInsuranceError::Overflow)?;
+    set_insurance_fund(env, &asset, balance);
+
+    emit_insurance_fund_deposit(
+        env,
+        InsuranceFundDepositEvent {
+            funder,
+            asset,
+            amount,
+            balance,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(balance)
+}
+
+/// Accrue the insurance premium on a position
+///
+/// Adds the premium owed since the last accrual to the position's borrow
+/// interest; it reaches the insurance fund once repaid (see
+/// `collect_premium`). The caller is responsible for persisting the position.
+///
+/// # Returns
+/// Returns the premium charged
+pub(crate) fn accrue_premium(
+    env: &Env,
+    user: &Address,
+    position: &mut Position,
+) -> Result<i128, LiquidationInsuranceError> {
+    let mut policy = match get_insurance_policy(env, user) {
+        Some(policy) if policy.active => policy,
+        _ => return Ok(0),
+    };
+
+    let now = env.ledger().timestamp();
+    let premium = crate::interest_rate::calculate_accrued_interest(
+        position.debt,
+        policy.last_premium_time,
+        now,
+        policy.premium_bps,
+    )
+    .map_err(|_| LiquidationInsuranceError::Overflow)?;
+
+    policy.last_premium_time = now;
+    // Interest written off since the last accrual takes its premium with it
+    policy.premiums_owed = policy.premiums_owed.min(position.borrow_interest);
+    if premium > 0 {
+        position.borrow_interest = position
+            .borrow_interest
+            .checked_add(premium)
+            .ok_or(LiquidationInsuranceError::Overflow)?;
+        policy.premiums_paid = policy
+            .premiums_paid
+            .checked_add(premium)
+            .ok_or(LiquidationInsuranceError::Overflow)?;
+        policy.premiums_owed = policy
+            .premiums_owed
+            .checked_add(premium)
+            .ok_or(LiquidationInsuranceError::Overflow)?;
+    }
+    save_policy(env, user, &policy);
+
+    Ok(premium)
+}
+
+/// Credit repaid interest to the insurance fund, up to the premium owed
+///
+/// Repaid interest settles the premium owed before the protocol's interest.
+/// Called wherever a borrower's interest is repaid in `asset`; premiums owed
+/// on a cancelled policy are still collected.
+///
+/// # Returns
+/// Returns the part of `interest_paid` credited to the fund
+pub(crate) fn collect_premium(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    interest_paid: i128,
+) -> Result<i128, LiquidationInsuranceError> {
+    if interest_paid <= 0 {
+        return Ok(0);
+    }
+    let mut policy = match get_insurance_policy(env, user) {
+        Some(policy) if policy.premiums_owed > 0 && &policy.debt_asset == asset => policy,
+        _ => return Ok(0),
+    };
+
+    let collected = interest_paid.min(policy.premiums_owed);
+    policy.premiums_owed -= collected;
+    save_policy(env, user, &policy);
+
+    let balance = get_insurance_fund(env, asset)
+        .checked_add(collected)
+        .ok_or(LiquidationInsuranceError::Overflow)?;
+    set_insurance_fund(env, asset, balance);
+    Ok(collected)
+}
+
+/// Premium accrued on a borrower's debt since the last accrual, without booking it
+pub(crate) fn pending_premium(
+    env: &Env,
+    user: &Address,
+    position: &Position,
+) -> Result<i128, LiquidationInsuranceError> {
+    match get_insurance_policy(env, user) {
+        Some(policy) if policy.active => crate::interest_rate::calculate_accrued_interest(
+            position.debt,
+            policy.last_premium_time,
+            env.ledger().timestamp(),
+            policy.premium_bps,
+        )
+        .map_err(|_| LiquidationInsuranceError::Overflow),
+        _ => Ok(0),
+    }
+}
+
+/// Accrue the premium on a borrower's stored position
+fn settle_premium(env: &Env, user: &Address) -> Result<(), LiquidationInsuranceError> {
+    let position_key = DepositDataKey::Position(user.clone());
+    if let Some(mut position) = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+    {
+        if accrue_premium(env, user, &mut position)? > 0 {
+            env.storage().persistent().set(&position_key, &position);
+        }
+    }
+    Ok(())
+}
+
+/// Buy (or replace) liquidation protection for the caller's position
+///
+/// Any premium owed on an existing policy is settled first. The new policy
+/// locks in the currently configured premium rate.
+///
+/// # Arguments
+/// * `user` - The borrower
+/// * `debt_asset` - The asset the policy pays out in (None for native XLM)
+/// * `coverage` - Maximum debt the fund will repay on the borrower's behalf
+///
+/// # Errors
+/// * `LiquidationInsuranceError::InsuranceDisabled` - If insurance is not configured or disabled
+/// * `LiquidationInsuranceError::InvalidAmount` - If coverage is zero or negative
+/// * `LiquidationInsuranceError::CoverageExceeded` - If coverage exceeds `max_coverage`
+/// * `LiquidationInsuranceError::NoPosition` - If the user has no position
+pub fn buy_insurance(
+    env: &Env,
+    user: Address,
+    debt_asset: Option<Address>,
+    coverage: i128,
+) -> Result<InsurancePolicy, LiquidationInsuranceError> {
+    user.require_auth();
+
+    let config = get_insurance_config(env)
+        .filter(|c| c.enabled)
+        .ok_or(LiquidationInsuranceError::InsuranceDisabled)?;
+    if coverage <= 0 {
+        return Err(LiquidationInsuranceError::InvalidAmount);
+    }
+    if coverage > config.max_coverage {
+        return Err(LiquidationInsuranceError::CoverageExceeded);
+    }
+    if !env
+        .storage()
+        .persistent()
+        .has(&DepositDataKey::Position(user.clone()))
+    {
+        return Err(LiquidationInsuranceError::NoPosition);
+    }
+
+    settle_premium(env, &user)?;
+    let (premiums_paid, premiums_owed) = get_insurance_policy(env, &user)
+        .map(|p| (p.premiums_paid, p.premiums_owed))
+        .unwrap_or((0, 0));
+
+    let now = env.ledger().timestamp();
+    let policy = InsurancePolicy {
+        debt_asset: debt_asset.clone(),
+        coverage,
+        remaining_coverage: coverage,
+        premium_bps: config.premium_bps,
+        premiums_paid,
+        premiums_owed,
+        last_premium_time: now,
+        active: true,
+    };
+    save_policy(env, &user, &policy);
+
+    emit_insurance_policy_purchased(
+        env,
+        InsurancePolicyPurchasedEvent {
+            user,
+            debt_asset,
+            coverage,
+            premium_bps: config.premium_bps,
+            timestamp: now,
+        },
+    );
+
+    Ok(policy)
+}
+
+/// Cancel the caller's policy after settling the premium owed
+pub fn cancel_insurance(env: &Env, user: Address) -> Result<(), LiquidationInsuranceError> {
+    user.require_auth();
+
+    match get_insurance_policy(env, &user) {
+        Some(policy) if policy.active => {}
+        _ => return Err(LiquidationInsuranceError::PolicyNotFound),
+    }
+    settle_premium(env, &user)?;
+
+    let mut policy =
+        get_insurance_policy(env, &user).ok_or(LiquidationInsuranceError::PolicyNotFound)?;
+    policy.active = false;
+    save_policy(env, &user, &policy);
+
+    emit_insurance_policy_cancelled(
+        env,
+        InsurancePolicyCancelledEvent {
+            user,
+            premiums_paid: policy.premiums_paid,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Pay out an insured borrower's claim ahead of a liquidation
+///
+/// Repays just enough of the position's debt (interest first, then principal)
+/// to lift it back above the liquidation threshold, bounded by the remaining
+/// coverage and the fund balance. The caller is responsible for persisting
+/// the position.
+///
+/// # Arguments
+/// * `borrower` - The borrower being liquidated
+/// * `position` - The borrower's position with interest already accrued
+/// * `debt_asset` - The debt asset being liquidated
+/// * `collateral_value` - Collateral value in debt asset terms
+///
+/// # Returns
+/// Returns the amount of debt repaid by the fund
+pub(crate) fn apply_protection(
+    env: &Env,
+    borrower: &Address,
+    position: &mut Position,
+    debt_asset: &Option<Address>,
+    collateral_value: i128,
+) -> Result<i128, LiquidationInsuranceError> {
+    let mut policy = match get_insurance_policy(env, borrower) {
+        Some(policy) if policy.active && &policy.debt_asset == debt_asset => policy,
+        _ => return Ok(0),
+    };
+
+    let total_debt = position
+        .debt
+        .checked_add(position.borrow_interest)
+        .ok_or(LiquidationInsuranceError::Overflow)?;
+    let threshold = crate::risk_params::get_liquidation_threshold(env)
+        .map_err(|_| LiquidationInsuranceError::InvalidConfig)?;
+
+    // Largest debt that keeps the position out of liquidation range
+    let safe_debt = collateral_value
+        .checked_mul(BASIS_POINTS_SCALE)
+        .ok_or(LiquidationInsuranceError::Overflow)?
+        .checked_div(threshold)
+        .ok_or(LiquidationInsuranceError::Overflow)?;
+    let needed = total_debt.saturating_sub(safe_debt).max(0);
+
+    let fund_balance = get_insurance_fund(env, debt_asset);
+    let payout = needed.min(policy.remaining_coverage).min(fund_balance);
+    if payout <= 0 {
+        return Ok(0);
+    }
+
+    let interest_paid = payout.min(position.borrow_interest);
+    position.borrow_interest -= interest_paid;
+    position.debt -= payout - interest_paid;
+
+    set_insurance_fund(env, debt_asset, fund_balance - payout);
+    policy.remaining_coverage -= payout;
+    if policy.remaining_coverage == 0 {
+        policy.active = false;
+    }
+    save_policy(env, borrower, &policy);
+    collect_premium(env, borrower, debt_asset, interest_paid)?;
+
+    emit_insurance_claimed(
+        env,
+        InsuranceClaimedEvent {
+            borrower: borrower.clone(),
+            debt_asset: debt_asset.clone(),
+            amount: payout,
+            remaining_coverage: policy.remaining_coverage,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(payout)
+}
@@ -0,0 +1,407 @@
+//! # LP Collateral Module
+//!
+//! Lets liquidity providers of the protocol's native AMM pools (see
+//! `stellarlend_amm::pools`) pledge their LP shares as cross-asset
+//! collateral and borrow against them.
+//!
+//! ## Fair Value
+//! Pool reserves can be pushed around by a swap in the same transaction, so
+//! shares are not valued at their spot reserves. Each side of a pool is
+//! valued at the geometric mean of both sides' oracle values:
+//!
+//! `side_value = sqrt(B_a * p_a * B_b * p_b)`
+//!
+//! which only depends on the pool invariant `B_a * B_b` and the oracle
+//! prices. Spot reserves are always worth at least `2 * side_value`, so
+//! moving the reserves can only lower the value of pledged shares. Pledged
+//! shares are worth `side_value * shares / total_shares` on each side.
+//!
+//! ## Haircuts
+//! Each side of a pledge is one leg of the user's cross-asset portfolio (see
+//! `cross_asset::get_portfolio`), valued at fair value less the pool token's
+//! LP haircut (`reserve::set_lp_haircut`) and weighted by the token's
+//! collateral factor and liquidation threshold.
+//!
+//! ## Liquidation
+//! `liquidate_lp_collateral` repays debt of a liquidatable portfolio and
+//! moves pledged shares worth the repayment plus the liquidation incentive,
+//! at fair value before haircuts, to the liquidator's own pledge in the pool.
+//!
+//! ## Invariants
+//! - Pledged shares are held by this contract in the pool until withdrawn.
+//! - Shares can only be pledged while both pool tokens are registered,
+//!   enabled as collateral and not frozen.
+//! - Withdrawals are rejected if they would lower health factor below 1.0.
+
+#![allow(unused)]
+use soroban_sdk::{contracttype, Address, Env, Vec};
+use stellarlend_amm::ConstantProductPool;
+
+use crate::cross_asset::{
+    get_asset_config, get_reserve_price, get_user_asset_position, get_user_position_summary,
+    AssetConfig, AssetKey, CrossAssetError, PortfolioLeg,
+};
+
+/// Storage keys for pledged LP shares
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum LpCollateralDataKey {
+    /// Shares of a pool pledged by a user: PledgedLpShares(user, pool_id) -> i128
+    PledgedLpShares(Address, u32),
+    /// Pools a user has pledged shares of: PledgedLpPools(user) -> Vec<u32>
+    PledgedLpPools(Address),
+}
+
+/// Get the LP shares of a pool a user has pledged as collateral
+pub fn get_pledged_shares(env: &Env, user: &Address, pool_id: u32) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&LpCollateralDataKey::PledgedLpShares(user.clone(), pool_id))
+        .unwrap_or(0)
+}
+
+/// Get the pools a user has pledged LP shares of
+pub fn get_pledged_pools(env: &Env, user: &Address) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&LpCollateralDataKey::PledgedLpPools(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_pledged_shares(env: &Env, user: &Address, pool_id: u32, shares: i128) {
+    let key = LpCollateralDataKey::PledgedLpShares(user.clone(), pool_id);
+    let mut pools = get_pledged_pools(env, user);
+    let index = pools.first_index_of(pool_id);
+    if shares == 0 {
+        env.storage().persistent().remove(&key);
+        if let Some(index) = index {
+            pools.remove(index);
+        }
+    } else {
+        env.storage().persistent().set(&key, &shares);
+        if index.is_none() {
+            pools.push_back(pool_id);
+        }
+    }
+
+    let pools_key = LpCollateralDataKey::PledgedLpPools(user.clone());
+    if pools.is_empty() {
+        env.storage().persistent().remove(&pools_key);
+    } else {
+        env.storage().persistent().set(&pools_key, &pools);
+    }
+}
+
+/// Move every pledge of one user to another (see `cross_asset::move_user_positions`)
+pub(crate) fn move_pledges(env: &Env, from: &Address, to: &Address) {
+    for pool_id in get_pledged_pools(env, from).iter() {
+        let shares = get_pledged_shares(env, from, pool_id);
+        set_pledged_shares(env, from, pool_id, 0);
+        set_pledged_shares(
+            env,
+            to,
+            pool_id,
+            get_pledged_shares(env, to, pool_id) + shares,
+        );
+    }
+}
+
+fn get_pool(env: &Env, pool_id: u32) -> Result<ConstantProductPool, CrossAssetError> {
+    stellarlend_amm::pools::get_pool(env, pool_id).map_err(|_| CrossAssetError::LpPoolNotFound)
+}
+
+/// Integer square root (floor)
+fn sqrt(value: i128) -> i128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// A native pool priced at its tokens' oracle prices
+struct PricedPool {
+    pool: ConstantProductPool,
+    config_a: AssetConfig,
+    config_b: AssetConfig,
+    price_a: i128,
+    price_b: i128,
+    /// Fair value of each side of the whole pool, in USD (7 decimals)
+    side_value: i128,
+}
+
+fn price_pool(env: &Env, pool: ConstantProductPool) -> Result<PricedPool, CrossAssetError> {
+    let asset_a = Some(pool.token_a.clone());
+    let asset_b = Some(pool.token_b.clone());
+    let config_a = get_asset_config(env, &AssetKey::from_option(asset_a.clone()))?;
+    let config_b = get_asset_config(env, &AssetKey::from_option(asset_b.clone()))?;
+    let price_a = get_reserve_price(env, &asset_a, &config_a)?;
+    let price_b = get_reserve_price(env, &asset_b, &config_b)?;
+
+    let value_a = pool.reserve_a.saturating_mul(price_a) / 10_000_000;
+    let value_b = pool.reserve_b.saturating_mul(price_b) / 10_000_000;
+    let side_value = match value_a.checked_mul(value_b) {
+        Some(product) => sqrt(product),
+        None => sqrt(value_a).saturating_mul(sqrt(value_b)),
+    };
+
+    Ok(PricedPool {
+        pool,
+        config_a,
+        config_b,
+        price_a,
+        price_b,
+        side_value,
+    })
+}
+
+/// Fair value of LP shares of a native pool, before haircuts.
+///
+/// # Returns
+/// The value of both sides in USD (7 decimals).
+///
+/// # Errors
+/// * `LpPoolNotFound` - The pool does not exist
+/// * `AssetNotConfigured` - A pool token is not registered
+/// * `PriceStale` / `InvalidPrice` - A pool token has no usable price
+pub fn get_lp_fair_value(env: &Env, pool_id: u32, shares: i128) -> Result<i128, CrossAssetError> {
+    let priced = price_pool(env, get_pool(env, pool_id)?)?;
+    Ok(priced.side_value.saturating_mul(2).saturating_mul(shares) / priced.pool.total_shares)
+}
+
+/// Append one portfolio leg per pool token of each of a user's pledges
+///
+/// # Errors
+/// * `PriceStale` / `InvalidPrice` - A pool token has no usable price
+pub(crate) fn append_portfolio_legs(
+    env: &Env,
+    user: &Address,
+    legs: &mut Vec<PortfolioLeg>,
+) -> Result<(), CrossAssetError> {
+    for pool_id in get_pledged_pools(env, user).iter() {
+        let shares = get_pledged_shares(env, user, pool_id);
+        let priced = price_pool(env, get_pool(env, pool_id)?)?;
+        let value = priced.side_value.saturating_mul(shares) / priced.pool.total_shares;
+
+        let sides = [
+            (&priced.pool.token_a, &priced.config_a, priced.price_a),
+            (&priced.pool.token_b, &priced.config_b, priced.price_b),
+        ];
+        for (token, config, price) in sides {
+            let asset = Some(token.clone());
+            let haircut = crate::reserve::get_lp_haircut(env, &asset);
+            let (collateral_factor, liquidation_threshold) = if config.can_collateralize {
+                (config.collateral_factor, config.liquidation_threshold)
+            } else {
+                (0, 0)
+            };
+            legs.push_back(PortfolioLeg {
+                asset,
+                price,
+                collateral_value: value.saturating_mul(10_000 - haircut) / 10_000,
+                debt_value: 0,
+                collateral_factor,
+                liquidation_threshold,
+                lp_pool: Some(pool_id),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Pledge LP shares of a native pool as collateral.
+///
+/// Requires user authorization. The shares move from the user to this
+/// contract and count towards the user's cross-asset portfolio.
+///
+/// # Returns
+/// The user's pledged shares of the pool after the deposit.
+///
+/// # Errors
+/// * `InvalidAmount` - `shares` is not positive
+/// * `LpPoolNotFound` - The pool does not exist
+/// * `AssetNotConfigured` - A pool token is not registered
+/// * `AssetDisabled` - A pool token is not enabled for collateral or is frozen
+/// * `InsufficientCollateral` - The user holds fewer than `shares` LP shares
+/// * `OperationPaused` - Deposits are paused
+pub fn deposit_lp_collateral(
+    env: &Env,
+    user: Address,
+    pool_id: u32,
+    shares: i128,
+) -> Result<i128, CrossAssetError> {
+    user.require_auth();
+    crate::cross_asset::require_not_paused(env, "pause_deposit")?;
+    if shares <= 0 {
+        return Err(CrossAssetError::InvalidAmount);
+    }
+
+    let pool = get_pool(env, pool_id)?;
+    for token in [pool.token_a, pool.token_b] {
+        let asset = Some(token);
+        let config = get_asset_config(env, &AssetKey::from_option(asset.clone()))?;
+        if !config.can_collateralize || crate::risk_management::is_asset_frozen(env, &asset) {
+            return Err(CrossAssetError::AssetDisabled);
+        }
+    }
+
+    let contract = env.current_contract_address();
+    stellarlend_amm::pools::move_lp_shares(env, user.clone(), contract, pool_id, shares)
+        .map_err(|_| CrossAssetError::InsufficientCollateral)?;
+
+    let pledged = get_pledged_shares(env, &user, pool_id) + shares;
+    set_pledged_shares(env, &user, pool_id, pledged);
+    crate::health_history::record_cross_asset_snapshot(env, &user, "lp_deposit");
+
+    Ok(pledged)
+}
+
+/// Withdraw pledged LP shares of a native pool.
+///
+/// Requires user authorization. If the user has debt, the withdrawal is
+/// rolled back when it would lower their health factor below 1.0.
+///
+/// # Returns
+/// The user's pledged shares of the pool after the withdrawal.
+///
+/// # Errors
+/// * `InvalidAmount` - `shares` is not positive
+/// * `InsufficientCollateral` - The user pledged fewer than `shares` shares
+/// * `UnhealthyPosition` - Withdrawal would drop health factor below 1.0
+/// * `PriceStale` - Stale price prevents health factor calculation
+/// * `OperationPaused` - Withdrawals are paused, unless the protocol is in
+///   emergency withdrawal mode and the user has no debt
+pub fn withdraw_lp_collateral(
+    env: &Env,
+    user: Address,
+    pool_id: u32,
+    shares: i128,
+) -> Result<i128, CrossAssetError> {
+    user.require_auth();
+    let debt_free = !crate::cross_asset::has_debt(env, &user);
+    let emergency_exit = debt_free && crate::risk_management::is_emergency_withdrawal_mode(env);
+    if !emergency_exit {
+        crate::cross_asset::require_not_paused(env, "pause_withdraw")?;
+    }
+    if shares <= 0 {
+        return Err(CrossAssetError::InvalidAmount);
+    }
+
+    let pledged = get_pledged_shares(env, &user, pool_id);
+    if pledged < shares {
+        return Err(CrossAssetError::InsufficientCollateral);
+    }
+    set_pledged_shares(env, &user, pool_id, pledged - shares);
+
+    if !debt_free {
+        let summary = get_user_position_summary(env, &user)?;
+        if summary.total_debt_value > 0 && summary.health_factor < 10_000 {
+            set_pledged_shares(env, &user, pool_id, pledged);
+            return Err(CrossAssetError::UnhealthyPosition);
+        }
+    }
+
+    let contract = env.current_contract_address();
+    stellarlend_amm::pools::move_lp_shares(env, contract, user.clone(), pool_id, shares)
+        .map_err(|_| CrossAssetError::InsufficientCollateral)?;
+    crate::health_history::record_cross_asset_snapshot(env, &user, "lp_withdraw");
+
+    Ok(pledged - shares)
+}
+
+/// Liquidate pledged LP shares of an unhealthy portfolio.
+///
+/// Requires liquidator authorization. Repays `debt_amount` of the borrower's
+/// `debt_asset` debt (interest first) and moves pledged shares of the pool
+/// worth the repayment plus the liquidation incentive, at fair value before
+/// haircuts, to the liquidator's pledge, capped at the borrower's pledge.
+///
+/// # Returns
+/// `(debt_repaid, shares_seized)`
+///
+/// # Errors
+/// * `InvalidAmount` - `debt_amount` is not positive
+/// * `NotAuthorized` - The liquidator is the borrower
+/// * `NotLiquidatable` - The portfolio is healthy or owes no `debt_asset`
+/// * `ExceedsCloseFactor` - `debt_amount` exceeds the close factor
+/// * `InsufficientCollateral` - The borrower pledged no shares of the pool
+/// * `PriceStale` - Stale price prevents health factor calculation
+/// * `OperationPaused` - Liquidations are paused
+pub fn liquidate_lp_collateral(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_asset: Option<Address>,
+    pool_id: u32,
+    debt_amount: i128,
+) -> Result<(i128, i128), CrossAssetError> {
+    liquidator.require_auth();
+    crate::cross_asset::require_not_paused(env, "pause_liquidate")?;
+    if debt_amount <= 0 {
+        return Err(CrossAssetError::InvalidAmount);
+    }
+    if liquidator == borrower {
+        return Err(CrossAssetError::NotAuthorized);
+    }
+
+    let summary = get_user_position_summary(env, &borrower)?;
+    if !summary.is_liquidatable {
+        return Err(CrossAssetError::NotLiquidatable);
+    }
+
+    let debt_config = get_asset_config(env, &AssetKey::from_option(debt_asset.clone()))?;
+    let debt_position = get_user_asset_position(env, &borrower, debt_asset.clone());
+    let total_debt = debt_position
+        .debt_principal
+        .saturating_add(debt_position.accrued_interest);
+    if total_debt == 0 {
+        return Err(CrossAssetError::NotLiquidatable);
+    }
+    let close_factor =
+        crate::reserve::effective_close_factor(env, &debt_asset, summary.health_factor);
+    if debt_amount > total_debt.saturating_mul(close_factor) / 10_000 {
+        return Err(CrossAssetError::ExceedsCloseFactor);
+    }
+
+    let pledged = get_pledged_shares(env, &borrower, pool_id);
+    if pledged == 0 {
+        return Err(CrossAssetError::InsufficientCollateral);
+    }
+    let priced = price_pool(env, get_pool(env, pool_id)?)?;
+    let pool_value = priced.side_value.saturating_mul(2);
+    if pool_value == 0 {
+        return Err(CrossAssetError::InvalidPrice);
+    }
+    let debt_price = get_reserve_price(env, &debt_asset, &debt_config)?;
+    let incentive_bps = crate::cross_asset::liquidation_incentive(env);
+    let seized_value = debt_amount
+        .saturating_mul(debt_price)
+        .saturating_mul(10_000 + incentive_bps)
+        / 100_000_000_000;
+    let shares_seized =
+        (seized_value.saturating_mul(priced.pool.total_shares) / pool_value).min(pledged);
+
+    crate::cross_asset::repay_liquidated_debt(
+        env,
+        &borrower,
+        &debt_asset,
+        debt_position,
+        debt_amount,
+    );
+    set_pledged_shares(env, &borrower, pool_id, pledged - shares_seized);
+    set_pledged_shares(
+        env,
+        &liquidator,
+        pool_id,
+        get_pledged_shares(env, &liquidator, pool_id) + shares_seized,
+    );
+    crate::health_history::record_cross_asset_snapshot(env, &borrower, "lp_liquidate");
+
+    Ok((debt_amount, shares_seized))
+}
@@ -0,0 +1,364 @@
+//! # Position Migration Module
+//!
+//! Moves a user's core position to an upgraded pool deployment without
+//! repaying first. `migrate_position` settles the position's supply and
+//! borrow interest, removes it from this pool and re-creates it in the new
+//! pool through its `receive_migrated_position` entrypoint, all within one
+//! transaction: if the new pool rejects the position, nothing moves.
+//!
+//! ## Configuration
+//! Both sides are set by their admins. The old pool names the deployment
+//! positions may migrate to (`set_migration_target`); the new pool lists the
+//! pools it accepts positions from (`set_migration_source`).
+//!
+//! ## Scope
+//! Only bookkeeping moves: collateral, debt principal and booked interest.
+//! The tokens backing the migrated collateral and debt stay in this pool and
+//! are moved between deployments by the protocol operator. Core positions do
+//! not record which asset they hold, so per-asset supply and borrow totals of
+//! the reserves are not adjusted; pool-wide analytics are. Cross-asset
+//! positions are not migrated.
+//!
+//! ## Invariants
+//! - A position is either in this pool or in the target, never in both.
+//! - Interest is settled up to the migration, and the new pool accrues from
+//!   its own indexes afterwards.
+//! - A position token wrapping the position is burned when it leaves.
+//! - Positions in a liquidation auction or inside the action delay cannot
+//!   migrate.
+//! - A pool only accepts positions from listed sources, and only for users
+//!   without a position in it.
+
+#![allow(unused)]
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env, Vec};
+
+use crate::action_delay::ActionDelayDataKey;
+use crate::deposit::{DepositDataKey, Position, ProtocolAnalytics};
+use crate::events::{
+    emit_migration_source_updated, emit_migration_target_updated, emit_position_migrated,
+    emit_position_received, MigrationSourceUpdatedEvent, MigrationTargetUpdatedEvent,
+    PositionMigratedEvent, PositionReceivedEvent,
+};
+use crate::interest_index::InterestIndexDataKey;
+use crate::liquidation_grace::LiquidationGraceDataKey;
+use crate::position_token::PositionTokenDataKey;
+use crate::reserve::ReserveDataKey;
+use crate::supply_interest::SupplyInterestDataKey;
+
+/// Errors that can occur during position migration
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MigrationError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// The pool is not the configured migration target
+    InvalidTarget = 2,
+    /// The user has no collateral or debt to migrate
+    NoPosition = 3,
+    /// The position is being liquidated by auction
+    PositionLocked = 4,
+    /// The position was funded too recently (see `action_delay`)
+    ActionDelayActive = 5,
+    /// The sending pool is not an accepted migration source
+    UnknownSource = 6,
+    /// The user already has a position in the receiving pool
+    PositionExists = 7,
+    /// Amounts are negative or the interest could not be settled
+    InvalidPosition = 8,
+}
+
+/// Storage keys for position migration data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum MigrationDataKey {
+    /// Deployment positions may migrate to: Address
+    MigrationTarget,
+    /// Whether positions are accepted from a pool: bool
+    MigrationSource(Address),
+}
+
+/// Entrypoint of the pool receiving migrated positions
+#[contractclient(name = "MigrationTargetClient")]
+pub trait MigrationTarget {
+    /// Re-create a position migrated from `source`
+    fn receive_migrated_position(
+        env: Env,
+        source: Address,
+        user: Address,
+        collateral: i128,
+        debt: i128,
+        borrow_interest: i128,
+    );
+}
+
+/// Set the deployment positions may migrate to (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `new_pool` - The upgraded pool (None disables migration)
+///
+/// # Errors
+/// * `MigrationError::Unauthorized` - If caller is not the admin
+pub fn set_migration_target(
+    env: &Env,
+    caller: Address,
+    new_pool: Option<Address>,
+) -> Result<(), MigrationError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| MigrationError::Unauthorized)?;
+
+    match &new_pool {
+        Some(pool) => env
+            .storage()
+            .persistent()
+            .set(&MigrationDataKey::MigrationTarget, pool),
+        None => env
+            .storage()
+            .persistent()
+            .remove(&MigrationDataKey::MigrationTarget),
+    }
+
+    emit_migration_target_updated(
+        env,
+        MigrationTargetUpdatedEvent {
+            admin: caller,
+            new_pool,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the deployment positions may migrate to
+pub fn get_migration_target(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<MigrationDataKey, Address>(&MigrationDataKey::MigrationTarget)
+}
+
+/// Accept or stop accepting positions from a pool (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `source` - The pool positions migrate from
+/// * `allowed` - Whether its positions are accepted
+///
+/// # Errors
+/// * `MigrationError::Unauthorized` - If caller is not the admin
+pub fn set_migration_source(
+    env: &Env,
+    caller: Address,
+    source: Address,
+    allowed: bool,
+) -> Result<(), MigrationError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| MigrationError::Unauthorized)?;
+
+    let key = MigrationDataKey::MigrationSource(source.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    emit_migration_source_updated(
+        env,
+        MigrationSourceUpdatedEvent {
+            admin: caller,
+            source,
+            allowed,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Whether positions are accepted from a pool
+pub fn is_migration_source(env: &Env, source: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<MigrationDataKey, bool>(&MigrationDataKey::MigrationSource(source.clone()))
+        .unwrap_or(false)
+}
+
+/// Apply a migrated position to the pool-wide analytics
+fn adjust_protocol_analytics(env: &Env, collateral: i128, debt: i128) {
+    let analytics_key = DepositDataKey::ProtocolAnalytics;
+    let mut analytics = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, ProtocolAnalytics>(&analytics_key)
+        .unwrap_or(ProtocolAnalytics {
+            total_deposits: 0,
+            total_borrows: 0,
+            total_value_locked: 0,
+        });
+    analytics.total_deposits = analytics.total_deposits.saturating_add(collateral).max(0);
+    analytics.total_borrows = analytics.total_borrows.saturating_add(debt).max(0);
+    analytics.total_value_locked = analytics
+        .total_value_locked
+        .saturating_add(collateral)
+        .max(0);
+    env.storage().persistent().set(&analytics_key, &analytics);
+}
+
+/// Migrate a user's core position to the upgraded pool
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `new_pool` - The configured migration target
+///
+/// # Returns
+/// Returns a tuple (collateral, debt) moved, with debt including interest
+///
+/// # Errors
+/// * `MigrationError::InvalidTarget` - If `new_pool` is not the migration target
+/// * `MigrationError::PositionLocked` - If the position is being auctioned
+/// * `MigrationError::ActionDelayActive` - If the user deposited too recently
+/// * `MigrationError::NoPosition` - If the user has no collateral or debt
+/// * `MigrationError::InvalidPosition` - If the interest cannot be settled
+pub fn migrate_position(
+    env: &Env,
+    user: Address,
+    new_pool: Address,
+) -> Result<(i128, i128), MigrationError> {
+    user.require_auth();
+
+    if get_migration_target(env).as_ref() != Some(&new_pool) {
+        return Err(MigrationError::InvalidTarget);
+    }
+    if crate::auction::get_auction(env, &user).is_some() {
+        return Err(MigrationError::PositionLocked);
+    }
+    if crate::action_delay::is_delayed(env, &user) {
+        return Err(MigrationError::ActionDelayActive);
+    }
+
+    // Bring supply and borrow interest up to the migration
+    crate::supply_interest::settle_supply_interest(env, &user);
+    let position_key = DepositDataKey::Position(user.clone());
+    let mut position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+        .ok_or(MigrationError::NoPosition)?;
+    crate::interest_index::accrue_position(env, &user, &mut position)
+        .map_err(|_| MigrationError::InvalidPosition)?;
+    if position.collateral <= 0 && position.debt <= 0 && position.borrow_interest <= 0 {
+        return Err(MigrationError::NoPosition);
+    }
+
+    let storage = env.storage().persistent();
+    storage.remove(&position_key);
+    storage.remove(&DepositDataKey::CollateralBalance(user.clone()));
+    storage.remove(&InterestIndexDataKey::BorrowSnapshot(user.clone()));
+    storage.remove(&SupplyInterestDataKey::UserSnapshot(user.clone()));
+    storage.remove(&ReserveDataKey::UserDebtAssets(user.clone()));
+    storage.remove(&ActionDelayDataKey::LastDepositLedger(user.clone()));
+    storage.remove(&LiquidationGraceDataKey::GraceStarted(user.clone()));
+    if let Some(token_id) = crate::position_token::get_position_token(env, &user) {
+        storage.remove(&PositionTokenDataKey::PositionTokenOwner(token_id));
+        storage.remove(&PositionTokenDataKey::PositionTokenOf(user.clone()));
+    }
+    crate::liquidation_index::sync_borrower(env, &user);
+    adjust_protocol_analytics(env, -position.collateral, -position.debt);
+
+    MigrationTargetClient::new(env, &new_pool).receive_migrated_position(
+        &env.current_contract_address(),
+        &user,
+        &position.collateral,
+        &position.debt,
+        &position.borrow_interest,
+    );
+
+    let debt = position.debt + position.borrow_interest;
+    emit_position_migrated(
+        env,
+        PositionMigratedEvent {
+            user,
+            new_pool,
+            collateral: position.collateral,
+            debt,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok((position.collateral, debt))
+}
+
+/// Re-create a position migrated from another pool
+///
+/// Called by the source pool during `migrate_position`. The position starts
+/// accruing from this pool's current indexes.
+///
+/// # Arguments
+/// * `source` - The pool the position comes from (must authorize)
+/// * `user` - The position owner
+/// * `collateral` - The collateral, including settled supply interest
+/// * `debt` - The debt principal
+/// * `borrow_interest` - The booked borrow interest
+///
+/// # Errors
+/// * `MigrationError::UnknownSource` - If `source` is not accepted
+/// * `MigrationError::InvalidPosition` - If an amount is negative
+/// * `MigrationError::PositionExists` - If the user already has a position
+pub fn receive_migrated_position(
+    env: &Env,
+    source: Address,
+    user: Address,
+    collateral: i128,
+    debt: i128,
+    borrow_interest: i128,
+) -> Result<(), MigrationError> {
+    source.require_auth();
+
+    if !is_migration_source(env, &source) {
+        return Err(MigrationError::UnknownSource);
+    }
+    if collateral < 0 || debt < 0 || borrow_interest < 0 {
+        return Err(MigrationError::InvalidPosition);
+    }
+    let position_key = DepositDataKey::Position(user.clone());
+    if env.storage().persistent().has(&position_key) {
+        return Err(MigrationError::PositionExists);
+    }
+
+    let indexes = crate::interest_index::accrue_interest(env);
+    let storage = env.storage().persistent();
+    storage.set(
+        &position_key,
+        &Position {
+            collateral,
+            debt,
+            borrow_interest,
+            last_accrual_time: indexes.last_update,
+        },
+    );
+    storage.set(
+        &DepositDataKey::CollateralBalance(user.clone()),
+        &collateral,
+    );
+    storage.set(
+        &InterestIndexDataKey::BorrowSnapshot(user.clone()),
+        &indexes.borrow_index,
+    );
+    storage.set(
+        &SupplyInterestDataKey::UserSnapshot(user.clone()),
+        &indexes.supply_index,
+    );
+    crate::liquidation_index::sync_borrower(env, &user);
+    adjust_protocol_analytics(env, collateral, debt);
+
+    emit_position_received(
+        env,
+        PositionReceivedEvent {
+            user,
+            source,
+            collateral,
+            debt: debt + borrow_interest,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,324 @@
+//! # Operator Approvals Module
+//!
+//! Scoped approvals that let a user authorize a specific contract (a vault,
+//! a router, a keeper) to act on their position without handing it blanket
+//! authorization. An approval names:
+//! - the operations the operator may perform (`OP_*` bit flags);
+//! - a cap per asset, consumed by every operator action in that asset;
+//! - an expiry timestamp.
+//!
+//! Operators act through the `operator_*` entrypoints. Funds still move
+//! between the protocol and the user's own account: deposits and repayments
+//! are pulled from the user, withdrawals and borrows are paid to the user.
+//!
+//! ## Invariants
+//! - Only the user can approve or revoke operators on their position.
+//! - An operator action must be covered by a live approval: the operation
+//!   bit is set, the expiry is in the future, and the remaining cap of the
+//!   asset covers the amount.
+//! - Remaining caps only decrease; re-approving replaces the approval.
+//! - A user has at most `MAX_OPERATORS_PER_USER` operators.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::events::{
+    emit_operator_action, emit_operator_approved, emit_operator_revoked, OperatorActionEvent,
+    OperatorApprovedEvent, OperatorRevokedEvent,
+};
+
+/// Operator may deposit collateral
+pub const OP_DEPOSIT: u32 = 1;
+/// Operator may withdraw collateral
+pub const OP_WITHDRAW: u32 = 1 << 1;
+/// Operator may borrow
+pub const OP_BORROW: u32 = 1 << 2;
+/// Operator may repay debt
+pub const OP_REPAY: u32 = 1 << 3;
+/// All operations an operator can be approved for
+pub const ALL_OPERATIONS: u32 = OP_DEPOSIT | OP_WITHDRAW | OP_BORROW | OP_REPAY;
+
+/// Maximum number of operators a user can approve
+pub const MAX_OPERATORS_PER_USER: u32 = 10;
+
+/// Errors that can occur during operator operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OperatorError {
+    /// Operation set is empty or contains unknown operations
+    InvalidOperations = 1,
+    /// Caps are empty, duplicated or not positive
+    InvalidCap = 2,
+    /// Expiry is not in the future
+    InvalidExpiry = 3,
+    /// User cannot approve themselves as an operator
+    InvalidOperator = 4,
+    /// User already has the maximum number of operators
+    TooManyOperators = 5,
+    /// Operator is not approved by the user
+    ApprovalNotFound = 6,
+    /// Approval has expired
+    ApprovalExpired = 7,
+    /// Operation is not covered by the approval
+    OperationNotApproved = 8,
+    /// Amount exceeds the remaining cap of the asset
+    CapExceeded = 9,
+    /// Amount must be greater than zero
+    InvalidAmount = 10,
+}
+
+/// Storage keys for operator data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum OperatorDataKey {
+    /// Approval of an operator by a user: OperatorApproval
+    Approval(Address, Address),
+    /// Operators approved by a user: Vec<Address>
+    UserOperators(Address),
+}
+
+/// Remaining amount an operator may move in one asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorCap {
+    /// The asset (None for native XLM)
+    pub asset: Option<Address>,
+    /// Amount still available to the operator
+    pub remaining: i128,
+}
+
+/// A user's approval of an operator
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorApproval {
+    /// The approved operator
+    pub operator: Address,
+    /// Approved operations (`OP_*` bit flags)
+    pub operations: u32,
+    /// Per-asset caps; assets without a cap cannot be used
+    pub caps: Vec<OperatorCap>,
+    /// Timestamp after which the approval is void
+    pub expires_at: u64,
+}
+
+fn get_user_operators(env: &Env, user: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get::<OperatorDataKey, Vec<Address>>(&OperatorDataKey::UserOperators(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get a user's approval of an operator
+pub fn get_operator_approval(
+    env: &Env,
+    user: &Address,
+    operator: &Address,
+) -> Option<OperatorApproval> {
+    env.storage()
+        .persistent()
+        .get::<OperatorDataKey, OperatorApproval>(&OperatorDataKey::Approval(
+            user.clone(),
+            operator.clone(),
+        ))
+}
+
+/// Get all approvals of a user, including expired ones
+pub fn get_operator_approvals(env: &Env, user: &Address) -> Vec<OperatorApproval> {
+    let mut approvals = Vec::new(env);
+    for operator in get_user_operators(env, user).iter() {
+        if let Some(approval) = get_operator_approval(env, user, &operator) {
+            approvals.push_back(approval);
+        }
+    }
+    approvals
+}
+
+/// Approve an operator, replacing any previous approval of it
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `operator` - The contract or account being approved
+/// * `operations` - Approved operations (`OP_*` bit flags)
+/// * `caps` - Per-asset caps on the amounts the operator may move
+/// * `expires_at` - Timestamp after which the approval is void
+///
+/// # Errors
+/// * `OperatorError::InvalidOperator` - If the user approves themselves
+/// * `OperatorError::InvalidOperations` - If `operations` is empty or unknown
+/// * `OperatorError::InvalidCap` - If caps are empty, duplicated or not positive
+/// * `OperatorError::InvalidExpiry` - If `expires_at` is not in the future
+/// * `OperatorError::TooManyOperators` - If the user has no operator slot left
+pub fn approve_operator(
+    env: &Env,
+    user: Address,
+    operator: Address,
+    operations: u32,
+    caps: Vec<OperatorCap>,
+    expires_at: u64,
+) -> Result<(), OperatorError> {
+    user.require_auth();
+
+    if operator == user {
+        return Err(OperatorError::InvalidOperator);
+    }
+    if operations == 0 || operations & !ALL_OPERATIONS != 0 {
+        return Err(OperatorError::InvalidOperations);
+    }
+    if caps.is_empty() {
+        return Err(OperatorError::InvalidCap);
+    }
+    for (i, cap) in caps.iter().enumerate() {
+        if cap.remaining <= 0 {
+            return Err(OperatorError::InvalidCap);
+        }
+        for other in caps.iter().skip(i + 1) {
+            if other.asset == cap.asset {
+                return Err(OperatorError::InvalidCap);
+            }
+        }
+    }
+    let timestamp = env.ledger().timestamp();
+    if expires_at <= timestamp {
+        return Err(OperatorError::InvalidExpiry);
+    }
+
+    let mut operators = get_user_operators(env, &user);
+    if !operators.contains(&operator) {
+        if operators.len() >= MAX_OPERATORS_PER_USER {
+            return Err(OperatorError::TooManyOperators);
+        }
+        operators.push_back(operator.clone());
+        env.storage()
+            .persistent()
+            .set(&OperatorDataKey::UserOperators(user.clone()), &operators);
+    }
+
+    let approval = OperatorApproval {
+        operator: operator.clone(),
+        operations,
+        caps,
+        expires_at,
+    };
+    env.storage().persistent().set(
+        &OperatorDataKey::Approval(user.clone(), operator.clone()),
+        &approval,
+    );
+
+    emit_operator_approved(
+        env,
+        OperatorApprovedEvent {
+            user,
+            operator,
+            operations,
+            expires_at,
+            timestamp,
+        },
+    );
+    Ok(())
+}
+
+/// Revoke an operator's approval
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `operator` - The operator to revoke
+///
+/// # Errors
+/// * `OperatorError::ApprovalNotFound` - If the operator is not approved
+pub fn revoke_operator(env: &Env, user: Address, operator: Address) -> Result<(), OperatorError> {
+    user.require_auth();
+
+    let mut operators = get_user_operators(env, &user);
+    let index = operators
+        .first_index_of(&operator)
+        .ok_or(OperatorError::ApprovalNotFound)?;
+    operators.remove(index);
+    env.storage()
+        .persistent()
+        .set(&OperatorDataKey::UserOperators(user.clone()), &operators);
+    env.storage()
+        .persistent()
+        .remove(&OperatorDataKey::Approval(user.clone(), operator.clone()));
+
+    emit_operator_revoked(
+        env,
+        OperatorRevokedEvent {
+            user,
+            operator,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Authorize an operator action and consume its cap
+///
+/// Must be called before the operator's action is executed.
+///
+/// # Arguments
+/// * `operator` - The acting operator (must authorize)
+/// * `user` - The position owner
+/// * `operation` - The `OP_*` flag of the action
+/// * `asset` - The asset of the action
+/// * `amount` - The amount of the action
+///
+/// # Errors
+/// * `OperatorError::InvalidAmount` - If amount is not positive
+/// * `OperatorError::ApprovalNotFound` - If the operator is not approved
+/// * `OperatorError::ApprovalExpired` - If the approval has expired
+/// * `OperatorError::OperationNotApproved` - If the operation is not approved
+/// * `OperatorError::CapExceeded` - If the asset's remaining cap is too low
+pub(crate) fn consume_approval(
+    env: &Env,
+    operator: &Address,
+    user: &Address,
+    operation: u32,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<(), OperatorError> {
+    operator.require_auth();
+
+    if amount <= 0 {
+        return Err(OperatorError::InvalidAmount);
+    }
+    let mut approval =
+        get_operator_approval(env, user, operator).ok_or(OperatorError::ApprovalNotFound)?;
+    let timestamp = env.ledger().timestamp();
+    if timestamp >= approval.expires_at {
+        return Err(OperatorError::ApprovalExpired);
+    }
+    if approval.operations & operation == 0 {
+        return Err(OperatorError::OperationNotApproved);
+    }
+
+    let index = approval
+        .caps
+        .iter()
+        .position(|cap| cap.asset == *asset)
+        .ok_or(OperatorError::CapExceeded)? as u32;
+    let mut cap = approval.caps.get(index).unwrap();
+    if amount > cap.remaining {
+        return Err(OperatorError::CapExceeded);
+    }
+    cap.remaining -= amount;
+    approval.caps.set(index, cap);
+    env.storage().persistent().set(
+        &OperatorDataKey::Approval(user.clone(), operator.clone()),
+        &approval,
+    );
+
+    emit_operator_action(
+        env,
+        OperatorActionEvent {
+            user: user.clone(),
+            operator: operator.clone(),
+            operation,
+            asset: asset.clone(),
+            amount,
+            timestamp,
+        },
+    );
+    Ok(())
+}
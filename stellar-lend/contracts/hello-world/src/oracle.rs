@@ -11,14 +11,15 @@
 //!
 //! ## Safety
 //! - Price deviation between consecutive updates is bounded (default ±5%).
-//! - Staleness threshold defaults to 1 hour; configurable by admin.
+//! - Staleness threshold defaults to 1 hour; configurable by admin, globally
+//!   or per asset (`max_price_age`).
 //! - Sanity-check bounds on min/max price are enforced on every update.
 //! - Only the admin or the designated oracle address may submit price updates.
 
 #![allow(unused)]
 use crate::deposit::DepositDataKey;
 use crate::events::{emit_price_updated, PriceUpdatedEvent};
-use crate::risk_management::get_admin;
+use crate::admin::get_admin;
 use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
 /// Errors that can occur during oracle operations
@@ -65,6 +66,12 @@ pub enum OracleDataKey {
     OracleConfig,
     /// Pause switches specifically for oracle updates: Map<Symbol, bool>
     PauseSwitches,
+    /// Address of the designated primary oracle for an asset
+    /// Value type: Address
+    PrimaryOracle(Address),
+    /// Last price reported by the fallback oracle for an asset
+    /// Value type: PriceFeed
+    FallbackFeed(Address),
 }
 
 /// Price feed data structure
@@ -128,7 +135,7 @@ fn get_default_config() -> OracleConfig {
 }
 
 /// Get oracle configuration
-fn get_oracle_config(env: &Env) -> OracleConfig {
+pub(crate) fn get_oracle_config(env: &Env) -> OracleConfig {
     let config_key = OracleDataKey::OracleConfig;
     env.storage()
         .persistent()
@@ -167,16 +174,11 @@ fn validate_price(env: &Env, price: i128) -> Result<(), OracleError> {
 }
 
 /// Check if price is stale
-fn is_price_stale(env: &Env, last_updated: u64) -> bool {
-    let config = get_oracle_config(env);
-    let current_time = env.ledger().timestamp();
-
-    if current_time < last_updated {
-        return true; // Invalid timestamp
-    }
-
-    let age = current_time - last_updated;
-    age > config.max_staleness_seconds
+///
+/// Uses the asset's `max_price_age` (see `price_oracle`), which defaults to
+/// the configured staleness threshold.
+fn is_price_stale(env: &Env, asset: &Address, last_updated: u64) -> bool {
+    crate::price_oracle::check_price_age(env, &Some(asset.clone()), last_updated).is_err()
 }
 
 /// Check price deviation between two prices
@@ -312,6 +314,13 @@ pub fn update_price_feed(
         check_price_deviation(env, price, feed.price)?;
     }
 
+    // A fresh price after an outage starts the asset's recovery window
+    if let Some(ref feed) = current_feed {
+        if is_price_stale(env, &asset, feed.last_updated) {
+            crate::liquidation_grace::start_oracle_recovery(env, &Some(asset.clone()));
+        }
+    }
+
     // Create new price feed
     let timestamp = env.ledger().timestamp();
     let oracle_clone = oracle.clone();
@@ -373,7 +382,7 @@ pub fn get_price(env: &Env, asset: &Address) -> Result<i128, OracleError> {
         .get::<OracleDataKey, PriceFeed>(&feed_key)
     {
         // Check if price is stale
-        if is_price_stale(env, feed.last_updated) {
+        if is_price_stale(env, asset, feed.last_updated) {
             // Try fallback oracle
             if let Ok(fallback_price) = get_fallback_price(env, asset) {
                 return Ok(fallback_price);
@@ -409,7 +418,7 @@ fn get_fallback_price(env: &Env, asset: &Address) -> Result<i128, OracleError> {
             .get::<OracleDataKey, PriceFeed>(&feed_key)
         {
             // Check if fallback price is valid and from authorized oracle
-            if feed.oracle == fallback_oracle && !is_price_stale(env, feed.last_updated) {
+            if feed.oracle == fallback_oracle && !is_price_stale(env, asset, feed.last_updated) {
                 cache_price(env, asset, feed.price);
                 return Ok(feed.price);
             }
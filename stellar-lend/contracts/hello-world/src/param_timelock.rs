@@ -0,0 +1,430 @@
+//! # Parameter Timelock Module
+//!
+//! Delays risk-parameter changes so integrators can react before they take
+//! effect. While a delay is configured, changes to
+//! - asset collateral factors (`update_asset_config`),
+//! - the global risk parameters, including the liquidation incentive
+//!   (`set_risk_params`),
+//! - the interest rate model (`update_interest_rate_config`), and
+//! - the AMM slippage tolerances
+//!
+//! can no longer be made directly. They are queued with `queue_param_change`
+//! and applied with `execute_param_change` once the delay has elapsed.
+//!
+//! ## Lifecycle
+//! A queued change gets an ETA of `queued_at + delay` and can be executed
+//! from the ETA until `PARAM_CHANGE_GRACE_PERIOD` after it, or cancelled at
+//! any point before. Every step emits a typed event
+//! (`param_change_queued`, `param_change_executed`,
+//! `param_change_cancelled`).
+//!
+//! ## Roles
+//! - The admin or a risk manager can queue and execute changes.
+//! - A guardian can also cancel them.
+//! - Only the admin can change the delay itself.
+//!
+//! ## Invariants
+//! - `delay == 0` disables the timelock; queued changes are then executable
+//!   immediately and direct setters work again.
+//! - The delay can be raised directly, but lowering it must itself be queued.
+//! - A change is executed or cancelled at most once.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::events::{
+    emit_param_change_cancelled, emit_param_change_executed, emit_param_change_queued,
+    emit_param_timelock_delay_updated, ParamChangeCancelledEvent, ParamChangeExecutedEvent,
+    ParamChangeQueuedEvent, ParamTimelockDelayUpdatedEvent,
+};
+use crate::risk_management::RiskDataKey;
+
+/// Longest delay that can be configured (30 days)
+pub const MAX_PARAM_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60;
+
+/// How long a change stays executable after its ETA (14 days)
+pub const PARAM_CHANGE_GRACE_PERIOD: u64 = 14 * 24 * 60 * 60;
+
+/// Errors that can occur during parameter timelock operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ParamTimelockError {
+    /// Caller lacks the required role
+    Unauthorized = 1,
+    /// Delay is longer than `MAX_PARAM_TIMELOCK_DELAY`
+    InvalidDelay = 2,
+    /// No change exists with this id
+    ChangeNotFound = 3,
+    /// The change was already executed or cancelled
+    ChangeNotQueued = 4,
+    /// The change's ETA has not been reached
+    TimelockNotElapsed = 5,
+    /// The change's grace period has passed
+    ChangeExpired = 6,
+    /// Lowering the delay must be queued
+    TimelockActive = 7,
+    /// The change is out of range or could not be applied
+    InvalidChange = 8,
+}
+
+/// Interest rate model fields to update (None keeps the current value)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestRateUpdate {
+    pub base_rate: Option<i128>,
+    pub kink: Option<i128>,
+    pub multiplier: Option<i128>,
+    pub jump_multiplier: Option<i128>,
+    pub rate_floor: Option<i128>,
+    pub rate_ceiling: Option<i128>,
+    pub spread: Option<i128>,
+}
+
+/// A parameter change that goes through the timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParamChange {
+    /// Collateral factor of an asset in basis points (asset None = XLM)
+    CollateralFactor(Option<Address>, i128),
+    /// Risk parameters (min_cr, liq_threshold, close_factor, liq_incentive)
+    RiskParams(Option<i128>, Option<i128>, Option<i128>, Option<i128>),
+    /// Interest rate model
+    InterestModel(InterestRateUpdate),
+    /// AMM slippage tolerances (default, max) in basis points
+    AmmSlippage(i128, i128),
+    /// Timelock delay in seconds (admin only)
+    TimelockDelay(u64),
+}
+
+/// Lifecycle state of a queued change
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParamChangeStatus {
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+/// A change in the timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedParamChange {
+    pub id: u64,
+    pub change: ParamChange,
+    pub proposer: Address,
+    pub queued_at: u64,
+    /// Earliest execution time
+    pub eta: u64,
+    pub status: ParamChangeStatus,
+}
+
+/// Storage keys for parameter timelock data
+///
+/// The delay itself lives at `RiskDataKey::ParameterChangeTimelock`.
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum ParamTimelockDataKey {
+    /// A queued change by id: QueuedParamChange
+    QueuedParamChange(u64),
+    /// Id of the next queued change: u64
+    NextParamChangeId,
+    /// Ids of changes still queued: Vec<u64>
+    PendingParamChanges,
+}
+
+/// Get the timelock delay in seconds (0 = disabled)
+pub fn get_param_timelock_delay(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<RiskDataKey, u64>(&RiskDataKey::ParameterChangeTimelock)
+        .unwrap_or(0)
+}
+
+/// Whether timelocked parameters can only be changed through the queue
+pub(crate) fn is_param_timelock_active(env: &Env) -> bool {
+    get_param_timelock_delay(env) > 0
+}
+
+/// Get a queued change
+pub fn get_param_change(env: &Env, id: u64) -> Option<QueuedParamChange> {
+    env.storage()
+        .persistent()
+        .get::<ParamTimelockDataKey, QueuedParamChange>(&ParamTimelockDataKey::QueuedParamChange(
+            id,
+        ))
+}
+
+/// Get the ids of changes that are still queued
+pub fn get_pending_param_changes(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get::<ParamTimelockDataKey, Vec<u64>>(&ParamTimelockDataKey::PendingParamChanges)
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_pending_param_changes(env: &Env, pending: &Vec<u64>) {
+    env.storage()
+        .persistent()
+        .set(&ParamTimelockDataKey::PendingParamChanges, pending);
+}
+
+fn store_delay(env: &Env, delay: u64) {
+    env.storage()
+        .persistent()
+        .set(&RiskDataKey::ParameterChangeTimelock, &delay);
+}
+
+/// Set the timelock delay (admin only)
+///
+/// Enabling or raising the delay takes effect immediately. While a delay is
+/// set, lowering it requires queuing `ParamChange::TimelockDelay`.
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `delay` - Delay in seconds (0 disables the timelock)
+///
+/// # Errors
+/// * `ParamTimelockError::Unauthorized` - If caller is not the admin
+/// * `ParamTimelockError::InvalidDelay` - If the delay is too long
+/// * `ParamTimelockError::TimelockActive` - If the delay would be lowered
+pub fn set_param_timelock_delay(
+    env: &Env,
+    caller: Address,
+    delay: u64,
+) -> Result<(), ParamTimelockError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| ParamTimelockError::Unauthorized)?;
+
+    if delay > MAX_PARAM_TIMELOCK_DELAY {
+        return Err(ParamTimelockError::InvalidDelay);
+    }
+    if delay < get_param_timelock_delay(env) {
+        return Err(ParamTimelockError::TimelockActive);
+    }
+    store_delay(env, delay);
+
+    emit_param_timelock_delay_updated(
+        env,
+        ParamTimelockDelayUpdatedEvent {
+            admin: caller,
+            delay,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+fn require_change_authority(
+    env: &Env,
+    caller: &Address,
+    change: &ParamChange,
+) -> Result<(), ParamTimelockError> {
+    let authorized = match change {
+        ParamChange::TimelockDelay(_) => crate::admin::require_admin(env, caller),
+        _ => crate::admin::require_risk_manager(env, caller),
+    };
+    authorized.map_err(|_| ParamTimelockError::Unauthorized)
+}
+
+/// Range checks that do not depend on the parameters' current values
+fn validate_change(change: &ParamChange) -> Result<(), ParamTimelockError> {
+    let valid = match change {
+        ParamChange::CollateralFactor(_, cf) => (0..=10_000).contains(cf),
+        ParamChange::AmmSlippage(default, max) => 0 <= *default && default <= max && *max <= 10_000,
+        ParamChange::TimelockDelay(delay) => *delay <= MAX_PARAM_TIMELOCK_DELAY,
+        ParamChange::RiskParams(..) | ParamChange::InterestModel(_) => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(ParamTimelockError::InvalidChange)
+    }
+}
+
+/// Queue a parameter change (admin or risk manager)
+///
+/// # Arguments
+/// * `caller` - The proposer (admin or risk manager; admin for the delay)
+/// * `change` - The change to apply once the delay has elapsed
+///
+/// # Returns
+/// Returns the id of the queued change
+///
+/// # Errors
+/// * `ParamTimelockError::Unauthorized` - If caller lacks the required role
+/// * `ParamTimelockError::InvalidChange` - If a value is out of range
+pub fn queue_param_change(
+    env: &Env,
+    caller: Address,
+    change: ParamChange,
+) -> Result<u64, ParamTimelockError> {
+    caller.require_auth();
+    require_change_authority(env, &caller, &change)?;
+    validate_change(&change)?;
+
+    let id = env
+        .storage()
+        .persistent()
+        .get::<ParamTimelockDataKey, u64>(&ParamTimelockDataKey::NextParamChangeId)
+        .unwrap_or(0);
+    let now = env.ledger().timestamp();
+    let eta = now.saturating_add(get_param_timelock_delay(env));
+
+    let queued = QueuedParamChange {
+        id,
+        change: change.clone(),
+        proposer: caller.clone(),
+        queued_at: now,
+        eta,
+        status: ParamChangeStatus::Queued,
+    };
+    env.storage()
+        .persistent()
+        .set(&ParamTimelockDataKey::QueuedParamChange(id), &queued);
+    env.storage()
+        .persistent()
+        .set(&ParamTimelockDataKey::NextParamChangeId, &(id + 1));
+    let mut pending = get_pending_param_changes(env);
+    pending.push_back(id);
+    set_pending_param_changes(env, &pending);
+
+    emit_param_change_queued(
+        env,
+        ParamChangeQueuedEvent {
+            id,
+            change,
+            proposer: caller,
+            eta,
+            timestamp: now,
+        },
+    );
+    Ok(id)
+}
+
+fn take_queued(env: &Env, id: u64) -> Result<QueuedParamChange, ParamTimelockError> {
+    let queued = get_param_change(env, id).ok_or(ParamTimelockError::ChangeNotFound)?;
+    if queued.status != ParamChangeStatus::Queued {
+        return Err(ParamTimelockError::ChangeNotQueued);
+    }
+    Ok(queued)
+}
+
+fn close_queued(env: &Env, mut queued: QueuedParamChange, status: ParamChangeStatus) {
+    queued.status = status;
+    env.storage()
+        .persistent()
+        .set(&ParamTimelockDataKey::QueuedParamChange(queued.id), &queued);
+
+    let mut pending = get_pending_param_changes(env);
+    if let Some(index) = pending.first_index_of(queued.id) {
+        pending.remove(index);
+        set_pending_param_changes(env, &pending);
+    }
+}
+
+fn apply_change(env: &Env, change: &ParamChange) -> Result<(), ParamTimelockError> {
+    let applied = match change.clone() {
+        ParamChange::CollateralFactor(asset, cf) => {
+            crate::cross_asset::apply_collateral_factor(env, asset, cf).is_ok()
+        }
+        ParamChange::RiskParams(mcr, lt, cf, li) => {
+            crate::risk_params::set_risk_params(env, mcr, lt, cf, li).is_ok()
+        }
+        ParamChange::InterestModel(update) => crate::interest_rate::apply_interest_rate_config(
+            env,
+            update.base_rate,
+            update.kink,
+            update.multiplier,
+            update.jump_multiplier,
+            update.rate_floor,
+            update.rate_ceiling,
+            update.spread,
+        )
+        .is_ok(),
+        ParamChange::AmmSlippage(default, max) => {
+            crate::amm::set_amm_slippage(env, default, max).is_ok()
+        }
+        ParamChange::TimelockDelay(delay) => {
+            store_delay(env, delay);
+            true
+        }
+    };
+    if applied {
+        Ok(())
+    } else {
+        Err(ParamTimelockError::InvalidChange)
+    }
+}
+
+/// Apply a queued change once its delay has elapsed (admin or risk manager)
+///
+/// # Arguments
+/// * `caller` - The executor (admin or risk manager; admin for the delay)
+/// * `id` - The queued change
+///
+/// # Errors
+/// * `ParamTimelockError::Unauthorized` - If caller lacks the required role
+/// * `ParamTimelockError::ChangeNotFound` - If there is no such change
+/// * `ParamTimelockError::ChangeNotQueued` - If it was executed or cancelled
+/// * `ParamTimelockError::TimelockNotElapsed` - If the ETA has not been reached
+/// * `ParamTimelockError::ChangeExpired` - If the grace period has passed
+/// * `ParamTimelockError::InvalidChange` - If the change cannot be applied to
+///   the current parameters
+pub fn execute_param_change(env: &Env, caller: Address, id: u64) -> Result<(), ParamTimelockError> {
+    caller.require_auth();
+    let queued = take_queued(env, id)?;
+    require_change_authority(env, &caller, &queued.change)?;
+
+    let now = env.ledger().timestamp();
+    if now < queued.eta {
+        return Err(ParamTimelockError::TimelockNotElapsed);
+    }
+    if now > queued.eta.saturating_add(PARAM_CHANGE_GRACE_PERIOD) {
+        return Err(ParamTimelockError::ChangeExpired);
+    }
+
+    apply_change(env, &queued.change)?;
+    let change = queued.change.clone();
+    close_queued(env, queued, ParamChangeStatus::Executed);
+
+    emit_param_change_executed(
+        env,
+        ParamChangeExecutedEvent {
+            id,
+            change,
+            executor: caller,
+            timestamp: now,
+        },
+    );
+    Ok(())
+}
+
+/// Cancel a queued change (admin, risk manager or guardian)
+///
+/// # Errors
+/// * `ParamTimelockError::Unauthorized` - If caller lacks the required role
+/// * `ParamTimelockError::ChangeNotFound` - If there is no such change
+/// * `ParamTimelockError::ChangeNotQueued` - If it was executed or cancelled
+pub fn cancel_param_change(env: &Env, caller: Address, id: u64) -> Result<(), ParamTimelockError> {
+    caller.require_auth();
+    crate::admin::require_risk_manager(env, &caller)
+        .or_else(|_| crate::admin::require_guardian(env, &caller))
+        .map_err(|_| ParamTimelockError::Unauthorized)?;
+    let queued = take_queued(env, id)?;
+
+    let change = queued.change.clone();
+    close_queued(env, queued, ParamChangeStatus::Cancelled);
+
+    emit_param_change_cancelled(
+        env,
+        ParamChangeCancelledEvent {
+            id,
+            change,
+            caller,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,204 @@
+//! # Permit Module
+//!
+//! Signed operations (meta-transactions) so a relayer can submit deposits,
+//! repayments and withdrawals for a user who only signs off-chain. The user
+//! registers an ed25519 signing key once; afterwards any account may submit
+//! a `Permit` signed with that key through `execute_permit` and pay the
+//! transaction fee.
+//!
+//! ## Signing
+//! A wallet signs the 32-byte digest returned by `get_permit_digest`: the
+//! SHA-256 of the XDR of `("permit", contract address, permit)`. Binding the
+//! contract address keeps a permit from being replayed on another
+//! deployment.
+//!
+//! Funds still move between the protocol and the user's own account, as for
+//! operators: deposits and repayments are pulled through the user's token
+//! allowance to this contract, withdrawals are paid to the user.
+//!
+//! ## Invariants
+//! - Each permit carries the user's next nonce and is executed at most once;
+//!   nonces only increase.
+//! - A permit is void after `expires_at`.
+//! - An invalid signature aborts the call.
+//! - Only the user can register or replace their signing key; replacing it
+//!   voids every permit signed with the old key.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+use crate::events::{
+    emit_permit_executed, emit_permit_signer_updated, PermitExecutedEvent, PermitSignerUpdatedEvent,
+};
+use crate::operators::{OP_DEPOSIT, OP_REPAY, OP_WITHDRAW};
+
+/// Errors that can occur during permit operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PermitError {
+    /// The user has not registered a signing key
+    SignerNotSet = 1,
+    /// The permit has expired
+    PermitExpired = 2,
+    /// The nonce is not the user's next nonce
+    InvalidNonce = 3,
+    /// Operation is not a single deposit, withdraw or repay (`OP_*` flag)
+    InvalidOperation = 4,
+    /// Amount must be greater than zero
+    InvalidAmount = 5,
+    /// The signed operation was rejected by the protocol
+    OperationFailed = 6,
+}
+
+/// An operation signed by a user for a relayer to submit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Permit {
+    /// The position owner who signed the permit
+    pub user: Address,
+    /// `OP_DEPOSIT`, `OP_WITHDRAW` or `OP_REPAY`
+    pub operation: u32,
+    /// The asset (None for native XLM)
+    pub asset: Option<Address>,
+    /// The amount to deposit, withdraw or repay
+    pub amount: i128,
+    /// The user's next nonce
+    pub nonce: u64,
+    /// Timestamp after which the permit is void
+    pub expires_at: u64,
+}
+
+/// Storage keys for permit data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum PermitDataKey {
+    /// A user's ed25519 permit signing key: BytesN<32>
+    PermitSigner(Address),
+    /// A user's next permit nonce: u64
+    PermitNonce(Address),
+}
+
+/// Register or replace the key that signs a user's permits
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `public_key` - The ed25519 public key
+pub fn set_permit_signer(env: &Env, user: Address, public_key: BytesN<32>) {
+    user.require_auth();
+    env.storage()
+        .persistent()
+        .set(&PermitDataKey::PermitSigner(user.clone()), &public_key);
+    emit_permit_signer_updated(
+        env,
+        PermitSignerUpdatedEvent {
+            user,
+            public_key,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+/// Get the key that signs a user's permits
+pub fn get_permit_signer(env: &Env, user: &Address) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get::<PermitDataKey, BytesN<32>>(&PermitDataKey::PermitSigner(user.clone()))
+}
+
+/// Get the nonce the user's next permit must carry
+pub fn get_permit_nonce(env: &Env, user: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<PermitDataKey, u64>(&PermitDataKey::PermitNonce(user.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the digest a user signs to authorize `permit`
+pub fn get_permit_digest(env: &Env, permit: &Permit) -> BytesN<32> {
+    let payload = (
+        Symbol::new(env, "permit"),
+        env.current_contract_address(),
+        permit.clone(),
+    )
+        .to_xdr(env);
+    env.crypto().sha256(&payload).into()
+}
+
+/// Execute a signed permit on behalf of its user
+///
+/// # Arguments
+/// * `permit` - The signed operation
+/// * `signature` - The user's ed25519 signature of `get_permit_digest(permit)`
+///
+/// # Returns
+/// The user's collateral after a deposit or withdrawal, or the remaining debt
+/// after a repayment
+///
+/// # Errors
+/// * `PermitError::InvalidOperation` - If the operation is not supported
+/// * `PermitError::InvalidAmount` - If the amount is not positive
+/// * `PermitError::PermitExpired` - If the permit has expired
+/// * `PermitError::InvalidNonce` - If the nonce was used or skips ahead
+/// * `PermitError::SignerNotSet` - If the user has no signing key
+/// * `PermitError::OperationFailed` - If the deposit, withdrawal or repayment fails
+pub fn execute_permit(
+    env: &Env,
+    permit: Permit,
+    signature: BytesN<64>,
+) -> Result<i128, PermitError> {
+    if permit.operation != OP_DEPOSIT
+        && permit.operation != OP_WITHDRAW
+        && permit.operation != OP_REPAY
+    {
+        return Err(PermitError::InvalidOperation);
+    }
+    if permit.amount <= 0 {
+        return Err(PermitError::InvalidAmount);
+    }
+    if env.ledger().timestamp() > permit.expires_at {
+        return Err(PermitError::PermitExpired);
+    }
+    let nonce = get_permit_nonce(env, &permit.user);
+    if permit.nonce != nonce {
+        return Err(PermitError::InvalidNonce);
+    }
+    let public_key = get_permit_signer(env, &permit.user).ok_or(PermitError::SignerNotSet)?;
+
+    let digest: Bytes = get_permit_digest(env, &permit).into();
+    env.crypto()
+        .ed25519_verify(&public_key, &digest, &signature);
+
+    env.storage().persistent().set(
+        &PermitDataKey::PermitNonce(permit.user.clone()),
+        &(nonce + 1),
+    );
+
+    let user = permit.user.clone();
+    let asset = permit.asset.clone();
+    let result = match permit.operation {
+        OP_DEPOSIT => crate::deposit::deposit_collateral(env, user, asset, permit.amount)
+            .map_err(|_| PermitError::OperationFailed)?,
+        OP_WITHDRAW => crate::withdraw::withdraw_collateral(env, user, asset, permit.amount)
+            .map_err(|_| PermitError::OperationFailed)?,
+        _ => {
+            crate::repay::repay_debt(env, user, asset, permit.amount)
+                .map_err(|_| PermitError::OperationFailed)?
+                .0
+        }
+    };
+
+    emit_permit_executed(
+        env,
+        PermitExecutedEvent {
+            user: permit.user,
+            operation: permit.operation,
+            asset: permit.asset,
+            amount: permit.amount,
+            nonce,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(result)
+}
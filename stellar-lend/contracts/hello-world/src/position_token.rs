@@ -0,0 +1,302 @@
+//! # Position Token Module
+//!
+//! Transferable handles for whole lending positions, so a position can be
+//! sold or moved to another wallet in one step. A user wraps their position
+//! into a position token with `wrap_position`; `transfer_position` then moves
+//! the token together with everything booked under the owner's account:
+//! core collateral and debt (with their interest snapshots), and every
+//! cross-asset collateral and debt position. The recipient assumes the debt.
+//!
+//! ## Ownership
+//! The token follows the position rather than holding it: positions stay
+//! booked under the owner's account, so the owner manages it with the regular
+//! entrypoints and it remains liquidatable at all times. `unwrap_position`
+//! burns the token and leaves the position where it is.
+//!
+//! ## Invariants
+//! - A token is always owned by the account its position is booked under,
+//!   and an account owns at most one token.
+//! - Transfers need the authorization of both parties; the recipient must
+//!   hold no position, so positions are never merged.
+//! - Positions with a running liquidation auction cannot be transferred.
+//! - A pending action delay and liquidation grace window move with the
+//!   position.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, TryFromVal, Val, Vec};
+
+use crate::action_delay::ActionDelayDataKey;
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{
+    emit_position_transferred, emit_position_unwrapped, emit_position_wrapped,
+    PositionTransferredEvent, PositionUnwrappedEvent, PositionWrappedEvent,
+};
+use crate::interest_index::InterestIndexDataKey;
+use crate::liquidation_grace::LiquidationGraceDataKey;
+use crate::reserve::ReserveDataKey;
+use crate::supply_interest::SupplyInterestDataKey;
+
+/// Errors that can occur during position token operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PositionTokenError {
+    /// The account has no collateral or debt to wrap
+    NoPosition = 1,
+    /// The account already owns a position token
+    AlreadyWrapped = 2,
+    /// No position token exists with this id
+    TokenNotFound = 3,
+    /// Caller does not own the position token
+    NotOwner = 4,
+    /// Recipient is the owner or the protocol itself
+    InvalidRecipient = 5,
+    /// Recipient already holds a position or a position token
+    RecipientHasPosition = 6,
+    /// The position is being liquidated by auction
+    PositionLocked = 7,
+}
+
+/// Storage keys for position token data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum PositionTokenDataKey {
+    /// Id of the next position token: u64
+    NextPositionTokenId,
+    /// Owner of a position token: Address
+    PositionTokenOwner(u64),
+    /// Position token owned by an account: u64
+    PositionTokenOf(Address),
+}
+
+/// Whether an account has any collateral or debt booked under it
+fn has_position(env: &Env, user: &Address) -> bool {
+    let core = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+        .map(|position| {
+            position.collateral > 0 || position.debt > 0 || position.borrow_interest > 0
+        })
+        .unwrap_or(false);
+    core || crate::cross_asset::has_asset_positions(env, user)
+}
+
+/// Move a persistent entry from one key to another, if present
+fn move_entry<K, V>(env: &Env, from_key: &K, to_key: &K)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let storage = env.storage().persistent();
+    if let Some(value) = storage.get::<K, V>(from_key) {
+        storage.remove(from_key);
+        storage.set(to_key, &value);
+    }
+}
+
+/// Get the owner of a position token
+pub fn get_position_token_owner(env: &Env, token_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<PositionTokenDataKey, Address>(&PositionTokenDataKey::PositionTokenOwner(token_id))
+}
+
+/// Get the position token owned by an account
+pub fn get_position_token(env: &Env, user: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get::<PositionTokenDataKey, u64>(&PositionTokenDataKey::PositionTokenOf(user.clone()))
+}
+
+fn require_owner(env: &Env, owner: &Address, token_id: u64) -> Result<(), PositionTokenError> {
+    match get_position_token_owner(env, token_id) {
+        None => Err(PositionTokenError::TokenNotFound),
+        Some(current) if current != *owner => Err(PositionTokenError::NotOwner),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Wrap a user's position into a new position token
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+///
+/// # Returns
+/// Returns the id of the new token
+///
+/// # Errors
+/// * `PositionTokenError::AlreadyWrapped` - If the user already owns a token
+/// * `PositionTokenError::NoPosition` - If the user has no collateral or debt
+pub fn wrap_position(env: &Env, user: Address) -> Result<u64, PositionTokenError> {
+    user.require_auth();
+
+    if get_position_token(env, &user).is_some() {
+        return Err(PositionTokenError::AlreadyWrapped);
+    }
+    if !has_position(env, &user) {
+        return Err(PositionTokenError::NoPosition);
+    }
+
+    let storage = env.storage().persistent();
+    let token_id = storage
+        .get::<PositionTokenDataKey, u64>(&PositionTokenDataKey::NextPositionTokenId)
+        .unwrap_or(1);
+    storage.set(&PositionTokenDataKey::NextPositionTokenId, &(token_id + 1));
+    storage.set(&PositionTokenDataKey::PositionTokenOwner(token_id), &user);
+    storage.set(
+        &PositionTokenDataKey::PositionTokenOf(user.clone()),
+        &token_id,
+    );
+
+    emit_position_wrapped(
+        env,
+        PositionWrappedEvent {
+            user,
+            token_id,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(token_id)
+}
+
+/// Burn a position token, leaving the position with its owner
+///
+/// # Arguments
+/// * `owner` - The token owner (must authorize)
+/// * `token_id` - The position token
+///
+/// # Errors
+/// * `PositionTokenError::TokenNotFound` - If the token does not exist
+/// * `PositionTokenError::NotOwner` - If the caller does not own the token
+pub fn unwrap_position(env: &Env, owner: Address, token_id: u64) -> Result<(), PositionTokenError> {
+    owner.require_auth();
+    require_owner(env, &owner, token_id)?;
+
+    let storage = env.storage().persistent();
+    storage.remove(&PositionTokenDataKey::PositionTokenOwner(token_id));
+    storage.remove(&PositionTokenDataKey::PositionTokenOf(owner.clone()));
+
+    emit_position_unwrapped(
+        env,
+        PositionUnwrappedEvent {
+            user: owner,
+            token_id,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Transfer a position token and the whole position behind it
+///
+/// Moves all collateral and debt booked under `from` to `to`, which assumes
+/// the debt. Health is unchanged by the move, so no health check applies.
+///
+/// # Arguments
+/// * `from` - The token owner (must authorize)
+/// * `to` - The new owner (must authorize, as they take on the debt)
+/// * `token_id` - The position token
+///
+/// # Errors
+/// * `PositionTokenError::TokenNotFound` - If the token does not exist
+/// * `PositionTokenError::NotOwner` - If `from` does not own the token
+/// * `PositionTokenError::InvalidRecipient` - If `to` is `from` or the protocol
+/// * `PositionTokenError::RecipientHasPosition` - If `to` holds a position or token
+/// * `PositionTokenError::PositionLocked` - If the position is being auctioned
+pub fn transfer_position(
+    env: &Env,
+    from: Address,
+    to: Address,
+    token_id: u64,
+) -> Result<(), PositionTokenError> {
+    from.require_auth();
+    require_owner(env, &from, token_id)?;
+
+    if to == from || to == env.current_contract_address() {
+        return Err(PositionTokenError::InvalidRecipient);
+    }
+    to.require_auth();
+    if get_position_token(env, &to).is_some() || has_position(env, &to) {
+        return Err(PositionTokenError::RecipientHasPosition);
+    }
+    if crate::auction::get_auction(env, &from).is_some() {
+        return Err(PositionTokenError::PositionLocked);
+    }
+
+    let position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(from.clone()));
+
+    // Core position and the state derived from it
+    move_entry::<_, Position>(
+        env,
+        &DepositDataKey::Position(from.clone()),
+        &DepositDataKey::Position(to.clone()),
+    );
+    move_entry::<_, i128>(
+        env,
+        &DepositDataKey::CollateralBalance(from.clone()),
+        &DepositDataKey::CollateralBalance(to.clone()),
+    );
+    move_entry::<_, i128>(
+        env,
+        &InterestIndexDataKey::BorrowSnapshot(from.clone()),
+        &InterestIndexDataKey::BorrowSnapshot(to.clone()),
+    );
+    move_entry::<_, i128>(
+        env,
+        &SupplyInterestDataKey::UserSnapshot(from.clone()),
+        &SupplyInterestDataKey::UserSnapshot(to.clone()),
+    );
+    move_entry::<_, Vec<Option<Address>>>(
+        env,
+        &ReserveDataKey::UserDebtAssets(from.clone()),
+        &ReserveDataKey::UserDebtAssets(to.clone()),
+    );
+    move_entry::<_, u32>(
+        env,
+        &ActionDelayDataKey::LastDepositLedger(from.clone()),
+        &ActionDelayDataKey::LastDepositLedger(to.clone()),
+    );
+    move_entry::<_, u32>(
+        env,
+        &LiquidationGraceDataKey::GraceStarted(from.clone()),
+        &LiquidationGraceDataKey::GraceStarted(to.clone()),
+    );
+    crate::liquidation_index::sync_borrower(env, &from);
+    crate::liquidation_index::sync_borrower(env, &to);
+
+    crate::cross_asset::move_user_positions(env, &from, &to);
+
+    let storage = env.storage().persistent();
+    storage.remove(&PositionTokenDataKey::PositionTokenOf(from.clone()));
+    storage.set(&PositionTokenDataKey::PositionTokenOwner(token_id), &to);
+    storage.set(
+        &PositionTokenDataKey::PositionTokenOf(to.clone()),
+        &token_id,
+    );
+
+    let (collateral, debt) = position
+        .map(|position| {
+            (
+                position.collateral,
+                position.debt + position.borrow_interest,
+            )
+        })
+        .unwrap_or((0, 0));
+    emit_position_transferred(
+        env,
+        PositionTransferredEvent {
+            from,
+            to,
+            token_id,
+            collateral,
+            debt,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,323 @@
+//! # Price Oracle Module
+//!
+//! Pulls reserve prices from SEP-40 price feed contracts (e.g. Reflector)
+//! instead of relying on prices pushed to the pool.
+//!
+//! ## Feeds
+//! The admin points a reserve at a feed contract and the feed's name for the
+//! asset (`Sep40Asset::Stellar(token)` or `Sep40Asset::Other(ticker)`). The
+//! feed's `decimals()` are read once when it is configured; prices returned
+//! by `lastprice` are normalized to `PRICE_SCALE` (7 decimals).
+//!
+//! ## Cache
+//! A fetched price is cached per reserve together with the feed's timestamp
+//! and is reused for the oracle config's `cache_ttl_seconds`, so repeated
+//! health checks within a ledger cost a single cross-contract call.
+//!
+//! ## Consumers
+//! Cross-asset health factors and liquidation price conversions read
+//! `get_feed_price`. Reserves without a feed keep using the prices stored by
+//! `update_asset_price` / `update_price_feed`.
+//!
+//! ## Price Age
+//! Each reserve has a `max_price_age` (heartbeat): the oldest price, by its
+//! own timestamp, the protocol will act on. It defaults to the oracle config's
+//! `max_staleness_seconds` and applies to feed prices, pushed oracle prices
+//! and cross-asset config prices alike.
+//!
+//! ## Invariants
+//! - Only the admin can set or remove a reserve's feed or price age.
+//! - A price is never older than its reserve's `max_price_age`; a stale or
+//!   missing price is an error, never a silent fallback to a stored price.
+//! - Prices are strictly positive.
+
+#![allow(unused)]
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env, Symbol};
+
+/// Fixed-point scale of normalized prices (7 decimals)
+pub const PRICE_SCALE_DECIMALS: u32 = 7;
+
+/// Errors that can occur during price oracle operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PriceOracleError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Feed address is invalid or reports unusable decimals
+    InvalidFeed = 2,
+    /// Reserve has no price feed
+    FeedNotConfigured = 3,
+    /// Feed has no price for the asset
+    PriceUnavailable = 4,
+    /// Feed price is older than the staleness threshold
+    StalePrice = 5,
+    /// Feed price is zero or negative
+    InvalidPrice = 6,
+    /// Overflow occurred while normalizing the price
+    Overflow = 7,
+    /// Maximum price age must be greater than zero
+    InvalidMaxPriceAge = 8,
+}
+
+/// Asset identifier of the SEP-40 interface
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Sep40Asset {
+    /// A Stellar asset, by contract address
+    Stellar(Address),
+    /// An off-chain asset, by ticker
+    Other(Symbol),
+}
+
+/// Price record of the SEP-40 interface
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sep40PriceData {
+    /// Price in the feed's decimals
+    pub price: i128,
+    /// Timestamp of the price
+    pub timestamp: u64,
+}
+
+/// SEP-40 price feed interface
+#[contractclient(name = "Sep40Client")]
+pub trait Sep40PriceFeed {
+    /// Most recent price of an asset
+    fn lastprice(env: Env, asset: Sep40Asset) -> Option<Sep40PriceData>;
+    /// Decimals of the feed's prices
+    fn decimals(env: Env) -> u32;
+}
+
+/// Storage keys for price oracle data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum PriceOracleDataKey {
+    /// Feed of a reserve: PriceFeedSource
+    FeedSource(Option<Address>),
+    /// Last price fetched for a reserve: FeedPrice
+    FeedPrice(Option<Address>),
+    /// Oldest usable price of a reserve, in seconds: u64
+    MaxPriceAge(Option<Address>),
+}
+
+/// SEP-40 feed configured for a reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceFeedSource {
+    /// Feed contract
+    pub feed: Address,
+    /// The reserve's asset as named by the feed
+    pub feed_asset: Sep40Asset,
+    /// Decimals of the feed's prices
+    pub decimals: u32,
+}
+
+/// Normalized price fetched from a feed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeedPrice {
+    /// Price with `PRICE_SCALE_DECIMALS` decimals
+    pub price: i128,
+    /// Timestamp reported by the feed
+    pub timestamp: u64,
+    /// Ledger timestamp the price was fetched at
+    pub fetched_at: u64,
+}
+
+/// Convert a price with `decimals` decimals to `PRICE_SCALE_DECIMALS`
+fn normalize(price: i128, decimals: u32) -> Result<i128, PriceOracleError> {
+    if decimals >= PRICE_SCALE_DECIMALS {
+        let divisor = 10i128
+            .checked_pow(decimals - PRICE_SCALE_DECIMALS)
+            .ok_or(PriceOracleError::Overflow)?;
+        Ok(price / divisor)
+    } else {
+        10i128
+            .checked_pow(PRICE_SCALE_DECIMALS - decimals)
+            .and_then(|factor| price.checked_mul(factor))
+            .ok_or(PriceOracleError::Overflow)
+    }
+}
+
+/// Get the feed configured for a reserve
+pub fn get_price_feed(env: &Env, asset: &Option<Address>) -> Option<PriceFeedSource> {
+    env.storage()
+        .persistent()
+        .get::<PriceOracleDataKey, PriceFeedSource>(&PriceOracleDataKey::FeedSource(asset.clone()))
+}
+
+/// Point a reserve at a SEP-40 price feed (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `asset` - The reserve (None for native XLM)
+/// * `feed` - The SEP-40 feed contract
+/// * `feed_asset` - The reserve's asset as named by the feed
+///
+/// # Errors
+/// * `PriceOracleError::Unauthorized` - If caller is not the admin
+/// * `PriceOracleError::InvalidFeed` - If the feed is this contract or
+///   reports more than 18 decimals
+pub fn set_price_feed(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    feed: Address,
+    feed_asset: Sep40Asset,
+) -> Result<(), PriceOracleError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| PriceOracleError::Unauthorized)?;
+    if feed == env.current_contract_address() {
+        return Err(PriceOracleError::InvalidFeed);
+    }
+
+    let decimals = Sep40Client::new(env, &feed).decimals();
+    if decimals > 18 {
+        return Err(PriceOracleError::InvalidFeed);
+    }
+
+    env.storage().persistent().set(
+        &PriceOracleDataKey::FeedSource(asset.clone()),
+        &PriceFeedSource {
+            feed,
+            feed_asset,
+            decimals,
+        },
+    );
+    env.storage()
+        .persistent()
+        .remove(&PriceOracleDataKey::FeedPrice(asset));
+    Ok(())
+}
+
+/// Stop reading a reserve's price from a feed (admin only)
+pub fn remove_price_feed(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+) -> Result<(), PriceOracleError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| PriceOracleError::Unauthorized)?;
+    env.storage()
+        .persistent()
+        .remove(&PriceOracleDataKey::FeedSource(asset.clone()));
+    env.storage()
+        .persistent()
+        .remove(&PriceOracleDataKey::FeedPrice(asset));
+    Ok(())
+}
+
+/// Get the oldest usable price age of a reserve, in seconds
+///
+/// Defaults to the oracle config's `max_staleness_seconds`.
+pub fn get_max_price_age(env: &Env, asset: &Option<Address>) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<PriceOracleDataKey, u64>(&PriceOracleDataKey::MaxPriceAge(asset.clone()))
+        .unwrap_or_else(|| crate::oracle::get_oracle_config(env).max_staleness_seconds)
+}
+
+/// Set the oldest usable price age of a reserve (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `asset` - The reserve (None for native XLM)
+/// * `max_age` - Maximum price age in seconds
+///
+/// # Errors
+/// * `PriceOracleError::Unauthorized` - If caller is not the admin
+/// * `PriceOracleError::InvalidMaxPriceAge` - If `max_age` is zero
+pub fn set_max_price_age(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    max_age: u64,
+) -> Result<(), PriceOracleError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| PriceOracleError::Unauthorized)?;
+    if max_age == 0 {
+        return Err(PriceOracleError::InvalidMaxPriceAge);
+    }
+    env.storage()
+        .persistent()
+        .set(&PriceOracleDataKey::MaxPriceAge(asset), &max_age);
+    Ok(())
+}
+
+/// Check that a reserve's price updated at `updated_at` is not too old
+///
+/// # Errors
+/// * `PriceOracleError::StalePrice` - If the price is older than the
+///   reserve's `max_price_age`, or dated in the future
+pub fn check_price_age(
+    env: &Env,
+    asset: &Option<Address>,
+    updated_at: u64,
+) -> Result<(), PriceOracleError> {
+    let now = env.ledger().timestamp();
+    if updated_at > now || now - updated_at > get_max_price_age(env, asset) {
+        return Err(PriceOracleError::StalePrice);
+    }
+    Ok(())
+}
+
+/// Fetch a reserve's price from its feed, bypassing the cache
+///
+/// # Returns
+/// The normalized price, which is also cached
+///
+/// # Errors
+/// * `PriceOracleError::FeedNotConfigured` - If the reserve has no feed
+/// * `PriceOracleError::PriceUnavailable` - If the feed has no price
+/// * `PriceOracleError::InvalidPrice` - If the price is not positive
+/// * `PriceOracleError::StalePrice` - If the price is older than the
+///   reserve's `max_price_age`
+pub fn refresh_price(env: &Env, asset: &Option<Address>) -> Result<i128, PriceOracleError> {
+    let source = get_price_feed(env, asset).ok_or(PriceOracleError::FeedNotConfigured)?;
+    let data = Sep40Client::new(env, &source.feed)
+        .lastprice(&source.feed_asset)
+        .ok_or(PriceOracleError::PriceUnavailable)?;
+    let price = normalize(data.price, source.decimals)?;
+    if price <= 0 {
+        return Err(PriceOracleError::InvalidPrice);
+    }
+
+    let fetched = FeedPrice {
+        price,
+        timestamp: data.timestamp,
+        fetched_at: env.ledger().timestamp(),
+    };
+    check_price_age(env, asset, fetched.timestamp)?;
+    env.storage()
+        .persistent()
+        .set(&PriceOracleDataKey::FeedPrice(asset.clone()), &fetched);
+    Ok(price)
+}
+
+/// Get a reserve's price from its feed, using the cache when fresh
+///
+/// # Returns
+/// None if the reserve has no feed, otherwise the normalized price or the
+/// reason it is unavailable
+pub fn get_feed_price(
+    env: &Env,
+    asset: &Option<Address>,
+) -> Option<Result<i128, PriceOracleError>> {
+    get_price_feed(env, asset)?;
+
+    let ttl = crate::oracle::get_oracle_config(env).cache_ttl_seconds;
+    if let Some(cached) = env
+        .storage()
+        .persistent()
+        .get::<PriceOracleDataKey, FeedPrice>(&PriceOracleDataKey::FeedPrice(asset.clone()))
+    {
+        if env.ledger().timestamp() <= cached.fetched_at.saturating_add(ttl)
+            && check_price_age(env, asset, cached.timestamp).is_ok()
+        {
+            return Some(Ok(cached.price));
+        }
+    }
+    Some(refresh_price(env, asset))
+}
@@ -0,0 +1,171 @@
+//! # Protocol Init Module
+//!
+//! Brings a fresh deployment up in one call. `initialize_protocol` takes an
+//! `InitConfig` and sets the admin, the default risk and rate models, the
+//! native asset, oracle parameters and fallback oracles, reserves, AMM
+//! settings and protocols, and roles, replacing the sequence of
+//! `initialize` / `initialize_asset` / `set_amm_pool` / ... calls.
+//!
+//! ## Invariants
+//! - The admin authorizes the call once; every section is applied as that admin.
+//! - Sections are validated with the same rules as their setters. Any failure
+//!   reverts the whole call, so a deployment is never left half configured.
+//! - Idempotency guard: the call is rejected once the protocol is initialized,
+//!   including by the legacy `initialize`, or if any section is already set.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::admin::RoleGrant;
+use crate::config_export::OracleSourceConfig;
+use crate::cross_asset::AssetConfig;
+use crate::deposit::DepositDataKey;
+use crate::events::{emit_protocol_initialized, ProtocolInitializedEvent};
+use crate::oracle::OracleConfig;
+use stellarlend_amm::{AmmProtocolConfig, AmmSettings};
+
+/// Errors that can occur during protocol initialization
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProtocolInitError {
+    /// Protocol, or one of its sections, is already initialized
+    AlreadyInitialized = 1,
+    /// A section of the config failed validation
+    InvalidConfig = 2,
+}
+
+/// Storage keys for protocol initialization data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum ProtocolInitDataKey {
+    /// Set once `initialize_protocol` has completed: bool
+    ProtocolInitialized,
+}
+
+/// Full configuration of a fresh deployment
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitConfig {
+    /// Protocol admin (must authorize the call)
+    pub admin: Address,
+    /// Native asset contract address
+    pub native_asset: Option<Address>,
+    /// Reserve configs, in registration order
+    pub reserves: Vec<AssetConfig>,
+    /// Oracle safety parameters
+    pub oracle_config: OracleConfig,
+    /// Fallback oracles of priced assets
+    pub oracle_sources: Vec<OracleSourceConfig>,
+    /// AMM operation settings: empty to leave unset, otherwise one entry
+    pub amm_settings: Vec<AmmSettings>,
+    /// AMM protocols to register
+    pub amm_protocols: Vec<AmmProtocolConfig>,
+    /// Role assignments
+    pub roles: Vec<RoleGrant>,
+}
+
+/// Check whether `initialize_protocol` has completed
+pub fn is_protocol_initialized(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get::<ProtocolInitDataKey, bool>(&ProtocolInitDataKey::ProtocolInitialized)
+        .unwrap_or(false)
+}
+
+/// Initialize the protocol with its full configuration in one call
+///
+/// # Arguments
+/// * `config` - The deployment configuration; `config.admin` must authorize
+///
+/// # Errors
+/// * `ProtocolInitError::AlreadyInitialized` - If the protocol or any section is already set
+/// * `ProtocolInitError::InvalidConfig` - If any section fails validation
+pub fn initialize_protocol(env: &Env, config: InitConfig) -> Result<(), ProtocolInitError> {
+    if is_protocol_initialized(env)
+        || crate::admin::has_admin(env)
+        || crate::risk_params::get_risk_params(env).is_some()
+        || crate::interest_rate::get_interest_rate_config(env).is_some()
+        || !crate::cross_asset::get_asset_list(env).is_empty()
+    {
+        return Err(ProtocolInitError::AlreadyInitialized);
+    }
+    config.admin.require_auth();
+
+    crate::admin::set_admin(env, config.admin.clone(), None)
+        .map_err(|_| ProtocolInitError::AlreadyInitialized)?;
+    crate::risk_management::initialize_risk_management(env, config.admin.clone())
+        .map_err(|_| ProtocolInitError::InvalidConfig)?;
+    crate::risk_params::initialize_risk_params(env)
+        .map_err(|_| ProtocolInitError::InvalidConfig)?;
+    crate::interest_rate::initialize_interest_rate_config(env, config.admin.clone())
+        .map_err(|_| ProtocolInitError::AlreadyInitialized)?;
+
+    apply_config(env, &config)?;
+
+    env.storage()
+        .persistent()
+        .set(&ProtocolInitDataKey::ProtocolInitialized, &true);
+
+    emit_protocol_initialized(
+        env,
+        ProtocolInitializedEvent {
+            admin: config.admin,
+            reserves: config.reserves.len(),
+            amm_protocols: config.amm_protocols.len(),
+            roles: config.roles.len(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Apply every section of a config as `config.admin`
+///
+/// The caller is responsible for authorizing `config.admin` as the protocol admin.
+pub(crate) fn apply_config(env: &Env, config: &InitConfig) -> Result<(), ProtocolInitError> {
+    let admin = config.admin.clone();
+
+    if let Some(native_asset) = config.native_asset.clone() {
+        if native_asset == env.current_contract_address() {
+            return Err(ProtocolInitError::InvalidConfig);
+        }
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::NativeAssetAddress, &native_asset);
+    }
+
+    crate::oracle::configure_oracle(env, admin.clone(), config.oracle_config.clone())
+        .map_err(|_| ProtocolInitError::InvalidConfig)?;
+    for source in config.oracle_sources.iter() {
+        if let Some(fallback_oracle) = source.fallback_oracle {
+            crate::oracle::set_fallback_oracle(env, admin.clone(), source.asset, fallback_oracle)
+                .map_err(|_| ProtocolInitError::InvalidConfig)?;
+        }
+    }
+
+    // The cross-asset module keeps its own admin; a fresh deployment has none yet
+    crate::cross_asset::set_admin_if_unset(env, &admin);
+    for reserve in config.reserves.iter() {
+        crate::cross_asset::register_asset(env, reserve.asset.clone(), reserve)
+            .map_err(|_| ProtocolInitError::InvalidConfig)?;
+    }
+
+    for amm_settings in config.amm_settings.iter() {
+        stellarlend_amm::update_amm_settings(env, admin.clone(), amm_settings)
+            .map_err(|_| ProtocolInitError::InvalidConfig)?;
+    }
+    for protocol in config.amm_protocols.iter() {
+        stellarlend_amm::add_amm_protocol(env, admin.clone(), protocol)
+            .map_err(|_| ProtocolInitError::InvalidConfig)?;
+    }
+
+    for grant in config.roles.iter() {
+        crate::admin::grant_role(env, admin.clone(), grant.role, grant.account)
+            .map_err(|_| ProtocolInitError::InvalidConfig)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+//! # Rate Statistics Module
+//!
+//! Time-weighted averages of utilization and borrow rate per reserve, so
+//! rate-strategy governance and integrators can base decisions on smoothed
+//! data instead of instantaneous spikes.
+//!
+//! ## Accumulators
+//! Every reserve keeps running integrals of utilization and borrow rate over
+//! time (`value * seconds`). They are advanced whenever interest is accrued
+//! on a borrow, repayment or liquidation in that reserve, using the rate in
+//! force at accrual time. Core reserves share the pool's utilization curve,
+//! so their averages differ only in when they are sampled.
+//!
+//! ## Windows
+//! For each configured window (1d/7d/30d by default) the reserve keeps up to
+//! `CHECKPOINTS_PER_WINDOW + 1` checkpoints of the integrals, spaced
+//! `window / CHECKPOINTS_PER_WINDOW` apart. The average over a window is the
+//! growth of the integrals since the oldest checkpoint inside the window,
+//! divided by the time elapsed since it.
+//!
+//! ## Invariants
+//! - Recording never fails the accrual that triggers it.
+//! - Averages are only reported once a reserve has at least one checkpoint;
+//!   `observed_seconds` tells how much history backs them.
+//! - Only the admin can change the windows; checkpoints are kept per window
+//!   length, so existing history is not reinterpreted.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, vec, Address, Env, Vec};
+
+/// Checkpoints taken per window
+pub const CHECKPOINTS_PER_WINDOW: u64 = 24;
+
+/// Maximum number of configured windows
+pub const MAX_RATE_WINDOWS: u32 = 5;
+
+/// Shortest configurable window (1 hour)
+pub const MIN_RATE_WINDOW: u64 = 60 * 60;
+
+/// Longest configurable window (90 days)
+pub const MAX_RATE_WINDOW: u64 = 90 * 24 * 60 * 60;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Errors that can occur during rate statistics operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RateStatsError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Window list is empty, too long, duplicated or out of range
+    InvalidWindow = 2,
+}
+
+/// Storage keys for rate statistics data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum RateStatsDataKey {
+    /// Configured window lengths in seconds: Vec<u64>
+    AveragingWindows,
+    /// Running integrals of a reserve: RateAccumulator
+    RateAccumulator(Option<Address>),
+    /// Checkpoints of a reserve for a window length: Vec<RateAccumulator>
+    RateCheckpoints(Option<Address>, u64),
+}
+
+/// Running utilization and borrow rate integrals of a reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateAccumulator {
+    /// Timestamp the integrals were last advanced to
+    pub timestamp: u64,
+    /// Integral of utilization (bps * seconds)
+    pub cumulative_utilization: i128,
+    /// Integral of the borrow rate (bps * seconds)
+    pub cumulative_borrow_rate: i128,
+}
+
+/// Time-weighted averages of a reserve over a window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateStats {
+    /// Window length in seconds
+    pub window: u64,
+    /// Time-weighted average utilization (bps)
+    pub avg_utilization: i128,
+    /// Time-weighted average borrow rate (bps)
+    pub avg_borrow_rate: i128,
+    /// Seconds of history backing the averages
+    pub observed_seconds: u64,
+}
+
+fn current_values(env: &Env) -> (i128, i128) {
+    let utilization = crate::interest_rate::calculate_utilization(env).unwrap_or(0);
+    let borrow_rate = crate::interest_rate::calculate_borrow_rate(env).unwrap_or(0);
+    (utilization, borrow_rate)
+}
+
+/// Advance an accumulator to `timestamp` at constant utilization and rate
+fn advance(
+    accumulator: &RateAccumulator,
+    timestamp: u64,
+    utilization: i128,
+    borrow_rate: i128,
+) -> RateAccumulator {
+    let elapsed = timestamp.saturating_sub(accumulator.timestamp) as i128;
+    RateAccumulator {
+        timestamp: timestamp.max(accumulator.timestamp),
+        cumulative_utilization: accumulator
+            .cumulative_utilization
+            .saturating_add(utilization.saturating_mul(elapsed)),
+        cumulative_borrow_rate: accumulator
+            .cumulative_borrow_rate
+            .saturating_add(borrow_rate.saturating_mul(elapsed)),
+    }
+}
+
+fn get_accumulator(env: &Env, asset: &Option<Address>) -> Option<RateAccumulator> {
+    env.storage()
+        .persistent()
+        .get::<RateStatsDataKey, RateAccumulator>(&RateStatsDataKey::RateAccumulator(asset.clone()))
+}
+
+fn get_checkpoints(env: &Env, asset: &Option<Address>, window: u64) -> Vec<RateAccumulator> {
+    env.storage()
+        .persistent()
+        .get::<RateStatsDataKey, Vec<RateAccumulator>>(&RateStatsDataKey::RateCheckpoints(
+            asset.clone(),
+            window,
+        ))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the configured window lengths in seconds
+pub fn get_rate_windows(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get::<RateStatsDataKey, Vec<u64>>(&RateStatsDataKey::AveragingWindows)
+        .unwrap_or(vec![
+            env,
+            SECONDS_PER_DAY,
+            7 * SECONDS_PER_DAY,
+            30 * SECONDS_PER_DAY,
+        ])
+}
+
+/// Configure the averaging windows (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `windows` - Window lengths in seconds
+///
+/// # Errors
+/// * `RateStatsError::Unauthorized` - If caller is not the admin
+/// * `RateStatsError::InvalidWindow` - If the list is empty, too long,
+///   contains duplicates or a window outside `[MIN_RATE_WINDOW, MAX_RATE_WINDOW]`
+pub fn set_rate_windows(
+    env: &Env,
+    caller: Address,
+    windows: Vec<u64>,
+) -> Result<(), RateStatsError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| RateStatsError::Unauthorized)?;
+
+    if windows.is_empty() || windows.len() > MAX_RATE_WINDOWS {
+        return Err(RateStatsError::InvalidWindow);
+    }
+    for (i, window) in windows.iter().enumerate() {
+        if !(MIN_RATE_WINDOW..=MAX_RATE_WINDOW).contains(&window) {
+            return Err(RateStatsError::InvalidWindow);
+        }
+        if windows.iter().skip(i + 1).any(|other| other == window) {
+            return Err(RateStatsError::InvalidWindow);
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&RateStatsDataKey::AveragingWindows, &windows);
+    Ok(())
+}
+
+/// Advance the integrals of a reserve and take due checkpoints
+///
+/// Called whenever interest is accrued in the reserve.
+pub(crate) fn record_accrual(env: &Env, asset: &Option<Address>) {
+    let timestamp = env.ledger().timestamp();
+    let (utilization, borrow_rate) = current_values(env);
+    let accumulator = match get_accumulator(env, asset) {
+        Some(accumulator) => advance(&accumulator, timestamp, utilization, borrow_rate),
+        None => RateAccumulator {
+            timestamp,
+            cumulative_utilization: 0,
+            cumulative_borrow_rate: 0,
+        },
+    };
+    env.storage().persistent().set(
+        &RateStatsDataKey::RateAccumulator(asset.clone()),
+        &accumulator,
+    );
+
+    for window in get_rate_windows(env).iter() {
+        let mut checkpoints = get_checkpoints(env, asset, window);
+        let due = match checkpoints.last() {
+            Some(last) => timestamp >= last.timestamp + window / CHECKPOINTS_PER_WINDOW,
+            None => true,
+        };
+        if !due {
+            continue;
+        }
+        checkpoints.push_back(accumulator.clone());
+        while checkpoints.len() as u64 > CHECKPOINTS_PER_WINDOW + 1 {
+            checkpoints.pop_front();
+        }
+        env.storage().persistent().set(
+            &RateStatsDataKey::RateCheckpoints(asset.clone(), window),
+            &checkpoints,
+        );
+    }
+}
+
+/// Get the time-weighted averages of a reserve over a window
+///
+/// # Arguments
+/// * `asset` - The reserve (None for native XLM)
+/// * `window` - A configured window length in seconds
+///
+/// # Returns
+/// None until the reserve has a checkpoint for the window. With less than a
+/// second of history the current utilization and rate are returned.
+pub fn get_rate_stats(env: &Env, asset: &Option<Address>, window: u64) -> Option<RateStats> {
+    let accumulator = get_accumulator(env, asset)?;
+    let checkpoints = get_checkpoints(env, asset, window);
+    let timestamp = env.ledger().timestamp();
+
+    // Oldest checkpoint inside the window, or the latest one after a quiet spell
+    let start = checkpoints
+        .iter()
+        .find(|checkpoint| timestamp.saturating_sub(checkpoint.timestamp) <= window)
+        .or_else(|| checkpoints.last())?;
+
+    let (utilization, borrow_rate) = current_values(env);
+    let now = advance(&accumulator, timestamp, utilization, borrow_rate);
+    let observed_seconds = now.timestamp.saturating_sub(start.timestamp);
+    if observed_seconds == 0 {
+        return Some(RateStats {
+            window,
+            avg_utilization: utilization,
+            avg_borrow_rate: borrow_rate,
+            observed_seconds,
+        });
+    }
+
+    Some(RateStats {
+        window,
+        avg_utilization: (now.cumulative_utilization - start.cumulative_utilization)
+            / observed_seconds as i128,
+        avg_borrow_rate: (now.cumulative_borrow_rate - start.cumulative_borrow_rate)
+            / observed_seconds as i128,
+        observed_seconds,
+    })
+}
+
+/// Get the time-weighted averages of a reserve over every configured window
+pub fn get_all_rate_stats(env: &Env, asset: &Option<Address>) -> Vec<RateStats> {
+    let mut stats = Vec::new(env);
+    for window in get_rate_windows(env).iter() {
+        if let Some(window_stats) = get_rate_stats(env, asset, window) {
+            stats.push_back(window_stats);
+        }
+    }
+    stats
+}
@@ -9,6 +9,24 @@
 //! 1. Accrued interest is paid first.
 //! 2. Any remaining repayment amount reduces the principal debt.
 //!
+//! The asset's reserve factor share of the interest paid is credited to the
+//! protocol reserve (see `reserve`).
+//!
+//! ## Over-repayment
+//! Repayments are capped at the outstanding debt and only the capped amount
+//! is pulled from the user, so any excess never leaves their account. Passing
+//! `i128::MAX` therefore repays the position in full.
+//!
+//! ## Repaying with Collateral
+//! `repay_with_collateral` lets a borrower deleverage without outside
+//! capital: the given amount of their own collateral is sold through the AMM
+//! router against the protocol's hosted pools and the proceeds repay the
+//! debt. The swap must return at least the oracle value of the collateral
+//! less `SWAP_SLIPPAGE_TOLERANCE_BPS`; proceeds above the outstanding debt
+//! are paid out to the borrower. No liquidation bonus is involved.
+//! Borrowers can also let keepers do this for them once their health
+//! factor drops below a trigger (see `auto_deleverage`).
+//!
 //! ## Invariants
 //! - Repay amount must be strictly positive.
 //! - User must have outstanding debt to repay.
@@ -43,62 +61,38 @@ pub enum RepayError {
     Overflow = 6,
     /// Reentrancy detected
     Reentrancy = 7,
+    /// Not enough collateral to sell
+    InsufficientCollateral = 8,
+    /// AMM swap of the collateral failed
+    SwapFailed = 9,
+    /// AMM swap returned less than the minimum output
+    SlippageExceeded = 10,
+    /// Repaying with collateral would leave the position liquidatable and
+    /// less healthy than before
+    UnhealthyPosition = 11,
 }
 
-/// Calculate interest accrued since last accrual time
-///
-/// Uses dynamic interest rate based on current protocol utilization.
-///
-/// # Arguments
-/// * `env` - The Soroban environment
-/// * `principal` - The principal amount to calculate interest on
-/// * `last_accrual_time` - The timestamp of the last interest accrual
-/// * `current_time` - The current ledger timestamp
-///
-/// # Returns
-/// * `Result<i128, RepayError>` - The accrued interest amount or an error
-fn calculate_accrued_interest(
-    env: &Env,
-    principal: i128,
-    last_accrual_time: u64,
-    current_time: u64,
-) -> Result<i128, RepayError> {
-    if principal == 0 {
-        return Ok(0);
-    }
-    if current_time <= last_accrual_time {
-        return Ok(0);
-    }
-    let rate_bps = crate::interest_rate::calculate_borrow_rate(env).map_err(|_| RepayError::Overflow)?;
-    crate::interest_rate::calculate_accrued_interest(
-        principal,
-        last_accrual_time,
-        current_time,
-        rate_bps,
-    ).map_err(|_| RepayError::Overflow)
-}
+/// Slippage tolerance against the oracle price for repay-with-collateral (1%)
+const SWAP_SLIPPAGE_TOLERANCE_BPS: i128 = 100;
 
 /// Accrue interest on a position
 ///
-/// Updates the position's borrow_interest and last_accrual_time based on elapsed time
-/// and the current interest rate.
+/// Books the interest accrued since the last accrual through the pool's
+/// borrow index (see `interest_index`).
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
+/// * `user` - The owner of the position
 /// * `position` - A mutable reference to the user's position
 ///
 /// # Returns
 /// * `Result<(), RepayError>` - Success or an error
-fn accrue_interest(env: &Env, position: &mut Position) -> Result<(), RepayError> {
-    let current_time = env.ledger().timestamp();
+fn accrue_interest(env: &Env, user: &Address, position: &mut Position) -> Result<(), RepayError> {
     if position.debt == 0 {
         position.borrow_interest = 0;
-        position.last_accrual_time = current_time;
-        return Ok(());
     }
-    let new_interest = calculate_accrued_interest(env, position.debt, position.last_accrual_time, current_time)?;
-    position.borrow_interest = position.borrow_interest.checked_add(new_interest).ok_or(RepayError::Overflow)?;
-    position.last_accrual_time = current_time;
+    crate::interest_index::accrue_position(env, user, position)
+        .map_err(|_| RepayError::Overflow)?;
     Ok(())
 }
 
@@ -125,7 +119,8 @@ fn get_native_asset_address(env: &Env) -> Result<Address, RepayError> {
 /// * `env` - The Soroban environment
 /// * `user` - The address of the user repaying debt
 /// * `asset` - The address of the asset contract to repay (None for native XLM)
-/// * `amount` - The amount to repay
+/// * `amount` - The amount to repay; amounts above the outstanding debt
+///   (e.g. `i128::MAX`) repay it in full and the excess is not transferred
 ///
 /// # Returns
 /// Returns a tuple (remaining_debt, interest_paid, principal_paid)
@@ -153,23 +148,254 @@ pub fn repay_debt(
     user: Address,
     asset: Option<Address>,
     amount: i128,
+) -> Result<(i128, i128, i128), RepayError> {
+    repay_internal(env, user, asset, amount, true)
+}
+
+/// Repay debt by selling the user's own collateral through the AMM router
+///
+/// `amount` of collateral is removed from the position and swapped by this
+/// contract into `debt_asset`; the proceeds repay the debt, interest first,
+/// and any excess over the outstanding debt is transferred to the user. The
+/// whole call reverts if the swap fails or returns less than the oracle
+/// value of the collateral minus `SWAP_SLIPPAGE_TOLERANCE_BPS`.
+///
+/// # Arguments
+/// * `user` - The borrower
+/// * `debt_asset` - The asset the debt is repaid in
+/// * `collateral_asset` - The collateral asset sold
+/// * `amount` - The amount of collateral to sell
+///
+/// # Returns
+/// Returns a tuple (remaining_debt, collateral_sold, debt_repaid)
+///
+/// # Errors
+/// * `RepayError::InvalidAsset` - If both assets are the same
+/// * `RepayError::InsufficientCollateral` - If the user holds less collateral
+/// * `RepayError::SlippageExceeded` - If the swap output is too low
+/// * `RepayError::UnhealthyPosition` - If the position would end up
+///   liquidatable with a lower health factor
+/// * Otherwise the same as `repay_debt`
+pub fn repay_with_collateral(
+    env: &Env,
+    user: Address,
+    debt_asset: Address,
+    collateral_asset: Address,
+    amount: i128,
+) -> Result<(i128, i128, i128), RepayError> {
+    user.require_auth();
+    repay_with_collateral_internal(env, user, debt_asset, collateral_asset, amount)
+}
+
+/// Sell collateral to repay debt without the user's authorization
+///
+/// Same as `repay_with_collateral`; callers must have authorized the sale
+/// some other way (see `auto_deleverage`).
+pub(crate) fn repay_with_collateral_internal(
+    env: &Env,
+    user: Address,
+    debt_asset: Address,
+    collateral_asset: Address,
+    amount: i128,
 ) -> Result<(i128, i128, i128), RepayError> {
     if amount <= 0 {
         return Err(RepayError::InvalidAmount);
     }
+    if debt_asset == collateral_asset || collateral_asset == env.current_contract_address() {
+        return Err(RepayError::InvalidAsset);
+    }
+
+    let debt_before = get_outstanding_debt(env, &user)?;
+    if debt_before == 0 {
+        return Err(RepayError::NoDebt);
+    }
+
+    // Credit supply interest earned so far; it can be sold too
+    crate::supply_interest::settle_supply_interest(env, &user);
 
+    let collateral_key = DepositDataKey::CollateralBalance(user.clone());
+    let collateral_before = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&collateral_key)
+        .unwrap_or(0);
+    if collateral_before < amount {
+        return Err(RepayError::InsufficientCollateral);
+    }
+
+    // Minimum output from the oracle value of the collateral sold
+    let collateral_price = crate::liquidate::get_asset_price(env, &Some(collateral_asset.clone()))
+        .map_err(|_| RepayError::InvalidAsset)?;
+    let debt_price = crate::liquidate::get_asset_price(env, &Some(debt_asset.clone()))
+        .map_err(|_| RepayError::InvalidAsset)?;
+    if debt_price <= 0 {
+        return Err(RepayError::InvalidAsset);
+    }
+    let min_amount_out = amount
+        .checked_mul(collateral_price)
+        .and_then(|v| v.checked_mul(10_000 - SWAP_SLIPPAGE_TOLERANCE_BPS))
+        .and_then(|v| v.checked_div(debt_price.checked_mul(10_000)?))
+        .ok_or(RepayError::Overflow)?
+        .max(1);
+
+    // Take the collateral off the position before selling it
+    let collateral_after = take_collateral(env, &user, &collateral_asset, amount)?;
+
+    let params = stellarlend_amm::SwapParams {
+        protocol: env.current_contract_address(),
+        token_in: Some(collateral_asset),
+        token_out: Some(debt_asset.clone()),
+        amount_in: amount,
+        min_amount_out,
+        slippage_tolerance: SWAP_SLIPPAGE_TOLERANCE_BPS,
+        deadline: env.ledger().timestamp(),
+        use_oracle_floor: false,
+    };
+    let amount_out = stellarlend_amm::execute_swap(env, env.current_contract_address(), params)
+        .map_err(|e| match e {
+            stellarlend_amm::AmmError::MinOutputNotMet
+            | stellarlend_amm::AmmError::SlippageExceeded => RepayError::SlippageExceeded,
+            _ => RepayError::SwapFailed,
+        })?;
+    if amount_out < min_amount_out {
+        return Err(RepayError::SlippageExceeded);
+    }
+
+    let (remaining_debt, interest_paid, principal_paid) = repay_internal(
+        env,
+        user.clone(),
+        Some(debt_asset.clone()),
+        amount_out,
+        false,
+    )?;
+    let debt_repaid = interest_paid + principal_paid;
+
+    // Selling collateral below the debt ratio must not push the position
+    // deeper underwater
+    if remaining_debt > 0
+        && crate::risk_params::can_be_liquidated(env, collateral_after, remaining_debt)
+            .unwrap_or(false)
+        && collateral_after.saturating_mul(debt_before)
+            < collateral_before.saturating_mul(remaining_debt)
+    {
+        return Err(RepayError::UnhealthyPosition);
+    }
+
+    // Proceeds above the outstanding debt go back to the user
+    let surplus = amount_out - debt_repaid;
+    if surplus > 0 {
+        soroban_sdk::token::Client::new(env, &debt_asset).transfer(
+            &env.current_contract_address(),
+            &user,
+            &surplus,
+        );
+    }
+
+    Ok((remaining_debt, amount, debt_repaid))
+}
+
+/// Repay a user's debt with tokens this contract already holds, without the
+/// user's authorization
+///
+/// Books the repayment exactly like `repay_debt`; callers must have brought
+/// the funds into the contract some other way (see `hedge_market`).
+///
+/// # Returns
+/// Returns a tuple (remaining_debt, interest_paid, principal_paid)
+pub(crate) fn repay_from_contract(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(i128, i128, i128), RepayError> {
+    repay_internal(env, user, asset, amount, false)
+}
+
+/// Remove `amount` of `collateral_asset` from a user's position
+///
+/// The tokens stay with this contract; the caller decides where they go.
+///
+/// # Returns
+/// The user's collateral balance left
+///
+/// # Errors
+/// * `RepayError::InsufficientCollateral` - If the user holds less collateral
+pub(crate) fn take_collateral(
+    env: &Env,
+    user: &Address,
+    collateral_asset: &Address,
+    amount: i128,
+) -> Result<i128, RepayError> {
+    let collateral_key = DepositDataKey::CollateralBalance(user.clone());
+    let collateral_before = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&collateral_key)
+        .unwrap_or(0);
+    if collateral_before < amount {
+        return Err(RepayError::InsufficientCollateral);
+    }
+
+    let collateral_after = collateral_before - amount;
+    env.storage()
+        .persistent()
+        .set(&collateral_key, &collateral_after);
+    let position_key = DepositDataKey::Position(user.clone());
+    if let Some(mut position) = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+    {
+        position.collateral = collateral_after;
+        env.storage().persistent().set(&position_key, &position);
+    }
+    crate::reserve::record_supply_change(
+        env,
+        &crate::deposit::canonical_asset(env, Some(collateral_asset.clone())),
+        -amount,
+    );
+    Ok(collateral_after)
+}
+
+/// Repay debt and, when `pull` is set, transfer the repaid amount from the
+/// user
+///
+/// # Returns
+/// Returns a tuple (remaining_debt, interest_paid, principal_paid)
+fn repay_internal(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+    pull: bool,
+) -> Result<(i128, i128, i128), RepayError> {
+    // Validate amount
+    if amount <= 0 {
+        return Err(RepayError::InvalidAmount);
+    }
+
+    // Check if repayments are paused
     let pause_switches_key = DepositDataKey::PauseSwitches;
-    if let Some(pause_map) = env.storage().persistent().get::<DepositDataKey, Map<Symbol, bool>>(&pause_switches_key) {
+    if let Some(pause_map) = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Map<Symbol, bool>>(&pause_switches_key)
+    {
         if let Some(paused) = pause_map.get(Symbol::new(env, "pause_repay")) {
-            if paused { return Err(RepayError::RepayPaused); }
+            if paused {
+                return Err(RepayError::RepayPaused);
+            }
         }
     }
+    if crate::risk_management::is_operation_paused(env, Symbol::new(env, "pause_repay")) {
+        return Err(RepayError::RepayPaused);
+    }
 
     let timestamp = env.ledger().timestamp();
 
-    if let Some(ref asset_addr) = asset {
-        if asset_addr == &env.current_contract_address() {
-            return Err(RepayError::InvalidAsset);
+    // Wrapped native XLM is booked as native XLM
+    let asset = crate::deposit::canonical_asset(env, asset);
+
     // Determine the asset contract address to use
     let asset_addr = match &asset {
         Some(addr) => {
@@ -182,61 +408,54 @@ pub fn repay_debt(
         None => get_native_asset_address(env)?,
     };
 
-    let reserve_factor = if let Some(asset_addr) = asset.as_ref() {
-        let params_key = DepositDataKey::AssetParams(asset_addr.clone());
-        if let Some(params) = env.storage().persistent().get::<DepositDataKey, crate::deposit::AssetParams>(&params_key) {
-            1000 // Default 10%
-        } else {
-            1000
-        }
-    } else {
-        1000
-    };
-
+    // Get user position
     let position_key = DepositDataKey::Position(user.clone());
-    let mut position = env.storage().persistent().get::<DepositDataKey, Position>(&position_key).ok_or(RepayError::NoDebt)?;
+    let mut position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+        .ok_or(RepayError::NoDebt)?;
 
     if position.debt == 0 && position.borrow_interest == 0 {
         return Err(RepayError::NoDebt);
     }
 
-    accrue_interest(env, &mut position)?;
-
-    let total_debt = position.debt.checked_add(position.borrow_interest).ok_or(RepayError::Overflow)?;
-    let repay_amount = if amount >= total_debt { total_debt } else { amount };
+    // Accrue interest before repayment
+    accrue_interest(env, &user, &mut position)?;
+    crate::rate_stats::record_accrual(env, &asset);
+    crate::liquidation_insurance::accrue_premium(env, &user, &mut position)
+        .map_err(|_| RepayError::Overflow)?;
 
-    if let Some(ref asset_addr) = asset {
-        #[cfg(not(test))]
-        {
-            let token_client = soroban_sdk::token::Client::new(env, asset_addr);
-            let user_balance = token_client.balance(&user);
-            if user_balance < repay_amount {
-                return Err(RepayError::InsufficientBalance);
-            }
-            token_client.transfer_from(&env.current_contract_address(), &user, &env.current_contract_address(), &repay_amount);
-        }
-    }
+    // Repayment is capped at the outstanding debt
+    let total_debt = position
+        .debt
+        .checked_add(position.borrow_interest)
+        .ok_or(RepayError::Overflow)?;
+    let repay_amount = if amount >= total_debt {
+        total_debt
+    } else {
+        amount
+    };
 
-    let interest_paid = if repay_amount <= position.borrow_interest { repay_amount } else { position.borrow_interest };
-    let principal_paid = repay_amount.checked_sub(interest_paid).ok_or(RepayError::Overflow)?;
     // Handle asset transfer - user pays the contract
-    // We use the determined asset_addr (either token or native)
-    let token_client = soroban_sdk::token::Client::new(env, &asset_addr);
+    if pull {
+        let token_client = soroban_sdk::token::Client::new(env, &asset_addr);
 
-    // Check user balance
-    let user_balance = token_client.balance(&user);
-    if user_balance < repay_amount {
-        return Err(RepayError::InsufficientBalance);
-    }
+        // Check user balance
+        let user_balance = token_client.balance(&user);
+        if user_balance < repay_amount {
+            return Err(RepayError::InsufficientBalance);
+        }
 
-    // Transfer tokens from user to contract
-    // The user must have approved the contract to spend their tokens
-    token_client.transfer_from(
-        &env.current_contract_address(), // spender (this contract)
-        &user,                           // from (user)
-        &env.current_contract_address(), // to (this contract)
-        &repay_amount,
-    );
+        // Transfer tokens from user to contract
+        // The user must have approved the contract to spend their tokens
+        token_client.transfer_from(
+            &env.current_contract_address(), // spender (this contract)
+            &user,                           // from (user)
+            &env.current_contract_address(), // to (this contract)
+            &repay_amount,
+        );
+    }
 
     // Calculate interest and principal portions
     // Interest is paid first, then principal
@@ -257,26 +476,28 @@ pub fn repay_debt(
         .unwrap_or(0); // Should not underflow, but handle gracefully
 
     position.debt = position.debt.checked_sub(principal_paid).unwrap_or(0); // Should not underflow, but handle gracefully
-
-    position.borrow_interest = position.borrow_interest.checked_sub(interest_paid).unwrap_or(0);
-    position.debt = position.debt.checked_sub(principal_paid).unwrap_or(0);
     position.last_accrual_time = timestamp;
 
     env.storage().persistent().set(&position_key, &position);
-
-    if interest_paid > 0 {
-        let reserve_amount = interest_paid.checked_mul(reserve_factor).ok_or(RepayError::Overflow)?.checked_div(10000).ok_or(RepayError::Overflow)?;
-        if reserve_amount > 0 {
-            let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
-            let current_reserve = env.storage().persistent().get::<DepositDataKey, i128>(&reserve_key).unwrap_or(0);
-            env.storage().persistent().set(&reserve_key, &(current_reserve.checked_add(reserve_amount).ok_or(RepayError::Overflow)?));
-        }
-    }
-
+    crate::reserve::record_borrow_change(env, &asset, -principal_paid);
+    crate::reserve::clear_debt_assets_if_repaid(env, &user, &position);
+    crate::storage_ttl::extend_position_ttl(env, &user);
+    crate::storage_ttl::extend_reserve_ttl(env, &asset);
+    crate::liquidation_index::sync_borrower(env, &user);
+
+    // Repaid insurance premium goes to the insurance fund; the reserve share
+    // of the remaining interest is credited per the asset's reserve factor
+    let premium_paid =
+        crate::liquidation_insurance::collect_premium(env, &user, &asset, interest_paid)
+            .map_err(|_| RepayError::Overflow)?;
+    crate::reserve::accrue_reserve(env, asset.clone(), interest_paid - premium_paid)
+        .map_err(|_| RepayError::Overflow)?;
+
+    // Update user analytics
     update_user_analytics_repay(env, &user, repay_amount, timestamp)?;
+
+    // Update protocol analytics
     update_protocol_analytics_repay(env, repay_amount)?;
-    add_activity_log(env, &user, Symbol::new(env, "repay"), repay_amount, asset.clone(), timestamp).map_err(|e| RepayError::Overflow)?;
-    log_repay(env, RepayEvent { user: user.clone(), asset: asset.clone(), amount: repay_amount, timestamp });
 
     // Add to activity log
     add_activity_log(
@@ -291,6 +512,8 @@ pub fn repay_debt(
         crate::deposit::DepositError::Overflow => RepayError::Overflow,
         _ => RepayError::Overflow,
     })?;
+    crate::health_history::record_health_snapshot(env, &user, "repay");
+    crate::liquidation_grace::clear_if_healthy(env, &user);
 
     // Emit repay event
     emit_repay(
@@ -299,20 +522,78 @@ pub fn repay_debt(
             user: user.clone(),
             asset: asset.clone(),
             amount: repay_amount,
+            principal_paid,
+            interest_paid,
             timestamp,
         },
     );
 
     // Emit position updated event
     emit_position_updated_event(env, &user, &position);
+
+    // Emit analytics updated event
     emit_analytics_updated_event(env, &user, "repay", repay_amount, timestamp);
+
+    // Emit user activity tracked event
     emit_user_activity_tracked_event(env, &user, Symbol::new(env, "repay"), repay_amount, timestamp);
 
-    let remaining_debt = position.debt.checked_add(position.borrow_interest).unwrap_or(0);
+    // Calculate remaining debt
+    let remaining_debt = position
+        .debt
+        .checked_add(position.borrow_interest)
+        .unwrap_or(0);
+
     Ok((remaining_debt, interest_paid, principal_paid))
 }
 
-fn update_user_analytics_repay(env: &Env, user: &Address, amount: i128, timestamp: u64) -> Result<(), RepayError> {
+/// Outstanding debt of a user: principal plus interest accrued up to now
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The address of the user
+///
+/// # Returns
+/// * `Result<i128, RepayError>` - The amount that repays the position in full
+pub fn get_outstanding_debt(env: &Env, user: &Address) -> Result<i128, RepayError> {
+    let position = match env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    {
+        Some(position) if position.debt > 0 => position,
+        _ => return Ok(0),
+    };
+    let pending = crate::interest_index::pending_interest(env, user, &position)
+        .map_err(|_| RepayError::Overflow)?;
+    position
+        .debt
+        .checked_add(position.borrow_interest)
+        .and_then(|debt| debt.checked_add(pending))
+        .ok_or(RepayError::Overflow)
+}
+
+/// Repay a user's whole outstanding debt
+///
+/// The repaid amount is computed on-chain, interest included, so the
+/// position closes without dust left over from client-side rounding.
+///
+/// # Returns
+/// Returns a tuple (remaining_debt, interest_paid, principal_paid)
+///
+/// # Errors
+/// Same as `repay_debt`; `RepayError::NoDebt` if nothing is owed
+pub fn repay_all(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+) -> Result<(i128, i128, i128), RepayError> {
+    let outstanding = get_outstanding_debt(env, &user)?;
+    if outstanding == 0 {
+        return Err(RepayError::NoDebt);
+    }
+    repay_debt(env, user, asset, outstanding)
+}
+
 /// Update user analytics after repayment
 ///
 /// # Arguments
@@ -330,20 +611,41 @@ fn update_user_analytics_repay(
     timestamp: u64,
 ) -> Result<(), RepayError> {
     let analytics_key = DepositDataKey::UserAnalytics(user.clone());
-    let mut analytics = env.storage().persistent().get::<DepositDataKey, UserAnalytics>(&analytics_key)
-        .unwrap_or_else(|| UserAnalytics {
-            total_deposits: 0, total_borrows: 0, total_withdrawals: 0, total_repayments: 0,
-            collateral_value: 0, debt_value: 0, collateralization_ratio: 0, activity_score: 0,
-            transaction_count: 0, first_interaction: timestamp, last_activity: timestamp,
-            risk_level: 0, loyalty_tier: 0,
+    let mut analytics = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, UserAnalytics>(&analytics_key)
+        .unwrap_or(UserAnalytics {
+            total_deposits: 0,
+            total_borrows: 0,
+            total_withdrawals: 0,
+            total_repayments: 0,
+            collateral_value: 0,
+            debt_value: 0,
+            collateralization_ratio: 0,
+            activity_score: 0,
+            transaction_count: 0,
+            first_interaction: timestamp,
+            last_activity: timestamp,
+            risk_level: 0,
+            loyalty_tier: 0,
         });
 
-    analytics.total_repayments = analytics.total_repayments.checked_add(amount).ok_or(RepayError::Overflow)?;
+    analytics.total_repayments = analytics
+        .total_repayments
+        .checked_add(amount)
+        .ok_or(RepayError::Overflow)?;
+
+    // Update debt value (decrease by repayment amount)
     analytics.debt_value = analytics.debt_value.checked_sub(amount).unwrap_or(0);
 
+    // Recalculate collateralization ratio
     if analytics.debt_value > 0 && analytics.collateral_value > 0 {
-        analytics.collateralization_ratio = analytics.collateral_value.checked_mul(10000)
-            .and_then(|v| v.checked_div(analytics.debt_value)).unwrap_or(0);
+        analytics.collateralization_ratio = analytics
+            .collateral_value
+            .checked_mul(10000)
+            .and_then(|v| v.checked_div(analytics.debt_value))
+            .unwrap_or(0);
     } else {
         analytics.collateralization_ratio = 0;
     }
@@ -355,10 +657,6 @@ fn update_user_analytics_repay(
     Ok(())
 }
 
-fn update_protocol_analytics_repay(env: &Env, amount: i128) -> Result<(), RepayError> {
-    let analytics_key = DepositDataKey::ProtocolAnalytics;
-    let mut analytics = env.storage().persistent().get::<DepositDataKey, ProtocolAnalytics>(&analytics_key)
-        .unwrap_or(ProtocolAnalytics { total_deposits: 0, total_borrows: 0, total_value_locked: 0 });
 /// Update protocol analytics after repayment
 ///
 /// # Arguments
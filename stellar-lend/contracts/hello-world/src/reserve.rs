@@ -16,9 +16,65 @@
 //! - Range: 0 - 5000 bps (0% - 50%)
 //!
 //! ### Reserve Accrual
-//! - Reserves accrue automatically when interest is calculated during repayment
+//! - Reserves accrue automatically when interest is paid during repayment
 //! - Formula: `reserve_amount = total_interest * reserve_factor / 10000`
-//! - Tracked separately per asset in persistent storage
+//! - Borrow fees and flash loan fees are credited to the same reserve
+//! - Each credit is also recorded by source in `treasury`
+//!
+//! ### Supply Cap
+//! - Governance-set ceiling on the amount of an asset deposited into the pool
+//! - Deposits that would exceed it fail; withdrawals and liquidations free capacity
+//! - A cap of zero means unlimited
+//!
+//! ### Borrow Cap
+//! - Ceiling on the outstanding principal borrowed of an asset, regardless of
+//!   how much collateral backs it
+//! - Borrows that would exceed it fail; repayments, liquidations and bad debt
+//!   write-offs free capacity
+//! - A cap of zero means unlimited
+//!
+//! ### Escrowed Balances
+//! - Tokens the contract holds on behalf of other modules, such as strategy
+//!   vault collateral, sit in the same token balance as lending liquidity
+//! - Those modules record what they hold per asset, and borrows can only draw
+//!   on the balance left after subtracting it
+//! - Reserves of AMM pools and LBPs hosted by the contract are tracked by the
+//!   AMM module and subtracted the same way
+//!
+//! ### Siloed Borrowing
+//! - A siloed asset can only be borrowed by accounts with no debt in any
+//!   other asset, and an account holding siloed debt cannot borrow anything
+//!   else
+//! - Core positions aggregate debt, so the assets an account borrowed are
+//!   tracked until its debt is fully repaid, liquidated or written off
+//!
+//! ### Liquidation Threshold
+//! - Share of a collateral asset's value counted towards its debt when deciding
+//!   liquidations, kept apart from the collateral factor (max LTV) that caps
+//!   borrowing so positions have a buffer before they can be liquidated
+//! - Must lie between the asset's collateral factor and 100%
+//! - A threshold of zero falls back to the global minimum collateral ratio
+//!   from `risk_params`
+//!
+//! ### Close Factor
+//! - Share of a position's debt in the asset that one liquidation can repay
+//! - Defaults to the global close factor from `risk_params`
+//! - Rises to 100% once the position's health factor drops below the asset's
+//!   deep distress threshold, so badly underwater positions can be cleared in
+//!   a single call
+//!
+//! ### Liquidation Protocol Fee
+//! - Share of the liquidation bonus on a collateral asset kept by the protocol
+//! - The liquidator receives the seized collateral minus the protocol's cut,
+//!   which is credited to the asset's reserve
+//! - A fee of zero leaves the whole bonus to the liquidator
+//!
+//! ### LP Haircut
+//! - Extra discount on an asset's value when it is held through liquidity
+//!   pool shares pledged as collateral, on top of its collateral factor and
+//!   liquidation threshold
+//! - Covers the impermanent loss and exit cost of unwinding the pool
+//! - A haircut of zero values the pool side like the asset itself
 //!
 //! ### Treasury Withdrawal
 //! - Admin can withdraw accrued reserves to a treasury address
@@ -27,21 +83,49 @@
 //! - All withdrawals are logged via events
 //!
 //! ## Storage Layout
-//! - `ReserveBalance(asset)` — accumulated reserve per asset
+//! - `DepositDataKey::ProtocolReserve(asset)` — accumulated reserve per asset
 //! - `ReserveFactor(asset)` — reserve factor per asset (basis points)
+//! - `SupplyCap(asset)` / `TotalSupplied(asset)` — supply cap and amount deposited per asset
+//! - `BorrowCap(asset)` / `TotalBorrowed(asset)` — borrow cap and principal borrowed per asset
+//! - `Escrowed(asset)` — contract balance of the asset held for other modules
+//! - `Siloed(asset)` — whether the asset can only be borrowed in isolation
+//! - `LiquidationThreshold(asset)` — liquidation threshold per asset (basis points)
+//! - `CloseFactor(asset)` / `DeepDistressHealthFactor(asset)` — close factor per
+//!   asset and the health factor below which it becomes 100% (basis points)
+//! - `LiquidationProtocolFee(asset)` — protocol share of the liquidation bonus
+//!   per collateral asset (basis points)
+//! - `LpHaircut(asset)` — discount on the asset's side of pledged LP shares
+//!   (basis points)
+//! - `UserDebtAssets(user)` — assets borrowed by a user since their debt was last zero
+//! - `ReserveAssets` — assets that have ever been supplied or borrowed
 //! - `TreasuryAddress` — destination address for reserve withdrawals
 //!
 //! ## Security Invariants
 //! - Reserve factor must be between 0 and 5000 bps (0% - 50%)
-//! - Only admin can modify reserve factors or withdraw reserves
+//! - Only admin or a risk manager can modify reserve parameters, and only
+//!   admin can withdraw reserves
 //! - Withdrawals cannot exceed accrued reserve balance
+//! - Deposits never take an asset's total supplied above its supply cap
+//! - Borrows never take an asset's total borrowed above its borrow cap
+//! - An account with siloed debt owes only that one asset
+//! - An asset's liquidation threshold is never below its collateral factor
+//! - The deep distress threshold is below a health factor of 1.0
 //! - User funds (collateral, principal) are never accessible via treasury operations
 //! - All state changes emit events for transparency and auditability
 
 #![allow(unused)]
-use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol, Vec};
 
-use crate::deposit::DepositDataKey;
+use crate::deposit::resolve_token_address;
+use crate::events::{
+    emit_borrow_cap_updated, emit_close_factor_updated, emit_liq_protocol_fee_updated,
+    emit_liquidation_threshold_updated, emit_lp_haircut_updated, emit_siloed_updated,
+    emit_supply_cap_updated, BorrowCapUpdatedEvent, CloseFactorUpdatedEvent,
+    LiqProtocolFeeUpdatedEvent, LiquidationThresholdUpdatedEvent, LpHaircutUpdatedEvent,
+    SiloedUpdatedEvent, SupplyCapUpdatedEvent,
+};
+
+use crate::deposit::{AssetParams, DepositDataKey, Position};
 
 /// Maximum allowed reserve factor (50% = 5000 basis points)
 /// This ensures that at least 50% of interest always goes to lenders
@@ -74,6 +158,24 @@ pub enum ReserveError {
     Overflow = 7,
     /// Treasury address not configured
     TreasuryNotSet = 8,
+    /// Supply cap must not be negative
+    InvalidSupplyCap = 9,
+    /// Deposit would exceed the asset's supply cap
+    SupplyCapExceeded = 10,
+    /// Borrow cap must not be negative
+    InvalidBorrowCap = 11,
+    /// Borrow would exceed the asset's borrow cap
+    BorrowCapExceeded = 12,
+    /// Borrow would mix a siloed asset with other debt
+    SiloedBorrowViolation = 13,
+    /// Liquidation threshold is below the collateral factor or above 100%
+    InvalidLiquidationThreshold = 14,
+    /// Close factor or deep distress threshold out of range
+    InvalidCloseFactor = 15,
+    /// Liquidation protocol fee out of range (0-100%)
+    InvalidLiquidationProtocolFee = 16,
+    /// LP haircut out of range (0-100%)
+    InvalidLpHaircut = 17,
 }
 
 /// Storage keys for reserve and treasury data
@@ -81,21 +183,83 @@ pub enum ReserveError {
 #[derive(Clone)]
 #[cfg_attr(test, derive(Debug, PartialEq))]
 pub enum ReserveDataKey {
-    /// Reserve balance per asset: ReserveBalance(asset) -> i128
-    /// Tracks accumulated protocol reserves for each asset
-    ReserveBalance(Option<Address>),
     /// Reserve factor per asset: ReserveFactor(asset) -> i128
     /// Percentage of interest allocated to reserves (in basis points)
     ReserveFactor(Option<Address>),
     /// Treasury address: TreasuryAddress -> Address
     /// Destination for reserve withdrawals
     TreasuryAddress,
+    /// Supply cap per asset: SupplyCap(asset) -> i128
+    /// Maximum total deposits of the asset (0 = unlimited)
+    SupplyCap(Option<Address>),
+    /// Total deposits per asset: TotalSupplied(asset) -> i128
+    TotalSupplied(Option<Address>),
+    /// Borrow cap per asset: BorrowCap(asset) -> i128
+    /// Maximum total principal borrowed of the asset (0 = unlimited)
+    BorrowCap(Option<Address>),
+    /// Total principal borrowed per asset: TotalBorrowed(asset) -> i128
+    TotalBorrowed(Option<Address>),
+    /// Balance held for other modules per asset: Escrowed(asset) -> i128
+    /// Part of the contract's token balance that cannot be lent out
+    Escrowed(Option<Address>),
+    /// Siloed flag per asset: Siloed(asset) -> bool
+    Siloed(Option<Address>),
+    /// Assets a user has debt in: UserDebtAssets(user) -> Vec<Option<Address>>
+    UserDebtAssets(Address),
+    /// Liquidation threshold per asset: LiquidationThreshold(asset) -> i128
+    /// Basis points of collateral value counted against debt (0 = global ratio)
+    LiquidationThreshold(Option<Address>),
+    /// Close factor per asset: CloseFactor(asset) -> i128
+    /// Basis points of debt repayable per liquidation (0 = global close factor)
+    CloseFactor(Option<Address>),
+    /// Deep distress threshold per asset: DeepDistressHealthFactor(asset) -> i128
+    /// Health factor in basis points below which the close factor is 100% (0 = never)
+    DeepDistressHealthFactor(Option<Address>),
+    /// Liquidation protocol fee per collateral asset: LiquidationProtocolFee(asset) -> i128
+    /// Basis points of the liquidation bonus credited to reserves
+    LiquidationProtocolFee(Option<Address>),
+    /// LP haircut per asset: LpHaircut(asset) -> i128
+    /// Basis points knocked off the asset's side of pledged LP shares
+    LpHaircut(Option<Address>),
+    /// Assets that have ever been supplied or borrowed: ReserveAssets -> Vec<Option<Address>>
+    ReserveAssets,
+}
+
+/// Reserve configuration of an asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveConfig {
+    /// Share of repaid borrower interest kept by the protocol (basis points)
+    pub reserve_factor_bps: i128,
+    /// Destination of reserve withdrawals, if configured
+    pub treasury: Option<Address>,
+    /// Maximum total deposits of the asset (0 = unlimited)
+    pub supply_cap: i128,
+    /// Maximum total principal borrowed of the asset (0 = unlimited)
+    pub borrow_cap: i128,
+    /// Whether the asset can only be borrowed in isolation
+    pub siloed: bool,
+    /// Share of collateral value counted against debt for liquidations, in
+    /// basis points (0 = global minimum collateral ratio)
+    pub liquidation_threshold_bps: i128,
+    /// Share of debt repayable per liquidation, in basis points (0 = global
+    /// close factor)
+    pub close_factor_bps: i128,
+    /// Health factor, in basis points, below which a liquidation can repay
+    /// all debt (0 = never)
+    pub deep_distress_hf_bps: i128,
+    /// Share of the liquidation bonus on the asset kept by the protocol, in
+    /// basis points
+    pub liquidation_protocol_fee_bps: i128,
+    /// Extra discount on the asset's side of LP shares pledged as
+    /// collateral, in basis points
+    pub lp_haircut_bps: i128,
 }
 
 /// Initialize reserve configuration for an asset
 ///
 /// Sets the default reserve factor for a new asset. Should be called when
-/// a new asset is added to the protocol.
+/// a new asset is added to the protocol. Reserves already accrued are kept.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
@@ -115,7 +279,7 @@ pub fn initialize_reserve_config(
     reserve_factor_bps: i128,
 ) -> Result<(), ReserveError> {
     // Validate reserve factor
-    if reserve_factor_bps < 0 || reserve_factor_bps > MAX_RESERVE_FACTOR_BPS {
+    if !(0..=MAX_RESERVE_FACTOR_BPS).contains(&reserve_factor_bps) {
         return Err(ReserveError::InvalidReserveFactor);
     }
 
@@ -125,10 +289,6 @@ pub fn initialize_reserve_config(
         .persistent()
         .set(&factor_key, &reserve_factor_bps);
 
-    // Initialize reserve balance to zero
-    let balance_key = ReserveDataKey::ReserveBalance(asset.clone());
-    env.storage().persistent().set(&balance_key, &0i128);
-
     // Emit initialization event
     let topics = (Symbol::new(env, "reserve_initialized"),);
     env.events().publish(topics, (asset, reserve_factor_bps));
@@ -136,7 +296,7 @@ pub fn initialize_reserve_config(
     Ok(())
 }
 
-/// Set the reserve factor for an asset (admin only)
+/// Set the reserve factor for an asset (admin or risk manager)
 ///
 /// Updates the percentage of interest income allocated to protocol reserves.
 /// This affects future interest accruals but does not retroactively change
@@ -144,16 +304,16 @@ pub fn initialize_reserve_config(
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin or risk manager)
 /// * `asset` - The asset address (None for native asset)
 /// * `reserve_factor_bps` - New reserve factor in basis points (0-5000)
 ///
 /// # Errors
-/// * `ReserveError::Unauthorized` - If caller is not admin
+/// * `ReserveError::Unauthorized` - If caller is neither admin nor risk manager
 /// * `ReserveError::InvalidReserveFactor` - If reserve factor > MAX_RESERVE_FACTOR_BPS
 ///
 /// # Security
-/// * Requires admin authorization
+/// * Requires admin or risk manager authorization
 /// * Validates reserve factor bounds
 /// * Emits event for transparency
 pub fn set_reserve_factor(
@@ -164,10 +324,10 @@ pub fn set_reserve_factor(
 ) -> Result<(), ReserveError> {
     // Require admin authorization
     caller.require_auth();
-    require_admin(env, &caller)?;
+    require_risk_manager(env, &caller)?;
 
     // Validate reserve factor
-    if reserve_factor_bps < 0 || reserve_factor_bps > MAX_RESERVE_FACTOR_BPS {
+    if !(0..=MAX_RESERVE_FACTOR_BPS).contains(&reserve_factor_bps) {
         return Err(ReserveError::InvalidReserveFactor);
     }
 
@@ -254,7 +414,7 @@ pub fn accrue_reserve(
         .ok_or(ReserveError::Overflow)?;
 
     // Update reserve balance
-    let balance_key = ReserveDataKey::ReserveBalance(asset.clone());
+    let balance_key = DepositDataKey::ProtocolReserve(asset.clone());
     let current_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
 
     let new_balance = current_balance
@@ -262,6 +422,12 @@ pub fn accrue_reserve(
         .ok_or(ReserveError::Overflow)?;
 
     env.storage().persistent().set(&balance_key, &new_balance);
+    crate::treasury::record_revenue(
+        env,
+        &asset,
+        crate::treasury::RevenueSource::ReserveFactor,
+        reserve_amount,
+    );
 
     // Emit event
     let topics = (Symbol::new(env, "reserve_accrued"),);
@@ -280,10 +446,684 @@ pub fn accrue_reserve(
 /// # Returns
 /// Current reserve balance
 pub fn get_reserve_balance(env: &Env, asset: Option<Address>) -> i128 {
-    let balance_key = ReserveDataKey::ReserveBalance(asset);
+    let balance_key = DepositDataKey::ProtocolReserve(asset);
     env.storage().persistent().get(&balance_key).unwrap_or(0)
 }
 
+/// Get the reserve configuration of an asset
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `asset` - The asset address (None for native asset)
+///
+/// # Returns
+/// The reserve factor, caps, siloed flag and liquidation parameters of the
+/// asset and the treasury address
+pub fn get_reserve_config(env: &Env, asset: Option<Address>) -> ReserveConfig {
+    ReserveConfig {
+        reserve_factor_bps: get_reserve_factor(env, asset.clone()),
+        treasury: get_treasury_address(env),
+        supply_cap: get_supply_cap(env, &asset),
+        borrow_cap: get_borrow_cap(env, &asset),
+        siloed: is_siloed(env, &asset),
+        liquidation_threshold_bps: get_liquidation_threshold(env, &asset),
+        close_factor_bps: get_close_factor(env, &asset),
+        deep_distress_hf_bps: get_deep_distress_health_factor(env, &asset),
+        liquidation_protocol_fee_bps: get_liquidation_protocol_fee(env, &asset),
+        lp_haircut_bps: get_lp_haircut(env, &asset),
+    }
+}
+
+/// Set the supply cap of an asset (admin or risk manager)
+///
+/// Lowering the cap below the amount already supplied blocks new deposits
+/// but does not affect existing ones.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk manager)
+/// * `asset` - The asset address (None for native asset)
+/// * `supply_cap` - Maximum total deposits of the asset (0 = unlimited)
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is neither admin nor risk manager
+/// * `ReserveError::InvalidSupplyCap` - If the cap is negative
+pub fn set_supply_cap(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    supply_cap: i128,
+) -> Result<(), ReserveError> {
+    caller.require_auth();
+    require_risk_manager(env, &caller)?;
+
+    if supply_cap < 0 {
+        return Err(ReserveError::InvalidSupplyCap);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&ReserveDataKey::SupplyCap(asset.clone()), &supply_cap);
+
+    emit_supply_cap_updated(
+        env,
+        SupplyCapUpdatedEvent {
+            caller,
+            asset,
+            supply_cap,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the supply cap of an asset (0 = unlimited)
+pub fn get_supply_cap(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::SupplyCap(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the total amount of an asset deposited into the pool
+pub fn get_total_supplied(env: &Env, asset: &Option<Address>) -> i128 {
+    let key = ReserveDataKey::TotalSupplied(asset.clone());
+    crate::storage_ttl::extend_persistent(env, &key);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Get how much more of an asset can be deposited before its supply cap
+///
+/// # Returns
+/// The remaining capacity, zero if the cap is reached, or `i128::MAX` if the
+/// asset has no cap
+pub fn get_remaining_supply_capacity(env: &Env, asset: &Option<Address>) -> i128 {
+    let supply_cap = get_supply_cap(env, asset);
+    if supply_cap == 0 {
+        return i128::MAX;
+    }
+    (supply_cap - get_total_supplied(env, asset)).max(0)
+}
+
+/// Check that depositing `amount` of an asset stays within its supply cap
+///
+/// # Errors
+/// * `ReserveError::SupplyCapExceeded` - If the deposit would exceed the cap
+pub(crate) fn check_supply_cap(
+    env: &Env,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<(), ReserveError> {
+    if amount > get_remaining_supply_capacity(env, asset) {
+        return Err(ReserveError::SupplyCapExceeded);
+    }
+    Ok(())
+}
+
+/// Add `delta` to the total supplied of an asset, flooring at zero
+///
+/// Core collateral is fungible across assets, so withdrawals of an asset can
+/// exceed its recorded deposits.
+pub(crate) fn record_supply_change(env: &Env, asset: &Option<Address>, delta: i128) {
+    let previous = get_total_supplied(env, asset);
+    if previous == 0 && delta > 0 {
+        track_reserve_asset(env, asset);
+    }
+    let total = previous.saturating_add(delta).max(0);
+    let key = ReserveDataKey::TotalSupplied(asset.clone());
+    env.storage().persistent().set(&key, &total);
+    crate::storage_ttl::extend_persistent(env, &key);
+}
+
+/// Set the borrow cap of an asset (admin or risk manager)
+///
+/// Lowering the cap below the amount already borrowed blocks new borrows
+/// but does not affect existing debt.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk manager)
+/// * `asset` - The asset address (None for native asset)
+/// * `borrow_cap` - Maximum total principal borrowed of the asset (0 = unlimited)
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is neither admin nor risk manager
+/// * `ReserveError::InvalidBorrowCap` - If the cap is negative
+pub fn set_borrow_cap(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    borrow_cap: i128,
+) -> Result<(), ReserveError> {
+    caller.require_auth();
+    require_risk_manager(env, &caller)?;
+
+    if borrow_cap < 0 {
+        return Err(ReserveError::InvalidBorrowCap);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&ReserveDataKey::BorrowCap(asset.clone()), &borrow_cap);
+
+    emit_borrow_cap_updated(
+        env,
+        BorrowCapUpdatedEvent {
+            caller,
+            asset,
+            borrow_cap,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the borrow cap of an asset (0 = unlimited)
+pub fn get_borrow_cap(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::BorrowCap(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the total principal borrowed of an asset
+pub fn get_total_borrowed(env: &Env, asset: &Option<Address>) -> i128 {
+    let key = ReserveDataKey::TotalBorrowed(asset.clone());
+    crate::storage_ttl::extend_persistent(env, &key);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Get how much more of an asset can be borrowed before its borrow cap
+///
+/// # Returns
+/// The remaining capacity, zero if the cap is reached, or `i128::MAX` if the
+/// asset has no cap
+pub fn get_remaining_borrow_capacity(env: &Env, asset: &Option<Address>) -> i128 {
+    let borrow_cap = get_borrow_cap(env, asset);
+    if borrow_cap == 0 {
+        return i128::MAX;
+    }
+    (borrow_cap - get_total_borrowed(env, asset)).max(0)
+}
+
+/// Check that borrowing `amount` of an asset stays within its borrow cap
+///
+/// # Errors
+/// * `ReserveError::BorrowCapExceeded` - If the borrow would exceed the cap
+pub(crate) fn check_borrow_cap(
+    env: &Env,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<(), ReserveError> {
+    if amount > get_remaining_borrow_capacity(env, asset) {
+        return Err(ReserveError::BorrowCapExceeded);
+    }
+    Ok(())
+}
+
+/// Add `delta` to the total principal borrowed of an asset, flooring at zero
+pub(crate) fn record_borrow_change(env: &Env, asset: &Option<Address>, delta: i128) {
+    let previous = get_total_borrowed(env, asset);
+    if previous == 0 && delta > 0 {
+        track_reserve_asset(env, asset);
+    }
+    let total = previous.saturating_add(delta).max(0);
+    let key = ReserveDataKey::TotalBorrowed(asset.clone());
+    env.storage().persistent().set(&key, &total);
+    crate::storage_ttl::extend_persistent(env, &key);
+}
+
+/// Get the contract balance of an asset held in escrow for other modules
+pub fn get_escrowed_balance(env: &Env, asset: &Option<Address>) -> i128 {
+    let asset = crate::deposit::canonical_asset(env, asset.clone());
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::Escrowed(asset))
+        .unwrap_or(0)
+}
+
+/// Add `delta` to the balance of an asset held in escrow, flooring at zero
+///
+/// Called whenever a module that keeps tokens in the contract outside the
+/// lending pool receives or releases them.
+pub(crate) fn record_escrow_change(env: &Env, asset: &Option<Address>, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+    let asset = crate::deposit::canonical_asset(env, asset.clone());
+    let total = get_escrowed_balance(env, &asset)
+        .saturating_add(delta)
+        .max(0);
+    let key = ReserveDataKey::Escrowed(asset);
+    env.storage().persistent().set(&key, &total);
+    crate::storage_ttl::extend_persistent(env, &key);
+}
+
+/// Get the part of the contract's `token` balance that can be lent out as
+/// `asset`: the balance less the amount held in escrow and the reserves of
+/// hosted AMM pools and LBPs
+pub(crate) fn available_liquidity(env: &Env, asset: &Option<Address>, token: &Address) -> i128 {
+    let balance =
+        soroban_sdk::token::Client::new(env, token).balance(&env.current_contract_address());
+    balance
+        .saturating_sub(get_escrowed_balance(env, asset))
+        .saturating_sub(stellarlend_amm::amm::get_hosted_reserve(env, token))
+        .max(0)
+}
+
+/// Get the assets that have ever been supplied to or borrowed from the pool
+pub fn get_reserve_assets(env: &Env) -> Vec<Option<Address>> {
+    env.storage()
+        .persistent()
+        .get::<ReserveDataKey, Vec<Option<Address>>>(&ReserveDataKey::ReserveAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add an asset to the reserve assets if it is not listed yet
+///
+/// Only called when an asset's total goes from zero to positive, so the list
+/// is not read on every operation.
+fn track_reserve_asset(env: &Env, asset: &Option<Address>) {
+    let mut assets = get_reserve_assets(env);
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        env.storage()
+            .persistent()
+            .set(&ReserveDataKey::ReserveAssets, &assets);
+    }
+}
+
+/// Mark an asset as siloed or not (admin or risk manager)
+///
+/// Existing positions are unaffected; the flag applies to new borrows.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk manager)
+/// * `asset` - The asset address (None for native asset)
+/// * `siloed` - Whether the asset can only be borrowed in isolation
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is neither admin nor risk manager
+pub fn set_siloed(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    siloed: bool,
+) -> Result<(), ReserveError> {
+    caller.require_auth();
+    require_risk_manager(env, &caller)?;
+
+    env.storage()
+        .persistent()
+        .set(&ReserveDataKey::Siloed(asset.clone()), &siloed);
+
+    emit_siloed_updated(
+        env,
+        SiloedUpdatedEvent {
+            caller,
+            asset,
+            siloed,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Check whether an asset can only be borrowed in isolation
+pub fn is_siloed(env: &Env, asset: &Option<Address>) -> bool {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::Siloed(asset.clone()))
+        .unwrap_or(false)
+}
+
+/// Get the assets a user has borrowed since their debt was last zero
+pub fn get_user_debt_assets(env: &Env, user: &Address) -> Vec<Option<Address>> {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::UserDebtAssets(user.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Check that borrowing an asset does not mix siloed debt with other debt
+///
+/// # Errors
+/// * `ReserveError::SiloedBorrowViolation` - If the asset is siloed and the
+///   user owes another asset, or the user owes another, siloed asset
+pub(crate) fn check_siloed_borrow(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+) -> Result<(), ReserveError> {
+    let asset_siloed = is_siloed(env, asset);
+    for debt_asset in get_user_debt_assets(env, user).iter() {
+        if &debt_asset != asset && (asset_siloed || is_siloed(env, &debt_asset)) {
+            return Err(ReserveError::SiloedBorrowViolation);
+        }
+    }
+    Ok(())
+}
+
+/// Record that a user has borrowed an asset
+pub(crate) fn record_debt_asset(env: &Env, user: &Address, asset: &Option<Address>) {
+    let mut assets = get_user_debt_assets(env, user);
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        env.storage()
+            .persistent()
+            .set(&ReserveDataKey::UserDebtAssets(user.clone()), &assets);
+    }
+}
+
+/// Set the liquidation threshold of a collateral asset (admin or risk manager)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk manager)
+/// * `asset` - The asset address (None for native asset)
+/// * `liquidation_threshold_bps` - Share of collateral value counted against
+///   debt, in basis points (0 = global minimum collateral ratio)
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is neither admin nor risk manager
+/// * `ReserveError::InvalidLiquidationThreshold` - If the threshold is above
+///   100% or below the asset's collateral factor
+pub fn set_liquidation_threshold(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    liquidation_threshold_bps: i128,
+) -> Result<(), ReserveError> {
+    caller.require_auth();
+    require_risk_manager(env, &caller)?;
+
+    if !(0..=BASIS_POINTS_SCALE).contains(&liquidation_threshold_bps) {
+        return Err(ReserveError::InvalidLiquidationThreshold);
+    }
+    if liquidation_threshold_bps > 0 {
+        let collateral_factor = asset.as_ref().and_then(|asset_addr| {
+            env.storage()
+                .persistent()
+                .get::<DepositDataKey, AssetParams>(&DepositDataKey::AssetParams(
+                    asset_addr.clone(),
+                ))
+                .map(|params| params.collateral_factor)
+        });
+        if collateral_factor.is_some_and(|factor| liquidation_threshold_bps < factor) {
+            return Err(ReserveError::InvalidLiquidationThreshold);
+        }
+    }
+
+    env.storage().persistent().set(
+        &ReserveDataKey::LiquidationThreshold(asset.clone()),
+        &liquidation_threshold_bps,
+    );
+
+    emit_liquidation_threshold_updated(
+        env,
+        LiquidationThresholdUpdatedEvent {
+            caller,
+            asset,
+            liquidation_threshold_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the liquidation threshold of an asset (0 = global minimum collateral ratio)
+pub fn get_liquidation_threshold(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::LiquidationThreshold(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Set the close factor of a debt asset and its deep distress threshold (admin or risk manager)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk manager)
+/// * `asset` - The asset address (None for native asset)
+/// * `close_factor_bps` - Share of debt repayable per liquidation, in basis
+///   points (0 = global close factor)
+/// * `deep_distress_hf_bps` - Health factor, in basis points, below which a
+///   liquidation can repay all debt (0 = never)
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is neither admin nor risk manager
+/// * `ReserveError::InvalidCloseFactor` - If the close factor is above 100%
+///   or the deep distress threshold is not below a health factor of 1.0
+pub fn set_close_factor(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    close_factor_bps: i128,
+    deep_distress_hf_bps: i128,
+) -> Result<(), ReserveError> {
+    caller.require_auth();
+    require_risk_manager(env, &caller)?;
+
+    if !(0..=BASIS_POINTS_SCALE).contains(&close_factor_bps)
+        || !(0..BASIS_POINTS_SCALE).contains(&deep_distress_hf_bps)
+    {
+        return Err(ReserveError::InvalidCloseFactor);
+    }
+
+    env.storage().persistent().set(
+        &ReserveDataKey::CloseFactor(asset.clone()),
+        &close_factor_bps,
+    );
+    env.storage().persistent().set(
+        &ReserveDataKey::DeepDistressHealthFactor(asset.clone()),
+        &deep_distress_hf_bps,
+    );
+
+    emit_close_factor_updated(
+        env,
+        CloseFactorUpdatedEvent {
+            caller,
+            asset,
+            close_factor_bps,
+            deep_distress_hf_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the close factor of an asset (0 = global close factor)
+pub fn get_close_factor(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::CloseFactor(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the deep distress threshold of an asset (0 = never)
+pub fn get_deep_distress_health_factor(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::DeepDistressHealthFactor(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the close factor applying to a liquidation of debt in an asset
+///
+/// # Arguments
+/// * `asset` - The debt asset
+/// * `health_factor` - Health factor of the position, in basis points
+///
+/// # Returns
+/// 100% below the asset's deep distress threshold, otherwise the asset's
+/// close factor or the global one
+pub(crate) fn effective_close_factor(
+    env: &Env,
+    asset: &Option<Address>,
+    health_factor: i128,
+) -> i128 {
+    let deep_distress = get_deep_distress_health_factor(env, asset);
+    if deep_distress > 0 && health_factor < deep_distress {
+        return BASIS_POINTS_SCALE;
+    }
+    match get_close_factor(env, asset) {
+        0 => crate::risk_params::get_close_factor(env).unwrap_or(5000),
+        close_factor => close_factor,
+    }
+}
+
+/// Set the protocol's share of the liquidation bonus on a collateral asset (admin or risk manager)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk manager)
+/// * `asset` - The collateral asset address (None for native asset)
+/// * `fee_bps` - Share of the bonus credited to reserves, in basis points
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is neither admin nor risk manager
+/// * `ReserveError::InvalidLiquidationProtocolFee` - If the fee is outside 0-100%
+pub fn set_liquidation_protocol_fee(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    fee_bps: i128,
+) -> Result<(), ReserveError> {
+    caller.require_auth();
+    require_risk_manager(env, &caller)?;
+
+    if !(0..=BASIS_POINTS_SCALE).contains(&fee_bps) {
+        return Err(ReserveError::InvalidLiquidationProtocolFee);
+    }
+
+    env.storage().persistent().set(
+        &ReserveDataKey::LiquidationProtocolFee(asset.clone()),
+        &fee_bps,
+    );
+
+    emit_liq_protocol_fee_updated(
+        env,
+        LiqProtocolFeeUpdatedEvent {
+            caller,
+            asset,
+            fee_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the protocol's share of the liquidation bonus on a collateral asset
+pub fn get_liquidation_protocol_fee(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::LiquidationProtocolFee(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Set the extra haircut on an asset's side of pledged LP shares (admin or risk manager)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk manager)
+/// * `asset` - The pool's underlying asset (None for native asset)
+/// * `haircut_bps` - Discount applied to the asset's share of the pool, in basis points
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is neither admin nor risk manager
+/// * `ReserveError::InvalidLpHaircut` - If the haircut is outside 0-100%
+pub fn set_lp_haircut(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    haircut_bps: i128,
+) -> Result<(), ReserveError> {
+    caller.require_auth();
+    require_risk_manager(env, &caller)?;
+
+    if !(0..=BASIS_POINTS_SCALE).contains(&haircut_bps) {
+        return Err(ReserveError::InvalidLpHaircut);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&ReserveDataKey::LpHaircut(asset.clone()), &haircut_bps);
+
+    emit_lp_haircut_updated(
+        env,
+        LpHaircutUpdatedEvent {
+            caller,
+            asset,
+            haircut_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the extra haircut on an asset's side of pledged LP shares
+pub fn get_lp_haircut(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::LpHaircut(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Split the bonus of a liquidation between the liquidator and the protocol
+///
+/// The protocol's cut is credited to the collateral asset's reserve.
+///
+/// # Arguments
+/// * `asset` - The collateral asset
+/// * `bonus` - Collateral seized on top of the repaid debt's value
+///
+/// # Returns
+/// `(liquidator_bonus, protocol_fee)`
+pub(crate) fn split_liquidation_bonus(
+    env: &Env,
+    asset: &Option<Address>,
+    bonus: i128,
+) -> Result<(i128, i128), ReserveError> {
+    let fee_bps = get_liquidation_protocol_fee(env, asset);
+    if bonus <= 0 || fee_bps == 0 {
+        return Ok((bonus.max(0), 0));
+    }
+    let protocol_fee =
+        bonus.checked_mul(fee_bps).ok_or(ReserveError::Overflow)? / BASIS_POINTS_SCALE;
+
+    let balance_key = DepositDataKey::ProtocolReserve(asset.clone());
+    let balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+    let new_balance = balance
+        .checked_add(protocol_fee)
+        .ok_or(ReserveError::Overflow)?;
+    env.storage().persistent().set(&balance_key, &new_balance);
+    crate::treasury::record_revenue(
+        env,
+        asset,
+        crate::treasury::RevenueSource::LiquidationFee,
+        protocol_fee,
+    );
+
+    Ok((bonus - protocol_fee, protocol_fee))
+}
+
+/// Forget the assets a user borrowed once their position has no debt left
+pub(crate) fn clear_debt_assets_if_repaid(env: &Env, user: &Address, position: &Position) {
+    if position.debt == 0 && position.borrow_interest == 0 {
+        env.storage()
+            .persistent()
+            .remove(&ReserveDataKey::UserDebtAssets(user.clone()));
+    }
+}
+
 /// Set the treasury address (admin only)
 ///
 /// Configures the destination address for reserve withdrawals.
@@ -387,7 +1227,7 @@ pub fn withdraw_reserve_to_treasury(
     let treasury = get_treasury_address(env).ok_or(ReserveError::TreasuryNotSet)?;
 
     // Get current reserve balance
-    let balance_key = ReserveDataKey::ReserveBalance(asset.clone());
+    let balance_key = DepositDataKey::ProtocolReserve(asset.clone());
     let current_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
 
     // Validate sufficient reserves
@@ -401,10 +1241,9 @@ pub fn withdraw_reserve_to_treasury(
         .ok_or(ReserveError::Overflow)?;
 
     env.storage().persistent().set(&balance_key, &new_balance);
+    crate::treasury::record_disbursement(env, &asset, amount);
 
-    // Transfer tokens to treasury
-    // Note: In production, this would call the token contract's transfer function
-    // For now, we emit an event indicating the transfer should occur
+    // Emit event
     let topics = (Symbol::new(env, "reserve_withdrawn"), caller);
     env.events().publish(
         topics,
@@ -412,12 +1251,9 @@ pub fn withdraw_reserve_to_treasury(
     );
 
     // Transfer tokens to treasury
-    #[cfg(not(test))]
-    {
-        if let Some(ref asset_addr) = asset {
-            let token_client = soroban_sdk::token::Client::new(env, asset_addr);
-            token_client.transfer(&env.current_contract_address(), &treasury, &amount);
-        }
+    if let Some(token_addr) = resolve_token_address(env, &asset) {
+        let token_client = soroban_sdk::token::Client::new(env, &token_addr);
+        token_client.transfer(&env.current_contract_address(), &treasury, &amount);
     }
 
     Ok(amount)
@@ -446,6 +1282,11 @@ fn require_admin(env: &Env, caller: &Address) -> Result<(), ReserveError> {
     Ok(())
 }
 
+/// Require that the caller is the admin or a risk manager
+fn require_risk_manager(env: &Env, caller: &Address) -> Result<(), ReserveError> {
+    crate::admin::require_risk_manager(env, caller).map_err(|_| ReserveError::Unauthorized)
+}
+
 /// Get reserve statistics for an asset
 ///
 /// Returns comprehensive reserve information for reporting and analytics.
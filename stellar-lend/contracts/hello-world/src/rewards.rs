@@ -0,0 +1,619 @@
+//! # Rewards Module
+//!
+//! Liquidity mining: the protocol emits reward tokens to the suppliers and
+//! borrowers of each reserve. The admin funds emission schedules per reserve
+//! and reward token, paying `supply_per_second` to the reserve's suppliers
+//! and `borrow_per_second` to its borrowers until the schedule ends. A
+//! reserve can stream several reward tokens at once (e.g. the protocol token
+//! plus a partner token), each on its own schedule. Users claim what they
+//! earned with `claim_rewards`.
+//!
+//! ## Accrual
+//! Each reserve keeps a supply and a borrow reward index per reward token:
+//! rewards emitted per unit supplied (borrowed), scaled by
+//! `REWARD_INDEX_SCALE`. The indexes grow by `rate * elapsed / total` and
+//! every user keeps a snapshot of them. A user's rewards are credited from
+//! the difference whenever their balance in the reserve changes, before the
+//! change applies, so rewards are always earned on the balance held at the
+//! time.
+//!
+//! Balances are the cross-asset positions (see `cross_asset`): collateral on
+//! the supply side and debt principal on the borrow side. Emissions during
+//! periods where a side of a reserve is empty are not allocated and stay in
+//! the contract.
+//!
+//! ## Boost
+//! Rewards accrue on working balances rather than raw balances. Without a
+//! stake the working balance is 40% of the balance; stakers (see `staking`)
+//! add a share of the reserve's total in proportion to their share of the
+//! total stake weight, up to the full balance:
+//!
+//! `working = min(0.4 * balance + 0.6 * total * weight / total_weight, balance)`
+//!
+//! so a staker earns up to `MAX_BOOST_BPS` (2.5x) what an unboosted user
+//! with the same balance earns. Indexes are shared over the working total,
+//! so boosts redistribute emissions rather than add to them. A user's
+//! working balances are recomputed, at the totals of the time, whenever
+//! their balance in the reserve changes and in every reserve when they lock
+//! or unlock a stake; positions untouched since before boosts existed
+//! accrue once `refresh_boosts` is called.
+//!
+//! ## Funding
+//! Setting a schedule charges the admin for the whole schedule, less what
+//! remains of the schedule it replaces; a smaller schedule refunds the
+//! difference. A reserve streams at most `MAX_REWARD_TOKENS` reward tokens,
+//! which bounds the work done on every balance change.
+//!
+//! ## Invariants
+//! - Indexes only increase, and only until the schedule's end time.
+//! - Rewards paid out in a token never exceed the emissions funded in it.
+//! - A user's snapshots are moved to the current indexes whenever rewards
+//!   are credited or claimed, so nothing is credited twice.
+//! - A working balance never exceeds the balance it is derived from, and the
+//!   working total is the sum of the working balances.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Map, Symbol, Vec};
+
+use crate::cross_asset::{AssetKey, AssetPosition};
+use crate::events::{
+    emit_reward_emission_updated, emit_rewards_claimed, RewardEmissionUpdatedEvent,
+    RewardsClaimedEvent,
+};
+use crate::staking::MAX_BOOST_BPS;
+
+/// Scale of the reward indexes (1e18)
+pub const REWARD_INDEX_SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// Maximum number of reward tokens a reserve streams
+pub const MAX_REWARD_TOKENS: u32 = 5;
+
+/// Share of the balance that earns rewards without a boost (40%)
+const BOOST_BASE_BPS: i128 = 4_000;
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Errors that can occur during reward operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RewardsError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// The reward token cannot be the protocol itself
+    InvalidRewardToken = 2,
+    /// The reserve already streams `MAX_REWARD_TOKENS` reward tokens
+    TooManyRewardTokens = 3,
+    /// Emission rates must not be negative
+    InvalidEmission = 4,
+    /// The reserve is not configured for cross-asset lending
+    AssetNotConfigured = 5,
+    /// Arithmetic overflow occurred
+    Overflow = 6,
+}
+
+/// Emission schedule of a reward token in a reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardEmission {
+    /// Reward tokens per second shared by the reserve's suppliers
+    pub supply_per_second: i128,
+    /// Reward tokens per second shared by the reserve's borrowers
+    pub borrow_per_second: i128,
+    /// Timestamp emissions stop at
+    pub end_time: u64,
+}
+
+/// Reward indexes of a reward token in a reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardIndexes {
+    /// Rewards per unit supplied, scaled by `REWARD_INDEX_SCALE`
+    pub supply_index: i128,
+    /// Rewards per unit borrowed, scaled by `REWARD_INDEX_SCALE`
+    pub borrow_index: i128,
+    /// Timestamp the indexes were last advanced to
+    pub last_update: u64,
+}
+
+/// A user's reward state for a reward token in a reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserRewardState {
+    /// Supply index the user was last credited up to
+    pub supply_index: i128,
+    /// Borrow index the user was last credited up to
+    pub borrow_index: i128,
+    /// Rewards credited and not yet claimed
+    pub accrued: i128,
+}
+
+/// Balances that accrue rewards in a reserve, after boosts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkingBalances {
+    /// Working supply balance
+    pub supply: i128,
+    /// Working borrow balance
+    pub borrow: i128,
+}
+
+/// Storage keys for reward data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum RewardsDataKey {
+    /// Reward tokens a reserve has streamed: Vec<Address>
+    RewardTokens(AssetKey),
+    /// Emission schedule of a reward token in a reserve: RewardEmission
+    RewardSchedule(AssetKey, Address),
+    /// Reward indexes of a reward token in a reserve: RewardIndexes
+    RewardIndex(AssetKey, Address),
+    /// A user's reward state for a reward token in a reserve: UserRewardState
+    UserRewards(Address, AssetKey, Address),
+    /// A user's working balances in a reserve: WorkingBalances
+    WorkingBalance(Address, AssetKey),
+    /// Sum of the working balances in a reserve: WorkingBalances
+    WorkingTotal(AssetKey),
+}
+
+/// Get the reward tokens a reserve streams or has streamed
+pub fn get_reward_tokens(env: &Env, asset: &Option<Address>) -> Vec<Address> {
+    reward_tokens(env, &AssetKey::from_option(asset.clone()))
+}
+
+fn reward_tokens(env: &Env, asset_key: &AssetKey) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, Vec<Address>>(&RewardsDataKey::RewardTokens(asset_key.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the emission schedule of a reward token in a reserve
+pub fn get_reward_emission(
+    env: &Env,
+    asset: &Option<Address>,
+    reward_token: &Address,
+) -> Option<RewardEmission> {
+    get_emission(env, &AssetKey::from_option(asset.clone()), reward_token)
+}
+
+fn get_emission(env: &Env, asset_key: &AssetKey, reward_token: &Address) -> Option<RewardEmission> {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, RewardEmission>(&RewardsDataKey::RewardSchedule(
+            asset_key.clone(),
+            reward_token.clone(),
+        ))
+}
+
+/// Rewards a schedule still has to emit
+fn remaining_budget(env: &Env, emission: &Option<RewardEmission>) -> i128 {
+    match emission {
+        Some(emission) if emission.end_time > env.ledger().timestamp() => {
+            let remaining = (emission.end_time - env.ledger().timestamp()) as i128;
+            emission
+                .supply_per_second
+                .saturating_add(emission.borrow_per_second)
+                .saturating_mul(remaining)
+        }
+        _ => 0,
+    }
+}
+
+/// Set the emission schedule of a reward token in a reserve (admin only)
+///
+/// Credits the rewards emitted so far under the token's previous schedule,
+/// then charges the admin for the new schedule net of what the previous one
+/// had left, or refunds the difference.
+///
+/// # Arguments
+/// * `caller` - The admin address, funding the schedule
+/// * `asset` - The reserve (None for native XLM)
+/// * `reward_token` - The token the schedule pays out
+/// * `supply_per_second` - Rewards per second for the reserve's suppliers
+/// * `borrow_per_second` - Rewards per second for the reserve's borrowers
+/// * `duration` - Seconds the schedule runs from now
+///
+/// # Errors
+/// * `RewardsError::Unauthorized` - If caller is not the admin
+/// * `RewardsError::InvalidEmission` - If a rate is negative
+/// * `RewardsError::InvalidRewardToken` - If the token is the protocol itself
+/// * `RewardsError::AssetNotConfigured` - If the reserve is not configured
+/// * `RewardsError::TooManyRewardTokens` - If the reserve streams too many tokens
+/// * `RewardsError::Overflow` - If the schedule's total overflows
+pub fn set_reward_emission(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    reward_token: Address,
+    supply_per_second: i128,
+    borrow_per_second: i128,
+    duration: u64,
+) -> Result<RewardEmission, RewardsError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| RewardsError::Unauthorized)?;
+
+    if supply_per_second < 0 || borrow_per_second < 0 {
+        return Err(RewardsError::InvalidEmission);
+    }
+    if reward_token == env.current_contract_address() {
+        return Err(RewardsError::InvalidRewardToken);
+    }
+    let asset_key = AssetKey::from_option(asset.clone());
+    crate::cross_asset::get_asset_config(env, &asset_key)
+        .map_err(|_| RewardsError::AssetNotConfigured)?;
+
+    let budget = supply_per_second
+        .checked_add(borrow_per_second)
+        .and_then(|rate| rate.checked_mul(duration as i128))
+        .ok_or(RewardsError::Overflow)?;
+    let now = env.ledger().timestamp();
+    let end_time = now.checked_add(duration).ok_or(RewardsError::Overflow)?;
+
+    let mut tokens = reward_tokens(env, &asset_key);
+    if !tokens.contains(&reward_token) {
+        if tokens.len() >= MAX_REWARD_TOKENS {
+            return Err(RewardsError::TooManyRewardTokens);
+        }
+        tokens.push_back(reward_token.clone());
+        env.storage()
+            .persistent()
+            .set(&RewardsDataKey::RewardTokens(asset_key.clone()), &tokens);
+    }
+
+    // Close the previous schedule at the current indexes
+    accrue_indexes(env, &asset_key, &reward_token);
+    let previous = get_emission(env, &asset_key, &reward_token);
+    let shortfall = budget - remaining_budget(env, &previous);
+    let token_client = soroban_sdk::token::Client::new(env, &reward_token);
+    if shortfall > 0 {
+        token_client.transfer(&caller, env.current_contract_address(), &shortfall);
+    } else if shortfall < 0 {
+        token_client.transfer(&env.current_contract_address(), &caller, &-shortfall);
+    }
+
+    let emission = RewardEmission {
+        supply_per_second,
+        borrow_per_second,
+        end_time,
+    };
+    env.storage().persistent().set(
+        &RewardsDataKey::RewardSchedule(asset_key, reward_token.clone()),
+        &emission,
+    );
+
+    emit_reward_emission_updated(
+        env,
+        RewardEmissionUpdatedEvent {
+            asset,
+            reward_token,
+            supply_per_second,
+            borrow_per_second,
+            end_time,
+            timestamp: now,
+        },
+    );
+    Ok(emission)
+}
+
+/// Index growth for `rate` over `elapsed` seconds shared by `total`
+fn index_delta(rate: i128, elapsed: u64, total: i128) -> i128 {
+    if rate <= 0 || total <= 0 {
+        return 0;
+    }
+    rate.saturating_mul(elapsed as i128)
+        .saturating_mul(REWARD_INDEX_SCALE)
+        / total
+}
+
+/// Get the reward indexes of a reward token in a reserve as of now, without
+/// storing them
+pub fn get_reward_indexes(
+    env: &Env,
+    asset: &Option<Address>,
+    reward_token: &Address,
+) -> RewardIndexes {
+    current_indexes(env, &AssetKey::from_option(asset.clone()), reward_token)
+}
+
+fn current_indexes(env: &Env, asset_key: &AssetKey, reward_token: &Address) -> RewardIndexes {
+    let now = env.ledger().timestamp();
+    let mut indexes = env
+        .storage()
+        .persistent()
+        .get::<RewardsDataKey, RewardIndexes>(&RewardsDataKey::RewardIndex(
+            asset_key.clone(),
+            reward_token.clone(),
+        ))
+        .unwrap_or(RewardIndexes {
+            supply_index: 0,
+            borrow_index: 0,
+            last_update: now,
+        });
+
+    if let Some(emission) = get_emission(env, asset_key, reward_token) {
+        let until = now.min(emission.end_time);
+        if until > indexes.last_update {
+            let elapsed = until - indexes.last_update;
+            let totals = working_total(env, asset_key);
+            indexes.supply_index = indexes.supply_index.saturating_add(index_delta(
+                emission.supply_per_second,
+                elapsed,
+                totals.supply,
+            ));
+            indexes.borrow_index = indexes.borrow_index.saturating_add(index_delta(
+                emission.borrow_per_second,
+                elapsed,
+                totals.borrow,
+            ));
+        }
+    }
+    indexes.last_update = indexes.last_update.max(now);
+    indexes
+}
+
+/// Advance and store the reward indexes of a reward token in a reserve
+fn accrue_indexes(env: &Env, asset_key: &AssetKey, reward_token: &Address) -> RewardIndexes {
+    let indexes = current_indexes(env, asset_key, reward_token);
+    env.storage().persistent().set(
+        &RewardsDataKey::RewardIndex(asset_key.clone(), reward_token.clone()),
+        &indexes,
+    );
+    indexes
+}
+
+/// A user's reward state with rewards credited up to `indexes`
+fn credited_state(
+    env: &Env,
+    user: &Address,
+    asset_key: &AssetKey,
+    reward_token: &Address,
+    working: &WorkingBalances,
+    indexes: &RewardIndexes,
+) -> UserRewardState {
+    // Without a state the user's working balances have not changed since the indexes started
+    let mut state = env
+        .storage()
+        .persistent()
+        .get::<RewardsDataKey, UserRewardState>(&RewardsDataKey::UserRewards(
+            user.clone(),
+            asset_key.clone(),
+            reward_token.clone(),
+        ))
+        .unwrap_or(UserRewardState {
+            supply_index: 0,
+            borrow_index: 0,
+            accrued: 0,
+        });
+
+    let supply_rewards = working
+        .supply
+        .saturating_mul(indexes.supply_index - state.supply_index)
+        / REWARD_INDEX_SCALE;
+    let borrow_rewards = working
+        .borrow
+        .saturating_mul(indexes.borrow_index - state.borrow_index)
+        / REWARD_INDEX_SCALE;
+    state.accrued = state
+        .accrued
+        .saturating_add(supply_rewards)
+        .saturating_add(borrow_rewards);
+    state.supply_index = indexes.supply_index;
+    state.borrow_index = indexes.borrow_index;
+    state
+}
+
+fn no_working_balances() -> WorkingBalances {
+    WorkingBalances {
+        supply: 0,
+        borrow: 0,
+    }
+}
+
+fn working_balances(env: &Env, user: &Address, asset_key: &AssetKey) -> WorkingBalances {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, WorkingBalances>(&RewardsDataKey::WorkingBalance(
+            user.clone(),
+            asset_key.clone(),
+        ))
+        .unwrap_or(no_working_balances())
+}
+
+fn working_total(env: &Env, asset_key: &AssetKey) -> WorkingBalances {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, WorkingBalances>(&RewardsDataKey::WorkingTotal(asset_key.clone()))
+        .unwrap_or(no_working_balances())
+}
+
+/// Working balance of `balance` out of a reserve side's `total`
+fn working_amount(balance: i128, total: i128, weight: i128, total_weight: i128) -> i128 {
+    if balance <= 0 {
+        return 0;
+    }
+    let base = balance.saturating_mul(BOOST_BASE_BPS) / BASIS_POINTS_SCALE;
+    if weight <= 0 || total_weight <= 0 {
+        return base;
+    }
+    let boost = total
+        .max(balance)
+        .saturating_mul(weight)
+        .saturating_mul(BASIS_POINTS_SCALE - BOOST_BASE_BPS)
+        / total_weight.saturating_mul(BASIS_POINTS_SCALE);
+    base.saturating_add(boost).min(balance)
+}
+
+/// Credit a user's rewards in a reserve and recompute their working balances
+///
+/// Called by `cross_asset` whenever the user's balance in the reserve
+/// changes, with the position after the change. Rewards up to now accrue on
+/// the working balances from before the change.
+pub(crate) fn checkpoint_user_rewards(
+    env: &Env,
+    user: &Address,
+    asset_key: &AssetKey,
+    position: &AssetPosition,
+) {
+    let previous = working_balances(env, user, asset_key);
+    for reward_token in reward_tokens(env, asset_key).iter() {
+        let indexes = accrue_indexes(env, asset_key, &reward_token);
+        let state = credited_state(env, user, asset_key, &reward_token, &previous, &indexes);
+        env.storage().persistent().set(
+            &RewardsDataKey::UserRewards(user.clone(), asset_key.clone(), reward_token),
+            &state,
+        );
+    }
+
+    let weight = crate::staking::boosting_weight(env, user);
+    let total_weight = crate::staking::get_total_stake_weight(env);
+    let working = WorkingBalances {
+        supply: working_amount(
+            position.collateral,
+            crate::cross_asset::get_total_supply(env, asset_key),
+            weight,
+            total_weight,
+        ),
+        borrow: working_amount(
+            position.debt_principal,
+            crate::cross_asset::get_total_borrow(env, asset_key),
+            weight,
+            total_weight,
+        ),
+    };
+    let mut totals = working_total(env, asset_key);
+    totals.supply = (totals.supply + working.supply - previous.supply).max(0);
+    totals.borrow = (totals.borrow + working.borrow - previous.borrow).max(0);
+
+    let storage = env.storage().persistent();
+    storage.set(&RewardsDataKey::WorkingTotal(asset_key.clone()), &totals);
+    storage.set(
+        &RewardsDataKey::WorkingBalance(user.clone(), asset_key.clone()),
+        &working,
+    );
+}
+
+/// Recompute a user's working balances in every reserve
+///
+/// Called when the user's stake changes; anyone may call it through the
+/// contract to bring a user's boost up to date.
+pub fn refresh_boosts(env: &Env, user: &Address) {
+    for asset_key in crate::cross_asset::get_asset_list(env).iter() {
+        let position =
+            crate::cross_asset::get_user_asset_position(env, user, asset_key.to_option());
+        checkpoint_user_rewards(env, user, &asset_key, &position);
+    }
+}
+
+/// Get a user's reward boost in a reserve
+///
+/// # Returns
+/// Returns a tuple (supply boost, borrow boost) in basis points, from
+/// 10_000 (1x) to `MAX_BOOST_BPS` (2.5x)
+pub fn get_reward_boost(env: &Env, user: &Address, asset: &Option<Address>) -> (i128, i128) {
+    let working = working_balances(env, user, &AssetKey::from_option(asset.clone()));
+    let position = crate::cross_asset::get_user_asset_position(env, user, asset.clone());
+    let boost = |working: i128, balance: i128| {
+        if balance <= 0 {
+            BASIS_POINTS_SCALE
+        } else {
+            (working.saturating_mul(MAX_BOOST_BPS) / balance).max(BASIS_POINTS_SCALE)
+        }
+    };
+    (
+        boost(working.supply, position.collateral),
+        boost(working.borrow, position.debt_principal),
+    )
+}
+
+/// Add `amount` of `reward_token` to a per-token total
+fn add_reward(totals: &mut Map<Address, i128>, reward_token: Address, amount: i128) {
+    if amount > 0 {
+        let total = totals.get(reward_token.clone()).unwrap_or(0);
+        totals.set(reward_token, total.saturating_add(amount));
+    }
+}
+
+/// Get the rewards a user can claim from the given reserves, per reward token
+pub fn get_claimable_rewards(
+    env: &Env,
+    user: &Address,
+    assets: &Vec<Option<Address>>,
+) -> Map<Address, i128> {
+    let mut totals = Map::new(env);
+    for asset in assets.iter() {
+        let asset_key = AssetKey::from_option(asset.clone());
+        let working = working_balances(env, user, &asset_key);
+        for reward_token in reward_tokens(env, &asset_key).iter() {
+            let indexes = current_indexes(env, &asset_key, &reward_token);
+            let state = credited_state(env, user, &asset_key, &reward_token, &working, &indexes);
+            add_reward(&mut totals, reward_token, state.accrued);
+        }
+    }
+    totals
+}
+
+/// Get the rewards a user can claim from every reserve, per reward token
+pub fn get_pending_rewards(env: &Env, user: &Address) -> Map<Address, i128> {
+    let mut assets = Vec::new(env);
+    for asset_key in crate::cross_asset::get_asset_list(env).iter() {
+        assets.push_back(asset_key.to_option());
+    }
+    get_claimable_rewards(env, user, &assets)
+}
+
+/// Claim a user's rewards from the given reserves
+///
+/// # Arguments
+/// * `user` - The reward earner (must authorize)
+/// * `assets` - The reserves to claim from (None for native XLM)
+/// * `to` - The recipient of the rewards
+///
+/// # Returns
+/// Returns the amount paid per reward token
+pub fn claim_rewards(
+    env: &Env,
+    user: Address,
+    assets: Vec<Option<Address>>,
+    to: Address,
+) -> Result<Map<Address, i128>, RewardsError> {
+    user.require_auth();
+
+    let mut totals: Map<Address, i128> = Map::new(env);
+    for asset in assets.iter() {
+        let asset_key = AssetKey::from_option(asset.clone());
+        let working = working_balances(env, &user, &asset_key);
+        for reward_token in reward_tokens(env, &asset_key).iter() {
+            let indexes = accrue_indexes(env, &asset_key, &reward_token);
+            let mut state =
+                credited_state(env, &user, &asset_key, &reward_token, &working, &indexes);
+            add_reward(&mut totals, reward_token.clone(), state.accrued);
+            state.accrued = 0;
+            env.storage().persistent().set(
+                &RewardsDataKey::UserRewards(user.clone(), asset_key.clone(), reward_token),
+                &state,
+            );
+        }
+    }
+
+    for (reward_token, amount) in totals.iter() {
+        soroban_sdk::token::Client::new(env, &reward_token).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+        emit_rewards_claimed(
+            env,
+            RewardsClaimedEvent {
+                user: user.clone(),
+                to: to.clone(),
+                reward_token,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+    Ok(totals)
+}
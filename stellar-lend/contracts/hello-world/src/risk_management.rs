@@ -9,18 +9,35 @@
 //! - **Liquidation incentive** (default 10%): bonus awarded to liquidators
 //!
 //! ## Pause Controls
-//! - Per-operation pause switches (deposit, withdraw, borrow, repay, liquidate)
+//! - Per-operation pause switches (deposit, withdraw, borrow, repay,
+//!   liquidate, swap), each enforced independently by its operation
+//! - Per-asset freeze flags: a frozen asset takes no new deposits, borrows or
+//!   AMM swaps, while withdrawals, repayments and liquidations stay open so
+//!   positions in it can still be unwound
 //! - Global emergency pause that halts all operations immediately
+//! - A guardian (see `admin`) can pause or freeze, but only the admin can
+//!   unpause or unfreeze
+//!
+//! ## Emergency Withdrawal Mode
+//! A terminal state the admin can put the protocol in when it has to be wound
+//! down. Entering it turns on the emergency pause and pauses borrows; from
+//! then on nothing can be unpaused or unfrozen again. Suppliers without debt
+//! can still withdraw, whatever the withdraw switches say, so their funds are
+//! never trapped by a prolonged pause. Borrowers repay first (if repayments
+//! are open) and then withdraw the same way.
 //!
 //! ## Safety
 //! - Parameter changes are limited to ±10% per update to prevent drastic shifts.
 //! - Min collateral ratio must always be ≥ liquidation threshold.
-//! - Only the admin address can modify risk parameters.
+//! - Only the admin or a risk manager can modify risk parameters.
+//! - While the parameter timelock is enabled, risk parameters can only be
+//!   changed through it (see `param_timelock`).
 
 #![allow(unused)]
 use crate::events::{
-    emit_admin_action, emit_pause_state_changed, emit_risk_params_updated, AdminActionEvent,
-    PauseStateChangedEvent, RiskParamsUpdatedEvent,
+    emit_admin_action, emit_asset_freeze_changed, emit_emergency_withdrawal_mode,
+    emit_pause_state_changed, emit_risk_params_updated, AdminActionEvent, AssetFreezeChangedEvent,
+    EmergencyWithdrawalModeEvent, PauseStateChangedEvent, RiskParamsUpdatedEvent,
 };
 use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
@@ -55,6 +72,10 @@ pub enum RiskManagementError {
     GovernanceRequired = 12,
     /// Contract has already been initialized
     AlreadyInitialized = 13,
+    /// Parameters must be changed through the parameter timelock
+    TimelockActive = 14,
+    /// The protocol is in emergency withdrawal mode, which cannot be left
+    EmergencyWithdrawalActive = 15,
 }
 /// Storage keys for risk management data
 #[contracttype]
@@ -70,9 +91,15 @@ pub enum RiskDataKey {
     /// Global emergency pause flag. If true, all protocol operations are halted.
     /// Value type: bool
     EmergencyPause,
-    /// Timelock for safety of sensitive parameter changes
-    /// Value type: u64 (timestamp)
+    /// Delay applied to queued risk-parameter changes (see `param_timelock`)
+    /// Value type: u64 (seconds)
     ParameterChangeTimelock,
+    /// Whether an asset is frozen (None = native XLM)
+    /// Value type: bool
+    FrozenAsset(Option<Address>),
+    /// Timestamp emergency withdrawal mode was entered at
+    /// Value type: u64
+    EmergencyWithdrawalMode,
 }
 
 /// Risk configuration parameters for pause switches
@@ -99,6 +126,8 @@ pub enum PauseOperation {
     Repay,
     /// Pause liquidation operations
     Liquidate,
+    /// Pause AMM swaps
+    Swap,
     /// Pause all operations (emergency)
     All,
 }
@@ -127,7 +156,7 @@ pub fn initialize_risk_management(env: &Env, admin: Address) -> Result<(), RiskM
     }
 
     // Set admin
-    env.storage().persistent().set(&admin_key, &admin);
+    env.storage().persistent().set(&RiskDataKey::Admin, &admin);
 
     // Initialize default risk config for pause switches
     let default_config = RiskConfig {
@@ -162,6 +191,7 @@ fn create_default_pause_switches(env: &Env) -> Map<Symbol, bool> {
     switches.set(Symbol::new(env, "pause_borrow"), false);
     switches.set(Symbol::new(env, "pause_repay"), false);
     switches.set(Symbol::new(env, "pause_liquidate"), false);
+    switches.set(Symbol::new(env, "pause_swap"), false);
     switches
 }
 
@@ -176,6 +206,31 @@ pub fn require_admin(env: &Env, caller: &Address) -> Result<(), RiskManagementEr
     crate::admin::require_admin(env, caller).map_err(|_| RiskManagementError::Unauthorized)
 }
 
+/// Check if caller is admin or risk manager
+pub fn require_risk_manager(env: &Env, caller: &Address) -> Result<(), RiskManagementError> {
+    crate::admin::require_risk_manager(env, caller).map_err(|_| RiskManagementError::Unauthorized)
+}
+
+/// Require the caller to authorize and be allowed to set pause switches:
+/// the admin either way, a guardian only to pause. Nothing can be unpaused in
+/// emergency withdrawal mode.
+fn require_pause_authority(
+    env: &Env,
+    caller: &Address,
+    pausing: bool,
+) -> Result<(), RiskManagementError> {
+    caller.require_auth();
+    if !pausing && is_emergency_withdrawal_mode(env) {
+        return Err(RiskManagementError::EmergencyWithdrawalActive);
+    }
+    if pausing {
+        crate::admin::require_guardian(env, caller)
+    } else {
+        crate::admin::require_admin(env, caller)
+    }
+    .map_err(|_| RiskManagementError::Unauthorized)
+}
+
 /// Get current risk configuration
 pub fn get_risk_config(env: &Env) -> Option<RiskConfig> {
     let config_key = RiskDataKey::RiskConfig;
@@ -186,13 +241,13 @@ pub fn get_risk_config(env: &Env) -> Option<RiskConfig> {
 
 
 
-/// Set pause switches (admin only)
+/// Set pause switches (admin, or guardian to pause)
 ///
 /// Updates pause switches for different operations.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin, or guardian when pausing)
 /// * `operation` - The operation to pause/unpause (as Symbol)
 /// * `paused` - Whether to pause (true) or unpause (false)
 ///
@@ -200,19 +255,25 @@ pub fn get_risk_config(env: &Env) -> Option<RiskConfig> {
 /// Returns Ok(()) on success
 ///
 /// # Errors
-/// * `RiskManagementError::Unauthorized` - If caller is not admin
+/// * `RiskManagementError::Unauthorized` - If caller is not admin, or is a
+///   guardian unpausing
 pub fn set_pause_switch(
     env: &Env,
     caller: Address,
     operation: Symbol,
     paused: bool,
 ) -> Result<(), RiskManagementError> {
-    // Check admin
-    require_admin(env, &caller)?;
+    require_pause_authority(env, &caller, paused)?;
 
     // Get current config
     let mut config = get_risk_config(env).ok_or(RiskManagementError::InvalidParameter)?;
 
+    // Reopening liquidations starts the post-incident recovery window
+    let liquidate_op = Symbol::new(env, "pause_liquidate");
+    if operation == liquidate_op && !paused && is_operation_paused(env, liquidate_op) {
+        crate::liquidation_grace::start_unpause_recovery(env);
+    }
+
     // Update pause switch
     config.pause_switches.set(operation.clone(), paused);
 
@@ -229,11 +290,12 @@ pub fn set_pause_switch(
     Ok(())
 }
 
-/// Set multiple pause switches at once (admin only)
+/// Set multiple pause switches at once (admin, or guardian to pause)
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin, or guardian when every
+///   switch pauses)
 /// * `switches` - Map of operation symbols to pause states
 ///
 /// # Returns
@@ -243,12 +305,18 @@ pub fn set_pause_switches(
     caller: Address,
     switches: Map<Symbol, bool>,
 ) -> Result<(), RiskManagementError> {
-    // Check admin
-    require_admin(env, &caller)?;
+    let pausing = switches.values().iter().all(|paused| paused);
+    require_pause_authority(env, &caller, pausing)?;
 
     // Get current config
     let mut config = get_risk_config(env).ok_or(RiskManagementError::InvalidParameter)?;
 
+    // Reopening liquidations starts the post-incident recovery window
+    let liquidate_op = Symbol::new(env, "pause_liquidate");
+    if switches.get(liquidate_op.clone()) == Some(false) && is_operation_paused(env, liquidate_op) {
+        crate::liquidation_grace::start_unpause_recovery(env);
+    }
+
     // Update all pause switches
     for (op, paused) in switches.iter() {
         config.pause_switches.set(op, paused);
@@ -298,13 +366,13 @@ pub fn check_operation_paused(env: &Env, operation: Symbol) -> bool {
     is_operation_paused(env, operation)
 }
 
-/// Set emergency pause (admin only)
+/// Set emergency pause (admin, or guardian to pause)
 ///
 /// Emergency pause stops all operations immediately.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin, or guardian when pausing)
 /// * `paused` - Whether to enable (true) or disable (false) emergency pause
 ///
 /// # Returns
@@ -314,8 +382,12 @@ pub fn set_emergency_pause(
     caller: Address,
     paused: bool,
 ) -> Result<(), RiskManagementError> {
-    // Check admin
-    require_admin(env, &caller)?;
+    require_pause_authority(env, &caller, paused)?;
+
+    // Lifting the emergency pause starts the post-incident recovery window
+    if !paused && is_emergency_paused(env) {
+        crate::liquidation_grace::start_unpause_recovery(env);
+    }
 
     // Set emergency pause
     let emergency_key = RiskDataKey::EmergencyPause;
@@ -344,6 +416,110 @@ pub fn check_emergency_pause(env: &Env) -> Result<(), RiskManagementError> {
     Ok(())
 }
 
+/// Freeze or unfreeze an asset (admin, or guardian to freeze)
+///
+/// A frozen asset takes no new deposits, borrows or AMM swaps; withdrawals,
+/// repayments and liquidations are unaffected.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin, or guardian when freezing)
+/// * `asset` - The asset (None for native XLM)
+/// * `frozen` - Whether to freeze (true) or unfreeze (false)
+///
+/// # Errors
+/// * `RiskManagementError::Unauthorized` - If caller is not admin, or is a
+///   guardian unfreezing
+pub fn set_asset_frozen(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    frozen: bool,
+) -> Result<(), RiskManagementError> {
+    require_pause_authority(env, &caller, frozen)?;
+
+    env.storage()
+        .persistent()
+        .set(&RiskDataKey::FrozenAsset(asset.clone()), &frozen);
+
+    emit_asset_freeze_changed(
+        env,
+        AssetFreezeChangedEvent {
+            actor: caller,
+            asset,
+            frozen,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Check if an asset is frozen
+pub fn is_asset_frozen(env: &Env, asset: &Option<Address>) -> bool {
+    env.storage()
+        .persistent()
+        .get::<RiskDataKey, bool>(&RiskDataKey::FrozenAsset(asset.clone()))
+        .unwrap_or(false)
+}
+
+/// Enter emergency withdrawal mode (admin only)
+///
+/// Turns on the emergency pause and pauses borrows for good; suppliers
+/// without debt keep being able to withdraw. The mode cannot be left.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin)
+///
+/// # Errors
+/// * `RiskManagementError::Unauthorized` - If caller is not admin
+/// * `RiskManagementError::EmergencyWithdrawalActive` - If the mode is
+///   already active
+/// * `RiskManagementError::InvalidParameter` - If risk management is not
+///   initialized
+pub fn enter_emergency_withdrawal_mode(
+    env: &Env,
+    caller: Address,
+) -> Result<(), RiskManagementError> {
+    caller.require_auth();
+    require_admin(env, &caller)?;
+    if is_emergency_withdrawal_mode(env) {
+        return Err(RiskManagementError::EmergencyWithdrawalActive);
+    }
+
+    let mut config = get_risk_config(env).ok_or(RiskManagementError::InvalidParameter)?;
+    config
+        .pause_switches
+        .set(Symbol::new(env, "pause_borrow"), true);
+    config.last_update = env.ledger().timestamp();
+    env.storage()
+        .persistent()
+        .set(&RiskDataKey::RiskConfig, &config);
+    env.storage()
+        .persistent()
+        .set(&RiskDataKey::EmergencyPause, &true);
+    env.storage().persistent().set(
+        &RiskDataKey::EmergencyWithdrawalMode,
+        &env.ledger().timestamp(),
+    );
+
+    emit_emergency_withdrawal_mode(
+        env,
+        EmergencyWithdrawalModeEvent {
+            admin: caller,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Check if the protocol is in emergency withdrawal mode
+pub fn is_emergency_withdrawal_mode(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .has(&RiskDataKey::EmergencyWithdrawalMode)
+}
+
 
 
 
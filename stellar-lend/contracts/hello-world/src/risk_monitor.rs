@@ -0,0 +1,652 @@
+//! # Risk Monitor Module
+//!
+//! Solvency and risk metrics for monitoring bots.
+//!
+//! The metric getters are pure reads that need no authorization, so bots can
+//! poll freely. `get_risk_monitor_report` bundles all metrics into one call.
+//!
+//! ## Metrics
+//! - **Solvency ratio**: total collateral value over total debt value across
+//!   the cross-asset reserves, in basis points (`i128::MAX` with no debt).
+//! - **Aggregated bad debt**: sum over accounts of the debt value not backed
+//!   by the account's own collateral.
+//! - **Concentration**: the largest supplier and borrower of each reserve and
+//!   their share of the reserve's totals.
+//! - **Oracle staleness**: age of each asset's latest price feed against the
+//!   configured staleness limit.
+//!
+//! Values use the cross-asset reserve prices (7 decimals), matching
+//! `get_user_position_summary`.
+//!
+//! ## Dashboard snapshot
+//! `get_risk_snapshot` returns the health factor distribution of all
+//! accounts, utilization and cap usage of a page of reserves (at most
+//! `MAX_SNAPSHOT_RESERVES`), and liquidation volume over the trailing
+//! `LIQUIDATION_WINDOW_SECONDS` from the activity log.
+//!
+//! ## Heartbeat
+//! `heartbeat` is permissionless and emits a compact digest event — totals,
+//! worst health factor bucket counts, pause flags and oracle freshness — at
+//! most once per `heartbeat_interval` ledgers. A missing heartbeat is a
+//! liveness signal; drifting digests are an early warning.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Map, Symbol, Vec};
+
+use crate::cross_asset::{AssetKey, AssetPosition, UserAssetKey};
+use crate::deposit::{Activity, DepositDataKey};
+use crate::events::{emit_heartbeat, HeartbeatEvent};
+use crate::oracle::{OracleDataKey, PriceFeed};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Reserve price scale (7 decimals)
+const PRICE_SCALE: i128 = 10_000_000;
+
+/// Default minimum ledgers between heartbeats (~1 hour at 5s ledgers)
+pub const DEFAULT_HEARTBEAT_INTERVAL: u32 = 720;
+
+/// Maximum reserves returned per risk snapshot page
+pub const MAX_SNAPSHOT_RESERVES: u32 = 20;
+
+/// Trailing window for risk snapshot liquidation volume (24 hours)
+pub const LIQUIDATION_WINDOW_SECONDS: u64 = 86_400;
+
+/// Errors that can occur during risk monitoring operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RiskMonitorError {
+    /// Caller is not authorized (not admin)
+    Unauthorized = 1,
+    /// Heartbeat interval must be greater than zero
+    InvalidInterval = 2,
+    /// The previous heartbeat is too recent
+    HeartbeatTooSoon = 3,
+}
+
+/// Storage keys for risk monitoring data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum RiskMonitorDataKey {
+    /// Minimum ledgers between heartbeats: u32
+    HeartbeatInterval,
+    /// Ledger of the last heartbeat: u32
+    LastHeartbeatLedger,
+}
+
+/// Protocol-wide solvency figures
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolvencySnapshot {
+    /// Value of all collateral supplied
+    pub total_collateral_value: i128,
+    /// Value of all outstanding debt, including accrued interest
+    pub total_debt_value: i128,
+    /// Debt value not covered by the borrowing account's collateral
+    pub bad_debt_value: i128,
+    /// Collateral value / debt value (bps)
+    pub solvency_ratio_bps: i128,
+}
+
+/// Largest-position concentration of one reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveConcentration {
+    /// Reserve asset (None = native)
+    pub asset: Option<Address>,
+    /// Total supplied to the reserve
+    pub total_supply: i128,
+    /// Total borrowed from the reserve
+    pub total_borrow: i128,
+    /// Account with the largest supply
+    pub largest_supplier: Option<Address>,
+    /// Largest single supply
+    pub largest_supply: i128,
+    /// Largest supply as a share of total supply (bps)
+    pub supply_concentration_bps: i128,
+    /// Account with the largest debt
+    pub largest_borrower: Option<Address>,
+    /// Largest single debt, including accrued interest
+    pub largest_borrow: i128,
+    /// Largest debt as a share of total borrows (bps)
+    pub borrow_concentration_bps: i128,
+}
+
+/// Freshness of one asset's price feed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleStaleness {
+    /// Priced asset
+    pub asset: Address,
+    /// Whether a price feed exists
+    pub has_feed: bool,
+    /// Timestamp of the latest update (0 without a feed)
+    pub last_updated: u64,
+    /// Seconds since the latest update
+    pub age_seconds: u64,
+    /// Configured staleness limit
+    pub max_staleness_seconds: u64,
+    /// Whether the feed is missing or older than the limit
+    pub is_stale: bool,
+}
+
+/// Compact digest emitted by `heartbeat`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeartbeatDigest {
+    /// Ledger the digest was taken at
+    pub ledger: u32,
+    /// Value of all collateral supplied
+    pub total_supplied_value: i128,
+    /// Value of all outstanding debt
+    pub total_borrowed_value: i128,
+    /// Accounts with health factor below 1.05x (risk level 5)
+    pub critical_accounts: u32,
+    /// Accounts with health factor in [1.05x, 1.1x) (risk level 4)
+    pub high_risk_accounts: u32,
+    /// Accounts with health factor in [1.1x, 1.2x) (risk level 3)
+    pub elevated_risk_accounts: u32,
+    /// Whether the emergency pause is active
+    pub emergency_paused: bool,
+    /// Operations whose pause switch is on
+    pub paused_operations: Vec<Symbol>,
+    /// Oldest latest-update among reserve price feeds (0 without feeds)
+    pub oldest_oracle_update: u64,
+    /// Most recent update among reserve price feeds
+    pub newest_oracle_update: u64,
+    /// Reserve price feeds that are missing or stale
+    pub stale_oracle_feeds: u32,
+}
+
+/// Accounts per health factor bucket, following the analytics risk levels
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthFactorDistribution {
+    /// Accounts without debt
+    pub no_debt_accounts: u32,
+    /// Health factor of 1.5x or more (risk level 1)
+    pub low_risk_accounts: u32,
+    /// Health factor in [1.2x, 1.5x) (risk level 2)
+    pub moderate_risk_accounts: u32,
+    /// Health factor in [1.1x, 1.2x) (risk level 3)
+    pub elevated_risk_accounts: u32,
+    /// Health factor in [1.05x, 1.1x) (risk level 4)
+    pub high_risk_accounts: u32,
+    /// Health factor below 1.05x (risk level 5)
+    pub critical_accounts: u32,
+}
+
+/// Utilization and cap usage of one reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveRiskEntry {
+    /// Reserve asset (None = native)
+    pub asset: Option<Address>,
+    /// Total supplied to the reserve
+    pub total_supply: i128,
+    /// Total borrowed from the reserve
+    pub total_borrow: i128,
+    /// Total borrow / total supply (bps)
+    pub utilization_bps: i128,
+    /// Supply cap (0 = unlimited)
+    pub supply_cap: i128,
+    /// Total supply / supply cap (bps, 0 when unlimited)
+    pub supply_cap_usage_bps: i128,
+    /// Borrow cap (0 = unlimited)
+    pub borrow_cap: i128,
+    /// Total borrow / borrow cap (bps, 0 when unlimited)
+    pub borrow_cap_usage_bps: i128,
+}
+
+/// Aggregate feed for a protocol risk dashboard
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskSnapshot {
+    /// Health factor distribution across all accounts
+    pub health_factors: HealthFactorDistribution,
+    /// Requested page of reserves
+    pub reserves: Vec<ReserveRiskEntry>,
+    /// Number of registered reserves
+    pub reserve_count: u32,
+    /// Start of the next page, if any
+    pub next_start: Option<u32>,
+    /// Trailing window the liquidation figures cover
+    pub liquidation_window_seconds: u64,
+    /// Liquidations within the window
+    pub liquidation_count: u32,
+    /// Debt liquidated within the window, in debt asset units
+    pub liquidation_volume: i128,
+    /// Snapshot timestamp
+    pub timestamp: u64,
+}
+
+/// Batched risk report
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskMonitorReport {
+    /// Protocol-wide solvency
+    pub solvency: SolvencySnapshot,
+    /// Concentration of every registered reserve
+    pub reserves: Vec<ReserveConcentration>,
+    /// Feed freshness of every registered reserve with a priced asset
+    pub oracles: Vec<OracleStaleness>,
+    /// Report timestamp
+    pub timestamp: u64,
+}
+
+fn share_bps(part: i128, total: i128) -> i128 {
+    if total <= 0 {
+        return 0;
+    }
+    part.saturating_mul(BASIS_POINTS_SCALE) / total
+}
+
+fn debt_of(position: &AssetPosition) -> i128 {
+    position
+        .debt_principal
+        .saturating_add(position.accrued_interest)
+}
+
+/// Value every account's positions: account -> (collateral value, debt value)
+fn get_account_values(env: &Env) -> Map<Address, (i128, i128)> {
+    let positions = crate::cross_asset::get_user_asset_positions(env);
+
+    let mut accounts: Map<Address, (i128, i128)> = Map::new(env);
+    for (key, position) in positions.iter() {
+        let price = match crate::cross_asset::get_asset_config(env, &key.asset) {
+            Ok(config) => config.price,
+            Err(_) => continue,
+        };
+        let collateral_value = position.collateral.saturating_mul(price) / PRICE_SCALE;
+        let debt_value = debt_of(&position).saturating_mul(price) / PRICE_SCALE;
+
+        let (collateral, debt) = accounts.get(key.user.clone()).unwrap_or((0, 0));
+        accounts.set(
+            key.user,
+            (
+                collateral.saturating_add(collateral_value),
+                debt.saturating_add(debt_value),
+            ),
+        );
+    }
+    accounts
+}
+
+/// Get protocol-wide solvency figures
+pub fn get_solvency_snapshot(env: &Env) -> SolvencySnapshot {
+    let mut total_collateral_value: i128 = 0;
+    let mut total_debt_value: i128 = 0;
+    let mut bad_debt_value: i128 = 0;
+    for (_, (collateral, debt)) in get_account_values(env).iter() {
+        total_collateral_value = total_collateral_value.saturating_add(collateral);
+        total_debt_value = total_debt_value.saturating_add(debt);
+        if debt > collateral {
+            bad_debt_value = bad_debt_value.saturating_add(debt - collateral);
+        }
+    }
+
+    let solvency_ratio_bps = if total_debt_value == 0 {
+        i128::MAX
+    } else {
+        total_collateral_value.saturating_mul(BASIS_POINTS_SCALE) / total_debt_value
+    };
+
+    SolvencySnapshot {
+        total_collateral_value,
+        total_debt_value,
+        bad_debt_value,
+        solvency_ratio_bps,
+    }
+}
+
+/// Get the protocol solvency ratio (bps)
+pub fn get_solvency_ratio(env: &Env) -> i128 {
+    get_solvency_snapshot(env).solvency_ratio_bps
+}
+
+/// Get the aggregated bad debt value across all accounts
+pub fn get_aggregate_bad_debt(env: &Env) -> i128 {
+    get_solvency_snapshot(env).bad_debt_value
+}
+
+/// Get the largest-position concentration of a reserve
+pub fn get_reserve_concentration(env: &Env, asset: Option<Address>) -> ReserveConcentration {
+    let asset_key = AssetKey::from_option(asset.clone());
+    let positions = crate::cross_asset::get_user_asset_positions(env);
+
+    let mut largest_supplier = None;
+    let mut largest_supply: i128 = 0;
+    let mut largest_borrower = None;
+    let mut largest_borrow: i128 = 0;
+    for (key, position) in positions.iter() {
+        if key.asset != asset_key {
+            continue;
+        }
+        if position.collateral > largest_supply {
+            largest_supply = position.collateral;
+            largest_supplier = Some(key.user.clone());
+        }
+        let debt = debt_of(&position);
+        if debt > largest_borrow {
+            largest_borrow = debt;
+            largest_borrower = Some(key.user);
+        }
+    }
+
+    let total_supply = crate::cross_asset::get_total_supply(env, &asset_key);
+    let total_borrow = crate::cross_asset::get_total_borrow(env, &asset_key);
+
+    ReserveConcentration {
+        asset,
+        total_supply,
+        total_borrow,
+        largest_supplier,
+        largest_supply,
+        supply_concentration_bps: share_bps(largest_supply, total_supply),
+        largest_borrower,
+        largest_borrow,
+        borrow_concentration_bps: share_bps(largest_borrow, total_borrow),
+    }
+}
+
+/// Get the freshness of an asset's price feed
+pub fn get_oracle_staleness(env: &Env, asset: Address) -> OracleStaleness {
+    let max_staleness_seconds = crate::price_oracle::get_max_price_age(env, &Some(asset.clone()));
+    let now = env.ledger().timestamp();
+
+    match env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, PriceFeed>(&OracleDataKey::PriceFeed(asset.clone()))
+    {
+        Some(feed) => {
+            let age_seconds = now.saturating_sub(feed.last_updated);
+            OracleStaleness {
+                asset,
+                has_feed: true,
+                last_updated: feed.last_updated,
+                age_seconds,
+                max_staleness_seconds,
+                is_stale: age_seconds > max_staleness_seconds,
+            }
+        }
+        None => OracleStaleness {
+            asset,
+            has_feed: false,
+            last_updated: 0,
+            age_seconds: 0,
+            max_staleness_seconds,
+            is_stale: true,
+        },
+    }
+}
+
+/// Get every risk metric in one call
+///
+/// Oracle entries cover each registered reserve's token; the native reserve
+/// is reported through the configured native asset address when one is set.
+pub fn get_risk_monitor_report(env: &Env) -> RiskMonitorReport {
+    let mut reserves = Vec::new(env);
+    for asset_key in crate::cross_asset::get_asset_list(env).iter() {
+        reserves.push_back(get_reserve_concentration(env, asset_key.to_option()));
+    }
+
+    RiskMonitorReport {
+        solvency: get_solvency_snapshot(env),
+        reserves,
+        oracles: get_reserve_oracle_staleness(env),
+        timestamp: env.ledger().timestamp(),
+    }
+}
+
+/// Feed freshness of every registered reserve with a priced asset
+fn get_reserve_oracle_staleness(env: &Env) -> Vec<OracleStaleness> {
+    let mut oracles = Vec::new(env);
+    for asset_key in crate::cross_asset::get_asset_list(env).iter() {
+        let priced_asset = match asset_key {
+            AssetKey::Token(address) => Some(address),
+            AssetKey::Native => env
+                .storage()
+                .persistent()
+                .get::<DepositDataKey, Address>(&DepositDataKey::NativeAssetAddress),
+        };
+        if let Some(address) = priced_asset {
+            oracles.push_back(get_oracle_staleness(env, address));
+        }
+    }
+    oracles
+}
+
+/// Get the utilization and cap usage of a reserve
+fn get_reserve_risk_entry(env: &Env, asset_key: &AssetKey) -> ReserveRiskEntry {
+    let total_supply = crate::cross_asset::get_total_supply(env, asset_key);
+    let total_borrow = crate::cross_asset::get_total_borrow(env, asset_key);
+    let (supply_cap, borrow_cap) = match crate::cross_asset::get_asset_config(env, asset_key) {
+        Ok(config) => (config.max_supply, config.max_borrow),
+        Err(_) => (0, 0),
+    };
+
+    ReserveRiskEntry {
+        asset: asset_key.to_option(),
+        total_supply,
+        total_borrow,
+        utilization_bps: share_bps(total_borrow, total_supply),
+        supply_cap,
+        supply_cap_usage_bps: share_bps(total_supply, supply_cap),
+        borrow_cap,
+        borrow_cap_usage_bps: share_bps(total_borrow, borrow_cap),
+    }
+}
+
+/// Get the health factor distribution across all accounts
+fn get_health_factor_distribution(env: &Env) -> HealthFactorDistribution {
+    let mut distribution = HealthFactorDistribution {
+        no_debt_accounts: 0,
+        low_risk_accounts: 0,
+        moderate_risk_accounts: 0,
+        elevated_risk_accounts: 0,
+        high_risk_accounts: 0,
+        critical_accounts: 0,
+    };
+    for (_, (collateral, debt)) in get_account_values(env).iter() {
+        if debt == 0 {
+            distribution.no_debt_accounts += 1;
+            continue;
+        }
+        let health_factor = collateral.saturating_mul(BASIS_POINTS_SCALE) / debt;
+        match crate::analytics::calculate_user_risk_level(health_factor) {
+            1 => distribution.low_risk_accounts += 1,
+            2 => distribution.moderate_risk_accounts += 1,
+            3 => distribution.elevated_risk_accounts += 1,
+            4 => distribution.high_risk_accounts += 1,
+            _ => distribution.critical_accounts += 1,
+        }
+    }
+    distribution
+}
+
+/// Get a risk dashboard snapshot
+///
+/// # Arguments
+/// * `start` - Index of the first reserve to return
+/// * `limit` - Reserves to return (0 or above `MAX_SNAPSHOT_RESERVES` = the maximum)
+pub fn get_risk_snapshot(env: &Env, start: u32, limit: u32) -> RiskSnapshot {
+    let limit = if limit == 0 || limit > MAX_SNAPSHOT_RESERVES {
+        MAX_SNAPSHOT_RESERVES
+    } else {
+        limit
+    };
+
+    let asset_list = crate::cross_asset::get_asset_list(env);
+    let reserve_count = asset_list.len();
+    let end = start.saturating_add(limit).min(reserve_count);
+    let mut reserves = Vec::new(env);
+    for i in start..end {
+        reserves.push_back(get_reserve_risk_entry(env, &asset_list.get(i).unwrap()));
+    }
+    let next_start = if end < reserve_count { Some(end) } else { None };
+
+    let now = env.ledger().timestamp();
+    let window_start = now.saturating_sub(LIQUIDATION_WINDOW_SECONDS);
+    let liquidate = Symbol::new(env, "liquidate");
+    let mut liquidation_count: u32 = 0;
+    let mut liquidation_volume: i128 = 0;
+    let log = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Vec<Activity>>(&DepositDataKey::ActivityLog)
+        .unwrap_or(Vec::new(env));
+    for activity in log.iter() {
+        if activity.activity_type == liquidate && activity.timestamp >= window_start {
+            liquidation_count += 1;
+            liquidation_volume = liquidation_volume.saturating_add(activity.amount);
+        }
+    }
+
+    RiskSnapshot {
+        health_factors: get_health_factor_distribution(env),
+        reserves,
+        reserve_count,
+        next_start,
+        liquidation_window_seconds: LIQUIDATION_WINDOW_SECONDS,
+        liquidation_count,
+        liquidation_volume,
+        timestamp: now,
+    }
+}
+
+/// Set the minimum number of ledgers between heartbeats (admin only)
+pub fn set_heartbeat_interval(
+    env: &Env,
+    caller: Address,
+    ledgers: u32,
+) -> Result<(), RiskMonitorError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| RiskMonitorError::Unauthorized)?;
+    if ledgers == 0 {
+        return Err(RiskMonitorError::InvalidInterval);
+    }
+    env.storage()
+        .persistent()
+        .set(&RiskMonitorDataKey::HeartbeatInterval, &ledgers);
+    Ok(())
+}
+
+/// Get the minimum number of ledgers between heartbeats
+pub fn get_heartbeat_interval(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<RiskMonitorDataKey, u32>(&RiskMonitorDataKey::HeartbeatInterval)
+        .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL)
+}
+
+/// Get the ledger of the last heartbeat
+pub fn get_last_heartbeat_ledger(env: &Env) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get::<RiskMonitorDataKey, u32>(&RiskMonitorDataKey::LastHeartbeatLedger)
+}
+
+/// Build the heartbeat digest for the current ledger
+pub fn get_heartbeat_digest(env: &Env) -> HeartbeatDigest {
+    let mut total_supplied_value: i128 = 0;
+    let mut total_borrowed_value: i128 = 0;
+    let mut critical_accounts: u32 = 0;
+    let mut high_risk_accounts: u32 = 0;
+    let mut elevated_risk_accounts: u32 = 0;
+    for (_, (collateral, debt)) in get_account_values(env).iter() {
+        total_supplied_value = total_supplied_value.saturating_add(collateral);
+        total_borrowed_value = total_borrowed_value.saturating_add(debt);
+        if debt == 0 {
+            continue;
+        }
+        let health_factor = collateral.saturating_mul(BASIS_POINTS_SCALE) / debt;
+        match crate::analytics::calculate_user_risk_level(health_factor) {
+            5 => critical_accounts += 1,
+            4 => high_risk_accounts += 1,
+            3 => elevated_risk_accounts += 1,
+            _ => {}
+        }
+    }
+
+    let mut paused_operations = Vec::new(env);
+    if let Some(config) = crate::risk_management::get_risk_config(env) {
+        for (operation, paused) in config.pause_switches.iter() {
+            if paused {
+                paused_operations.push_back(operation);
+            }
+        }
+    }
+
+    let mut oldest_oracle_update: u64 = 0;
+    let mut newest_oracle_update: u64 = 0;
+    let mut stale_oracle_feeds: u32 = 0;
+    for feed in get_reserve_oracle_staleness(env).iter() {
+        if feed.is_stale {
+            stale_oracle_feeds += 1;
+        }
+        if !feed.has_feed {
+            continue;
+        }
+        if oldest_oracle_update == 0 || feed.last_updated < oldest_oracle_update {
+            oldest_oracle_update = feed.last_updated;
+        }
+        newest_oracle_update = newest_oracle_update.max(feed.last_updated);
+    }
+
+    HeartbeatDigest {
+        ledger: env.ledger().sequence(),
+        total_supplied_value,
+        total_borrowed_value,
+        critical_accounts,
+        high_risk_accounts,
+        elevated_risk_accounts,
+        emergency_paused: crate::risk_management::is_emergency_paused(env),
+        paused_operations,
+        oldest_oracle_update,
+        newest_oracle_update,
+        stale_oracle_feeds,
+    }
+}
+
+/// Emit a heartbeat digest event
+///
+/// Anyone can call this, at most once per heartbeat interval.
+///
+/// # Errors
+/// * `RiskMonitorError::HeartbeatTooSoon` - If the interval has not elapsed
+pub fn heartbeat(env: &Env) -> Result<HeartbeatDigest, RiskMonitorError> {
+    let ledger = env.ledger().sequence();
+    if let Some(last) = get_last_heartbeat_ledger(env) {
+        if ledger < last.saturating_add(get_heartbeat_interval(env)) {
+            return Err(RiskMonitorError::HeartbeatTooSoon);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&RiskMonitorDataKey::LastHeartbeatLedger, &ledger);
+
+    let digest = get_heartbeat_digest(env);
+    emit_heartbeat(
+        env,
+        HeartbeatEvent {
+            ledger: digest.ledger,
+            total_supplied_value: digest.total_supplied_value,
+            total_borrowed_value: digest.total_borrowed_value,
+            critical_accounts: digest.critical_accounts,
+            high_risk_accounts: digest.high_risk_accounts,
+            elevated_risk_accounts: digest.elevated_risk_accounts,
+            emergency_paused: digest.emergency_paused,
+            paused_operations: digest.paused_operations.clone(),
+            oldest_oracle_update: digest.oldest_oracle_update,
+            newest_oracle_update: digest.newest_oracle_update,
+            stale_oracle_feeds: digest.stale_oracle_feeds,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(digest)
+}
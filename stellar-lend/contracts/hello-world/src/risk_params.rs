@@ -102,7 +102,7 @@ pub fn get_risk_params(env: &Env) -> Option<RiskParams> {
 }
 
 /// Validate risk configuration
-fn validate_risk_params(config: &RiskParams) -> Result<(), RiskParamsError> {
+pub(crate) fn validate_risk_params(config: &RiskParams) -> Result<(), RiskParamsError> {
     // Validate min collateral ratio
     if config.min_collateral_ratio < MIN_COLLATERAL_RATIO_MIN
         || config.min_collateral_ratio > MIN_COLLATERAL_RATIO_MAX
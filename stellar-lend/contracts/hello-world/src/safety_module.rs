@@ -0,0 +1,770 @@
+//! # Safety Module
+//!
+//! An insurance fund of the protocol token (the governance `vote_token` set
+//! by `gov_initialize`) that backstops bad debt. Stakers deposit tokens for
+//! shares of the fund; a share is worth `fund / total_shares` tokens, so a
+//! slash reduces every staker's holdings in proportion.
+//!
+//! ## Slashing
+//! When an asset accrues a deficit (see `bad_debt`), up to `max_slash_bps`
+//! of the fund can be slashed per call and sent to the treasury, to be sold
+//! for the asset, while the deficit it covers is cleared:
+//! - the admin or the treasury chooses the tokens slashed and the deficit
+//!   they cover (`slash_safety_module`), or
+//! - with `auto_slash` enabled, anyone can trigger a slash priced with the
+//!   oracle feeds of the asset and the protocol token
+//!   (`trigger_safety_slash`).
+//!
+//! ## Yield
+//! While the fund is not needed, the admin or the treasury pays stakers a
+//! yield out of an asset's protocol reserve. It is split per share and
+//! claimed by each staker in that asset.
+//!
+//! ## Withdrawals
+//! Withdrawing needs a cooldown, started by the staker, to have elapsed and
+//! must happen within the unstake window that follows it, so stakers cannot
+//! leave right ahead of a slash.
+//!
+//! ## Invariants
+//! - A slash never takes more than `max_slash_bps` of the fund, and never
+//!   covers more than the asset's bad debt.
+//! - Yield is only paid out of protocol reserves, never out of user funds.
+//! - Staking clears a pending cooldown.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::deposit::DepositDataKey;
+use crate::events::{
+    emit_safety_config_updated, emit_safety_cooldown_started, emit_safety_module_slashed,
+    emit_safety_staked, emit_safety_unstaked, emit_safety_yield_claimed, emit_safety_yield_paid,
+    SafetyConfigUpdatedEvent, SafetyCooldownStartedEvent, SafetyModuleSlashedEvent,
+    SafetyStakedEvent, SafetyUnstakedEvent, SafetyYieldClaimedEvent, SafetyYieldPaidEvent,
+};
+use crate::oracle::{OracleDataKey, PriceFeed};
+use crate::storage::GovernanceDataKey;
+use crate::types::GovernanceConfig;
+
+/// Highest slash cap the admin can configure (50%, in basis points)
+pub const MAX_SLASH_CAP_BPS: i128 = 5_000;
+
+/// Most assets the fund can pay yield in
+pub const MAX_YIELD_ASSETS: u32 = 5;
+
+/// Fixed-point scale of the yield indexes
+const YIELD_INDEX_SCALE: i128 = 1_000_000_000_000_000_000;
+
+const BPS_SCALE: i128 = 10_000;
+
+/// Errors that can occur during safety module operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SafetyModuleError {
+    /// Caller is not authorized (not admin or treasury)
+    Unauthorized = 1,
+    /// Governance, and with it the protocol token, is not initialized
+    GovernanceNotInitialized = 2,
+    /// Amount must be greater than zero
+    InvalidAmount = 3,
+    /// Configuration values are out of range
+    InvalidConfig = 4,
+    /// User holds fewer shares than requested
+    InsufficientShares = 5,
+    /// The user has not started a cooldown
+    CooldownNotStarted = 6,
+    /// The cooldown has not elapsed yet
+    CooldownActive = 7,
+    /// The unstake window after the cooldown has passed
+    CooldownExpired = 8,
+    /// Slash exceeds the configured share of the fund
+    ExceedsSlashCap = 9,
+    /// Covered amount exceeds the asset's bad debt
+    ExceedsBadDebt = 10,
+    /// The asset has no deficit to cover
+    NoDeficit = 11,
+    /// Automatic slashing is disabled
+    AutoSlashDisabled = 12,
+    /// No treasury address is configured to receive slashed tokens
+    TreasuryNotSet = 13,
+    /// The protocol token has no usable price feed
+    PriceNotAvailable = 14,
+    /// Protocol reserve is too small to pay the yield
+    InsufficientReserve = 15,
+    /// The fund already pays yield in `MAX_YIELD_ASSETS` assets
+    TooManyYieldAssets = 16,
+    /// Nobody is staked in the fund
+    NothingStaked = 17,
+    /// Arithmetic overflow occurred
+    Overflow = 18,
+}
+
+/// Safety module parameters
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SafetyModuleConfig {
+    /// Largest share of the fund a single slash can take, in basis points
+    pub max_slash_bps: i128,
+    /// Seconds between starting a cooldown and being able to withdraw
+    pub cooldown: u64,
+    /// Seconds after the cooldown during which withdrawals are allowed
+    pub unstake_window: u64,
+    /// Whether anyone can trigger an oracle-priced slash
+    pub auto_slash: bool,
+}
+
+/// A staker's yield in one asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SafetyYieldState {
+    /// Yield index at the last settlement
+    pub index: i128,
+    /// Yield settled but not yet claimed
+    pub accrued: i128,
+}
+
+/// Storage keys for safety module data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum SafetyModuleDataKey {
+    /// Safety module parameters: SafetyModuleConfig
+    SlashingConfig,
+    /// Protocol tokens held by the fund: i128
+    InsuranceFund,
+    /// Shares issued to all stakers: i128
+    TotalSafetyShares,
+    /// A staker's shares: i128
+    StakerShares(Address),
+    /// When a staker started their cooldown: u64
+    UnstakeCooldown(Address),
+    /// Assets the fund has paid yield in: Vec<Option<Address>>
+    YieldAssets,
+    /// Yield paid per share of an asset, scaled by 1e18: i128
+    YieldIndex(Option<Address>),
+    /// A staker's yield in an asset: SafetyYieldState
+    StakerYield(Address, Option<Address>),
+}
+
+fn default_config() -> SafetyModuleConfig {
+    SafetyModuleConfig {
+        max_slash_bps: 3_000,
+        cooldown: 10 * 24 * 60 * 60,
+        unstake_window: 2 * 24 * 60 * 60,
+        auto_slash: false,
+    }
+}
+
+fn protocol_token(env: &Env) -> Result<Address, SafetyModuleError> {
+    env.storage()
+        .instance()
+        .get::<GovernanceDataKey, GovernanceConfig>(&GovernanceDataKey::Config)
+        .map(|config| config.vote_token)
+        .ok_or(SafetyModuleError::GovernanceNotInitialized)
+}
+
+/// Require the caller to be the admin or the treasury address
+fn require_admin_or_treasury(env: &Env, caller: &Address) -> Result<(), SafetyModuleError> {
+    caller.require_auth();
+    if crate::reserve::get_treasury_address(env).as_ref() == Some(caller) {
+        return Ok(());
+    }
+    crate::admin::require_admin(env, caller).map_err(|_| SafetyModuleError::Unauthorized)
+}
+
+/// Get the safety module parameters
+pub fn get_safety_module_config(env: &Env) -> SafetyModuleConfig {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, SafetyModuleConfig>(&SafetyModuleDataKey::SlashingConfig)
+        .unwrap_or_else(default_config)
+}
+
+/// Set the safety module parameters (admin only)
+///
+/// # Errors
+/// * `SafetyModuleError::Unauthorized` - If caller is not admin
+/// * `SafetyModuleError::InvalidConfig` - If the slash cap is outside
+///   `1..=MAX_SLASH_CAP_BPS` or the unstake window is zero
+pub fn set_safety_module_config(
+    env: &Env,
+    caller: Address,
+    config: SafetyModuleConfig,
+) -> Result<(), SafetyModuleError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| SafetyModuleError::Unauthorized)?;
+    if config.max_slash_bps <= 0
+        || config.max_slash_bps > MAX_SLASH_CAP_BPS
+        || config.unstake_window == 0
+    {
+        return Err(SafetyModuleError::InvalidConfig);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::SlashingConfig, &config);
+    emit_safety_config_updated(
+        env,
+        SafetyConfigUpdatedEvent {
+            admin: caller,
+            config,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the protocol tokens held by the fund
+pub fn get_safety_fund(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::InsuranceFund)
+        .unwrap_or(0)
+}
+
+/// Get the shares issued to all stakers
+pub fn get_safety_total_shares(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::TotalSafetyShares)
+        .unwrap_or(0)
+}
+
+/// Get a staker's shares
+pub fn get_safety_shares(env: &Env, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::StakerShares(user.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the protocol tokens a staker's shares are currently worth
+pub fn get_safety_balance(env: &Env, user: &Address) -> i128 {
+    shares_to_tokens(env, get_safety_shares(env, user)).unwrap_or(0)
+}
+
+/// Get when a staker started their cooldown, if they have
+pub fn get_safety_cooldown(env: &Env, user: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, u64>(&SafetyModuleDataKey::UnstakeCooldown(user.clone()))
+}
+
+fn shares_to_tokens(env: &Env, shares: i128) -> Result<i128, SafetyModuleError> {
+    let total_shares = get_safety_total_shares(env);
+    if total_shares == 0 {
+        return Ok(0);
+    }
+    Ok(shares
+        .checked_mul(get_safety_fund(env))
+        .ok_or(SafetyModuleError::Overflow)?
+        / total_shares)
+}
+
+fn set_fund(env: &Env, fund: i128) {
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::InsuranceFund, &fund);
+}
+
+fn set_shares(env: &Env, user: &Address, shares: i128, total_shares: i128) {
+    let key = SafetyModuleDataKey::StakerShares(user.clone());
+    if shares == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &shares);
+    }
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::TotalSafetyShares, &total_shares);
+}
+
+fn get_yield_assets(env: &Env) -> Vec<Option<Address>> {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, Vec<Option<Address>>>(&SafetyModuleDataKey::YieldAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_yield_index(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::YieldIndex(asset.clone()))
+        .unwrap_or(0)
+}
+
+fn get_yield_state(env: &Env, user: &Address, asset: &Option<Address>) -> SafetyYieldState {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, SafetyYieldState>(&SafetyModuleDataKey::StakerYield(
+            user.clone(),
+            asset.clone(),
+        ))
+        .unwrap_or(SafetyYieldState {
+            index: 0,
+            accrued: 0,
+        })
+}
+
+/// A staker's yield in an asset, settled up to the current index
+fn settled_yield_state(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    shares: i128,
+) -> Result<SafetyYieldState, SafetyModuleError> {
+    let state = get_yield_state(env, user, asset);
+    let index = get_yield_index(env, asset);
+    let earned = shares
+        .checked_mul(index - state.index)
+        .ok_or(SafetyModuleError::Overflow)?
+        / YIELD_INDEX_SCALE;
+    Ok(SafetyYieldState {
+        index,
+        accrued: state
+            .accrued
+            .checked_add(earned)
+            .ok_or(SafetyModuleError::Overflow)?,
+    })
+}
+
+/// Settle a staker's yield in every asset before their shares change
+fn settle_yield(env: &Env, user: &Address, shares: i128) -> Result<(), SafetyModuleError> {
+    for asset in get_yield_assets(env).iter() {
+        let state = settled_yield_state(env, user, &asset, shares)?;
+        env.storage().persistent().set(
+            &SafetyModuleDataKey::StakerYield(user.clone(), asset.clone()),
+            &state,
+        );
+    }
+    Ok(())
+}
+
+/// Get a staker's unclaimed yield in an asset
+pub fn get_safety_yield(env: &Env, user: &Address, asset: &Option<Address>) -> i128 {
+    settled_yield_state(env, user, asset, get_safety_shares(env, user))
+        .map(|state| state.accrued)
+        .unwrap_or(0)
+}
+
+/// Stake protocol tokens in the fund
+///
+/// Clears any cooldown the staker had started.
+///
+/// # Arguments
+/// * `user` - The staker (must authorize)
+/// * `amount` - Protocol tokens to stake
+///
+/// # Returns
+/// Returns the shares issued
+///
+/// # Errors
+/// * `SafetyModuleError::GovernanceNotInitialized` - If there is no protocol token
+/// * `SafetyModuleError::InvalidAmount` - If the amount is not positive or
+///   buys no shares
+/// * `SafetyModuleError::Overflow` - If share accounting overflows
+pub fn stake_safety(env: &Env, user: Address, amount: i128) -> Result<i128, SafetyModuleError> {
+    user.require_auth();
+    let token = protocol_token(env)?;
+    if amount <= 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+
+    let fund = get_safety_fund(env);
+    let total_shares = get_safety_total_shares(env);
+    let shares = if total_shares == 0 || fund == 0 {
+        amount
+    } else {
+        amount
+            .checked_mul(total_shares)
+            .ok_or(SafetyModuleError::Overflow)?
+            / fund
+    };
+    if shares == 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+
+    let user_shares = get_safety_shares(env, &user);
+    settle_yield(env, &user, user_shares)?;
+
+    soroban_sdk::token::Client::new(env, &token).transfer(
+        &user,
+        env.current_contract_address(),
+        &amount,
+    );
+
+    set_fund(
+        env,
+        fund.checked_add(amount)
+            .ok_or(SafetyModuleError::Overflow)?,
+    );
+    set_shares(
+        env,
+        &user,
+        user_shares
+            .checked_add(shares)
+            .ok_or(SafetyModuleError::Overflow)?,
+        total_shares
+            .checked_add(shares)
+            .ok_or(SafetyModuleError::Overflow)?,
+    );
+    env.storage()
+        .persistent()
+        .remove(&SafetyModuleDataKey::UnstakeCooldown(user.clone()));
+
+    emit_safety_staked(
+        env,
+        SafetyStakedEvent {
+            user,
+            amount,
+            shares,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(shares)
+}
+
+/// Start the cooldown that must elapse before a staker can withdraw
+///
+/// # Errors
+/// * `SafetyModuleError::InsufficientShares` - If the user holds no shares
+pub fn start_safety_cooldown(env: &Env, user: Address) -> Result<u64, SafetyModuleError> {
+    user.require_auth();
+    if get_safety_shares(env, &user) == 0 {
+        return Err(SafetyModuleError::InsufficientShares);
+    }
+
+    let now = env.ledger().timestamp();
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::UnstakeCooldown(user.clone()), &now);
+    emit_safety_cooldown_started(
+        env,
+        SafetyCooldownStartedEvent {
+            user,
+            timestamp: now,
+        },
+    );
+    Ok(now)
+}
+
+/// Redeem shares for protocol tokens once the cooldown has elapsed
+///
+/// # Arguments
+/// * `user` - The staker (must authorize)
+/// * `shares` - Shares to redeem
+///
+/// # Returns
+/// Returns the protocol tokens returned
+///
+/// # Errors
+/// * `SafetyModuleError::GovernanceNotInitialized` - If there is no protocol token
+/// * `SafetyModuleError::InvalidAmount` - If shares is not positive
+/// * `SafetyModuleError::InsufficientShares` - If the user holds fewer shares
+/// * `SafetyModuleError::CooldownNotStarted` - If no cooldown was started
+/// * `SafetyModuleError::CooldownActive` - If the cooldown has not elapsed
+/// * `SafetyModuleError::CooldownExpired` - If the unstake window has passed
+pub fn unstake_safety(env: &Env, user: Address, shares: i128) -> Result<i128, SafetyModuleError> {
+    user.require_auth();
+    let token = protocol_token(env)?;
+    if shares <= 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+    let user_shares = get_safety_shares(env, &user);
+    if shares > user_shares {
+        return Err(SafetyModuleError::InsufficientShares);
+    }
+
+    let config = get_safety_module_config(env);
+    let started = get_safety_cooldown(env, &user).ok_or(SafetyModuleError::CooldownNotStarted)?;
+    let now = env.ledger().timestamp();
+    let unlocked_at = started.saturating_add(config.cooldown);
+    if now < unlocked_at {
+        return Err(SafetyModuleError::CooldownActive);
+    }
+    if now > unlocked_at.saturating_add(config.unstake_window) {
+        return Err(SafetyModuleError::CooldownExpired);
+    }
+
+    let amount = shares_to_tokens(env, shares)?;
+    settle_yield(env, &user, user_shares)?;
+    set_fund(env, get_safety_fund(env) - amount);
+    set_shares(
+        env,
+        &user,
+        user_shares - shares,
+        get_safety_total_shares(env) - shares,
+    );
+    if shares == user_shares {
+        env.storage()
+            .persistent()
+            .remove(&SafetyModuleDataKey::UnstakeCooldown(user.clone()));
+    }
+
+    if amount > 0 {
+        soroban_sdk::token::Client::new(env, &token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+    }
+
+    emit_safety_unstaked(
+        env,
+        SafetyUnstakedEvent {
+            user,
+            shares,
+            amount,
+            timestamp: now,
+        },
+    );
+    Ok(amount)
+}
+
+/// Largest slash currently allowed, in protocol tokens
+pub fn get_safety_slash_cap(env: &Env) -> i128 {
+    get_safety_fund(env).saturating_mul(get_safety_module_config(env).max_slash_bps) / BPS_SCALE
+}
+
+/// Move slashed tokens to the treasury and clear the deficit they cover
+fn execute_slash(
+    env: &Env,
+    asset: Option<Address>,
+    amount: i128,
+    covered: i128,
+) -> Result<i128, SafetyModuleError> {
+    let token = protocol_token(env)?;
+    let treasury =
+        crate::reserve::get_treasury_address(env).ok_or(SafetyModuleError::TreasuryNotSet)?;
+
+    set_fund(env, get_safety_fund(env) - amount);
+    let remaining = crate::bad_debt::cover_from_safety_module(env, &asset, covered)
+        .map_err(|_| SafetyModuleError::Overflow)?;
+    soroban_sdk::token::Client::new(env, &token).transfer(
+        &env.current_contract_address(),
+        &treasury,
+        &amount,
+    );
+
+    emit_safety_module_slashed(
+        env,
+        SafetyModuleSlashedEvent {
+            asset,
+            amount,
+            covered,
+            treasury,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(remaining)
+}
+
+/// Slash the fund to cover part of an asset's deficit (admin or treasury)
+///
+/// The slashed tokens are sent to the treasury.
+///
+/// # Arguments
+/// * `caller` - The admin or treasury address
+/// * `asset` - The asset whose deficit is covered (None = native)
+/// * `amount` - Protocol tokens to slash
+/// * `covered` - Deficit the slashed tokens cover
+///
+/// # Returns
+/// Returns the asset's remaining bad debt
+///
+/// # Errors
+/// * `SafetyModuleError::Unauthorized` - If caller is neither admin nor treasury
+/// * `SafetyModuleError::InvalidAmount` - If amount or covered is not positive
+/// * `SafetyModuleError::ExceedsBadDebt` - If covered exceeds the asset's bad debt
+/// * `SafetyModuleError::ExceedsSlashCap` - If amount exceeds the slash cap
+/// * `SafetyModuleError::TreasuryNotSet` - If no treasury is configured
+pub fn slash_safety_module(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    amount: i128,
+    covered: i128,
+) -> Result<i128, SafetyModuleError> {
+    require_admin_or_treasury(env, &caller)?;
+    if amount <= 0 || covered <= 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+    if covered > crate::bad_debt::get_bad_debt(env, &asset) {
+        return Err(SafetyModuleError::ExceedsBadDebt);
+    }
+    if amount > get_safety_slash_cap(env) {
+        return Err(SafetyModuleError::ExceedsSlashCap);
+    }
+    execute_slash(env, asset, amount, covered)
+}
+
+/// Slash the fund against an asset's deficit at oracle prices
+///
+/// Callable by anyone while `auto_slash` is enabled. Slashes the protocol
+/// tokens worth the whole deficit, or the slash cap if that is smaller, in
+/// which case the deficit is covered in proportion.
+///
+/// # Returns
+/// Returns the asset's remaining bad debt
+///
+/// # Errors
+/// * `SafetyModuleError::AutoSlashDisabled` - If automatic slashing is off
+/// * `SafetyModuleError::NoDeficit` - If the asset has no bad debt
+/// * `SafetyModuleError::PriceNotAvailable` - If the protocol token has no
+///   positive price feed
+/// * `SafetyModuleError::NothingStaked` - If the fund is empty
+/// * `SafetyModuleError::TreasuryNotSet` - If no treasury is configured
+pub fn trigger_safety_slash(env: &Env, asset: Option<Address>) -> Result<i128, SafetyModuleError> {
+    if !get_safety_module_config(env).auto_slash {
+        return Err(SafetyModuleError::AutoSlashDisabled);
+    }
+    let deficit = crate::bad_debt::get_bad_debt(env, &asset);
+    if deficit <= 0 {
+        return Err(SafetyModuleError::NoDeficit);
+    }
+
+    let feed = env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, PriceFeed>(&OracleDataKey::PriceFeed(protocol_token(env)?))
+        .filter(|feed| feed.price > 0)
+        .ok_or(SafetyModuleError::PriceNotAvailable)?;
+    let deficit_value = crate::bad_debt::value_of(env, &asset, deficit);
+    let needed = deficit_value
+        .checked_mul(10i128.saturating_pow(feed.decimals))
+        .ok_or(SafetyModuleError::Overflow)?
+        .checked_add(feed.price - 1)
+        .ok_or(SafetyModuleError::Overflow)?
+        / feed.price;
+
+    let amount = needed.min(get_safety_slash_cap(env));
+    if amount <= 0 {
+        return Err(SafetyModuleError::NothingStaked);
+    }
+    let covered = if amount == needed {
+        deficit
+    } else {
+        deficit
+            .checked_mul(amount)
+            .ok_or(SafetyModuleError::Overflow)?
+            / needed
+    };
+    if covered <= 0 {
+        return Err(SafetyModuleError::NoDeficit);
+    }
+    execute_slash(env, asset, amount, covered)
+}
+
+/// Pay stakers a yield out of an asset's protocol reserve (admin or treasury)
+///
+/// # Arguments
+/// * `caller` - The admin or treasury address
+/// * `asset` - The reserve asset paid out (None = native)
+/// * `amount` - Amount moved from the reserve to stakers
+///
+/// # Errors
+/// * `SafetyModuleError::Unauthorized` - If caller is neither admin nor treasury
+/// * `SafetyModuleError::InvalidAmount` - If amount is not positive
+/// * `SafetyModuleError::NothingStaked` - If nobody is staked
+/// * `SafetyModuleError::InsufficientReserve` - If the reserve is too small
+/// * `SafetyModuleError::TooManyYieldAssets` - If the asset would exceed
+///   `MAX_YIELD_ASSETS`
+pub fn pay_safety_yield(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(), SafetyModuleError> {
+    require_admin_or_treasury(env, &caller)?;
+    if amount <= 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+    let total_shares = get_safety_total_shares(env);
+    if total_shares == 0 {
+        return Err(SafetyModuleError::NothingStaked);
+    }
+    let reserve = crate::reserve::get_reserve_balance(env, asset.clone());
+    if amount > reserve {
+        return Err(SafetyModuleError::InsufficientReserve);
+    }
+
+    let mut assets = get_yield_assets(env);
+    if !assets.contains(&asset) {
+        if assets.len() >= MAX_YIELD_ASSETS {
+            return Err(SafetyModuleError::TooManyYieldAssets);
+        }
+        assets.push_back(asset.clone());
+        env.storage()
+            .persistent()
+            .set(&SafetyModuleDataKey::YieldAssets, &assets);
+    }
+
+    let index = get_yield_index(env, &asset)
+        .checked_add(
+            amount
+                .checked_mul(YIELD_INDEX_SCALE)
+                .ok_or(SafetyModuleError::Overflow)?
+                / total_shares,
+        )
+        .ok_or(SafetyModuleError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::YieldIndex(asset.clone()), &index);
+    env.storage().persistent().set(
+        &DepositDataKey::ProtocolReserve(asset.clone()),
+        &(reserve - amount),
+    );
+    crate::treasury::record_disbursement(env, &asset, amount);
+
+    emit_safety_yield_paid(
+        env,
+        SafetyYieldPaidEvent {
+            asset,
+            amount,
+            total_shares,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Claim a staker's yield in an asset
+///
+/// # Returns
+/// Returns the amount claimed
+pub fn claim_safety_yield(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+) -> Result<i128, SafetyModuleError> {
+    user.require_auth();
+    let state = settled_yield_state(env, &user, &asset, get_safety_shares(env, &user))?;
+    let amount = state.accrued;
+    env.storage().persistent().set(
+        &SafetyModuleDataKey::StakerYield(user.clone(), asset.clone()),
+        &SafetyYieldState {
+            index: state.index,
+            accrued: 0,
+        },
+    );
+
+    if amount > 0 {
+        if let Some(token_addr) = crate::deposit::resolve_token_address(env, &asset) {
+            soroban_sdk::token::Client::new(env, &token_addr).transfer(
+                &env.current_contract_address(),
+                &user,
+                &amount,
+            );
+        }
+        emit_safety_yield_claimed(
+            env,
+            SafetyYieldClaimedEvent {
+                user,
+                asset,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+    Ok(amount)
+}
@@ -0,0 +1,486 @@
+//! # Savings Rate Module
+//!
+//! Lets holders of the protocol stablecoin (or another admin-approved asset)
+//! park idle balances and earn a governance-set savings rate.
+//!
+//! Each approved asset has a savings market with its own annual rate. Deposits
+//! are tracked as shares of the market; a share index grows at the savings rate
+//! so balances compound every time the market is touched. Withdrawals are
+//! instant.
+//!
+//! ## Funding
+//! Savings interest is paid out of a per-market funding pool and never exceeds
+//! it: when the pool runs dry the index simply stops growing. The pool is
+//! topped up by:
+//! - anyone transferring the asset in with `fund_savings`, and
+//! - for the protocol stablecoin, `harvest_stability_fees`, which mints the CDP
+//!   stability fees repaid since the last harvest into the pool. Fees still
+//!   owed by vaults are not harvested: the stablecoin minted only replaces
+//!   what repayments burned.
+//!
+//! ## Invariants
+//! - `rate_bps` lies in `[0, MAX_SAVINGS_RATE_BPS]`.
+//! - Interest paid never exceeds the market's funding pool.
+//! - Stability fees are harvested at most once, and only once repaid.
+//! - Savings deposits and funding pools are escrowed in `reserve`, so the
+//!   lending pool never lends them out.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+use crate::events::{
+    emit_savings_deposit, emit_savings_funded, emit_savings_rate_updated, emit_savings_withdraw,
+    SavingsDepositEvent, SavingsFundedEvent, SavingsRateUpdatedEvent, SavingsWithdrawEvent,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Seconds per year used for rate accrual
+const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+
+/// Fixed-point scale of the share index (9 decimals)
+pub const SAVINGS_INDEX_SCALE: i128 = 1_000_000_000;
+
+/// Maximum savings rate (20% APR)
+pub const MAX_SAVINGS_RATE_BPS: i128 = 2_000;
+
+/// Errors that can occur during savings operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SavingsError {
+    /// Caller is not authorized (not admin)
+    Unauthorized = 1,
+    /// Amount must be greater than zero
+    InvalidAmount = 2,
+    /// Savings rate is out of range
+    InvalidRate = 3,
+    /// Asset has no savings market
+    MarketNotFound = 4,
+    /// Savings market is closed to new deposits
+    MarketDisabled = 5,
+    /// User savings balance is too low
+    InsufficientBalance = 6,
+    /// The CDP stablecoin is not configured
+    StablecoinNotConfigured = 7,
+    /// Overflow occurred during calculation
+    Overflow = 8,
+}
+
+/// Storage keys for savings data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum SavingsDataKey {
+    /// Savings market of an asset: SavingsMarket
+    SavingsMarket(Address),
+    /// Shares held by a user in a market: SavingsShares(asset, user) -> i128
+    SavingsShares(Address, Address),
+    /// Total CDP stability fees already harvested into savings: i128
+    FeesHarvested,
+}
+
+/// Savings market of one asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavingsMarket {
+    /// Annual savings rate (bps)
+    pub rate_bps: i128,
+    /// Whether new deposits are accepted
+    pub enabled: bool,
+    /// Value of one share (scaled by SAVINGS_INDEX_SCALE)
+    pub index: i128,
+    /// Total shares outstanding
+    pub total_shares: i128,
+    /// Remaining budget for paying savings interest
+    pub funding: i128,
+    /// Last time the index was updated
+    pub last_update: u64,
+}
+
+fn load_market(env: &Env, asset: &Address) -> Result<SavingsMarket, SavingsError> {
+    env.storage()
+        .persistent()
+        .get::<SavingsDataKey, SavingsMarket>(&SavingsDataKey::SavingsMarket(asset.clone()))
+        .ok_or(SavingsError::MarketNotFound)
+}
+
+/// Persist a savings market, escrowing the change in what it holds so the
+/// lending pool does not lend it out
+fn save_market(env: &Env, asset: &Address, market: &SavingsMarket) {
+    let key = SavingsDataKey::SavingsMarket(asset.clone());
+    let previous = env
+        .storage()
+        .persistent()
+        .get::<SavingsDataKey, SavingsMarket>(&key)
+        .map_or(0, |m| market_holdings(&m));
+    crate::reserve::record_escrow_change(
+        env,
+        &Some(asset.clone()),
+        market_holdings(market) - previous,
+    );
+    env.storage().persistent().set(&key, market);
+}
+
+/// Balance held by a savings market: deposits with their credited interest,
+/// plus the funding pool
+fn market_holdings(market: &SavingsMarket) -> i128 {
+    shares_to_amount(market.total_shares, market.index)
+        .unwrap_or(i128::MAX)
+        .saturating_add(market.funding)
+}
+
+fn get_shares(env: &Env, asset: &Address, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SavingsDataKey, i128>(&SavingsDataKey::SavingsShares(asset.clone(), user.clone()))
+        .unwrap_or(0)
+}
+
+fn set_shares(env: &Env, asset: &Address, user: &Address, shares: i128) {
+    let key = SavingsDataKey::SavingsShares(asset.clone(), user.clone());
+    if shares == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &shares);
+    }
+}
+
+fn shares_to_amount(shares: i128, index: i128) -> Result<i128, SavingsError> {
+    shares
+        .checked_mul(index)
+        .ok_or(SavingsError::Overflow)?
+        .checked_div(SAVINGS_INDEX_SCALE)
+        .ok_or(SavingsError::Overflow)
+}
+
+/// Grow the share index at the savings rate, bounded by the funding pool
+fn accrue_market(env: &Env, market: &mut SavingsMarket) -> Result<(), SavingsError> {
+    let now = env.ledger().timestamp();
+    if now <= market.last_update {
+        return Ok(());
+    }
+    let elapsed = (now - market.last_update) as i128;
+    market.last_update = now;
+
+    if market.total_shares == 0 || market.rate_bps == 0 || market.funding == 0 {
+        return Ok(());
+    }
+
+    let deposits = shares_to_amount(market.total_shares, market.index)?;
+    let owed = deposits
+        .checked_mul(market.rate_bps)
+        .ok_or(SavingsError::Overflow)?
+        .checked_mul(elapsed)
+        .ok_or(SavingsError::Overflow)?
+        .checked_div(BASIS_POINTS_SCALE * SECONDS_PER_YEAR)
+        .ok_or(SavingsError::Overflow)?;
+    let interest = owed.min(market.funding);
+    if interest == 0 {
+        return Ok(());
+    }
+
+    let index_growth = interest
+        .checked_mul(SAVINGS_INDEX_SCALE)
+        .ok_or(SavingsError::Overflow)?
+        .checked_div(market.total_shares)
+        .ok_or(SavingsError::Overflow)?;
+    market.index = market
+        .index
+        .checked_add(index_growth)
+        .ok_or(SavingsError::Overflow)?;
+
+    // Only the interest actually credited through the index leaves the pool
+    let credited = index_growth
+        .checked_mul(market.total_shares)
+        .ok_or(SavingsError::Overflow)?
+        / SAVINGS_INDEX_SCALE;
+    market.funding -= credited;
+    Ok(())
+}
+
+/// Create or update the savings market of an asset (admin only)
+///
+/// Interest owed under the previous rate is accrued before the new rate takes
+/// effect. Emits `savings_rate_updated_event` whenever the rate changes.
+///
+/// # Arguments
+/// * `caller` - The caller address (must be admin)
+/// * `asset` - The savings asset
+/// * `rate_bps` - Annual savings rate (0 - MAX_SAVINGS_RATE_BPS)
+/// * `enabled` - Whether new deposits are accepted
+///
+/// # Errors
+/// * `SavingsError::Unauthorized` - If caller is not admin
+/// * `SavingsError::InvalidRate` - If the rate is out of range
+pub fn configure_savings_market(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    rate_bps: i128,
+    enabled: bool,
+) -> Result<(), SavingsError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| SavingsError::Unauthorized)?;
+    if !(0..=MAX_SAVINGS_RATE_BPS).contains(&rate_bps) {
+        return Err(SavingsError::InvalidRate);
+    }
+
+    let now = env.ledger().timestamp();
+    let mut market = match load_market(env, &asset) {
+        Ok(mut market) => {
+            accrue_market(env, &mut market)?;
+            market
+        }
+        Err(_) => SavingsMarket {
+            rate_bps: 0,
+            enabled,
+            index: SAVINGS_INDEX_SCALE,
+            total_shares: 0,
+            funding: 0,
+            last_update: now,
+        },
+    };
+
+    let old_rate_bps = market.rate_bps;
+    market.rate_bps = rate_bps;
+    market.enabled = enabled;
+    save_market(env, &asset, &market);
+
+    if old_rate_bps != rate_bps {
+        emit_savings_rate_updated(
+            env,
+            SavingsRateUpdatedEvent {
+                asset,
+                old_rate_bps,
+                new_rate_bps: rate_bps,
+                timestamp: now,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Deposit into the savings market of an asset
+///
+/// # Returns
+/// Returns the user's savings balance after the deposit
+pub fn savings_deposit(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    user.require_auth();
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let mut market = load_market(env, &asset)?;
+    if !market.enabled {
+        return Err(SavingsError::MarketDisabled);
+    }
+    accrue_market(env, &mut market)?;
+
+    let shares = amount
+        .checked_mul(SAVINGS_INDEX_SCALE)
+        .ok_or(SavingsError::Overflow)?
+        .checked_div(market.index)
+        .ok_or(SavingsError::Overflow)?;
+    if shares == 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let token_client = soroban_sdk::token::Client::new(env, &asset);
+    token_client.transfer(&user, env.current_contract_address(), &amount);
+
+    market.total_shares = market
+        .total_shares
+        .checked_add(shares)
+        .ok_or(SavingsError::Overflow)?;
+    save_market(env, &asset, &market);
+
+    let user_shares = get_shares(env, &asset, &user)
+        .checked_add(shares)
+        .ok_or(SavingsError::Overflow)?;
+    set_shares(env, &asset, &user, user_shares);
+
+    emit_savings_deposit(
+        env,
+        SavingsDepositEvent {
+            user,
+            asset,
+            amount,
+            shares,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    shares_to_amount(user_shares, market.index)
+}
+
+/// Withdraw from the savings market of an asset
+///
+/// Withdrawals are instant and always allowed, even when the market is
+/// closed to new deposits.
+///
+/// # Returns
+/// Returns the user's remaining savings balance
+pub fn savings_withdraw(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    user.require_auth();
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let mut market = load_market(env, &asset)?;
+    accrue_market(env, &mut market)?;
+
+    let user_shares = get_shares(env, &asset, &user);
+    let balance = shares_to_amount(user_shares, market.index)?;
+    if amount > balance {
+        return Err(SavingsError::InsufficientBalance);
+    }
+
+    // Round shares up so withdrawals never take more than they burn
+    let shares = if amount == balance {
+        user_shares
+    } else {
+        let scaled = amount
+            .checked_mul(SAVINGS_INDEX_SCALE)
+            .ok_or(SavingsError::Overflow)?;
+        (scaled + market.index - 1) / market.index
+    };
+
+    market.total_shares -= shares;
+    save_market(env, &asset, &market);
+    let remaining_shares = user_shares - shares;
+    set_shares(env, &asset, &user, remaining_shares);
+
+    let token_client = soroban_sdk::token::Client::new(env, &asset);
+    token_client.transfer(&env.current_contract_address(), &user, &amount);
+
+    emit_savings_withdraw(
+        env,
+        SavingsWithdrawEvent {
+            user,
+            asset,
+            amount,
+            shares,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    shares_to_amount(remaining_shares, market.index)
+}
+
+/// Top up the interest funding pool of a savings market
+///
+/// # Returns
+/// Returns the market's funding pool after the top-up
+pub fn fund_savings(
+    env: &Env,
+    funder: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    funder.require_auth();
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let mut market = load_market(env, &asset)?;
+    accrue_market(env, &mut market)?;
+
+    let token_client = soroban_sdk::token::Client::new(env, &asset);
+    token_client.transfer(&funder, env.current_contract_address(), &amount);
+
+    credit_funding(env, &asset, &mut market, funder, amount)
+}
+
+fn credit_funding(
+    env: &Env,
+    asset: &Address,
+    market: &mut SavingsMarket,
+    source: Address,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    market.funding = market
+        .funding
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    save_market(env, asset, market);
+
+    emit_savings_funded(
+        env,
+        SavingsFundedEvent {
+            asset: asset.clone(),
+            source,
+            amount,
+            funding: market.funding,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(market.funding)
+}
+
+/// Mint CDP stability fees collected since the last harvest into the
+/// stablecoin savings pool
+///
+/// Anyone can call this.
+///
+/// # Returns
+/// Returns the amount harvested
+pub fn harvest_stability_fees(env: &Env) -> Result<i128, SavingsError> {
+    let stablecoin = crate::cdp::get_cdp_config(env)
+        .ok_or(SavingsError::StablecoinNotConfigured)?
+        .stablecoin;
+    let mut market = load_market(env, &stablecoin)?;
+    accrue_market(env, &mut market)?;
+
+    let harvested = env
+        .storage()
+        .persistent()
+        .get::<SavingsDataKey, i128>(&SavingsDataKey::FeesHarvested)
+        .unwrap_or(0);
+    let fees = crate::cdp::get_cdp_fees_collected(env);
+    let amount = fees - harvested;
+    if amount <= 0 {
+        return Ok(0);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&SavingsDataKey::FeesHarvested, &fees);
+    soroban_sdk::token::StellarAssetClient::new(env, &stablecoin)
+        .mint(&env.current_contract_address(), &amount);
+
+    credit_funding(
+        env,
+        &stablecoin,
+        &mut market,
+        env.current_contract_address(),
+        amount,
+    )?;
+    Ok(amount)
+}
+
+/// Get the savings market of an asset with interest accrued up to now
+pub fn get_savings_market(env: &Env, asset: &Address) -> Option<SavingsMarket> {
+    let mut market = load_market(env, asset).ok()?;
+    accrue_market(env, &mut market).ok()?;
+    Some(market)
+}
+
+/// Get a user's savings balance including accrued interest
+pub fn get_savings_balance(env: &Env, asset: &Address, user: &Address) -> i128 {
+    get_savings_market(env, asset)
+        .and_then(|market| shares_to_amount(get_shares(env, asset, user), market.index).ok())
+        .unwrap_or(0)
+}
@@ -0,0 +1,372 @@
+//! # Shadow Oracle Module
+//!
+//! Validates a new price feed in production before it is promoted. The admin
+//! registers a "shadow" oracle per asset; its prices are logged and compared
+//! against the primary feed but never read by the protocol, so a bad shadow
+//! feed cannot affect borrowing, liquidations or any other decision.
+//!
+//! Each `submit_shadow_price` is compared against the asset's stored primary
+//! `PriceFeed` (normalized to the primary's decimals) and emits a
+//! `shadow_price_observed_event` with the deviation. A deviation above the
+//! oracle config's `max_deviation_bps` counts as a breach.
+//!
+//! ## Stats
+//! Per asset, lifetime sample and breach counts are kept alongside the last
+//! `SHADOW_WINDOW_SIZE` observations, from which `get_shadow_oracle_stats`
+//! derives the rolling average and maximum deviation.
+//!
+//! ## Invariants
+//! - Only the registered shadow oracle of an asset may submit its prices.
+//! - Changing or removing the shadow oracle resets the asset's stats.
+//! - `promote_shadow_oracle` makes the shadow the primary oracle and clears
+//!   the shadow slot; it is the only path from shadow to protocol decisions.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::events::{emit_shadow_price_observed, ShadowPriceObservedEvent};
+use crate::oracle::{OracleDataKey, PriceFeed};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Observations kept per asset for rolling stats
+pub const SHADOW_WINDOW_SIZE: u32 = 32;
+
+/// Errors that can occur during shadow oracle operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ShadowOracleError {
+    /// Caller is not authorized (not admin or not the shadow oracle)
+    Unauthorized = 1,
+    /// Oracle address is invalid
+    InvalidOracle = 2,
+    /// Price must be greater than zero
+    InvalidPrice = 3,
+    /// Asset has no shadow oracle
+    ShadowNotConfigured = 4,
+    /// Asset has no primary price to compare against
+    PrimaryPriceUnavailable = 5,
+    /// Overflow occurred during calculation
+    Overflow = 6,
+}
+
+/// Storage keys for shadow oracle data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum ShadowOracleDataKey {
+    /// Shadow oracle of an asset: Address
+    ShadowOracle(Address),
+    /// Lifetime counters of an asset's shadow feed: ShadowCounters
+    DeviationCounters(Address),
+    /// Most recent observations of an asset's shadow feed: Vec<ShadowObservation>
+    RecentObservations(Address),
+}
+
+/// A shadow price compared against the primary
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShadowObservation {
+    /// Shadow price, normalized to the primary's decimals
+    pub shadow_price: i128,
+    /// Primary price at the time of the observation
+    pub primary_price: i128,
+    /// Absolute deviation from the primary (basis points)
+    pub deviation_bps: i128,
+    /// Observation timestamp
+    pub timestamp: u64,
+}
+
+/// Lifetime counters of a shadow feed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShadowCounters {
+    /// Observations since the shadow oracle was registered
+    pub samples: u64,
+    /// Observations above the max deviation
+    pub breaches: u64,
+}
+
+/// Rolling comparison stats of a shadow feed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShadowOracleStats {
+    /// Asset being priced
+    pub asset: Address,
+    /// Registered shadow oracle
+    pub shadow_oracle: Address,
+    /// Observations since the shadow oracle was registered
+    pub samples: u64,
+    /// Observations above the max deviation
+    pub breaches: u64,
+    /// Observations in the rolling window
+    pub window_samples: u32,
+    /// Average deviation over the rolling window (basis points)
+    pub window_avg_deviation_bps: i128,
+    /// Maximum deviation over the rolling window (basis points)
+    pub window_max_deviation_bps: i128,
+    /// Latest observation: empty before the first submission, otherwise one entry
+    pub last_observation: Vec<ShadowObservation>,
+}
+
+/// Get the shadow oracle of an asset
+pub fn get_shadow_oracle(env: &Env, asset: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<ShadowOracleDataKey, Address>(&ShadowOracleDataKey::ShadowOracle(asset.clone()))
+}
+
+fn get_counters(env: &Env, asset: &Address) -> ShadowCounters {
+    env.storage()
+        .persistent()
+        .get::<ShadowOracleDataKey, ShadowCounters>(&ShadowOracleDataKey::DeviationCounters(
+            asset.clone(),
+        ))
+        .unwrap_or(ShadowCounters {
+            samples: 0,
+            breaches: 0,
+        })
+}
+
+fn get_observations(env: &Env, asset: &Address) -> Vec<ShadowObservation> {
+    env.storage()
+        .persistent()
+        .get::<ShadowOracleDataKey, Vec<ShadowObservation>>(
+            &ShadowOracleDataKey::RecentObservations(asset.clone()),
+        )
+        .unwrap_or(Vec::new(env))
+}
+
+fn clear_stats(env: &Env, asset: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&ShadowOracleDataKey::DeviationCounters(asset.clone()));
+    env.storage()
+        .persistent()
+        .remove(&ShadowOracleDataKey::RecentObservations(asset.clone()));
+}
+
+/// Register or replace the shadow oracle of an asset (admin only)
+///
+/// # Arguments
+/// * `caller` - The caller address (must be admin)
+/// * `asset` - The asset address
+/// * `shadow_oracle` - The oracle to validate
+pub fn set_shadow_oracle(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    shadow_oracle: Address,
+) -> Result<(), ShadowOracleError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| ShadowOracleError::Unauthorized)?;
+    if shadow_oracle == env.current_contract_address() {
+        return Err(ShadowOracleError::InvalidOracle);
+    }
+
+    clear_stats(env, &asset);
+    env.storage().persistent().set(
+        &ShadowOracleDataKey::ShadowOracle(asset.clone()),
+        &shadow_oracle,
+    );
+    Ok(())
+}
+
+/// Remove the shadow oracle of an asset and its stats (admin only)
+pub fn remove_shadow_oracle(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+) -> Result<(), ShadowOracleError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| ShadowOracleError::Unauthorized)?;
+    get_shadow_oracle(env, &asset).ok_or(ShadowOracleError::ShadowNotConfigured)?;
+
+    clear_stats(env, &asset);
+    env.storage()
+        .persistent()
+        .remove(&ShadowOracleDataKey::ShadowOracle(asset));
+    Ok(())
+}
+
+/// Promote the shadow oracle of an asset to primary oracle (admin only)
+///
+/// Clears the shadow slot and its stats. Prices from the promoted oracle go
+/// through `update_price_feed` from then on.
+pub fn promote_shadow_oracle(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+) -> Result<Address, ShadowOracleError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| ShadowOracleError::Unauthorized)?;
+    let shadow_oracle =
+        get_shadow_oracle(env, &asset).ok_or(ShadowOracleError::ShadowNotConfigured)?;
+
+    crate::oracle::set_primary_oracle(env, caller, asset.clone(), shadow_oracle.clone())
+        .map_err(|_| ShadowOracleError::Unauthorized)?;
+    clear_stats(env, &asset);
+    env.storage()
+        .persistent()
+        .remove(&ShadowOracleDataKey::ShadowOracle(asset));
+    Ok(shadow_oracle)
+}
+
+/// Submit a shadow price for comparison (shadow oracle only)
+///
+/// The price is logged and compared against the primary feed; it is never
+/// used for protocol decisions.
+///
+/// # Arguments
+/// * `oracle` - The shadow oracle of the asset
+/// * `asset` - The asset address
+/// * `price` - The shadow price
+/// * `decimals` - Decimals of `price`
+///
+/// # Returns
+/// Returns the deviation from the primary price in basis points
+pub fn submit_shadow_price(
+    env: &Env,
+    oracle: Address,
+    asset: Address,
+    price: i128,
+    decimals: u32,
+) -> Result<i128, ShadowOracleError> {
+    oracle.require_auth();
+    let shadow_oracle =
+        get_shadow_oracle(env, &asset).ok_or(ShadowOracleError::ShadowNotConfigured)?;
+    if oracle != shadow_oracle {
+        return Err(ShadowOracleError::Unauthorized);
+    }
+    if price <= 0 {
+        return Err(ShadowOracleError::InvalidPrice);
+    }
+
+    let primary = env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, PriceFeed>(&OracleDataKey::PriceFeed(asset.clone()))
+        .ok_or(ShadowOracleError::PrimaryPriceUnavailable)?;
+    if primary.price <= 0 {
+        return Err(ShadowOracleError::PrimaryPriceUnavailable);
+    }
+
+    let shadow_price = normalize_price(price, decimals, primary.decimals)?;
+    if shadow_price <= 0 {
+        return Err(ShadowOracleError::InvalidPrice);
+    }
+    let deviation_bps = (shadow_price - primary.price)
+        .abs()
+        .checked_mul(BASIS_POINTS_SCALE)
+        .ok_or(ShadowOracleError::Overflow)?
+        / primary.price;
+    let breached = deviation_bps > crate::oracle::get_oracle_config(env).max_deviation_bps;
+    let timestamp = env.ledger().timestamp();
+
+    let mut counters = get_counters(env, &asset);
+    counters.samples += 1;
+    if breached {
+        counters.breaches += 1;
+    }
+    env.storage().persistent().set(
+        &ShadowOracleDataKey::DeviationCounters(asset.clone()),
+        &counters,
+    );
+
+    let mut observations = get_observations(env, &asset);
+    if observations.len() >= SHADOW_WINDOW_SIZE {
+        observations.pop_front();
+    }
+    observations.push_back(ShadowObservation {
+        shadow_price,
+        primary_price: primary.price,
+        deviation_bps,
+        timestamp,
+    });
+    env.storage().persistent().set(
+        &ShadowOracleDataKey::RecentObservations(asset.clone()),
+        &observations,
+    );
+
+    emit_shadow_price_observed(
+        env,
+        ShadowPriceObservedEvent {
+            asset,
+            shadow_oracle,
+            shadow_price,
+            primary_price: primary.price,
+            deviation_bps,
+            breached,
+            timestamp,
+        },
+    );
+
+    Ok(deviation_bps)
+}
+
+/// Rescale a price from `from_decimals` to `to_decimals`
+fn normalize_price(
+    price: i128,
+    from_decimals: u32,
+    to_decimals: u32,
+) -> Result<i128, ShadowOracleError> {
+    if from_decimals == to_decimals {
+        return Ok(price);
+    }
+    if from_decimals < to_decimals {
+        let factor = 10i128
+            .checked_pow(to_decimals - from_decimals)
+            .ok_or(ShadowOracleError::Overflow)?;
+        price.checked_mul(factor).ok_or(ShadowOracleError::Overflow)
+    } else {
+        let factor = 10i128
+            .checked_pow(from_decimals - to_decimals)
+            .ok_or(ShadowOracleError::Overflow)?;
+        Ok(price / factor)
+    }
+}
+
+/// Get the rolling comparison stats of an asset's shadow feed
+///
+/// # Errors
+/// * `ShadowOracleError::ShadowNotConfigured` - If the asset has no shadow oracle
+pub fn get_shadow_oracle_stats(
+    env: &Env,
+    asset: Address,
+) -> Result<ShadowOracleStats, ShadowOracleError> {
+    let shadow_oracle =
+        get_shadow_oracle(env, &asset).ok_or(ShadowOracleError::ShadowNotConfigured)?;
+    let counters = get_counters(env, &asset);
+    let observations = get_observations(env, &asset);
+
+    let mut total_deviation: i128 = 0;
+    let mut max_deviation: i128 = 0;
+    for observation in observations.iter() {
+        total_deviation = total_deviation.saturating_add(observation.deviation_bps);
+        max_deviation = max_deviation.max(observation.deviation_bps);
+    }
+    let window_samples = observations.len();
+    let window_avg_deviation_bps = if window_samples > 0 {
+        total_deviation / window_samples as i128
+    } else {
+        0
+    };
+
+    let mut last_observation = Vec::new(env);
+    if let Some(observation) = observations.last() {
+        last_observation.push_back(observation);
+    }
+
+    Ok(ShadowOracleStats {
+        asset,
+        shadow_oracle,
+        samples: counters.samples,
+        breaches: counters.breaches,
+        window_samples,
+        window_avg_deviation_bps,
+        window_max_deviation_bps: max_deviation,
+        last_observation,
+    })
+}
@@ -0,0 +1,243 @@
+//! # Staking Module
+//!
+//! Vote-escrow staking of the governance token (the `vote_token` set by
+//! `gov_initialize`) to boost lending rewards. A user locks tokens until an
+//! unlock time of their choice, at most `MAX_LOCK_DURATION` ahead, and
+//! receives a stake weight proportional to both the amount and the lock
+//! duration:
+//!
+//! `weight = amount * (unlock_time - now) / MAX_LOCK_DURATION`
+//!
+//! The weight is fixed when the lock is created or extended and stops
+//! counting towards boosts once the unlock time has passed.
+//!
+//! ## Boost
+//! The weight raises the user's reward accrual in every reserve by up to
+//! `MAX_BOOST_BPS` (2.5x); see `rewards` for how the multiplier is derived.
+//! Boosts are recomputed whenever the user's position changes, and in every
+//! reserve when the user locks or unlocks.
+//!
+//! ## Invariants
+//! - Locked tokens cannot be withdrawn before the unlock time.
+//! - A lock can only be topped up or extended, never shortened.
+//! - The total weight is the sum of the weights of all existing locks.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+use crate::events::{emit_stake_locked, emit_stake_unlocked, StakeLockedEvent, StakeUnlockedEvent};
+use crate::storage::GovernanceDataKey;
+use crate::types::GovernanceConfig;
+
+/// Longest lock, giving a weight equal to the amount locked (4 years)
+pub const MAX_LOCK_DURATION: u64 = 4 * 365 * 24 * 60 * 60;
+
+/// Largest reward boost (2.5x, in basis points)
+pub const MAX_BOOST_BPS: i128 = 25_000;
+
+/// Errors that can occur during staking operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StakingError {
+    /// Governance, and with it the governance token, is not initialized
+    GovernanceNotInitialized = 1,
+    /// Amount must not be negative, and positive for a new lock
+    InvalidAmount = 2,
+    /// Unlock time must be in the future, within `MAX_LOCK_DURATION`,
+    /// and not before the current unlock time
+    InvalidUnlockTime = 3,
+    /// The user has no lock
+    NoStake = 4,
+    /// The lock has not reached its unlock time
+    StakeLocked = 5,
+    /// Arithmetic overflow occurred
+    Overflow = 6,
+}
+
+/// A user's locked stake
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeLock {
+    /// Governance tokens locked
+    pub amount: i128,
+    /// Timestamp the tokens can be withdrawn from
+    pub unlock_time: u64,
+    /// Stake weight, fixed when the lock was last changed
+    pub weight: i128,
+}
+
+/// Storage keys for staking data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum StakingDataKey {
+    /// A user's locked stake: StakeLock
+    UserStake(Address),
+    /// Sum of the weights of all locks: i128
+    TotalStakeWeight,
+}
+
+fn governance_token(env: &Env) -> Result<Address, StakingError> {
+    env.storage()
+        .instance()
+        .get::<GovernanceDataKey, GovernanceConfig>(&GovernanceDataKey::Config)
+        .map(|config| config.vote_token)
+        .ok_or(StakingError::GovernanceNotInitialized)
+}
+
+/// Get a user's locked stake
+pub fn get_stake(env: &Env, user: &Address) -> Option<StakeLock> {
+    env.storage()
+        .persistent()
+        .get::<StakingDataKey, StakeLock>(&StakingDataKey::UserStake(user.clone()))
+}
+
+/// Get the sum of the weights of all locks
+pub fn get_total_stake_weight(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<StakingDataKey, i128>(&StakingDataKey::TotalStakeWeight)
+        .unwrap_or(0)
+}
+
+/// Weight of a user's lock that currently boosts rewards
+pub(crate) fn boosting_weight(env: &Env, user: &Address) -> i128 {
+    match get_stake(env, user) {
+        Some(stake) if stake.unlock_time > env.ledger().timestamp() => stake.weight,
+        _ => 0,
+    }
+}
+
+fn set_total_stake_weight(env: &Env, weight: i128) {
+    env.storage()
+        .persistent()
+        .set(&StakingDataKey::TotalStakeWeight, &weight.max(0));
+}
+
+/// Lock governance tokens, or top up and extend an existing lock
+///
+/// The weight of the whole lock is recomputed from the new amount and the
+/// time left until the new unlock time.
+///
+/// # Arguments
+/// * `user` - The staker (must authorize)
+/// * `amount` - Governance tokens to add to the lock
+/// * `unlock_time` - Timestamp the lock ends at
+///
+/// # Returns
+/// Returns the updated lock
+///
+/// # Errors
+/// * `StakingError::GovernanceNotInitialized` - If there is no governance token
+/// * `StakingError::InvalidAmount` - If the amount is negative, or zero for a new lock
+/// * `StakingError::InvalidUnlockTime` - If the unlock time is out of range or
+///   earlier than the current one
+/// * `StakingError::Overflow` - If the locked amount overflows
+pub fn lock_stake(
+    env: &Env,
+    user: Address,
+    amount: i128,
+    unlock_time: u64,
+) -> Result<StakeLock, StakingError> {
+    user.require_auth();
+    let token = governance_token(env)?;
+
+    let existing = get_stake(env, &user);
+    if amount < 0 || (amount == 0 && existing.is_none()) {
+        return Err(StakingError::InvalidAmount);
+    }
+    let now = env.ledger().timestamp();
+    let current_unlock = existing
+        .as_ref()
+        .map(|stake| stake.unlock_time)
+        .unwrap_or(0);
+    if unlock_time <= now || unlock_time - now > MAX_LOCK_DURATION || unlock_time < current_unlock {
+        return Err(StakingError::InvalidUnlockTime);
+    }
+
+    let (locked, previous_weight) = existing
+        .map(|stake| (stake.amount, stake.weight))
+        .unwrap_or((0, 0));
+    let total_amount = locked.checked_add(amount).ok_or(StakingError::Overflow)?;
+    let weight = total_amount
+        .checked_mul((unlock_time - now) as i128)
+        .ok_or(StakingError::Overflow)?
+        / MAX_LOCK_DURATION as i128;
+
+    if amount > 0 {
+        soroban_sdk::token::Client::new(env, &token).transfer(
+            &user,
+            env.current_contract_address(),
+            &amount,
+        );
+    }
+
+    let stake = StakeLock {
+        amount: total_amount,
+        unlock_time,
+        weight,
+    };
+    env.storage()
+        .persistent()
+        .set(&StakingDataKey::UserStake(user.clone()), &stake);
+    set_total_stake_weight(env, get_total_stake_weight(env) - previous_weight + weight);
+    crate::rewards::refresh_boosts(env, &user);
+
+    emit_stake_locked(
+        env,
+        StakeLockedEvent {
+            user,
+            amount,
+            total_amount,
+            unlock_time,
+            weight,
+            timestamp: now,
+        },
+    );
+    Ok(stake)
+}
+
+/// Withdraw a user's locked tokens once the unlock time has passed
+///
+/// # Arguments
+/// * `user` - The staker (must authorize)
+///
+/// # Returns
+/// Returns the amount of governance tokens returned
+///
+/// # Errors
+/// * `StakingError::GovernanceNotInitialized` - If there is no governance token
+/// * `StakingError::NoStake` - If the user has no lock
+/// * `StakingError::StakeLocked` - If the unlock time has not passed
+pub fn unlock_stake(env: &Env, user: Address) -> Result<i128, StakingError> {
+    user.require_auth();
+    let token = governance_token(env)?;
+
+    let stake = get_stake(env, &user).ok_or(StakingError::NoStake)?;
+    if env.ledger().timestamp() < stake.unlock_time {
+        return Err(StakingError::StakeLocked);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&StakingDataKey::UserStake(user.clone()));
+    set_total_stake_weight(env, get_total_stake_weight(env) - stake.weight);
+    crate::rewards::refresh_boosts(env, &user);
+
+    soroban_sdk::token::Client::new(env, &token).transfer(
+        &env.current_contract_address(),
+        &user,
+        &stake.amount,
+    );
+
+    emit_stake_unlocked(
+        env,
+        StakeUnlockedEvent {
+            user,
+            amount: stake.amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(stake.amount)
+}
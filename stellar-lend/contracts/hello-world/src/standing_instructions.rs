@@ -0,0 +1,375 @@
+//! # Standing Instructions Module
+//!
+//! Lets users register standing instructions on their position, such as
+//! "repay 100 USDC from my wallet every 7 days" or "top up collateral if the
+//! health factor drops below 1.1". Any keeper can execute an instruction
+//! once its trigger is met and earns the bounty the user attached to it.
+//!
+//! ## Instructions
+//! An instruction deposits or repays (`OP_DEPOSIT` or `OP_REPAY`) a fixed
+//! amount of one asset, up to a fixed number of times. Funds move as for
+//! permits: the amount and the bounty are pulled from the user's wallet
+//! through their token allowance to this contract, so the user must keep an
+//! allowance covering both.
+//!
+//! ## Triggers
+//! - `Every(period)`: executable once per `period` seconds, the first time
+//!   one period after registration.
+//! - `HealthFactorBelow(threshold)`: executable whenever the health factor of
+//!   the user's position is below `threshold` (scaled by 10000).
+//!
+//! ## Invariants
+//! - Only the owner can register or cancel their instructions.
+//! - An instruction executes at most `executions_left` times and is removed
+//!   after its last execution.
+//! - The bounty is paid in the instruction's asset, and only when the action
+//!   itself succeeds.
+//! - A user has at most `MAX_INSTRUCTIONS_PER_USER` instructions.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::events::{
+    emit_instruction_cancelled, emit_instruction_created, emit_instruction_executed,
+    InstructionCancelledEvent, InstructionCreatedEvent, InstructionExecutedEvent,
+};
+use crate::operators::{OP_DEPOSIT, OP_REPAY};
+
+/// Maximum number of instructions a user can have
+pub const MAX_INSTRUCTIONS_PER_USER: u32 = 10;
+
+/// Errors that can occur during standing instruction operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StandingInstructionError {
+    /// Operation is not a single deposit or repay (`OP_*` flag)
+    InvalidOperation = 1,
+    /// Amount must be greater than zero and the bounty not negative
+    InvalidAmount = 2,
+    /// Trigger period or threshold is not positive
+    InvalidTrigger = 3,
+    /// Executions must be greater than zero
+    InvalidExecutions = 4,
+    /// User already has the maximum number of instructions
+    TooManyInstructions = 5,
+    /// No instruction with this id
+    InstructionNotFound = 6,
+    /// Caller does not own the instruction
+    Unauthorized = 7,
+    /// The instruction's trigger is not met
+    TriggerNotMet = 8,
+    /// The deposit or repayment was rejected by the protocol
+    OperationFailed = 9,
+}
+
+/// Storage keys for standing instruction data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum StandingInstructionDataKey {
+    /// An instruction by id: StandingInstruction
+    Instruction(u64),
+    /// Ids of a user's instructions: Vec<u64>
+    UserInstructions(Address),
+    /// Next instruction id: u64
+    NextInstructionId,
+}
+
+/// Condition under which an instruction may be executed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InstructionTrigger {
+    /// Once per period, in seconds
+    Every(u64),
+    /// While the health factor (scaled by 10000) is below the threshold
+    HealthFactorBelow(i128),
+}
+
+/// A standing instruction on a user's position
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingInstruction {
+    /// Instruction id
+    pub id: u64,
+    /// The position owner
+    pub owner: Address,
+    /// `OP_DEPOSIT` or `OP_REPAY`
+    pub operation: u32,
+    /// The asset (None for native XLM)
+    pub asset: Option<Address>,
+    /// Amount deposited or repaid per execution
+    pub amount: i128,
+    /// When the instruction may be executed
+    pub trigger: InstructionTrigger,
+    /// Amount of the asset paid to the keeper per execution
+    pub bounty: i128,
+    /// Executions remaining
+    pub executions_left: u32,
+    /// Earliest timestamp of the next execution
+    pub next_execution: u64,
+}
+
+fn get_user_instruction_ids(env: &Env, user: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get::<StandingInstructionDataKey, Vec<u64>>(&StandingInstructionDataKey::UserInstructions(
+            user.clone(),
+        ))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Remove an instruction and its entry in the owner's list
+fn remove_instruction(env: &Env, instruction: &StandingInstruction) {
+    let mut ids = get_user_instruction_ids(env, &instruction.owner);
+    if let Some(index) = ids.first_index_of(instruction.id) {
+        ids.remove(index);
+    }
+    env.storage().persistent().set(
+        &StandingInstructionDataKey::UserInstructions(instruction.owner.clone()),
+        &ids,
+    );
+    env.storage()
+        .persistent()
+        .remove(&StandingInstructionDataKey::Instruction(instruction.id));
+}
+
+/// Get an instruction by id
+pub fn get_standing_instruction(env: &Env, id: u64) -> Option<StandingInstruction> {
+    env.storage()
+        .persistent()
+        .get::<StandingInstructionDataKey, StandingInstruction>(
+            &StandingInstructionDataKey::Instruction(id),
+        )
+}
+
+/// Get all instructions of a user
+pub fn get_standing_instructions(env: &Env, user: &Address) -> Vec<StandingInstruction> {
+    let mut instructions = Vec::new(env);
+    for id in get_user_instruction_ids(env, user).iter() {
+        if let Some(instruction) = get_standing_instruction(env, id) {
+            instructions.push_back(instruction);
+        }
+    }
+    instructions
+}
+
+/// Register a standing instruction
+///
+/// # Arguments
+/// * `owner` - The position owner (must authorize)
+/// * `operation` - `OP_DEPOSIT` or `OP_REPAY`
+/// * `asset` - The asset (None for native XLM)
+/// * `amount` - Amount deposited or repaid per execution
+/// * `trigger` - When the instruction may be executed
+/// * `bounty` - Amount of the asset paid to the keeper per execution
+/// * `executions` - Number of times the instruction may be executed
+///
+/// # Returns
+/// The id of the new instruction
+///
+/// # Errors
+/// * `StandingInstructionError::InvalidOperation` - If the operation is not supported
+/// * `StandingInstructionError::InvalidAmount` - If the amount is not positive or the bounty negative
+/// * `StandingInstructionError::InvalidTrigger` - If the period or threshold is not positive
+/// * `StandingInstructionError::InvalidExecutions` - If `executions` is zero
+/// * `StandingInstructionError::TooManyInstructions` - If the user has no slot left
+pub fn create_standing_instruction(
+    env: &Env,
+    owner: Address,
+    operation: u32,
+    asset: Option<Address>,
+    amount: i128,
+    trigger: InstructionTrigger,
+    bounty: i128,
+    executions: u32,
+) -> Result<u64, StandingInstructionError> {
+    owner.require_auth();
+
+    if operation != OP_DEPOSIT && operation != OP_REPAY {
+        return Err(StandingInstructionError::InvalidOperation);
+    }
+    if amount <= 0 || bounty < 0 {
+        return Err(StandingInstructionError::InvalidAmount);
+    }
+    let timestamp = env.ledger().timestamp();
+    let next_execution = match trigger {
+        InstructionTrigger::Every(period) if period > 0 => timestamp
+            .checked_add(period)
+            .ok_or(StandingInstructionError::InvalidTrigger)?,
+        InstructionTrigger::HealthFactorBelow(threshold) if threshold > 0 => timestamp,
+        _ => return Err(StandingInstructionError::InvalidTrigger),
+    };
+    if executions == 0 {
+        return Err(StandingInstructionError::InvalidExecutions);
+    }
+
+    let mut ids = get_user_instruction_ids(env, &owner);
+    if ids.len() >= MAX_INSTRUCTIONS_PER_USER {
+        return Err(StandingInstructionError::TooManyInstructions);
+    }
+
+    let id = env
+        .storage()
+        .persistent()
+        .get::<StandingInstructionDataKey, u64>(&StandingInstructionDataKey::NextInstructionId)
+        .unwrap_or(1);
+    env.storage()
+        .persistent()
+        .set(&StandingInstructionDataKey::NextInstructionId, &(id + 1));
+
+    let instruction = StandingInstruction {
+        id,
+        owner: owner.clone(),
+        operation,
+        asset: asset.clone(),
+        amount,
+        trigger,
+        bounty,
+        executions_left: executions,
+        next_execution,
+    };
+    env.storage()
+        .persistent()
+        .set(&StandingInstructionDataKey::Instruction(id), &instruction);
+    ids.push_back(id);
+    env.storage().persistent().set(
+        &StandingInstructionDataKey::UserInstructions(owner.clone()),
+        &ids,
+    );
+
+    emit_instruction_created(
+        env,
+        InstructionCreatedEvent {
+            id,
+            owner,
+            operation,
+            asset,
+            amount,
+            bounty,
+            executions,
+            timestamp,
+        },
+    );
+    Ok(id)
+}
+
+/// Cancel a standing instruction
+///
+/// # Arguments
+/// * `owner` - The instruction owner (must authorize)
+/// * `id` - The instruction id
+///
+/// # Errors
+/// * `StandingInstructionError::InstructionNotFound` - If the instruction does not exist
+/// * `StandingInstructionError::Unauthorized` - If `owner` does not own it
+pub fn cancel_standing_instruction(
+    env: &Env,
+    owner: Address,
+    id: u64,
+) -> Result<(), StandingInstructionError> {
+    owner.require_auth();
+
+    let instruction =
+        get_standing_instruction(env, id).ok_or(StandingInstructionError::InstructionNotFound)?;
+    if instruction.owner != owner {
+        return Err(StandingInstructionError::Unauthorized);
+    }
+    remove_instruction(env, &instruction);
+
+    emit_instruction_cancelled(
+        env,
+        InstructionCancelledEvent {
+            id,
+            owner,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Execute a standing instruction whose trigger is met (any keeper)
+///
+/// # Arguments
+/// * `keeper` - The executing account (must authorize), paid the bounty
+/// * `id` - The instruction id
+///
+/// # Returns
+/// The user's collateral after a deposit, or the remaining debt after a
+/// repayment
+///
+/// # Errors
+/// * `StandingInstructionError::InstructionNotFound` - If the instruction does not exist
+/// * `StandingInstructionError::TriggerNotMet` - If the trigger is not met
+/// * `StandingInstructionError::OperationFailed` - If the deposit or repayment fails
+pub fn execute_standing_instruction(
+    env: &Env,
+    keeper: Address,
+    id: u64,
+) -> Result<i128, StandingInstructionError> {
+    keeper.require_auth();
+
+    let mut instruction =
+        get_standing_instruction(env, id).ok_or(StandingInstructionError::InstructionNotFound)?;
+    let timestamp = env.ledger().timestamp();
+    if timestamp < instruction.next_execution {
+        return Err(StandingInstructionError::TriggerNotMet);
+    }
+    let owner = instruction.owner.clone();
+    match instruction.trigger {
+        InstructionTrigger::Every(period) => {
+            instruction.next_execution = timestamp.saturating_add(period);
+        }
+        InstructionTrigger::HealthFactorBelow(threshold) => {
+            let health_factor = crate::analytics::get_account_data(env, &owner)
+                .map_err(|_| StandingInstructionError::TriggerNotMet)?
+                .health_factor;
+            if health_factor >= threshold {
+                return Err(StandingInstructionError::TriggerNotMet);
+            }
+        }
+    }
+
+    let asset = instruction.asset.clone();
+    let result = if instruction.operation == OP_DEPOSIT {
+        crate::deposit::deposit_collateral(env, owner.clone(), asset.clone(), instruction.amount)
+            .map_err(|_| StandingInstructionError::OperationFailed)?
+    } else {
+        crate::repay::repay_debt(env, owner.clone(), asset.clone(), instruction.amount)
+            .map_err(|_| StandingInstructionError::OperationFailed)?
+            .0
+    };
+
+    if instruction.bounty > 0 {
+        if let Some(token_addr) = crate::deposit::resolve_token_address(env, &asset) {
+            soroban_sdk::token::Client::new(env, &token_addr).transfer_from(
+                &env.current_contract_address(),
+                &owner,
+                &keeper,
+                &instruction.bounty,
+            );
+        }
+    }
+
+    instruction.executions_left -= 1;
+    if instruction.executions_left == 0 {
+        remove_instruction(env, &instruction);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&StandingInstructionDataKey::Instruction(id), &instruction);
+    }
+
+    emit_instruction_executed(
+        env,
+        InstructionExecutedEvent {
+            id,
+            owner,
+            keeper,
+            amount: instruction.amount,
+            bounty: instruction.bounty,
+            timestamp,
+        },
+    );
+    Ok(result)
+}
@@ -0,0 +1,167 @@
+//! # Storage TTL Module
+//!
+//! Persistent entries on Soroban are archived once their time-to-live runs
+//! out, and an archived position or reserve cannot be used until it is
+//! restored. This module keeps the lending state live:
+//!
+//! - Core operations extend the TTL of the user's position entries and of
+//!   the reserve they touch (`extend_position_ttl`, `extend_reserve_ttl`).
+//! - Reserve totals and cross-asset positions extend their entries whenever
+//!   they are read or written.
+//! - `bump_position` lets anyone keep a dormant position alive before it is
+//!   archived, without touching its balances.
+//!
+//! ## Archival Recovery
+//! A contract cannot read or restore archived entries itself. When a
+//! position has been archived, a client restores its entries with a
+//! `RestoreFootprint` operation and then calls `restore_position(user)`,
+//! which books the interest owed since the last accrual and extends the
+//! entries again. The persistent entries of a core position are:
+//!
+//! - `DepositDataKey::Position(user)` — collateral, debt and booked interest
+//! - `DepositDataKey::CollateralBalance(user)` — collateral principal
+//! - `ReserveDataKey::UserDebtAssets(user)` — assets the user owes
+//! - `SupplyInterestDataKey::UserSnapshot(user)` — supply index at the last
+//!   settlement
+//! - `InterestIndexDataKey::BorrowSnapshot(user)` — borrow index at the last
+//!   accrual
+//! - `InterestIndexDataKey::InterestIndexes` — the pool indexes (shared)
+//!
+//! Cross-asset positions live in one shared map (`positions`) that accrues
+//! from each position's `last_updated` once restored.
+//!
+//! Interest is back-filled from the snapshots, so the time a position spent
+//! archived is charged and credited at the rates the pool actually had.
+//! Restoring the position without its snapshots would lose that interest;
+//! `restore_position` therefore runs only after all of them are live.
+//!
+//! ## Invariants
+//! - An entry is only extended when its TTL has dropped below
+//!   `PERSISTENT_TTL_THRESHOLD`, and then to `PERSISTENT_TTL_EXTEND_TO`.
+//! - Extending never creates an entry; missing keys are skipped.
+//! - Extending never changes a stored value.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, Address, Env, IntoVal, Val};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{emit_position_restored, PositionRestoredEvent};
+use crate::interest_index::InterestIndexDataKey;
+use crate::reserve::ReserveDataKey;
+use crate::supply_interest::SupplyInterestDataKey;
+
+/// Errors that can occur while restoring a position
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StorageTtlError {
+    /// The user has no core position
+    PositionNotFound = 1,
+    /// Interest could not be computed
+    Overflow = 2,
+}
+
+/// Ledgers closed per day at ~5 seconds per ledger
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Remaining TTL below which an entry is extended (30 days)
+pub const PERSISTENT_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+
+/// TTL an entry is extended to (120 days)
+pub const PERSISTENT_TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
+/// Extend the TTL of a persistent entry if it exists
+pub(crate) fn extend_persistent<K>(env: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    let storage = env.storage().persistent();
+    if storage.has(key) {
+        storage.extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
+    }
+}
+
+/// Extend the TTL of the contract instance and its code
+pub(crate) fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
+}
+
+/// Extend the TTL of every entry making up a user's core position
+pub(crate) fn extend_position_ttl(env: &Env, user: &Address) {
+    extend_persistent(env, &DepositDataKey::Position(user.clone()));
+    extend_persistent(env, &DepositDataKey::CollateralBalance(user.clone()));
+    extend_persistent(env, &ReserveDataKey::UserDebtAssets(user.clone()));
+    extend_persistent(env, &SupplyInterestDataKey::UserSnapshot(user.clone()));
+    extend_persistent(env, &InterestIndexDataKey::BorrowSnapshot(user.clone()));
+}
+
+/// Extend the TTL of the balances making up an asset's reserve
+///
+/// Only entries the core operations already touch are extended, so the
+/// transaction footprint does not grow.
+pub(crate) fn extend_reserve_ttl(env: &Env, asset: &Option<Address>) {
+    extend_persistent(env, &ReserveDataKey::TotalSupplied(asset.clone()));
+    extend_persistent(env, &ReserveDataKey::TotalBorrowed(asset.clone()));
+    extend_persistent(env, &InterestIndexDataKey::InterestIndexes);
+}
+
+/// Keep a user's position from being archived (callable by anyone)
+///
+/// Extends the user's core position entries, the shared cross-asset
+/// position map and the contract instance. Balances are not touched.
+///
+/// # Arguments
+/// * `user` - The position owner
+pub fn bump_position(env: &Env, user: &Address) {
+    extend_position_ttl(env, user);
+    crate::cross_asset::extend_user_positions_ttl(env);
+    extend_instance(env);
+}
+
+/// Book a restored position's interest and keep it live (callable by anyone)
+///
+/// Settles supply interest from the user's supply snapshot and books borrow
+/// interest from their borrow snapshot, i.e. for the whole time since the
+/// last accrual, including time spent archived. The interest is owed either
+/// way, so anyone may trigger it.
+///
+/// # Arguments
+/// * `user` - The position owner
+///
+/// # Returns
+/// The restored position
+///
+/// # Errors
+/// * `StorageTtlError::PositionNotFound` - If the user has no core position
+/// * `StorageTtlError::Overflow` - If the interest overflows
+pub fn restore_position(env: &Env, user: &Address) -> Result<Position, StorageTtlError> {
+    let position_key = DepositDataKey::Position(user.clone());
+    if !env.storage().persistent().has(&position_key) {
+        return Err(StorageTtlError::PositionNotFound);
+    }
+
+    let supply_interest = crate::supply_interest::settle_supply_interest(env, user);
+    let mut position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+        .ok_or(StorageTtlError::PositionNotFound)?;
+    let borrow_interest = crate::interest_index::accrue_position(env, user, &mut position)
+        .map_err(|_| StorageTtlError::Overflow)?;
+    env.storage().persistent().set(&position_key, &position);
+    crate::liquidation_index::sync_borrower(env, user);
+    bump_position(env, user);
+
+    emit_position_restored(
+        env,
+        PositionRestoredEvent {
+            user: user.clone(),
+            supply_interest,
+            borrow_interest,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(position)
+}
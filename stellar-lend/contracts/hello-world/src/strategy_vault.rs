@@ -0,0 +1,970 @@
+//! # Strategy Vault Module
+//!
+//! Leveraged strategy vaults that run a looped position on a collateral/debt
+//! pair (e.g. 3x stXLM/XLM) on behalf of their depositors.
+//!
+//! Depositors supply the vault's collateral asset and receive vault shares.
+//! Keepers hold the vault's leverage inside an admin-configured band around
+//! the target by looping (borrow the debt asset from the pool and swap it into
+//! collateral through the AMM router) or deleveraging (sell collateral through
+//! the router and repay the pool). The vault books the amounts the swaps
+//! actually returned; each swap must return at least the oracle value of its
+//! input less `SWAP_SLIPPAGE_TOLERANCE_BPS`. Vault borrows count towards the
+//! pool's total borrows and borrow cap, and the vault debt accrues interest at
+//! the protocol borrow rate.
+//!
+//! ## Leverage
+//! `leverage_bps = collateral_value * 10_000 / equity`, where
+//! `equity = collateral_value - debt_value`. A vault with no debt has a
+//! leverage of exactly 10_000 (1x).
+//!
+//! ## Performance Fee
+//! Whenever a vault is touched, share-price gains above the vault's high-water
+//! mark are charged `performance_fee_bps` by minting shares to the configured
+//! fee recipient. The high-water mark is then raised to the new share price,
+//! so the same gain is never charged twice.
+//!
+//! ## Invariants
+//! - `target_leverage_bps` lies in `[10_000, MAX_TARGET_LEVERAGE_BPS]`.
+//! - Deposits can never push vault equity above `deposit_cap`.
+//! - Keepers can only rebalance when leverage is outside the band.
+//! - Withdrawals deleverage pro rata; any part of the redeemed debt the swap
+//!   did not cover is charged to the withdrawer, so the remaining holders'
+//!   equity is unchanged.
+//! - `collateral` only ever holds tokens the vault received: deposits and
+//!   swap output, less collateral sold and paid out.
+//! - Vault collateral is escrowed in `reserve`, so the pool never lends it
+//!   out to borrowers or to other vaults.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+
+use crate::events::{
+    emit_strategy_vault_created, emit_vault_deposit, emit_vault_fee_charged, emit_vault_rebalanced,
+    emit_vault_withdraw, StrategyVaultCreatedEvent, VaultDepositEvent, VaultFeeChargedEvent,
+    VaultRebalancedEvent, VaultWithdrawEvent,
+};
+
+/// Basis points scale (100% = 10_000)
+const BASIS_POINTS_SCALE: i128 = 10_000;
+
+/// Fixed-point scale for vault share prices (7 decimals)
+pub const SHARE_PRICE_SCALE: i128 = 10_000_000;
+
+/// Maximum target leverage a vault can be configured with (10x)
+pub const MAX_TARGET_LEVERAGE_BPS: i128 = 100_000;
+
+/// Maximum performance fee (50%)
+pub const MAX_PERFORMANCE_FEE_BPS: i128 = 5_000;
+
+/// Role allowed to rebalance vaults besides the admin
+const KEEPER_ROLE: &str = "keeper";
+
+/// Slippage tolerance against the oracle price for each swap (1%)
+const SWAP_SLIPPAGE_TOLERANCE_BPS: i128 = 100;
+
+/// Errors that can occur during strategy vault operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StrategyVaultError {
+    /// Caller is not the admin or an authorized keeper
+    Unauthorized = 1,
+    /// Amount must be greater than zero
+    InvalidAmount = 2,
+    /// Vault configuration is invalid
+    InvalidConfig = 3,
+    /// Vault does not exist
+    VaultNotFound = 4,
+    /// Vault is disabled for new deposits
+    VaultDisabled = 5,
+    /// Deposit would exceed the vault's deposit cap
+    DepositCapExceeded = 6,
+    /// User does not hold enough shares
+    InsufficientShares = 7,
+    /// Vault leverage is already inside the rebalancing band
+    WithinRebalanceBand = 8,
+    /// Oracle price unavailable for one of the vault assets
+    PriceUnavailable = 9,
+    /// Overflow occurred during calculation
+    Overflow = 10,
+    /// Vault debt exceeds its collateral value
+    Insolvent = 11,
+    /// The pool cannot lend the debt asset (borrow cap or liquidity)
+    BorrowFailed = 12,
+    /// AMM swap failed
+    SwapFailed = 13,
+    /// AMM swap returned less than the minimum output
+    SlippageExceeded = 14,
+}
+
+/// Storage keys for strategy vault data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum StrategyVaultDataKey {
+    /// Number of vaults created so far (next vault id)
+    VaultCount,
+    /// Configuration of a vault: StrategyVaultConfig
+    Config(u32),
+    /// Accounting state of a vault: StrategyVaultState
+    State(u32),
+    /// Shares held by a user in a vault: i128
+    Shares(u32, Address),
+}
+
+/// Strategy vault configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyVaultConfig {
+    /// Asset deposited by users and held as collateral
+    pub collateral_asset: Address,
+    /// Asset borrowed to lever the position
+    pub debt_asset: Address,
+    /// Target leverage in basis points (30_000 = 3x)
+    pub target_leverage_bps: i128,
+    /// Allowed deviation from the target before a rebalance is permitted (bps)
+    pub rebalance_band_bps: i128,
+    /// Maximum vault equity, in collateral asset units
+    pub deposit_cap: i128,
+    /// Fee charged on share-price gains above the high-water mark (bps)
+    pub performance_fee_bps: i128,
+    /// Receiver of performance fee shares
+    pub fee_recipient: Address,
+    /// Whether new deposits are accepted
+    pub enabled: bool,
+}
+
+/// Strategy vault accounting state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyVaultState {
+    /// Collateral held by the vault position, in collateral asset units
+    pub collateral: i128,
+    /// Outstanding debt including accrued interest, in debt asset units
+    pub debt: i128,
+    /// Part of `debt` borrowed from the pool, excluding interest
+    pub principal: i128,
+    /// Total vault shares outstanding
+    pub total_shares: i128,
+    /// Highest share price on which performance fees were charged
+    pub high_water_mark: i128,
+    /// Cumulative shares minted as performance fees
+    pub fee_shares_minted: i128,
+    /// Last time interest was accrued on the vault debt
+    pub last_accrual_time: u64,
+    /// Last time the vault was rebalanced by a keeper
+    pub last_rebalance_time: u64,
+}
+
+/// Read-only view of a vault used by keepers and front-ends
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyVaultView {
+    pub config: StrategyVaultConfig,
+    pub state: StrategyVaultState,
+    /// Net asset value in collateral asset units
+    pub equity: i128,
+    /// Current leverage in basis points
+    pub leverage_bps: i128,
+    /// Current share price (scaled by SHARE_PRICE_SCALE)
+    pub share_price: i128,
+    /// Whether leverage is outside the rebalancing band
+    pub needs_rebalance: bool,
+}
+
+fn validate_config(config: &StrategyVaultConfig) -> Result<(), StrategyVaultError> {
+    if config.collateral_asset == config.debt_asset {
+        return Err(StrategyVaultError::InvalidConfig);
+    }
+    if config.target_leverage_bps < BASIS_POINTS_SCALE
+        || config.target_leverage_bps > MAX_TARGET_LEVERAGE_BPS
+    {
+        return Err(StrategyVaultError::InvalidConfig);
+    }
+    if config.rebalance_band_bps <= 0 || config.rebalance_band_bps >= config.target_leverage_bps {
+        return Err(StrategyVaultError::InvalidConfig);
+    }
+    if config.deposit_cap <= 0 {
+        return Err(StrategyVaultError::InvalidConfig);
+    }
+    if config.performance_fee_bps < 0 || config.performance_fee_bps > MAX_PERFORMANCE_FEE_BPS {
+        return Err(StrategyVaultError::InvalidConfig);
+    }
+    Ok(())
+}
+
+fn get_config(env: &Env, vault_id: u32) -> Result<StrategyVaultConfig, StrategyVaultError> {
+    env.storage()
+        .persistent()
+        .get::<StrategyVaultDataKey, StrategyVaultConfig>(&StrategyVaultDataKey::Config(vault_id))
+        .ok_or(StrategyVaultError::VaultNotFound)
+}
+
+fn get_state(env: &Env, vault_id: u32) -> Result<StrategyVaultState, StrategyVaultError> {
+    env.storage()
+        .persistent()
+        .get::<StrategyVaultDataKey, StrategyVaultState>(&StrategyVaultDataKey::State(vault_id))
+        .ok_or(StrategyVaultError::VaultNotFound)
+}
+
+/// Persist the state of a vault, escrowing the change in its collateral so
+/// the pool does not lend it out
+fn save_state(env: &Env, vault_id: u32, config: &StrategyVaultConfig, state: &StrategyVaultState) {
+    let previous = get_state(env, vault_id).map_or(0, |s| s.collateral);
+    crate::reserve::record_escrow_change(
+        env,
+        &Some(config.collateral_asset.clone()),
+        state.collateral - previous,
+    );
+    env.storage()
+        .persistent()
+        .set(&StrategyVaultDataKey::State(vault_id), state);
+}
+
+fn set_shares(env: &Env, vault_id: u32, user: &Address, shares: i128) {
+    let key = StrategyVaultDataKey::Shares(vault_id, user.clone());
+    if shares == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &shares);
+    }
+}
+
+/// Get the shares held by `user` in a vault
+pub fn get_vault_shares(env: &Env, vault_id: u32, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<StrategyVaultDataKey, i128>(&StrategyVaultDataKey::Shares(vault_id, user.clone()))
+        .unwrap_or(0)
+}
+
+/// Oracle prices for the (collateral, debt) legs of a vault
+fn get_prices(env: &Env, config: &StrategyVaultConfig) -> Result<(i128, i128), StrategyVaultError> {
+    let collateral_price = crate::oracle::get_price(env, &config.collateral_asset)
+        .map_err(|_| StrategyVaultError::PriceUnavailable)?;
+    let debt_price = crate::oracle::get_price(env, &config.debt_asset)
+        .map_err(|_| StrategyVaultError::PriceUnavailable)?;
+    if collateral_price <= 0 || debt_price <= 0 {
+        return Err(StrategyVaultError::PriceUnavailable);
+    }
+    Ok((collateral_price, debt_price))
+}
+
+/// Convert a debt asset amount into collateral asset units
+fn debt_to_collateral(
+    amount: i128,
+    collateral_price: i128,
+    debt_price: i128,
+) -> Result<i128, StrategyVaultError> {
+    amount
+        .checked_mul(debt_price)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(collateral_price)
+        .ok_or(StrategyVaultError::Overflow)
+}
+
+/// Convert a collateral asset amount into debt asset units
+fn collateral_to_debt(
+    amount: i128,
+    collateral_price: i128,
+    debt_price: i128,
+) -> Result<i128, StrategyVaultError> {
+    amount
+        .checked_mul(collateral_price)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(debt_price)
+        .ok_or(StrategyVaultError::Overflow)
+}
+
+/// Vault equity in collateral asset units
+fn calculate_equity(
+    state: &StrategyVaultState,
+    collateral_price: i128,
+    debt_price: i128,
+) -> Result<i128, StrategyVaultError> {
+    let debt_in_collateral = debt_to_collateral(state.debt, collateral_price, debt_price)?;
+    state
+        .collateral
+        .checked_sub(debt_in_collateral)
+        .ok_or(StrategyVaultError::Overflow)
+}
+
+/// Leverage in basis points (collateral / equity)
+fn calculate_leverage(collateral: i128, equity: i128) -> Result<i128, StrategyVaultError> {
+    if equity <= 0 {
+        return Err(StrategyVaultError::Insolvent);
+    }
+    collateral
+        .checked_mul(BASIS_POINTS_SCALE)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(equity)
+        .ok_or(StrategyVaultError::Overflow)
+}
+
+/// Share price in SHARE_PRICE_SCALE units (1.0 for an empty vault)
+fn calculate_share_price(equity: i128, total_shares: i128) -> Result<i128, StrategyVaultError> {
+    if total_shares == 0 {
+        return Ok(SHARE_PRICE_SCALE);
+    }
+    equity
+        .checked_mul(SHARE_PRICE_SCALE)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(total_shares)
+        .ok_or(StrategyVaultError::Overflow)
+}
+
+/// Accrue protocol borrow interest on the vault debt
+fn accrue_vault_interest(
+    env: &Env,
+    state: &mut StrategyVaultState,
+) -> Result<(), StrategyVaultError> {
+    let now = env.ledger().timestamp();
+    if state.debt > 0 && now > state.last_accrual_time {
+        let rate_bps = crate::interest_rate::calculate_borrow_rate(env)
+            .map_err(|_| StrategyVaultError::Overflow)?;
+        let interest = crate::interest_rate::calculate_accrued_interest(
+            state.debt,
+            state.last_accrual_time,
+            now,
+            rate_bps,
+        )
+        .map_err(|_| StrategyVaultError::Overflow)?;
+        state.debt = state
+            .debt
+            .checked_add(interest)
+            .ok_or(StrategyVaultError::Overflow)?;
+    }
+    state.last_accrual_time = now;
+    Ok(())
+}
+
+/// Charge the performance fee on gains above the high-water mark
+///
+/// Mints fee shares to the fee recipient worth `performance_fee_bps` of the
+/// gain and raises the high-water mark to the post-fee share price.
+fn charge_performance_fee(
+    env: &Env,
+    vault_id: u32,
+    config: &StrategyVaultConfig,
+    state: &mut StrategyVaultState,
+    equity: i128,
+) -> Result<(), StrategyVaultError> {
+    if state.total_shares == 0 || equity <= 0 {
+        return Ok(());
+    }
+    let share_price = calculate_share_price(equity, state.total_shares)?;
+    if share_price <= state.high_water_mark {
+        return Ok(());
+    }
+
+    let gain = share_price
+        .checked_sub(state.high_water_mark)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_mul(state.total_shares)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(SHARE_PRICE_SCALE)
+        .ok_or(StrategyVaultError::Overflow)?;
+    let fee_value = gain
+        .checked_mul(config.performance_fee_bps)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(BASIS_POINTS_SCALE)
+        .ok_or(StrategyVaultError::Overflow)?;
+
+    if fee_value > 0 && fee_value < equity {
+        // Shares worth `fee_value` after dilution: fee * shares / (equity - fee)
+        let fee_shares = fee_value
+            .checked_mul(state.total_shares)
+            .ok_or(StrategyVaultError::Overflow)?
+            .checked_div(equity - fee_value)
+            .ok_or(StrategyVaultError::Overflow)?;
+        if fee_shares > 0 {
+            state.total_shares = state
+                .total_shares
+                .checked_add(fee_shares)
+                .ok_or(StrategyVaultError::Overflow)?;
+            state.fee_shares_minted = state
+                .fee_shares_minted
+                .checked_add(fee_shares)
+                .ok_or(StrategyVaultError::Overflow)?;
+            let recipient_shares = get_vault_shares(env, vault_id, &config.fee_recipient)
+                .checked_add(fee_shares)
+                .ok_or(StrategyVaultError::Overflow)?;
+            set_shares(env, vault_id, &config.fee_recipient, recipient_shares);
+
+            emit_vault_fee_charged(
+                env,
+                VaultFeeChargedEvent {
+                    vault_id,
+                    recipient: config.fee_recipient.clone(),
+                    fee_value,
+                    fee_shares,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+        }
+    }
+
+    state.high_water_mark = calculate_share_price(equity, state.total_shares)?;
+    Ok(())
+}
+
+/// Accrue interest and charge fees, returning the settled equity and prices
+fn settle_vault(
+    env: &Env,
+    vault_id: u32,
+    config: &StrategyVaultConfig,
+    state: &mut StrategyVaultState,
+) -> Result<(i128, i128, i128), StrategyVaultError> {
+    accrue_vault_interest(env, state)?;
+    let (collateral_price, debt_price) = get_prices(env, config)?;
+    let equity = calculate_equity(state, collateral_price, debt_price)?;
+    charge_performance_fee(env, vault_id, config, state, equity)?;
+    Ok((equity, collateral_price, debt_price))
+}
+
+/// Swap `amount_in` through the AMM router, requiring at least
+/// `min_amount_out`
+fn swap(
+    env: &Env,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+    min_amount_out: i128,
+) -> Result<i128, StrategyVaultError> {
+    let params = stellarlend_amm::SwapParams {
+        protocol: env.current_contract_address(),
+        token_in: Some(token_in.clone()),
+        token_out: Some(token_out.clone()),
+        amount_in,
+        min_amount_out,
+        slippage_tolerance: SWAP_SLIPPAGE_TOLERANCE_BPS,
+        deadline: env.ledger().timestamp(),
+        use_oracle_floor: false,
+    };
+    let amount_out = stellarlend_amm::execute_swap(env, env.current_contract_address(), params)
+        .map_err(|e| match e {
+            stellarlend_amm::AmmError::MinOutputNotMet
+            | stellarlend_amm::AmmError::SlippageExceeded => StrategyVaultError::SlippageExceeded,
+            _ => StrategyVaultError::SwapFailed,
+        })?;
+    if amount_out < min_amount_out {
+        return Err(StrategyVaultError::SlippageExceeded);
+    }
+    Ok(amount_out)
+}
+
+/// Oracle value of `amount` less the swap slippage tolerance
+fn min_swap_output(amount: i128) -> Result<i128, StrategyVaultError> {
+    Ok(amount
+        .checked_mul(BASIS_POINTS_SCALE - SWAP_SLIPPAGE_TOLERANCE_BPS)
+        .ok_or(StrategyVaultError::Overflow)?
+        / BASIS_POINTS_SCALE)
+}
+
+/// Loop the vault position: borrow `debt_amount` from the pool and swap it
+/// into collateral
+///
+/// Returns the collateral bought.
+fn lever_up(
+    env: &Env,
+    config: &StrategyVaultConfig,
+    state: &mut StrategyVaultState,
+    debt_amount: i128,
+    collateral_price: i128,
+    debt_price: i128,
+) -> Result<i128, StrategyVaultError> {
+    let debt_asset = Some(config.debt_asset.clone());
+    crate::reserve::check_borrow_cap(env, &debt_asset, debt_amount)
+        .map_err(|_| StrategyVaultError::BorrowFailed)?;
+    if crate::reserve::available_liquidity(env, &debt_asset, &config.debt_asset) < debt_amount {
+        return Err(StrategyVaultError::BorrowFailed);
+    }
+
+    let expected = debt_to_collateral(debt_amount, collateral_price, debt_price)?;
+    let collateral_bought = swap(
+        env,
+        &config.debt_asset,
+        &config.collateral_asset,
+        debt_amount,
+        min_swap_output(expected)?.max(1),
+    )?;
+
+    crate::reserve::record_borrow_change(env, &debt_asset, debt_amount);
+    state.debt = state
+        .debt
+        .checked_add(debt_amount)
+        .ok_or(StrategyVaultError::Overflow)?;
+    state.principal = state
+        .principal
+        .checked_add(debt_amount)
+        .ok_or(StrategyVaultError::Overflow)?;
+    state.collateral = state
+        .collateral
+        .checked_add(collateral_bought)
+        .ok_or(StrategyVaultError::Overflow)?;
+    Ok(collateral_bought)
+}
+
+/// Deleverage the vault position: sell the oracle value of `debt_amount` in
+/// collateral and repay the pool with the proceeds, interest first
+///
+/// Returns a tuple (collateral_sold, debt_repaid). Swap output above the
+/// outstanding debt is credited to the protocol reserve.
+fn deleverage(
+    env: &Env,
+    config: &StrategyVaultConfig,
+    state: &mut StrategyVaultState,
+    debt_amount: i128,
+    collateral_price: i128,
+    debt_price: i128,
+) -> Result<(i128, i128), StrategyVaultError> {
+    let debt_amount = debt_amount.min(state.debt);
+    if debt_amount <= 0 {
+        return Ok((0, 0));
+    }
+    let collateral_sold = debt_to_collateral(debt_amount, collateral_price, debt_price)?;
+    if collateral_sold > state.collateral {
+        return Err(StrategyVaultError::Insolvent);
+    }
+    if collateral_sold == 0 {
+        return Ok((0, 0));
+    }
+
+    let proceeds = swap(
+        env,
+        &config.collateral_asset,
+        &config.debt_asset,
+        collateral_sold,
+        min_swap_output(debt_amount)?.max(1),
+    )?;
+    let debt_repaid = proceeds.min(state.debt);
+    let surplus = proceeds - debt_repaid;
+    if surplus > 0 {
+        let reserve_key =
+            crate::deposit::DepositDataKey::ProtocolReserve(Some(config.debt_asset.clone()));
+        let reserve = env
+            .storage()
+            .persistent()
+            .get::<crate::deposit::DepositDataKey, i128>(&reserve_key)
+            .unwrap_or(0);
+        env.storage().persistent().set(
+            &reserve_key,
+            &reserve
+                .checked_add(surplus)
+                .ok_or(StrategyVaultError::Overflow)?,
+        );
+    }
+
+    let interest_paid = debt_repaid.min(state.debt - state.principal);
+    let principal_paid = debt_repaid - interest_paid;
+    crate::reserve::record_borrow_change(env, &Some(config.debt_asset.clone()), -principal_paid);
+    state.debt -= debt_repaid;
+    state.principal -= principal_paid;
+    state.collateral -= collateral_sold;
+    Ok((collateral_sold, debt_repaid))
+}
+
+/// Create a new strategy vault (admin only)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin)
+/// * `config` - The vault configuration
+///
+/// # Returns
+/// Returns the id of the new vault
+///
+/// # Errors
+/// * `StrategyVaultError::Unauthorized` - If caller is not admin
+/// * `StrategyVaultError::InvalidConfig` - If the configuration is invalid
+pub fn create_vault(
+    env: &Env,
+    caller: Address,
+    config: StrategyVaultConfig,
+) -> Result<u32, StrategyVaultError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| StrategyVaultError::Unauthorized)?;
+    validate_config(&config)?;
+
+    let vault_id = env
+        .storage()
+        .persistent()
+        .get::<StrategyVaultDataKey, u32>(&StrategyVaultDataKey::VaultCount)
+        .unwrap_or(0);
+
+    let now = env.ledger().timestamp();
+    let state = StrategyVaultState {
+        collateral: 0,
+        debt: 0,
+        principal: 0,
+        total_shares: 0,
+        high_water_mark: SHARE_PRICE_SCALE,
+        fee_shares_minted: 0,
+        last_accrual_time: now,
+        last_rebalance_time: now,
+    };
+
+    env.storage()
+        .persistent()
+        .set(&StrategyVaultDataKey::Config(vault_id), &config);
+    save_state(env, vault_id, &config, &state);
+    env.storage()
+        .persistent()
+        .set(&StrategyVaultDataKey::VaultCount, &(vault_id + 1));
+
+    emit_strategy_vault_created(
+        env,
+        StrategyVaultCreatedEvent {
+            vault_id,
+            collateral_asset: config.collateral_asset,
+            debt_asset: config.debt_asset,
+            target_leverage_bps: config.target_leverage_bps,
+            timestamp: now,
+        },
+    );
+
+    Ok(vault_id)
+}
+
+/// Update the configuration of an existing vault (admin only)
+///
+/// The collateral and debt assets of a vault cannot be changed.
+pub fn update_vault_config(
+    env: &Env,
+    caller: Address,
+    vault_id: u32,
+    config: StrategyVaultConfig,
+) -> Result<(), StrategyVaultError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| StrategyVaultError::Unauthorized)?;
+    let current = get_config(env, vault_id)?;
+    if current.collateral_asset != config.collateral_asset
+        || current.debt_asset != config.debt_asset
+    {
+        return Err(StrategyVaultError::InvalidConfig);
+    }
+    validate_config(&config)?;
+    env.storage()
+        .persistent()
+        .set(&StrategyVaultDataKey::Config(vault_id), &config);
+    Ok(())
+}
+
+/// Deposit collateral into a vault in exchange for shares
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The depositor
+/// * `vault_id` - The vault to deposit into
+/// * `amount` - Amount of the vault's collateral asset to deposit
+///
+/// # Returns
+/// Returns the number of shares minted
+///
+/// # Errors
+/// * `StrategyVaultError::InvalidAmount` - If amount is not positive
+/// * `StrategyVaultError::VaultDisabled` - If the vault does not accept deposits
+/// * `StrategyVaultError::DepositCapExceeded` - If the deposit would exceed the cap
+pub fn vault_deposit(
+    env: &Env,
+    user: Address,
+    vault_id: u32,
+    amount: i128,
+) -> Result<i128, StrategyVaultError> {
+    user.require_auth();
+    if amount <= 0 {
+        return Err(StrategyVaultError::InvalidAmount);
+    }
+
+    let config = get_config(env, vault_id)?;
+    if !config.enabled {
+        return Err(StrategyVaultError::VaultDisabled);
+    }
+
+    let mut state = get_state(env, vault_id)?;
+    let (equity, _, _) = settle_vault(env, vault_id, &config, &mut state)?;
+
+    let new_equity = equity
+        .checked_add(amount)
+        .ok_or(StrategyVaultError::Overflow)?;
+    if new_equity > config.deposit_cap {
+        return Err(StrategyVaultError::DepositCapExceeded);
+    }
+
+    let shares = if state.total_shares == 0 {
+        amount
+    } else {
+        if equity <= 0 {
+            return Err(StrategyVaultError::Insolvent);
+        }
+        amount
+            .checked_mul(state.total_shares)
+            .ok_or(StrategyVaultError::Overflow)?
+            .checked_div(equity)
+            .ok_or(StrategyVaultError::Overflow)?
+    };
+    if shares <= 0 {
+        return Err(StrategyVaultError::InvalidAmount);
+    }
+
+    let token_client = soroban_sdk::token::Client::new(env, &config.collateral_asset);
+    token_client.transfer(&user, env.current_contract_address(), &amount);
+
+    state.collateral = state
+        .collateral
+        .checked_add(amount)
+        .ok_or(StrategyVaultError::Overflow)?;
+    state.total_shares = state
+        .total_shares
+        .checked_add(shares)
+        .ok_or(StrategyVaultError::Overflow)?;
+    save_state(env, vault_id, &config, &state);
+
+    let user_shares = get_vault_shares(env, vault_id, &user)
+        .checked_add(shares)
+        .ok_or(StrategyVaultError::Overflow)?;
+    set_shares(env, vault_id, &user, user_shares);
+
+    emit_vault_deposit(
+        env,
+        VaultDepositEvent {
+            vault_id,
+            user,
+            amount,
+            shares,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(shares)
+}
+
+/// Redeem vault shares for collateral
+///
+/// Deleverages the vault pro rata: collateral worth the redeemed share of debt
+/// is sold through the AMM router to repay it and the remainder of the
+/// redeemed collateral is paid to the user. Debt left uncovered by slippage
+/// is paid for out of the user's collateral.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The share holder
+/// * `vault_id` - The vault to withdraw from
+/// * `shares` - Number of shares to redeem
+///
+/// # Returns
+/// Returns the collateral amount paid out
+///
+/// # Errors
+/// * `StrategyVaultError::InvalidAmount` - If shares is not positive
+/// * `StrategyVaultError::InsufficientShares` - If the user holds fewer shares
+/// * `StrategyVaultError::Insolvent` - If the redeemed debt exceeds the redeemed collateral
+/// * `StrategyVaultError::SlippageExceeded` - If the swap output is too low
+pub fn vault_withdraw(
+    env: &Env,
+    user: Address,
+    vault_id: u32,
+    shares: i128,
+) -> Result<i128, StrategyVaultError> {
+    user.require_auth();
+    if shares <= 0 {
+        return Err(StrategyVaultError::InvalidAmount);
+    }
+
+    let config = get_config(env, vault_id)?;
+    let mut state = get_state(env, vault_id)?;
+    let (_, collateral_price, debt_price) = settle_vault(env, vault_id, &config, &mut state)?;
+
+    let user_shares = get_vault_shares(env, vault_id, &user);
+    if shares > user_shares {
+        return Err(StrategyVaultError::InsufficientShares);
+    }
+
+    let collateral_share = state
+        .collateral
+        .checked_mul(shares)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(state.total_shares)
+        .ok_or(StrategyVaultError::Overflow)?;
+    let debt_share = state
+        .debt
+        .checked_mul(shares)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(state.total_shares)
+        .ok_or(StrategyVaultError::Overflow)?;
+
+    let (collateral_sold, debt_repaid) = deleverage(
+        env,
+        &config,
+        &mut state,
+        debt_share,
+        collateral_price,
+        debt_price,
+    )?;
+    // Debt the swap did not cover stays in the vault, backed by collateral
+    // taken from the withdrawer's share
+    let uncovered = debt_to_collateral(
+        debt_share.saturating_sub(debt_repaid).max(0),
+        collateral_price,
+        debt_price,
+    )?;
+    let amount_out = collateral_share
+        .checked_sub(collateral_sold)
+        .and_then(|v| v.checked_sub(uncovered))
+        .ok_or(StrategyVaultError::Overflow)?;
+    if amount_out < 0 {
+        return Err(StrategyVaultError::Insolvent);
+    }
+
+    state.collateral = state
+        .collateral
+        .checked_sub(amount_out)
+        .ok_or(StrategyVaultError::Overflow)?;
+    state.total_shares -= shares;
+    save_state(env, vault_id, &config, &state);
+    set_shares(env, vault_id, &user, user_shares - shares);
+
+    if amount_out > 0 {
+        let token_client = soroban_sdk::token::Client::new(env, &config.collateral_asset);
+        token_client.transfer(&env.current_contract_address(), &user, &amount_out);
+    }
+
+    emit_vault_withdraw(
+        env,
+        VaultWithdrawEvent {
+            vault_id,
+            user,
+            shares,
+            amount: amount_out,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(amount_out)
+}
+
+/// Rebalance a vault back to its target leverage (keeper or admin)
+///
+/// Loops the position when leverage is below `target - band` and
+/// deleverages when it is above `target + band`.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `keeper` - The caller (must hold the `keeper` role or be admin)
+/// * `vault_id` - The vault to rebalance
+///
+/// # Returns
+/// Returns the vault leverage after rebalancing, in basis points
+///
+/// # Errors
+/// * `StrategyVaultError::Unauthorized` - If caller is not a keeper or admin
+/// * `StrategyVaultError::WithinRebalanceBand` - If leverage is already inside the band
+/// * `StrategyVaultError::Insolvent` - If vault equity is not positive
+/// * `StrategyVaultError::BorrowFailed` - If the pool cannot lend the debt asset
+/// * `StrategyVaultError::SlippageExceeded` - If a swap output is too low
+pub fn rebalance_vault(
+    env: &Env,
+    keeper: Address,
+    vault_id: u32,
+) -> Result<i128, StrategyVaultError> {
+    keeper.require_auth();
+    crate::admin::require_role_or_admin(env, &keeper, Symbol::new(env, KEEPER_ROLE))
+        .map_err(|_| StrategyVaultError::Unauthorized)?;
+
+    let config = get_config(env, vault_id)?;
+    let mut state = get_state(env, vault_id)?;
+    let (equity, collateral_price, debt_price) = settle_vault(env, vault_id, &config, &mut state)?;
+
+    let old_leverage = calculate_leverage(state.collateral, equity)?;
+    let lower = config.target_leverage_bps - config.rebalance_band_bps;
+    let upper = config.target_leverage_bps + config.rebalance_band_bps;
+    if old_leverage >= lower && old_leverage <= upper {
+        return Err(StrategyVaultError::WithinRebalanceBand);
+    }
+
+    let target_collateral = equity
+        .checked_mul(config.target_leverage_bps)
+        .ok_or(StrategyVaultError::Overflow)?
+        .checked_div(BASIS_POINTS_SCALE)
+        .ok_or(StrategyVaultError::Overflow)?;
+    let target_debt = collateral_to_debt(target_collateral - equity, collateral_price, debt_price)?;
+
+    if target_debt > state.debt {
+        let borrow_amount = target_debt - state.debt;
+        lever_up(
+            env,
+            &config,
+            &mut state,
+            borrow_amount,
+            collateral_price,
+            debt_price,
+        )?;
+    } else {
+        let repay_amount = state.debt - target_debt;
+        deleverage(
+            env,
+            &config,
+            &mut state,
+            repay_amount,
+            collateral_price,
+            debt_price,
+        )?;
+    }
+
+    let new_equity = calculate_equity(&state, collateral_price, debt_price)?;
+    let new_leverage = calculate_leverage(state.collateral, new_equity)?;
+    state.last_rebalance_time = env.ledger().timestamp();
+    save_state(env, vault_id, &config, &state);
+
+    emit_vault_rebalanced(
+        env,
+        VaultRebalancedEvent {
+            vault_id,
+            keeper,
+            old_leverage_bps: old_leverage,
+            new_leverage_bps: new_leverage,
+            collateral: state.collateral,
+            debt: state.debt,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(new_leverage)
+}
+
+/// Get a snapshot of a vault including its live equity, leverage and share price
+pub fn get_vault(env: &Env, vault_id: u32) -> Result<StrategyVaultView, StrategyVaultError> {
+    let config = get_config(env, vault_id)?;
+    let mut state = get_state(env, vault_id)?;
+    accrue_vault_interest(env, &mut state)?;
+    let (collateral_price, debt_price) = get_prices(env, &config)?;
+    let equity = calculate_equity(&state, collateral_price, debt_price)?;
+
+    let leverage_bps = if state.collateral == 0 {
+        BASIS_POINTS_SCALE
+    } else {
+        calculate_leverage(state.collateral, equity)?
+    };
+    let share_price = calculate_share_price(equity.max(0), state.total_shares)?;
+    let needs_rebalance = state.collateral > 0
+        && (leverage_bps < config.target_leverage_bps - config.rebalance_band_bps
+            || leverage_bps > config.target_leverage_bps + config.rebalance_band_bps);
+
+    Ok(StrategyVaultView {
+        config,
+        state,
+        equity,
+        leverage_bps,
+        share_price,
+        needs_rebalance,
+    })
+}
+
+/// Get the number of vaults created
+pub fn get_vault_count(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<StrategyVaultDataKey, u32>(&StrategyVaultDataKey::VaultCount)
+        .unwrap_or(0)
+}
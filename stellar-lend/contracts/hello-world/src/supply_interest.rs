@@ -0,0 +1,145 @@
+//! # Supply Interest Module
+//!
+//! Pays depositors a yield funded by borrower interest through a cumulative
+//! supply index.
+//!
+//! ## Supply Index
+//! The supply index is kept by the `interest_index` module next to the
+//! borrow index. It starts at `INDEX_SCALE` and grows over time at the supply
+//! rate of the `interest_rate` module scaled by utilization, i.e. at the share
+//! of borrower interest passed on to depositors. It is advanced before every
+//! deposit, withdrawal, borrow, repayment and liquidation, so each period is
+//! compounded at the rate in force during it.
+//!
+//! ## Settlement
+//! Every user keeps a snapshot of the index taken when their collateral was
+//! last settled. Settling credits `collateral * (index / snapshot - 1)` to the
+//! collateral balance and position and moves the snapshot to the current
+//! index. Collateral is settled before a user deposits, withdraws, borrows or
+//! is liquidated; `get_user_supply_balance` reports the settled balance
+//! without writing.
+//!
+//! ## Invariants
+//! - The supply index never decreases.
+//! - Depositors earn at most the interest charged to borrowers: the supply
+//!   rate never exceeds the borrow rate and is applied to the borrowed share
+//!   of deposits only.
+//! - Settlement does not change utilization: credited interest adds to the
+//!   value locked, not to total deposits.
+//! - Collateral without a snapshot (deposited before supply interest existed)
+//!   starts earning from its first settlement.
+//! - Accrual and settlement never fail the operation that triggers them.
+
+#![allow(unused)]
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::deposit::{DepositDataKey, Position, ProtocolAnalytics};
+use crate::events::{emit_supply_interest_accrued, SupplyInterestAccruedEvent};
+
+/// Storage keys for supply interest data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum SupplyInterestDataKey {
+    /// Supply index at a user's last settlement: i128
+    UserSnapshot(Address),
+}
+
+/// Interest earned by `balance` since the index was at `snapshot`
+fn interest_since(balance: i128, snapshot: i128, index: i128) -> i128 {
+    if balance <= 0 || snapshot <= 0 || index <= snapshot {
+        return 0;
+    }
+    balance.saturating_mul(index - snapshot) / snapshot
+}
+
+fn get_user_snapshot(env: &Env, user: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get::<SupplyInterestDataKey, i128>(&SupplyInterestDataKey::UserSnapshot(user.clone()))
+}
+
+fn get_collateral(env: &Env, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the current supply index, including growth since the last update
+pub fn get_supply_index(env: &Env) -> i128 {
+    crate::interest_index::get_interest_indexes(env).supply_index
+}
+
+/// Get a user's collateral including supply interest not yet settled
+///
+/// # Arguments
+/// * `user` - The address of the user
+///
+/// # Returns
+/// The collateral principal plus accrued supply interest
+pub fn get_user_supply_balance(env: &Env, user: &Address) -> i128 {
+    let collateral = get_collateral(env, user);
+    match get_user_snapshot(env, user) {
+        Some(snapshot) => {
+            collateral.saturating_add(interest_since(collateral, snapshot, get_supply_index(env)))
+        }
+        None => collateral,
+    }
+}
+
+/// Credit a user's accrued supply interest to their collateral
+///
+/// Advances the interest indexes first.
+///
+/// # Returns
+/// The interest credited
+pub(crate) fn settle_supply_interest(env: &Env, user: &Address) -> i128 {
+    let index = crate::interest_index::accrue_interest(env).supply_index;
+    let snapshot_key = SupplyInterestDataKey::UserSnapshot(user.clone());
+    let interest = match get_user_snapshot(env, user) {
+        Some(snapshot) => interest_since(get_collateral(env, user), snapshot, index),
+        None => 0,
+    };
+    env.storage().persistent().set(&snapshot_key, &index);
+    if interest <= 0 {
+        return 0;
+    }
+
+    let collateral = get_collateral(env, user).saturating_add(interest);
+    env.storage().persistent().set(
+        &DepositDataKey::CollateralBalance(user.clone()),
+        &collateral,
+    );
+    let position_key = DepositDataKey::Position(user.clone());
+    if let Some(mut position) = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+    {
+        position.collateral = collateral;
+        env.storage().persistent().set(&position_key, &position);
+    }
+
+    // Interest is value locked, but not a deposit: utilization is unchanged
+    let analytics_key = DepositDataKey::ProtocolAnalytics;
+    if let Some(mut analytics) = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, ProtocolAnalytics>(&analytics_key)
+    {
+        analytics.total_value_locked = analytics.total_value_locked.saturating_add(interest);
+        env.storage().persistent().set(&analytics_key, &analytics);
+    }
+
+    emit_supply_interest_accrued(
+        env,
+        SupplyInterestAccruedEvent {
+            user: user.clone(),
+            amount: interest,
+            supply_index: index,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    interest
+}
@@ -0,0 +1,237 @@
+//! # Supply Token Module
+//!
+//! Pool side of the tokenized supply positions. Each reserve can have a
+//! supply token (sToken, the `stellarlend-stoken` contract) registered by the
+//! admin. The token keeps no balances itself: it reads a holder's supplied
+//! balance through `supply_token_balance` and moves supply between holders
+//! through `supply_token_transfer`.
+//!
+//! ## Balances
+//! Supply tokens represent the per-asset collateral of the cross-asset
+//! positions (`cross_asset`). That collateral does not accrue supply interest
+//! yet, so the balance equals the supplied amount; any interest credited to
+//! those positions later is reflected in the balance without changes here.
+//!
+//! ## Redemption
+//! `get_exchange_rate` reports the underlying amount one sToken unit redeems
+//! for, scaled by `EXCHANGE_RATE_SCALE`. As balances are the supplied amount
+//! itself, the rate is 1.0 for every registered asset. `redeem` burns supply
+//! tokens at that rate by withdrawing the underlying from the holder's
+//! position, with the same pause and health checks as `cross_asset_withdraw`.
+//!
+//! ## Invariants
+//! - Only the token registered for an asset can move its supply.
+//! - A transfer never leaves the sender with a health factor below 1.0 when
+//!   the asset is enabled as collateral.
+//! - A token is only registered if it is bound to this pool and asset.
+
+#![allow(unused)]
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env};
+
+use crate::cross_asset::CrossAssetError;
+use crate::events::{
+    emit_supply_token_redeemed, emit_supply_token_updated, SupplyTokenRedeemedEvent,
+    SupplyTokenUpdatedEvent,
+};
+
+/// Errors that can occur during supply token operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SupplyTokenError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// The token is not bound to this pool and asset
+    InvalidToken = 2,
+    /// No supply token is registered for the asset
+    TokenNotSet = 3,
+    /// Amount must be greater than zero
+    InvalidAmount = 4,
+    /// Sender's supplied balance is below the amount
+    InsufficientBalance = 5,
+    /// Transfer would drop the sender's health factor below 1.0
+    UnhealthyPosition = 6,
+    /// A price needed for the health check is stale or missing
+    PriceNotAvailable = 7,
+    /// Withdrawals are paused (see `risk_management`)
+    OperationPaused = 8,
+}
+
+/// Scale of the supply token exchange rate (1.0 = 10_000_000)
+pub const EXCHANGE_RATE_SCALE: i128 = 10_000_000;
+
+/// Storage keys for supply token data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum SupplyTokenDataKey {
+    /// Supply token contract of an asset: Address
+    SupplyTokenAddress(Option<Address>),
+}
+
+/// Binding reported by a supply token contract
+#[contractclient(name = "SupplyTokenBindingClient")]
+pub trait SupplyTokenBinding {
+    /// Lending pool the token is bound to
+    fn pool(env: Env) -> Address;
+    /// Reserve asset the token represents
+    fn asset(env: Env) -> Option<Address>;
+}
+
+/// Register the supply token of an asset (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `asset` - The reserve asset (None for native XLM)
+/// * `token` - The supply token contract, bound to this pool and `asset`
+///
+/// # Errors
+/// * `SupplyTokenError::Unauthorized` - If caller is not the admin
+/// * `SupplyTokenError::InvalidToken` - If the token is bound elsewhere
+pub fn set_supply_token(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    token: Address,
+) -> Result<(), SupplyTokenError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| SupplyTokenError::Unauthorized)?;
+
+    let binding = SupplyTokenBindingClient::new(env, &token);
+    if binding.pool() != env.current_contract_address() || binding.asset() != asset {
+        return Err(SupplyTokenError::InvalidToken);
+    }
+
+    env.storage().persistent().set(
+        &SupplyTokenDataKey::SupplyTokenAddress(asset.clone()),
+        &token,
+    );
+
+    emit_supply_token_updated(
+        env,
+        SupplyTokenUpdatedEvent {
+            admin: caller,
+            asset,
+            token,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the supply token registered for an asset
+pub fn get_supply_token(env: &Env, asset: Option<Address>) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<SupplyTokenDataKey, Address>(&SupplyTokenDataKey::SupplyTokenAddress(asset))
+}
+
+/// Supplied balance of `user` in `asset`, as reported by its supply token
+pub fn supply_token_balance(env: &Env, asset: Option<Address>, user: &Address) -> i128 {
+    crate::cross_asset::get_user_asset_position(env, user, asset).collateral
+}
+
+/// Move supplied `asset` between holders on behalf of its supply token
+///
+/// The token authorizes the holder (or spender) before calling in.
+///
+/// # Arguments
+/// * `asset` - The reserve asset (None for native XLM)
+/// * `from` - The sender
+/// * `to` - The recipient
+/// * `amount` - The amount of supply to move
+///
+/// # Errors
+/// * `SupplyTokenError::TokenNotSet` - If the asset has no supply token
+/// * `SupplyTokenError::InvalidAmount` - If the amount is not positive
+/// * `SupplyTokenError::InsufficientBalance` - If the sender's balance is too low
+/// * `SupplyTokenError::UnhealthyPosition` - If the sender would become liquidatable
+/// * `SupplyTokenError::PriceNotAvailable` - If the health check has no valid price
+pub fn supply_token_transfer(
+    env: &Env,
+    asset: Option<Address>,
+    from: Address,
+    to: Address,
+    amount: i128,
+) -> Result<(), SupplyTokenError> {
+    let token = get_supply_token(env, asset.clone()).ok_or(SupplyTokenError::TokenNotSet)?;
+    token.require_auth();
+
+    crate::cross_asset::transfer_collateral(env, &from, &to, asset, amount).map_err(|e| match e {
+        CrossAssetError::InvalidAmount => SupplyTokenError::InvalidAmount,
+        CrossAssetError::InsufficientCollateral => SupplyTokenError::InsufficientBalance,
+        CrossAssetError::UnhealthyPosition => SupplyTokenError::UnhealthyPosition,
+        _ => SupplyTokenError::PriceNotAvailable,
+    })
+}
+
+/// Get the underlying amount one supply token unit redeems for
+///
+/// # Arguments
+/// * `asset` - The reserve asset (None for native XLM)
+///
+/// # Returns
+/// The exchange rate, scaled by `EXCHANGE_RATE_SCALE`
+///
+/// # Errors
+/// * `SupplyTokenError::TokenNotSet` - If the asset has no supply token
+pub fn get_exchange_rate(env: &Env, asset: Option<Address>) -> Result<i128, SupplyTokenError> {
+    get_supply_token(env, asset).ok_or(SupplyTokenError::TokenNotSet)?;
+    Ok(EXCHANGE_RATE_SCALE)
+}
+
+/// Redeem supply tokens for the underlying asset
+///
+/// Withdraws the underlying amount from the holder's position at the
+/// current exchange rate.
+///
+/// # Arguments
+/// * `user` - The holder redeeming
+/// * `asset` - The reserve asset (None for native XLM)
+/// * `stoken_amount` - The amount of supply tokens to redeem
+///
+/// # Returns
+/// The underlying amount withdrawn
+///
+/// # Errors
+/// * `SupplyTokenError::TokenNotSet` - If the asset has no supply token
+/// * `SupplyTokenError::InvalidAmount` - If the amount is not positive
+/// * `SupplyTokenError::InsufficientBalance` - If the holder's balance is too low
+/// * `SupplyTokenError::UnhealthyPosition` - If the holder would become liquidatable
+/// * `SupplyTokenError::OperationPaused` - If withdrawals are paused
+/// * `SupplyTokenError::PriceNotAvailable` - If the health check has no valid price
+pub fn redeem(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    stoken_amount: i128,
+) -> Result<i128, SupplyTokenError> {
+    let rate = get_exchange_rate(env, asset.clone())?;
+    if stoken_amount <= 0 {
+        return Err(SupplyTokenError::InvalidAmount);
+    }
+    let underlying = stoken_amount
+        .checked_mul(rate)
+        .map(|value| value / EXCHANGE_RATE_SCALE)
+        .ok_or(SupplyTokenError::InvalidAmount)?;
+
+    crate::cross_asset::cross_asset_withdraw(env, user.clone(), asset.clone(), underlying)
+        .map_err(|e| match e {
+            CrossAssetError::InsufficientCollateral => SupplyTokenError::InsufficientBalance,
+            CrossAssetError::UnhealthyPosition => SupplyTokenError::UnhealthyPosition,
+            CrossAssetError::OperationPaused => SupplyTokenError::OperationPaused,
+            _ => SupplyTokenError::PriceNotAvailable,
+        })?;
+
+    emit_supply_token_redeemed(
+        env,
+        SupplyTokenRedeemedEvent {
+            user,
+            asset,
+            stoken_amount,
+            underlying,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(underlying)
+}
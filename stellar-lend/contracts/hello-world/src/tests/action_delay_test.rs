@@ -0,0 +1,92 @@
+#![cfg(test)]
+
+use crate::action_delay::ActionDelayError;
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::withdraw::{withdraw_collateral, WithdrawError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+/// Contract with a user holding 2_000 tokens approved to the contract
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let user = Address::generate(env);
+    StellarAssetClient::new(env, &token).mint(&user, &2_000);
+    TokenClient::new(env, &token).approve(
+        &user,
+        &contract_id,
+        &2_000,
+        &(env.ledger().sequence() + 100),
+    );
+    (contract_id, client, admin, token, user)
+}
+
+#[test]
+fn test_deposit_blocks_borrow_and_withdraw_until_delay_passes() {
+    let env = Env::default();
+    let (contract_id, client, admin, token, user) = setup(&env);
+    let asset = Some(token.clone());
+
+    client.set_action_delay(&admin, &1);
+    client.deposit_collateral(&user, &asset, &1_000);
+    let deposit_ledger = env.ledger().sequence();
+    assert_eq!(client.get_action_unlock_ledger(&user), deposit_ledger + 1);
+
+    // Same ledger: deposit -> borrow / withdraw is rejected
+    assert!(client.try_borrow_asset(&user, &asset, &100).is_err());
+    assert!(client.try_withdraw_collateral(&user, &asset, &100).is_err());
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            borrow_asset(&env, user.clone(), asset.clone(), 100),
+            Err(BorrowError::ActionDelayActive)
+        );
+        assert_eq!(
+            withdraw_collateral(&env, user.clone(), asset.clone(), 100),
+            Err(WithdrawError::ActionDelayActive)
+        );
+    });
+
+    // Next ledger: both go through
+    env.ledger()
+        .with_mut(|li| li.sequence_number = deposit_ledger + 1);
+    client.borrow_asset(&user, &asset, &100);
+    client.withdraw_collateral(&user, &asset, &100);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 1_100);
+}
+
+#[test]
+fn test_action_delay_is_optional_and_admin_only() {
+    let env = Env::default();
+    let (_contract_id, client, admin, token, user) = setup(&env);
+    let asset = Some(token);
+
+    // Disabled by default: the same-ledger sequence is allowed
+    assert_eq!(client.get_action_delay(), 0);
+    client.deposit_collateral(&user, &asset, &1_000);
+    assert_eq!(client.get_action_unlock_ledger(&user), 0);
+    client.borrow_asset(&user, &asset, &100);
+    client.withdraw_collateral(&user, &asset, &100);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_action_delay(&stranger, &1),
+        Err(Ok(ActionDelayError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_action_delay(&admin, &721),
+        Err(Ok(ActionDelayError::InvalidDelay))
+    );
+    client.set_action_delay(&admin, &10);
+    assert_eq!(client.get_action_delay(), 10);
+}
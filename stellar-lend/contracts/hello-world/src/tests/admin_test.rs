@@ -26,6 +26,7 @@ use crate::admin::{
     set_admin, AdminError,
 };
 use crate::flash_loan::FlashLoanConfig;
+use crate::interest_rate::to_rate_view;
 use crate::oracle::OracleConfig;
 use crate::{HelloContract, HelloContractClient};
 use soroban_sdk::{
@@ -494,8 +495,11 @@ fn test_update_interest_rate_config_only_base_rate() {
         &None,
     );
     // Borrow rate at 0 % utilization = base rate = 110 bps (floor may apply)
-    let rate = client.get_borrow_rate();
-    assert!(rate >= 50, "borrow rate should be at or above the floor");
+    let rate = client.get_borrow_rate(&None);
+    assert!(
+        rate >= to_rate_view(50),
+        "borrow rate should be at or above the floor"
+    );
 }
 
 /// Admin can update only `kink_utilization_bps`.
@@ -569,8 +573,8 @@ fn test_update_interest_rate_config_only_spread() {
         &None,
         &Some(220_i128),
     );
-    let borrow_rate = client.get_borrow_rate();
-    let supply_rate = client.get_supply_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
+    let supply_rate = client.get_supply_rate(&None);
     assert!(
         supply_rate <= borrow_rate,
         "supply rate must remain <= borrow rate after spread update"
@@ -608,11 +612,11 @@ fn test_set_emergency_rate_adjustment_positive() {
     // +500 bps adjustment
     client.set_emergency_rate_adjustment(&admin, &500_i128);
     // Rate should increase – verify by comparing borrow rate after adjustment.
-    let rate = client.get_borrow_rate();
+    let rate = client.get_borrow_rate(&None);
     // At 0 % utilization: base_rate (100) + emergency (500) = 600, but capped at floor (50).
     // Rate must be >= floor (50 bps).
     assert!(
-        rate >= 50,
+        rate >= to_rate_view(50),
         "rate should be at least the floor after positive adjustment"
     );
 }
@@ -624,10 +628,10 @@ fn test_set_emergency_rate_adjustment_negative() {
     let (_id, admin, client) = setup(&e);
     // −50 bps adjustment (won’t push below floor)
     client.set_emergency_rate_adjustment(&admin, &-50_i128);
-    let rate = client.get_borrow_rate();
+    let rate = client.get_borrow_rate(&None);
     // Rate cannot go below the 50 bps floor.
     assert!(
-        rate >= 50,
+        rate >= to_rate_view(50),
         "rate should be clipped to floor after negative adjustment"
     );
 }
@@ -640,8 +644,11 @@ fn test_set_emergency_rate_adjustment_zero() {
     client.set_emergency_rate_adjustment(&admin, &500_i128);
     client.set_emergency_rate_adjustment(&admin, &0_i128);
     // Rate should return to base level.
-    let rate = client.get_borrow_rate();
-    assert!(rate >= 50, "rate must still be above floor after reset");
+    let rate = client.get_borrow_rate(&None);
+    assert!(
+        rate >= to_rate_view(50),
+        "rate must still be above floor after reset"
+    );
 }
 
 /// Emergency rate adjustment exceeding ±10 000 bps must be rejected.
@@ -76,6 +76,7 @@ fn test_amm_full_lifecycle() {
         min_amount_out: 9000,
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let amount_out = client.amm_swap(&user, &swap_params);
@@ -153,6 +154,7 @@ fn test_amm_swap_invalid_params() {
         min_amount_out: 5000,
         slippage_tolerance: 100,
         deadline: 1000, // Past
+        use_oracle_floor: false,
     };
 
     let result = client.try_amm_swap(&user, &swap_params);
@@ -182,6 +184,7 @@ fn test_amm_swap_slippage_exceeded() {
         min_amount_out: 9950, // Mock will return 9900 (1% slippage), so this should fail
         slippage_tolerance: 100,
         deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: false,
     };
 
     let result = client.try_amm_swap(&user, &swap_params);
@@ -6,7 +6,11 @@
 
 use crate::deposit::{DepositDataKey, ProtocolAnalytics};
 use crate::{HelloContract, HelloContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
 
 fn create_test_env() -> Env {
     let env = Env::default();
@@ -105,8 +109,8 @@ fn test_analytics_user_report_after_repay() {
     let (env, contract_id, client, _admin, user, native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
     let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &native_asset);
-    token_client.mint(&user, &1000);
-    token_client.approve(&user, &contract_id, &1000, &(env.ledger().sequence() + 100));
+    token_client.mint(&user, &6000);
+    token_client.approve(&user, &contract_id, &6000, &(env.ledger().sequence() + 100));
 
     client.deposit_collateral(&user, &None, &5000);
     client.borrow_asset(&user, &None, &1000);
@@ -158,3 +162,121 @@ fn test_analytics_average_borrow_rate_non_negative() {
     let report = client.get_protocol_report();
     assert!(report.metrics.average_borrow_rate >= 0);
 }
+
+// =============================================================================
+// Account data
+// =============================================================================
+
+#[test]
+fn test_account_data_matches_individual_views() {
+    let env = create_test_env();
+    let (_contract_id, admin, client) = setup_contract_with_admin(&env);
+    let user = Address::generate(&env);
+
+    let empty = client.get_account_data(&user);
+    assert_eq!(empty.total_collateral, 0);
+    assert_eq!(empty.total_debt, 0);
+    assert_eq!(empty.available_borrows, 0);
+    assert_eq!(empty.health_factor, i128::MAX);
+
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &1_000);
+    let data = client.get_account_data(&user);
+    assert_eq!(data.total_collateral, 10_000);
+    assert_eq!(data.total_debt, client.get_user_debt(&user));
+    assert_eq!(
+        data.available_borrows,
+        client.get_max_borrowable(&user, &None)
+    );
+    // The default 105% liquidation ratio counts 95.23% of the collateral
+    assert_eq!(data.liquidation_threshold, 9_523);
+    assert_eq!(data.health_factor, 95_238);
+
+    // A native liquidation threshold replaces the global ratio
+    client.set_liquidation_threshold(&admin, &None, &8_000);
+    let data = client.get_account_data(&user);
+    assert_eq!(data.liquidation_threshold, 8_000);
+    assert_eq!(data.health_factor, 80_000);
+}
+
+// =============================================================================
+// Market snapshots
+// =============================================================================
+
+#[test]
+fn test_market_snapshot_tracks_each_asset() {
+    let env = create_test_env();
+    let (contract_id, admin, client) = setup_contract_with_admin(&env);
+    let user = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&user, &3_000);
+    TokenClient::new(&env, &token).approve(
+        &user,
+        &contract_id,
+        &3_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    client.deposit_collateral(&user, &Some(token.clone()), &3_000);
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &2_500);
+    env.as_contract(&contract_id, || {
+        crate::reserve::accrue_reserve(&env, None, 1_000).unwrap();
+    });
+
+    let native = client.get_market_snapshot(&None);
+    assert_eq!(native.asset, None);
+    assert_eq!(native.total_supplied, 10_000);
+    assert_eq!(native.total_borrowed, 2_500);
+    assert_eq!(native.total_reserves, 100);
+    assert_eq!(native.utilization_rate, 2_500);
+    assert_eq!(native.timestamp, env.ledger().timestamp());
+
+    let market = client.get_market_snapshot(&Some(token.clone()));
+    assert_eq!(market.total_supplied, 3_000);
+    assert_eq!(market.total_borrowed, 0);
+    assert_eq!(market.total_reserves, 0);
+    assert_eq!(market.utilization_rate, 0);
+    // Core reserves share the pool's rates
+    assert_eq!(market.borrow_rate, native.borrow_rate);
+    assert_eq!(market.supply_rate, native.supply_rate);
+}
+
+#[test]
+fn test_protocol_overview_values_every_reserve() {
+    let env = create_test_env();
+    let (contract_id, admin, client) = setup_contract_with_admin(&env);
+    let user = Address::generate(&env);
+    let overview = client.get_protocol_overview();
+    assert_eq!(overview.active_reserves, 0);
+    assert_eq!(overview.total_value_locked, 0);
+
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&user, &3_000);
+    TokenClient::new(&env, &token).approve(
+        &user,
+        &contract_id,
+        &3_000,
+        &(env.ledger().sequence() + 100),
+    );
+    client.update_price_feed(&admin, &token, &20_000_000, &7, &admin);
+
+    client.deposit_collateral(&user, &Some(token.clone()), &3_000);
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &2_500);
+
+    // The token is worth 2 units; the unpriced native asset counts at face value
+    let overview = client.get_protocol_overview();
+    assert_eq!(overview.total_value_locked, 16_000);
+    assert_eq!(overview.total_debt, 2_500);
+    assert_eq!(overview.active_reserves, 2);
+    assert!(!overview.paused);
+    assert_eq!(overview.deficit, 0);
+
+    client.set_emergency_pause(&admin, &true);
+    assert!(client.get_protocol_overview().paused);
+}
@@ -4,6 +4,7 @@
 //! Covers interest rate config, risk params, and per-parameter validation.
 
 use crate::deposit::{DepositDataKey, ProtocolAnalytics};
+use crate::interest_rate::to_rate_view;
 use crate::{HelloContract, HelloContractClient};
 use soroban_sdk::{testutils::Address as _, Address, Env};
 
@@ -56,8 +57,8 @@ fn test_update_interest_rate_config_base_rate() {
         &None,
         &None,
     );
-    let rate = client.get_borrow_rate();
-    assert!(rate >= 200);
+    let rate = client.get_borrow_rate(&None);
+    assert!(rate >= to_rate_view(200));
 }
 
 #[test]
@@ -75,8 +76,8 @@ fn test_update_interest_rate_config_kink() {
         &None,
         &None,
     );
-    let util = client.get_utilization();
-    assert_eq!(util, 5000);
+    let util = client.get_utilization(&None);
+    assert_eq!(util, to_rate_view(5000));
 }
 
 #[test]
@@ -93,8 +94,8 @@ fn test_update_interest_rate_config_spread() {
         &None,
         &Some(300),
     );
-    let borrow_rate = client.get_borrow_rate();
-    let supply_rate = client.get_supply_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
+    let supply_rate = client.get_supply_rate(&None);
     assert!(borrow_rate >= supply_rate);
 }
 
@@ -113,9 +114,9 @@ fn test_interest_rate_config_floor_ceiling_enforcement() {
         &None,
         &None,
     );
-    let rate = client.get_borrow_rate();
-    assert!(rate >= 100);
-    assert!(rate <= 10000);
+    let rate = client.get_borrow_rate(&None);
+    assert!(rate >= to_rate_view(100));
+    assert!(rate <= to_rate_view(10000));
 }
 
 // =============================================================================
@@ -0,0 +1,153 @@
+#![cfg(test)]
+
+use crate::auction::AuctionError;
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+/// Borrower with 1_000 of collateral and 850 of debt in the same token,
+/// liquidatable under an 80% liquidation threshold
+fn setup(env: &Env) -> (Address, Address, Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    let borrower = Address::generate(env);
+    StellarAssetClient::new(env, &token).mint(&contract_id, &1_000);
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 1_000,
+                debt: 850,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+    (contract_id, admin, token, borrower, client)
+}
+
+fn collateral_balance(env: &Env, contract_id: &Address, user: &Address) -> i128 {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&DepositDataKey::CollateralBalance(user.clone()))
+            .unwrap_or(0)
+    })
+}
+
+fn funded_bidder(env: &Env, contract_id: &Address, token: &Address) -> Address {
+    let bidder = Address::generate(env);
+    StellarAssetClient::new(env, token).mint(&bidder, &1_000);
+    TokenClient::new(env, token).approve(
+        &bidder,
+        contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+    bidder
+}
+
+#[test]
+fn test_auction_price_falls_until_settlement() {
+    let env = Env::default();
+    let (contract_id, admin, token, borrower, client) = setup(&env);
+    let asset = Some(token.clone());
+    let bidder = funded_bidder(&env, &contract_id, &token);
+
+    assert_eq!(
+        client.try_start_liquidation_auction(&borrower, &asset, &asset),
+        Err(Ok(AuctionError::NotLiquidatable))
+    );
+    client.set_liquidation_threshold(&admin, &asset, &8_000);
+    client.set_auction_duration(&admin, &1_000);
+
+    // Half the debt is auctioned against that much collateral plus the 10% bonus
+    let auction = client
+        .start_liquidation_auction(&borrower, &asset, &asset)
+        .unwrap();
+    assert_eq!(auction.tab, 425);
+    assert_eq!(auction.base_collateral, 425);
+    assert_eq!(auction.lot, 467);
+    assert_eq!(collateral_balance(&env, &contract_id, &borrower), 533);
+    assert_eq!(
+        client.try_start_liquidation_auction(&borrower, &asset, &asset),
+        Err(Ok(AuctionError::AuctionActive))
+    );
+    assert_eq!(
+        client.try_settle_liquidation_auction(&borrower),
+        Err(Ok(AuctionError::AuctionNotFinished))
+    );
+
+    // No bonus at the start, half of it halfway through
+    assert_eq!(
+        client.bid_liquidation_auction(&bidder, &borrower, &100),
+        100
+    );
+    env.ledger().with_mut(|li| li.timestamp += 500);
+    assert_eq!(client.quote_auction_bid(&borrower, &100), 104);
+    assert_eq!(
+        client.bid_liquidation_auction(&bidder, &borrower, &100),
+        104
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&bidder), 1_004);
+    assert_eq!(client.get_user_debt(&borrower), 650);
+
+    // Unsold collateral goes back to the borrower
+    env.ledger().with_mut(|li| li.timestamp += 500);
+    assert_eq!(
+        client.try_bid_liquidation_auction(&bidder, &borrower, &100),
+        Err(Ok(AuctionError::AuctionExpired))
+    );
+    assert_eq!(client.settle_liquidation_auction(&borrower), 263);
+    assert_eq!(collateral_balance(&env, &contract_id, &borrower), 796);
+    assert_eq!(client.get_liquidation_auction(&borrower), None);
+}
+
+#[test]
+fn test_auction_settles_once_tab_is_repaid() {
+    let env = Env::default();
+    let (contract_id, admin, token, borrower, client) = setup(&env);
+    let asset = Some(token.clone());
+    let bidder = funded_bidder(&env, &contract_id, &token);
+    client.set_liquidation_threshold(&admin, &asset, &8_000);
+
+    assert_eq!(
+        client.try_set_auction_duration(&admin, &0),
+        Err(Ok(AuctionError::InvalidDuration))
+    );
+    assert_eq!(
+        client.try_set_auction_duration(&Address::generate(&env), &600),
+        Err(Ok(AuctionError::Unauthorized))
+    );
+    assert_eq!(client.get_auction_duration(), 3_600);
+
+    client.start_liquidation_auction(&borrower, &asset, &asset);
+    env.ledger().with_mut(|li| li.timestamp += 1_800);
+
+    // Bids are capped at the remaining tab
+    assert_eq!(
+        client.bid_liquidation_auction(&bidder, &borrower, &1_000),
+        446
+    );
+    let auction = client.get_liquidation_auction(&borrower).unwrap();
+    assert_eq!(auction.tab_remaining, 0);
+    assert_eq!(auction.lot_remaining, 21);
+    assert_eq!(client.settle_liquidation_auction(&borrower), 21);
+    assert_eq!(client.get_user_debt(&borrower), 425);
+    assert_eq!(collateral_balance(&env, &contract_id, &borrower), 554);
+}
@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use crate::auto_deleverage::{AutoDeleverageConfig, AutoDeleverageError};
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+struct ProtectionTest {
+    env: Env,
+    client: HelloContractClient<'static>,
+    usdc: Address,
+    xlm: Address,
+    borrower: Address,
+}
+
+/// Borrower with 1_000 XLM of collateral and 900 USDC of debt, and a 50/50
+/// USDC/XLM pool hosted by the protocol's AMM router
+fn setup() -> ProtectionTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    StellarAssetClient::new(&env, &usdc).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &xlm).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &xlm).mint(&contract_id, &1_000);
+    let borrower = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        stellarlend_amm::update_amm_settings(
+            &env,
+            admin.clone(),
+            stellarlend_amm::AmmSettings {
+                default_slippage: 100,
+                max_slippage: 1_000,
+                swap_enabled: true,
+                liquidity_enabled: true,
+                auto_swap_threshold: 10_000,
+                pair_max_slippage: soroban_sdk::Map::new(&env),
+                max_swap_history: 1000,
+                swap_history_retention: 0,
+                max_deadline_window: 3600,
+            },
+        )
+        .unwrap();
+        stellarlend_amm::lbp::create_lbp_pool(
+            &env,
+            admin.clone(),
+            usdc.clone(),
+            xlm.clone(),
+            10_000,
+            10_000,
+            stellarlend_amm::WeightSchedule {
+                start_time: 0,
+                end_time: 1_000,
+                start_weight_a: 5_000,
+                end_weight_a: 5_000,
+            },
+            0,
+        )
+        .unwrap();
+
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 1_000,
+                debt: 900,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+
+    ProtectionTest {
+        env,
+        client,
+        usdc,
+        xlm,
+        borrower,
+    }
+}
+
+fn config(t: &ProtectionTest, trigger_health_factor: i128) -> AutoDeleverageConfig {
+    AutoDeleverageConfig {
+        trigger_health_factor,
+        collateral_asset: t.xlm.clone(),
+        debt_asset: t.usdc.clone(),
+        max_amount: 100,
+        fee_bps: 100,
+    }
+}
+
+#[test]
+fn test_keeper_deleverages_below_trigger() {
+    let t = setup();
+    let keeper = Address::generate(&t.env);
+    assert_eq!(
+        t.client
+            .try_execute_auto_deleverage(&keeper, &t.borrower, &100),
+        Err(Ok(AutoDeleverageError::ProtectionNotSet))
+    );
+
+    t.client
+        .enable_auto_deleverage(&t.borrower, &config(&t, 12_000));
+    let health_before = t.client.get_account_data(&t.borrower).health_factor;
+    assert!(health_before < 12_000);
+    assert_eq!(
+        t.client
+            .try_execute_auto_deleverage(&keeper, &t.borrower, &101),
+        Err(Ok(AutoDeleverageError::InvalidAmount))
+    );
+
+    // 1 XLM goes to the keeper; 99 XLM sell for 10_000 * 99 / 10_099 = 98 USDC
+    let (remaining_debt, debt_repaid, fee) =
+        t.client.execute_auto_deleverage(&keeper, &t.borrower, &100);
+    assert_eq!((remaining_debt, debt_repaid, fee), (802, 98, 1));
+    assert_eq!(TokenClient::new(&t.env, &t.xlm).balance(&keeper), 1);
+    let account = t.client.get_account_data(&t.borrower);
+    assert_eq!(account.total_collateral, 900);
+    assert!(account.health_factor > health_before);
+}
+
+#[test]
+fn test_auto_deleverage_respects_trigger_and_config() {
+    let t = setup();
+    let keeper = Address::generate(&t.env);
+
+    // The position is healthier than this trigger
+    t.client
+        .enable_auto_deleverage(&t.borrower, &config(&t, 10_100));
+    assert_eq!(
+        t.client
+            .try_execute_auto_deleverage(&keeper, &t.borrower, &100),
+        Err(Ok(AutoDeleverageError::TriggerNotMet))
+    );
+
+    let mut invalid = config(&t, 10_000);
+    assert_eq!(
+        t.client.try_enable_auto_deleverage(&t.borrower, &invalid),
+        Err(Ok(AutoDeleverageError::InvalidConfig))
+    );
+    invalid = config(&t, 12_000);
+    invalid.fee_bps = 101;
+    assert_eq!(
+        t.client.try_enable_auto_deleverage(&t.borrower, &invalid),
+        Err(Ok(AutoDeleverageError::InvalidConfig))
+    );
+
+    t.client.disable_auto_deleverage(&t.borrower);
+    assert_eq!(t.client.get_auto_deleverage(&t.borrower), None);
+    assert_eq!(
+        t.client.try_disable_auto_deleverage(&t.borrower),
+        Err(Ok(AutoDeleverageError::ProtectionNotSet))
+    );
+}
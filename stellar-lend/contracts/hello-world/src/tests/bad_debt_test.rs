@@ -0,0 +1,179 @@
+#![cfg(test)]
+
+use crate::bad_debt::BadDebtError;
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+fn setup(env: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (contract_id, admin, client)
+}
+
+fn create_position(env: &Env, contract_id: &Address, user: &Address, collateral: i128, debt: i128) {
+    env.as_contract(contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        let position = Position {
+            collateral,
+            debt,
+            borrow_interest: 0,
+            last_accrual_time: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::Position(user.clone()), &position);
+    });
+}
+
+fn get_position(env: &Env, contract_id: &Address, user: &Address) -> Position {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+            .unwrap()
+    })
+}
+
+fn set_reserve(env: &Env, contract_id: &Address, asset: &Option<Address>, amount: i128) {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::ProtocolReserve(asset.clone()), &amount);
+    });
+}
+
+#[test]
+fn test_liquidation_realizes_bad_debt() {
+    let env = Env::default();
+    let (contract_id, _admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    create_position(&env, &contract_id, &borrower, 500, 1_000);
+
+    // Seizing 500 * 1.1 exhausts the collateral with 500 still owed
+    client.liquidate(&liquidator, &borrower, &None, &None, &500);
+
+    let position = get_position(&env, &contract_id, &borrower);
+    assert_eq!(position.collateral, 0);
+    assert_eq!(position.debt, 0);
+    assert_eq!(client.get_bad_debt(&None), 500);
+    assert_eq!(client.get_total_shortfall(), 500);
+}
+
+#[test]
+fn test_partial_liquidation_leaves_no_bad_debt() {
+    let env = Env::default();
+    let (contract_id, _admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    create_position(&env, &contract_id, &borrower, 1_000, 1_000);
+
+    client.liquidate(&liquidator, &borrower, &None, &None, &500);
+
+    let position = get_position(&env, &contract_id, &borrower);
+    assert_eq!(position.collateral, 450);
+    assert_eq!(position.debt, 500);
+    assert_eq!(client.get_bad_debt(&None), 0);
+    assert_eq!(client.get_total_shortfall(), 0);
+}
+
+#[test]
+fn test_write_off_values_shortfall_with_oracle() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let token = Some(Address::generate(&env));
+    let borrower = Address::generate(&env);
+
+    let result = client.try_write_off_bad_debt(&admin, &borrower, &token);
+    assert_eq!(result, Err(Ok(BadDebtError::NoPosition)));
+
+    create_position(&env, &contract_id, &borrower, 100, 300);
+    let attacker = Address::generate(&env);
+    let result = client.try_write_off_bad_debt(&attacker, &borrower, &token);
+    assert_eq!(result, Err(Ok(BadDebtError::Unauthorized)));
+    let result = client.try_write_off_bad_debt(&admin, &borrower, &token);
+    assert_eq!(result, Err(Ok(BadDebtError::CollateralRemaining)));
+
+    create_position(&env, &contract_id, &borrower, 0, 300);
+    client.update_price_feed(
+        &admin,
+        token.as_ref().unwrap(),
+        &(2 * PRICE_ONE),
+        &7,
+        &admin,
+    );
+    assert_eq!(client.write_off_bad_debt(&admin, &borrower, &token), 300);
+    assert_eq!(get_position(&env, &contract_id, &borrower).debt, 0);
+    assert_eq!(client.get_bad_debt(&token), 300);
+    assert_eq!(client.get_total_shortfall(), 600);
+
+    let result = client.try_write_off_bad_debt(&admin, &borrower, &token);
+    assert_eq!(result, Err(Ok(BadDebtError::NoDebt)));
+}
+
+#[test]
+fn test_cover_bad_debt_from_reserve() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    create_position(&env, &contract_id, &borrower, 0, 500);
+    client.write_off_bad_debt(&admin, &borrower, &None);
+
+    let result = client.try_cover_bad_debt(&admin, &None, &200);
+    assert_eq!(result, Err(Ok(BadDebtError::InsufficientReserve)));
+
+    set_reserve(&env, &contract_id, &None, 1_000);
+    let result = client.try_cover_bad_debt(&admin, &None, &0);
+    assert_eq!(result, Err(Ok(BadDebtError::InvalidAmount)));
+    let result = client.try_cover_bad_debt(&admin, &None, &501);
+    assert_eq!(result, Err(Ok(BadDebtError::ExceedsBadDebt)));
+
+    assert_eq!(client.cover_bad_debt(&admin, &None, &200), 300);
+    assert_eq!(client.get_total_shortfall(), 300);
+    let reserve = env.as_contract(&contract_id, || {
+        crate::reserve::get_reserve_balance(&env, None)
+    });
+    assert_eq!(reserve, 800);
+}
+
+#[test]
+fn test_treasury_burns_reserves_against_deficit() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    create_position(&env, &contract_id, &borrower, 0, 500);
+
+    let result = client.try_burn_reserves_for_deficit(&admin, &None);
+    assert_eq!(result, Err(Ok(BadDebtError::NoDebt)));
+    client.write_off_bad_debt(&admin, &borrower, &None);
+    assert_eq!(client.get_deficit(&None), 500);
+    assert_eq!(get_position(&env, &contract_id, &borrower).debt, 0);
+
+    let result = client.try_burn_reserves_for_deficit(&admin, &None);
+    assert_eq!(result, Err(Ok(BadDebtError::InsufficientReserve)));
+    set_reserve(&env, &contract_id, &None, 300);
+
+    let result = client.try_burn_reserves_for_deficit(&treasury, &None);
+    assert_eq!(result, Err(Ok(BadDebtError::Unauthorized)));
+    client.set_treasury_address(&admin, &treasury);
+    assert_eq!(client.burn_reserves_for_deficit(&treasury, &None), 300);
+    assert_eq!(client.get_deficit(&None), 200);
+
+    set_reserve(&env, &contract_id, &None, 1_000);
+    assert_eq!(client.burn_reserves_for_deficit(&admin, &None), 200);
+    assert_eq!(client.get_deficit(&None), 0);
+    let reserve = env.as_contract(&contract_id, || {
+        crate::reserve::get_reserve_balance(&env, None)
+    });
+    assert_eq!(reserve, 800);
+}
@@ -0,0 +1,96 @@
+#![cfg(test)]
+
+use crate::batch_actions::{BatchAction, BatchActionError, MAX_BATCH_ACTIONS};
+use soroban_sdk::{
+    token::{StellarAssetClient, TokenClient},
+    vec, Vec,
+};
+
+#[test]
+fn test_execute_checks_collateral_ratio_once_at_the_end() {
+    let (env, contract_id, client, _admin, user, native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    StellarAssetClient::new(&env, &native_asset).mint(&user, &20_000);
+    TokenClient::new(&env, &native_asset).approve(
+        &user,
+        &contract_id,
+        &20_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    // Deposit and borrow under one signature
+    let results = client.execute(
+        &user,
+        &vec![
+            &env,
+            BatchAction::Deposit(None, 10_000),
+            BatchAction::Borrow(None, 8_000),
+        ],
+    );
+    assert_eq!(results, vec![&env, 10_000, 8_000]);
+    assert_eq!(client.get_user_debt(&user), 8_000);
+
+    // On its own the withdrawal would leave a 100% ratio
+    assert!(client
+        .try_withdraw_collateral(&user, &None, &2_000)
+        .is_err());
+    assert_eq!(
+        client.try_execute(&user, &vec![&env, BatchAction::Withdraw(None, 2_000)]),
+        Err(Ok(BatchActionError::InsufficientCollateralRatio))
+    );
+
+    // The repayment later in the batch makes room for it
+    let results = client.execute(
+        &user,
+        &vec![
+            &env,
+            BatchAction::Withdraw(None, 2_000),
+            BatchAction::Repay(None, 3_000),
+        ],
+    );
+    assert_eq!(results, vec![&env, 8_000, 5_000]);
+    let account = client.get_account_data(&user);
+    assert_eq!(account.total_collateral, 8_000);
+    assert_eq!(account.total_debt, 5_000);
+}
+
+#[test]
+fn test_execute_reverts_the_whole_batch() {
+    let (env, contract_id, client, _admin, user, native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    StellarAssetClient::new(&env, &native_asset).mint(&user, &10_000);
+    TokenClient::new(&env, &native_asset).approve(
+        &user,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    assert_eq!(
+        client.try_execute(&user, &vec![&env]),
+        Err(Ok(BatchActionError::EmptyBatch))
+    );
+    let mut actions = Vec::new(&env);
+    for _ in 0..=MAX_BATCH_ACTIONS {
+        actions.push_back(BatchAction::Deposit(None, 100));
+    }
+    assert_eq!(
+        client.try_execute(&user, &actions),
+        Err(Ok(BatchActionError::TooManyActions))
+    );
+
+    // The deposit is rolled back with the rejected withdrawal
+    assert_eq!(
+        client.try_execute(
+            &user,
+            &vec![
+                &env,
+                BatchAction::Deposit(None, 5_000),
+                BatchAction::Withdraw(None, 6_000),
+            ],
+        ),
+        Err(Ok(BatchActionError::ActionFailed))
+    );
+    assert_eq!(client.get_account_data(&user).total_collateral, 0);
+    assert_eq!(TokenClient::new(&env, &native_asset).balance(&user), 10_000);
+}
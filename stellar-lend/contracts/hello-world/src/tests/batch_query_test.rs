@@ -0,0 +1,78 @@
+#![cfg(test)]
+
+use crate::batch_query::{BatchQueryError, QueryRequest, QueryResult, MAX_QUERIES};
+use crate::cross_asset::AssetConfig;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Vec};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+#[test]
+fn test_query_many_answers_in_request_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &None,
+        &AssetConfig {
+            asset: None,
+            collateral_factor: 7_500,
+            liquidation_threshold: 8_000,
+            reserve_factor: 1_000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: PRICE_ONE,
+            price_updated_at: env.ledger().timestamp(),
+        },
+    );
+    let user = Address::generate(&env);
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &1_000);
+    client.cross_asset_deposit(&user, &None, &2_000);
+    client.cross_asset_borrow(&user, &None, &500);
+
+    let unknown = Some(Address::generate(&env));
+    let results = client.query_many(&vec![
+        &env,
+        QueryRequest::AccountData(user.clone()),
+        QueryRequest::AssetPosition(user.clone(), None),
+        QueryRequest::HealthFactor(user.clone()),
+        QueryRequest::Price(None),
+        QueryRequest::Price(unknown),
+        QueryRequest::ReserveConfig(None),
+    ]);
+    assert_eq!(results.len(), 6);
+    assert_eq!(
+        results.get_unchecked(0),
+        QueryResult::AccountData(client.get_account_data(&user))
+    );
+    assert_eq!(
+        results.get_unchecked(1),
+        QueryResult::AssetPosition(client.get_user_asset_position(&user, &None))
+    );
+    assert_eq!(
+        results.get_unchecked(2),
+        QueryResult::HealthFactor(2_000 * 8_000 / 500)
+    );
+    assert_eq!(results.get_unchecked(3), QueryResult::Price(PRICE_ONE));
+    assert_eq!(results.get_unchecked(4), QueryResult::Unavailable);
+    assert_eq!(
+        results.get_unchecked(5),
+        QueryResult::ReserveConfig(client.get_reserve_config(&None))
+    );
+
+    let mut requests = Vec::new(&env);
+    for _ in 0..=MAX_QUERIES {
+        requests.push_back(QueryRequest::HealthFactor(user.clone()));
+    }
+    assert_eq!(
+        client.try_query_many(&requests),
+        Err(Ok(BatchQueryError::TooManyQueries))
+    );
+}
@@ -0,0 +1,126 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+struct SwapTest {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'static>,
+    usdc: Address,
+    xlm: Address,
+}
+
+fn setup() -> SwapTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    // Seed a 50/50 USDC/XLM pool hosted by the protocol's AMM router
+    StellarAssetClient::new(&env, &usdc).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &xlm).mint(&admin, &10_000);
+    env.as_contract(&contract_id, || {
+        stellarlend_amm::update_amm_settings(
+            &env,
+            admin.clone(),
+            stellarlend_amm::AmmSettings {
+                default_slippage: 100,
+                max_slippage: 1_000,
+                swap_enabled: true,
+                liquidity_enabled: true,
+                auto_swap_threshold: 10_000,
+                pair_max_slippage: soroban_sdk::Map::new(&env),
+                max_swap_history: 1000,
+                swap_history_retention: 0,
+                max_deadline_window: 3600,
+            },
+        )
+        .unwrap();
+        stellarlend_amm::lbp::create_lbp_pool(
+            &env,
+            admin.clone(),
+            usdc.clone(),
+            xlm.clone(),
+            10_000,
+            10_000,
+            stellarlend_amm::WeightSchedule {
+                start_time: 0,
+                end_time: 1_000,
+                start_weight_a: 5_000,
+                end_weight_a: 5_000,
+            },
+            0,
+        )
+        .unwrap();
+    });
+
+    SwapTest {
+        env,
+        contract_id,
+        client,
+        usdc,
+        xlm,
+    }
+}
+
+fn get_position(t: &SwapTest, user: &Address) -> Option<Position> {
+    t.env.as_contract(&t.contract_id, || {
+        t.env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    })
+}
+
+#[test]
+fn test_borrow_and_swap_delivers_receive_asset() {
+    let t = setup();
+    let user = Address::generate(&t.env);
+    t.client.deposit_collateral(&user, &None, &1_000);
+
+    // 100 USDC sells for 10_000 * 100 / 10_100 = 99 XLM
+    let (total_debt, received) = t.client.borrow_and_swap(&user, &t.usdc, &100, &t.xlm, &90);
+    assert_eq!(total_debt, 100);
+    assert_eq!(received, 99);
+
+    // Debt is booked in the borrowed asset; only the swap output is paid out
+    assert_eq!(get_position(&t, &user).unwrap().debt, 100);
+    assert_eq!(TokenClient::new(&t.env, &t.xlm).balance(&user), 99);
+    assert_eq!(TokenClient::new(&t.env, &t.usdc).balance(&user), 0);
+}
+
+#[test]
+fn test_borrow_and_swap_reverts_on_slippage() {
+    let t = setup();
+    let user = Address::generate(&t.env);
+    t.client.deposit_collateral(&user, &None, &1_000);
+
+    assert!(t
+        .client
+        .try_borrow_and_swap(&user, &t.usdc, &100, &t.xlm, &100)
+        .is_err());
+    assert!(t
+        .client
+        .try_borrow_and_swap(&user, &t.usdc, &100, &t.usdc, &90)
+        .is_err());
+
+    // Nothing was borrowed
+    assert_eq!(get_position(&t, &user).unwrap().debt, 0);
+    assert_eq!(TokenClient::new(&t.env, &t.xlm).balance(&user), 0);
+}
@@ -0,0 +1,98 @@
+#![cfg(test)]
+
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::reserve::ReserveError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+fn setup(env: &Env) -> (Address, Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (contract_id, admin, token, client)
+}
+
+fn fund(env: &Env, contract_id: &Address, token: &Address, user: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(user, &amount);
+    TokenClient::new(env, token).approve(
+        user,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_borrow_cap_limits_borrows_despite_collateral() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    let user = Address::generate(&env);
+    fund(&env, &contract_id, &token, &user, 2_000_000);
+    client.deposit_collateral(&user, &asset, &1_000_000);
+
+    assert_eq!(client.get_remaining_borrow_capacity(&asset), i128::MAX);
+    assert_eq!(
+        client.try_set_borrow_cap(&admin, &asset, &-1),
+        Err(Ok(ReserveError::InvalidBorrowCap))
+    );
+    assert_eq!(
+        client.try_set_borrow_cap(&Address::generate(&env), &asset, &10_000),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+    client.set_borrow_cap(&admin, &asset, &10_000);
+    assert_eq!(client.get_reserve_config(&asset).borrow_cap, 10_000);
+    assert_eq!(client.get_max_borrowable(&user, &asset), 10_000);
+
+    client.borrow_asset(&user, &asset, &6_000);
+    assert_eq!(client.get_remaining_borrow_capacity(&asset), 4_000);
+    assert_eq!(client.get_max_borrowable(&user, &asset), 4_000);
+
+    // Plenty of collateral, but the asset is at its ceiling
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            borrow_asset(&env, user.clone(), asset.clone(), 4_001),
+            Err(BorrowError::BorrowCapExceeded)
+        );
+    });
+    assert!(client.try_borrow_asset(&user, &asset, &4_001).is_err());
+    client.borrow_asset(&user, &asset, &4_000);
+    assert_eq!(client.get_remaining_borrow_capacity(&asset), 0);
+
+    // Repaid principal frees capacity
+    client.repay_debt(&user, &asset, &2_500);
+    assert_eq!(client.get_remaining_borrow_capacity(&asset), 2_500);
+
+    // Caps are per asset
+    assert_eq!(client.get_remaining_borrow_capacity(&None), i128::MAX);
+}
+
+#[test]
+fn test_lowering_borrow_cap_keeps_existing_debt() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    let user = Address::generate(&env);
+    fund(&env, &contract_id, &token, &user, 1_000_000);
+    client.deposit_collateral(&user, &asset, &1_000_000);
+
+    client.borrow_asset(&user, &asset, &5_000);
+    client.set_borrow_cap(&admin, &asset, &3_000);
+    assert_eq!(client.get_remaining_borrow_capacity(&asset), 0);
+    assert!(client.try_borrow_asset(&user, &asset, &1).is_err());
+    assert_eq!(client.get_user_debt(&user), 5_000);
+
+    // Removing the cap makes the asset unlimited again
+    client.set_borrow_cap(&admin, &asset, &0);
+    client.borrow_asset(&user, &asset, &1_000);
+    assert_eq!(client.get_remaining_borrow_capacity(&asset), i128::MAX);
+}
@@ -92,7 +92,7 @@ fn test_bridge_deposit_withdraw() {
         let config = AssetConfig {
             asset: Some(asset.clone()),
             collateral_factor: 7500,
-            borrow_factor: 8000,
+            liquidation_threshold: 8000,
             reserve_factor: 1000,
             max_supply: 1_000_000,
             max_borrow: 1_000_000,
@@ -0,0 +1,413 @@
+#![cfg(test)]
+
+use crate::cdp::{CdpCollateralType, CdpError};
+use crate::oracle::OracleConfig;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol,
+};
+
+const PRICE_ONE: i128 = 10_000_000;
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+struct CdpTest {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'static>,
+    admin: Address,
+    stablecoin: Address,
+    collateral: Address,
+}
+
+fn collateral_type() -> CdpCollateralType {
+    CdpCollateralType {
+        min_collateral_ratio_bps: 15_000,
+        liquidation_ratio_bps: 13_000,
+        liquidation_penalty_bps: 1_000,
+        stability_fee_bps: 500,
+        debt_ceiling: 100_000,
+        enabled: true,
+    }
+}
+
+fn setup() -> CdpTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    // The contract administers the stablecoin so it can mint and burn it
+    let stablecoin = env
+        .register_stellar_asset_contract_v2(contract_id.clone())
+        .address();
+    let collateral = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    client.update_price_feed(&admin, &stablecoin, &PRICE_ONE, &7, &admin);
+    client.update_price_feed(&admin, &collateral, &(2 * PRICE_ONE), &7, &admin);
+
+    client.initialize_cdp(&admin, &stablecoin, &1_000_000);
+    client.set_cdp_collateral_type(&admin, &collateral, &collateral_type());
+
+    CdpTest {
+        env,
+        contract_id,
+        client,
+        admin,
+        stablecoin,
+        collateral,
+    }
+}
+
+fn funded_user(t: &CdpTest, amount: i128) -> Address {
+    let user = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.collateral).mint(&user, &amount);
+    user
+}
+
+#[test]
+fn test_deposit_and_mint() {
+    let t = setup();
+    let user = funded_user(&t, 10_000);
+
+    assert_eq!(t.client.cdp_deposit(&user, &t.collateral, &1_000), 1_000);
+
+    // 1_000 collateral worth 2_000 supports 1_333 at a 150% minimum ratio
+    assert_eq!(t.client.cdp_mint(&user, &t.collateral, &1_300), 1_300);
+    assert_eq!(
+        TokenClient::new(&t.env, &t.stablecoin).balance(&user),
+        1_300
+    );
+    assert_eq!(t.client.get_cdp_total_debt(), 1_300);
+    assert_eq!(
+        t.client.get_cdp_collateral_ratio(&user, &t.collateral),
+        15_384
+    );
+
+    let result = t.client.try_cdp_mint(&user, &t.collateral, &100);
+    assert_eq!(result, Err(Ok(CdpError::Undercollateralized)));
+}
+
+#[test]
+fn test_collateral_type_validation() {
+    let t = setup();
+    let other = Address::generate(&t.env);
+
+    let mut params = collateral_type();
+    params.min_collateral_ratio_bps = 12_000;
+    let result = t
+        .client
+        .try_set_cdp_collateral_type(&t.admin, &other, &params);
+    assert_eq!(result, Err(Ok(CdpError::InvalidConfig)));
+
+    let result = t
+        .client
+        .try_set_cdp_collateral_type(&t.admin, &t.stablecoin, &collateral_type());
+    assert_eq!(result, Err(Ok(CdpError::InvalidConfig)));
+
+    let attacker = Address::generate(&t.env);
+    let result = t
+        .client
+        .try_set_cdp_collateral_type(&attacker, &other, &collateral_type());
+    assert_eq!(result, Err(Ok(CdpError::Unauthorized)));
+
+    let user = funded_user(&t, 1_000);
+    let result = t.client.try_cdp_deposit(&user, &other, &100);
+    assert_eq!(result, Err(Ok(CdpError::CollateralNotSupported)));
+}
+
+#[test]
+fn test_debt_ceilings() {
+    let t = setup();
+    let user = funded_user(&t, 1_000_000);
+    t.client.cdp_deposit(&user, &t.collateral, &1_000_000);
+
+    let result = t.client.try_cdp_mint(&user, &t.collateral, &100_001);
+    assert_eq!(result, Err(Ok(CdpError::DebtCeilingExceeded)));
+
+    t.client.set_cdp_debt_ceiling(&t.admin, &50_000);
+    let result = t.client.try_cdp_mint(&user, &t.collateral, &50_001);
+    assert_eq!(result, Err(Ok(CdpError::DebtCeilingExceeded)));
+
+    t.client.cdp_mint(&user, &t.collateral, &50_000);
+
+    let mut params = collateral_type();
+    params.enabled = false;
+    t.client
+        .set_cdp_collateral_type(&t.admin, &t.collateral, &params);
+    let result = t.client.try_cdp_mint(&user, &t.collateral, &1);
+    assert_eq!(result, Err(Ok(CdpError::CollateralDisabled)));
+}
+
+#[test]
+fn test_stability_fee_and_repay() {
+    let t = setup();
+    let user = funded_user(&t, 10_000);
+    t.client.cdp_deposit(&user, &t.collateral, &10_000);
+    t.client.cdp_mint(&user, &t.collateral, &10_000);
+
+    // 5% stability fee over one year
+    t.env
+        .ledger()
+        .with_mut(|li| li.timestamp += SECONDS_PER_YEAR);
+    let vault = t.client.get_cdp_vault(&user, &t.collateral).unwrap();
+    assert_eq!(vault.debt, 10_500);
+    assert_eq!(vault.fees, 500);
+
+    // Repayments settle the fees first
+    assert_eq!(t.client.cdp_repay(&user, &t.collateral, &10_000), 500);
+    assert_eq!(TokenClient::new(&t.env, &t.stablecoin).balance(&user), 0);
+    assert_eq!(t.client.get_cdp_fees_accrued(), 500);
+    assert_eq!(t.client.get_cdp_fees_collected(), 500);
+    let vault = t.client.get_cdp_vault(&user, &t.collateral).unwrap();
+    assert_eq!(vault.fees, 0);
+    assert_eq!(t.client.get_cdp_total_debt(), 500);
+}
+
+#[test]
+fn test_withdraw_respects_collateral_ratio() {
+    let t = setup();
+    let user = funded_user(&t, 1_000);
+    t.client.cdp_deposit(&user, &t.collateral, &1_000);
+    t.client.cdp_mint(&user, &t.collateral, &1_000);
+
+    // 250 left is worth 500, below 150% of 1_000
+    let result = t.client.try_cdp_withdraw(&user, &t.collateral, &750);
+    assert_eq!(result, Err(Ok(CdpError::Undercollateralized)));
+
+    assert_eq!(t.client.cdp_withdraw(&user, &t.collateral, &250), 750);
+
+    t.client.cdp_repay(&user, &t.collateral, &1_000);
+    assert_eq!(t.client.cdp_withdraw(&user, &t.collateral, &750), 0);
+    assert_eq!(
+        TokenClient::new(&t.env, &t.collateral).balance(&user),
+        1_000
+    );
+    assert!(t.client.get_cdp_vault(&user, &t.collateral).is_none());
+}
+
+#[test]
+fn test_liquidate_cdp() {
+    let t = setup();
+    let mut params = collateral_type();
+    params.stability_fee_bps = 5_000;
+    t.client
+        .set_cdp_collateral_type(&t.admin, &t.collateral, &params);
+
+    let user = funded_user(&t, 1_000);
+    t.client.cdp_deposit(&user, &t.collateral, &1_000);
+    t.client.cdp_mint(&user, &t.collateral, &1_300);
+
+    let liquidator = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.stablecoin).mint(&liquidator, &1_000);
+
+    let result = t
+        .client
+        .try_liquidate_cdp(&liquidator, &user, &t.collateral, &100);
+    assert_eq!(result, Err(Ok(CdpError::NotLiquidatable)));
+
+    // A year of 50% stability fees takes the debt to 1_950 (ratio ~102%)
+    t.env
+        .ledger()
+        .with_mut(|li| li.timestamp += SECONDS_PER_YEAR);
+    t.client
+        .update_price_feed(&t.admin, &t.stablecoin, &PRICE_ONE, &7, &t.admin);
+    t.client
+        .update_price_feed(&t.admin, &t.collateral, &(2 * PRICE_ONE), &7, &t.admin);
+
+    let result = t
+        .client
+        .try_liquidate_cdp(&liquidator, &user, &t.collateral, &976);
+    assert_eq!(result, Err(Ok(CdpError::ExceedsCloseFactor)));
+
+    // 600 covered at 0.5 collateral per stablecoin plus a 10% penalty
+    let (covered, seized) = t
+        .client
+        .liquidate_cdp(&liquidator, &user, &t.collateral, &600);
+    assert_eq!(covered, 600);
+    assert_eq!(seized, 330);
+
+    let vault = t.client.get_cdp_vault(&user, &t.collateral).unwrap();
+    assert_eq!(vault.debt, 1_350);
+    assert_eq!(vault.collateral, 670);
+    assert_eq!(
+        TokenClient::new(&t.env, &t.collateral).balance(&liquidator),
+        330
+    );
+    assert_eq!(
+        TokenClient::new(&t.env, &t.stablecoin).balance(&liquidator),
+        400
+    );
+}
+
+#[test]
+fn test_repay_with_collateral_through_amm() {
+    let t = setup();
+    let user = funded_user(&t, 1_000);
+    t.client.cdp_deposit(&user, &t.collateral, &1_000);
+    t.client.cdp_mint(&user, &t.collateral, &1_000);
+
+    // Deployed AMM with a 50/50 collateral/stablecoin pool
+    let amm_id = t.env.register(stellarlend_amm::AmmContract, ());
+    let amm = stellarlend_amm::AmmContractClient::new(&t.env, &amm_id);
+    amm.initialize_amm_settings(&t.admin, &100, &1_000, &10_000);
+    StellarAssetClient::new(&t.env, &t.collateral).mint(&t.admin, &10_000);
+    StellarAssetClient::new(&t.env, &t.stablecoin).mint(&t.admin, &10_000);
+    amm.create_lbp_pool(
+        &t.admin,
+        &t.collateral,
+        &t.stablecoin,
+        &10_000,
+        &10_000,
+        &stellarlend_amm::WeightSchedule {
+            start_time: 0,
+            end_time: 1_000,
+            start_weight_a: 5_000,
+            end_weight_a: 5_000,
+        },
+        &0,
+    );
+
+    // The AMM is not registered with the router yet
+    let result =
+        t.client
+            .try_cdp_repay_with_collateral(&user, &t.collateral, &100, &amm_id, &1, &1_000);
+    assert_eq!(result, Err(Ok(CdpError::SwapFailed)));
+
+    t.client.set_amm_pool(
+        &t.admin,
+        &stellarlend_amm::AmmProtocolConfig {
+            protocol_address: amm_id.clone(),
+            protocol_name: soroban_sdk::Symbol::new(&t.env, "stellarlend"),
+            enabled: true,
+            fee_tier: 0,
+            min_swap_amount: 1,
+            max_swap_amount: 1_000_000,
+            supported_pairs: soroban_sdk::Vec::new(&t.env),
+        },
+    );
+
+    // 100 collateral sells for 10_000 * 100 / 10_100 = 99 stablecoin
+    let debt = t
+        .client
+        .cdp_repay_with_collateral(&user, &t.collateral, &100, &amm_id, &90, &1_000);
+    assert_eq!(debt, 901);
+
+    let vault = t.client.get_cdp_vault(&user, &t.collateral).unwrap();
+    assert_eq!(vault.collateral, 900);
+    assert_eq!(t.client.get_cdp_total_debt(), 901);
+
+    // The collateral left this contract and the stablecoin it burned came
+    // from the AMM
+    let collateral = TokenClient::new(&t.env, &t.collateral);
+    let stablecoin = TokenClient::new(&t.env, &t.stablecoin);
+    assert_eq!(collateral.balance(&t.contract_id), 900);
+    assert_eq!(collateral.balance(&amm_id), 10_100);
+    assert_eq!(stablecoin.balance(&amm_id), 9_901);
+    assert_eq!(stablecoin.balance(&t.contract_id), 0);
+}
+
+#[test]
+fn test_liquidation_writes_off_debt_left_without_collateral() {
+    let t = setup();
+    let user = funded_user(&t, 1_000);
+    t.client.cdp_deposit(&user, &t.collateral, &1_000);
+    t.client.cdp_mint(&user, &t.collateral, &1_300);
+
+    // Collateral crashes to 0.5 in two steps: the vault's 1_000 collateral
+    // is worth 500
+    t.client.configure_oracle(
+        &t.admin,
+        &OracleConfig {
+            max_deviation_bps: 5_000,
+            max_staleness_seconds: 3_600,
+            cache_ttl_seconds: 0,
+            min_price: 1,
+            max_price: 1_000 * PRICE_ONE,
+        },
+    );
+    t.client
+        .update_price_feed(&t.admin, &t.collateral, &PRICE_ONE, &7, &t.admin);
+    t.client
+        .update_price_feed(&t.admin, &t.collateral, &(PRICE_ONE / 2), &7, &t.admin);
+    let liquidator = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.stablecoin).mint(&liquidator, &600);
+
+    // 600 covered plus the penalty is worth more than the whole vault
+    let (covered, seized) = t
+        .client
+        .liquidate_cdp(&liquidator, &user, &t.collateral, &600);
+    assert_eq!(covered, 600);
+    assert_eq!(seized, 1_000);
+
+    // The remaining 700 cannot be repaid against the vault
+    assert!(t.client.get_cdp_vault(&user, &t.collateral).is_none());
+    assert_eq!(t.client.get_cdp_total_debt(), 0);
+    assert_eq!(t.client.get_bad_debt(&Some(t.stablecoin.clone())), 700);
+}
+
+#[test]
+fn test_cdp_operations_respect_pause_and_freeze() {
+    let t = setup();
+    let user = funded_user(&t, 1_000);
+    t.client.cdp_deposit(&user, &t.collateral, &500);
+    t.client.cdp_mint(&user, &t.collateral, &100);
+
+    t.client.set_emergency_pause(&t.admin, &true);
+    let result = t.client.try_cdp_deposit(&user, &t.collateral, &100);
+    assert_eq!(result, Err(Ok(CdpError::OperationPaused)));
+    let result = t.client.try_cdp_withdraw(&user, &t.collateral, &100);
+    assert_eq!(result, Err(Ok(CdpError::OperationPaused)));
+    t.client.set_emergency_pause(&t.admin, &false);
+
+    t.client
+        .set_pause_switch(&t.admin, &Symbol::new(&t.env, "pause_borrow"), &true);
+    let result = t.client.try_cdp_mint(&user, &t.collateral, &100);
+    assert_eq!(result, Err(Ok(CdpError::OperationPaused)));
+    t.client
+        .set_pause_switch(&t.admin, &Symbol::new(&t.env, "pause_borrow"), &false);
+
+    t.client
+        .set_pause_switch(&t.admin, &Symbol::new(&t.env, "pause_repay"), &true);
+    let result = t.client.try_cdp_repay(&user, &t.collateral, &100);
+    assert_eq!(result, Err(Ok(CdpError::OperationPaused)));
+    t.client
+        .set_pause_switch(&t.admin, &Symbol::new(&t.env, "pause_repay"), &false);
+
+    t.client
+        .set_asset_frozen(&t.admin, &Some(t.collateral.clone()), &true);
+    let result = t.client.try_cdp_deposit(&user, &t.collateral, &100);
+    assert_eq!(result, Err(Ok(CdpError::AssetFrozen)));
+    let result = t.client.try_cdp_mint(&user, &t.collateral, &100);
+    assert_eq!(result, Err(Ok(CdpError::AssetFrozen)));
+    // Frozen collateral can still be repaid against
+    assert_eq!(t.client.cdp_repay(&user, &t.collateral, &100), 0);
+}
+
+#[test]
+fn test_cdp_collateral_is_escrowed() {
+    let t = setup();
+    let user = funded_user(&t, 1_000);
+    let asset = Some(t.collateral.clone());
+    let escrowed = || {
+        t.env.as_contract(&t.contract_id, || {
+            crate::reserve::get_escrowed_balance(&t.env, &asset)
+        })
+    };
+
+    t.client.cdp_deposit(&user, &t.collateral, &1_000);
+    assert_eq!(escrowed(), 1_000);
+    let liquidity = t.env.as_contract(&t.contract_id, || {
+        crate::reserve::available_liquidity(&t.env, &asset, &t.collateral)
+    });
+    assert_eq!(liquidity, 0);
+
+    t.client.cdp_withdraw(&user, &t.collateral, &400);
+    assert_eq!(escrowed(), 600);
+}
@@ -0,0 +1,129 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate, LiquidationError};
+use crate::reserve::ReserveError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+/// Borrower with 1_000 of collateral and 850 of debt in the same token,
+/// whose 80% liquidation threshold puts the health factor at 0.94
+fn setup(env: &Env) -> (Address, Address, Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.set_liquidation_threshold(&admin, &Some(token.clone()), &8_000);
+
+    let borrower = Address::generate(env);
+    StellarAssetClient::new(env, &token).mint(&contract_id, &1_000);
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 1_000,
+                debt: 850,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+    (contract_id, admin, token, borrower, client)
+}
+
+fn try_liquidate(
+    env: &Env,
+    contract_id: &Address,
+    borrower: &Address,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<(i128, i128, i128), LiquidationError> {
+    let liquidator = Address::generate(env);
+    env.as_contract(contract_id, || {
+        liquidate(
+            env,
+            liquidator,
+            borrower.clone(),
+            asset.clone(),
+            asset.clone(),
+            amount,
+        )
+    })
+}
+
+#[test]
+fn test_reserve_close_factor_overrides_global() {
+    let env = Env::default();
+    let (contract_id, admin, token, borrower, client) = setup(&env);
+    let asset = Some(token.clone());
+
+    assert_eq!(
+        client.try_set_reserve_close_factor(&admin, &asset, &10_001, &0),
+        Err(Ok(ReserveError::InvalidCloseFactor))
+    );
+    assert_eq!(
+        client.try_set_reserve_close_factor(&admin, &asset, &3_000, &10_000),
+        Err(Ok(ReserveError::InvalidCloseFactor))
+    );
+    assert_eq!(
+        client.try_set_reserve_close_factor(&Address::generate(&env), &asset, &3_000, &0),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+
+    // Global 50%: up to 425 of the 850 debt
+    assert_eq!(
+        try_liquidate(&env, &contract_id, &borrower, &asset, 426),
+        Err(LiquidationError::ExceedsCloseFactor)
+    );
+
+    client.set_reserve_close_factor(&admin, &asset, &3_000, &0);
+    let config = client.get_reserve_config(&asset);
+    assert_eq!(config.close_factor_bps, 3_000);
+    assert_eq!(config.deep_distress_hf_bps, 0);
+    assert_eq!(
+        try_liquidate(&env, &contract_id, &borrower, &asset, 256),
+        Err(LiquidationError::ExceedsCloseFactor)
+    );
+}
+
+#[test]
+fn test_deep_distress_allows_full_liquidation() {
+    let env = Env::default();
+    let (contract_id, admin, token, borrower, client) = setup(&env);
+    let asset = Some(token.clone());
+    let liquidator = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&liquidator, &850);
+    TokenClient::new(&env, &token).approve(
+        &liquidator,
+        &contract_id,
+        &850,
+        &(env.ledger().sequence() + 100),
+    );
+
+    // A health factor of 0.94 is not below 0.9
+    client.set_reserve_close_factor(&admin, &asset, &0, &9_000);
+    assert_eq!(
+        try_liquidate(&env, &contract_id, &borrower, &asset, 850),
+        Err(LiquidationError::ExceedsCloseFactor)
+    );
+
+    // It is below 0.95, so the whole debt can be cleared in one call
+    client.set_reserve_close_factor(&admin, &asset, &0, &9_500);
+    let (debt_liquidated, collateral_seized, _) =
+        client.liquidate(&liquidator, &borrower, &asset, &asset, &850);
+    assert_eq!(debt_liquidated, 850);
+    assert_eq!(collateral_seized, 935);
+    assert_eq!(client.get_user_debt(&borrower), 0);
+}
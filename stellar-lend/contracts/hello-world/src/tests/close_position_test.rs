@@ -0,0 +1,115 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+fn setup(env: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let native_asset = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::NativeAssetAddress, &native_asset);
+    });
+    (contract_id, native_asset, client)
+}
+
+fn fund(env: &Env, contract_id: &Address, native_asset: &Address, user: &Address, amount: i128) {
+    token::StellarAssetClient::new(env, native_asset).mint(user, &amount);
+    token::Client::new(env, native_asset).approve(
+        user,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+fn get_position(env: &Env, contract_id: &Address, user: &Address) -> Position {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_withdraw_all_empties_collateral() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, native_asset, client) = setup(&env);
+    let user = Address::generate(&env);
+    fund(&env, &contract_id, &native_asset, &user, 1_337);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    client.deposit_collateral(&user, &None, &337);
+
+    assert_eq!(client.withdraw_all(&user, &None), 1_337);
+    assert_eq!(get_position(&env, &contract_id, &user).collateral, 0);
+    assert_eq!(
+        token::Client::new(&env, &native_asset).balance(&user),
+        1_337
+    );
+
+    // Nothing left to close
+    assert_eq!(client.close_position(&user, &None, &None), (0, 0));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_withdraw_all_with_debt_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, native_asset, client) = setup(&env);
+    let user = Address::generate(&env);
+    fund(&env, &contract_id, &native_asset, &user, 1_000);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    client.borrow_asset(&user, &None, &500);
+
+    client.withdraw_all(&user, &None);
+}
+
+#[test]
+fn test_close_position_repays_accrued_interest() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, native_asset, client) = setup(&env);
+    let user = Address::generate(&env);
+    fund(&env, &contract_id, &native_asset, &user, 1_000_000);
+
+    client.deposit_collateral(&user, &None, &1_000_000);
+    client.borrow_asset(&user, &None, &500_000);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += 365 * 24 * 60 * 60);
+
+    fund(&env, &contract_id, &native_asset, &user, 1_000_000);
+
+    let (debt_repaid, collateral_withdrawn) = client.close_position(&user, &None, &None);
+    assert!(debt_repaid > 500_000, "accrued interest is repaid");
+    assert!(
+        collateral_withdrawn > 1_000_000,
+        "supply interest is withdrawn"
+    );
+
+    let position = get_position(&env, &contract_id, &user);
+    assert_eq!(position.debt, 0);
+    assert_eq!(position.borrow_interest, 0);
+    assert_eq!(position.collateral, 0);
+    // The collateral comes back in native XLM
+    assert_eq!(
+        token::Client::new(&env, &native_asset).balance(&user),
+        1_000_000 + collateral_withdrawn - debt_repaid
+    );
+}
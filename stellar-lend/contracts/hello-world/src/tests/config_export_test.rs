@@ -0,0 +1,169 @@
+#![cfg(test)]
+
+use crate::config_export::ConfigExportError;
+use crate::cross_asset::AssetConfig;
+use crate::oracle::OracleConfig;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol, Vec};
+use stellarlend_amm::{AmmProtocolConfig, TokenPair};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+fn asset_config(env: &Env, asset: Option<Address>, price: i128) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 1_000_000,
+        max_borrow: 500_000,
+        can_collateralize: true,
+        can_borrow: true,
+        price,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+fn deploy(env: &Env) -> (HelloContractClient<'_>, Address) {
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (client, admin)
+}
+
+/// A deployment with two reserves, a native asset, tuned oracle settings,
+/// a fallback oracle, an AMM protocol and a keeper
+fn configured_deployment(env: &Env) -> (HelloContractClient<'_>, Address) {
+    let (client, admin) = deploy(env);
+    client.initialize_ca(&admin);
+
+    let token = Address::generate(env);
+    client.initialize_asset(&None, &asset_config(env, None, PRICE_ONE));
+    client.initialize_asset(
+        &Some(token.clone()),
+        &asset_config(env, Some(token.clone()), 2 * PRICE_ONE),
+    );
+    client.set_native_asset_address(&admin, &Address::generate(env));
+
+    client.configure_oracle(
+        &admin,
+        &OracleConfig {
+            max_deviation_bps: 1_000,
+            max_staleness_seconds: 600,
+            cache_ttl_seconds: 60,
+            min_price: 1,
+            max_price: 1_000_000 * PRICE_ONE,
+        },
+    );
+    client.set_fallback_oracle(&admin, &token, &Address::generate(env));
+
+    let mut supported_pairs = Vec::new(env);
+    supported_pairs.push_back(TokenPair {
+        token_a: None,
+        token_b: Some(token.clone()),
+        pool_address: Address::generate(env),
+    });
+    client.set_amm_pool(
+        &admin,
+        &AmmProtocolConfig {
+            protocol_address: Address::generate(env),
+            protocol_name: Symbol::new(env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1_000,
+            max_swap_amount: 1_000_000_000,
+            supported_pairs,
+        },
+    );
+
+    let keeper = Symbol::new(env, "keeper");
+    let retired = Address::generate(env);
+    client.grant_role(&admin, &keeper, &Address::generate(env));
+    client.grant_role(&admin, &keeper, &retired);
+    client.revoke_role(&admin, &keeper, &retired);
+
+    (client, admin)
+}
+
+#[test]
+fn test_export_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = configured_deployment(&env);
+
+    let config = client.export_config();
+    assert_eq!(config.admin, Some(admin));
+    assert_eq!(config.reserves.len(), 2);
+    assert_eq!(config.reserves.get(1).unwrap().max_borrow, 500_000);
+    assert_eq!(config.oracle_config.max_staleness_seconds, 600);
+    assert_eq!(config.oracle_sources.len(), 2);
+    assert_eq!(config.oracle_sources.get(0).unwrap().fallback_oracle, None);
+    assert!(config
+        .oracle_sources
+        .get(1)
+        .unwrap()
+        .fallback_oracle
+        .is_some());
+    assert_eq!(config.amm_protocols.len(), 1);
+    assert_eq!(config.roles.len(), 1);
+    assert_eq!(config.risk_params.close_factor, 5_000);
+    assert_eq!(config.amm_settings.len(), 0);
+}
+
+#[test]
+fn test_import_config_reproduces_deployment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (source, _) = configured_deployment(&env);
+    let mut config = source.export_config();
+
+    let (target, target_admin) = deploy(&env);
+    let attacker = Address::generate(&env);
+    let result = target.try_import_config(&attacker, &config);
+    assert_eq!(result, Err(Ok(ConfigExportError::Unauthorized)));
+
+    target.import_config(&target_admin, &config);
+
+    config.admin = Some(target_admin.clone());
+    assert_eq!(target.export_config(), config);
+
+    let result = target.try_import_config(&target_admin, &config);
+    assert_eq!(result, Err(Ok(ConfigExportError::AlreadyConfigured)));
+}
+
+#[test]
+fn test_import_config_rejects_invalid_sections() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (source, _) = configured_deployment(&env);
+    let config = source.export_config();
+
+    let (target, admin) = deploy(&env);
+    let mut invalid = config.clone();
+    let mut reserve = invalid.reserves.get(0).unwrap();
+    reserve.liquidation_threshold = reserve.collateral_factor - 1;
+    invalid.reserves.set(0, reserve);
+    let result = target.try_import_config(&admin, &invalid);
+    assert_eq!(result, Err(Ok(ConfigExportError::InvalidConfig)));
+
+    let mut invalid = config.clone();
+    invalid.interest_rate_config.rate_floor_bps = invalid.interest_rate_config.rate_ceiling_bps + 1;
+    let result = target.try_import_config(&admin, &invalid);
+    assert_eq!(result, Err(Ok(ConfigExportError::InvalidConfig)));
+
+    // Failed imports leave the deployment untouched
+    assert_eq!(target.export_config().reserves.len(), 0);
+    target.import_config(&admin, &config);
+    assert_eq!(target.export_config().reserves.len(), 2);
+}
+
+#[test]
+fn test_export_config_requires_initialization() {
+    let env = Env::default();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+
+    let result = client.try_export_config();
+    assert_eq!(result, Err(Ok(ConfigExportError::NotInitialized)));
+}
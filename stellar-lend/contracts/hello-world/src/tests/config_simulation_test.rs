@@ -0,0 +1,117 @@
+#![cfg(test)]
+
+use crate::config_simulation::ConfigSimulationError;
+use crate::cross_asset::AssetConfig;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+fn asset_config(env: &Env, asset: Option<Address>, price: i128) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+/// Native reserve priced at 1.0 and a token reserve priced at 2.0
+///
+/// - alice: 1_000 native collateral, 300 token debt (1.33x)
+/// - bob: 500 token collateral, 100 native debt (8x)
+/// - carol: 3_000 native collateral, 100 token debt (12x)
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+
+    let token = Address::generate(env);
+    client.initialize_asset(&None, &asset_config(env, None, PRICE_ONE));
+    client.initialize_asset(
+        &Some(token.clone()),
+        &asset_config(env, Some(token.clone()), 2 * PRICE_ONE),
+    );
+
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+    let carol = Address::generate(env);
+    client.cross_asset_deposit(&alice, &None, &1_000);
+    client.cross_asset_borrow(&alice, &Some(token.clone()), &300);
+    client.cross_asset_deposit(&bob, &Some(token.clone()), &500);
+    client.cross_asset_borrow(&bob, &None, &100);
+    client.cross_asset_deposit(&carol, &None, &3_000);
+    client.cross_asset_borrow(&carol, &Some(token.clone()), &100);
+
+    (client, token)
+}
+
+#[test]
+fn test_simulate_threshold_cut() {
+    let env = Env::default();
+    let (client, _token) = setup(&env);
+
+    // At a 50% threshold alice's 1_000 backs only 500 against 600 owed
+    let mut config = asset_config(&env, None, PRICE_ONE);
+    config.collateral_factor = 5_000;
+    config.liquidation_threshold = 5_000;
+    let impact = client.simulate_config_change(&None, &config);
+    assert_eq!(impact.accounts_evaluated, 3);
+    assert_eq!(impact.newly_liquidatable, 1);
+    assert_eq!(impact.affected_collateral_value, 1_000);
+    assert_eq!(impact.affected_debt_value, 600);
+
+    // Nothing is applied
+    let impact = client.simulate_config_change(&None, &asset_config(&env, None, PRICE_ONE));
+    assert_eq!(impact.newly_liquidatable, 0);
+}
+
+#[test]
+fn test_simulate_price_move_skips_unhealthy_accounts() {
+    let env = Env::default();
+    let (client, token) = setup(&env);
+    let token = Some(token);
+
+    let impact =
+        client.simulate_config_change(&token, &asset_config(&env, token.clone(), 3 * PRICE_ONE));
+    assert_eq!(impact.newly_liquidatable, 1);
+    assert_eq!(impact.affected_debt_value, 900);
+
+    // Once alice is already underwater she is no longer part of the blast radius
+    client.update_asset_price(&token, &(4 * PRICE_ONE));
+    let mut config = asset_config(&env, None, PRICE_ONE);
+    config.collateral_factor = 5_000;
+    config.liquidation_threshold = 5_000;
+    let impact = client.simulate_config_change(&None, &config);
+    assert_eq!(impact.newly_liquidatable, 0);
+    assert_eq!(impact.affected_debt_value, 0);
+}
+
+#[test]
+fn test_simulate_config_change_validation() {
+    let env = Env::default();
+    let (client, token) = setup(&env);
+
+    let unknown = Some(Address::generate(&env));
+    let result = client
+        .try_simulate_config_change(&unknown, &asset_config(&env, unknown.clone(), PRICE_ONE));
+    assert_eq!(result, Err(Ok(ConfigSimulationError::AssetNotConfigured)));
+
+    let result =
+        client.try_simulate_config_change(&None, &asset_config(&env, Some(token), PRICE_ONE));
+    assert_eq!(result, Err(Ok(ConfigSimulationError::AssetMismatch)));
+
+    let mut config = asset_config(&env, None, PRICE_ONE);
+    config.liquidation_threshold = 7_000;
+    let result = client.try_simulate_config_change(&None, &config);
+    assert_eq!(result, Err(Ok(ConfigSimulationError::InvalidConfig)));
+}
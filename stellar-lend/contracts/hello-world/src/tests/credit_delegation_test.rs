@@ -0,0 +1,118 @@
+#![cfg(test)]
+
+use crate::credit_delegation::CreditDelegationError;
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+/// Delegator with 1_000 tokens of collateral, which also fund the reserve
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let delegator = Address::generate(env);
+    StellarAssetClient::new(env, &token).mint(&delegator, &1_000);
+    TokenClient::new(env, &token).approve(
+        &delegator,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+    client.deposit_collateral(&delegator, &Some(token.clone()), &1_000);
+    (contract_id, client, token, delegator)
+}
+
+fn get_position(env: &Env, contract_id: &Address, user: &Address) -> Option<Position> {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    })
+}
+
+#[test]
+fn test_delegatee_borrows_against_delegator_collateral() {
+    let env = Env::default();
+    let (contract_id, client, token, delegator) = setup(&env);
+    let asset = Some(token.clone());
+    let delegatee = Address::generate(&env);
+
+    client.approve_credit(&delegator, &delegatee, &asset, &300);
+    assert_eq!(
+        client.get_credit_allowance(&delegator, &delegatee, &asset),
+        300
+    );
+
+    let total_debt = client.borrow_with_credit(&delegatee, &delegator, &asset, &200);
+    assert_eq!(total_debt, 200);
+
+    // The delegatee gets the funds, the delegator carries the debt
+    assert_eq!(TokenClient::new(&env, &token).balance(&delegatee), 200);
+    assert_eq!(
+        get_position(&env, &contract_id, &delegator).unwrap().debt,
+        200
+    );
+    assert!(get_position(&env, &contract_id, &delegatee).is_none());
+    assert_eq!(
+        client.get_credit_allowance(&delegator, &delegatee, &asset),
+        100
+    );
+
+    assert_eq!(
+        client.try_borrow_with_credit(&delegatee, &delegator, &asset, &150),
+        Err(Ok(CreditDelegationError::InsufficientAllowance))
+    );
+
+    // Revoking leaves nothing to borrow
+    client.approve_credit(&delegator, &delegatee, &asset, &0);
+    assert_eq!(
+        client.get_credit_allowance(&delegator, &delegatee, &asset),
+        0
+    );
+    assert_eq!(
+        client.try_borrow_with_credit(&delegatee, &delegator, &asset, &1),
+        Err(Ok(CreditDelegationError::InsufficientAllowance))
+    );
+}
+
+#[test]
+fn test_delegated_borrow_is_bounded_by_delegator_collateral() {
+    let env = Env::default();
+    let (contract_id, client, token, delegator) = setup(&env);
+    let asset = Some(token);
+    let delegatee = Address::generate(&env);
+
+    assert_eq!(
+        client.try_approve_credit(&delegator, &delegator, &asset, &100),
+        Err(Ok(CreditDelegationError::InvalidDelegatee))
+    );
+    assert_eq!(
+        client.try_approve_credit(&delegator, &delegatee, &asset, &-1),
+        Err(Ok(CreditDelegationError::InvalidAmount))
+    );
+
+    // A large allowance does not lift the delegator's borrowing limit
+    client.approve_credit(&delegator, &delegatee, &asset, &5_000);
+    assert_eq!(
+        client.try_borrow_with_credit(&delegatee, &delegator, &asset, &2_000),
+        Err(Ok(CreditDelegationError::BorrowFailed))
+    );
+    assert_eq!(
+        get_position(&env, &contract_id, &delegator).unwrap().debt,
+        0
+    );
+    assert_eq!(
+        client.get_credit_allowance(&delegator, &delegatee, &asset),
+        5_000
+    );
+}
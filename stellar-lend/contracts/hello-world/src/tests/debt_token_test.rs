@@ -0,0 +1,75 @@
+#![cfg(test)]
+
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::TokenClient,
+    Address, Env, String,
+};
+use stellarlend_debt_token::{DebtToken, DebtTokenClient};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Pool with a debt token bound to it
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+
+    let debt_token = env.register(DebtToken, ());
+    DebtTokenClient::new(env, &debt_token).initialize(
+        &contract_id,
+        &7,
+        &String::from_str(env, "StellarLend Variable Debt"),
+        &String::from_str(env, "vdXLM"),
+    );
+    (client, debt_token)
+}
+
+#[test]
+fn test_debt_token_balance_grows_with_borrow_index() {
+    let env = Env::default();
+    let (client, debt_token) = setup(&env);
+    let token = TokenClient::new(&env, &debt_token);
+    let lender = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    client.deposit_collateral(&lender, &None, &1_000_000);
+    client.deposit_collateral(&borrower, &None, &1_000_000);
+    assert_eq!(token.balance(&borrower), 0);
+    client.borrow_asset(&borrower, &None, &500_000);
+    assert_eq!(token.balance(&borrower), 500_000);
+    assert_eq!(token.balance(&lender), 0);
+
+    // Interest shows up without any interaction with the pool
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    assert_eq!(token.balance(&borrower), 536_250);
+    assert_eq!(token.balance(&borrower), client.get_user_debt(&borrower));
+    assert_eq!(token.symbol(), String::from_str(&env, "vdXLM"));
+    assert_eq!(token.decimals(), 7);
+}
+
+#[test]
+fn test_debt_token_is_not_transferable() {
+    let env = Env::default();
+    let (client, debt_token) = setup(&env);
+    let token = TokenClient::new(&env, &debt_token);
+    let borrower = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.deposit_collateral(&borrower, &None, &1_000_000);
+    client.borrow_asset(&borrower, &None, &100_000);
+
+    assert!(token.try_transfer(&borrower, &other, &1).is_err());
+    assert!(token
+        .try_approve(&borrower, &other, &1, &(env.ledger().sequence() + 10))
+        .is_err());
+    assert!(token
+        .try_transfer_from(&other, &borrower, &other, &1)
+        .is_err());
+    assert!(token.try_burn(&borrower, &1).is_err());
+    assert_eq!(token.allowance(&borrower, &other), 0);
+    assert_eq!(token.balance(&borrower), 100_000);
+    assert_eq!(token.balance(&other), 0);
+}
@@ -137,7 +137,7 @@ fn test_risk_config_exists_after_init() {
 fn test_utilization_zero_at_launch() {
     let e = env();
     let (_id, _admin, client) = setup(&e);
-    let util = client.get_utilization();
+    let util = client.get_utilization(&None);
     assert_eq!(util, 0, "utilization should be 0 at launch");
 }
 
@@ -148,8 +148,8 @@ fn test_interest_rates_accessible_after_init() {
     let e = env();
     let (_id, _admin, client) = setup(&e);
 
-    let borrow_rate = client.get_borrow_rate();
-    let supply_rate = client.get_supply_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
+    let supply_rate = client.get_supply_rate(&None);
 
     assert!(borrow_rate >= 0, "borrow_rate must be non-negative");
     assert!(supply_rate >= 0, "supply_rate must be non-negative");
@@ -0,0 +1,81 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+fn setup(env: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (contract_id, token, client)
+}
+
+fn get_position(env: &Env, contract_id: &Address, user: &Address) -> Option<Position> {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    })
+}
+
+#[test]
+fn test_deposit_on_behalf_credits_recipient() {
+    let env = Env::default();
+    let (contract_id, token, client) = setup(&env);
+    let omnibus = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    StellarAssetClient::new(&env, &token).mint(&omnibus, &1_000);
+    TokenClient::new(&env, &token).approve(
+        &omnibus,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    let balance = client.deposit_collateral_for(&omnibus, &user, &Some(token.clone()), &600);
+    assert_eq!(balance, 600);
+
+    // Tokens come from the payer; only the recipient's position is credited
+    assert_eq!(TokenClient::new(&env, &token).balance(&omnibus), 400);
+    assert_eq!(TokenClient::new(&env, &token).balance(&contract_id), 600);
+    assert_eq!(
+        get_position(&env, &contract_id, &user).unwrap().collateral,
+        600
+    );
+    assert!(get_position(&env, &contract_id, &omnibus).is_none());
+
+    // The recipient owns the collateral and can borrow against it
+    client.borrow_asset(&user, &None, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_deposit_on_behalf_checks_payer_balance() {
+    let env = Env::default();
+    let (contract_id, token, client) = setup(&env);
+    let payer = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    // The recipient's balance does not count
+    StellarAssetClient::new(&env, &token).mint(&user, &1_000);
+    StellarAssetClient::new(&env, &token).mint(&payer, &100);
+    TokenClient::new(&env, &token).approve(
+        &payer,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    client.deposit_collateral_for(&payer, &user, &Some(token), &600);
+}
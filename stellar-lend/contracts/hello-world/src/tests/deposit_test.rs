@@ -241,7 +241,7 @@ fn test_deposit_collateral_multiple_users() {
 // ============================================================================
 
 #[test]
-#[should_panic(expected = "Deposit error: InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_deposit_collateral_zero_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -252,7 +252,7 @@ fn test_deposit_collateral_zero_amount() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit error: InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_deposit_collateral_negative_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -263,7 +263,7 @@ fn test_deposit_collateral_negative_amount() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit error: AssetNotEnabled")]
+#[should_panic(expected = "Error(Contract, #10)")]
 fn test_deposit_collateral_asset_not_enabled() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -279,7 +279,7 @@ fn test_deposit_collateral_asset_not_enabled() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit error: DepositPaused")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_deposit_collateral_paused() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -292,7 +292,7 @@ fn test_deposit_collateral_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit error: DepositPaused")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_deposit_collateral_emergency_paused() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -305,7 +305,7 @@ fn test_deposit_collateral_emergency_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit error: InsufficientBalance")]
+#[should_panic(expected = "Error(Contract, #3)")]
 fn test_deposit_collateral_insufficient_balance() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -323,7 +323,7 @@ fn test_deposit_collateral_insufficient_balance() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit error: InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_deposit_collateral_exceeds_max_deposit() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -341,7 +341,7 @@ fn test_deposit_collateral_exceeds_max_deposit() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit error: InvalidAsset")]
+#[should_panic(expected = "Error(Contract, #2)")]
 fn test_deposit_collateral_self_asset() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -371,7 +371,7 @@ fn test_deposit_collateral_max_i128() {
 }
 
 #[test]
-#[should_panic(expected = "Deposit error: Overflow")]
+#[should_panic(expected = "Error(Contract, #14)")]
 fn test_deposit_collateral_overflow() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
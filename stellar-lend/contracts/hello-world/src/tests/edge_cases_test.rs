@@ -40,7 +40,7 @@ fn edge_unauthorized_set_pause_switch() {
 
 /// Boundary: deposit zero amount rejected.
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn edge_deposit_zero_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -53,7 +53,7 @@ fn edge_deposit_zero_amount() {
 
 /// Boundary: withdraw zero amount rejected.
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn edge_withdraw_zero_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -67,7 +67,7 @@ fn edge_withdraw_zero_amount() {
 
 /// Boundary: borrow zero amount rejected.
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn edge_borrow_zero_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -81,7 +81,7 @@ fn edge_borrow_zero_amount() {
 
 /// Boundary: repay zero amount rejected.
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn edge_repay_zero_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use crate::admin::GUARDIAN_ROLE;
+use crate::cross_asset::AssetConfig;
+use crate::risk_management::RiskManagementError;
+use crate::withdraw::{withdraw_collateral, WithdrawError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol,
+};
+
+#[test]
+fn test_debt_free_suppliers_exit_in_emergency_withdrawal_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &None,
+        &AssetConfig {
+            asset: None,
+            collateral_factor: 7_500,
+            liquidation_threshold: 8_000,
+            reserve_factor: 1_000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+        },
+    );
+    let guardian = Address::generate(&env);
+    client.grant_role(&admin, &Symbol::new(&env, GUARDIAN_ROLE), &guardian);
+
+    // A supplier of a token, a borrower and a cross-asset supplier
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let supplier = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&supplier, &1_000);
+    TokenClient::new(&env, &token).approve(
+        &supplier,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+    client.deposit_collateral(&supplier, &Some(token.clone()), &1_000);
+    let borrower = Address::generate(&env);
+    client.deposit_collateral(&borrower, &None, &10_000);
+    client.borrow_asset(&borrower, &None, &1_000);
+    let ca_supplier = Address::generate(&env);
+    client.cross_asset_deposit(&ca_supplier, &None, &500);
+
+    client.set_pause_switch(&guardian, &Symbol::new(&env, "pause_withdraw"), &true);
+    assert_eq!(
+        client.try_enter_emergency_withdrawal_mode(&guardian),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    client.enter_emergency_withdrawal_mode(&admin);
+    assert!(client.is_emergency_withdrawal_mode());
+    assert!(client.is_emergency_paused());
+    assert!(client.is_operation_paused(&Symbol::new(&env, "pause_borrow")));
+
+    // Suppliers without debt get out despite the paused withdrawals
+    assert_eq!(
+        client.withdraw_collateral(&supplier, &Some(token.clone()), &1_000),
+        0
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&supplier), 1_000);
+    assert_eq!(
+        client
+            .cross_asset_withdraw(&ca_supplier, &None, &500)
+            .collateral,
+        0
+    );
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            withdraw_collateral(&env, borrower.clone(), None, 100),
+            Err(WithdrawError::WithdrawPaused)
+        );
+    });
+
+    // The mode is terminal
+    assert_eq!(
+        client.try_set_emergency_pause(&admin, &false),
+        Err(Ok(RiskManagementError::EmergencyWithdrawalActive))
+    );
+    assert_eq!(
+        client.try_set_pause_switch(&admin, &Symbol::new(&env, "pause_withdraw"), &false),
+        Err(Ok(RiskManagementError::EmergencyWithdrawalActive))
+    );
+    assert_eq!(
+        client.try_enter_emergency_withdrawal_mode(&admin),
+        Err(Ok(RiskManagementError::EmergencyWithdrawalActive))
+    );
+}
@@ -13,7 +13,7 @@ use crate::events::{
     emit_admin_action, emit_borrow, emit_deposit, emit_flash_loan_initiated,
     emit_flash_loan_repaid, emit_liquidation, emit_pause_state_changed, emit_price_updated,
     emit_repay, emit_risk_params_updated, emit_withdrawal, AdminActionEvent, BorrowEvent,
-    DepositEvent, FlashLoanInitiatedEvent, FlashLoanRepaidEvent, LiquidationEvent,
+    DepositEvent, FlashLoanInitiatedEvent, FlashLoanRepaidEvent, LiquidationExecutedEvent,
     PauseStateChangedEvent, PriceUpdatedEvent, RepayEvent, RiskParamsUpdatedEvent, WithdrawalEvent,
 };
 
@@ -21,7 +21,7 @@ use crate::{HelloContract, HelloContractClient};
 
 use soroban_sdk::{
     contracttype,
-    testutils::{Address as _, Events},
+    testutils::{Address as _, Events, Ledger},
     Address, Env, Symbol, TryFromVal,
 };
 
@@ -62,6 +62,8 @@ pub struct TestRepayEvent {
     pub user: Address,
     pub asset: Option<Address>,
     pub amount: i128,
+    pub principal_paid: i128,
+    pub interest_paid: i128,
     pub timestamp: u64,
 }
 
@@ -75,6 +77,8 @@ pub struct TestLiquidationEvent {
     pub debt_liquidated: i128,
     pub collateral_seized: i128,
     pub incentive_amount: i128,
+    pub liquidator_bonus: i128,
+    pub protocol_fee: i128,
     pub timestamp: u64,
 }
 
@@ -134,6 +138,18 @@ pub struct TestPauseStateChangedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TestAccrueEvent {
+    pub borrow_index: i128,
+    pub supply_index: i128,
+    pub borrow_rate: i128,
+    pub supply_rate: i128,
+    pub utilization: i128,
+    pub elapsed: u64,
+    pub timestamp: u64,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Test helpers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -190,6 +206,48 @@ fn test_deposit_event_structure() {
     });
 }
 
+/// Core lending events are published under `[<event_name>, "v1"]` topics.
+#[test]
+fn test_core_events_have_versioned_topics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+
+    env.as_contract(&contract_id, || {
+        let user = Address::generate(&env);
+
+        emit_deposit(
+            &env,
+            DepositEvent {
+                user: user.clone(),
+                asset: None,
+                amount: 1_000,
+                timestamp: 100,
+            },
+        );
+        emit_borrow(
+            &env,
+            BorrowEvent {
+                user: user.clone(),
+                asset: None,
+                amount: 500,
+                timestamp: 100,
+            },
+        );
+
+        let all = env.events().all();
+        assert_eq!(all.len(), 2);
+        for (i, name) in ["deposit_event", "borrow_event"].iter().enumerate() {
+            let (_c, topics, _d) = all.get_unchecked(i as u32);
+            assert_eq!(topics.len(), 2);
+            let topic_name = Symbol::try_from_val(&env, &topics.get_unchecked(0)).unwrap();
+            let version = Symbol::try_from_val(&env, &topics.get_unchecked(1)).unwrap();
+            assert_eq!(topic_name, Symbol::new(&env, name));
+            assert_eq!(version, Symbol::new(&env, "v1"));
+        }
+    });
+}
+
 /// `emit_withdrawal` emits a WithdrawalEvent with the correct fields.
 #[test]
 fn test_withdrawal_event_structure() {
@@ -272,6 +330,8 @@ fn test_repay_event_structure() {
                 user: user.clone(),
                 asset: None,
                 amount: 2_000,
+                principal_paid: 1_500,
+                interest_paid: 500,
                 timestamp: 400,
             },
         );
@@ -284,11 +344,13 @@ fn test_repay_event_structure() {
 
         assert_eq!(decoded.user, user);
         assert_eq!(decoded.amount, 2_000);
+        assert_eq!(decoded.principal_paid, 1_500);
+        assert_eq!(decoded.interest_paid, 500);
         assert_eq!(decoded.timestamp, 400);
     });
 }
 
-/// `emit_liquidation` emits a LiquidationEvent with all fields correct.
+/// `emit_liquidation` emits a LiquidationExecutedEvent with all fields correct.
 #[test]
 fn test_liquidation_event_structure() {
     let env = Env::default();
@@ -301,7 +363,7 @@ fn test_liquidation_event_structure() {
 
         emit_liquidation(
             &env,
-            LiquidationEvent {
+            LiquidationExecutedEvent {
                 liquidator: liquidator.clone(),
                 borrower: borrower.clone(),
                 debt_asset: None,
@@ -309,6 +371,8 @@ fn test_liquidation_event_structure() {
                 debt_liquidated: 1_000,
                 collateral_seized: 1_100,
                 incentive_amount: 100,
+                liquidator_bonus: 100,
+                protocol_fee: 0,
                 timestamp: 999,
             },
         );
@@ -317,7 +381,7 @@ fn test_liquidation_event_structure() {
         assert_eq!(all.len(), 1);
         let (_c, _t, data) = all.get_unchecked(0);
         let decoded: TestLiquidationEvent = TestLiquidationEvent::try_from_val(&env, &data)
-            .expect("Failed to decode LiquidationEvent");
+            .expect("Failed to decode LiquidationExecutedEvent");
 
         assert_eq!(decoded.liquidator, liquidator);
         assert_eq!(decoded.borrower, borrower);
@@ -347,7 +411,7 @@ fn test_liquidation_event_with_token_assets() {
 
         emit_liquidation(
             &env,
-            LiquidationEvent {
+            LiquidationExecutedEvent {
                 liquidator: liquidator.clone(),
                 borrower: borrower.clone(),
                 debt_asset: Some(debt_asset.clone()),
@@ -355,6 +419,8 @@ fn test_liquidation_event_with_token_assets() {
                 debt_liquidated: 2_000,
                 collateral_seized: 2_200,
                 incentive_amount: 200,
+                liquidator_bonus: 200,
+                protocol_fee: 0,
                 timestamp: 500,
             },
         );
@@ -647,12 +713,14 @@ fn test_all_event_helpers_emit_one_event_each() {
                 user: a.clone(),
                 asset: None,
                 amount: 1,
+                principal_paid: 1,
+                interest_paid: 0,
                 timestamp: 0,
             },
         );
         emit_liquidation(
             &env,
-            LiquidationEvent {
+            LiquidationExecutedEvent {
                 liquidator: a.clone(),
                 borrower: b.clone(),
                 debt_asset: None,
@@ -660,6 +728,8 @@ fn test_all_event_helpers_emit_one_event_each() {
                 debt_liquidated: 1,
                 collateral_seized: 1,
                 incentive_amount: 0,
+                liquidator_bonus: 0,
+                protocol_fee: 0,
                 timestamp: 0,
             },
         );
@@ -796,7 +866,7 @@ fn test_no_sensitive_data_in_deposit_event() {
     });
 }
 
-/// LiquidationEvent only contains the two participating actors.
+/// LiquidationExecutedEvent only contains the two participating actors.
 /// An uninvolved user's address must not appear in the event.
 #[test]
 fn test_no_sensitive_data_in_liquidation_event() {
@@ -811,7 +881,7 @@ fn test_no_sensitive_data_in_liquidation_event() {
 
         emit_liquidation(
             &env,
-            LiquidationEvent {
+            LiquidationExecutedEvent {
                 liquidator: liquidator.clone(),
                 borrower: borrower.clone(),
                 debt_asset: None,
@@ -819,6 +889,8 @@ fn test_no_sensitive_data_in_liquidation_event() {
                 debt_liquidated: 500,
                 collateral_seized: 550,
                 incentive_amount: 50,
+                liquidator_bonus: 50,
+                protocol_fee: 0,
                 timestamp: 777,
             },
         );
@@ -836,39 +908,85 @@ fn test_no_sensitive_data_in_liquidation_event() {
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Integration: deposit → borrow → repay event count grows
+// Integration: deposit → borrow → repay each emit their own event
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Verifies the typical user flow emits progressively more events at each step.
+/// Whether the last contract invocation emitted an event whose first topic
+/// is `name`; `env.events().all()` only holds that invocation's events.
+fn emitted(env: &Env, name: &str) -> bool {
+    let expected = Symbol::new(env, name);
+    env.events().all().iter().any(|(_c, topics, _d)| {
+        topics
+            .get(0)
+            .and_then(|topic| Symbol::try_from_val(env, &topic).ok())
+            == Some(expected.clone())
+    })
+}
+
+/// Verifies each step of the typical user flow emits its own event.
 #[test]
 fn test_event_sequence_deposit_borrow_repay() {
     let (env, contract_id, client, _admin, user, native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
     let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &native_asset);
-    token_client.mint(&user, &10_000);
+    // Covers the deposit and the repayment
+    token_client.mint(&user, &55_000);
     token_client.approve(
         &user,
         &contract_id,
-        &10_000,
+        &55_000,
         &(env.ledger().sequence() + 100),
     );
 
     client.deposit_collateral(&user, &None, &50_000);
-    let after_deposit = env.events().all().len();
-    assert!(after_deposit > 0, "Deposit should emit at least one event");
-
-    let _res = client.borrow_asset(&user, &None, &10_000);
-    let after_borrow = env.events().all().len();
+    assert!(
+        emitted(&env, "deposit_event"),
+        "Deposit should emit a deposit event"
+    );
 
+    client.borrow_asset(&user, &None, &10_000);
     assert!(
-        after_borrow >= after_deposit,
-        "Borrow should emit additional events"
+        emitted(&env, "borrow_event"),
+        "Borrow should emit a borrow event"
     );
+    assert!(!emitted(&env, "deposit_event"));
 
     client.repay_debt(&user, &None, &5_000);
-    let after_repay = env.events().all().len();
     assert!(
-        after_repay >= after_borrow,
-        "Repay should emit additional events"
+        emitted(&env, "repay_event"),
+        "Repay should emit a repay event"
     );
+    assert!(!emitted(&env, "borrow_event"));
+}
+
+/// Advancing the pool indexes emits an `accrue` event with the new indexes
+/// and the rates they grew at.
+#[test]
+fn test_accrue_event_reports_indexes_and_rates() {
+    let (env, _contract_id, client) = setup();
+    init(&client, &Address::generate(&env));
+    let lender = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    client.deposit_collateral(&lender, &None, &1_000_000);
+    client.deposit_collateral(&borrower, &None, &1_000_000);
+    client.borrow_asset(&borrower, &None, &500_000);
+
+    let year = 365 * 24 * 60 * 60;
+    env.ledger().with_mut(|li| li.timestamp += year);
+    let indexes = client.accrue_interest();
+
+    let all = env.events().all();
+    let (_c, topics, data) = all.get_unchecked(all.len() - 1);
+    let topic = Symbol::try_from_val(&env, &topics.get_unchecked(0)).unwrap();
+    assert_eq!(topic, Symbol::new(&env, "accrue"));
+    let decoded = TestAccrueEvent::try_from_val(&env, &data).expect("Failed to decode AccrueEvent");
+
+    // 25% utilization: 7.25% borrow rate
+    assert_eq!(decoded.borrow_index, indexes.borrow_index);
+    assert_eq!(decoded.supply_index, indexes.supply_index);
+    assert_eq!(decoded.utilization, 2_500);
+    assert_eq!(decoded.borrow_rate, 725);
+    assert!(decoded.supply_rate > 0 && decoded.supply_rate < decoded.borrow_rate);
+    assert_eq!(decoded.elapsed, year);
+    assert_eq!(decoded.timestamp, env.ledger().timestamp());
 }
@@ -0,0 +1,101 @@
+#![cfg(test)]
+
+use crate::flash_loan::{flash_loan, FlashLoanError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Bytes, Env,
+};
+
+/// Receiver that repays principal plus fee, or only the principal when
+/// `data` is not empty
+#[contract]
+pub struct MockFlashLoanReceiver;
+
+#[contractimpl]
+impl MockFlashLoanReceiver {
+    pub fn on_flash_loan(
+        env: Env,
+        lender: Address,
+        asset: Address,
+        amount: i128,
+        fee: i128,
+        data: Bytes,
+    ) -> bool {
+        let repayment = if data.is_empty() {
+            amount + fee
+        } else {
+            amount
+        };
+        TokenClient::new(&env, &asset).transfer(
+            &env.current_contract_address(),
+            &lender,
+            &repayment,
+        );
+        true
+    }
+}
+
+/// Lender holding 10_000 of liquidity and a receiver holding 100 for fees
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let receiver = env.register(MockFlashLoanReceiver, ());
+    StellarAssetClient::new(env, &token).mint(&contract_id, &10_000);
+    StellarAssetClient::new(env, &token).mint(&receiver, &100);
+    (contract_id, client, token, receiver)
+}
+
+#[test]
+fn test_flash_loan_invokes_receiver_and_collects_fee() {
+    let env = Env::default();
+    let (contract_id, client, token, receiver) = setup(&env);
+
+    // Default fee: 9 bps of 10_000
+    let fee = client.flash_loan(&receiver, &token, &10_000, &Bytes::new(&env));
+    assert_eq!(fee, 9);
+
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&contract_id), 10_009);
+    assert_eq!(token_client.balance(&receiver), 91);
+    assert_eq!(client.get_reserve_balance(&Some(token.clone())), 9);
+
+    // The loan is settled, so the receiver can borrow again
+    client.flash_loan(&receiver, &token, &10_000, &Bytes::new(&env));
+    assert_eq!(client.get_reserve_balance(&Some(token)), 18);
+}
+
+#[test]
+fn test_flash_loan_reverts_when_not_repaid() {
+    let env = Env::default();
+    let (contract_id, client, token, receiver) = setup(&env);
+    let underpay = Bytes::from_array(&env, &[1]);
+
+    assert!(client
+        .try_flash_loan(&receiver, &token, &10_000, &underpay)
+        .is_err());
+    // More than the available liquidity
+    assert!(client
+        .try_flash_loan(&receiver, &token, &10_001, &Bytes::new(&env))
+        .is_err());
+
+    // Nothing moved
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&contract_id), 10_000);
+    assert_eq!(token_client.balance(&receiver), 100);
+    assert_eq!(client.get_reserve_balance(&Some(token.clone())), 0);
+
+    env.as_contract(&contract_id, || {
+        let result = flash_loan(&env, receiver.clone(), token.clone(), 10_000, underpay);
+        assert_eq!(result, Err(FlashLoanError::NotRepaid));
+    });
+}
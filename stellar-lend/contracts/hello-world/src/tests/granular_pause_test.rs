@@ -0,0 +1,149 @@
+#![cfg(test)]
+
+use crate::admin::GUARDIAN_ROLE;
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::cross_asset::CrossAssetError;
+use crate::deposit::{deposit_collateral, DepositError};
+use crate::repay::{repay_debt, RepayError};
+use crate::risk_management::RiskManagementError;
+use crate::withdraw::{withdraw_collateral, WithdrawError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol,
+};
+use stellarlend_amm::{AmmError, SwapParams};
+
+/// Contract with a guardian and a user holding 2_000 tokens approved to the
+/// contract
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let guardian = Address::generate(env);
+    client.grant_role(&admin, &Symbol::new(env, GUARDIAN_ROLE), &guardian);
+
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let user = Address::generate(env);
+    StellarAssetClient::new(env, &token).mint(&user, &2_000);
+    TokenClient::new(env, &token).approve(
+        &user,
+        &contract_id,
+        &2_000,
+        &(env.ledger().sequence() + 100),
+    );
+    (contract_id, client, guardian, token, user)
+}
+
+#[test]
+fn test_each_switch_blocks_only_its_operation() {
+    let env = Env::default();
+    let (contract_id, client, guardian, _token, user) = setup(&env);
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &1_000);
+
+    client.set_pause_switch(&guardian, &Symbol::new(&env, "pause_borrow"), &true);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            borrow_asset(&env, user.clone(), None, 100),
+            Err(BorrowError::BorrowPaused)
+        );
+    });
+    // Everything else keeps working
+    client.deposit_collateral(&user, &None, &1_000);
+    client.withdraw_collateral(&user, &None, &1_000);
+
+    client.set_pause_switch(&guardian, &Symbol::new(&env, "pause_withdraw"), &true);
+    client.set_pause_switch(&guardian, &Symbol::new(&env, "pause_repay"), &true);
+    client.set_pause_switch(&guardian, &Symbol::new(&env, "pause_deposit"), &true);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            withdraw_collateral(&env, user.clone(), None, 100),
+            Err(WithdrawError::WithdrawPaused)
+        );
+        assert_eq!(
+            repay_debt(&env, user.clone(), None, 100),
+            Err(RepayError::RepayPaused)
+        );
+        assert_eq!(
+            deposit_collateral(&env, user.clone(), None, 100),
+            Err(DepositError::DepositPaused)
+        );
+    });
+    assert_eq!(
+        client.try_cross_asset_deposit(&user, &None, &100),
+        Err(Ok(CrossAssetError::OperationPaused))
+    );
+
+    assert!(!client.is_operation_paused(&Symbol::new(&env, "pause_swap")));
+    client.set_pause_switch(&guardian, &Symbol::new(&env, "pause_swap"), &true);
+    assert_eq!(
+        client.try_amm_swap(
+            &user,
+            &SwapParams {
+                protocol: Address::generate(&env),
+                token_in: None,
+                token_out: Some(Address::generate(&env)),
+                amount_in: 100,
+                min_amount_out: 90,
+                slippage_tolerance: 100,
+                deadline: env.ledger().timestamp() + 3600,
+                use_oracle_floor: false,
+            }
+        ),
+        Err(Ok(AmmError::SwapPaused))
+    );
+}
+
+#[test]
+fn test_frozen_asset_takes_no_new_exposure() {
+    let env = Env::default();
+    let (contract_id, client, guardian, token, user) = setup(&env);
+    let asset = Some(token.clone());
+    client.deposit_collateral(&user, &asset, &1_000);
+
+    client.set_asset_frozen(&guardian, &asset, &true);
+    assert!(client.is_asset_frozen(&asset));
+    assert!(!client.is_asset_frozen(&None));
+    assert_eq!(
+        client.try_set_asset_frozen(&guardian, &asset, &false),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            deposit_collateral(&env, user.clone(), asset.clone(), 100),
+            Err(DepositError::AssetFrozen)
+        );
+        assert_eq!(
+            borrow_asset(&env, user.clone(), asset.clone(), 100),
+            Err(BorrowError::AssetFrozen)
+        );
+    });
+    assert_eq!(
+        client.try_amm_swap(
+            &user,
+            &SwapParams {
+                protocol: Address::generate(&env),
+                token_in: None,
+                token_out: asset.clone(),
+                amount_in: 100,
+                min_amount_out: 90,
+                slippage_tolerance: 100,
+                deadline: env.ledger().timestamp() + 3600,
+                use_oracle_floor: false,
+            }
+        ),
+        Err(Ok(AmmError::SwapPaused))
+    );
+
+    // Other markets are unaffected and positions in the frozen one can unwind
+    assert_eq!(client.withdraw_collateral(&user, &asset, &400), 600);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 1_400);
+    client.deposit_collateral(&user, &None, &1_000);
+}
@@ -0,0 +1,136 @@
+#![cfg(test)]
+
+use crate::cross_asset::AssetConfig;
+use crate::health_history::{MAX_HEALTH_SNAPSHOTS, MAX_HISTORY_PAGE};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>) {
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (admin, client)
+}
+
+fn asset_config(env: &Env, asset: Option<Address>) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price: PRICE_ONE,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+#[test]
+fn test_core_interactions_record_snapshots() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_admin, client) = setup(&env);
+    let user = Address::generate(&env);
+
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &4_000);
+    client.deposit_collateral(&user, &None, &2_000);
+
+    let history = client.get_health_history(&user, &0, &10);
+    assert_eq!(history.len(), 3);
+
+    let deposit = history.get(0).unwrap();
+    assert_eq!(deposit.reason, Symbol::new(&env, "deposit"));
+    assert_eq!(deposit.health_factor, i128::MAX);
+    assert_eq!(deposit.risk_level, 1);
+    assert!(!deposit.cross_asset);
+
+    let borrow = history.get(1).unwrap();
+    assert_eq!(borrow.reason, Symbol::new(&env, "borrow"));
+    assert_eq!(borrow.collateral, 10_000);
+    assert_eq!(borrow.debt, 4_000);
+    assert_eq!(borrow.health_factor, 25_000);
+
+    let deposit = history.get(2).unwrap();
+    assert_eq!(deposit.reason, Symbol::new(&env, "deposit"));
+    assert_eq!(deposit.health_factor, 30_000);
+
+    // Users without interactions have no history
+    let stranger = Address::generate(&env);
+    assert_eq!(client.get_health_history(&stranger, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_history_is_bounded_and_paginated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_admin, client) = setup(&env);
+    let user = Address::generate(&env);
+
+    for _ in 0..MAX_HEALTH_SNAPSHOTS + 5 {
+        client.deposit_collateral(&user, &None, &100);
+    }
+
+    // The oldest snapshots are dropped first
+    let page = client.get_health_history(&user, &0, &(MAX_HEALTH_SNAPSHOTS * 2));
+    assert_eq!(page.len(), MAX_HISTORY_PAGE);
+    assert_eq!(page.get(0).unwrap().collateral, 600);
+
+    let page = client.get_health_history(&user, &(MAX_HEALTH_SNAPSHOTS - 10), &20);
+    assert_eq!(page.len(), 10);
+    assert_eq!(
+        page.get(9).unwrap().collateral,
+        100 * (MAX_HEALTH_SNAPSHOTS + 5) as i128
+    );
+    assert_eq!(
+        client
+            .get_health_history(&user, &MAX_HEALTH_SNAPSHOTS, &10)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_price_updates_record_threshold_crossings() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, client) = setup(&env);
+    client.initialize_ca(&admin);
+    let token = Address::generate(&env);
+    client.initialize_asset(&None, &asset_config(&env, None));
+    client.initialize_asset(
+        &Some(token.clone()),
+        &asset_config(&env, Some(token.clone())),
+    );
+
+    let user = Address::generate(&env);
+    client.cross_asset_deposit(&user, &None, &10_000);
+    client.cross_asset_borrow(&user, &Some(token.clone()), &5_000);
+    let history = client.get_health_history(&user, &0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().health_factor, 16_000);
+    assert!(history.get(1).unwrap().cross_asset);
+
+    // 1.2: health factor drops to 13_333, crossing into risk level 2
+    client.update_asset_price(&Some(token.clone()), &12_000_000);
+    // 1.25: health factor 12_800 stays in the same band
+    client.update_asset_price(&Some(token.clone()), &12_500_000);
+    // 2.0: health factor 8_000, liquidatable
+    client.update_asset_price(&Some(token.clone()), &(2 * PRICE_ONE));
+
+    let history = client.get_health_history(&user, &0, &10);
+    assert_eq!(history.len(), 4);
+    let crossing = history.get(2).unwrap();
+    assert_eq!(crossing.reason, Symbol::new(&env, "price_cross"));
+    assert_eq!(crossing.health_factor, 13_333);
+    assert_eq!(crossing.risk_level, 2);
+    let crossing = history.get(3).unwrap();
+    assert_eq!(crossing.health_factor, 8_000);
+    assert_eq!(crossing.risk_level, 5);
+    assert_eq!(crossing.debt, 10_000);
+}
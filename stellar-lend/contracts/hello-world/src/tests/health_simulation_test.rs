@@ -0,0 +1,53 @@
+#![cfg(test)]
+
+use crate::health_simulation::{HealthSimulationError, SimAction};
+use soroban_sdk::{
+    token::{StellarAssetClient, TokenClient},
+    vec,
+};
+
+#[test]
+fn test_simulated_health_factor_matches_executed_actions() {
+    let (env, contract_id, client, admin, user, native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    StellarAssetClient::new(&env, &native_asset).mint(&user, &20_000);
+    TokenClient::new(&env, &native_asset).approve(
+        &user,
+        &contract_id,
+        &20_000,
+        &(env.ledger().sequence() + 100),
+    );
+    client.set_liquidation_threshold(&admin, &None, &8_000);
+    client.deposit_collateral(&user, &None, &10_000);
+
+    let actions = vec![
+        &env,
+        SimAction::Borrow(2_000),
+        SimAction::Withdraw(1_000),
+        SimAction::Repay(500),
+    ];
+    let simulated = client.simulate_health_factor(&user, &actions);
+    assert_eq!(simulated, 9_000 * 8_000 / 1_500);
+    // Nothing was executed
+    assert_eq!(client.get_user_debt(&user), 0);
+    assert_eq!(client.simulate_health_factor(&user, &vec![&env]), i128::MAX);
+
+    client.borrow_asset(&user, &None, &2_000);
+    client.withdraw_collateral(&user, &None, &1_000);
+    client.repay_debt(&user, &None, &500);
+    assert_eq!(client.get_account_data(&user).health_factor, simulated);
+
+    // Over-repaying clears the debt; invalid sequences are rejected
+    assert_eq!(
+        client.simulate_health_factor(&user, &vec![&env, SimAction::Repay(5_000)]),
+        i128::MAX
+    );
+    assert_eq!(
+        client.try_simulate_health_factor(&user, &vec![&env, SimAction::Withdraw(9_001)]),
+        Err(Ok(HealthSimulationError::InsufficientCollateral))
+    );
+    assert_eq!(
+        client.try_simulate_health_factor(&user, &vec![&env, SimAction::Deposit(0)]),
+        Err(Ok(HealthSimulationError::InvalidAmount))
+    );
+}
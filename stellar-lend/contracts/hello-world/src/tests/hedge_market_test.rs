@@ -0,0 +1,283 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::hedge_market::{HedgeMarketError, HfPutStatus};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol,
+};
+
+const EXPIRY: u64 = 1_000;
+
+struct HedgeTest {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'static>,
+    admin: Address,
+    token: Address,
+    writer: Address,
+}
+
+fn setup() -> HedgeTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Native debt is settled in this token
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.set_native_asset_address(&admin, &token);
+
+    let writer = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&writer, &10_000);
+
+    HedgeTest {
+        env,
+        contract_id,
+        client,
+        admin,
+        token,
+        writer,
+    }
+}
+
+fn set_position(t: &HedgeTest, user: &Address, collateral: i128, debt: i128) {
+    t.env.as_contract(&t.contract_id, || {
+        let position = Position {
+            collateral,
+            debt,
+            borrow_interest: 0,
+            last_accrual_time: t.env.ledger().timestamp(),
+        };
+        t.env
+            .storage()
+            .persistent()
+            .set(&DepositDataKey::Position(user.clone()), &position);
+        crate::reserve::record_debt_asset(&t.env, user, &None);
+    });
+}
+
+fn get_position(t: &HedgeTest, user: &Address) -> Position {
+    t.env.as_contract(&t.contract_id, || {
+        t.env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+            .unwrap()
+    })
+}
+
+fn balance(t: &HedgeTest, account: &Address) -> i128 {
+    TokenClient::new(&t.env, &t.token).balance(account)
+}
+
+/// Writer escrows 5_000 behind a 1.2x strike with a 2% premium
+fn create_offer(t: &HedgeTest) -> u64 {
+    t.client
+        .create_hedge_offer(&t.writer, &None, &12_000, &200, &5_000, &EXPIRY)
+}
+
+fn insured_borrower(t: &HedgeTest) -> Address {
+    let borrower = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.token).mint(&borrower, &1_000);
+    set_position(t, &borrower, 15_000, 10_000);
+    borrower
+}
+
+#[test]
+fn test_create_hedge_offer_validation() {
+    let t = setup();
+
+    // Strike must sit above the liquidation point
+    let result = t
+        .client
+        .try_create_hedge_offer(&t.writer, &None, &10_000, &200, &5_000, &EXPIRY);
+    assert_eq!(result, Err(Ok(HedgeMarketError::InvalidOffer)));
+
+    let result = t
+        .client
+        .try_create_hedge_offer(&t.writer, &None, &12_000, &10_001, &5_000, &EXPIRY);
+    assert_eq!(result, Err(Ok(HedgeMarketError::InvalidOffer)));
+
+    let result = t
+        .client
+        .try_create_hedge_offer(&t.writer, &None, &12_000, &200, &5_000, &0);
+    assert_eq!(result, Err(Ok(HedgeMarketError::InvalidOffer)));
+
+    let offer_id = create_offer(&t);
+    let offer = t.client.get_hedge_offer(&offer_id).unwrap();
+    assert_eq!(offer.available, 5_000);
+    assert!(offer.active);
+    assert_eq!(balance(&t, &t.contract_id), 5_000);
+    assert_eq!(balance(&t, &t.writer), 5_000);
+}
+
+#[test]
+fn test_buy_hf_put() {
+    let t = setup();
+    let offer_id = create_offer(&t);
+    let borrower = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.token).mint(&borrower, &1_000);
+
+    let result = t.client.try_buy_hf_put(&borrower, &offer_id, &None, &1_000);
+    assert_eq!(result, Err(Ok(HedgeMarketError::NoPosition)));
+
+    // 12_000 weighted by the 1.05x liquidation threshold is 1.14x, past
+    // the strike
+    set_position(&t, &borrower, 12_000, 10_000);
+    let result = t.client.try_buy_hf_put(&borrower, &offer_id, &None, &1_000);
+    assert_eq!(result, Err(Ok(HedgeMarketError::StrikeReached)));
+
+    set_position(&t, &borrower, 15_000, 10_000);
+    let result = t.client.try_buy_hf_put(&borrower, &offer_id, &None, &5_001);
+    assert_eq!(result, Err(Ok(HedgeMarketError::InsufficientCapacity)));
+
+    let put_id = t.client.buy_hf_put(&borrower, &offer_id, &None, &2_000);
+    let put = t.client.get_hf_put(&put_id).unwrap();
+    assert_eq!(put.premium_paid, 40);
+    assert_eq!(put.status, HfPutStatus::Active);
+    assert_eq!(balance(&t, &borrower), 960);
+    assert_eq!(balance(&t, &t.writer), 5_040);
+    assert_eq!(
+        t.client.get_hedge_offer(&offer_id).unwrap().available,
+        3_000
+    );
+}
+
+#[test]
+fn test_keeper_exercises_put_below_strike() {
+    let t = setup();
+    let offer_id = create_offer(&t);
+    let borrower = insured_borrower(&t);
+    let put_id = t.client.buy_hf_put(&borrower, &offer_id, &None, &2_000);
+
+    let keeper = Address::generate(&t.env);
+    let result = t.client.try_exercise_hf_put(&keeper, &put_id);
+    assert_eq!(result, Err(Ok(HedgeMarketError::Unauthorized)));
+    t.client
+        .grant_role(&t.admin, &Symbol::new(&t.env, "keeper"), &keeper);
+
+    let result = t.client.try_exercise_hf_put(&keeper, &put_id);
+    assert_eq!(result, Err(Ok(HedgeMarketError::StrikeNotReached)));
+
+    // A price spike drags the position to 1.1x collateral (HF 1.05),
+    // below the strike
+    set_position(&t, &borrower, 11_000, 10_000);
+    assert_eq!(t.client.exercise_hf_put(&keeper, &put_id), 2_000);
+
+    // 11_000 / 8_000 = 1.375x, well clear of liquidation
+    let position = get_position(&t, &borrower);
+    assert_eq!(position.debt, 8_000);
+    assert_eq!(
+        t.client.get_hf_put(&put_id).unwrap().status,
+        HfPutStatus::Exercised
+    );
+
+    let result = t.client.try_exercise_hf_put(&keeper, &put_id);
+    assert_eq!(result, Err(Ok(HedgeMarketError::PutNotActive)));
+}
+
+#[test]
+fn test_expire_and_cancel_return_liquidity() {
+    let t = setup();
+    let offer_id = create_offer(&t);
+    let borrower = insured_borrower(&t);
+    let put_id = t.client.buy_hf_put(&borrower, &offer_id, &None, &2_000);
+
+    let result = t.client.try_expire_hf_put(&put_id);
+    assert_eq!(result, Err(Ok(HedgeMarketError::PutNotExpired)));
+
+    let result = t.client.try_cancel_hedge_offer(&borrower, &offer_id);
+    assert_eq!(result, Err(Ok(HedgeMarketError::Unauthorized)));
+    assert_eq!(t.client.cancel_hedge_offer(&t.writer, &offer_id), 3_000);
+
+    let result = t.client.try_buy_hf_put(&borrower, &offer_id, &None, &100);
+    assert_eq!(result, Err(Ok(HedgeMarketError::OfferUnavailable)));
+
+    // The outstanding put survives cancellation until it expires
+    t.env.ledger().with_mut(|li| li.timestamp = EXPIRY + 1);
+    let keeper = t.admin.clone();
+    let result = t.client.try_exercise_hf_put(&keeper, &put_id);
+    assert_eq!(result, Err(Ok(HedgeMarketError::PutExpired)));
+
+    assert_eq!(t.client.expire_hf_put(&put_id), 2_000);
+    assert_eq!(balance(&t, &t.writer), 10_040);
+    assert_eq!(balance(&t, &t.contract_id), 0);
+
+    let result = t.client.try_expire_hf_put(&put_id);
+    assert_eq!(result, Err(Ok(HedgeMarketError::PutNotActive)));
+}
+
+#[test]
+fn test_put_requires_owed_debt_asset() {
+    let t = setup();
+    let other = t
+        .env
+        .register_stellar_asset_contract_v2(t.admin.clone())
+        .address();
+    StellarAssetClient::new(&t.env, &other).mint(&t.writer, &5_000);
+    let offer_id = t.client.create_hedge_offer(
+        &t.writer,
+        &Some(other.clone()),
+        &12_000,
+        &200,
+        &5_000,
+        &EXPIRY,
+    );
+
+    // The borrower only owes native debt
+    let borrower = insured_borrower(&t);
+    let result = t.client.try_buy_hf_put(&borrower, &offer_id, &None, &1_000);
+    assert_eq!(result, Err(Ok(HedgeMarketError::DebtAssetNotOwed)));
+    assert_eq!(
+        t.client.get_hedge_offer(&offer_id).unwrap().available,
+        5_000
+    );
+}
+
+#[test]
+fn test_exercise_books_repayment_like_repay() {
+    let t = setup();
+    let offer_id = create_offer(&t);
+    let escrowed = || {
+        t.env.as_contract(&t.contract_id, || {
+            crate::reserve::get_escrowed_balance(&t.env, &None)
+        })
+    };
+    assert_eq!(escrowed(), 5_000);
+
+    let borrower = insured_borrower(&t);
+    let put_id = t.client.buy_hf_put(&borrower, &offer_id, &None, &2_000);
+    assert_eq!(escrowed(), 5_000);
+
+    // 9_000 principal plus 1_000 interest against 11_000 collateral
+    t.env.as_contract(&t.contract_id, || {
+        let position = Position {
+            collateral: 11_000,
+            debt: 9_000,
+            borrow_interest: 1_000,
+            last_accrual_time: t.env.ledger().timestamp(),
+        };
+        t.env
+            .storage()
+            .persistent()
+            .set(&DepositDataKey::Position(borrower.clone()), &position);
+    });
+    assert_eq!(t.client.exercise_hf_put(&t.admin, &put_id), 2_000);
+
+    // Interest first, with the reserve factor's share kept by the protocol
+    let position = get_position(&t, &borrower);
+    assert_eq!(position.borrow_interest, 0);
+    assert_eq!(position.debt, 8_000);
+    assert_eq!(t.client.get_reserve_balance(&None), 100);
+
+    // The repaid 2_000 joins the pool; the offer's 3_000 stays escrowed
+    assert_eq!(escrowed(), 3_000);
+}
@@ -42,11 +42,11 @@ fn integration_full_flow_deposit_borrow_repay_withdraw() {
     let (env, contract_id, client, _admin, user, native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
     let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &native_asset);
-    token_client.mint(&user, &5_000);
+    token_client.mint(&user, &15_000);
     token_client.approve(
         &user,
         &contract_id,
-        &5_000,
+        &15_000,
         &(env.ledger().sequence() + 100),
     );
 
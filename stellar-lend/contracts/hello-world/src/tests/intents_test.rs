@@ -0,0 +1,67 @@
+#![cfg(test)]
+
+use crate::intents::{self, IntentError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>) {
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (contract_id, client)
+}
+
+#[test]
+fn test_cancel_intent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_contract_id, client) = setup(&env);
+    let user = Address::generate(&env);
+
+    assert_eq!(client.get_user_nonce(&user), 0);
+    assert!(!client.is_intent_cancelled(&user, &2));
+
+    client.cancel_intent(&user, &2);
+    assert!(client.is_intent_cancelled(&user, &2));
+    // Cancelling does not move the nonce
+    assert_eq!(client.get_user_nonce(&user), 0);
+
+    assert_eq!(
+        client.try_cancel_intent(&user, &2),
+        Err(Ok(IntentError::IntentCancelled))
+    );
+    // Other users' nonces are independent
+    assert!(!client.is_intent_cancelled(&Address::generate(&env), &2));
+}
+
+#[test]
+fn test_consume_nonce_skips_cancelled_intents() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, client) = setup(&env);
+    let user = Address::generate(&env);
+
+    client.cancel_intent(&user, &1);
+
+    env.as_contract(&contract_id, || {
+        intents::consume_nonce(&env, &user, 0).unwrap();
+        assert_eq!(
+            intents::consume_nonce(&env, &user, 0),
+            Err(IntentError::NonceAlreadyUsed)
+        );
+        assert_eq!(
+            intents::consume_nonce(&env, &user, 1),
+            Err(IntentError::IntentCancelled)
+        );
+        intents::consume_nonce(&env, &user, 2).unwrap();
+    });
+    assert_eq!(client.get_user_nonce(&user), 3);
+
+    // Spent nonces can no longer be cancelled
+    assert_eq!(
+        client.try_cancel_intent(&user, &2),
+        Err(Ok(IntentError::NonceAlreadyUsed))
+    );
+    client.cancel_intent(&user, &3);
+}
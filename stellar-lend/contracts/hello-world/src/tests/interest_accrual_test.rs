@@ -106,11 +106,11 @@ fn test_repay_accrues_interest_with_time_advance() {
     let (env, contract_id, client, _admin, user, native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
     let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &native_asset);
-    token_client.mint(&user, &15_000);
+    token_client.mint(&user, &35_000);
     token_client.approve(
         &user,
         &contract_id,
-        &15_000,
+        &35_000,
         &(env.ledger().sequence() + 100),
     );
 
@@ -132,11 +132,11 @@ fn test_borrow_then_repay_full_debt_includes_interest() {
     let (env, contract_id, client, _admin, user, native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
     let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &native_asset);
-    token_client.mint(&user, &15_000);
+    token_client.mint(&user, &115_000);
     token_client.approve(
         &user,
         &contract_id,
-        &15_000,
+        &115_000,
         &(env.ledger().sequence() + 100),
     );
 
@@ -152,6 +152,59 @@ fn test_borrow_then_repay_full_debt_includes_interest() {
     assert!(remaining >= 0);
 }
 
+#[test]
+fn test_repay_max_amount_repays_everything() {
+    let (env, contract_id, client, _admin, user, native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    soroban_sdk::token::StellarAssetClient::new(&env, &native_asset).mint(&user, &115_000);
+    let token_client = soroban_sdk::token::Client::new(&env, &native_asset);
+    token_client.approve(
+        &user,
+        &contract_id,
+        &115_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    client.deposit_collateral(&user, &None, &100_000);
+    client.borrow_asset(&user, &None, &10_000);
+    env.ledger().with_mut(|li| li.timestamp += SECONDS_PER_YEAR);
+
+    // Only the outstanding debt is pulled; the excess stays with the user
+    let (remaining, interest_paid, principal_paid) = client.repay_debt(&user, &None, &i128::MAX);
+    assert_eq!(remaining, 0);
+    assert_eq!(principal_paid, 10_000);
+    assert!(interest_paid > 0);
+    assert_eq!(
+        token_client.balance(&user),
+        15_000 - principal_paid - interest_paid
+    );
+}
+
+#[test]
+fn test_borrow_limit_includes_accrued_interest() {
+    let env = create_test_env();
+    let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
+    let user = Address::generate(&env);
+    client.deposit_collateral(&user, &None, &1_100_000);
+    client.borrow_asset(&user, &None, &500_000);
+
+    // After a year the accrued interest eats into the remaining headroom
+    env.ledger().with_mut(|li| li.timestamp += SECONDS_PER_YEAR);
+    assert!(client.try_borrow_asset(&user, &None, &500_000).is_err());
+    let total_debt = client.borrow_asset(&user, &None, &1);
+    let position = client.get_user_report(&user).position;
+    assert!(position.borrow_interest > 0);
+    assert_eq!(total_debt, position.debt + position.borrow_interest);
+
+    // 110% minimum collateral ratio, on collateral including supply interest
+    let max_debt = position.collateral * 10000 / 11000;
+    let headroom = max_debt - total_debt;
+    assert!(client
+        .try_borrow_asset(&user, &None, &(headroom + 1))
+        .is_err());
+    assert_eq!(client.borrow_asset(&user, &None, &headroom), max_debt);
+}
+
 // =============================================================================
 // Index / rate consistency
 // =============================================================================
@@ -161,7 +214,7 @@ fn test_borrow_rate_used_in_accrual_consistent() {
     let env = create_test_env();
     let (contract_id, _admin, client) = setup_contract_with_admin(&env);
     set_protocol_analytics(&env, &contract_id, 10000, 5000);
-    let rate = client.get_borrow_rate();
+    let rate = client.get_borrow_rate(&None);
     assert!(rate >= 0);
     env.as_contract(&contract_id, || {
         let config = get_interest_rate_config(&env);
@@ -177,7 +230,7 @@ fn test_accrual_index_consistency_after_config_update() {
     client.deposit_collateral(&user, &None, &50_000);
     client.borrow_asset(&user, &None, &10_000);
 
-    let rate_before = client.get_borrow_rate();
+    let rate_before = client.get_borrow_rate(&None);
     client.update_interest_rate_config(
         &admin,
         &None,
@@ -188,6 +241,6 @@ fn test_accrual_index_consistency_after_config_update() {
         &None,
         &None,
     );
-    let rate_after = client.get_borrow_rate();
+    let rate_after = client.get_borrow_rate(&None);
     assert!(rate_after >= rate_before || rate_after >= 0);
 }
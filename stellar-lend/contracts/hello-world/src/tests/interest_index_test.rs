@@ -0,0 +1,91 @@
+#![cfg(test)]
+
+use crate::interest_index::INDEX_SCALE;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+fn setup(env: &Env) -> HelloContractClient<'_> {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+    client
+}
+
+#[test]
+fn test_user_debt_includes_elapsed_interest() {
+    let env = Env::default();
+    let client = setup(&env);
+    let lender = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    client.deposit_collateral(&lender, &None, &1_000_000);
+    client.deposit_collateral(&borrower, &None, &1_000_000);
+    client.borrow_asset(&borrower, &None, &500_000);
+    assert_eq!(client.get_user_debt(&borrower), 500_000);
+
+    // 25% utilization: 7.25% borrow rate, visible without any interaction
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    assert_eq!(
+        client.get_interest_indexes().borrow_index,
+        INDEX_SCALE * 10_725 / 10_000
+    );
+    assert_eq!(client.get_user_debt(&borrower), 536_250);
+    assert_eq!(
+        client.get_user_report(&borrower).position.borrow_interest,
+        0
+    );
+
+    // Accruing stores the indexes; it is idempotent within a ledger
+    let indexes = client.accrue_interest();
+    assert_eq!(indexes.last_update, env.ledger().timestamp());
+    assert_eq!(client.accrue_interest(), indexes);
+    assert_eq!(client.get_user_debt(&borrower), 536_250);
+
+    // The next interaction books exactly the same interest
+    client.borrow_asset(&borrower, &None, &1);
+    let position = client.get_user_report(&borrower).position;
+    assert_eq!(position.borrow_interest, 36_250);
+    assert_eq!(client.get_user_debt(&borrower), 536_251);
+}
+
+#[test]
+fn test_borrowers_accrue_from_their_own_snapshot() {
+    let env = Env::default();
+    let client = setup(&env);
+    let lender = Address::generate(&env);
+    let idle = Address::generate(&env);
+    let active = Address::generate(&env);
+    let late = Address::generate(&env);
+
+    client.deposit_collateral(&lender, &None, &2_000_000);
+    for user in [&idle, &active, &late] {
+        client.deposit_collateral(user, &None, &1_000_000);
+    }
+    client.borrow_asset(&idle, &None, &100_000);
+    client.borrow_asset(&active, &None, &100_000);
+
+    // Booking interest does not change what a borrower owes
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    client.withdraw_collateral(&active, &None, &1);
+    let debt = client.get_user_debt(&idle);
+    assert!(debt > 100_000);
+    assert_eq!(client.get_user_debt(&active), debt);
+
+    // A late borrower owes nothing for the period before their borrow
+    client.borrow_asset(&late, &None, &100_000);
+    assert_eq!(client.get_user_debt(&late), 100_000);
+
+    // Interest compounds on booked and unbooked interest alike
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    let late_interest = client.get_user_debt(&late) - 100_000;
+    assert!(late_interest > 0);
+    let idle_debt = client.get_user_debt(&idle);
+    assert!((idle_debt - client.get_user_debt(&active)).abs() <= 1);
+    assert!(idle_debt - debt > late_interest);
+}
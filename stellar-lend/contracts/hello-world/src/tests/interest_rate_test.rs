@@ -12,7 +12,7 @@
 
 use crate::deposit::{DepositDataKey, ProtocolAnalytics};
 use crate::interest_rate::{
-    calculate_accrued_interest, get_interest_rate_config, InterestRateConfig,
+    calculate_accrued_interest, get_interest_rate_config, to_rate_view, InterestRateConfig,
 };
 use crate::{HelloContract, HelloContractClient};
 use soroban_sdk::{testutils::Address as _, Address, Env};
@@ -82,7 +82,7 @@ fn test_utilization_zero_borrows() {
     // Set deposits but no borrows
     set_protocol_analytics(&env, &contract_id, 10000, 0);
 
-    let utilization = client.get_utilization();
+    let utilization = client.get_utilization(&None);
     assert_eq!(utilization, 0);
 }
 
@@ -95,8 +95,8 @@ fn test_utilization_fifty_percent() {
     // Set 50% utilization
     set_protocol_analytics(&env, &contract_id, 10000, 5000);
 
-    let utilization = client.get_utilization();
-    assert_eq!(utilization, 5000); // 50% = 5000 basis points
+    let utilization = client.get_utilization(&None);
+    assert_eq!(utilization, to_rate_view(5000)); // 50% = 5000 basis points
 }
 
 /// Test utilization at 80% (kink)
@@ -108,8 +108,8 @@ fn test_utilization_at_kink() {
     // Set 80% utilization (default kink)
     set_protocol_analytics(&env, &contract_id, 10000, 8000);
 
-    let utilization = client.get_utilization();
-    assert_eq!(utilization, 8000); // 80% = 8000 basis points
+    let utilization = client.get_utilization(&None);
+    assert_eq!(utilization, to_rate_view(8000)); // 80% = 8000 basis points
 }
 
 /// Test utilization at 100%
@@ -121,8 +121,8 @@ fn test_utilization_full() {
     // Set 100% utilization
     set_protocol_analytics(&env, &contract_id, 10000, 10000);
 
-    let utilization = client.get_utilization();
-    assert_eq!(utilization, 10000); // 100% = 10000 basis points
+    let utilization = client.get_utilization(&None);
+    assert_eq!(utilization, to_rate_view(10000)); // 100% = 10000 basis points
 }
 
 /// Test utilization caps at 100% even with more borrows than deposits
@@ -134,8 +134,8 @@ fn test_utilization_capped_at_100() {
     // Set more borrows than deposits (shouldn't happen normally)
     set_protocol_analytics(&env, &contract_id, 10000, 15000);
 
-    let utilization = client.get_utilization();
-    assert_eq!(utilization, 10000); // Capped at 100%
+    let utilization = client.get_utilization(&None);
+    assert_eq!(utilization, to_rate_view(10000)); // Capped at 100%
 }
 
 /// Test utilization with no deposits returns 0
@@ -147,7 +147,7 @@ fn test_utilization_no_deposits() {
     // No deposits
     set_protocol_analytics(&env, &contract_id, 0, 0);
 
-    let utilization = client.get_utilization();
+    let utilization = client.get_utilization(&None);
     assert_eq!(utilization, 0);
 }
 
@@ -163,12 +163,12 @@ fn test_borrow_rate_at_zero_utilization() {
 
     set_protocol_analytics(&env, &contract_id, 10000, 0);
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // At 0% utilization, rate should be base rate (100 bps = 1%)
     // But it should be at least the floor (50 bps)
-    assert!(borrow_rate >= 50);
-    assert_eq!(borrow_rate, 100); // Base rate
+    assert!(borrow_rate >= to_rate_view(50));
+    assert_eq!(borrow_rate, to_rate_view(100)); // Base rate
 }
 
 /// Test borrow rate below kink (linear increase)
@@ -180,11 +180,11 @@ fn test_borrow_rate_below_kink() {
     // 40% utilization (below 80% kink)
     set_protocol_analytics(&env, &contract_id, 10000, 4000);
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // Rate = base_rate + (utilization / kink_utilization) * multiplier
     // Rate = 100 + (4000 / 8000) * 2000 = 100 + 1000 = 1100 bps (11%)
-    assert_eq!(borrow_rate, 1100);
+    assert_eq!(borrow_rate, to_rate_view(1100));
 }
 
 /// Test borrow rate at kink
@@ -196,10 +196,10 @@ fn test_borrow_rate_at_kink() {
     // Exactly at kink (80%)
     set_protocol_analytics(&env, &contract_id, 10000, 8000);
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // Rate at kink = base_rate + multiplier = 100 + 2000 = 2100 bps (21%)
-    assert_eq!(borrow_rate, 2100);
+    assert_eq!(borrow_rate, to_rate_view(2100));
 }
 
 /// Test borrow rate above kink (steeper increase)
@@ -211,12 +211,12 @@ fn test_borrow_rate_above_kink() {
     // 90% utilization (above 80% kink)
     set_protocol_analytics(&env, &contract_id, 10000, 9000);
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // Rate = rate_at_kink + (utilization - kink) / (10000 - kink) * jump_multiplier
     // Rate = 2100 + (9000 - 8000) / (10000 - 8000) * 10000
     // Rate = 2100 + (1000 / 2000) * 10000 = 2100 + 5000 = 7100 bps (71%)
-    assert_eq!(borrow_rate, 7100);
+    assert_eq!(borrow_rate, to_rate_view(7100));
 }
 
 /// Test borrow rate at 100% utilization
@@ -228,11 +228,11 @@ fn test_borrow_rate_at_full_utilization() {
     // 100% utilization
     set_protocol_analytics(&env, &contract_id, 10000, 10000);
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // Rate = rate_at_kink + jump_multiplier = 2100 + 10000 = 12100
     // But capped at ceiling (10000 bps = 100%)
-    assert_eq!(borrow_rate, 10000);
+    assert_eq!(borrow_rate, to_rate_view(10000));
 }
 
 // =============================================================================
@@ -248,13 +248,13 @@ fn test_supply_rate_calculation() {
     // 40% utilization
     set_protocol_analytics(&env, &contract_id, 10000, 4000);
 
-    let borrow_rate = client.get_borrow_rate();
-    let supply_rate = client.get_supply_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
+    let supply_rate = client.get_supply_rate(&None);
 
-    // Supply rate = borrow_rate - spread (200 bps = 2%)
-    // Expected: 1100 - 200 = 900 bps (9%)
-    assert_eq!(supply_rate, borrow_rate - 200);
-    assert_eq!(supply_rate, 900);
+    // Supply rate = (borrow_rate - spread) * utilization
+    // Expected: (1100 - 200) * 40% = 360 bps (3.6%)
+    assert_eq!(borrow_rate, to_rate_view(1100));
+    assert_eq!(supply_rate, to_rate_view(360));
 }
 
 /// Test supply rate at low utilization respects floor
@@ -263,13 +263,14 @@ fn test_supply_rate_floor_enforcement() {
     let env = create_test_env();
     let (contract_id, _admin, client) = setup_contract_with_admin(&env);
 
-    // Very low utilization
-    set_protocol_analytics(&env, &contract_id, 10000, 100);
+    // Very low utilization: 225 - 200 = 25 bps, below the floor
+    set_protocol_analytics(&env, &contract_id, 10000, 500);
 
-    let supply_rate = client.get_supply_rate();
+    let supply_rate = client.get_supply_rate(&None);
 
-    // Supply rate should not go below floor (50 bps)
-    assert!(supply_rate >= 50);
+    // Supply rate is held at the floor (50 bps) and earned on the 5% of the
+    // pool that is lent out
+    assert_eq!(supply_rate, to_rate_view(50 * 500 / 10_000));
 }
 
 // =============================================================================
@@ -297,10 +298,10 @@ fn test_rate_floor_enforcement() {
         &None,
     );
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // Rate should be at floor even if calculated rate is lower
-    assert!(borrow_rate >= 100);
+    assert!(borrow_rate >= to_rate_view(100));
 }
 
 /// Test rate ceiling is enforced
@@ -312,11 +313,11 @@ fn test_rate_ceiling_enforcement() {
     // Set 100% utilization
     set_protocol_analytics(&env, &contract_id, 10000, 10000);
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // Rate should not exceed ceiling (10000 bps = 100%)
-    assert!(borrow_rate <= 10000);
-    assert_eq!(borrow_rate, 10000);
+    assert!(borrow_rate <= to_rate_view(10000));
+    assert_eq!(borrow_rate, to_rate_view(10000));
 }
 
 // =============================================================================
@@ -332,16 +333,16 @@ fn test_emergency_rate_adjustment_positive() {
     set_protocol_analytics(&env, &contract_id, 10000, 4000);
 
     // Get rate before adjustment
-    let rate_before = client.get_borrow_rate();
+    let rate_before = client.get_borrow_rate(&None);
 
     // Apply positive emergency adjustment (500 bps = 5%)
     client.set_emergency_rate_adjustment(&admin, &500);
 
     // Get rate after adjustment
-    let rate_after = client.get_borrow_rate();
+    let rate_after = client.get_borrow_rate(&None);
 
     // Rate should increase by 500 bps
-    assert_eq!(rate_after, rate_before + 500);
+    assert_eq!(rate_after, rate_before + to_rate_view(500));
 }
 
 /// Test negative emergency rate adjustment
@@ -353,16 +354,16 @@ fn test_emergency_rate_adjustment_negative() {
     set_protocol_analytics(&env, &contract_id, 10000, 4000);
 
     // Get rate before adjustment
-    let rate_before = client.get_borrow_rate();
+    let rate_before = client.get_borrow_rate(&None);
 
     // Apply negative emergency adjustment (-300 bps = -3%)
     client.set_emergency_rate_adjustment(&admin, &(-300));
 
     // Get rate after adjustment
-    let rate_after = client.get_borrow_rate();
+    let rate_after = client.get_borrow_rate(&None);
 
     // Rate should decrease by 300 bps
-    assert_eq!(rate_after, rate_before - 300);
+    assert_eq!(rate_after, rate_before - to_rate_view(300));
 }
 
 /// Test emergency adjustment unauthorized
@@ -411,8 +412,8 @@ fn test_update_config_base_rate() {
         &None,
     );
 
-    let borrow_rate = client.get_borrow_rate();
-    assert_eq!(borrow_rate, 200);
+    let borrow_rate = client.get_borrow_rate(&None);
+    assert_eq!(borrow_rate, to_rate_view(200));
 }
 
 /// Test updating kink utilization
@@ -437,11 +438,11 @@ fn test_update_config_kink() {
     );
 
     // Now 70% is above kink, so rate should be higher
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // With kink at 60%, rate at kink = 100 + 2000 = 2100
     // Above kink: 2100 + (7000 - 6000) / (10000 - 6000) * 10000 = 2100 + 2500 = 4600
-    assert_eq!(borrow_rate, 4600);
+    assert_eq!(borrow_rate, to_rate_view(4600));
 }
 
 /// Test updating multiplier
@@ -465,10 +466,10 @@ fn test_update_config_multiplier() {
         &None,
     );
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // Rate = 100 + (4000 / 8000) * 4000 = 100 + 2000 = 2100
-    assert_eq!(borrow_rate, 2100);
+    assert_eq!(borrow_rate, to_rate_view(2100));
 }
 
 /// Test updating jump multiplier
@@ -492,10 +493,10 @@ fn test_update_config_jump_multiplier() {
         &None,
     );
 
-    let borrow_rate = client.get_borrow_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
 
     // Rate = 2100 + (1000 / 2000) * 5000 = 2100 + 2500 = 4600
-    assert_eq!(borrow_rate, 4600);
+    assert_eq!(borrow_rate, to_rate_view(4600));
 }
 
 /// Test updating spread
@@ -518,11 +519,11 @@ fn test_update_config_spread() {
         &Some(500), // new spread
     );
 
-    let borrow_rate = client.get_borrow_rate();
-    let supply_rate = client.get_supply_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
+    let supply_rate = client.get_supply_rate(&None);
 
-    // Supply rate should now be borrow rate - 500
-    assert_eq!(supply_rate, borrow_rate - 500);
+    // Supply rate should now be (borrow rate - 500) at 40% utilization
+    assert_eq!(supply_rate, (borrow_rate - to_rate_view(500)) * 4 / 10);
 }
 
 /// Test config update unauthorized
@@ -741,7 +742,7 @@ fn test_rate_changes_with_utilization() {
         let util_bps = (util * 100) as i128;
         set_protocol_analytics(&env, &contract_id, 10000, util_bps);
 
-        let rate = client.get_borrow_rate();
+        let rate = client.get_borrow_rate(&None);
 
         // Rate should always increase with utilization
         assert!(
@@ -761,11 +762,11 @@ fn test_rate_jump_at_kink() {
 
     // Just below kink (79%)
     set_protocol_analytics(&env, &contract_id, 10000, 7900);
-    let rate_below_kink = client.get_borrow_rate();
+    let rate_below_kink = client.get_borrow_rate(&None);
 
     // Just above kink (81%)
     set_protocol_analytics(&env, &contract_id, 10000, 8100);
-    let rate_above_kink = client.get_borrow_rate();
+    let rate_above_kink = client.get_borrow_rate(&None);
 
     // Rate above kink should be noticeably higher due to jump multiplier
     assert!(rate_above_kink > rate_below_kink);
@@ -792,11 +793,11 @@ fn test_very_small_utilization() {
     // 0.01% utilization
     set_protocol_analytics(&env, &contract_id, 1_000_000, 100);
 
-    let rate = client.get_borrow_rate();
+    let rate = client.get_borrow_rate(&None);
 
     // Rate should be close to base rate
-    assert!(rate >= 50); // At least floor
-    assert!(rate <= 200); // Not too far from base rate
+    assert!(rate >= to_rate_view(50)); // At least floor
+    assert!(rate <= to_rate_view(200)); // Not too far from base rate
 }
 
 /// Test very large values
@@ -813,11 +814,11 @@ fn test_large_values() {
         500_000_000_000i128,
     );
 
-    let utilization = client.get_utilization();
-    let rate = client.get_borrow_rate();
+    let utilization = client.get_utilization(&None);
+    let rate = client.get_borrow_rate(&None);
 
     // 50% utilization
-    assert_eq!(utilization, 5000);
+    assert_eq!(utilization, to_rate_view(5000));
     // Rate should be calculated correctly
     assert!(rate > 0);
 }
@@ -831,9 +832,9 @@ fn test_rate_consistency() {
     set_protocol_analytics(&env, &contract_id, 10000, 5000);
 
     // Multiple calls should return same rate
-    let rate1 = client.get_borrow_rate();
-    let rate2 = client.get_borrow_rate();
-    let rate3 = client.get_borrow_rate();
+    let rate1 = client.get_borrow_rate(&None);
+    let rate2 = client.get_borrow_rate(&None);
+    let rate3 = client.get_borrow_rate(&None);
 
     assert_eq!(rate1, rate2);
     assert_eq!(rate2, rate3);
@@ -856,8 +857,8 @@ fn test_full_interest_rate_workflow() {
 
     // 2. Set initial utilization
     set_protocol_analytics(&env, &contract_id, 10000, 4000);
-    let initial_rate = client.get_borrow_rate();
-    assert_eq!(initial_rate, 1100); // base + half of multiplier
+    let initial_rate = client.get_borrow_rate(&None);
+    assert_eq!(initial_rate, to_rate_view(1100)); // base + half of multiplier
 
     // 3. Update config
     client.update_interest_rate_config(
@@ -872,15 +873,15 @@ fn test_full_interest_rate_workflow() {
     );
 
     // 4. Verify rate changed
-    let new_rate = client.get_borrow_rate();
+    let new_rate = client.get_borrow_rate(&None);
     assert!(new_rate > initial_rate);
 
     // 5. Apply emergency adjustment
     client.set_emergency_rate_adjustment(&admin, &300);
 
     // 6. Verify emergency adjustment applied
-    let emergency_rate = client.get_borrow_rate();
-    assert_eq!(emergency_rate, new_rate + 300);
+    let emergency_rate = client.get_borrow_rate(&None);
+    assert_eq!(emergency_rate, new_rate + to_rate_view(300));
 }
 
 /// Test interest accrual over time
@@ -905,12 +906,32 @@ fn test_interest_accrual_over_time() {
     });
 
     // Get rate
-    let rate = client.get_borrow_rate();
+    let rate_bps = client.get_borrow_rate(&None) / to_rate_view(1);
 
     // Calculate expected interest for 1 year on 50,000 borrowed
-    let expected_interest = calculate_accrued_interest(50_000, 0, SECONDS_PER_YEAR, rate).unwrap();
+    let expected_interest =
+        calculate_accrued_interest(50_000, 0, SECONDS_PER_YEAR, rate_bps).unwrap();
 
     // Interest should be reasonable (between 1% and 100% of principal)
     assert!(expected_interest > 500); // > 1%
     assert!(expected_interest < 50_000); // < 100%
 }
+
+#[test]
+fn test_rate_views_use_seven_decimals() {
+    let env = create_test_env();
+    let (contract_id, _admin, client) = setup_contract_with_admin(&env);
+
+    // 50% utilization: 100 + (5000 / 8000) * 2000 = 1350 bps = 13.5%,
+    // deposits earn (1350 - 200) * 50% = 575 bps
+    set_protocol_analytics(&env, &contract_id, 10000, 5000);
+    assert_eq!(client.get_utilization(&None), 5_000_000);
+    assert_eq!(client.get_borrow_rate(&None), 1_350_000);
+    assert_eq!(client.get_supply_rate(&None), 575_000);
+
+    // Core reserves share the pool's curve
+    let asset = Some(Address::generate(&env));
+    assert_eq!(client.get_utilization(&asset), 5_000_000);
+    assert_eq!(client.get_borrow_rate(&asset), 1_350_000);
+    assert_eq!(client.get_supply_rate(&asset), 575_000);
+}
@@ -0,0 +1,161 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::leverage::{open_leveraged_position, LeverageError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+struct LeverageTest {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'static>,
+    usdc: Address,
+    xlm: Address,
+    user: Address,
+}
+
+/// User holding 1_000 XLM approved to the contract, and a deep 50/50
+/// USDC/XLM pool hosted by the protocol's AMM router
+fn setup() -> LeverageTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    StellarAssetClient::new(&env, &usdc).mint(&admin, &1_000_000);
+    StellarAssetClient::new(&env, &xlm).mint(&admin, &1_000_000);
+    env.as_contract(&contract_id, || {
+        stellarlend_amm::update_amm_settings(
+            &env,
+            admin.clone(),
+            stellarlend_amm::AmmSettings {
+                default_slippage: 100,
+                max_slippage: 1_000,
+                swap_enabled: true,
+                liquidity_enabled: true,
+                auto_swap_threshold: 10_000,
+                pair_max_slippage: soroban_sdk::Map::new(&env),
+                max_swap_history: 1000,
+                swap_history_retention: 0,
+                max_deadline_window: 3600,
+            },
+        )
+        .unwrap();
+        stellarlend_amm::lbp::create_lbp_pool(
+            &env,
+            admin.clone(),
+            usdc.clone(),
+            xlm.clone(),
+            1_000_000,
+            1_000_000,
+            stellarlend_amm::WeightSchedule {
+                start_time: 0,
+                end_time: 1_000,
+                start_weight_a: 5_000,
+                end_weight_a: 5_000,
+            },
+            0,
+        )
+        .unwrap();
+    });
+
+    let user = Address::generate(&env);
+    StellarAssetClient::new(&env, &xlm).mint(&user, &1_000);
+    TokenClient::new(&env, &xlm).approve(
+        &user,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    LeverageTest {
+        env,
+        contract_id,
+        client,
+        usdc,
+        xlm,
+        user,
+    }
+}
+
+fn get_position(t: &LeverageTest) -> Option<Position> {
+    t.env.as_contract(&t.contract_id, || {
+        t.env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(t.user.clone()))
+    })
+}
+
+#[test]
+fn test_open_leveraged_position_loops_to_target() {
+    let t = setup();
+
+    // 1_000 XLM at 2x: borrow 909 USDC (the headroom) for 908 XLM, then
+    // 92 USDC for 91 XLM, which is within 0.5% of the 2_000 XLM target
+    let (collateral_added, debt_added, leverage) = t
+        .client
+        .open_leveraged_position(&t.user, &t.xlm, &t.usdc, &1_000, &20_000);
+    assert_eq!(collateral_added, 1_999);
+    assert_eq!(debt_added, 1_001);
+    assert_eq!(leverage, 19_990);
+
+    let position = get_position(&t).unwrap();
+    assert_eq!(position.collateral, 1_999);
+    assert_eq!(position.debt, 1_001);
+    // Borrowed funds never reached the user
+    assert_eq!(TokenClient::new(&t.env, &t.xlm).balance(&t.user), 0);
+    assert_eq!(TokenClient::new(&t.env, &t.usdc).balance(&t.user), 0);
+}
+
+#[test]
+fn test_open_leveraged_position_is_bounded_by_max_ltv() {
+    let t = setup();
+
+    // 110% minimum collateral ratio: max LTV 90.9%, about 11x
+    let max_leverage = t.client.get_max_leverage(&t.usdc);
+    assert_eq!(max_leverage, 109_890);
+
+    assert!(t
+        .client
+        .try_open_leveraged_position(&t.user, &t.xlm, &t.usdc, &1_000, &10_000)
+        .is_err());
+    assert!(t
+        .client
+        .try_open_leveraged_position(&t.user, &t.xlm, &t.xlm, &1_000, &20_000)
+        .is_err());
+    assert!(t
+        .client
+        .try_open_leveraged_position(&t.user, &t.xlm, &t.usdc, &1_000, &max_leverage)
+        .is_err());
+
+    // Nothing was deposited or borrowed
+    assert!(get_position(&t).is_none());
+    assert_eq!(TokenClient::new(&t.env, &t.xlm).balance(&t.user), 1_000);
+
+    t.env.as_contract(&t.contract_id, || {
+        let result = open_leveraged_position(
+            &t.env,
+            t.user.clone(),
+            t.xlm.clone(),
+            t.usdc.clone(),
+            1_000,
+            max_leverage,
+        );
+        assert_eq!(result, Err(LeverageError::InvalidLeverage));
+    });
+}
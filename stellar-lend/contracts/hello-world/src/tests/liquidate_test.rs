@@ -268,7 +268,7 @@ fn test_liquidate_not_undercollateralized() {
 
 /// Test liquidation at exact threshold boundary
 #[test]
-#[should_panic(expected = "Liquidation error")]
+#[should_panic(expected = "Error(Contract, #17)")]
 fn test_liquidate_at_threshold_boundary() {
     let env = create_test_env();
     let (contract_id, _admin, client) = setup_contract_with_admin(&env);
@@ -308,7 +308,7 @@ fn test_liquidate_just_below_threshold() {
 
 /// Test liquidation when paused
 #[test]
-#[should_panic(expected = "Liquidation error")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_liquidate_paused() {
     let env = create_test_env();
     let (contract_id, admin, client) = setup_contract_with_admin(&env);
@@ -328,7 +328,7 @@ fn test_liquidate_paused() {
 
 /// Test liquidation with emergency pause
 #[test]
-#[should_panic(expected = "Liquidation error")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_liquidate_emergency_paused() {
     let env = create_test_env();
     let (contract_id, admin, client) = setup_contract_with_admin(&env);
@@ -509,7 +509,7 @@ fn test_liquidate_multiple_liquidations() {
 
 /// Test liquidation with zero amount
 #[test]
-#[should_panic(expected = "Liquidation error")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_liquidate_zero_amount() {
     let env = create_test_env();
     let (contract_id, _admin, client) = setup_contract_with_admin(&env);
@@ -524,7 +524,7 @@ fn test_liquidate_zero_amount() {
 
 /// Test liquidation with negative amount
 #[test]
-#[should_panic(expected = "Liquidation error")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_liquidate_negative_amount() {
     let env = create_test_env();
     let (contract_id, _admin, client) = setup_contract_with_admin(&env);
@@ -539,7 +539,7 @@ fn test_liquidate_negative_amount() {
 
 /// Test liquidation of user with no debt
 #[test]
-#[should_panic(expected = "Liquidation error")]
+#[should_panic(expected = "Error(Contract, #17)")]
 fn test_liquidate_no_debt() {
     let env = create_test_env();
     let (contract_id, _admin, client) = setup_contract_with_admin(&env);
@@ -567,7 +567,7 @@ fn test_liquidate_no_debt() {
 
 /// Test liquidation of non-existent position
 #[test]
-#[should_panic(expected = "Liquidation error")]
+#[should_panic(expected = "Error(Contract, #17)")]
 fn test_liquidate_no_position() {
     let env = create_test_env();
     let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
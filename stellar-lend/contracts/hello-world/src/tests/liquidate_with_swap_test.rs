@@ -0,0 +1,163 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate_with_swap, LiquidationError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+struct SwapTest {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'static>,
+    usdc: Address,
+    xlm: Address,
+    borrower: Address,
+}
+
+/// Borrower with 1_000 XLM of collateral and 1_000 USDC of debt, and a
+/// 50/50 USDC/XLM pool hosted by the protocol's AMM router
+fn setup() -> SwapTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    StellarAssetClient::new(&env, &usdc).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &xlm).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &xlm).mint(&contract_id, &1_000);
+    let borrower = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        stellarlend_amm::update_amm_settings(
+            &env,
+            admin.clone(),
+            stellarlend_amm::AmmSettings {
+                default_slippage: 100,
+                max_slippage: 1_000,
+                swap_enabled: true,
+                liquidity_enabled: true,
+                auto_swap_threshold: 10_000,
+                pair_max_slippage: soroban_sdk::Map::new(&env),
+                max_swap_history: 1000,
+                swap_history_retention: 0,
+                max_deadline_window: 3600,
+            },
+        )
+        .unwrap();
+        stellarlend_amm::lbp::create_lbp_pool(
+            &env,
+            admin.clone(),
+            usdc.clone(),
+            xlm.clone(),
+            10_000,
+            10_000,
+            stellarlend_amm::WeightSchedule {
+                start_time: 0,
+                end_time: 1_000,
+                start_weight_a: 5_000,
+                end_weight_a: 5_000,
+            },
+            0,
+        )
+        .unwrap();
+
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 1_000,
+                debt: 1_000,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+
+    SwapTest {
+        env,
+        contract_id,
+        client,
+        usdc,
+        xlm,
+        borrower,
+    }
+}
+
+fn get_position(t: &SwapTest) -> Position {
+    t.env.as_contract(&t.contract_id, || {
+        t.env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(t.borrower.clone()))
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_liquidate_with_swap_pays_surplus_to_liquidator() {
+    let t = setup();
+    let liquidator = Address::generate(&t.env);
+
+    // 100 USDC of debt seizes 110 XLM, which sells for 10_000 * 110 / 10_110 = 108 USDC
+    let (debt_liquidated, collateral_seized, surplus) =
+        t.client
+            .liquidate_with_swap(&liquidator, &t.borrower, &t.usdc, &t.xlm, &100, &5);
+    assert_eq!(debt_liquidated, 100);
+    assert_eq!(collateral_seized, 110);
+    assert_eq!(surplus, 8);
+
+    // The liquidator supplied nothing and only receives the surplus
+    assert_eq!(TokenClient::new(&t.env, &t.usdc).balance(&liquidator), 8);
+    assert_eq!(TokenClient::new(&t.env, &t.xlm).balance(&liquidator), 0);
+    let position = get_position(&t);
+    assert_eq!(position.debt, 900);
+    assert_eq!(position.collateral, 890);
+}
+
+#[test]
+fn test_liquidate_with_swap_reverts_on_slippage() {
+    let t = setup();
+    let liquidator = Address::generate(&t.env);
+
+    assert!(t
+        .client
+        .try_liquidate_with_swap(&liquidator, &t.borrower, &t.usdc, &t.xlm, &100, &9)
+        .is_err());
+    assert!(t
+        .client
+        .try_liquidate_with_swap(&liquidator, &t.borrower, &t.usdc, &t.usdc, &100, &0)
+        .is_err());
+
+    // Nothing was liquidated
+    assert_eq!(get_position(&t).debt, 1_000);
+    assert_eq!(TokenClient::new(&t.env, &t.usdc).balance(&liquidator), 0);
+
+    t.env.as_contract(&t.contract_id, || {
+        let result = liquidate_with_swap(
+            &t.env,
+            liquidator.clone(),
+            t.borrower.clone(),
+            t.usdc.clone(),
+            t.xlm.clone(),
+            100,
+            9,
+        );
+        assert_eq!(result, Err(LiquidationError::SlippageExceeded));
+    });
+}
@@ -0,0 +1,204 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::LiquidationError;
+use crate::liquidation_grace::LiquidationGraceError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Symbol,
+};
+
+fn setup(env: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (contract_id, admin, client)
+}
+
+fn create_position(env: &Env, contract_id: &Address, user: &Address, collateral: i128, debt: i128) {
+    env.as_contract(contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        let position = Position {
+            collateral,
+            debt,
+            borrow_interest: 0,
+            last_accrual_time: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::Position(user.clone()), &position);
+    });
+}
+
+#[test]
+fn test_set_liquidation_grace_validation() {
+    let env = Env::default();
+    let (_contract_id, admin, client) = setup(&env);
+
+    assert_eq!(client.get_liquidation_grace(&None), None);
+    client.set_liquidation_grace(&admin, &None, &100, &5_000);
+    let config = client.get_liquidation_grace(&None).unwrap();
+    assert_eq!(config.grace_ledgers, 100);
+    assert_eq!(config.max_debt, 5_000);
+
+    let result = client.try_set_liquidation_grace(&admin, &None, &17_281, &5_000);
+    assert_eq!(result, Err(Ok(LiquidationGraceError::InvalidConfig)));
+    let result = client.try_set_liquidation_grace(&admin, &None, &100, &0);
+    assert_eq!(result, Err(Ok(LiquidationGraceError::InvalidConfig)));
+
+    let attacker = Address::generate(&env);
+    let result = client.try_set_liquidation_grace(&attacker, &None, &100, &5_000);
+    assert_eq!(result, Err(Ok(LiquidationGraceError::Unauthorized)));
+}
+
+#[test]
+fn test_grace_window_delays_liquidation_of_small_positions() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    client.set_liquidation_grace(&admin, &None, &100, &5_000);
+
+    // 100% collateral ratio, below the 105% liquidation threshold
+    create_position(&env, &contract_id, &borrower, 1_000, 1_000);
+    let start = env.ledger().sequence();
+
+    // The first attempt only starts the grace window
+    let result = client.liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert_eq!(result, (0, 0, 0));
+    assert_eq!(client.get_grace_started(&borrower), Some(start));
+
+    // Liquidations fail with GracePeriodActive while the window runs
+    env.ledger().with_mut(|li| li.sequence_number = start + 99);
+    let result = client.try_liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert!(result.is_err());
+
+    // Liquidatable once the window has elapsed
+    env.ledger().with_mut(|li| li.sequence_number = start + 100);
+    let (debt_liquidated, _, _) = client.liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert_eq!(debt_liquidated, 100);
+}
+
+#[test]
+fn test_large_positions_get_no_grace_window() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    client.set_liquidation_grace(&admin, &None, &100, &999);
+
+    create_position(&env, &contract_id, &borrower, 1_000, 1_000);
+    let (debt_liquidated, _, _) = client.liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert_eq!(debt_liquidated, 100);
+    assert_eq!(client.get_grace_started(&borrower), None);
+}
+
+#[test]
+fn test_top_up_clears_grace_window() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    client.set_liquidation_grace(&admin, &None, &100, &5_000);
+
+    create_position(&env, &contract_id, &borrower, 1_000, 1_000);
+    client.liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert!(client.get_grace_started(&borrower).is_some());
+
+    // Still liquidatable after a partial top-up: the window keeps running
+    client.deposit_collateral(&borrower, &None, &10);
+    assert!(client.get_grace_started(&borrower).is_some());
+
+    client.deposit_collateral(&borrower, &None, &500);
+    assert_eq!(client.get_grace_started(&borrower), None);
+}
+
+#[test]
+fn test_recovery_grace_after_unpause() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    create_position(&env, &contract_id, &borrower, 1_000, 1_000);
+
+    assert_eq!(
+        client.try_set_recovery_grace(&admin, &17_281),
+        Err(Ok(LiquidationGraceError::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_set_recovery_grace(&Address::generate(&env), &50),
+        Err(Ok(LiquidationGraceError::Unauthorized))
+    );
+    client.set_recovery_grace(&admin, &50);
+    assert_eq!(client.get_recovery_grace(), 50);
+
+    // Unpausing a switch that was never set does not start a window
+    let op = Symbol::new(&env, "pause_liquidate");
+    client.set_pause_switch(&admin, &op, &false);
+    assert_eq!(client.get_recovery_grace_until(&None), 0);
+
+    client.set_pause_switch(&admin, &op, &true);
+    let start = env.ledger().sequence();
+    client.set_pause_switch(&admin, &op, &false);
+    assert_eq!(client.get_recovery_grace_until(&None), start + 50);
+
+    env.ledger().with_mut(|li| li.sequence_number = start + 49);
+    assert!(client
+        .try_liquidate(&liquidator, &borrower, &None, &None, &100)
+        .is_err());
+
+    env.ledger().with_mut(|li| li.sequence_number = start + 50);
+    let (debt_liquidated, _, _) = client.liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert_eq!(debt_liquidated, 100);
+
+    // Lifting an emergency pause blocks liquidations again
+    client.set_emergency_pause(&admin, &true);
+    client.set_emergency_pause(&admin, &false);
+    assert_eq!(client.get_recovery_grace_until(&None), start + 100);
+    assert!(client
+        .try_liquidate(&liquidator, &borrower, &None, &None, &100)
+        .is_err());
+}
+
+#[test]
+fn test_recovery_grace_after_oracle_outage() {
+    let env = Env::default();
+    let (_contract_id, admin, client) = setup(&env);
+    let token = Address::generate(&env);
+    let asset = Some(token.clone());
+    client.set_recovery_grace(&admin, &50);
+
+    client.update_price_feed(&admin, &token, &10_000_000, &7, &admin);
+    client.update_price_feed(&admin, &token, &10_000_000, &7, &admin);
+    assert_eq!(client.get_recovery_grace_until(&asset), 0);
+
+    // The first price after the feed went stale starts the asset's window
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    client.update_price_feed(&admin, &token, &10_000_000, &7, &admin);
+    let start = env.ledger().sequence();
+    assert_eq!(client.get_recovery_grace_until(&asset), start + 50);
+    assert_eq!(client.get_recovery_grace_until(&None), 0);
+
+    // Liquidations touching the asset are blocked, others are not
+    let borrower = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        let result = crate::liquidate::liquidate(
+            &env,
+            Address::generate(&env),
+            borrower.clone(),
+            None,
+            asset.clone(),
+            100,
+        );
+        assert_eq!(result, Err(LiquidationError::GracePeriodActive));
+        let result =
+            crate::liquidate::liquidate(&env, Address::generate(&env), borrower, None, None, 100);
+        assert_eq!(result, Err(LiquidationError::NotLiquidatable));
+    });
+}
@@ -0,0 +1,114 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+fn setup(env: &Env) -> (Address, Option<Address>, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (contract_id, Some(token), client)
+}
+
+fn fund(env: &Env, contract_id: &Address, token: &Address, user: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(user, &amount);
+    TokenClient::new(env, token).approve(
+        user,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+/// Deposit 1_000_000 and borrow 100_000 of the token
+fn open_borrower(
+    env: &Env,
+    contract_id: &Address,
+    asset: &Option<Address>,
+    client: &HelloContractClient,
+) -> Address {
+    let user = Address::generate(env);
+    fund(env, contract_id, asset.as_ref().unwrap(), &user, 1_000_000);
+    client.deposit_collateral(&user, asset, &1_000_000);
+    client.borrow_asset(&user, asset, &100_000);
+    user
+}
+
+/// Cut a position's collateral, as a collateral price drop would
+fn set_collateral(env: &Env, contract_id: &Address, user: &Address, collateral: i128) {
+    env.as_contract(contract_id, || {
+        let key = DepositDataKey::Position(user.clone());
+        let mut position: Position = env.storage().persistent().get(&key).unwrap();
+        position.collateral = collateral;
+        env.storage().persistent().set(&key, &position);
+    });
+}
+
+#[test]
+fn test_index_pages_through_liquidatable_borrowers() {
+    let env = Env::default();
+    let (contract_id, asset, client) = setup(&env);
+    let depositor = Address::generate(&env);
+    fund(
+        &env,
+        &contract_id,
+        asset.as_ref().unwrap(),
+        &depositor,
+        1_000,
+    );
+    client.deposit_collateral(&depositor, &asset, &1_000);
+    let first = open_borrower(&env, &contract_id, &asset, &client);
+    let second = open_borrower(&env, &contract_id, &asset, &client);
+    let third = open_borrower(&env, &contract_id, &asset, &client);
+
+    // Depositors without debt are not indexed
+    assert_eq!(client.get_indexed_borrower_count(), 3);
+    assert_eq!(client.get_liquidatable_positions(&0, &0).positions.len(), 0);
+
+    set_collateral(&env, &contract_id, &second, 100_000);
+    set_collateral(&env, &contract_id, &third, 50_000);
+
+    let page = client.get_liquidatable_positions(&0, &2);
+    assert_eq!(page.indexed_borrowers, 3);
+    assert_eq!(page.next_cursor, Some(2));
+    assert_eq!(page.positions.len(), 1);
+    let position = page.positions.get(0).unwrap();
+    assert_eq!(position.borrower, second);
+    assert_eq!(position.debt, 100_000);
+    assert_eq!(position.health_factor, 10_000);
+    assert_eq!(position.risk_level, 5);
+
+    let page = client.get_liquidatable_positions(&2, &2);
+    assert_eq!(page.next_cursor, None);
+    assert_eq!(page.positions.get(0).unwrap().borrower, third);
+    assert_eq!(page.positions.get(0).unwrap().health_factor, 5_000);
+    assert_eq!(client.get_liquidatable_positions(&3, &2).positions.len(), 0);
+
+    // Repaid borrowers leave the index and the last one takes their slot
+    fund(&env, &contract_id, asset.as_ref().unwrap(), &first, 100_000);
+    client.repay_debt(&first, &asset, &100_000);
+    assert_eq!(client.get_indexed_borrower_count(), 2);
+    let page = client.get_liquidatable_positions(&0, &1);
+    assert_eq!(page.positions.get(0).unwrap().borrower, third);
+    assert_eq!(page.next_cursor, Some(1));
+
+    fund(&env, &contract_id, asset.as_ref().unwrap(), &third, 100_000);
+    client.repay_debt(&third, &asset, &100_000);
+    let page = client.get_liquidatable_positions(&0, &0);
+    assert_eq!(page.indexed_borrowers, 1);
+    assert_eq!(page.positions.get(0).unwrap().borrower, second);
+
+    // Borrowing again re-indexes
+    client.borrow_asset(&first, &asset, &1_000);
+    assert_eq!(client.get_indexed_borrower_count(), 2);
+}
@@ -0,0 +1,234 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidation_insurance::LiquidationInsuranceError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+fn setup(env: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.set_insurance_config(&admin, &1_000, &10_000, &true);
+    (contract_id, admin, client)
+}
+
+fn create_position(env: &Env, contract_id: &Address, user: &Address, collateral: i128, debt: i128) {
+    env.as_contract(contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        let position = Position {
+            collateral,
+            debt,
+            borrow_interest: 0,
+            last_accrual_time: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::Position(user.clone()), &position);
+    });
+}
+
+/// Configure native XLM and fund its insurance fund from `admin`
+fn fund_native_insurance(env: &Env, admin: &Address, client: &HelloContractClient, amount: i128) {
+    let native = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.set_native_asset_address(admin, &native);
+    StellarAssetClient::new(env, &native).mint(admin, &amount);
+    client.fund_insurance(admin, &None, &amount);
+}
+
+fn get_position(env: &Env, contract_id: &Address, user: &Address) -> Position {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_set_insurance_config_validation() {
+    let env = Env::default();
+    let (_contract_id, admin, client) = setup(&env);
+
+    let config = client.get_insurance_config().unwrap();
+    assert_eq!(config.premium_bps, 1_000);
+    assert_eq!(config.max_coverage, 10_000);
+
+    let result = client.try_set_insurance_config(&admin, &2_001, &10_000, &true);
+    assert_eq!(result, Err(Ok(LiquidationInsuranceError::InvalidConfig)));
+
+    let attacker = Address::generate(&env);
+    let result = client.try_set_insurance_config(&attacker, &500, &10_000, &true);
+    assert_eq!(result, Err(Ok(LiquidationInsuranceError::Unauthorized)));
+}
+
+#[test]
+fn test_buy_insurance_requirements() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let user = Address::generate(&env);
+
+    let result = client.try_buy_insurance(&user, &None, &1_000);
+    assert_eq!(result, Err(Ok(LiquidationInsuranceError::NoPosition)));
+
+    create_position(&env, &contract_id, &user, 2_000, 1_000);
+    let result = client.try_buy_insurance(&user, &None, &10_001);
+    assert_eq!(result, Err(Ok(LiquidationInsuranceError::CoverageExceeded)));
+
+    let policy = client.buy_insurance(&user, &None, &1_000);
+    assert!(policy.active);
+    assert_eq!(policy.remaining_coverage, 1_000);
+    assert_eq!(policy.premium_bps, 1_000);
+
+    client.set_insurance_config(&admin, &1_000, &10_000, &false);
+    let result = client.try_buy_insurance(&user, &None, &1_000);
+    assert_eq!(
+        result,
+        Err(Ok(LiquidationInsuranceError::InsuranceDisabled))
+    );
+}
+
+#[test]
+fn test_premium_accrues_to_debt_and_fund() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let user = Address::generate(&env);
+    let asset = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    create_position(&env, &contract_id, &user, 2_000_000, 1_000_000);
+    client.buy_insurance(&user, &Some(asset.clone()), &1_000);
+
+    env.ledger().with_mut(|li| li.timestamp += SECONDS_PER_YEAR);
+    client.cancel_insurance(&user);
+
+    // 10% of 1_000_000 principal over one year, charged but not yet repaid
+    let position = get_position(&env, &contract_id, &user);
+    assert_eq!(position.borrow_interest, 100_000);
+    assert_eq!(client.get_insurance_fund(&Some(asset.clone())), 0);
+
+    let policy = client.get_insurance_policy(&user).unwrap();
+    assert!(!policy.active);
+    assert_eq!(policy.premiums_paid, 100_000);
+    assert_eq!(policy.premiums_owed, 100_000);
+
+    // Cancelled policies stop charging
+    let result = client.try_cancel_insurance(&user);
+    assert_eq!(result, Err(Ok(LiquidationInsuranceError::PolicyNotFound)));
+
+    // Repaid interest settles the premium first and funds the insurance
+    StellarAssetClient::new(&env, &asset).mint(&user, &60_000);
+    TokenClient::new(&env, &asset).approve(
+        &user,
+        &contract_id,
+        &60_000,
+        &(env.ledger().sequence() + 100),
+    );
+    client.repay_debt(&user, &Some(asset.clone()), &60_000);
+    assert_eq!(client.get_insurance_fund(&Some(asset.clone())), 60_000);
+    let policy = client.get_insurance_policy(&user).unwrap();
+    assert_eq!(policy.premiums_owed, 40_000);
+}
+
+#[test]
+fn test_insurance_rescues_position_before_liquidation() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+
+    // 100% collateral ratio, below the 105% liquidation threshold
+    create_position(&env, &contract_id, &borrower, 1_000, 1_000);
+    fund_native_insurance(&env, &admin, &client, 500);
+    client.buy_insurance(&borrower, &None, &200);
+
+    let result = client.liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert_eq!(result, (0, 0, 0));
+
+    // Just enough debt repaid to clear the threshold: 1000 * 10000 / 10500 = 952
+    let position = get_position(&env, &contract_id, &borrower);
+    assert_eq!(position.debt, 952);
+    assert_eq!(client.get_insurance_fund(&None), 452);
+
+    let policy = client.get_insurance_policy(&borrower).unwrap();
+    assert_eq!(policy.remaining_coverage, 152);
+    assert!(policy.active);
+}
+
+#[test]
+fn test_liquidation_proceeds_when_coverage_exhausted() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+
+    create_position(&env, &contract_id, &borrower, 1_000, 1_000);
+    fund_native_insurance(&env, &admin, &client, 500);
+    client.buy_insurance(&borrower, &None, &20);
+
+    let (debt_liquidated, _, _) = client.liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert_eq!(debt_liquidated, 100);
+
+    // 20 covered by the fund, 100 repaid by the liquidator
+    let position = get_position(&env, &contract_id, &borrower);
+    assert_eq!(position.debt, 880);
+
+    let policy = client.get_insurance_policy(&borrower).unwrap();
+    assert_eq!(policy.remaining_coverage, 0);
+    assert!(!policy.active);
+}
+
+#[test]
+fn test_policy_only_covers_its_debt_asset() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let borrower = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    let other_asset = Address::generate(&env);
+
+    create_position(&env, &contract_id, &borrower, 1_000, 1_000);
+    fund_native_insurance(&env, &admin, &client, 500);
+
+    // Policy written for a different debt asset does not cover native debt
+    client.buy_insurance(&borrower, &Some(other_asset), &200);
+    let (debt_liquidated, _, _) = client.liquidate(&liquidator, &borrower, &None, &None, &100);
+    assert_eq!(debt_liquidated, 100);
+    assert_eq!(client.get_insurance_fund(&None), 500);
+}
+
+#[test]
+fn test_fund_native_insurance_transfers_xlm() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+
+    // Native XLM has no token contract until one is configured
+    let result = client.try_fund_insurance(&admin, &None, &500);
+    assert_eq!(result, Err(Ok(LiquidationInsuranceError::InvalidAmount)));
+    assert_eq!(client.get_insurance_fund(&None), 0);
+
+    let native = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.set_native_asset_address(&admin, &native);
+    let funder = Address::generate(&env);
+    StellarAssetClient::new(&env, &native).mint(&funder, &500);
+
+    assert_eq!(client.fund_insurance(&funder, &None, &500), 500);
+    let xlm = TokenClient::new(&env, &native);
+    assert_eq!(xlm.balance(&funder), 0);
+    assert_eq!(xlm.balance(&contract_id), 500);
+    assert_eq!(client.get_insurance_fund(&None), 500);
+}
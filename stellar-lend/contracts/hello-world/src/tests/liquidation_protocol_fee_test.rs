@@ -0,0 +1,106 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::reserve::ReserveError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+/// Borrower with 1_000 of collateral and 1_000 of debt in the same token,
+/// and a liquidator holding 500 of it
+fn setup(env: &Env) -> (Address, Address, Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    let borrower = Address::generate(env);
+    let liquidator = Address::generate(env);
+    let sac = StellarAssetClient::new(env, &token);
+    sac.mint(&contract_id, &1_000);
+    sac.mint(&liquidator, &500);
+    TokenClient::new(env, &token).approve(&liquidator, &contract_id, &500, &1_000);
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 1_000,
+                debt: 1_000,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+    (admin, token, borrower, liquidator, client)
+}
+
+#[test]
+fn test_set_liquidation_protocol_fee_validation() {
+    let env = Env::default();
+    let (admin, token, _borrower, _liquidator, client) = setup(&env);
+    let asset = Some(token);
+
+    assert_eq!(
+        client.try_set_liquidation_protocol_fee(&admin, &asset, &10_001),
+        Err(Ok(ReserveError::InvalidLiquidationProtocolFee))
+    );
+    assert_eq!(
+        client.try_set_liquidation_protocol_fee(&admin, &asset, &-1),
+        Err(Ok(ReserveError::InvalidLiquidationProtocolFee))
+    );
+    assert_eq!(
+        client.try_set_liquidation_protocol_fee(&Address::generate(&env), &asset, &2_000),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+    assert_eq!(
+        client
+            .get_reserve_config(&asset)
+            .liquidation_protocol_fee_bps,
+        0
+    );
+    client.set_liquidation_protocol_fee(&admin, &asset, &2_000);
+    assert_eq!(
+        client
+            .get_reserve_config(&asset)
+            .liquidation_protocol_fee_bps,
+        2_000
+    );
+}
+
+#[test]
+fn test_liquidation_bonus_is_split_with_protocol() {
+    let env = Env::default();
+    let (admin, token, borrower, liquidator, client) = setup(&env);
+    let asset = Some(token.clone());
+    client.set_liquidation_protocol_fee(&admin, &asset, &2_000);
+
+    // 500 repaid for 550 of collateral: 40 of the 50 bonus to the liquidator
+    let (debt_liquidated, collateral_seized, _) =
+        client.liquidate(&liquidator, &borrower, &asset, &asset, &500);
+    assert_eq!(debt_liquidated, 500);
+    assert_eq!(collateral_seized, 550);
+    assert_eq!(TokenClient::new(&env, &token).balance(&liquidator), 540);
+    assert_eq!(client.get_reserve_balance(&asset), 10);
+}
+
+#[test]
+fn test_liquidator_keeps_whole_bonus_without_fee() {
+    let env = Env::default();
+    let (_admin, token, borrower, liquidator, client) = setup(&env);
+    let asset = Some(token.clone());
+
+    client.liquidate(&liquidator, &borrower, &asset, &asset, &500);
+    assert_eq!(TokenClient::new(&env, &token).balance(&liquidator), 550);
+    assert_eq!(client.get_reserve_balance(&asset), 0);
+}
@@ -0,0 +1,123 @@
+#![cfg(test)]
+
+use crate::deposit::{AssetParams, DepositDataKey, Position};
+use crate::reserve::ReserveError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+fn setup(env: &Env) -> (Address, Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (contract_id, admin, token, client)
+}
+
+fn set_collateral_factor(env: &Env, contract_id: &Address, token: &Address, factor: i128) {
+    env.as_contract(contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::AssetParams(token.clone()),
+            &AssetParams {
+                deposit_enabled: true,
+                collateral_factor: factor,
+                max_deposit: i128::MAX,
+                borrow_fee_bps: 0,
+            },
+        );
+    });
+}
+
+/// Borrower with 1_000 of collateral and 850 of debt (a 117% ratio)
+fn open_position(env: &Env, contract_id: &Address, token: &Address) -> Address {
+    let borrower = Address::generate(env);
+    StellarAssetClient::new(env, token).mint(contract_id, &1_000);
+    env.as_contract(contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 1_000,
+                debt: 850,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+    borrower
+}
+
+#[test]
+fn test_liquidation_threshold_must_cover_collateral_factor() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    set_collateral_factor(&env, &contract_id, &token, 7_500);
+
+    assert_eq!(
+        client.get_reserve_config(&asset).liquidation_threshold_bps,
+        0
+    );
+    assert_eq!(
+        client.try_set_liquidation_threshold(&admin, &asset, &7_000),
+        Err(Ok(ReserveError::InvalidLiquidationThreshold))
+    );
+    assert_eq!(
+        client.try_set_liquidation_threshold(&admin, &asset, &10_001),
+        Err(Ok(ReserveError::InvalidLiquidationThreshold))
+    );
+    assert_eq!(
+        client.try_set_liquidation_threshold(&Address::generate(&env), &asset, &8_000),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+    client.set_liquidation_threshold(&admin, &asset, &8_000);
+    assert_eq!(
+        client.get_reserve_config(&asset).liquidation_threshold_bps,
+        8_000
+    );
+}
+
+#[test]
+fn test_liquidation_uses_collateral_liquidation_threshold() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    set_collateral_factor(&env, &contract_id, &token, 7_500);
+    let borrower = open_position(&env, &contract_id, &token);
+    let liquidator = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&liquidator, &1_000);
+    TokenClient::new(&env, &token).approve(
+        &liquidator,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    // Above the global 105% ratio, so not liquidatable without a threshold
+    assert!(client
+        .try_liquidate(&liquidator, &borrower, &asset, &asset, &100)
+        .is_err());
+
+    // 80% of 1_000 no longer covers 850 of debt
+    client.set_liquidation_threshold(&admin, &asset, &8_000);
+    let (debt_liquidated, collateral_seized, _) =
+        client.liquidate(&liquidator, &borrower, &asset, &asset, &100);
+    assert_eq!(debt_liquidated, 100);
+    assert_eq!(collateral_seized, 110);
+
+    // 90% of the remaining 890 covers the remaining 750 of debt
+    client.set_liquidation_threshold(&admin, &asset, &9_000);
+    assert!(client
+        .try_liquidate(&liquidator, &borrower, &asset, &asset, &100)
+        .is_err());
+}
@@ -0,0 +1,282 @@
+//! Tests for borrowing against LP shares of native AMM pools.
+
+use crate::cross_asset::{AssetConfig, CrossAssetError};
+use crate::reserve::ReserveError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env};
+use stellarlend_amm::{LiquidityParams, SwapParams};
+
+const UNIT: i128 = 10_000_000;
+
+fn asset_config(env: &Env, asset: Option<Address>, price: i128) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+struct LpTest<'a> {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'a>,
+    admin: Address,
+    token_a: Address,
+    token_b: Address,
+    pool_id: u32,
+    provider: Address,
+}
+
+/// A native pool of 4_000 A (price 1.0) and 1_000 B (price 4.0), and a
+/// provider holding 200 of its 2_000 shares
+fn setup<'a>() -> LpTest<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    // AMM initialization also sets the protocol admin
+    client.initialize_amm(&admin, &100, &1000, &10000);
+    client.initialize_ca(&admin);
+
+    let token_a = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_b = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.initialize_asset(&None, &asset_config(&env, None, UNIT));
+    client.initialize_asset(
+        &Some(token_a.clone()),
+        &asset_config(&env, Some(token_a.clone()), UNIT),
+    );
+    client.initialize_asset(
+        &Some(token_b.clone()),
+        &asset_config(&env, Some(token_b.clone()), 4 * UNIT),
+    );
+
+    StellarAssetClient::new(&env, &token_a).mint(&admin, &(4_000 * UNIT));
+    StellarAssetClient::new(&env, &token_b).mint(&admin, &(1_000 * UNIT));
+    let pool_id = client.amm_create_pool(
+        &admin,
+        &token_a,
+        &token_b,
+        &(3_600 * UNIT),
+        &(900 * UNIT),
+        &30,
+    );
+
+    let provider = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_a).mint(&provider, &(400 * UNIT));
+    StellarAssetClient::new(&env, &token_b).mint(&provider, &(100 * UNIT));
+    let shares = client.amm_add_liquidity(
+        &provider,
+        &LiquidityParams {
+            protocol: contract_id.clone(),
+            token_a: Some(token_a.clone()),
+            token_b: Some(token_b.clone()),
+            amount_a: 400 * UNIT,
+            amount_b: 100 * UNIT,
+            min_amount_a: 0,
+            min_amount_b: 0,
+            deadline: env.ledger().timestamp() + 300,
+        },
+    );
+    assert_eq!(shares, 200 * UNIT);
+    client.set_lp_haircut(&admin, &Some(token_b.clone()), &5_000);
+
+    LpTest {
+        env,
+        contract_id,
+        client,
+        admin,
+        token_a,
+        token_b,
+        pool_id,
+        provider,
+    }
+}
+
+#[test]
+fn test_borrow_against_lp_shares_at_fair_value() {
+    let t = setup();
+    assert_eq!(
+        t.client.get_lp_fair_value(&t.pool_id, &(200 * UNIT)),
+        800 * UNIT
+    );
+    assert_eq!(
+        t.client
+            .get_reserve_config(&Some(t.token_b.clone()))
+            .lp_haircut_bps,
+        5_000
+    );
+
+    assert_eq!(
+        t.client
+            .deposit_lp_collateral(&t.provider, &t.pool_id, &(200 * UNIT)),
+        200 * UNIT
+    );
+    assert_eq!(t.client.amm_get_lp_shares(&t.pool_id, &t.provider), 0);
+    assert_eq!(
+        t.client.amm_get_lp_shares(&t.pool_id, &t.contract_id),
+        200 * UNIT
+    );
+
+    // Each side is worth 400; B loses half to its haircut. At 75% the
+    // shares support 300 + 150 of debt
+    assert_eq!(
+        t.client
+            .try_cross_asset_borrow(&t.provider, &None, &(450 * UNIT + 1)),
+        Err(Ok(CrossAssetError::ExceedsBorrowCapacity))
+    );
+    t.client
+        .cross_asset_borrow(&t.provider, &None, &(450 * UNIT));
+    let legs = t.client.get_portfolio(&t.provider);
+    assert_eq!(legs.len(), 3);
+    let leg_b = legs.get(2).unwrap();
+    assert_eq!(leg_b.asset, Some(t.token_b.clone()));
+    assert_eq!(leg_b.lp_pool, Some(t.pool_id));
+    assert_eq!(leg_b.collateral_value, 200 * UNIT);
+
+    // At 80% thresholds the shares hold 480 against 450 of debt
+    assert_eq!(
+        t.client
+            .try_withdraw_lp_collateral(&t.provider, &t.pool_id, &(13 * UNIT)),
+        Err(Ok(CrossAssetError::UnhealthyPosition))
+    );
+    assert_eq!(
+        t.client
+            .withdraw_lp_collateral(&t.provider, &t.pool_id, &(12 * UNIT)),
+        188 * UNIT
+    );
+
+    // Pushing the reserves around does not raise the fair value beyond the
+    // swap fee left in the pool
+    let trader = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.token_a).mint(&trader, &(4_000 * UNIT));
+    t.client.amm_swap(
+        &trader,
+        &SwapParams {
+            protocol: t.contract_id.clone(),
+            token_in: Some(t.token_a.clone()),
+            token_out: Some(t.token_b.clone()),
+            amount_in: 4_000 * UNIT,
+            min_amount_out: 1,
+            slippage_tolerance: 100,
+            deadline: t.env.ledger().timestamp() + 300,
+            use_oracle_floor: false,
+        },
+    );
+    let fair_value = t.client.get_lp_fair_value(&t.pool_id, &(200 * UNIT));
+    assert!(fair_value > 800 * UNIT && fair_value < 802 * UNIT);
+
+    // The haircut is bounded
+    assert_eq!(
+        t.client
+            .try_set_lp_haircut(&t.admin, &Some(t.token_b.clone()), &10_001),
+        Err(Ok(ReserveError::InvalidLpHaircut))
+    );
+}
+
+#[test]
+fn test_liquidate_lp_collateral() {
+    let t = setup();
+    let liquidator = Address::generate(&t.env);
+    t.client
+        .deposit_lp_collateral(&t.provider, &t.pool_id, &(200 * UNIT));
+    t.client
+        .cross_asset_borrow(&t.provider, &None, &(450 * UNIT));
+    assert_eq!(
+        t.client.try_liquidate_lp_collateral(
+            &liquidator,
+            &t.provider,
+            &None,
+            &t.pool_id,
+            &(100 * UNIT)
+        ),
+        Err(Ok(CrossAssetError::NotLiquidatable))
+    );
+
+    // B halves to 2.0, so each side of the pool is worth sqrt(4_000 * 2_000)
+    t.client
+        .update_asset_price(&Some(t.token_b.clone()), &(2 * UNIT));
+    assert!(
+        t.client
+            .get_user_position_summary(&t.provider)
+            .is_liquidatable
+    );
+    assert_eq!(t.client.get_best_liquidation(&t.provider), None);
+
+    let (repaid, seized) = t.client.liquidate_lp_collateral(
+        &liquidator,
+        &t.provider,
+        &None,
+        &t.pool_id,
+        &(100 * UNIT),
+    );
+    assert_eq!(repaid, 100 * UNIT);
+    // 110 of value at the pool's fair value per share
+    let share_value = t.client.get_lp_fair_value(&t.pool_id, &seized);
+    assert!(share_value <= 110 * UNIT && share_value > 110 * UNIT - 10);
+    assert_eq!(
+        t.client.get_pledged_lp_shares(&t.provider, &t.pool_id),
+        200 * UNIT - seized
+    );
+    assert_eq!(
+        t.client
+            .get_user_asset_position(&t.provider, &None)
+            .debt_principal,
+        350 * UNIT
+    );
+
+    // Seized shares are pledged to the liquidator, who owes nothing
+    assert_eq!(
+        t.client
+            .withdraw_lp_collateral(&liquidator, &t.pool_id, &seized),
+        0
+    );
+    assert_eq!(t.client.amm_get_lp_shares(&t.pool_id, &liquidator), seized);
+}
+
+#[test]
+fn test_pool_reserves_are_not_lendable() {
+    let t = setup();
+    let liquidity = |token: &Address| {
+        t.env.as_contract(&t.contract_id, || {
+            crate::reserve::available_liquidity(&t.env, &Some(token.clone()), token)
+        })
+    };
+
+    // The contract holds 4_000 A and 1_000 B, all of it pool reserves
+    assert_eq!(liquidity(&t.token_a), 0);
+    assert_eq!(liquidity(&t.token_b), 0);
+
+    StellarAssetClient::new(&t.env, &t.token_a).mint(&t.contract_id, &(50 * UNIT));
+    assert_eq!(liquidity(&t.token_a), 50 * UNIT);
+
+    // Swaps move reserves without touching the lendable balance
+    let user = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.token_b).mint(&user, &(10 * UNIT));
+    t.client.amm_swap(
+        &user,
+        &SwapParams {
+            protocol: t.contract_id.clone(),
+            token_in: Some(t.token_b.clone()),
+            token_out: Some(t.token_a.clone()),
+            amount_in: 10 * UNIT,
+            min_amount_out: 1,
+            slippage_tolerance: 100,
+            deadline: t.env.ledger().timestamp() + 300,
+            use_oracle_floor: false,
+        },
+    );
+    assert_eq!(liquidity(&t.token_a), 50 * UNIT);
+    assert_eq!(liquidity(&t.token_b), 0);
+}
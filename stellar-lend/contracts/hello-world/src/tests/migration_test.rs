@@ -0,0 +1,114 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::migration::MigrationError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Old and new pool, each with its own admin; a user holds 1_000_000
+/// collateral and 400_000 debt in the old pool
+fn setup(
+    env: &Env,
+) -> (
+    HelloContractClient<'_>,
+    HelloContractClient<'_>,
+    Address,
+    Address,
+) {
+    env.mock_all_auths();
+    let old_id = env.register(HelloContract, ());
+    let old_pool = HelloContractClient::new(env, &old_id);
+    let old_admin = Address::generate(env);
+    old_pool.initialize(&old_admin);
+
+    let new_id = env.register(HelloContract, ());
+    let new_pool = HelloContractClient::new(env, &new_id);
+    let new_admin = Address::generate(env);
+    new_pool.initialize(&new_admin);
+
+    let user = Address::generate(env);
+    old_pool.deposit_collateral(&user, &None, &1_000_000);
+    old_pool.borrow_asset(&user, &None, &400_000);
+    old_pool.set_migration_target(&old_admin, &Some(new_id));
+    (old_pool, new_pool, new_admin, user)
+}
+
+fn get_position(env: &Env, contract_id: &Address, user: &Address) -> Option<Position> {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    })
+}
+
+#[test]
+fn test_migrate_position_moves_collateral_and_debt() {
+    let env = Env::default();
+    let (old_pool, new_pool, new_admin, user) = setup(&env);
+    new_pool.set_migration_source(&new_admin, &old_pool.address, &true);
+
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    let debt = old_pool.get_user_debt(&user);
+    assert!(debt > 400_000);
+
+    let (collateral, migrated_debt) = old_pool.migrate_position(&user, &new_pool.address);
+    assert_eq!(migrated_debt, debt);
+    assert!(collateral >= 1_000_000);
+
+    // The position now lives in the new pool only, owing the same debt
+    assert!(get_position(&env, &old_pool.address, &user).is_none());
+    assert_eq!(old_pool.get_user_debt(&user), 0);
+    assert_eq!(old_pool.get_indexed_borrower_count(), 0);
+    assert_eq!(new_pool.get_user_debt(&user), debt);
+    assert_eq!(
+        get_position(&env, &new_pool.address, &user)
+            .unwrap()
+            .collateral,
+        collateral
+    );
+    assert_eq!(new_pool.get_indexed_borrower_count(), 1);
+
+    // It keeps accruing in the new pool
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    assert!(new_pool.get_user_debt(&user) > debt);
+
+    // A user cannot migrate into an existing position
+    assert_eq!(
+        new_pool.try_receive_migrated_position(&old_pool.address, &user, &1, &0, &0),
+        Err(Ok(MigrationError::PositionExists))
+    );
+}
+
+#[test]
+fn test_migration_rejected_by_new_pool_is_reverted() {
+    let env = Env::default();
+    let (old_pool, new_pool, _new_admin, user) = setup(&env);
+
+    // The new pool does not accept positions from the old one yet
+    assert!(old_pool
+        .try_migrate_position(&user, &new_pool.address)
+        .is_err());
+    assert_eq!(old_pool.get_user_debt(&user), 400_000);
+    assert_eq!(
+        get_position(&env, &old_pool.address, &user)
+            .unwrap()
+            .collateral,
+        1_000_000
+    );
+    assert!(get_position(&env, &new_pool.address, &user).is_none());
+
+    let other_pool = Address::generate(&env);
+    assert_eq!(
+        old_pool.try_migrate_position(&user, &other_pool),
+        Err(Ok(MigrationError::InvalidTarget))
+    );
+    assert_eq!(
+        old_pool.try_migrate_position(&Address::generate(&env), &new_pool.address),
+        Err(Ok(MigrationError::NoPosition))
+    );
+}
@@ -16,6 +16,72 @@ pub mod risk_params_test;
 pub mod security_test;
 pub mod test;
 pub mod withdraw_test;
+pub mod strategy_vault_test;
+pub mod liquidation_insurance_test;
+pub mod cdp_test;
+pub mod savings_test;
+pub mod hedge_market_test;
+pub mod risk_monitor_test;
+pub mod bad_debt_test;
+pub mod config_simulation_test;
+pub mod config_export_test;
+pub mod protocol_init_test;
+pub mod shadow_oracle_test;
+pub mod upgrade_rehearsal;
+pub mod upgrade_rehearsal_test;
+pub mod health_history_test;
+pub mod close_position_test;
+pub mod intents_test;
+pub mod borrow_and_swap_test;
+pub mod open_position_test;
+pub mod liquidation_grace_test;
+pub mod operators_test;
+pub mod rate_stats_test;
+pub mod deposit_on_behalf_test;
+pub mod native_asset_test;
+pub mod supply_interest_test;
+pub mod interest_index_test;
+pub mod reserve_factor_test;
+pub mod price_oracle_test;
+pub mod portfolio_health_test;
+pub mod supply_cap_test;
+pub mod borrow_cap_test;
+pub mod siloed_borrow_test;
+pub mod liquidation_threshold_test;
+pub mod close_factor_test;
+pub mod auction_test;
+pub mod liquidation_protocol_fee_test;
+pub mod liquidate_with_swap_test;
+pub mod liquidation_index_test;
+pub mod repay_with_collateral_test;
+pub mod leverage_test;
+pub mod flash_loan_receiver_test;
+pub mod action_delay_test;
+pub mod credit_delegation_test;
+pub mod supply_token_test;
+pub mod debt_token_test;
+pub mod permit_test;
+pub mod position_token_test;
+pub mod migration_test;
+pub mod rewards_test;
+pub mod staking_test;
+pub mod safety_module_test;
+pub mod treasury_test;
+pub mod roles_test;
+pub mod param_timelock_test;
+pub mod granular_pause_test;
+pub mod emergency_withdrawal_test;
+pub mod upgrade_test;
+pub mod storage_ttl_test;
+pub mod protocol_error_test;
+pub mod user_activity_test;
+pub mod health_simulation_test;
+pub mod batch_query_test;
+pub mod batch_actions_test;
+pub mod standing_instructions_test;
+pub mod auto_deleverage_test;
+pub mod oracle_floor_test;
+pub mod lp_collateral_test;
 // Cross-asset tests disabled - contract methods not yet implemented
 pub mod views_test;
 // Cross-asset tests re-enabled when contract exposes full CA API (try_* return Result; get_user_asset_position; try_ca_repay_debt)
@@ -1,87 +1,112 @@
 #![cfg(test)]
 
-use crate::multisig::{
-    get_ms_admins, get_ms_threshold, ms_approve, ms_execute, ms_propose_set_min_cr, ms_set_admins,
-};
-use crate::governance::GovernanceError;
-use crate::HelloContract;
-use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, Vec};
-
-fn setup() -> (Env, Address, Address) {
-    let env = Env::default();
+use crate::errors::GovernanceError;
+use crate::types::ProposalType;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address) {
     env.mock_all_auths();
     let contract_id = env.register(HelloContract, ());
-    let admin = Address::generate(&env);
-    env.as_contract(&contract_id, || {
-        crate::governance::initialize_governance(&env, admin.clone()).unwrap();
-    });
-    (env, contract_id, admin)
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let vote_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+    );
+    (client, admin)
 }
 
-#[test]
-fn test_ms_set_admins_bootstrap() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let a2 = Address::generate(&env);
-        let a3 = Address::generate(&env);
-        let mut admins = Vec::new(&env);
-        admins.push_back(admin.clone());
-        admins.push_back(a2);
-        admins.push_back(a3);
-        ms_set_admins(&env, admin, admins, 2).unwrap();
-        assert_eq!(get_ms_admins(&env).unwrap().len(), 3);
-        assert_eq!(get_ms_threshold(&env), 2);
-    });
+fn propose(env: &Env, client: &HelloContractClient, admin: &Address) -> u64 {
+    client.gov_create_proposal(
+        admin,
+        &ProposalType::EmergencyPause(true),
+        &String::from_str(env, "Emergency pause"),
+        &None,
+    )
 }
 
 #[test]
-fn test_ms_set_admins_empty_returns_error() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let result = ms_set_admins(&env, admin, Vec::new(&env), 1);
-        assert_eq!(result, Err(GovernanceError::InvalidMultisigConfig));
-    });
+fn test_initialize_makes_admin_sole_signer() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let config = client.gov_get_multisig_config().unwrap();
+    assert_eq!(config.admins.len(), 1);
+    assert_eq!(config.admins.get(0).unwrap(), admin);
+    assert_eq!(config.threshold, 1);
 }
 
 #[test]
-fn test_ms_set_admins_duplicate_returns_error() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let mut admins = Vec::new(&env);
-        admins.push_back(admin.clone());
-        admins.push_back(admin.clone());
-        let result = ms_set_admins(&env, admin, admins, 1);
-        assert_eq!(result, Err(GovernanceError::InvalidMultisigConfig));
-    });
+fn test_set_multisig_config() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let mut admins = Vec::new(&env);
+    admins.push_back(admin.clone());
+    admins.push_back(Address::generate(&env));
+    admins.push_back(Address::generate(&env));
+
+    client.gov_set_multisig_config(&admin, &admins, &2);
+    let config = client.gov_get_multisig_config().unwrap();
+    assert_eq!(config.admins.len(), 3);
+    assert_eq!(config.threshold, 2);
 }
 
 #[test]
-fn test_ms_propose_min_cr_at_100_percent_returns_error() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let result = ms_propose_set_min_cr(&env, admin, 10_000);
-        assert_eq!(result, Err(GovernanceError::InvalidProposal));
-    });
+fn test_set_multisig_config_validation() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+
+    let result = client.try_gov_set_multisig_config(&admin, &Vec::new(&env), &1);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidMultisigConfig)));
+
+    let mut admins = Vec::new(&env);
+    admins.push_back(admin.clone());
+    let result = client.try_gov_set_multisig_config(&admin, &admins, &0);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidMultisigConfig)));
+    let result = client.try_gov_set_multisig_config(&admin, &admins, &2);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidMultisigConfig)));
+
+    let attacker = Address::generate(&env);
+    let result = client.try_gov_set_multisig_config(&attacker, &admins, &1);
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
 }
 
 #[test]
-fn test_ms_full_flow_2_of_2() {
-    let (env, cid, admin) = setup();
+fn test_multisig_approvals() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
     let admin2 = Address::generate(&env);
-    env.as_contract(&cid, || {
-        let mut admins = Vec::new(&env);
-        admins.push_back(admin.clone());
-        admins.push_back(admin2.clone());
-        ms_set_admins(&env, admin.clone(), admins, 2).unwrap();
-        // propose auto-approves for admin (1 of 2)
-        let pid = ms_propose_set_min_cr(&env, admin.clone(), 15_000).unwrap();
-        // admin2 approves — threshold (2) now met
-        ms_approve(&env, admin2.clone(), pid).unwrap();
-    });
-    env.ledger().with_mut(|li| {
-        li.timestamp += 10 * 24 * 60 * 60;
-    });
-    env.as_contract(&cid, || {
-        ms_execute(&env, admin, 1).unwrap();
-    });
+    let mut admins = Vec::new(&env);
+    admins.push_back(admin.clone());
+    admins.push_back(admin2.clone());
+    client.gov_set_multisig_config(&admin, &admins, &2);
+
+    let proposal_id = propose(&env, &client, &admin);
+    client.gov_approve_proposal(&admin, &proposal_id);
+    client.gov_approve_proposal(&admin2, &proposal_id);
+
+    let approvals = client.gov_get_proposal_approvals(&proposal_id).unwrap();
+    assert_eq!(approvals.len(), 2);
+    assert!(approvals.contains(&admin2));
+
+    let result = client.try_gov_approve_proposal(&admin2, &proposal_id);
+    assert_eq!(result, Err(Ok(GovernanceError::AlreadyVoted)));
+
+    let outsider = Address::generate(&env);
+    let result = client.try_gov_approve_proposal(&outsider, &proposal_id);
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
+
+    let result = client.try_gov_approve_proposal(&admin, &(proposal_id + 1));
+    assert_eq!(result, Err(Ok(GovernanceError::ProposalNotFound)));
 }
@@ -0,0 +1,63 @@
+#![cfg(test)]
+
+use crate::deposit::DepositDataKey;
+use soroban_sdk::{
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+fn get_collateral_balance(env: &Env, contract_id: &Address, user: &Address) -> i128 {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+            .unwrap_or(0)
+    })
+}
+
+#[test]
+fn test_native_collateral_moves_through_asset_contract() {
+    let (env, contract_id, client, _admin, user, native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    let token_client = TokenClient::new(&env, &native_asset);
+    StellarAssetClient::new(&env, &native_asset).mint(&user, &1_000);
+    token_client.approve(
+        &user,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    // Native XLM is pulled through its asset contract
+    client.deposit_collateral(&user, &None, &600);
+    assert_eq!(token_client.balance(&user), 400);
+    assert_eq!(token_client.balance(&contract_id), 600);
+
+    // Wrapped XLM lands in the same balance as native XLM
+    let balance = client.deposit_collateral(&user, &Some(native_asset.clone()), &400);
+    assert_eq!(balance, 1_000);
+    assert_eq!(get_collateral_balance(&env, &contract_id, &user), 1_000);
+
+    // Withdrawals pay native XLM back, whichever way the asset is named
+    client.withdraw_collateral(&user, &Some(native_asset.clone()), &300);
+    client.withdraw_collateral(&user, &None, &700);
+    assert_eq!(token_client.balance(&user), 1_000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(get_collateral_balance(&env, &contract_id, &user), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_native_deposit_requires_balance() {
+    let (env, contract_id, client, _admin, user, native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    StellarAssetClient::new(&env, &native_asset).mint(&user, &100);
+    TokenClient::new(&env, &native_asset).approve(
+        &user,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    client.deposit_collateral(&user, &None, &600);
+}
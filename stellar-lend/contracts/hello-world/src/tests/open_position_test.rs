@@ -0,0 +1,51 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>) {
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (contract_id, client)
+}
+
+fn get_position(env: &Env, contract_id: &Address, user: &Address) -> Option<Position> {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    })
+}
+
+#[test]
+fn test_open_position_deposits_and_borrows() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, client) = setup(&env);
+    let user = Address::generate(&env);
+
+    let (collateral, total_debt) = client.open_position(&user, &None, &1_000, &None, &500);
+    assert_eq!(collateral, 1_000);
+    assert_eq!(total_debt, 500);
+
+    let position = get_position(&env, &contract_id, &user).unwrap();
+    assert_eq!(position.collateral, 1_000);
+    assert_eq!(position.debt, 500);
+}
+
+#[test]
+fn test_open_position_reverts_deposit_when_borrow_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, client) = setup(&env);
+    let user = Address::generate(&env);
+
+    // 1_000 collateral cannot back 1_000 of debt at the minimum collateral ratio
+    assert!(client
+        .try_open_position(&user, &None, &1_000, &None, &1_000)
+        .is_err());
+    assert!(get_position(&env, &contract_id, &user).is_none());
+}
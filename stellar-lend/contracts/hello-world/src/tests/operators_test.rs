@@ -0,0 +1,139 @@
+#![cfg(test)]
+
+use crate::errors::ProtocolError;
+use crate::operators::{OperatorCap, OperatorError, OP_BORROW, OP_DEPOSIT};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+fn setup(env: &Env) -> HelloContractClient<'_> {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client
+}
+
+#[test]
+fn test_approve_enumerate_and_revoke_operators() {
+    let env = Env::default();
+    let client = setup(&env);
+    let user = Address::generate(&env);
+    let vault = Address::generate(&env);
+    let router = Address::generate(&env);
+    let caps = vec![
+        &env,
+        OperatorCap {
+            asset: None,
+            remaining: 1_000,
+        },
+    ];
+
+    client.approve_operator(&user, &vault, &OP_DEPOSIT, &caps, &1_000);
+    client.approve_operator(&user, &router, &(OP_DEPOSIT | OP_BORROW), &caps, &2_000);
+    // Re-approving replaces the approval without a second slot
+    client.approve_operator(&user, &vault, &OP_BORROW, &caps, &3_000);
+
+    let approvals = client.get_operator_approvals(&user);
+    assert_eq!(approvals.len(), 2);
+    assert_eq!(approvals.get(0).unwrap().operator, vault);
+    assert_eq!(approvals.get(0).unwrap().operations, OP_BORROW);
+    assert_eq!(approvals.get(0).unwrap().expires_at, 3_000);
+
+    client.revoke_operator(&user, &vault);
+    assert_eq!(client.get_operator_approval(&user, &vault), None);
+    assert_eq!(client.get_operator_approvals(&user).len(), 1);
+    assert_eq!(
+        client.try_revoke_operator(&user, &vault),
+        Err(Ok(OperatorError::ApprovalNotFound))
+    );
+}
+
+#[test]
+fn test_approve_operator_validation() {
+    let env = Env::default();
+    let client = setup(&env);
+    let user = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let cap = OperatorCap {
+        asset: None,
+        remaining: 1_000,
+    };
+    let caps = vec![&env, cap.clone()];
+
+    assert_eq!(
+        client.try_approve_operator(&user, &user, &OP_DEPOSIT, &caps, &1_000),
+        Err(Ok(OperatorError::InvalidOperator))
+    );
+    assert_eq!(
+        client.try_approve_operator(&user, &operator, &0, &caps, &1_000),
+        Err(Ok(OperatorError::InvalidOperations))
+    );
+    assert_eq!(
+        client.try_approve_operator(&user, &operator, &16, &caps, &1_000),
+        Err(Ok(OperatorError::InvalidOperations))
+    );
+    assert_eq!(
+        client.try_approve_operator(
+            &user,
+            &operator,
+            &OP_DEPOSIT,
+            &vec![&env, cap.clone(), cap],
+            &1_000
+        ),
+        Err(Ok(OperatorError::InvalidCap))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(
+        client.try_approve_operator(&user, &operator, &OP_DEPOSIT, &caps, &1_000),
+        Err(Ok(OperatorError::InvalidExpiry))
+    );
+}
+
+#[test]
+fn test_operator_actions_are_scoped_and_capped() {
+    let env = Env::default();
+    let client = setup(&env);
+    let user = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let caps = vec![
+        &env,
+        OperatorCap {
+            asset: None,
+            remaining: 1_000,
+        },
+    ];
+    client.approve_operator(&user, &operator, &OP_DEPOSIT, &caps, &1_000);
+
+    assert_eq!(client.operator_deposit(&operator, &user, &None, &600), 600);
+    let approval = client.get_operator_approval(&user, &operator).unwrap();
+    assert_eq!(approval.caps.get(0).unwrap().remaining, 400);
+
+    // Over the remaining cap, in an uncapped asset, or outside the operation set
+    assert_eq!(
+        client.try_operator_deposit(&operator, &user, &None, &401),
+        Err(Ok(ProtocolError::OperatorCapExceeded))
+    );
+    let other_asset = Address::generate(&env);
+    assert_eq!(
+        client.try_operator_deposit(&operator, &user, &Some(other_asset), &1),
+        Err(Ok(ProtocolError::OperatorCapExceeded))
+    );
+    assert_eq!(
+        client.try_operator_borrow(&operator, &user, &None, &100),
+        Err(Ok(ProtocolError::OperatorNotApproved))
+    );
+    assert_eq!(
+        client.try_operator_withdraw(&Address::generate(&env), &user, &None, &100),
+        Err(Ok(ProtocolError::OperatorNotApproved))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(
+        client.try_operator_deposit(&operator, &user, &None, &100),
+        Err(Ok(ProtocolError::OperatorApprovalExpired))
+    );
+}
@@ -0,0 +1,70 @@
+//! Tests for swaps whose minimum output is derived from the lending oracle.
+
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol, Vec};
+use stellarlend_amm::{AmmError, AmmProtocolConfig, SwapParams, TokenPair};
+
+#[test]
+fn test_amm_swap_derives_min_amount_out_from_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = HelloContractClient::new(&env, &env.register(HelloContract, ()));
+    let admin = Address::generate(&env);
+    // AMM initialization also sets the protocol admin
+    client.initialize_amm(&admin, &100, &1000, &10000);
+
+    let token_in = Address::generate(&env);
+    let token_out = Address::generate(&env);
+    let protocol = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let mut supported_pairs = Vec::new(&env);
+    supported_pairs.push_back(TokenPair {
+        token_a: Some(token_in.clone()),
+        token_b: Some(token_out.clone()),
+        pool_address: Address::generate(&env),
+    });
+    client.set_amm_pool(
+        &admin,
+        &AmmProtocolConfig {
+            protocol_address: protocol.clone(),
+            protocol_name: Symbol::new(&env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1000,
+            max_swap_amount: 1_000_000_000,
+            supported_pairs,
+        },
+    );
+    client.update_price_feed(&admin, &token_in, &100_000_000, &8, &oracle);
+    client.update_price_feed(&admin, &token_out, &100_000_000, &8, &oracle);
+
+    let user = Address::generate(&env);
+    let mut params = SwapParams {
+        protocol,
+        token_in: Some(token_in.clone()),
+        token_out: Some(token_out),
+        amount_in: 10_000,
+        min_amount_out: 0,
+        slippage_tolerance: 100,
+        deadline: env.ledger().timestamp() + 3600,
+        use_oracle_floor: true,
+    };
+
+    // Floor at par less 1%; the mock protocol fills at exactly that
+    assert_eq!(client.amm_swap(&user, &params), 9_900);
+
+    // The input is now worth 5% more, so a fill at par misses the floor
+    client.update_price_feed(&admin, &token_in, &105_000_000, &8, &oracle);
+    params.slippage_tolerance = 0;
+    assert_eq!(
+        client.try_amm_swap(&user, &params),
+        Err(Ok(AmmError::MinOutputNotMet))
+    );
+
+    // Without the flag a zero minimum is still rejected
+    params.use_oracle_floor = false;
+    assert_eq!(
+        client.try_amm_swap(&user, &params),
+        Err(Ok(AmmError::InvalidSwapParams))
+    );
+}
@@ -0,0 +1,194 @@
+#![cfg(test)]
+
+use crate::admin::{GUARDIAN_ROLE, RISK_MANAGER_ROLE};
+use crate::cross_asset::{AssetConfig, CrossAssetError};
+use crate::interest_rate::InterestRateError;
+use crate::param_timelock::{
+    InterestRateUpdate, ParamChange, ParamChangeStatus, ParamTimelockError,
+    PARAM_CHANGE_GRACE_PERIOD,
+};
+use crate::risk_management::RiskManagementError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Symbol,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Pool with a native asset, a guardian, a risk manager and a one-day
+/// parameter timelock
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    let client = HelloContractClient::new(env, &env.register(HelloContract, ()));
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &None,
+        &AssetConfig {
+            asset: None,
+            collateral_factor: 7_500,
+            liquidation_threshold: 8_000,
+            reserve_factor: 1_000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+        },
+    );
+
+    let guardian = Address::generate(env);
+    let risk_manager = Address::generate(env);
+    client.grant_role(&admin, &Symbol::new(env, GUARDIAN_ROLE), &guardian);
+    client.grant_role(&admin, &Symbol::new(env, RISK_MANAGER_ROLE), &risk_manager);
+    client.set_param_timelock_delay(&admin, &DAY);
+    (client, admin, guardian, risk_manager)
+}
+
+#[test]
+fn test_risk_params_change_waits_for_delay() {
+    let env = Env::default();
+    let (client, _admin, _guardian, risk_manager) = setup(&env);
+
+    assert_eq!(
+        client.try_set_risk_params(&risk_manager, &None, &None, &None, &Some(1_050)),
+        Err(Ok(RiskManagementError::TimelockActive))
+    );
+
+    let change = ParamChange::RiskParams(None, None, None, Some(1_050));
+    let id = client.queue_param_change(&risk_manager, &change);
+    let queued = client.get_param_change(&id).unwrap();
+    assert_eq!(queued.eta, env.ledger().timestamp() + DAY);
+    assert_eq!(client.get_pending_param_changes().len(), 1);
+
+    assert_eq!(
+        client.try_execute_param_change(&risk_manager, &id),
+        Err(Ok(ParamTimelockError::TimelockNotElapsed))
+    );
+    env.ledger().with_mut(|li| li.timestamp += DAY);
+    client.execute_param_change(&risk_manager, &id);
+
+    assert_eq!(
+        client.get_risk_config().unwrap().liquidation_incentive,
+        1_050
+    );
+    assert_eq!(
+        client.get_param_change(&id).unwrap().status,
+        ParamChangeStatus::Executed
+    );
+    assert!(client.get_pending_param_changes().is_empty());
+    assert_eq!(
+        client.try_execute_param_change(&risk_manager, &id),
+        Err(Ok(ParamTimelockError::ChangeNotQueued))
+    );
+}
+
+#[test]
+fn test_collateral_factor_and_interest_model_changes() {
+    let env = Env::default();
+    let (client, admin, guardian, risk_manager) = setup(&env);
+
+    assert_eq!(
+        client.try_update_asset_config(&None, &Some(7_000), &None, &None, &None, &None, &None),
+        Err(Ok(CrossAssetError::TimelockActive))
+    );
+    // Fields outside the timelock can still be changed directly
+    client.update_asset_config(&None, &None, &None, &Some(1_000_000), &None, &None, &None);
+    assert_eq!(
+        client.try_update_interest_rate_config(
+            &risk_manager,
+            &Some(200),
+            &None,
+            &None,
+            &None,
+            &None,
+            &None,
+            &None
+        ),
+        Err(Ok(InterestRateError::TimelockActive))
+    );
+
+    let cf_id =
+        client.queue_param_change(&risk_manager, &ParamChange::CollateralFactor(None, 7_000));
+    let rate_id = client.queue_param_change(
+        &admin,
+        &ParamChange::InterestModel(InterestRateUpdate {
+            base_rate: Some(200),
+            kink: None,
+            multiplier: None,
+            jump_multiplier: None,
+            rate_floor: None,
+            rate_ceiling: None,
+            spread: None,
+        }),
+    );
+    assert_eq!(
+        client.try_queue_param_change(&admin, &ParamChange::CollateralFactor(None, 10_001)),
+        Err(Ok(ParamTimelockError::InvalidChange))
+    );
+
+    // A guardian can cancel but not queue or execute
+    assert_eq!(
+        client.try_queue_param_change(&guardian, &ParamChange::AmmSlippage(100, 500)),
+        Err(Ok(ParamTimelockError::Unauthorized))
+    );
+    client.cancel_param_change(&guardian, &rate_id);
+    assert_eq!(
+        client.get_param_change(&rate_id).unwrap().status,
+        ParamChangeStatus::Cancelled
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += DAY);
+    assert_eq!(
+        client.try_execute_param_change(&guardian, &cf_id),
+        Err(Ok(ParamTimelockError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_execute_param_change(&admin, &rate_id),
+        Err(Ok(ParamTimelockError::ChangeNotQueued))
+    );
+    client.execute_param_change(&admin, &cf_id);
+    assert_eq!(client.get_asset_config(&None).collateral_factor, 7_000);
+}
+
+#[test]
+fn test_delay_changes_and_expiry() {
+    let env = Env::default();
+    let (client, admin, _guardian, risk_manager) = setup(&env);
+
+    // Raising is immediate, lowering has to wait for the current delay
+    client.set_param_timelock_delay(&admin, &(2 * DAY));
+    assert_eq!(
+        client.try_set_param_timelock_delay(&admin, &0),
+        Err(Ok(ParamTimelockError::TimelockActive))
+    );
+    assert_eq!(
+        client.try_queue_param_change(&risk_manager, &ParamChange::TimelockDelay(0)),
+        Err(Ok(ParamTimelockError::Unauthorized))
+    );
+    let stale_id = client.queue_param_change(
+        &risk_manager,
+        &ParamChange::RiskParams(None, None, Some(5_200), None),
+    );
+    let delay_id = client.queue_param_change(&admin, &ParamChange::TimelockDelay(0));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += 2 * DAY + PARAM_CHANGE_GRACE_PERIOD + 1);
+    assert_eq!(
+        client.try_execute_param_change(&risk_manager, &stale_id),
+        Err(Ok(ParamTimelockError::ChangeExpired))
+    );
+
+    let delay_id_again = client.queue_param_change(&admin, &ParamChange::TimelockDelay(0));
+    assert_eq!(delay_id_again, delay_id + 1);
+    env.ledger().with_mut(|li| li.timestamp += 2 * DAY);
+    client.execute_param_change(&admin, &delay_id_again);
+    assert_eq!(client.get_param_timelock_delay(), 0);
+
+    // With the timelock disabled, direct setters work again
+    client.set_risk_params(&risk_manager, &None, &None, &Some(5_200), &None);
+    assert_eq!(client.get_risk_config().unwrap().close_factor, 5_200);
+}
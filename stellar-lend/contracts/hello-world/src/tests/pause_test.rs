@@ -101,10 +101,10 @@ fn test_all_operations_unpaused_at_start() {
 ///
 /// # Architecture note
 /// The public `set_pause_switch` API writes to `RiskDataKey::RiskConfig.pause_switches`.
-/// The actual `deposit_collateral` operation reads from `DepositDataKey::PauseSwitches`
-/// (a separate, module-level storage key).  Blocking the raw operation at the storage
-/// level is covered by the existing tests in `test.rs` using `env.as_contract` direct
-/// writes; these tests focus on the behaviour of the public contract API.
+/// The operations check both these switches and the legacy `DepositDataKey::PauseSwitches`
+/// (a separate, module-level storage key).  Blocking through the legacy key is covered
+/// by the existing tests in `test.rs` using `env.as_contract` direct writes, and through
+/// the public API in `granular_pause_test.rs`; these tests focus on the API state.
 #[test]
 fn test_pause_deposit_blocks_deposit() {
     let e = env();
@@ -234,8 +234,8 @@ fn test_unpause_repay_allows_repay() {
     client.set_native_asset_address(&admin, &native_asset);
     let user = Address::generate(&e);
     let token = soroban_sdk::token::StellarAssetClient::new(&e, &native_asset);
-    token.mint(&user, &1000);
-    token.approve(&user, &id, &1000, &(e.ledger().sequence() + 100));
+    token.mint(&user, &11_000);
+    token.approve(&user, &id, &11_000, &(e.ledger().sequence() + 100));
 
     client.deposit_collateral(&user, &None, &10_000_i128);
     client.borrow_asset(&user, &None, &1_000_i128);
@@ -314,8 +314,8 @@ fn test_emergency_pause_does_not_block_repay() {
     client.set_native_asset_address(&admin, &native_asset);
     let user = Address::generate(&e);
     let token = soroban_sdk::token::StellarAssetClient::new(&e, &native_asset);
-    token.mint(&user, &1000);
-    token.approve(&user, &id, &1000, &(e.ledger().sequence() + 100));
+    token.mint(&user, &11_000);
+    token.approve(&user, &id, &11_000, &(e.ledger().sequence() + 100));
 
     client.deposit_collateral(&user, &None, &10_000_i128);
     client.borrow_asset(&user, &None, &1_000_i128);
@@ -357,9 +357,9 @@ fn test_emergency_pause_does_not_block_read_functions() {
     let _ = client.get_liquidation_threshold();
     let _ = client.get_close_factor();
     let _ = client.get_liquidation_incentive();
-    let _ = client.get_utilization();
-    let _ = client.get_borrow_rate();
-    let _ = client.get_supply_rate();
+    let _ = client.get_utilization(&None);
+    let _ = client.get_borrow_rate(&None);
+    let _ = client.get_supply_rate(&None);
     let _ = client.is_operation_paused(&Symbol::new(&e, "pause_deposit"));
     let _ = client.can_be_liquidated(&100_i128, &100_i128);
     let _ = client.get_max_liquidatable_amount(&1_000_i128);
@@ -433,8 +433,8 @@ fn test_pause_deposit_does_not_affect_other_operations() {
     client.set_native_asset_address(&admin, &native_asset);
     let user = Address::generate(&e);
     let token = soroban_sdk::token::StellarAssetClient::new(&e, &native_asset);
-    token.mint(&user, &1100);
-    token.approve(&user, &id, &1100, &(e.ledger().sequence() + 100));
+    token.mint(&user, &11_100);
+    token.approve(&user, &id, &11_100, &(e.ledger().sequence() + 100));
 
     // Set up prior state while deposit is unpaused.
     client.deposit_collateral(&user, &None, &10_000_i128);
@@ -464,8 +464,8 @@ fn test_pause_borrow_does_not_affect_other_operations() {
     client.set_native_asset_address(&admin, &native_asset);
     let user = Address::generate(&e);
     let token = soroban_sdk::token::StellarAssetClient::new(&e, &native_asset);
-    token.mint(&user, &2100);
-    token.approve(&user, &id, &2100, &(e.ledger().sequence() + 100));
+    token.mint(&user, &13_100);
+    token.approve(&user, &id, &13_100, &(e.ledger().sequence() + 100));
 
     client.deposit_collateral(&user, &None, &10_000_i128);
     client.borrow_asset(&user, &None, &1_000_i128);
@@ -620,8 +620,8 @@ fn test_pause_state_persists_across_calls() {
 
     // Perform several other operations that do not touch deposit.
     client.get_risk_config();
-    client.get_utilization();
-    client.get_borrow_rate();
+    client.get_utilization(&None);
+    client.get_borrow_rate(&None);
     client.is_emergency_paused();
     client.can_be_liquidated(&120_i128, &100_i128);
 
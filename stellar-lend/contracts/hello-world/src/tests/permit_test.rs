@@ -0,0 +1,128 @@
+#![cfg(test)]
+
+use crate::operators::{OP_BORROW, OP_DEPOSIT, OP_WITHDRAW};
+use crate::permit::{Permit, PermitError};
+use crate::{HelloContract, HelloContractClient};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env,
+};
+
+/// User holding 2_000 tokens approved to the contract, with a permit key
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address, Address, SigningKey) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let user = Address::generate(env);
+    StellarAssetClient::new(env, &token).mint(&user, &2_000);
+    TokenClient::new(env, &token).approve(
+        &user,
+        &contract_id,
+        &2_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    client.set_permit_signer(
+        &user,
+        &BytesN::from_array(env, &signing_key.verifying_key().to_bytes()),
+    );
+    (client, token, user, signing_key)
+}
+
+fn permit(
+    env: &Env,
+    user: &Address,
+    operation: u32,
+    token: &Address,
+    amount: i128,
+    nonce: u64,
+) -> Permit {
+    Permit {
+        user: user.clone(),
+        operation,
+        asset: Some(token.clone()),
+        amount,
+        nonce,
+        expires_at: env.ledger().timestamp() + 60,
+    }
+}
+
+fn sign(env: &Env, client: &HelloContractClient, key: &SigningKey, permit: &Permit) -> BytesN<64> {
+    let digest = client.get_permit_digest(permit).to_array();
+    BytesN::from_array(env, &key.sign(&digest).to_bytes())
+}
+
+#[test]
+fn test_relayer_submits_signed_deposit_and_withdraw() {
+    let env = Env::default();
+    let (client, token, user, key) = setup(&env);
+
+    let deposit = permit(&env, &user, OP_DEPOSIT, &token, 1_000, 0);
+    let signature = sign(&env, &client, &key, &deposit);
+
+    // No authorization from the user is needed on-chain
+    env.set_auths(&[]);
+    assert_eq!(client.execute_permit(&deposit, &signature), 1_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 1_000);
+    assert_eq!(client.get_permit_nonce(&user), 1);
+
+    // A permit executes once
+    assert_eq!(
+        client.try_execute_permit(&deposit, &signature),
+        Err(Ok(PermitError::InvalidNonce))
+    );
+
+    let withdraw = permit(&env, &user, OP_WITHDRAW, &token, 400, 1);
+    let signature = sign(&env, &client, &key, &withdraw);
+    assert_eq!(client.execute_permit(&withdraw, &signature), 600);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 1_400);
+    assert_eq!(client.get_permit_nonce(&user), 2);
+}
+
+#[test]
+fn test_permit_rejects_bad_signature_expiry_and_operation() {
+    let env = Env::default();
+    let (client, token, user, key) = setup(&env);
+
+    // Signed by a different key
+    let deposit = permit(&env, &user, OP_DEPOSIT, &token, 1_000, 0);
+    let forged = sign(&env, &client, &SigningKey::from_bytes(&[9; 32]), &deposit);
+    assert!(client.try_execute_permit(&deposit, &forged).is_err());
+
+    // Signed for a different amount
+    let signature = sign(&env, &client, &key, &deposit);
+    let mut altered = deposit.clone();
+    altered.amount = 2_000;
+    assert!(client.try_execute_permit(&altered, &signature).is_err());
+    assert_eq!(client.get_permit_nonce(&user), 0);
+
+    env.ledger().with_mut(|li| li.timestamp += 61);
+    assert_eq!(
+        client.try_execute_permit(&deposit, &signature),
+        Err(Ok(PermitError::PermitExpired))
+    );
+
+    let borrow = permit(&env, &user, OP_BORROW, &token, 100, 0);
+    let signature = sign(&env, &client, &key, &borrow);
+    assert_eq!(
+        client.try_execute_permit(&borrow, &signature),
+        Err(Ok(PermitError::InvalidOperation))
+    );
+
+    let stranger = Address::generate(&env);
+    let unsigned = permit(&env, &stranger, OP_DEPOSIT, &token, 100, 0);
+    let signature = sign(&env, &client, &key, &unsigned);
+    assert_eq!(
+        client.try_execute_permit(&unsigned, &signature),
+        Err(Ok(PermitError::SignerNotSet))
+    );
+}
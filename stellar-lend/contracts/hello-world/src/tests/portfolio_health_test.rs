@@ -0,0 +1,210 @@
+#![cfg(test)]
+
+use crate::cross_asset::{AssetConfig, CrossAssetError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+fn asset_config(
+    env: &Env,
+    asset: Option<Address>,
+    price: i128,
+    collateral_factor: i128,
+    liquidation_threshold: i128,
+) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor,
+        liquidation_threshold,
+        reserve_factor: 1_000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: collateral_factor > 0,
+        can_borrow: true,
+        price,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+/// Native at 1.0 (75% / 80%), token A at 2.0 (60% / 70%) and token B at 0.5
+/// (debt only)
+fn setup(env: &Env) -> (HelloContractClient<'_>, Option<Address>, Option<Address>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+
+    let token_a = Some(Address::generate(env));
+    let token_b = Some(Address::generate(env));
+    client.initialize_asset(&None, &asset_config(env, None, PRICE_ONE, 7_500, 8_000));
+    client.initialize_asset(
+        &token_a,
+        &asset_config(env, token_a.clone(), 2 * PRICE_ONE, 6_000, 7_000),
+    );
+    client.initialize_asset(
+        &token_b,
+        &asset_config(env, token_b.clone(), PRICE_ONE / 2, 0, 0),
+    );
+    (client, token_a, token_b)
+}
+
+#[test]
+fn test_health_factor_spans_all_collateral_and_debt_assets() {
+    let env = Env::default();
+    let (client, token_a, token_b) = setup(&env);
+    let user = Address::generate(&env);
+
+    client.cross_asset_deposit(&user, &None, &1_000);
+    client.cross_asset_deposit(&user, &token_a, &500);
+    client.cross_asset_borrow(&user, &None, &400);
+    client.cross_asset_borrow(&user, &token_b, &300);
+
+    let legs = client.get_portfolio(&user);
+    assert_eq!(legs.len(), 3);
+    let leg_a = legs.get(1).unwrap();
+    assert_eq!(leg_a.asset, token_a);
+    assert_eq!(leg_a.collateral_value, 1_000);
+    assert_eq!(leg_a.liquidation_threshold, 7_000);
+    let leg_b = legs.get(2).unwrap();
+    assert_eq!(leg_b.debt_value, 150);
+    assert_eq!(leg_b.collateral_factor, 0);
+
+    // (1_000 * 80% + 1_000 * 70%) / (400 + 150)
+    assert_eq!(client.get_health_factor(&user), 1_500 * 10_000 / 550);
+    let summary = client.get_user_position_summary(&user);
+    assert_eq!(summary.total_collateral_value, 2_000);
+    assert_eq!(summary.weighted_collateral_value, 1_500);
+    assert_eq!(summary.total_debt_value, 550);
+    // 1_000 * 75% + 1_000 * 60% - 550
+    assert_eq!(summary.borrow_capacity, 800);
+    assert!(!summary.is_liquidatable);
+}
+
+#[test]
+fn test_borrowing_power_is_shared_across_assets() {
+    let env = Env::default();
+    let (client, token_a, token_b) = setup(&env);
+    let user = Address::generate(&env);
+
+    client.cross_asset_deposit(&user, &None, &1_000);
+    client.cross_asset_deposit(&user, &token_a, &500);
+    client.cross_asset_borrow(&user, &None, &550);
+
+    // 800 of borrowing power left, i.e. 1_600 of token B
+    assert_eq!(
+        client.try_cross_asset_borrow(&user, &token_b, &1_602),
+        Err(Ok(CrossAssetError::ExceedsBorrowCapacity))
+    );
+    client.cross_asset_borrow(&user, &token_b, &1_600);
+    let summary = client.get_user_position_summary(&user);
+    assert_eq!(summary.borrow_capacity, 0);
+    assert_eq!(summary.health_factor, 1_500 * 10_000 / 1_350);
+
+    // A drop in one collateral asset affects the whole portfolio
+    client.update_asset_price(&token_a, &PRICE_ONE);
+    let summary = client.get_user_position_summary(&user);
+    assert_eq!(summary.health_factor, 1_150 * 10_000 / 1_350);
+    assert!(summary.is_liquidatable);
+}
+
+#[test]
+fn test_liquidation_price_of_each_collateral() {
+    let env = Env::default();
+    let (client, token_a, token_b) = setup(&env);
+    let user = Address::generate(&env);
+
+    client.cross_asset_deposit(&user, &None, &1_000);
+    client.cross_asset_deposit(&user, &token_a, &500);
+    client.cross_asset_borrow(&user, &None, &400);
+    // Native collateral alone covers the debt: no price of A liquidates
+    assert_eq!(client.get_liquidation_price(&user, &token_a), 0);
+
+    client.cross_asset_borrow(&user, &None, &150);
+    client.cross_asset_borrow(&user, &token_b, &1_600);
+    // 800 of native weighted collateral against 1_350 of debt: A must cover
+    // 550 at 70%, i.e. 500 A worth 785.71
+    assert_eq!(
+        client.get_liquidation_price(&user, &token_a),
+        550 * PRICE_ONE / 350
+    );
+    // Native also backs 550 of its own debt: 1_000 * 80% - 550 covers 100
+    assert_eq!(
+        client.get_liquidation_price(&user, &None),
+        100 * PRICE_ONE / 250
+    );
+    assert_eq!(
+        client.try_get_liquidation_price(&user, &token_b),
+        Err(Ok(CrossAssetError::InsufficientCollateral))
+    );
+
+    client.update_asset_price(&token_a, &15_800_000);
+    assert!(!client.get_user_position_summary(&user).is_liquidatable);
+    client.update_asset_price(&token_a, &15_600_000);
+    assert!(client.get_user_position_summary(&user).is_liquidatable);
+}
+
+#[test]
+fn test_liquidator_picks_collateral_to_seize() {
+    let env = Env::default();
+    let (client, token_a, token_b) = setup(&env);
+    let user = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+
+    client.cross_asset_deposit(&user, &None, &1_000);
+    client.cross_asset_deposit(&user, &token_a, &500);
+    client.cross_asset_borrow(&user, &None, &550);
+    client.cross_asset_borrow(&user, &token_b, &1_600);
+    assert_eq!(client.get_best_liquidation(&user), None);
+    assert_eq!(
+        client.try_cross_asset_liquidate(&liquidator, &user, &token_b, &token_a, &100),
+        Err(Ok(CrossAssetError::NotLiquidatable))
+    );
+
+    client.update_asset_price(&token_a, &PRICE_ONE);
+
+    // Repaying half of the 800 of token B debt against native collateral
+    // earns the largest bonus: 400 * 10%
+    let best = client.get_best_liquidation(&user).unwrap();
+    assert_eq!(best.debt_asset, token_b);
+    assert_eq!(best.collateral_asset, None);
+    assert_eq!(best.debt_amount, 800);
+    assert_eq!(best.collateral_amount, 440);
+    assert_eq!(best.bonus_value, 40);
+
+    assert_eq!(
+        client.try_cross_asset_liquidate(&liquidator, &user, &token_b, &token_a, &801),
+        Err(Ok(CrossAssetError::ExceedsCloseFactor))
+    );
+    assert_eq!(
+        client.try_cross_asset_liquidate(&user, &user, &token_b, &token_a, &800),
+        Err(Ok(CrossAssetError::NotAuthorized))
+    );
+
+    // The liquidator may seize token A instead
+    let (repaid, seized) =
+        client.cross_asset_liquidate(&liquidator, &user, &token_b, &token_a, &800);
+    assert_eq!((repaid, seized), (800, 440));
+    assert_eq!(
+        client.get_user_asset_position(&user, &token_a).collateral,
+        60
+    );
+    assert_eq!(
+        client
+            .get_user_asset_position(&user, &token_b)
+            .debt_principal,
+        800
+    );
+    assert_eq!(
+        client.get_user_asset_position(&user, &None).collateral,
+        1_000
+    );
+    assert_eq!(
+        client
+            .get_user_asset_position(&liquidator, &token_a)
+            .collateral,
+        440
+    );
+}
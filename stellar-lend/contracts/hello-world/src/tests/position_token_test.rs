@@ -0,0 +1,149 @@
+#![cfg(test)]
+
+use crate::cross_asset::AssetConfig;
+use crate::deposit::{DepositDataKey, Position};
+use crate::position_token::PositionTokenError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Seller with 1_000_000 core collateral, 400_000 core debt and 500 of
+/// cross-asset native collateral; a lender supplies the rest of the pool
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &None,
+        &AssetConfig {
+            asset: None,
+            collateral_factor: 7_500,
+            liquidation_threshold: 8_000,
+            reserve_factor: 1_000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+        },
+    );
+
+    let lender = Address::generate(env);
+    let seller = Address::generate(env);
+    client.deposit_collateral(&lender, &None, &1_000_000);
+    client.deposit_collateral(&seller, &None, &1_000_000);
+    client.borrow_asset(&seller, &None, &400_000);
+    client.cross_asset_deposit(&seller, &None, &500);
+    (contract_id, client, seller)
+}
+
+fn get_position(env: &Env, contract_id: &Address, user: &Address) -> Option<Position> {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    })
+}
+
+#[test]
+fn test_transfer_moves_whole_position_and_debt() {
+    let env = Env::default();
+    let (contract_id, client, seller) = setup(&env);
+    let buyer = Address::generate(&env);
+
+    let token_id = client.wrap_position(&seller);
+    assert_eq!(client.get_position_token(&seller), Some(token_id));
+    assert_eq!(
+        client.get_position_token_owner(&token_id),
+        Some(seller.clone())
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    let debt = client.get_user_debt(&seller);
+    assert!(debt > 400_000);
+
+    client.transfer_position(&seller, &buyer, &token_id);
+
+    // The buyer owes exactly what the seller did and keeps accruing from there
+    assert_eq!(client.get_user_debt(&buyer), debt);
+    assert_eq!(client.get_user_debt(&seller), 0);
+    assert!(get_position(&env, &contract_id, &seller).is_none());
+    assert_eq!(
+        get_position(&env, &contract_id, &buyer).unwrap().collateral,
+        1_000_000
+    );
+    assert_eq!(
+        client.get_user_asset_position(&buyer, &None).collateral,
+        500
+    );
+    assert_eq!(client.get_user_asset_position(&seller, &None).collateral, 0);
+    assert_eq!(client.get_indexed_borrower_count(), 1);
+
+    assert_eq!(
+        client.get_position_token_owner(&token_id),
+        Some(buyer.clone())
+    );
+    assert_eq!(client.get_position_token(&buyer), Some(token_id));
+    assert_eq!(client.get_position_token(&seller), None);
+
+    // The new owner manages the position with the regular entrypoints
+    client.borrow_asset(&buyer, &None, &1_000);
+    assert_eq!(client.get_user_debt(&buyer), debt + 1_000);
+    let collateral = get_position(&env, &contract_id, &buyer).unwrap().collateral;
+    client.withdraw_collateral(&buyer, &None, &100_000);
+    assert_eq!(
+        get_position(&env, &contract_id, &buyer).unwrap().collateral,
+        collateral - 100_000
+    );
+
+    client.unwrap_position(&buyer, &token_id);
+    assert_eq!(client.get_position_token_owner(&token_id), None);
+    assert_eq!(client.get_position_token(&buyer), None);
+}
+
+#[test]
+fn test_transfer_requires_owner_and_empty_recipient() {
+    let env = Env::default();
+    let (_contract_id, client, seller) = setup(&env);
+    let buyer = Address::generate(&env);
+
+    assert_eq!(
+        client.try_wrap_position(&buyer),
+        Err(Ok(PositionTokenError::NoPosition))
+    );
+    let token_id = client.wrap_position(&seller);
+    assert_eq!(
+        client.try_wrap_position(&seller),
+        Err(Ok(PositionTokenError::AlreadyWrapped))
+    );
+
+    assert_eq!(
+        client.try_transfer_position(&buyer, &seller, &token_id),
+        Err(Ok(PositionTokenError::NotOwner))
+    );
+    assert_eq!(
+        client.try_transfer_position(&seller, &buyer, &(token_id + 1)),
+        Err(Ok(PositionTokenError::TokenNotFound))
+    );
+    assert_eq!(
+        client.try_transfer_position(&seller, &seller, &token_id),
+        Err(Ok(PositionTokenError::InvalidRecipient))
+    );
+
+    // Positions are never merged
+    client.cross_asset_deposit(&buyer, &None, &1);
+    assert_eq!(
+        client.try_transfer_position(&seller, &buyer, &token_id),
+        Err(Ok(PositionTokenError::RecipientHasPosition))
+    );
+    assert_eq!(client.get_user_debt(&seller), 400_000);
+    assert_eq!(client.get_position_token_owner(&token_id), Some(seller));
+}
@@ -0,0 +1,211 @@
+#![cfg(test)]
+
+use crate::cross_asset::{AssetConfig, CrossAssetError};
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate, LiquidationError};
+use crate::price_oracle::{PriceOracleError, Sep40Asset, Sep40PriceData};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+/// Minimal SEP-40 feed with 14 decimals, like Reflector
+#[contract]
+pub struct MockSep40Feed;
+
+#[contractimpl]
+impl MockSep40Feed {
+    pub fn set_price(env: Env, asset: Sep40Asset, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&asset, &Sep40PriceData { price, timestamp });
+    }
+
+    pub fn lastprice(env: Env, asset: Sep40Asset) -> Option<Sep40PriceData> {
+        env.storage().instance().get(&asset)
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        14
+    }
+}
+
+fn asset_config(env: &Env, asset: Option<Address>, price: i128) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address, MockSep40FeedClient<'_>) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+    let feed = MockSep40FeedClient::new(env, &env.register(MockSep40Feed, ()));
+    (client, admin, feed)
+}
+
+#[test]
+fn test_feed_prices_are_normalized_and_cached() {
+    let env = Env::default();
+    let (client, admin, feed) = setup(&env);
+    let token = Address::generate(&env);
+    let asset = Some(token.clone());
+    let feed_asset = Sep40Asset::Stellar(token.clone());
+
+    assert_eq!(
+        client.try_get_asset_price(&asset),
+        Err(Ok(PriceOracleError::FeedNotConfigured))
+    );
+    assert_eq!(
+        client.try_set_price_feed(&Address::generate(&env), &asset, &feed.address, &feed_asset),
+        Err(Ok(PriceOracleError::Unauthorized))
+    );
+    client.set_price_feed(&admin, &asset, &feed.address, &feed_asset);
+    assert_eq!(client.get_price_feed(&asset).unwrap().decimals, 14);
+    assert_eq!(
+        client.try_get_asset_price(&asset),
+        Err(Ok(PriceOracleError::PriceUnavailable))
+    );
+
+    // 2.5 with 14 decimals is reported with 7
+    let now = env.ledger().timestamp();
+    feed.set_price(&feed_asset, &250_000_000_000_000, &now);
+    assert_eq!(client.get_asset_price(&asset), 25_000_000);
+
+    // Cached until the TTL expires, unless refreshed
+    feed.set_price(&feed_asset, &300_000_000_000_000, &now);
+    assert_eq!(client.get_asset_price(&asset), 25_000_000);
+    assert_eq!(client.refresh_asset_price(&asset), 30_000_000);
+    assert_eq!(client.get_asset_price(&asset), 30_000_000);
+
+    // A feed that stops updating is stale, not silently reused
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    assert_eq!(
+        client.try_get_asset_price(&asset),
+        Err(Ok(PriceOracleError::StalePrice))
+    );
+
+    client.remove_price_feed(&admin, &asset);
+    assert_eq!(client.get_price_feed(&asset), None);
+}
+
+#[test]
+fn test_health_factor_uses_feed_price() {
+    let env = Env::default();
+    let (client, admin, feed) = setup(&env);
+    let token = Address::generate(&env);
+    let asset = Some(token.clone());
+    let feed_asset = Sep40Asset::Other(symbol_short!("TKN"));
+    let user = Address::generate(&env);
+
+    client.initialize_asset(&None, &asset_config(&env, None, PRICE_ONE));
+    client.initialize_asset(&asset, &asset_config(&env, asset.clone(), PRICE_ONE));
+    client.cross_asset_deposit(&user, &asset, &1_000);
+    client.cross_asset_borrow(&user, &None, &500);
+    assert_eq!(
+        client
+            .get_user_position_summary(&user)
+            .total_collateral_value,
+        1_000
+    );
+
+    // The feed prices the token at 2.0, overriding the stored 1.0
+    client.set_price_feed(&admin, &asset, &feed.address, &feed_asset);
+    let now = env.ledger().timestamp();
+    feed.set_price(&feed_asset, &200_000_000_000_000, &now);
+    let summary = client.get_user_position_summary(&user);
+    assert_eq!(summary.total_collateral_value, 2_000);
+    assert_eq!(summary.health_factor, 2_000 * 8_000 / 500);
+
+    // A stale feed blocks the health factor
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+    client.update_asset_price(&None, &PRICE_ONE);
+    assert_eq!(
+        client.try_get_user_position_summary(&user),
+        Err(Ok(CrossAssetError::PriceStale))
+    );
+}
+
+#[test]
+fn test_max_price_age_rejects_stale_prices() {
+    let env = Env::default();
+    let (client, admin, feed) = setup(&env);
+    let token = Address::generate(&env);
+    let asset = Some(token.clone());
+    let feed_asset = Sep40Asset::Stellar(token.clone());
+    let user = Address::generate(&env);
+
+    assert_eq!(client.get_max_price_age(&asset), 3_600);
+    assert_eq!(
+        client.try_set_max_price_age(&admin, &asset, &0),
+        Err(Ok(PriceOracleError::InvalidMaxPriceAge))
+    );
+    assert_eq!(
+        client.try_set_max_price_age(&Address::generate(&env), &asset, &600),
+        Err(Ok(PriceOracleError::Unauthorized))
+    );
+    client.set_max_price_age(&admin, &asset, &600);
+    assert_eq!(client.get_max_price_age(&asset), 600);
+    assert_eq!(client.get_max_price_age(&None), 3_600);
+
+    // Stored cross-asset prices honour the asset's heartbeat
+    client.initialize_asset(&None, &asset_config(&env, None, PRICE_ONE));
+    client.initialize_asset(&asset, &asset_config(&env, asset.clone(), PRICE_ONE));
+    client.cross_asset_deposit(&user, &asset, &1_000);
+    env.ledger().with_mut(|li| li.timestamp += 601);
+    assert_eq!(
+        client.try_get_user_position_summary(&user),
+        Err(Ok(CrossAssetError::PriceStale))
+    );
+
+    // So do feed prices used by liquidations
+    client.set_price_feed(&admin, &asset, &feed.address, &feed_asset);
+    let updated_at = env.ledger().timestamp();
+    feed.set_price(&feed_asset, &100_000_000_000_000, &updated_at);
+    assert_eq!(client.get_asset_price(&asset), PRICE_ONE);
+    env.ledger().with_mut(|li| li.timestamp += 601);
+
+    let borrower = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        let position = Position {
+            collateral: 1_000,
+            debt: 1_000,
+            borrow_interest: 0,
+            last_accrual_time: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::Position(borrower.clone()), &position);
+
+        let result = liquidate(
+            &env,
+            Address::generate(&env),
+            borrower.clone(),
+            asset.clone(),
+            None,
+            100,
+        );
+        assert_eq!(result, Err(LiquidationError::StalePrice));
+    });
+}
@@ -0,0 +1,57 @@
+#![cfg(test)]
+
+use crate::errors::ProtocolError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+#[test]
+fn test_core_entrypoints_return_protocol_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = HelloContractClient::new(&env, &env.register(HelloContract, ()));
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let user = Address::generate(&env);
+
+    assert_eq!(
+        client.try_deposit_collateral(&user, &None, &0),
+        Err(Ok(ProtocolError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_withdraw_collateral(&user, &None, &100),
+        Err(Ok(ProtocolError::InsufficientCollateral))
+    );
+    assert_eq!(
+        client.try_repay_debt(&user, &Some(Address::generate(&env)), &100),
+        Err(Ok(ProtocolError::NoDebt))
+    );
+
+    client.deposit_collateral(&user, &None, &10_000);
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &1_000_000),
+        Err(Ok(ProtocolError::MaxBorrowExceeded))
+    );
+    assert_eq!(
+        client.try_liquidate(&admin, &user, &None, &None, &100),
+        Err(Ok(ProtocolError::NotLiquidatable))
+    );
+
+    // Every operation's pause surfaces as the same code
+    client.set_pause_switch(&admin, &Symbol::new(&env, "pause_borrow"), &true);
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &100),
+        Err(Ok(ProtocolError::Paused))
+    );
+    client.set_pause_switch(&admin, &Symbol::new(&env, "pause_withdraw"), &true);
+    assert_eq!(
+        client.try_withdraw_collateral(&user, &None, &100),
+        Err(Ok(ProtocolError::Paused))
+    );
+
+    // A failed borrow reverts the deposit of the same call
+    assert_eq!(
+        client.try_open_position(&user, &None, &5_000, &None, &100),
+        Err(Ok(ProtocolError::Paused))
+    );
+    assert_eq!(client.get_user_report(&user).position.collateral, 10_000);
+}
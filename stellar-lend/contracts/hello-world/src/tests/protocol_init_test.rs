@@ -0,0 +1,186 @@
+#![cfg(test)]
+
+use crate::admin::RoleGrant;
+use crate::config_export::OracleSourceConfig;
+use crate::cross_asset::AssetConfig;
+use crate::oracle::OracleConfig;
+use crate::protocol_init::{InitConfig, ProtocolInitError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, Symbol, Vec};
+use stellarlend_amm::{AmmProtocolConfig, AmmSettings, TokenPair};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+fn asset_config(env: &Env, asset: Option<Address>, price: i128) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 1_000_000,
+        max_borrow: 500_000,
+        can_collateralize: true,
+        can_borrow: true,
+        price,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+fn setup(env: &Env) -> HelloContractClient<'_> {
+    let contract_id = env.register(HelloContract, ());
+    HelloContractClient::new(env, &contract_id)
+}
+
+/// A deployment config with two reserves, a native asset, a fallback oracle,
+/// AMM settings and a protocol, and a keeper
+fn init_config(env: &Env, admin: &Address) -> InitConfig {
+    let token = Address::generate(env);
+
+    let mut reserves = Vec::new(env);
+    reserves.push_back(asset_config(env, None, PRICE_ONE));
+    reserves.push_back(asset_config(env, Some(token.clone()), 2 * PRICE_ONE));
+
+    let mut oracle_sources = Vec::new(env);
+    oracle_sources.push_back(OracleSourceConfig {
+        asset: token.clone(),
+        fallback_oracle: Some(Address::generate(env)),
+    });
+
+    let mut amm_settings = Vec::new(env);
+    amm_settings.push_back(AmmSettings {
+        default_slippage: 100,
+        max_slippage: 1_000,
+        swap_enabled: true,
+        liquidity_enabled: true,
+        auto_swap_threshold: 10_000,
+        pair_max_slippage: Map::new(env),
+        max_swap_history: 1000,
+        swap_history_retention: 0,
+        max_deadline_window: 3600,
+    });
+
+    let mut supported_pairs = Vec::new(env);
+    supported_pairs.push_back(TokenPair {
+        token_a: None,
+        token_b: Some(token),
+        pool_address: Address::generate(env),
+    });
+    let mut amm_protocols = Vec::new(env);
+    amm_protocols.push_back(AmmProtocolConfig {
+        protocol_address: Address::generate(env),
+        protocol_name: Symbol::new(env, "TestAMM"),
+        enabled: true,
+        fee_tier: 30,
+        min_swap_amount: 1_000,
+        max_swap_amount: 1_000_000_000,
+        supported_pairs,
+    });
+
+    let mut roles = Vec::new(env);
+    roles.push_back(RoleGrant {
+        role: Symbol::new(env, "keeper"),
+        account: Address::generate(env),
+    });
+
+    InitConfig {
+        admin: admin.clone(),
+        native_asset: Some(Address::generate(env)),
+        reserves,
+        oracle_config: OracleConfig {
+            max_deviation_bps: 1_000,
+            max_staleness_seconds: 600,
+            cache_ttl_seconds: 60,
+            min_price: 1,
+            max_price: 1_000_000 * PRICE_ONE,
+        },
+        oracle_sources,
+        amm_settings,
+        amm_protocols,
+        roles,
+    }
+}
+
+#[test]
+fn test_initialize_protocol() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let admin = Address::generate(&env);
+    let config = init_config(&env, &admin);
+
+    assert!(!client.is_protocol_initialized());
+    client.initialize_protocol(&config);
+    assert!(client.is_protocol_initialized());
+
+    let exported = client.export_config();
+    assert_eq!(exported.admin, Some(admin));
+    assert_eq!(exported.native_asset, config.native_asset);
+    assert_eq!(exported.reserves, config.reserves);
+    assert_eq!(exported.oracle_config, config.oracle_config);
+    assert_eq!(
+        exported.oracle_sources.get(1).unwrap(),
+        config.oracle_sources.get(0).unwrap()
+    );
+    assert_eq!(exported.amm_settings, config.amm_settings);
+    assert_eq!(exported.amm_protocols, config.amm_protocols);
+    assert_eq!(exported.roles, config.roles);
+    assert_eq!(exported.risk_params.close_factor, 5_000);
+}
+
+#[test]
+fn test_initialize_protocol_is_idempotent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let admin = Address::generate(&env);
+    let config = init_config(&env, &admin);
+
+    client.initialize_protocol(&config);
+    let result = client.try_initialize_protocol(&config);
+    assert_eq!(result, Err(Ok(ProtocolInitError::AlreadyInitialized)));
+
+    // A deployment brought up by the legacy initializer is rejected too
+    let legacy = setup(&env);
+    legacy.initialize(&admin);
+    let result = legacy.try_initialize_protocol(&config);
+    assert_eq!(result, Err(Ok(ProtocolInitError::AlreadyInitialized)));
+    assert!(!legacy.is_protocol_initialized());
+}
+
+#[test]
+fn test_initialize_protocol_reverts_on_invalid_section() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let admin = Address::generate(&env);
+    let config = init_config(&env, &admin);
+
+    let mut invalid = config.clone();
+    let mut reserve = invalid.reserves.get(1).unwrap();
+    reserve.price = 0;
+    invalid.reserves.set(1, reserve);
+    let result = client.try_initialize_protocol(&invalid);
+    assert_eq!(result, Err(Ok(ProtocolInitError::InvalidConfig)));
+
+    let mut invalid = config.clone();
+    invalid.oracle_config.max_staleness_seconds = 0;
+    let result = client.try_initialize_protocol(&invalid);
+    assert_eq!(result, Err(Ok(ProtocolInitError::InvalidConfig)));
+
+    // Nothing was left behind by the failed calls
+    assert!(!client.is_protocol_initialized());
+    assert!(client.try_export_config().is_err());
+    client.initialize_protocol(&config);
+    assert_eq!(client.export_config().reserves.len(), 2);
+}
+
+#[test]
+fn test_initialize_protocol_requires_admin_auth() {
+    let env = Env::default();
+    let client = setup(&env);
+    let admin = Address::generate(&env);
+
+    let result = client.try_initialize_protocol(&init_config(&env, &admin));
+    assert!(result.is_err());
+    assert!(!client.is_protocol_initialized());
+}
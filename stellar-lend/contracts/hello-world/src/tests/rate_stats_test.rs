@@ -0,0 +1,98 @@
+#![cfg(test)]
+
+use crate::interest_rate::to_rate_view;
+use crate::rate_stats::RateStatsError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn setup(env: &Env) -> (Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (admin, client)
+}
+
+#[test]
+fn test_set_rate_windows_validation() {
+    let env = Env::default();
+    let (admin, client) = setup(&env);
+
+    assert_eq!(
+        client.get_rate_windows(),
+        vec![&env, DAY, 7 * DAY, 30 * DAY]
+    );
+    client.set_rate_windows(&admin, &vec![&env, 3_600, DAY]);
+    assert_eq!(client.get_rate_windows(), vec![&env, 3_600, DAY]);
+
+    for windows in [
+        vec![&env],
+        vec![&env, 60],
+        vec![&env, DAY, DAY],
+        vec![&env, 91 * DAY],
+    ] {
+        assert_eq!(
+            client.try_set_rate_windows(&admin, &windows),
+            Err(Ok(RateStatsError::InvalidWindow))
+        );
+    }
+    assert_eq!(
+        client.try_set_rate_windows(&Address::generate(&env), &vec![&env, DAY]),
+        Err(Ok(RateStatsError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_rate_stats_are_time_weighted() {
+    let env = Env::default();
+    let (_admin, client) = setup(&env);
+    let user = Address::generate(&env);
+
+    assert_eq!(client.get_rate_stats(&None, &DAY), None);
+    client.deposit_collateral(&user, &None, &10_000);
+
+    // 20% utilization for half a day, then 40% for the other half
+    client.borrow_asset(&user, &None, &2_000);
+    let low_rate = client.get_borrow_rate(&None);
+    env.ledger().with_mut(|li| li.timestamp += DAY / 2);
+    client.borrow_asset(&user, &None, &2_000);
+    let high_rate = client.get_borrow_rate(&None);
+    env.ledger().with_mut(|li| li.timestamp += DAY / 2);
+
+    let stats = client.get_rate_stats(&None, &DAY).unwrap();
+    assert_eq!(stats.window, DAY);
+    assert_eq!(stats.observed_seconds, DAY);
+    assert_eq!(stats.avg_utilization, 3_000);
+    assert_eq!(
+        to_rate_view(stats.avg_borrow_rate),
+        (low_rate + high_rate) / 2
+    );
+    assert!(low_rate < high_rate);
+
+    // Longer windows are backed by the same single day of history so far
+    let all = client.get_all_rate_stats(&None);
+    assert_eq!(all.len(), 3);
+    assert_eq!(all.get(2).unwrap().window, 30 * DAY);
+    assert_eq!(all.get(2).unwrap().avg_utilization, 3_000);
+
+    // The 1d window forgets the first day once enough checkpoints exist
+    for _ in 0..24 {
+        env.ledger().with_mut(|li| li.timestamp += DAY / 24);
+        client.borrow_asset(&user, &None, &1);
+    }
+    let stats = client.get_rate_stats(&None, &DAY).unwrap();
+    assert!(stats.observed_seconds <= DAY);
+    assert!(stats.avg_utilization >= 4_000);
+
+    // Other reserves have no history
+    assert_eq!(
+        client.get_rate_stats(&Some(Address::generate(&env)), &DAY),
+        None
+    );
+}
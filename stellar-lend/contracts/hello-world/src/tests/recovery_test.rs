@@ -1,91 +1,159 @@
 #![cfg(test)]
 
-use crate::recovery::{set_guardians, get_guardians, get_guardian_threshold};
-use crate::governance::GovernanceError;
-use crate::HelloContract;
-use soroban_sdk::{testutils::Address as _, Address, Env, Vec};
+use crate::errors::GovernanceError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
 
-fn setup() -> (Env, Address, Address) {
-    let env = Env::default();
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address) {
     env.mock_all_auths();
     let contract_id = env.register(HelloContract, ());
-    let admin = Address::generate(&env);
-    env.as_contract(&contract_id, || {
-        crate::risk_management::initialize_risk_management(&env, admin.clone()).unwrap();
-        crate::governance::initialize_governance(&env, admin.clone()).unwrap();
-    });
-    (env, contract_id, admin)
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let vote_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+    );
+    (client, admin)
+}
+
+fn add_guardians(
+    env: &Env,
+    client: &HelloContractClient,
+    admin: &Address,
+    count: u32,
+) -> soroban_sdk::Vec<Address> {
+    let mut guardians = soroban_sdk::Vec::new(env);
+    for _ in 0..count {
+        let guardian = Address::generate(env);
+        client.gov_add_guardian(admin, &guardian);
+        guardians.push_back(guardian);
+    }
+    guardians
 }
 
 #[test]
-fn test_set_guardians_bulk_success() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let mut gs = Vec::new(&env);
-        gs.push_back(Address::generate(&env));
-        gs.push_back(Address::generate(&env));
-        gs.push_back(Address::generate(&env));
-        set_guardians(&env, admin.clone(), gs, 2).unwrap();
-        assert_eq!(get_guardians(&env).unwrap().len(), 3);
-        assert_eq!(get_guardian_threshold(&env), 2);
-    });
+fn test_add_guardians_and_threshold() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    add_guardians(&env, &client, &admin, 3);
+    client.gov_set_guardian_threshold(&admin, &2);
+
+    let config = client.gov_get_guardian_config().unwrap();
+    assert_eq!(config.guardians.len(), 3);
+    assert_eq!(config.threshold, 2);
 }
 
 #[test]
-fn test_set_guardians_replaces_old_set() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let mut gs1 = Vec::new(&env);
-        gs1.push_back(Address::generate(&env));
-        gs1.push_back(Address::generate(&env));
-        set_guardians(&env, admin.clone(), gs1, 1).unwrap();
-        let mut gs2 = Vec::new(&env);
-        gs2.push_back(Address::generate(&env));
-        set_guardians(&env, admin.clone(), gs2, 1).unwrap();
-        assert_eq!(get_guardians(&env).unwrap().len(), 1);
-    });
+fn test_remove_guardian_clamps_threshold() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let guardians = add_guardians(&env, &client, &admin, 2);
+    client.gov_set_guardian_threshold(&admin, &2);
+
+    client.gov_remove_guardian(&admin, &guardians.get(0).unwrap());
+    let config = client.gov_get_guardian_config().unwrap();
+    assert_eq!(config.guardians.len(), 1);
+    assert_eq!(config.threshold, 1);
+
+    let result = client.try_gov_remove_guardian(&admin, &guardians.get(0).unwrap());
+    assert_eq!(result, Err(Ok(GovernanceError::GuardianNotFound)));
 }
 
 #[test]
-fn test_set_guardians_empty_returns_error() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let result = set_guardians(&env, admin, Vec::new(&env), 1);
-        assert_eq!(result, Err(GovernanceError::InvalidGuardianConfig));
-    });
+fn test_duplicate_guardian_returns_error() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let guardians = add_guardians(&env, &client, &admin, 1);
+
+    let result = client.try_gov_add_guardian(&admin, &guardians.get(0).unwrap());
+    assert_eq!(result, Err(Ok(GovernanceError::GuardianAlreadyExists)));
 }
 
 #[test]
-fn test_set_guardians_duplicate_returns_error() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let g = Address::generate(&env);
-        let mut gs = Vec::new(&env);
-        gs.push_back(g.clone());
-        gs.push_back(g);
-        let result = set_guardians(&env, admin, gs, 1);
-        assert_eq!(result, Err(GovernanceError::InvalidGuardianConfig));
-    });
+fn test_guardian_threshold_validation() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    add_guardians(&env, &client, &admin, 1);
+
+    let result = client.try_gov_set_guardian_threshold(&admin, &0);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidGuardianConfig)));
+    let result = client.try_gov_set_guardian_threshold(&admin, &5);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidGuardianConfig)));
 }
 
 #[test]
-fn test_set_guardians_threshold_too_high_returns_error() {
-    let (env, cid, admin) = setup();
-    env.as_contract(&cid, || {
-        let mut gs = Vec::new(&env);
-        gs.push_back(Address::generate(&env));
-        let result = set_guardians(&env, admin, gs, 5);
-        assert_eq!(result, Err(GovernanceError::InvalidGuardianConfig));
-    });
+fn test_non_admin_cannot_manage_guardians() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let attacker = Address::generate(&env);
+
+    let result = client.try_gov_add_guardian(&attacker, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
+    let result = client.try_gov_set_guardian_threshold(&attacker, &1);
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
 }
 
 #[test]
-fn test_set_guardians_non_admin_returns_unauthorized() {
-    let (env, cid, _admin) = setup();
-    env.as_contract(&cid, || {
-        let mut gs = Vec::new(&env);
-        gs.push_back(Address::generate(&env));
-        let result = set_guardians(&env, Address::generate(&env), gs, 1);
-        assert_eq!(result, Err(GovernanceError::Unauthorized));
-    });
+fn test_guardian_recovery_replaces_admin() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let guardians = add_guardians(&env, &client, &admin, 3);
+    client.gov_set_guardian_threshold(&admin, &2);
+    let new_admin = Address::generate(&env);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_gov_start_recovery(&outsider, &admin, &new_admin);
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
+
+    let g1 = guardians.get(0).unwrap();
+    let g2 = guardians.get(1).unwrap();
+    client.gov_start_recovery(&g1, &admin, &new_admin);
+    let result = client.try_gov_start_recovery(&g2, &admin, &new_admin);
+    assert_eq!(result, Err(Ok(GovernanceError::RecoveryInProgress)));
+
+    // The initiator's approval alone is below the threshold
+    let result = client.try_gov_execute_recovery(&g1);
+    assert_eq!(result, Err(Ok(GovernanceError::InsufficientApprovals)));
+    let result = client.try_gov_approve_recovery(&g1);
+    assert_eq!(result, Err(Ok(GovernanceError::AlreadyVoted)));
+
+    client.gov_approve_recovery(&g2);
+    assert_eq!(client.gov_get_recovery_approvals().unwrap().len(), 2);
+    client.gov_execute_recovery(&g1);
+
+    let admins = client.gov_get_multisig_config().unwrap().admins;
+    assert_eq!(admins.len(), 1);
+    assert_eq!(admins.get(0).unwrap(), new_admin);
+    assert!(client.gov_get_recovery_request().is_none());
+}
+
+#[test]
+fn test_expired_recovery_cannot_execute() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let guardians = add_guardians(&env, &client, &admin, 1);
+    let guardian = guardians.get(0).unwrap();
+
+    let result = client.try_gov_approve_recovery(&guardian);
+    assert_eq!(result, Err(Ok(GovernanceError::NoRecoveryInProgress)));
+
+    client.gov_start_recovery(&guardian, &admin, &Address::generate(&env));
+    let expires_at = client.gov_get_recovery_request().unwrap().expires_at;
+    env.ledger().with_mut(|li| li.timestamp = expires_at + 1);
+
+    let result = client.try_gov_execute_recovery(&guardian);
+    assert_eq!(result, Err(Ok(GovernanceError::ProposalExpired)));
 }
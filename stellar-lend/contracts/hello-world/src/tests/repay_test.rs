@@ -151,7 +151,7 @@ fn test_repay_full_debt() {
 }
 
 #[test]
-#[should_panic(expected = "Repay error: NoDebt")]
+#[should_panic(expected = "Error(Contract, #16)")]
 fn test_repay_no_debt() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
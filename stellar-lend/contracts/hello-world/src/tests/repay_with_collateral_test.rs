@@ -0,0 +1,178 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::repay::{repay_with_collateral, RepayError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+struct DeleverageTest {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'static>,
+    usdc: Address,
+    xlm: Address,
+    borrower: Address,
+}
+
+/// Borrower with 1_000 XLM of collateral and `debt` USDC of debt, and a
+/// 50/50 USDC/XLM pool hosted by the protocol's AMM router
+fn setup(debt: i128) -> DeleverageTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    StellarAssetClient::new(&env, &usdc).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &xlm).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &xlm).mint(&contract_id, &1_000);
+    let borrower = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        stellarlend_amm::update_amm_settings(
+            &env,
+            admin.clone(),
+            stellarlend_amm::AmmSettings {
+                default_slippage: 100,
+                max_slippage: 1_000,
+                swap_enabled: true,
+                liquidity_enabled: true,
+                auto_swap_threshold: 10_000,
+                pair_max_slippage: soroban_sdk::Map::new(&env),
+                max_swap_history: 1000,
+                swap_history_retention: 0,
+                max_deadline_window: 3600,
+            },
+        )
+        .unwrap();
+        stellarlend_amm::lbp::create_lbp_pool(
+            &env,
+            admin.clone(),
+            usdc.clone(),
+            xlm.clone(),
+            10_000,
+            10_000,
+            stellarlend_amm::WeightSchedule {
+                start_time: 0,
+                end_time: 1_000,
+                start_weight_a: 5_000,
+                end_weight_a: 5_000,
+            },
+            0,
+        )
+        .unwrap();
+
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 1_000,
+                debt,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+
+    DeleverageTest {
+        env,
+        contract_id,
+        client,
+        usdc,
+        xlm,
+        borrower,
+    }
+}
+
+fn get_position(t: &DeleverageTest) -> Position {
+    t.env.as_contract(&t.contract_id, || {
+        t.env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(t.borrower.clone()))
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_repay_with_collateral_deleverages_position() {
+    let t = setup(500);
+
+    // 100 XLM sells for 10_000 * 100 / 10_100 = 99 USDC
+    let (remaining_debt, collateral_sold, debt_repaid) =
+        t.client
+            .repay_with_collateral(&t.borrower, &t.usdc, &t.xlm, &100);
+    assert_eq!(remaining_debt, 401);
+    assert_eq!(collateral_sold, 100);
+    assert_eq!(debt_repaid, 99);
+
+    let position = get_position(&t);
+    assert_eq!(position.debt, 401);
+    assert_eq!(position.collateral, 900);
+    // No outside capital was needed and nothing was paid out
+    assert_eq!(TokenClient::new(&t.env, &t.usdc).balance(&t.borrower), 0);
+}
+
+#[test]
+fn test_repay_with_collateral_pays_out_excess_proceeds() {
+    let t = setup(50);
+
+    let (remaining_debt, _, debt_repaid) =
+        t.client
+            .repay_with_collateral(&t.borrower, &t.usdc, &t.xlm, &100);
+    assert_eq!(remaining_debt, 0);
+    assert_eq!(debt_repaid, 50);
+    assert_eq!(TokenClient::new(&t.env, &t.usdc).balance(&t.borrower), 49);
+    assert_eq!(get_position(&t).collateral, 900);
+}
+
+#[test]
+fn test_repay_with_collateral_rejects_bad_requests() {
+    let t = setup(500);
+
+    // Same asset on both sides, and more collateral than the position holds
+    assert!(t
+        .client
+        .try_repay_with_collateral(&t.borrower, &t.usdc, &t.usdc, &100)
+        .is_err());
+    assert!(t
+        .client
+        .try_repay_with_collateral(&t.borrower, &t.usdc, &t.xlm, &1_001)
+        .is_err());
+    // 200 XLM only sells for 196 USDC, below the 1% oracle tolerance
+    assert!(t
+        .client
+        .try_repay_with_collateral(&t.borrower, &t.usdc, &t.xlm, &200)
+        .is_err());
+
+    // Nothing was repaid or sold
+    let position = get_position(&t);
+    assert_eq!(position.debt, 500);
+    assert_eq!(position.collateral, 1_000);
+
+    t.env.as_contract(&t.contract_id, || {
+        let result = repay_with_collateral(
+            &t.env,
+            t.borrower.clone(),
+            t.usdc.clone(),
+            t.xlm.clone(),
+            200,
+        );
+        assert_eq!(result, Err(RepayError::SlippageExceeded));
+    });
+}
@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use crate::reserve::{ReserveError, DEFAULT_RESERVE_FACTOR_BPS};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+fn setup(env: &Env) -> (Address, Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (contract_id, admin, token, client)
+}
+
+fn fund(env: &Env, contract_id: &Address, token: &Address, user: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(user, &amount);
+    TokenClient::new(env, token).approve(
+        user,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_reserve_factor_diverts_repaid_interest() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    let borrower = Address::generate(&env);
+
+    let config = client.get_reserve_config(&asset);
+    assert_eq!(config.reserve_factor_bps, DEFAULT_RESERVE_FACTOR_BPS);
+    assert_eq!(config.treasury, None);
+
+    client.set_reserve_factor(&admin, &asset, &2_000);
+    assert_eq!(client.get_reserve_config(&asset).reserve_factor_bps, 2_000);
+    assert_eq!(
+        client.try_set_reserve_factor(&admin, &asset, &5_001),
+        Err(Ok(ReserveError::InvalidReserveFactor))
+    );
+
+    fund(&env, &contract_id, &token, &borrower, 2_000_000);
+    client.deposit_collateral(&borrower, &asset, &1_000_000);
+    client.borrow_asset(&borrower, &asset, &100_000);
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+
+    // 20% of the interest paid goes to the protocol reserve
+    let reserves = client.get_protocol_reserves(&asset);
+    let interest = client.get_user_debt(&borrower) - 100_000;
+    assert!(interest > 0);
+    client.repay_debt(&borrower, &asset, &interest);
+    assert_eq!(
+        client.get_protocol_reserves(&asset),
+        reserves + interest * 2_000 / 10_000
+    );
+    assert_eq!(
+        client.get_reserve_balance(&asset),
+        client.get_protocol_reserves(&asset)
+    );
+}
+
+#[test]
+fn test_withdraw_reserves_to_treasury() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    let borrower = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    fund(&env, &contract_id, &token, &borrower, 2_000_000);
+    client.deposit_collateral(&borrower, &asset, &1_000_000);
+    client.borrow_asset(&borrower, &asset, &100_000);
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    client.repay_debt(&borrower, &asset, &i128::MAX);
+    let reserves = client.get_protocol_reserves(&asset);
+    assert!(reserves > 0);
+
+    assert_eq!(
+        client.try_withdraw_reserves(&admin, &asset, &reserves),
+        Err(Ok(ReserveError::TreasuryNotSet))
+    );
+    client.set_treasury_address(&admin, &treasury);
+    assert_eq!(
+        client.get_reserve_config(&asset).treasury,
+        Some(treasury.clone())
+    );
+
+    assert_eq!(
+        client.try_withdraw_reserves(&Address::generate(&env), &asset, &1),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_withdraw_reserves(&admin, &asset, &(reserves + 1)),
+        Err(Ok(ReserveError::InsufficientReserve))
+    );
+
+    // Only reserves leave the pool, and only to the treasury
+    assert_eq!(
+        client.withdraw_reserves(&admin, &asset, &reserves),
+        reserves
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&treasury), reserves);
+    assert_eq!(client.get_protocol_reserves(&asset), 0);
+    assert_eq!(
+        client.try_withdraw_reserves(&admin, &asset, &1),
+        Err(Ok(ReserveError::InsufficientReserve))
+    );
+}
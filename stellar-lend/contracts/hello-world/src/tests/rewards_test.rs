@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use crate::cross_asset::AssetConfig;
+use crate::rewards::{RewardsError, MAX_REWARD_TOKENS};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+/// Pool with a native reserve and a reward token; the admin holds 10_000
+/// reward tokens
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &None,
+        &AssetConfig {
+            asset: None,
+            collateral_factor: 7_500,
+            liquidation_threshold: 8_000,
+            reserve_factor: 1_000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+        },
+    );
+
+    let reward_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(env, &reward_token).mint(&admin, &10_000);
+    (client, admin, reward_token)
+}
+
+fn pending(client: &HelloContractClient, user: &Address, reward_token: &Address) -> i128 {
+    client
+        .get_pending_rewards(user)
+        .get(reward_token.clone())
+        .unwrap_or(0)
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_emissions_split_between_suppliers_and_borrowers() {
+    let env = Env::default();
+    let (client, admin, reward_token) = setup(&env);
+    let token = TokenClient::new(&env, &reward_token);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let assets = vec![&env, None];
+
+    client.cross_asset_deposit(&alice, &None, &300);
+    client.cross_asset_deposit(&bob, &None, &100);
+    client.cross_asset_borrow(&alice, &None, &50);
+
+    // 10 per second to suppliers and 5 to borrowers for 100 seconds
+    client.set_reward_emission(&admin, &None, &reward_token, &10, &5, &100);
+    assert_eq!(token.balance(&admin), 8_500);
+
+    advance(&env, 10);
+    assert_eq!(pending(&client, &alice, &reward_token), 75 + 50);
+    assert_eq!(pending(&client, &bob, &reward_token), 25);
+
+    let recipient = Address::generate(&env);
+    let claimed = client.claim_rewards(&alice, &assets, &recipient);
+    assert_eq!(claimed.get(reward_token.clone()), Some(125));
+    assert_eq!(token.balance(&recipient), 125);
+    assert_eq!(pending(&client, &alice, &reward_token), 0);
+
+    // Nothing is emitted after the schedule ends
+    advance(&env, 200);
+    assert_eq!(pending(&client, &alice, &reward_token), 675 + 450);
+    assert_eq!(pending(&client, &bob, &reward_token), 250);
+    client.claim_rewards(&bob, &assets, &bob);
+    client.claim_rewards(&alice, &assets, &alice);
+    assert_eq!(token.balance(&bob), 250);
+    assert_eq!(token.balance(&alice), 1_125);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_rewards_follow_balance_changes() {
+    let env = Env::default();
+    let (client, admin, reward_token) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.cross_asset_deposit(&alice, &None, &200);
+    client.set_reward_emission(&admin, &None, &reward_token, &10, &0, &100);
+
+    // Alice earns everything until Bob joins
+    advance(&env, 10);
+    client.cross_asset_deposit(&bob, &None, &50);
+    advance(&env, 10);
+    assert_eq!(pending(&client, &alice, &reward_token), 100 + 80);
+    assert_eq!(pending(&client, &bob, &reward_token), 20);
+
+    // Bob's earnings stop once he withdraws
+    client.cross_asset_withdraw(&bob, &None, &50);
+    advance(&env, 10);
+    assert_eq!(pending(&client, &alice, &reward_token), 280);
+    assert_eq!(pending(&client, &bob, &reward_token), 20);
+
+    // Cutting the schedule short refunds what it had left
+    client.set_reward_emission(&admin, &None, &reward_token, &0, &0, &0);
+    assert_eq!(TokenClient::new(&env, &reward_token).balance(&admin), 9_700);
+    advance(&env, 10);
+    assert_eq!(pending(&client, &alice, &reward_token), 280);
+}
+
+#[test]
+fn test_reserve_streams_several_reward_tokens() {
+    let env = Env::default();
+    let (client, admin, reward_token) = setup(&env);
+    let partner_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &partner_token).mint(&admin, &10_000);
+    let alice = Address::generate(&env);
+
+    client.cross_asset_deposit(&alice, &None, &100);
+    client.set_reward_emission(&admin, &None, &reward_token, &10, &0, &100);
+    client.set_reward_emission(&admin, &None, &partner_token, &3, &0, &20);
+    assert_eq!(
+        client.get_reward_tokens(&None),
+        vec![&env, reward_token.clone(), partner_token.clone()]
+    );
+
+    // Each token follows its own rate and end time
+    advance(&env, 50);
+    let rewards = client.get_pending_rewards(&alice);
+    assert_eq!(rewards.len(), 2);
+    assert_eq!(rewards.get(reward_token.clone()), Some(500));
+    assert_eq!(rewards.get(partner_token.clone()), Some(60));
+
+    let claimed = client.claim_rewards(&alice, &vec![&env, None], &alice);
+    assert_eq!(claimed, rewards);
+    assert_eq!(TokenClient::new(&env, &reward_token).balance(&alice), 500);
+    assert_eq!(TokenClient::new(&env, &partner_token).balance(&alice), 60);
+    assert_eq!(client.get_pending_rewards(&alice).len(), 0);
+}
+
+#[test]
+fn test_reward_configuration_errors() {
+    let env = Env::default();
+    let (client, admin, reward_token) = setup(&env);
+
+    assert_eq!(
+        client.try_set_reward_emission(
+            &Address::generate(&env),
+            &None,
+            &reward_token,
+            &10,
+            &0,
+            &100
+        ),
+        Err(Ok(RewardsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_reward_emission(&admin, &None, &reward_token, &-1, &0, &100),
+        Err(Ok(RewardsError::InvalidEmission))
+    );
+    assert_eq!(
+        client.try_set_reward_emission(&admin, &None, &client.address, &10, &0, &100),
+        Err(Ok(RewardsError::InvalidRewardToken))
+    );
+    let unknown = Some(Address::generate(&env));
+    assert_eq!(
+        client.try_set_reward_emission(&admin, &unknown, &reward_token, &10, &0, &100),
+        Err(Ok(RewardsError::AssetNotConfigured))
+    );
+    assert_eq!(client.get_reward_emission(&None, &reward_token), None);
+
+    for _ in 0..MAX_REWARD_TOKENS {
+        client.set_reward_emission(&admin, &None, &Address::generate(&env), &0, &0, &0);
+    }
+    assert_eq!(
+        client.try_set_reward_emission(&admin, &None, &reward_token, &10, &0, &100),
+        Err(Ok(RewardsError::TooManyRewardTokens))
+    );
+}
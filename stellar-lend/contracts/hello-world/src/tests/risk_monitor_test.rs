@@ -0,0 +1,320 @@
+#![cfg(test)]
+
+use crate::cross_asset::AssetConfig;
+use crate::deposit::{DepositDataKey, Position};
+use crate::risk_monitor::RiskMonitorError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Symbol,
+};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+struct RiskMonitorTest {
+    env: Env,
+    client: HelloContractClient<'static>,
+    admin: Address,
+    token: Address,
+    alice: Address,
+    bob: Address,
+    carol: Address,
+}
+
+fn asset_config(env: &Env, asset: Option<Address>, price: i128) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+/// Native reserve priced at 1.0 and a token reserve priced at 2.0
+///
+/// - alice: 1_000 native collateral, 300 token debt
+/// - bob: 500 token collateral, 100 native debt
+/// - carol: 3_000 native collateral, 100 token debt
+fn setup() -> RiskMonitorTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+
+    let token = Address::generate(&env);
+    client.initialize_asset(&None, &asset_config(&env, None, PRICE_ONE));
+    client.initialize_asset(
+        &Some(token.clone()),
+        &asset_config(&env, Some(token.clone()), 2 * PRICE_ONE),
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    client.cross_asset_deposit(&alice, &None, &1_000);
+    client.cross_asset_borrow(&alice, &Some(token.clone()), &300);
+    client.cross_asset_deposit(&bob, &Some(token.clone()), &500);
+    client.cross_asset_borrow(&bob, &None, &100);
+    client.cross_asset_deposit(&carol, &None, &3_000);
+    client.cross_asset_borrow(&carol, &Some(token.clone()), &100);
+
+    RiskMonitorTest {
+        env,
+        client,
+        admin,
+        token,
+        alice,
+        bob,
+        carol,
+    }
+}
+
+#[test]
+fn test_solvency_and_bad_debt() {
+    let t = setup();
+
+    let snapshot = t.client.get_solvency_snapshot();
+    assert_eq!(snapshot.total_collateral_value, 5_000);
+    assert_eq!(snapshot.total_debt_value, 900);
+    assert_eq!(snapshot.bad_debt_value, 0);
+    assert_eq!(t.client.get_solvency_ratio(), 55_555);
+
+    // Doubling the token price leaves alice owing 1_200 against 1_000
+    t.client
+        .update_asset_price(&Some(t.token.clone()), &(4 * PRICE_ONE));
+    let snapshot = t.client.get_solvency_snapshot();
+    assert_eq!(snapshot.total_collateral_value, 6_000);
+    assert_eq!(snapshot.total_debt_value, 1_700);
+    assert_eq!(t.client.get_aggregate_bad_debt(), 200);
+    assert_eq!(t.client.get_solvency_ratio(), 35_294);
+}
+
+#[test]
+fn test_solvency_without_debt() {
+    let env = Env::default();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+
+    let snapshot = client.get_solvency_snapshot();
+    assert_eq!(snapshot.total_debt_value, 0);
+    assert_eq!(snapshot.solvency_ratio_bps, i128::MAX);
+    assert_eq!(client.get_aggregate_bad_debt(), 0);
+}
+
+#[test]
+fn test_reserve_concentration() {
+    let t = setup();
+
+    let native = t.client.get_reserve_concentration(&None);
+    assert_eq!(native.total_supply, 4_000);
+    assert_eq!(native.largest_supplier, Some(t.carol.clone()));
+    assert_eq!(native.supply_concentration_bps, 7_500);
+    assert_eq!(native.largest_borrower, Some(t.bob.clone()));
+    assert_eq!(native.borrow_concentration_bps, 10_000);
+
+    let token = t.client.get_reserve_concentration(&Some(t.token.clone()));
+    assert_eq!(token.total_borrow, 400);
+    assert_eq!(token.largest_borrower, Some(t.alice.clone()));
+    assert_eq!(token.largest_borrow, 300);
+    assert_eq!(token.borrow_concentration_bps, 7_500);
+}
+
+#[test]
+fn test_oracle_staleness_and_report() {
+    let t = setup();
+    t.client
+        .update_price_feed(&t.admin, &t.token, &(2 * PRICE_ONE), &7, &t.admin);
+
+    let staleness = t.client.get_oracle_staleness(&t.token);
+    assert!(staleness.has_feed);
+    assert!(!staleness.is_stale);
+    assert_eq!(staleness.max_staleness_seconds, 3_600);
+
+    let unknown = t.client.get_oracle_staleness(&Address::generate(&t.env));
+    assert!(!unknown.has_feed);
+    assert!(unknown.is_stale);
+
+    t.env.ledger().with_mut(|li| li.timestamp += 3_601);
+    let staleness = t.client.get_oracle_staleness(&t.token);
+    assert_eq!(staleness.age_seconds, 3_601);
+    assert!(staleness.is_stale);
+
+    // The native reserve has no oracle entry until its asset address is set
+    let report = t.client.get_risk_monitor_report();
+    assert_eq!(report.reserves.len(), 2);
+    assert_eq!(report.oracles.len(), 1);
+    assert_eq!(report.solvency, t.client.get_solvency_snapshot());
+
+    let native_asset = Address::generate(&t.env);
+    t.client.set_native_asset_address(&t.admin, &native_asset);
+    let report = t.client.get_risk_monitor_report();
+    assert_eq!(report.oracles.len(), 2);
+    assert_eq!(report.oracles.get(0).unwrap().asset, native_asset);
+    assert!(!report.oracles.get(0).unwrap().has_feed);
+}
+
+#[test]
+fn test_heartbeat_digest() {
+    let t = setup();
+    t.env.ledger().with_mut(|li| li.timestamp = 1_000);
+    t.client
+        .update_price_feed(&t.admin, &t.token, &(2 * PRICE_ONE), &7, &t.admin);
+
+    // At 3.0 alice owes 900 against 1_000, a 1.11x health factor
+    t.client
+        .update_asset_price(&Some(t.token.clone()), &(3 * PRICE_ONE));
+    t.client.set_emergency_pause(&t.admin, &true);
+    t.client
+        .set_pause_switch(&t.admin, &Symbol::new(&t.env, "pause_borrow"), &true);
+
+    let digest = t.client.heartbeat();
+    assert_eq!(digest.total_supplied_value, 5_500);
+    assert_eq!(digest.total_borrowed_value, 1_300);
+    assert_eq!(digest.critical_accounts, 0);
+    assert_eq!(digest.high_risk_accounts, 0);
+    assert_eq!(digest.elevated_risk_accounts, 1);
+    assert!(digest.emergency_paused);
+    assert_eq!(digest.paused_operations.len(), 1);
+    assert_eq!(
+        digest.paused_operations.get(0).unwrap(),
+        Symbol::new(&t.env, "pause_borrow")
+    );
+    assert_eq!(digest.oldest_oracle_update, 1_000);
+    assert_eq!(digest.newest_oracle_update, 1_000);
+    assert_eq!(digest.stale_oracle_feeds, 0);
+}
+
+#[test]
+fn test_heartbeat_rate_limit() {
+    let t = setup();
+    let start = t.env.ledger().sequence();
+    let digest = t.client.heartbeat();
+    assert_eq!(digest.ledger, start);
+    assert_eq!(t.client.get_heartbeat_status(), (720, Some(start)));
+
+    let result = t.client.try_heartbeat();
+    assert_eq!(result, Err(Ok(RiskMonitorError::HeartbeatTooSoon)));
+
+    t.env
+        .ledger()
+        .with_mut(|li| li.sequence_number = start + 719);
+    let result = t.client.try_heartbeat();
+    assert_eq!(result, Err(Ok(RiskMonitorError::HeartbeatTooSoon)));
+
+    t.env
+        .ledger()
+        .with_mut(|li| li.sequence_number = start + 720);
+    t.client.heartbeat();
+
+    let attacker = Address::generate(&t.env);
+    let result = t.client.try_set_heartbeat_interval(&attacker, &10);
+    assert_eq!(result, Err(Ok(RiskMonitorError::Unauthorized)));
+    let result = t.client.try_set_heartbeat_interval(&t.admin, &0);
+    assert_eq!(result, Err(Ok(RiskMonitorError::InvalidInterval)));
+
+    t.client.set_heartbeat_interval(&t.admin, &10);
+    t.env
+        .ledger()
+        .with_mut(|li| li.sequence_number = start + 730);
+    assert_eq!(t.client.heartbeat().ledger, start + 730);
+}
+
+#[test]
+fn test_risk_snapshot_buckets_and_reserves() {
+    let t = setup();
+    let dave = Address::generate(&t.env);
+    t.client.cross_asset_deposit(&dave, &None, &500);
+
+    // A capped third reserve, a quarter full
+    let capped = Address::generate(&t.env);
+    let mut config = asset_config(&t.env, Some(capped.clone()), PRICE_ONE);
+    config.max_supply = 1_000;
+    config.max_borrow = 500;
+    t.client.initialize_asset(&Some(capped.clone()), &config);
+    t.client
+        .cross_asset_deposit(&dave, &Some(capped.clone()), &250);
+
+    // At 3.0 alice owes 900 against 1_000, a 1.11x health factor
+    t.client
+        .update_asset_price(&Some(t.token.clone()), &(3 * PRICE_ONE));
+
+    let snapshot = t.client.get_risk_snapshot(&0, &0);
+    assert_eq!(snapshot.health_factors.no_debt_accounts, 1);
+    assert_eq!(snapshot.health_factors.low_risk_accounts, 2);
+    assert_eq!(snapshot.health_factors.elevated_risk_accounts, 1);
+    assert_eq!(snapshot.health_factors.critical_accounts, 0);
+    assert_eq!(snapshot.reserve_count, 3);
+    assert_eq!(snapshot.reserves.len(), 3);
+    assert_eq!(snapshot.next_start, None);
+
+    let native = snapshot.reserves.get(0).unwrap();
+    assert_eq!(native.total_supply, 4_500);
+    assert_eq!(native.utilization_bps, 222);
+    assert_eq!(native.supply_cap_usage_bps, 0);
+    let token = snapshot.reserves.get(1).unwrap();
+    assert_eq!(token.utilization_bps, 8_000);
+
+    let page = t.client.get_risk_snapshot(&1, &1);
+    assert_eq!(page.reserves.len(), 1);
+    assert_eq!(page.reserves.get(0).unwrap().asset, Some(t.token.clone()));
+    assert_eq!(page.next_start, Some(2));
+
+    let page = t.client.get_risk_snapshot(&2, &5);
+    let capped_entry = page.reserves.get(0).unwrap();
+    assert_eq!(capped_entry.asset, Some(capped));
+    assert_eq!(capped_entry.supply_cap_usage_bps, 2_500);
+    assert_eq!(capped_entry.borrow_cap_usage_bps, 0);
+    assert_eq!(page.next_start, None);
+
+    let page = t.client.get_risk_snapshot(&7, &5);
+    assert_eq!(page.reserves.len(), 0);
+}
+
+#[test]
+fn test_risk_snapshot_liquidation_window() {
+    let t = setup();
+    t.env.ledger().with_mut(|li| li.timestamp = 10_000);
+    let borrower = Address::generate(&t.env);
+    let liquidator = Address::generate(&t.env);
+    t.env.as_contract(&t.client.address, || {
+        t.env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &1_000i128,
+        );
+        let position = Position {
+            collateral: 1_000,
+            debt: 1_000,
+            borrow_interest: 0,
+            last_accrual_time: 10_000,
+        };
+        t.env
+            .storage()
+            .persistent()
+            .set(&DepositDataKey::Position(borrower.clone()), &position);
+    });
+
+    t.client
+        .liquidate(&liquidator, &borrower, &None, &None, &300);
+    t.client
+        .liquidate(&liquidator, &borrower, &None, &None, &200);
+
+    let snapshot = t.client.get_risk_snapshot(&0, &0);
+    assert_eq!(snapshot.liquidation_window_seconds, 86_400);
+    assert_eq!(snapshot.liquidation_count, 2);
+    assert_eq!(snapshot.liquidation_volume, 500);
+
+    t.env.ledger().with_mut(|li| li.timestamp = 10_000 + 86_401);
+    let snapshot = t.client.get_risk_snapshot(&0, &0);
+    assert_eq!(snapshot.liquidation_count, 0);
+    assert_eq!(snapshot.liquidation_volume, 0);
+}
@@ -1,17 +1,56 @@
-#![cfg(test)]
+//! # Risk Management Parameters Test Suite
+//!
+//! Comprehensive tests for risk parameter configuration and enforcement (#290).
+//!
+//! ## Test scenarios
+//!
+//! - **Set/Get params**: Initialize, set risk params (full and partial), verify get_risk_config and individual getters.
+//! - **Bounds**: Min/max for min_collateral_ratio, liquidation_threshold, close_factor, liquidation_incentive.
+//! - **Validation**: min_cr >= liquidation_threshold, 10% max change per update, InvalidParameter / ParameterChangeTooLarge.
+//! - **Enforcement**: require_min_collateral_ratio, can_be_liquidated, get_max_liquidatable_amount, get_liquidation_incentive_amount.
+//! - **Admin-only**: set_risk_params, set_pause_switch, set_emergency_pause reject non-admin (Unauthorized).
+//! - **Edge values**: Boundary values (exactly at min/max), zero debt, partial updates.
+//! - **Pause**: Operation pause switches and emergency pause; emergency pause blocks set_risk_params.
+//!
+//! ## Security assumptions validated
+//!
+//! - Only admin can change risk params and pause state.
+//! - Parameter changes are capped at ±10% per update.
+//! - Min collateral ratio must be >= liquidation threshold.
+//! - Close factor in [0, 100%], liquidation incentive in [0, 50%].
 
-use crate::{HelloContract, HelloContractClient};
-use soroban_sdk::{testutils::{Address as _}, Address, Env};
 use crate::risk_management::RiskManagementError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
 
-fn setup_test() -> (Env, HelloContractClient<'static>, Address) {
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Creates a test environment with all auths mocked.
+fn create_test_env() -> Env {
     let env = Env::default();
-    let contract_id = env.register_contract(None, HelloContract);
+    env.mock_all_auths();
+    env
+}
+
+/// Sets up contract and admin, initializes. Pass env from create_test_env() so client outlives env.
+fn setup(env: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (contract_id, admin, client)
+}
+
+fn setup_test() -> (Env, HelloContractClient<'static>, Address) {
+    let env = create_test_env();
+    let contract_id = env.register(HelloContract, ());
     let client = HelloContractClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    
+
     client.initialize(&admin);
-    
+
     (env, client, admin)
 }
 
@@ -93,48 +132,6 @@ fn test_get_liquidation_incentive_amount() {
     let liquidated_amount = 500_000;
     // default incentive is 1_000 (10%)
     assert_eq!(client.get_liquidation_incentive_amount(&liquidated_amount), 50_000);
-//! # Risk Management Parameters Test Suite
-//!
-//! Comprehensive tests for risk parameter configuration and enforcement (#290).
-//!
-//! ## Test scenarios
-//!
-//! - **Set/Get params**: Initialize, set risk params (full and partial), verify get_risk_config and individual getters.
-//! - **Bounds**: Min/max for min_collateral_ratio, liquidation_threshold, close_factor, liquidation_incentive.
-//! - **Validation**: min_cr >= liquidation_threshold, 10% max change per update, InvalidParameter / ParameterChangeTooLarge.
-//! - **Enforcement**: require_min_collateral_ratio, can_be_liquidated, get_max_liquidatable_amount, get_liquidation_incentive_amount.
-//! - **Admin-only**: set_risk_params, set_pause_switch, set_emergency_pause reject non-admin (Unauthorized).
-//! - **Edge values**: Boundary values (exactly at min/max), zero debt, partial updates.
-//! - **Pause**: Operation pause switches and emergency pause; emergency pause blocks set_risk_params.
-//!
-//! ## Security assumptions validated
-//!
-//! - Only admin can change risk params and pause state.
-//! - Parameter changes are capped at ±10% per update.
-//! - Min collateral ratio must be >= liquidation threshold.
-//! - Close factor in [0, 100%], liquidation incentive in [0, 50%].
-
-use crate::{HelloContract, HelloContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
-
-// =============================================================================
-// HELPERS
-// =============================================================================
-
-/// Creates a test environment with all auths mocked.
-fn create_test_env() -> Env {
-    let env = Env::default();
-    env.mock_all_auths();
-    env
-}
-
-/// Sets up contract and admin, initializes. Pass env from create_test_env() so client outlives env.
-fn setup(env: &Env) -> (Address, Address, HelloContractClient<'_>) {
-    let contract_id = env.register(HelloContract, ());
-    let client = HelloContractClient::new(env, &contract_id);
-    let admin = Address::generate(env);
-    client.initialize(&admin);
-    (contract_id, admin, client)
 }
 
 // =============================================================================
@@ -0,0 +1,124 @@
+#![cfg(test)]
+
+use crate::admin::{GUARDIAN_ROLE, RISK_MANAGER_ROLE};
+use crate::reserve::ReserveError;
+use crate::risk_management::RiskManagementError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, Symbol, Vec};
+use stellarlend_amm::{AmmError, AmmProtocolConfig};
+
+/// Pool with a guardian and a risk manager besides the admin
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address, Address, Address) {
+    env.mock_all_auths();
+    let client = HelloContractClient::new(env, &env.register(HelloContract, ()));
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let guardian = Address::generate(env);
+    let risk_manager = Address::generate(env);
+    client.grant_role(&admin, &Symbol::new(env, GUARDIAN_ROLE), &guardian);
+    client.grant_role(&admin, &Symbol::new(env, RISK_MANAGER_ROLE), &risk_manager);
+    (client, admin, guardian, risk_manager)
+}
+
+#[test]
+fn test_guardian_can_pause_but_not_unpause() {
+    let env = Env::default();
+    let (client, admin, guardian, risk_manager) = setup(&env);
+    let deposit_op = Symbol::new(&env, "pause_deposit");
+
+    client.set_pause_switch(&guardian, &deposit_op, &true);
+    assert!(client.is_operation_paused(&deposit_op));
+    assert_eq!(
+        client.try_set_pause_switch(&guardian, &deposit_op, &false),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_pause_switch(&risk_manager, &deposit_op, &true),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    client.set_pause_switch(&admin, &deposit_op, &false);
+    assert!(!client.is_operation_paused(&deposit_op));
+
+    // A batch that reopens anything needs the admin
+    let mut switches = Map::new(&env);
+    switches.set(Symbol::new(&env, "pause_borrow"), true);
+    switches.set(deposit_op.clone(), false);
+    assert_eq!(
+        client.try_set_pause_switches(&guardian, &switches),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+
+    client.set_emergency_pause(&guardian, &true);
+    assert!(client.is_emergency_paused());
+    assert_eq!(
+        client.try_set_emergency_pause(&guardian, &false),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    client.set_emergency_pause(&admin, &false);
+    assert!(!client.is_emergency_paused());
+}
+
+#[test]
+fn test_risk_manager_tunes_parameters_within_bounds() {
+    let env = Env::default();
+    let (client, admin, guardian, risk_manager) = setup(&env);
+    let asset = Some(Address::generate(&env));
+
+    client.set_reserve_factor(&risk_manager, &asset, &2_000);
+    client.set_supply_cap(&risk_manager, &asset, &1_000_000);
+    assert_eq!(client.get_reserve_config(&asset).reserve_factor_bps, 2_000);
+    assert_eq!(client.get_reserve_config(&asset).supply_cap, 1_000_000);
+
+    // The same bounds hold as for the admin
+    assert_eq!(
+        client.try_set_reserve_factor(&risk_manager, &asset, &5_001),
+        Err(Ok(ReserveError::InvalidReserveFactor))
+    );
+    assert_eq!(
+        client.try_set_reserve_factor(&guardian, &asset, &1_000),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+
+    // Treasury and role management stay with the admin
+    assert_eq!(
+        client.try_set_treasury_address(&risk_manager, &risk_manager),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+    assert!(client
+        .try_grant_role(
+            &risk_manager,
+            &Symbol::new(&env, GUARDIAN_ROLE),
+            &risk_manager
+        )
+        .is_err());
+
+    // Revoking the role removes the powers
+    client.revoke_role(&admin, &Symbol::new(&env, RISK_MANAGER_ROLE), &risk_manager);
+    assert_eq!(
+        client.try_set_reserve_factor(&risk_manager, &asset, &1_000),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_risk_manager_configures_amm_pools() {
+    let env = Env::default();
+    let (client, _admin, guardian, risk_manager) = setup(&env);
+    let protocol = Address::generate(&env);
+    let config = AmmProtocolConfig {
+        protocol_address: protocol.clone(),
+        protocol_name: Symbol::new(&env, "TestAMM"),
+        enabled: true,
+        fee_tier: 30,
+        min_swap_amount: 1000,
+        max_swap_amount: 1_000_000_000,
+        supported_pairs: Vec::new(&env),
+    };
+
+    client.set_amm_pool(&risk_manager, &config);
+    assert_eq!(
+        client.try_set_amm_pool(&guardian, &config),
+        Err(Ok(AmmError::Unauthorized))
+    );
+}
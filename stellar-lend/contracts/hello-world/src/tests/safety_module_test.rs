@@ -0,0 +1,227 @@
+#![cfg(test)]
+
+use crate::bad_debt::BadDebtDataKey;
+use crate::deposit::DepositDataKey;
+use crate::safety_module::{SafetyModuleConfig, SafetyModuleError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Pool with governance over `gov_token` and a treasury; Alice and Bob each
+/// hold 1_000 `gov_token`
+fn setup(
+    env: &Env,
+) -> (
+    HelloContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let gov_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.gov_initialize(&admin, &gov_token, &None, &None, &None, &None, &None, &None);
+    let treasury = Address::generate(env);
+    client.set_treasury_address(&admin, &treasury);
+
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+    StellarAssetClient::new(env, &gov_token).mint(&alice, &1_000);
+    StellarAssetClient::new(env, &gov_token).mint(&bob, &1_000);
+    (client, admin, gov_token, treasury, alice, bob)
+}
+
+fn set_bad_debt(env: &Env, client: &HelloContractClient, amount: i128) {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&BadDebtDataKey::BadDebt(None), &amount);
+    });
+}
+
+fn set_reserve(env: &Env, client: &HelloContractClient, asset: &Option<Address>, amount: i128) {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::ProtocolReserve(asset.clone()), &amount);
+    });
+}
+
+#[test]
+fn test_stakers_earn_yield_and_withdraw_after_cooldown() {
+    let env = Env::default();
+    let (client, admin, gov_token, _treasury, alice, bob) = setup(&env);
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let asset = Some(usdc.clone());
+    StellarAssetClient::new(&env, &usdc).mint(&client.address, &1_000);
+    set_reserve(&env, &client, &asset, 1_000);
+
+    assert_eq!(client.stake_safety(&alice, &600), 600);
+    assert_eq!(client.stake_safety(&bob, &200), 200);
+    assert_eq!(client.get_safety_fund(), 800);
+
+    // Yield is split per share and only moves out of the reserve
+    client.pay_safety_yield(&admin, &asset, &400);
+    assert_eq!(client.get_reserve_balance(&asset), 600);
+    assert_eq!(client.get_safety_yield(&alice, &asset), 300);
+    assert_eq!(client.get_safety_yield(&bob, &asset), 100);
+    assert_eq!(client.claim_safety_yield(&alice, &asset), 300);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&alice), 300);
+    assert_eq!(client.get_safety_yield(&alice, &asset), 0);
+    assert_eq!(
+        client.try_pay_safety_yield(&admin, &asset, &601),
+        Err(Ok(SafetyModuleError::InsufficientReserve))
+    );
+
+    // Withdrawing needs an elapsed cooldown, within the unstake window
+    assert_eq!(
+        client.try_unstake_safety(&bob, &200),
+        Err(Ok(SafetyModuleError::CooldownNotStarted))
+    );
+    client.start_safety_cooldown(&bob);
+    assert_eq!(
+        client.try_unstake_safety(&bob, &200),
+        Err(Ok(SafetyModuleError::CooldownActive))
+    );
+    env.ledger().with_mut(|li| li.timestamp += 13 * DAY);
+    assert_eq!(
+        client.try_unstake_safety(&bob, &200),
+        Err(Ok(SafetyModuleError::CooldownExpired))
+    );
+    client.start_safety_cooldown(&bob);
+    env.ledger().with_mut(|li| li.timestamp += 10 * DAY);
+    assert_eq!(client.unstake_safety(&bob, &200), 200);
+    assert_eq!(TokenClient::new(&env, &gov_token).balance(&bob), 1_000);
+    assert_eq!(client.get_safety_cooldown(&bob), None);
+
+    // Yield earned before leaving can still be claimed
+    assert_eq!(client.claim_safety_yield(&bob, &asset), 100);
+    assert_eq!(client.get_safety_fund(), 600);
+}
+
+#[test]
+fn test_admin_slash_is_capped_and_covers_deficit() {
+    let env = Env::default();
+    let (client, admin, gov_token, treasury, alice, bob) = setup(&env);
+    client.stake_safety(&alice, &750);
+    client.stake_safety(&bob, &250);
+    set_bad_debt(&env, &client, 500);
+
+    // At most 30% of the fund per slash by default
+    assert_eq!(client.get_safety_slash_cap(), 300);
+    assert_eq!(
+        client.try_slash_safety_module(&admin, &None, &301, &100),
+        Err(Ok(SafetyModuleError::ExceedsSlashCap))
+    );
+    assert_eq!(
+        client.try_slash_safety_module(&admin, &None, &100, &501),
+        Err(Ok(SafetyModuleError::ExceedsBadDebt))
+    );
+    assert_eq!(
+        client.try_slash_safety_module(&Address::generate(&env), &None, &100, &100),
+        Err(Ok(SafetyModuleError::Unauthorized))
+    );
+
+    assert_eq!(client.slash_safety_module(&admin, &None, &200, &400), 100);
+    assert_eq!(client.get_bad_debt(&None), 100);
+    assert_eq!(TokenClient::new(&env, &gov_token).balance(&treasury), 200);
+
+    // Stakers share the loss in proportion to their shares
+    assert_eq!(client.get_safety_fund(), 800);
+    assert_eq!(client.get_safety_balance(&alice), 600);
+    assert_eq!(client.get_safety_balance(&bob), 200);
+
+    // New stakers buy in at the slashed share price
+    let carol = Address::generate(&env);
+    StellarAssetClient::new(&env, &gov_token).mint(&carol, &400);
+    assert_eq!(client.stake_safety(&carol, &400), 500);
+    assert_eq!(client.get_safety_balance(&carol), 400);
+}
+
+#[test]
+fn test_auto_slash_prices_deficit_with_oracle() {
+    let env = Env::default();
+    let (client, admin, gov_token, treasury, alice, _bob) = setup(&env);
+    client.stake_safety(&alice, &1_000);
+    set_bad_debt(&env, &client, 300);
+
+    assert_eq!(
+        client.try_trigger_safety_slash(&None),
+        Err(Ok(SafetyModuleError::AutoSlashDisabled))
+    );
+    client.set_safety_module_config(
+        &admin,
+        &SafetyModuleConfig {
+            max_slash_bps: 3_000,
+            cooldown: 10 * DAY,
+            unstake_window: 2 * DAY,
+            auto_slash: true,
+        },
+    );
+    assert_eq!(
+        client.try_trigger_safety_slash(&None),
+        Err(Ok(SafetyModuleError::PriceNotAvailable))
+    );
+
+    // One protocol token is worth two units of the deficit
+    client.update_price_feed(&admin, &gov_token, &200, &2, &admin);
+    assert_eq!(client.trigger_safety_slash(&None), 0);
+    assert_eq!(TokenClient::new(&env, &gov_token).balance(&treasury), 150);
+    assert_eq!(client.get_safety_fund(), 850);
+
+    // A deficit larger than the cap is only covered in part
+    set_bad_debt(&env, &client, 1_000);
+    assert_eq!(client.trigger_safety_slash(&None), 490);
+    assert_eq!(client.get_safety_fund(), 595);
+    assert_eq!(
+        client.try_trigger_safety_slash(&Some(Address::generate(&env))),
+        Err(Ok(SafetyModuleError::NoDeficit))
+    );
+}
+
+#[test]
+fn test_safety_module_config_validation() {
+    let env = Env::default();
+    let (client, admin, _gov_token, _treasury, _alice, _bob) = setup(&env);
+    let config = client.get_safety_module_config();
+
+    assert_eq!(
+        client.try_set_safety_module_config(
+            &admin,
+            &SafetyModuleConfig {
+                max_slash_bps: 5_001,
+                ..config.clone()
+            }
+        ),
+        Err(Ok(SafetyModuleError::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_set_safety_module_config(&Address::generate(&env), &config),
+        Err(Ok(SafetyModuleError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_pay_safety_yield(&admin, &None, &10),
+        Err(Ok(SafetyModuleError::NothingStaked))
+    );
+
+    let other = HelloContractClient::new(&env, &env.register(HelloContract, ()));
+    assert_eq!(
+        other.try_stake_safety(&Address::generate(&env), &100),
+        Err(Ok(SafetyModuleError::GovernanceNotInitialized))
+    );
+}
@@ -0,0 +1,243 @@
+#![cfg(test)]
+
+use crate::cdp::CdpCollateralType;
+use crate::savings::SavingsError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const PRICE_ONE: i128 = 10_000_000;
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+struct SavingsTest {
+    env: Env,
+    client: HelloContractClient<'static>,
+    admin: Address,
+    stablecoin: Address,
+}
+
+fn setup() -> SavingsTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let stablecoin = env
+        .register_stellar_asset_contract_v2(contract_id.clone())
+        .address();
+    client.configure_savings_market(&admin, &stablecoin, &500, &true);
+
+    SavingsTest {
+        env,
+        client,
+        admin,
+        stablecoin,
+    }
+}
+
+fn funded_user(t: &SavingsTest, amount: i128) -> Address {
+    let user = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.stablecoin).mint(&user, &amount);
+    user
+}
+
+fn advance_one_year(t: &SavingsTest) {
+    t.env
+        .ledger()
+        .with_mut(|li| li.timestamp += SECONDS_PER_YEAR);
+}
+
+#[test]
+fn test_configure_savings_market_validation() {
+    let t = setup();
+    let market = t.client.get_savings_market(&t.stablecoin).unwrap();
+    assert_eq!(market.rate_bps, 500);
+    assert!(market.enabled);
+
+    let result = t
+        .client
+        .try_configure_savings_market(&t.admin, &t.stablecoin, &2_001, &true);
+    assert_eq!(result, Err(Ok(SavingsError::InvalidRate)));
+
+    let attacker = Address::generate(&t.env);
+    let result = t
+        .client
+        .try_configure_savings_market(&attacker, &t.stablecoin, &100, &true);
+    assert_eq!(result, Err(Ok(SavingsError::Unauthorized)));
+
+    let unknown = Address::generate(&t.env);
+    let user = funded_user(&t, 1_000);
+    let result = t.client.try_savings_deposit(&user, &unknown, &100);
+    assert_eq!(result, Err(Ok(SavingsError::MarketNotFound)));
+}
+
+#[test]
+fn test_savings_accrue_and_withdraw_instantly() {
+    let t = setup();
+    let user = funded_user(&t, 10_000);
+    let funder = funded_user(&t, 1_000);
+    t.client.fund_savings(&funder, &t.stablecoin, &1_000);
+
+    assert_eq!(
+        t.client.savings_deposit(&user, &t.stablecoin, &10_000),
+        10_000
+    );
+
+    // 5% over one year
+    advance_one_year(&t);
+    assert_eq!(t.client.get_savings_balance(&t.stablecoin, &user), 10_500);
+
+    // Burned shares round up in the protocol's favour
+    assert_eq!(t.client.savings_withdraw(&user, &t.stablecoin, &500), 9_999);
+    assert_eq!(t.client.savings_withdraw(&user, &t.stablecoin, &9_999), 0);
+    assert_eq!(
+        TokenClient::new(&t.env, &t.stablecoin).balance(&user),
+        10_499
+    );
+
+    let market = t.client.get_savings_market(&t.stablecoin).unwrap();
+    assert_eq!(market.funding, 500);
+    assert_eq!(market.total_shares, 0);
+
+    let result = t.client.try_savings_withdraw(&user, &t.stablecoin, &1);
+    assert_eq!(result, Err(Ok(SavingsError::InsufficientBalance)));
+}
+
+#[test]
+fn test_interest_limited_by_funding() {
+    let t = setup();
+    let user = funded_user(&t, 10_000);
+    let funder = funded_user(&t, 100);
+    t.client.fund_savings(&funder, &t.stablecoin, &100);
+    t.client.savings_deposit(&user, &t.stablecoin, &10_000);
+
+    advance_one_year(&t);
+    assert_eq!(t.client.get_savings_balance(&t.stablecoin, &user), 10_100);
+
+    // Nothing further accrues once the pool is drained
+    advance_one_year(&t);
+    assert_eq!(t.client.get_savings_balance(&t.stablecoin, &user), 10_100);
+    assert_eq!(
+        t.client.get_savings_market(&t.stablecoin).unwrap().funding,
+        0
+    );
+}
+
+#[test]
+fn test_rate_change_applies_going_forward() {
+    let t = setup();
+    let user = funded_user(&t, 10_000);
+    let funder = funded_user(&t, 10_000);
+    t.client.fund_savings(&funder, &t.stablecoin, &10_000);
+    t.client.savings_deposit(&user, &t.stablecoin, &10_000);
+
+    advance_one_year(&t);
+    t.client
+        .configure_savings_market(&t.admin, &t.stablecoin, &1_000, &false);
+
+    // 500 at 5%, then 10% on 10_500
+    advance_one_year(&t);
+    assert_eq!(t.client.get_savings_balance(&t.stablecoin, &user), 11_550);
+
+    // Closed markets reject deposits but still allow withdrawals
+    let result = t.client.try_savings_deposit(&funder, &t.stablecoin, &1);
+    assert_eq!(result, Err(Ok(SavingsError::MarketDisabled)));
+    assert_eq!(t.client.savings_withdraw(&user, &t.stablecoin, &11_550), 0);
+}
+
+#[test]
+fn test_harvest_stability_fees() {
+    let t = setup();
+    let result = t.client.try_harvest_stability_fees();
+    assert_eq!(result, Err(Ok(SavingsError::StablecoinNotConfigured)));
+
+    let collateral = t
+        .env
+        .register_stellar_asset_contract_v2(t.admin.clone())
+        .address();
+    t.client
+        .update_price_feed(&t.admin, &t.stablecoin, &PRICE_ONE, &7, &t.admin);
+    t.client
+        .update_price_feed(&t.admin, &collateral, &(2 * PRICE_ONE), &7, &t.admin);
+    t.client.initialize_cdp(&t.admin, &t.stablecoin, &1_000_000);
+    t.client.set_cdp_collateral_type(
+        &t.admin,
+        &collateral,
+        &CdpCollateralType {
+            min_collateral_ratio_bps: 15_000,
+            liquidation_ratio_bps: 13_000,
+            liquidation_penalty_bps: 1_000,
+            stability_fee_bps: 500,
+            debt_ceiling: 100_000,
+            enabled: true,
+        },
+    );
+
+    let borrower = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &collateral).mint(&borrower, &10_000);
+    t.client.cdp_deposit(&borrower, &collateral, &10_000);
+    t.client.cdp_mint(&borrower, &collateral, &10_000);
+
+    let saver = funded_user(&t, 10_000);
+    t.client.savings_deposit(&saver, &t.stablecoin, &10_000);
+
+    // Fees are realized on the next vault touch and repaid before principal
+    advance_one_year(&t);
+    t.client
+        .update_price_feed(&t.admin, &t.stablecoin, &PRICE_ONE, &7, &t.admin);
+    t.client
+        .update_price_feed(&t.admin, &collateral, &(2 * PRICE_ONE), &7, &t.admin);
+    t.client.cdp_repay(&borrower, &collateral, &200);
+    assert_eq!(t.client.get_cdp_fees_accrued(), 500);
+    assert_eq!(t.client.get_cdp_fees_collected(), 200);
+
+    // Only the repaid part of the fees is harvested
+    assert_eq!(t.client.harvest_stability_fees(), 200);
+    assert_eq!(t.client.harvest_stability_fees(), 0);
+
+    t.client.cdp_repay(&borrower, &collateral, &1_000);
+    assert_eq!(t.client.get_cdp_fees_collected(), 500);
+    assert_eq!(t.client.harvest_stability_fees(), 300);
+    assert_eq!(t.client.harvest_stability_fees(), 0);
+    assert_eq!(
+        t.client.get_savings_market(&t.stablecoin).unwrap().funding,
+        500
+    );
+
+    // The saver earned nothing while the pool was empty, then draws on the fees
+    advance_one_year(&t);
+    assert_eq!(t.client.get_savings_balance(&t.stablecoin, &saver), 10_500);
+}
+
+#[test]
+fn test_savings_balances_are_escrowed() {
+    let t = setup();
+    let asset = Some(t.stablecoin.clone());
+    let escrowed = || {
+        t.env.as_contract(&t.client.address, || {
+            crate::reserve::get_escrowed_balance(&t.env, &asset)
+        })
+    };
+
+    let user = funded_user(&t, 2_000);
+    t.client.savings_deposit(&user, &t.stablecoin, &1_000);
+    assert_eq!(escrowed(), 1_000);
+    t.client.fund_savings(&user, &t.stablecoin, &500);
+    assert_eq!(escrowed(), 1_500);
+    let liquidity = t.env.as_contract(&t.client.address, || {
+        crate::reserve::available_liquidity(&t.env, &asset, &t.stablecoin)
+    });
+    assert_eq!(liquidity, 0);
+
+    // Interest moves from the funding pool to the deposits; the unit of
+    // rounding dust left behind is not owed to anyone
+    advance_one_year(&t);
+    t.client.savings_withdraw(&user, &t.stablecoin, &400);
+    assert_eq!(escrowed(), 1_099);
+}
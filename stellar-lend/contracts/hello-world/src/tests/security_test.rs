@@ -53,7 +53,7 @@ fn security_deposit_negative_amount() {
 
 /// Negative amount rejected on withdraw (invalid input).
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn security_withdraw_negative_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -67,7 +67,7 @@ fn security_withdraw_negative_amount() {
 
 /// Withdraw more than balance rejected (insufficient collateral).
 #[test]
-#[should_panic(expected = "InsufficientCollateral")]
+#[should_panic(expected = "Error(Contract, #4)")]
 fn security_withdraw_exceeds_balance() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+use crate::shadow_oracle::{ShadowOracleError, SHADOW_WINDOW_SIZE};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const PRICE: i128 = 100_0000000;
+
+struct Setup<'a> {
+    client: HelloContractClient<'a>,
+    admin: Address,
+    asset: Address,
+    shadow: Address,
+}
+
+/// A deployment with a primary price of 100 (7 decimals) and a shadow oracle
+fn setup(env: &Env) -> Setup<'_> {
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let asset = Address::generate(env);
+    let shadow = Address::generate(env);
+    client.update_price_feed(&admin, &asset, &PRICE, &7, &Address::generate(env));
+    client.set_shadow_oracle(&admin, &asset, &shadow);
+
+    Setup {
+        client,
+        admin,
+        asset,
+        shadow,
+    }
+}
+
+#[test]
+fn test_submit_shadow_price_tracks_deviation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    let deviation = s
+        .client
+        .submit_shadow_price(&s.shadow, &s.asset, &101_0000000, &7);
+    assert_eq!(deviation, 100);
+
+    // 8-decimal price of 106 is normalized and breaches the 5% default
+    let deviation = s
+        .client
+        .submit_shadow_price(&s.shadow, &s.asset, &106_00000000, &8);
+    assert_eq!(deviation, 600);
+
+    let stats = s.client.get_shadow_oracle_stats(&s.asset);
+    assert_eq!(stats.shadow_oracle, s.shadow);
+    assert_eq!(stats.samples, 2);
+    assert_eq!(stats.breaches, 1);
+    assert_eq!(stats.window_samples, 2);
+    assert_eq!(stats.window_avg_deviation_bps, 350);
+    assert_eq!(stats.window_max_deviation_bps, 600);
+    let last = stats.last_observation.get(0).unwrap();
+    assert_eq!(last.shadow_price, 106_0000000);
+    assert_eq!(last.primary_price, PRICE);
+
+    // Shadow prices never reach the protocol
+    assert_eq!(s.client.get_price(&s.asset), PRICE);
+}
+
+#[test]
+fn test_shadow_stats_window_is_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+
+    for _ in 0..SHADOW_WINDOW_SIZE + 5 {
+        s.client
+            .submit_shadow_price(&s.shadow, &s.asset, &PRICE, &7);
+    }
+    let stats = s.client.get_shadow_oracle_stats(&s.asset);
+    assert_eq!(stats.samples, (SHADOW_WINDOW_SIZE + 5) as u64);
+    assert_eq!(stats.window_samples, SHADOW_WINDOW_SIZE);
+    assert_eq!(stats.window_max_deviation_bps, 0);
+
+    // Re-registering the shadow oracle resets its stats
+    s.client.set_shadow_oracle(&s.admin, &s.asset, &s.shadow);
+    let stats = s.client.get_shadow_oracle_stats(&s.asset);
+    assert_eq!(stats.samples, 0);
+    assert_eq!(stats.last_observation.len(), 0);
+}
+
+#[test]
+fn test_shadow_oracle_access_control() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    let stranger = Address::generate(&env);
+
+    let result = s
+        .client
+        .try_set_shadow_oracle(&stranger, &s.asset, &stranger);
+    assert_eq!(result, Err(Ok(ShadowOracleError::Unauthorized)));
+    let result = s
+        .client
+        .try_submit_shadow_price(&stranger, &s.asset, &PRICE, &7);
+    assert_eq!(result, Err(Ok(ShadowOracleError::Unauthorized)));
+    let result = s
+        .client
+        .try_submit_shadow_price(&s.shadow, &s.asset, &0, &7);
+    assert_eq!(result, Err(Ok(ShadowOracleError::InvalidPrice)));
+
+    let unpriced = Address::generate(&env);
+    let result = s
+        .client
+        .try_submit_shadow_price(&s.shadow, &unpriced, &PRICE, &7);
+    assert_eq!(result, Err(Ok(ShadowOracleError::ShadowNotConfigured)));
+    s.client.set_shadow_oracle(&s.admin, &unpriced, &s.shadow);
+    let result = s
+        .client
+        .try_submit_shadow_price(&s.shadow, &unpriced, &PRICE, &7);
+    assert_eq!(result, Err(Ok(ShadowOracleError::PrimaryPriceUnavailable)));
+
+    s.client.remove_shadow_oracle(&s.admin, &s.asset);
+    let result = s.client.try_get_shadow_oracle_stats(&s.asset);
+    assert_eq!(result, Err(Ok(ShadowOracleError::ShadowNotConfigured)));
+}
+
+#[test]
+fn test_promote_shadow_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let s = setup(&env);
+    s.client
+        .submit_shadow_price(&s.shadow, &s.asset, &102_0000000, &7);
+
+    let promoted = s.client.promote_shadow_oracle(&s.admin, &s.asset);
+    assert_eq!(promoted, s.shadow);
+    let result = s.client.try_get_shadow_oracle_stats(&s.asset);
+    assert_eq!(result, Err(Ok(ShadowOracleError::ShadowNotConfigured)));
+
+    // The promoted oracle now feeds the protocol directly
+    s.client
+        .update_price_feed(&s.shadow, &s.asset, &102_0000000, &7, &s.shadow);
+    assert_eq!(s.client.get_price(&s.asset), 102_0000000);
+}
@@ -0,0 +1,113 @@
+#![cfg(test)]
+
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+fn setup(env: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (contract_id, admin, client)
+}
+
+fn new_token(env: &Env, admin: &Address) -> Address {
+    env.register_stellar_asset_contract_v2(admin.clone())
+        .address()
+}
+
+fn fund(env: &Env, contract_id: &Address, token: &Address, user: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(user, &amount);
+    TokenClient::new(env, token).approve(
+        user,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_siloed_asset_is_borrowed_in_isolation() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let collateral = Some(new_token(&env, &admin));
+    let siloed_token = new_token(&env, &admin);
+    let siloed = Some(siloed_token.clone());
+    let other = Some(new_token(&env, &admin));
+    let user = Address::generate(&env);
+    fund(
+        &env,
+        &contract_id,
+        collateral.as_ref().unwrap(),
+        &user,
+        1_000_000,
+    );
+    client.deposit_collateral(&user, &collateral, &1_000_000);
+
+    assert!(client
+        .try_set_siloed(&Address::generate(&env), &siloed, &true)
+        .is_err());
+    client.set_siloed(&admin, &siloed, &true);
+    assert!(client.get_reserve_config(&siloed).siloed);
+    assert!(!client.get_reserve_config(&other).siloed);
+
+    client.borrow_asset(&user, &siloed, &10_000);
+    client.borrow_asset(&user, &siloed, &5_000);
+    assert_eq!(
+        client.get_user_debt_assets(&user),
+        vec![&env, siloed.clone()]
+    );
+
+    // Siloed debt blocks borrowing anything else
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            borrow_asset(&env, user.clone(), other.clone(), 1_000),
+            Err(BorrowError::SiloedBorrowViolation)
+        );
+    });
+
+    // Once repaid, the account is free to borrow other assets
+    fund(&env, &contract_id, &siloed_token, &user, 15_000);
+    client.repay_debt(&user, &siloed, &15_000);
+    assert_eq!(client.get_user_debt_assets(&user).len(), 0);
+    client.borrow_asset(&user, &other, &1_000);
+}
+
+#[test]
+fn test_siloed_asset_cannot_join_existing_debt() {
+    let env = Env::default();
+    let (contract_id, admin, client) = setup(&env);
+    let collateral = Some(new_token(&env, &admin));
+    let siloed = Some(new_token(&env, &admin));
+    let user = Address::generate(&env);
+    fund(
+        &env,
+        &contract_id,
+        collateral.as_ref().unwrap(),
+        &user,
+        1_000_000,
+    );
+    client.deposit_collateral(&user, &collateral, &1_000_000);
+    client.set_siloed(&admin, &siloed, &true);
+
+    client.borrow_asset(&user, &collateral, &1_000);
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            borrow_asset(&env, user.clone(), siloed.clone(), 1_000),
+            Err(BorrowError::SiloedBorrowViolation)
+        );
+    });
+
+    // Non-siloed assets mix freely
+    client.borrow_asset(&user, &None, &1_000);
+    assert_eq!(
+        client.get_user_debt_assets(&user),
+        vec![&env, collateral.clone(), None]
+    );
+}
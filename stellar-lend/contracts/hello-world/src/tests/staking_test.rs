@@ -0,0 +1,146 @@
+#![cfg(test)]
+
+use crate::cross_asset::AssetConfig;
+use crate::staking::{StakingError, MAX_LOCK_DURATION};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+/// Pool with a native reserve, governance over `gov_token` and a reward
+/// token; Alice and Bob each supply 100 and hold 1_000 `gov_token`
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address, Address, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &None,
+        &AssetConfig {
+            asset: None,
+            collateral_factor: 7_500,
+            liquidation_threshold: 8_000,
+            reserve_factor: 1_000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+        },
+    );
+
+    let gov_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.gov_initialize(&admin, &gov_token, &None, &None, &None, &None, &None, &None);
+    let reward_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(env, &reward_token).mint(&admin, &10_000);
+
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+    StellarAssetClient::new(env, &gov_token).mint(&alice, &1_000);
+    StellarAssetClient::new(env, &gov_token).mint(&bob, &1_000);
+    client.cross_asset_deposit(&alice, &None, &100);
+    client.cross_asset_deposit(&bob, &None, &100);
+    (client, admin, reward_token, alice, bob)
+}
+
+fn pending(client: &HelloContractClient, user: &Address, reward_token: &Address) -> i128 {
+    client
+        .get_pending_rewards(user)
+        .get(reward_token.clone())
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_locked_stake_boosts_rewards_up_to_max() {
+    let env = Env::default();
+    let (client, admin, reward_token, alice, bob) = setup(&env);
+
+    let unlock_time = env.ledger().timestamp() + MAX_LOCK_DURATION;
+    let stake = client.lock_stake(&alice, &1_000, &unlock_time);
+    assert_eq!(stake.weight, 1_000);
+    assert_eq!(client.get_total_stake_weight(), 1_000);
+    assert_eq!(client.get_reward_boost(&alice, &None), (25_000, 10_000));
+    assert_eq!(client.get_reward_boost(&bob, &None), (10_000, 10_000));
+
+    // Alice earns 2.5x what Bob does on the same balance
+    client.set_reward_emission(&admin, &None, &reward_token, &14, &0, &100);
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    assert_eq!(pending(&client, &alice, &reward_token), 100);
+    assert_eq!(pending(&client, &bob, &reward_token), 40);
+
+    client.claim_rewards(&alice, &vec![&env, None], &alice);
+    assert_eq!(TokenClient::new(&env, &reward_token).balance(&alice), 100);
+
+    // Once Bob stakes as much, Alice's boost shrinks at her next position change
+    let unlock_time = env.ledger().timestamp() + MAX_LOCK_DURATION;
+    client.lock_stake(&bob, &1_000, &unlock_time);
+    assert_eq!(client.get_reward_boost(&bob, &None), (25_000, 10_000));
+    client.cross_asset_deposit(&alice, &None, &900);
+    let (boost, _) = client.get_reward_boost(&alice, &None);
+    assert!(boost > 10_000 && boost < 25_000);
+}
+
+#[test]
+fn test_unlock_returns_tokens_and_removes_boost() {
+    let env = Env::default();
+    let (client, _admin, _reward_token, alice, _bob) = setup(&env);
+    let now = env.ledger().timestamp();
+
+    let stake = client.lock_stake(&alice, &1_000, &(now + MAX_LOCK_DURATION / 2));
+    assert_eq!(stake.weight, 500);
+    assert_eq!(client.get_reward_boost(&alice, &None).0, 25_000);
+    assert_eq!(
+        client.try_lock_stake(&alice, &0, &(now + 10)),
+        Err(Ok(StakingError::InvalidUnlockTime))
+    );
+    assert_eq!(
+        client.try_unlock_stake(&alice),
+        Err(Ok(StakingError::StakeLocked))
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += MAX_LOCK_DURATION / 2);
+    assert_eq!(client.unlock_stake(&alice), 1_000);
+    assert_eq!(client.get_stake(&alice), None);
+    assert_eq!(client.get_total_stake_weight(), 0);
+    assert_eq!(client.get_reward_boost(&alice, &None).0, 10_000);
+    assert_eq!(
+        client.try_unlock_stake(&alice),
+        Err(Ok(StakingError::NoStake))
+    );
+}
+
+#[test]
+fn test_lock_validation() {
+    let env = Env::default();
+    let (client, _admin, _reward_token, alice, _bob) = setup(&env);
+    let now = env.ledger().timestamp();
+
+    assert_eq!(
+        client.try_lock_stake(&alice, &0, &(now + 100)),
+        Err(Ok(StakingError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_lock_stake(&alice, &100, &(now + MAX_LOCK_DURATION + 1)),
+        Err(Ok(StakingError::InvalidUnlockTime))
+    );
+    assert_eq!(
+        client.try_lock_stake(&alice, &100, &now),
+        Err(Ok(StakingError::InvalidUnlockTime))
+    );
+
+    let other = HelloContractClient::new(&env, &env.register(HelloContract, ()));
+    assert_eq!(
+        other.try_lock_stake(&alice, &100, &(now + 100)),
+        Err(Ok(StakingError::GovernanceNotInitialized))
+    );
+}
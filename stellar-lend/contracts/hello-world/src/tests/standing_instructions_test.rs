@@ -0,0 +1,157 @@
+#![cfg(test)]
+
+use crate::operators::{OP_BORROW, OP_DEPOSIT, OP_REPAY};
+use crate::standing_instructions::{InstructionTrigger, StandingInstructionError};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address,
+};
+
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+#[test]
+fn test_periodic_repayment_pays_keeper_bounty() {
+    let (env, contract_id, client, _admin, user, native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    let token = TokenClient::new(&env, &native_asset);
+    StellarAssetClient::new(&env, &native_asset).mint(&user, &20_000);
+    token.approve(
+        &user,
+        &contract_id,
+        &20_000,
+        &(env.ledger().sequence() + 100),
+    );
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &4_000);
+    let keeper = Address::generate(&env);
+
+    let id = client.create_standing_instruction(
+        &user,
+        &OP_REPAY,
+        &None,
+        &1_000,
+        &InstructionTrigger::Every(WEEK),
+        &10,
+        &2,
+    );
+    assert_eq!(
+        client.try_execute_standing_instruction(&keeper, &id),
+        Err(Ok(StandingInstructionError::TriggerNotMet))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += WEEK);
+    let debt_before = client.get_user_debt(&user);
+    client.execute_standing_instruction(&keeper, &id);
+    assert_eq!(client.get_user_debt(&user), debt_before - 1_000);
+    assert_eq!(token.balance(&keeper), 10);
+    assert_eq!(
+        client
+            .get_standing_instruction(&id)
+            .unwrap()
+            .executions_left,
+        1
+    );
+    assert_eq!(
+        client.try_execute_standing_instruction(&keeper, &id),
+        Err(Ok(StandingInstructionError::TriggerNotMet))
+    );
+
+    // The last execution removes the instruction
+    env.ledger().with_mut(|li| li.timestamp += WEEK);
+    client.execute_standing_instruction(&keeper, &id);
+    assert_eq!(token.balance(&keeper), 20);
+    assert_eq!(client.get_standing_instruction(&id), None);
+    assert_eq!(client.get_standing_instructions(&user).len(), 0);
+    assert_eq!(
+        client.try_execute_standing_instruction(&keeper, &id),
+        Err(Ok(StandingInstructionError::InstructionNotFound))
+    );
+}
+
+#[test]
+fn test_collateral_top_up_below_health_factor() {
+    let (env, contract_id, client, admin, user, native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    StellarAssetClient::new(&env, &native_asset).mint(&user, &20_000);
+    TokenClient::new(&env, &native_asset).approve(
+        &user,
+        &contract_id,
+        &20_000,
+        &(env.ledger().sequence() + 100),
+    );
+    client.set_liquidation_threshold(&admin, &None, &8_000);
+    client.deposit_collateral(&user, &None, &10_000);
+    let keeper = Address::generate(&env);
+
+    let id = client.create_standing_instruction(
+        &user,
+        &OP_DEPOSIT,
+        &None,
+        &1_000,
+        &InstructionTrigger::HealthFactorBelow(11_000),
+        &0,
+        &5,
+    );
+    // No debt, no top-up
+    assert_eq!(
+        client.try_execute_standing_instruction(&keeper, &id),
+        Err(Ok(StandingInstructionError::TriggerNotMet))
+    );
+
+    // Health factor 1.0 after the borrow; one top-up lifts it to 1.1
+    client.borrow_asset(&user, &None, &8_000);
+    assert_eq!(client.execute_standing_instruction(&keeper, &id), 11_000);
+    assert_eq!(client.get_account_data(&user).health_factor, 11_000);
+    assert_eq!(
+        client.try_execute_standing_instruction(&keeper, &id),
+        Err(Ok(StandingInstructionError::TriggerNotMet))
+    );
+
+    assert_eq!(
+        client.try_cancel_standing_instruction(&keeper, &id),
+        Err(Ok(StandingInstructionError::Unauthorized))
+    );
+    client.cancel_standing_instruction(&user, &id);
+    assert_eq!(client.get_standing_instructions(&user).len(), 0);
+}
+
+#[test]
+fn test_create_standing_instruction_validates_input() {
+    let (_env, _contract_id, client, _admin, user, _native_asset) =
+        crate::tests::test_helpers::setup_env_with_native_asset();
+    let trigger = InstructionTrigger::Every(WEEK);
+
+    assert_eq!(
+        client.try_create_standing_instruction(&user, &OP_BORROW, &None, &1_000, &trigger, &0, &1),
+        Err(Ok(StandingInstructionError::InvalidOperation))
+    );
+    assert_eq!(
+        client.try_create_standing_instruction(&user, &OP_REPAY, &None, &0, &trigger, &0, &1),
+        Err(Ok(StandingInstructionError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_create_standing_instruction(
+            &user,
+            &OP_REPAY,
+            &None,
+            &1_000,
+            &InstructionTrigger::Every(0),
+            &0,
+            &1
+        ),
+        Err(Ok(StandingInstructionError::InvalidTrigger))
+    );
+    assert_eq!(
+        client.try_create_standing_instruction(&user, &OP_REPAY, &None, &1_000, &trigger, &0, &0),
+        Err(Ok(StandingInstructionError::InvalidExecutions))
+    );
+
+    for _ in 0..crate::standing_instructions::MAX_INSTRUCTIONS_PER_USER {
+        client.create_standing_instruction(&user, &OP_REPAY, &None, &1_000, &trigger, &0, &1);
+    }
+    assert_eq!(
+        client.try_create_standing_instruction(&user, &OP_REPAY, &None, &1_000, &trigger, &0, &1),
+        Err(Ok(StandingInstructionError::TooManyInstructions))
+    );
+}
@@ -0,0 +1,88 @@
+#![cfg(test)]
+
+use crate::deposit::DepositDataKey;
+use crate::interest_index::get_user_debt;
+use crate::reserve::ReserveDataKey;
+use crate::storage_ttl::{StorageTtlError, PERSISTENT_TTL_EXTEND_TO, PERSISTENT_TTL_THRESHOLD};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    Address, Env,
+};
+
+#[test]
+fn test_core_operations_and_bump_extend_position_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    let user = Address::generate(&env);
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &1_000);
+    let ttl = |key: &DepositDataKey| {
+        env.as_contract(&contract_id, || env.storage().persistent().get_ttl(key))
+    };
+    let position_key = DepositDataKey::Position(user.clone());
+    assert_eq!(ttl(&position_key), PERSISTENT_TTL_EXTEND_TO);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        assert_eq!(
+            storage.get_ttl(&ReserveDataKey::TotalSupplied(None)),
+            PERSISTENT_TTL_EXTEND_TO
+        );
+        assert_eq!(
+            storage.get_ttl(&ReserveDataKey::UserDebtAssets(user.clone())),
+            PERSISTENT_TTL_EXTEND_TO
+        );
+    });
+
+    // A dormant position drifts towards archival until someone bumps it
+    client.bump_position(&user);
+    env.ledger().with_mut(|li| {
+        li.sequence_number += PERSISTENT_TTL_EXTEND_TO - PERSISTENT_TTL_THRESHOLD + 1
+    });
+    assert_eq!(ttl(&position_key), PERSISTENT_TTL_THRESHOLD - 1);
+
+    env.set_auths(&[]);
+    client.bump_position(&user);
+    assert!(env.auths().is_empty());
+    assert_eq!(ttl(&position_key), PERSISTENT_TTL_EXTEND_TO);
+    assert_eq!(client.get_user_report(&user).position.debt, 1_000);
+
+    // Users without a position are skipped
+    client.bump_position(&Address::generate(&env));
+}
+
+#[test]
+fn test_restore_position_backfills_interest_from_snapshots() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    let user = Address::generate(&env);
+    client.deposit_collateral(&user, &None, &100_000);
+    client.borrow_asset(&user, &None, &50_000);
+    assert_eq!(
+        client.try_restore_position(&Address::generate(&env)),
+        Err(Ok(StorageTtlError::PositionNotFound))
+    );
+
+    // A year untouched, as if archived and restored
+    env.ledger()
+        .with_mut(|li| li.timestamp += 365 * 24 * 60 * 60);
+    let owed = env.as_contract(&contract_id, || get_user_debt(&env, &user));
+    assert!(owed > 50_000);
+
+    let position = client.restore_position(&user);
+    assert_eq!(position.debt + position.borrow_interest, owed);
+    assert_eq!(position.last_accrual_time, env.ledger().timestamp());
+    assert_eq!(client.get_user_report(&user).position, position);
+
+    // Nothing more is owed for the same period
+    let position = client.restore_position(&user);
+    assert_eq!(position.debt + position.borrow_interest, owed);
+}
@@ -0,0 +1,355 @@
+#![cfg(test)]
+
+use crate::strategy_vault::{StrategyVaultConfig, StrategyVaultError};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, Symbol};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+struct VaultTest {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'static>,
+    admin: Address,
+    collateral: Address,
+    debt: Address,
+    fee_recipient: Address,
+}
+
+fn setup() -> VaultTest {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let collateral = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let debt = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    client.update_price_feed(&admin, &collateral, &PRICE_ONE, &7, &admin);
+    client.update_price_feed(&admin, &debt, &PRICE_ONE, &7, &admin);
+
+    // Deep 50/50 collateral/debt pool hosted by the protocol's AMM router
+    StellarAssetClient::new(&env, &collateral).mint(&admin, &10_000_000);
+    StellarAssetClient::new(&env, &debt).mint(&admin, &10_000_000);
+    env.as_contract(&contract_id, || {
+        stellarlend_amm::update_amm_settings(
+            &env,
+            admin.clone(),
+            stellarlend_amm::AmmSettings {
+                default_slippage: 100,
+                max_slippage: 1_000,
+                swap_enabled: true,
+                liquidity_enabled: true,
+                auto_swap_threshold: 10_000,
+                pair_max_slippage: soroban_sdk::Map::new(&env),
+                max_swap_history: 1000,
+                swap_history_retention: 0,
+                max_deadline_window: 3600,
+            },
+        )
+        .unwrap();
+        stellarlend_amm::lbp::create_lbp_pool(
+            &env,
+            admin.clone(),
+            collateral.clone(),
+            debt.clone(),
+            10_000_000,
+            10_000_000,
+            stellarlend_amm::WeightSchedule {
+                start_time: 0,
+                end_time: 1_000,
+                start_weight_a: 5_000,
+                end_weight_a: 5_000,
+            },
+            0,
+        )
+        .unwrap();
+    });
+
+    // Lendable debt liquidity, separate from the pool's reserves
+    StellarAssetClient::new(&env, &debt).mint(&contract_id, &10_000_000);
+
+    let fee_recipient = Address::generate(&env);
+
+    VaultTest {
+        env,
+        contract_id,
+        client,
+        admin,
+        collateral,
+        debt,
+        fee_recipient,
+    }
+}
+
+fn vault_config(t: &VaultTest) -> StrategyVaultConfig {
+    StrategyVaultConfig {
+        collateral_asset: t.collateral.clone(),
+        debt_asset: t.debt.clone(),
+        target_leverage_bps: 30_000,
+        rebalance_band_bps: 2_000,
+        deposit_cap: 1_000_000,
+        performance_fee_bps: 1_000,
+        fee_recipient: t.fee_recipient.clone(),
+        enabled: true,
+    }
+}
+
+fn total_borrowed(t: &VaultTest) -> i128 {
+    t.env.as_contract(&t.contract_id, || {
+        crate::reserve::get_total_borrowed(&t.env, &Some(t.debt.clone()))
+    })
+}
+
+fn funded_user(t: &VaultTest, amount: i128) -> Address {
+    let user = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.collateral).mint(&user, &amount);
+    user
+}
+
+#[test]
+fn test_create_vault_and_deposit() {
+    let t = setup();
+    let vault_id = t.client.create_strategy_vault(&t.admin, &vault_config(&t));
+    assert_eq!(vault_id, 0);
+    assert_eq!(t.client.get_vault_count(), 1);
+
+    let user = funded_user(&t, 10_000);
+    let shares = t.client.vault_deposit(&user, &vault_id, &1_000);
+    assert_eq!(shares, 1_000);
+    assert_eq!(t.client.get_vault_shares(&vault_id, &user), 1_000);
+
+    let view = t.client.get_strategy_vault(&vault_id);
+    assert_eq!(view.state.collateral, 1_000);
+    assert_eq!(view.state.debt, 0);
+    assert_eq!(view.equity, 1_000);
+    assert_eq!(view.leverage_bps, 10_000);
+    assert!(view.needs_rebalance);
+}
+
+#[test]
+fn test_create_vault_invalid_config() {
+    let t = setup();
+    let mut config = vault_config(&t);
+    config.target_leverage_bps = 5_000;
+    let result = t.client.try_create_strategy_vault(&t.admin, &config);
+    assert_eq!(result, Err(Ok(StrategyVaultError::InvalidConfig)));
+
+    let mut config = vault_config(&t);
+    config.debt_asset = t.collateral.clone();
+    let result = t.client.try_create_strategy_vault(&t.admin, &config);
+    assert_eq!(result, Err(Ok(StrategyVaultError::InvalidConfig)));
+}
+
+#[test]
+fn test_create_vault_unauthorized() {
+    let t = setup();
+    let attacker = Address::generate(&t.env);
+    let result = t
+        .client
+        .try_create_strategy_vault(&attacker, &vault_config(&t));
+    assert_eq!(result, Err(Ok(StrategyVaultError::Unauthorized)));
+}
+
+#[test]
+fn test_rebalance_levers_to_target() {
+    let t = setup();
+    let vault_id = t.client.create_strategy_vault(&t.admin, &vault_config(&t));
+    let user = funded_user(&t, 10_000);
+    t.client.vault_deposit(&user, &vault_id, &1_000);
+
+    // 2_000 borrowed from the pool buys 1_999 collateral after price impact
+    let leverage = t.client.rebalance_vault(&t.admin, &vault_id);
+    assert_eq!(leverage, 30_020);
+
+    let view = t.client.get_strategy_vault(&vault_id);
+    assert_eq!(view.state.collateral, 2_999);
+    assert_eq!(view.state.debt, 2_000);
+    assert_eq!(view.state.principal, 2_000);
+    assert_eq!(view.equity, 999);
+    assert!(!view.needs_rebalance);
+    assert_eq!(total_borrowed(&t), 2_000);
+
+    // Already inside the band
+    let result = t.client.try_rebalance_vault(&t.admin, &vault_id);
+    assert_eq!(result, Err(Ok(StrategyVaultError::WithinRebalanceBand)));
+}
+
+#[test]
+fn test_rebalance_rejects_swap_below_oracle_value() {
+    let t = setup();
+    let vault_id = t.client.create_strategy_vault(&t.admin, &vault_config(&t));
+    let user = funded_user(&t, 10_000);
+    t.client.vault_deposit(&user, &vault_id, &1_000);
+
+    // The oracle values the debt asset 5% above the pool price
+    t.client
+        .update_price_feed(&t.admin, &t.debt, &(PRICE_ONE * 105 / 100), &7, &t.admin);
+    let result = t.client.try_rebalance_vault(&t.admin, &vault_id);
+    assert_eq!(result, Err(Ok(StrategyVaultError::SlippageExceeded)));
+    assert_eq!(total_borrowed(&t), 0);
+}
+
+#[test]
+fn test_rebalance_requires_keeper_role() {
+    let t = setup();
+    let vault_id = t.client.create_strategy_vault(&t.admin, &vault_config(&t));
+    let user = funded_user(&t, 10_000);
+    t.client.vault_deposit(&user, &vault_id, &1_000);
+
+    let keeper = Address::generate(&t.env);
+    let result = t.client.try_rebalance_vault(&keeper, &vault_id);
+    assert_eq!(result, Err(Ok(StrategyVaultError::Unauthorized)));
+
+    t.client
+        .grant_role(&t.admin, &Symbol::new(&t.env, "keeper"), &keeper);
+    assert_eq!(t.client.rebalance_vault(&keeper, &vault_id), 30_020);
+}
+
+#[test]
+fn test_deposit_cap_enforced() {
+    let t = setup();
+    let mut config = vault_config(&t);
+    config.deposit_cap = 1_500;
+    let vault_id = t.client.create_strategy_vault(&t.admin, &config);
+    let user = funded_user(&t, 10_000);
+
+    t.client.vault_deposit(&user, &vault_id, &1_000);
+    let result = t.client.try_vault_deposit(&user, &vault_id, &600);
+    assert_eq!(result, Err(Ok(StrategyVaultError::DepositCapExceeded)));
+
+    t.client.vault_deposit(&user, &vault_id, &500);
+    assert_eq!(t.client.get_strategy_vault(&vault_id).equity, 1_500);
+}
+
+#[test]
+fn test_deposit_disabled_vault() {
+    let t = setup();
+    let mut config = vault_config(&t);
+    config.enabled = false;
+    let vault_id = t.client.create_strategy_vault(&t.admin, &config);
+    let user = funded_user(&t, 10_000);
+
+    let result = t.client.try_vault_deposit(&user, &vault_id, &1_000);
+    assert_eq!(result, Err(Ok(StrategyVaultError::VaultDisabled)));
+}
+
+#[test]
+fn test_withdraw_deleverages_pro_rata() {
+    let t = setup();
+    let vault_id = t.client.create_strategy_vault(&t.admin, &vault_config(&t));
+    let user = funded_user(&t, 10_000);
+    t.client.vault_deposit(&user, &vault_id, &1_000);
+    t.client.rebalance_vault(&t.admin, &vault_id);
+
+    // Half the shares: 1_499 collateral and 1_000 debt, repaid by selling
+    // 1_000 collateral through the pool
+    let amount_out = t.client.vault_withdraw(&user, &vault_id, &500);
+    assert_eq!(amount_out, 499);
+    assert_eq!(t.client.get_vault_shares(&vault_id, &user), 500);
+    assert_eq!(
+        soroban_sdk::token::TokenClient::new(&t.env, &t.collateral).balance(&user),
+        9_499
+    );
+
+    let view = t.client.get_strategy_vault(&vault_id);
+    assert_eq!(view.state.collateral, 1_500);
+    assert_eq!(view.state.debt, 1_000);
+    assert_eq!(view.leverage_bps, 30_000);
+    assert_eq!(total_borrowed(&t), 1_000);
+
+    let result = t.client.try_vault_withdraw(&user, &vault_id, &501);
+    assert_eq!(result, Err(Ok(StrategyVaultError::InsufficientShares)));
+}
+
+#[test]
+fn test_performance_fee_charged_above_high_water_mark() {
+    let t = setup();
+    let vault_id = t.client.create_strategy_vault(&t.admin, &vault_config(&t));
+    let user = funded_user(&t, 10_000);
+    t.client.vault_deposit(&user, &vault_id, &1_000);
+    t.client.rebalance_vault(&t.admin, &vault_id);
+
+    // Collateral appreciates 5% against the debt asset
+    t.client.update_price_feed(
+        &t.admin,
+        &t.collateral,
+        &(PRICE_ONE * 105 / 100),
+        &7,
+        &t.admin,
+    );
+
+    // Any interaction settles the vault and charges the fee
+    t.client.vault_deposit(&user, &vault_id, &100);
+
+    let fee_shares = t.client.get_vault_shares(&vault_id, &t.fee_recipient);
+    assert!(fee_shares > 0);
+
+    let view = t.client.get_strategy_vault(&vault_id);
+    assert_eq!(view.state.fee_shares_minted, fee_shares);
+    assert!(view.state.high_water_mark > 10_000_000);
+
+    // No new gain, no new fee
+    t.client.vault_deposit(&user, &vault_id, &100);
+    assert_eq!(
+        t.client.get_vault_shares(&vault_id, &t.fee_recipient),
+        fee_shares
+    );
+}
+
+#[test]
+fn test_vault_collateral_is_not_lendable() {
+    let t = setup();
+    // Vault whose collateral is the pool's debt asset
+    let mut config = vault_config(&t);
+    config.collateral_asset = t.debt.clone();
+    config.debt_asset = t.collateral.clone();
+    let vault_id = t.client.create_strategy_vault(&t.admin, &config);
+
+    let debt_asset = Some(t.debt.clone());
+    let liquidity = || {
+        t.env.as_contract(&t.contract_id, || {
+            crate::reserve::available_liquidity(&t.env, &debt_asset, &t.debt)
+        })
+    };
+    let liquidity_before = liquidity();
+
+    let user = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &t.debt).mint(&user, &5_000);
+    t.client.vault_deposit(&user, &vault_id, &5_000);
+    let escrowed = t.env.as_contract(&t.contract_id, || {
+        crate::reserve::get_escrowed_balance(&t.env, &debt_asset)
+    });
+    assert_eq!(escrowed, 5_000);
+    assert_eq!(liquidity(), liquidity_before);
+
+    t.client.vault_withdraw(&user, &vault_id, &5_000);
+    let escrowed = t.env.as_contract(&t.contract_id, || {
+        crate::reserve::get_escrowed_balance(&t.env, &debt_asset)
+    });
+    assert_eq!(escrowed, 0);
+    assert_eq!(liquidity(), liquidity_before);
+}
+
+#[test]
+fn test_pool_reserves_are_not_lendable() {
+    let t = setup();
+    let debt_asset = Some(t.debt.clone());
+    let (hosted, liquidity) = t.env.as_contract(&t.contract_id, || {
+        (
+            stellarlend_amm::amm::get_hosted_reserve(&t.env, &t.debt),
+            crate::reserve::available_liquidity(&t.env, &debt_asset, &t.debt),
+        )
+    });
+
+    // Only the lendable mint counts, not the LBP's debt reserve
+    assert_eq!(hosted, 10_000_000);
+    assert_eq!(liquidity, 10_000_000);
+}
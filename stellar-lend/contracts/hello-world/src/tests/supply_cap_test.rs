@@ -0,0 +1,98 @@
+#![cfg(test)]
+
+use crate::deposit::{deposit_collateral, DepositError};
+use crate::reserve::ReserveError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+fn setup(env: &Env) -> (Address, Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (contract_id, admin, token, client)
+}
+
+fn fund(env: &Env, contract_id: &Address, token: &Address, user: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(user, &amount);
+    TokenClient::new(env, token).approve(
+        user,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_supply_cap_limits_deposits() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    let user = Address::generate(&env);
+    fund(&env, &contract_id, &token, &user, 10_000);
+
+    assert_eq!(client.get_remaining_supply_capacity(&asset), i128::MAX);
+    assert_eq!(
+        client.try_set_supply_cap(&admin, &asset, &-1),
+        Err(Ok(ReserveError::InvalidSupplyCap))
+    );
+    assert_eq!(
+        client.try_set_supply_cap(&Address::generate(&env), &asset, &5_000),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+    client.set_supply_cap(&admin, &asset, &5_000);
+    assert_eq!(client.get_reserve_config(&asset).supply_cap, 5_000);
+
+    client.deposit_collateral(&user, &asset, &4_000);
+    assert_eq!(client.get_remaining_supply_capacity(&asset), 1_000);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            deposit_collateral(&env, user.clone(), asset.clone(), 1_001),
+            Err(DepositError::SupplyCapExceeded)
+        );
+    });
+    assert!(client
+        .try_deposit_collateral(&user, &asset, &1_001)
+        .is_err());
+    client.deposit_collateral(&user, &asset, &1_000);
+    assert_eq!(client.get_remaining_supply_capacity(&asset), 0);
+
+    // Withdrawals free up capacity
+    client.withdraw_collateral(&user, &asset, &2_500);
+    assert_eq!(client.get_remaining_supply_capacity(&asset), 2_500);
+
+    // Caps are per asset
+    assert_eq!(client.get_remaining_supply_capacity(&None), i128::MAX);
+}
+
+#[test]
+fn test_lowering_supply_cap_blocks_new_deposits_only() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    let user = Address::generate(&env);
+    fund(&env, &contract_id, &token, &user, 10_000);
+
+    client.deposit_collateral(&user, &asset, &3_000);
+    client.set_supply_cap(&admin, &asset, &2_000);
+    assert_eq!(client.get_remaining_supply_capacity(&asset), 0);
+    assert!(client.try_deposit_collateral(&user, &asset, &1).is_err());
+
+    // Existing deposits stay withdrawable
+    client.withdraw_collateral(&user, &asset, &1_500);
+    assert_eq!(client.get_remaining_supply_capacity(&asset), 500);
+
+    // Removing the cap makes the asset unlimited again
+    client.set_supply_cap(&admin, &asset, &0);
+    client.deposit_collateral(&user, &asset, &7_000);
+    assert_eq!(client.get_remaining_supply_capacity(&asset), i128::MAX);
+}
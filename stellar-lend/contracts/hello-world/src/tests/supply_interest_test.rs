@@ -0,0 +1,72 @@
+#![cfg(test)]
+
+use crate::interest_index::INDEX_SCALE;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+fn setup(env: &Env) -> HelloContractClient<'_> {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+    client
+}
+
+#[test]
+fn test_depositors_earn_borrower_interest() {
+    let env = Env::default();
+    let client = setup(&env);
+    let lender = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    client.deposit_collateral(&lender, &None, &1_000_000);
+    client.deposit_collateral(&borrower, &None, &1_000_000);
+    client.borrow_asset(&borrower, &None, &500_000);
+
+    // 25% utilization: 7.25% borrow rate, 5.25% supply rate on a quarter of deposits
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    assert_eq!(
+        client.get_supply_index(),
+        INDEX_SCALE + INDEX_SCALE * 131 / 10_000
+    );
+    assert_eq!(client.get_user_supply_balance(&lender), 1_013_100);
+
+    // Depositors are paid out of, and never more than, borrower interest
+    client.borrow_asset(&borrower, &None, &1);
+    let borrower_interest = client.get_user_report(&borrower).position.borrow_interest;
+    assert!(2 * 13_100 <= borrower_interest);
+
+    // Interest is credited on the next interaction and can be withdrawn
+    assert_eq!(client.withdraw_all(&lender, &None), 1_013_100);
+    assert_eq!(client.get_user_supply_balance(&lender), 0);
+}
+
+#[test]
+fn test_supply_interest_settles_on_deposit() {
+    let env = Env::default();
+    let client = setup(&env);
+    let user = Address::generate(&env);
+
+    // Nothing is earned while nothing is borrowed
+    client.deposit_collateral(&user, &None, &1_000_000);
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    assert_eq!(client.get_supply_index(), INDEX_SCALE);
+    assert_eq!(client.get_user_supply_balance(&user), 1_000_000);
+
+    client.borrow_asset(&user, &None, &250_000);
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    let earned = client.get_user_supply_balance(&user) - 1_000_000;
+    assert!(earned > 0);
+
+    // A deposit settles the pending interest into the collateral
+    assert_eq!(
+        client.deposit_collateral(&user, &None, &1_000),
+        1_001_000 + earned
+    );
+    assert_eq!(client.get_user_supply_balance(&user), 1_001_000 + earned);
+}
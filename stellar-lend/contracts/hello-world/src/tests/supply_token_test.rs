@@ -0,0 +1,187 @@
+#![cfg(test)]
+
+use crate::cross_asset::AssetConfig;
+use crate::supply_token::{supply_token_transfer, SupplyTokenError, EXCHANGE_RATE_SCALE};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, Address, Env, String};
+use stellarlend_stoken::{SupplyToken, SupplyTokenClient};
+
+const PRICE_ONE: i128 = 10_000_000;
+
+fn asset_config(env: &Env, asset: Option<Address>, price: i128) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+/// Native reserve priced at 1.0, a token reserve priced at 2.0 with a
+/// registered supply token, and alice supplying 1_000 of the token
+fn setup(
+    env: &Env,
+) -> (
+    Address,
+    HelloContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+
+    let asset = Address::generate(env);
+    client.initialize_asset(&None, &asset_config(env, None, PRICE_ONE));
+    client.initialize_asset(
+        &Some(asset.clone()),
+        &asset_config(env, Some(asset.clone()), 2 * PRICE_ONE),
+    );
+
+    let stoken = env.register(SupplyToken, ());
+    SupplyTokenClient::new(env, &stoken).initialize(
+        &contract_id,
+        &Some(asset.clone()),
+        &7,
+        &String::from_str(env, "StellarLend sToken"),
+        &String::from_str(env, "sTKN"),
+    );
+    client.set_supply_token(&admin, &Some(asset.clone()), &stoken);
+
+    let alice = Address::generate(env);
+    client.cross_asset_deposit(&alice, &Some(asset.clone()), &1_000);
+    (contract_id, client, admin, asset, stoken, alice)
+}
+
+#[test]
+fn test_supply_token_moves_supplied_balance() {
+    let env = Env::default();
+    let (_contract_id, client, _admin, asset, stoken, alice) = setup(&env);
+    let asset = Some(asset);
+    let token = TokenClient::new(&env, &stoken);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    assert_eq!(client.get_supply_token(&asset), Some(stoken.clone()));
+    assert_eq!(token.symbol(), String::from_str(&env, "sTKN"));
+    assert_eq!(token.balance(&alice), 1_000);
+
+    token.transfer(&alice, &bob, &400);
+    assert_eq!(token.balance(&alice), 600);
+    assert_eq!(token.balance(&bob), 400);
+    assert_eq!(client.get_user_asset_position(&bob, &asset).collateral, 400);
+
+    // Allowances are spent by transfer_from
+    token.approve(&bob, &carol, &150, &(env.ledger().sequence() + 100));
+    token.transfer_from(&carol, &bob, &carol, &100);
+    assert_eq!(token.allowance(&bob, &carol), 50);
+    assert_eq!(token.balance(&carol), 100);
+    assert!(token.try_transfer_from(&carol, &bob, &carol, &51).is_err());
+
+    // The received supply can be withdrawn like any other
+    client.cross_asset_withdraw(&carol, &asset, &100);
+    assert_eq!(token.balance(&carol), 0);
+
+    // Supply leaves the pool by withdrawing, never by burning
+    assert!(token.try_burn(&alice, &1).is_err());
+    assert!(token.try_transfer(&alice, &bob, &601).is_err());
+    assert_eq!(token.balance(&alice), 600);
+}
+
+#[test]
+fn test_supply_token_transfer_revalidates_sender_health() {
+    let env = Env::default();
+    let (contract_id, client, _admin, asset, stoken, alice) = setup(&env);
+    let token = TokenClient::new(&env, &stoken);
+    let bob = Address::generate(&env);
+
+    // 1_000 native owed against 2_000 of collateral at an 80% threshold
+    client.cross_asset_borrow(&alice, &None, &1_000);
+
+    // 500 left would back only 800
+    assert!(token.try_transfer(&alice, &bob, &500).is_err());
+    assert_eq!(token.balance(&alice), 1_000);
+    assert_eq!(token.balance(&bob), 0);
+
+    // 800 left still backs 1_280
+    token.transfer(&alice, &bob, &200);
+    assert_eq!(token.balance(&alice), 800);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            supply_token_transfer(&env, Some(asset.clone()), alice.clone(), bob.clone(), 500),
+            Err(SupplyTokenError::UnhealthyPosition)
+        );
+    });
+}
+
+#[test]
+fn test_set_supply_token_checks_binding() {
+    let env = Env::default();
+    let (_contract_id, client, admin, asset, stoken, _alice) = setup(&env);
+    let other_asset = Some(Address::generate(&env));
+
+    // Bound to a different asset
+    assert_eq!(
+        client.try_set_supply_token(&admin, &other_asset, &stoken),
+        Err(Ok(SupplyTokenError::InvalidToken))
+    );
+    assert_eq!(
+        client.try_set_supply_token(&Address::generate(&env), &Some(asset), &stoken),
+        Err(Ok(SupplyTokenError::Unauthorized))
+    );
+    assert_eq!(client.get_supply_token(&other_asset), None);
+    assert_eq!(
+        client.try_supply_token_transfer(&other_asset, &Address::generate(&env), &stoken, &1),
+        Err(Ok(SupplyTokenError::TokenNotSet))
+    );
+}
+
+#[test]
+fn test_redeem_withdraws_underlying_at_exchange_rate() {
+    let env = Env::default();
+    let (_contract_id, client, _admin, asset, stoken, alice) = setup(&env);
+    let asset = Some(asset);
+    let token = TokenClient::new(&env, &stoken);
+
+    assert_eq!(client.get_exchange_rate(&asset), EXCHANGE_RATE_SCALE);
+    assert_eq!(
+        client.try_get_exchange_rate(&None),
+        Err(Ok(SupplyTokenError::TokenNotSet))
+    );
+
+    assert_eq!(client.redeem(&alice, &asset, &300), 300);
+    assert_eq!(token.balance(&alice), 700);
+    assert_eq!(
+        client.get_user_asset_position(&alice, &asset).collateral,
+        700
+    );
+
+    assert_eq!(
+        client.try_redeem(&alice, &asset, &0),
+        Err(Ok(SupplyTokenError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_redeem(&alice, &asset, &701),
+        Err(Ok(SupplyTokenError::InsufficientBalance))
+    );
+
+    // 700 native owed against 1_400 of collateral at an 80% threshold
+    client.cross_asset_borrow(&alice, &None, &700);
+    assert_eq!(
+        client.try_redeem(&alice, &asset, &300),
+        Err(Ok(SupplyTokenError::UnhealthyPosition))
+    );
+    assert_eq!(token.balance(&alice), 700);
+}
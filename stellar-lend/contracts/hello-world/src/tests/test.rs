@@ -1,6 +1,14 @@
+#![cfg(test)]
+
+use crate::analytics::AnalyticsDataKey;
+use crate::deposit::{self, DepositDataKey, Position, ProtocolAnalytics, UserAnalytics};
+use crate::interest_rate::to_rate_view;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Symbol,
+};
 
-<<<<<<< test/fee-collection-tests
-=======
 /// Helper function to create a test environment
 fn create_test_env() -> Env {
     let env = Env::default();
@@ -53,6 +61,7 @@ fn set_asset_params(
         deposit_enabled,
         collateral_factor,
         max_deposit,
+        borrow_fee_bps: 0,
     };
     let key = DepositDataKey::AssetParams(asset.clone());
     env.storage().persistent().set(&key, &params);
@@ -409,7 +418,7 @@ fn test_deposit_collateral_activity_log() {
 // }
 
 #[test]
-#[should_panic(expected = "Deposit error")]
+#[should_panic(expected = "Error(Contract, #14)")]
 fn test_deposit_collateral_overflow_protection() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1076,7 +1085,7 @@ fn test_withdraw_collateral_success() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_withdraw_collateral_zero_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1092,7 +1101,7 @@ fn test_withdraw_collateral_zero_amount() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_withdraw_collateral_negative_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1108,7 +1117,7 @@ fn test_withdraw_collateral_negative_amount() {
 }
 
 #[test]
-#[should_panic(expected = "InsufficientCollateral")]
+#[should_panic(expected = "Error(Contract, #4)")]
 fn test_withdraw_collateral_insufficient_balance() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1294,7 +1303,7 @@ fn test_analytics_get_user_activity_feed() {
     client.deposit_collateral(&user2, &None, &200);
     client.deposit_collateral(&user1, &None, &300);
 
-    let user1_activities = client.get_user_activity(&user1, &10, &0);
+    let user1_activities = client.get_user_activity_feed(&user1, &10, &0);
     assert!(user1_activities.len() >= 2);
 }
 
@@ -1474,7 +1483,7 @@ fn test_withdraw_collateral_multiple_withdrawals() {
 }
 
 #[test]
-#[should_panic(expected = "WithdrawPaused")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_withdraw_collateral_pause_switch() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1574,7 +1583,7 @@ fn test_withdraw_collateral_with_debt_collateral_ratio() {
 }
 
 #[test]
-#[should_panic(expected = "InsufficientCollateralRatio")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_withdraw_collateral_violates_collateral_ratio() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1680,7 +1689,7 @@ fn test_repay_debt_success_full() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_repay_debt_zero_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1705,7 +1714,7 @@ fn test_repay_debt_zero_amount() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_repay_debt_negative_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1730,7 +1739,7 @@ fn test_repay_debt_negative_amount() {
 }
 
 #[test]
-#[should_panic(expected = "NoDebt")]
+#[should_panic(expected = "Error(Contract, #16)")]
 fn test_repay_debt_no_debt() {
     let (_env, _contract_id, client, _admin, user, _native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
@@ -1739,7 +1748,7 @@ fn test_repay_debt_no_debt() {
 }
 
 #[test]
-#[should_panic(expected = "RepayPaused")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_repay_debt_pause_switch() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -1984,7 +1993,7 @@ fn test_borrow_asset_success() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_borrow_asset_zero_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2000,7 +2009,7 @@ fn test_borrow_asset_zero_amount() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidAmount")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_borrow_asset_negative_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2016,7 +2025,7 @@ fn test_borrow_asset_negative_amount() {
 }
 
 #[test]
-#[should_panic(expected = "InsufficientCollateral")]
+#[should_panic(expected = "Error(Contract, #4)")]
 fn test_borrow_asset_no_collateral() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2029,7 +2038,7 @@ fn test_borrow_asset_no_collateral() {
 }
 
 #[test]
-#[should_panic(expected = "MaxBorrowExceeded")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_borrow_asset_exceeds_collateral_ratio() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2048,7 +2057,7 @@ fn test_borrow_asset_exceeds_collateral_ratio() {
 }
 
 #[test]
-#[should_panic(expected = "MaxBorrowExceeded")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_borrow_asset_max_borrow_exceeded() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2072,7 +2081,7 @@ fn test_borrow_asset_max_borrow_exceeded() {
 }
 
 #[test]
-#[should_panic(expected = "BorrowPaused")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_borrow_asset_pause_switch() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2392,8 +2401,8 @@ fn test_borrow_asset_repay_then_borrow_again() {
     let (env, contract_id, client, _admin, user, native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
     let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &native_asset);
-    token_client.mint(&user, &1500);
-    token_client.approve(&user, &contract_id, &1500, &(env.ledger().sequence() + 100));
+    token_client.mint(&user, &6500);
+    token_client.approve(&user, &contract_id, &6500, &(env.ledger().sequence() + 100));
 
     // Deposit
     client.deposit_collateral(&user, &None, &2000);
@@ -2416,6 +2425,38 @@ fn test_borrow_asset_repay_then_borrow_again() {
     assert!(position.debt > 0);
 }
 
+#[test]
+fn test_get_max_borrowable_matches_borrow_limit() {
+    let env = create_test_env();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    let user = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    let disabled_token = create_token_contract(&env, &admin);
+    env.as_contract(&contract_id, || {
+        set_asset_params(&env, &token, true, 7500, 0);
+        set_asset_params(&env, &disabled_token, false, 7500, 0);
+    });
+    assert_eq!(client.get_max_borrowable(&user, &None), 0);
+
+    client.deposit_collateral(&user, &None, &1100);
+
+    // 1100 collateral at the 110% minimum ratio, scaled by the collateral factor
+    assert_eq!(client.get_max_borrowable(&user, &None), 1000);
+    assert_eq!(client.get_max_borrowable(&user, &Some(token.clone())), 750);
+    assert_eq!(client.get_max_borrowable(&user, &Some(disabled_token)), 0);
+
+    assert!(client
+        .try_borrow_asset(&user, &Some(token.clone()), &751)
+        .is_err());
+    client.borrow_asset(&user, &Some(token.clone()), &750);
+    assert_eq!(client.get_max_borrowable(&user, &Some(token)), 0);
+    assert_eq!(client.get_max_borrowable(&user, &None), 250);
+}
+
 #[test]
 fn test_borrow_asset_multiple_users() {
     let env = create_test_env();
@@ -2492,7 +2533,7 @@ fn test_get_price_success() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidPrice")]
+#[should_panic(expected = "Error(Contract, #29)")]
 fn test_update_price_feed_zero_price() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2509,7 +2550,7 @@ fn test_update_price_feed_zero_price() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidPrice")]
+#[should_panic(expected = "Error(Contract, #29)")]
 fn test_update_price_feed_negative_price() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2526,7 +2567,7 @@ fn test_update_price_feed_negative_price() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #32)")]
 fn test_update_price_feed_unauthorized() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2602,7 +2643,7 @@ fn test_set_fallback_oracle() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #32)")]
 fn test_set_fallback_oracle_unauthorized() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2778,7 +2819,7 @@ fn test_execute_flash_loan_invalid_callback() {
 // }
 
 #[test]
-#[should_panic(expected = "NotRepaid")]
+#[should_panic(expected = "Error(Contract, #27)")]
 fn test_repay_flash_loan_no_active_loan() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2795,7 +2836,7 @@ fn test_repay_flash_loan_no_active_loan() {
 }
 
 #[test]
-#[should_panic(expected = "NotRepaid")]
+#[should_panic(expected = "Error(Contract, #27)")]
 fn test_repay_flash_loan_insufficient_amount() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -2838,7 +2879,7 @@ fn test_set_flash_loan_fee() {
 }
 
 #[test]
-#[should_panic(expected = "InvalidCallback")]
+#[should_panic(expected = "Error(Contract, #32)")]
 fn test_set_flash_loan_fee_unauthorized() {
     let env = create_test_env();
     let contract_id = env.register(HelloContract, ());
@@ -3623,7 +3664,7 @@ fn test_utilization_calculation_zero_deposits() {
     client.initialize(&admin);
 
     // With no deposits, utilization should be 0%
-    let utilization = client.get_utilization();
+    let utilization = client.get_utilization(&None);
     assert_eq!(utilization, 0);
 }
 
@@ -3642,7 +3683,7 @@ fn test_utilization_calculation_no_borrows() {
     client.deposit_collateral(&user, &None, &1000);
 
     // Utilization should be 0% (no borrows)
-    let utilization = client.get_utilization();
+    let utilization = client.get_utilization(&None);
     assert_eq!(utilization, 0);
 }
 
@@ -3661,8 +3702,8 @@ fn test_utilization_calculation_partial() {
     client.deposit_collateral(&user, &None, &1000);
     client.borrow_asset(&user, &None, &500);
 
-    let utilization = client.get_utilization();
-    assert_eq!(utilization, 5000); // 50% = 5000 basis points
+    let utilization = client.get_utilization(&None);
+    assert_eq!(utilization, to_rate_view(5000)); // 50% = 5000 basis points
 }
 
 #[test]
@@ -3680,8 +3721,8 @@ fn test_borrow_rate_at_zero_utilization() {
     client.deposit_collateral(&user, &None, &1000);
 
     // Rate should be base rate (default: 100 bps = 1%)
-    let rate = client.get_borrow_rate();
-    assert_eq!(rate, 100); // Base rate
+    let rate = client.get_borrow_rate(&None);
+    assert_eq!(rate, to_rate_view(100)); // Base rate
 }
 
 #[test]
@@ -3699,14 +3740,14 @@ fn test_borrow_rate_below_kink() {
     client.deposit_collateral(&user, &None, &10000);
     client.borrow_asset(&user, &None, &4000);
 
-    let utilization = client.get_utilization();
-    assert_eq!(utilization, 4000); // 40%
+    let utilization = client.get_utilization(&None);
+    assert_eq!(utilization, to_rate_view(4000)); // 40%
 
     // Rate should be: base_rate + (utilization / kink) * multiplier
     // = 100 + (4000 / 8000) * 2000 = 100 + 0.5 * 2000 = 100 + 1000 = 1100 bps
-    let rate = client.get_borrow_rate();
+    let rate = client.get_borrow_rate(&None);
     let expected_rate = 100 + (4000 * 2000 / 8000);
-    assert_eq!(rate, expected_rate);
+    assert_eq!(rate, to_rate_view(expected_rate));
 }
 
 #[test]
@@ -3751,14 +3792,15 @@ fn test_borrow_rate_at_kink() {
     client.deposit_collateral(&user, &None, &30000);
     client.borrow_asset(&user, &None, &20000); // Max borrow for 30000 collateral
 
-    let utilization = client.get_utilization();
+    let utilization = client.get_utilization(&None);
     // With 30000 deposits and 20000 borrows, utilization = 20000 * 10000 / 30000 = 6667 bps (66.67%)
     // This is below the 80% kink, so the rate calculation is different
     // Rate = base_rate + (utilization / kink) * multiplier
     // = 100 + (6667 / 8000) * 2000 = 100 + 1666.75 ≈ 1767
-    let rate = client.get_borrow_rate();
-    let expected_rate = 100 + (utilization * 2000 / 8000); // base_rate + (util/kink) * multiplier
-    assert_eq!(rate, expected_rate);
+    assert_eq!(utilization, to_rate_view(6666));
+    let rate = client.get_borrow_rate(&None);
+    let expected_rate = 100 + (6666 * 2000 / 8000); // base_rate + (util/kink) * multiplier
+    assert_eq!(rate, to_rate_view(expected_rate));
 }
 
 #[test]
@@ -3781,14 +3823,15 @@ fn test_borrow_rate_above_kink() {
     client.deposit_collateral(&user, &None, &30000);
     client.borrow_asset(&user, &None, &20000); // Max borrow for 30000 collateral
 
-    let utilization = client.get_utilization();
+    let utilization = client.get_utilization(&None);
     // With 30000 deposits and 20000 borrows, utilization = 20000 * 10000 / 30000 = 6667 bps (66.67%)
     // This is below the 80% kink, so the rate calculation is different
     // Rate = base_rate + (utilization / kink) * multiplier
     // = 100 + (6667 / 8000) * 2000 = 100 + 1666.75 ≈ 1767
-    let rate = client.get_borrow_rate();
-    let expected_rate = 100 + (utilization * 2000 / 8000); // base_rate + (util/kink) * multiplier
-    assert_eq!(rate, expected_rate);
+    assert_eq!(utilization, to_rate_view(6666));
+    let rate = client.get_borrow_rate(&None);
+    let expected_rate = 100 + (6666 * 2000 / 8000); // base_rate + (util/kink) * multiplier
+    assert_eq!(rate, to_rate_view(expected_rate));
 }
 
 #[test]
@@ -3806,12 +3849,12 @@ fn test_supply_rate_calculation() {
     client.deposit_collateral(&user, &None, &10000);
     client.borrow_asset(&user, &None, &5000);
 
-    let borrow_rate = client.get_borrow_rate();
-    let supply_rate = client.get_supply_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
+    let supply_rate = client.get_supply_rate(&None);
 
-    // Supply rate = borrow rate - spread (default spread = 200 bps)
-    assert_eq!(supply_rate, borrow_rate - 200);
-    assert!(supply_rate >= 50); // Should be at least floor (50 bps)
+    // Supply rate = (borrow rate - spread) * utilization (default spread = 200 bps)
+    assert_eq!(supply_rate, (borrow_rate - to_rate_view(200)) / 2);
+    assert!(supply_rate >= to_rate_view(50) / 2); // Floor (50 bps) on half the pool
 }
 
 #[test]
@@ -3838,8 +3881,8 @@ fn test_rate_floor_enforcement() {
     client.set_emergency_rate_adjustment(&admin, &(-100));
 
     // Rate should still be at least floor (50 bps)
-    let rate = client.get_borrow_rate();
-    assert!(rate >= 50);
+    let rate = client.get_borrow_rate(&None);
+    assert!(rate >= to_rate_view(50));
 }
 
 #[test]
@@ -3872,8 +3915,8 @@ fn test_rate_ceiling_enforcement() {
     client.borrow_asset(&user, &None, &20000); // Max borrow
 
     // Rate should be capped at ceiling (5000 bps = 50%)
-    let rate = client.get_borrow_rate();
-    assert!(rate <= 5000);
+    let rate = client.get_borrow_rate(&None);
+    assert!(rate <= to_rate_view(5000));
 }
 
 #[test]
@@ -3894,7 +3937,7 @@ fn test_emergency_rate_adjustment() {
     client.deposit_collateral(&user, &None, &20000);
     client.borrow_asset(&user, &None, &10000);
 
-    let rate_before = client.get_borrow_rate();
+    let rate_before = client.get_borrow_rate(&None);
     // With 50% utilization (below 80% kink):
     // rate = base_rate + (utilization / kink) * multiplier
     // rate = 100 + (5000 / 8000) * 2000 = 100 + 1250 = 1350
@@ -3903,18 +3946,18 @@ fn test_emergency_rate_adjustment() {
     // Apply emergency adjustment of +500 bps
     client.set_emergency_rate_adjustment(&admin, &500);
 
-    let rate_after = client.get_borrow_rate();
+    let rate_after = client.get_borrow_rate(&None);
     // Rate should increase by 500 (unless capped)
     // 1350 + 500 = 1850, which is below ceiling (5000), so should work
-    assert_eq!(rate_after, rate_before + 500);
+    assert_eq!(rate_after, rate_before + to_rate_view(500));
 
     // Apply negative adjustment (replaces the previous +500)
     client.set_emergency_rate_adjustment(&admin, &(-300));
 
-    let rate_final = client.get_borrow_rate();
+    let rate_final = client.get_borrow_rate(&None);
     // Emergency adjustment replaces the previous one, so:
     // rate_final = rate_before + (-300) = rate_before - 300
-    assert_eq!(rate_final, rate_before - 300);
+    assert_eq!(rate_final, rate_before - to_rate_view(300));
 }
 
 #[test]
@@ -3940,8 +3983,8 @@ fn test_update_interest_rate_config() {
     );
 
     // Verify rate changed
-    let rate = client.get_borrow_rate();
-    assert_eq!(rate, 200); // Should be new base rate
+    let rate = client.get_borrow_rate(&None);
+    assert_eq!(rate, to_rate_view(200)); // Should be new base rate
 }
 
 #[test]
@@ -3996,9 +4039,9 @@ fn test_update_kink_utilization() {
     client.deposit_collateral(&user, &None, &10000);
     client.borrow_asset(&user, &None, &5000);
 
-    let rate = client.get_borrow_rate();
+    let rate = client.get_borrow_rate(&None);
     // Should be at kink: base_rate + multiplier = 100 + 2000 = 2100
-    assert_eq!(rate, 100 + 2000);
+    assert_eq!(rate, to_rate_view(100 + 2000));
 }
 
 #[test]
@@ -4028,11 +4071,11 @@ fn test_update_multiplier() {
     client.deposit_collateral(&user, &None, &10000);
     client.borrow_asset(&user, &None, &4000);
 
-    let rate = client.get_borrow_rate();
+    let rate = client.get_borrow_rate(&None);
     // Should be: base_rate + (utilization / kink) * new_multiplier
     // = 100 + (4000 / 8000) * 3000 = 100 + 1500 = 1600
     let expected_rate = 100 + (4000 * 3000 / 8000);
-    assert_eq!(rate, expected_rate);
+    assert_eq!(rate, to_rate_view(expected_rate));
 }
 
 #[test]
@@ -4050,8 +4093,8 @@ fn test_update_spread() {
     client.deposit_collateral(&user, &None, &10000);
     client.borrow_asset(&user, &None, &5000);
 
-    let borrow_rate = client.get_borrow_rate();
-    let supply_rate_before = client.get_supply_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
+    let supply_rate_before = client.get_supply_rate(&None);
 
     // Update spread to 500 bps
     client.update_interest_rate_config(
@@ -4065,11 +4108,12 @@ fn test_update_spread() {
         &Some(500),
     );
 
-    let supply_rate_after = client.get_supply_rate();
+    let supply_rate_after = client.get_supply_rate(&None);
 
-    // Supply rate should decrease by 300 bps (500 - 200)
-    assert_eq!(supply_rate_after, supply_rate_before - 300);
-    assert_eq!(supply_rate_after, borrow_rate - 500);
+    // Widening the spread by 300 bps (500 - 200) costs depositors 150 bps at
+    // 50% utilization
+    assert_eq!(supply_rate_after, supply_rate_before - to_rate_view(150));
+    assert_eq!(supply_rate_after, (borrow_rate - to_rate_view(500)) / 2);
 }
 
 #[test]
@@ -4086,19 +4130,19 @@ fn test_rate_changes_with_utilization() {
     // Start with deposit only (0% utilization)
     // Use 20000 deposits to allow larger borrows
     client.deposit_collateral(&user, &None, &20000);
-    let rate1 = client.get_borrow_rate();
-    assert_eq!(rate1, 100); // Base rate
+    let rate1 = client.get_borrow_rate(&None);
+    assert_eq!(rate1, to_rate_view(100)); // Base rate
 
     // Borrow 8000 (40% utilization: 8000/20000)
     // With 20000 collateral, max borrow = 13333, so 8000 is fine
     client.borrow_asset(&user, &None, &8000);
-    let rate2 = client.get_borrow_rate();
+    let rate2 = client.get_borrow_rate(&None);
     assert!(rate2 > rate1); // Rate should increase
 
     // Borrow more to 13333 (66.67% utilization - max for 20000 collateral: 13333/20000)
     // With 20000 collateral, max borrow = 13333, so we can borrow 5333 more
     client.borrow_asset(&user, &None, &5333);
-    let rate3 = client.get_borrow_rate();
+    let rate3 = client.get_borrow_rate(&None);
     assert!(rate3 > rate2); // Rate should increase further
 
     // Can't borrow more as we're at max (13333 total borrows)
@@ -4695,8 +4739,8 @@ fn test_analytics_user_activity_feed_filtering() {
     client.deposit_collateral(&user2, &None, &400);
     client.deposit_collateral(&user2, &None, &500);
 
-    let user1_activities = client.get_user_activity(&user1, &10, &0);
-    let user2_activities = client.get_user_activity(&user2, &10, &0);
+    let user1_activities = client.get_user_activity_feed(&user1, &10, &0);
+    let user2_activities = client.get_user_activity_feed(&user2, &10, &0);
 
     // User 1 should have at least 3 activities
     assert!(user1_activities.len() >= 3);
@@ -5400,10 +5444,3 @@ fn test_monitoring_protocol_state_over_time() {
     assert_eq!(report_t200.metrics.total_value_locked, 2500);
 }
 
-/// Test monitoring risk level changes
->>>>>>> main
-#[test]
-fn test_placeholder() {
-    // Legacy helper file. 
-    // Actual tests are in specialized files like fees_test.rs.
-}
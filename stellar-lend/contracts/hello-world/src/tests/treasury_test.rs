@@ -0,0 +1,201 @@
+#![cfg(test)]
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::risk_management::RiskManagementError;
+use crate::treasury::TreasuryError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol, Vec,
+};
+use stellarlend_amm::{AmmProtocolConfig, SwapParams, TokenPair};
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+fn setup(env: &Env) -> (Address, Address, Address, HelloContractClient<'_>) {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (contract_id, admin, token, client)
+}
+
+#[test]
+fn test_interest_and_liquidation_revenue_are_tracked_by_source() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let asset = Some(token.clone());
+    let borrower = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&borrower, &2_000_000);
+    TokenClient::new(&env, &token).approve(&borrower, &contract_id, &2_000_000, &1_000);
+
+    client.deposit_collateral(&borrower, &asset, &1_000_000);
+    client.borrow_asset(&borrower, &asset, &100_000);
+    env.ledger().with_mut(|li| li.timestamp += YEAR);
+    client.repay_debt(&borrower, &asset, &i128::MAX);
+    let reserves = client.get_reserve_balance(&asset);
+    assert!(reserves > 0);
+
+    let breakdown = client.get_revenue_breakdown(&asset);
+    assert_eq!(breakdown.reserve_factor, reserves);
+    assert_eq!(breakdown.liquidation_fees, 0);
+    assert_eq!(breakdown.total, reserves);
+    assert_eq!(
+        client.get_revenue_assets(),
+        Vec::from_array(&env, [asset.clone()])
+    );
+
+    // The protocol's cut of a liquidation bonus is its own source
+    let victim = Address::generate(&env);
+    let liquidator = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&liquidator, &500);
+    TokenClient::new(&env, &token).approve(&liquidator, &contract_id, &500, &1_000);
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(victim.clone()),
+            &1_000i128,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(victim.clone()),
+            &Position {
+                collateral: 1_000,
+                debt: 1_000,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+    client.set_liquidation_protocol_fee(&admin, &asset, &2_000);
+    client.liquidate(&liquidator, &victim, &asset, &asset, &500);
+
+    let breakdown = client.get_revenue_breakdown(&asset);
+    assert_eq!(breakdown.liquidation_fees, 10);
+    assert_eq!(breakdown.total, reserves + 10);
+
+    // Treasury withdrawals count as disbursed, without touching revenue
+    client.set_treasury_address(&admin, &Address::generate(&env));
+    client.withdraw_reserves(&admin, &asset, &reserves);
+    let breakdown = client.get_revenue_breakdown(&asset);
+    assert_eq!(breakdown.disbursed, reserves);
+    assert_eq!(breakdown.total, reserves + 10);
+}
+
+#[test]
+fn test_amm_protocol_fee_and_disbursement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = HelloContractClient::new(&env, &env.register(HelloContract, ()));
+    let admin = Address::generate(&env);
+    // AMM initialization also sets the protocol admin
+    client.initialize_amm(&admin, &100, &1000, &10000);
+    let token_in = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_out = Address::generate(&env);
+    let protocol = Address::generate(&env);
+    let asset = Some(token_in.clone());
+    let user = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_in).mint(&user, &10_000);
+
+    let mut supported_pairs = Vec::new(&env);
+    supported_pairs.push_back(TokenPair {
+        token_a: asset.clone(),
+        token_b: Some(token_out.clone()),
+        pool_address: Address::generate(&env),
+    });
+    client.set_amm_pool(
+        &admin,
+        &AmmProtocolConfig {
+            protocol_address: protocol.clone(),
+            protocol_name: Symbol::new(&env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1000,
+            max_swap_amount: 1_000_000_000,
+            supported_pairs,
+        },
+    );
+
+    assert_eq!(
+        client.try_set_amm_protocol_fee(&admin, &1_001),
+        Err(Ok(TreasuryError::InvalidFee))
+    );
+    client.set_amm_protocol_fee(&admin, &100);
+    assert_eq!(client.get_amm_protocol_fee(), 100);
+
+    // 1% of the input goes to the reserve and the rest is swapped
+    let amount_out = client.amm_swap(
+        &user,
+        &SwapParams {
+            protocol,
+            token_in: asset.clone(),
+            token_out: Some(token_out),
+            amount_in: 10_000,
+            min_amount_out: 9_000,
+            slippage_tolerance: 100,
+            deadline: env.ledger().timestamp() + 3600,
+            use_oracle_floor: false,
+        },
+    );
+    assert_eq!(amount_out, 9_801);
+    assert_eq!(TokenClient::new(&env, &token_in).balance(&user), 9_900);
+    assert_eq!(client.get_reserve_balance(&asset), 100);
+    assert_eq!(client.get_revenue_breakdown(&asset).amm_fees, 100);
+
+    let recipient = Address::generate(&env);
+    assert_eq!(
+        client.try_disburse_revenue(&Address::generate(&env), &asset, &recipient, &60),
+        Err(Ok(TreasuryError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_disburse_revenue(&admin, &asset, &recipient, &101),
+        Err(Ok(TreasuryError::InsufficientReserve))
+    );
+    assert_eq!(client.disburse_revenue(&admin, &asset, &recipient, &60), 40);
+    assert_eq!(TokenClient::new(&env, &token_in).balance(&recipient), 60);
+
+    let breakdown = client.get_revenue_breakdown(&asset);
+    assert_eq!(breakdown.amm_fees, 100);
+    assert_eq!(breakdown.total, 100);
+    assert_eq!(breakdown.disbursed, 60);
+}
+
+#[test]
+fn test_native_reserves_are_paid_out_in_xlm() {
+    let env = Env::default();
+    let (contract_id, admin, token, client) = setup(&env);
+    let recipient = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::ProtocolReserve(None), &500i128);
+    });
+
+    // Without a native token address nothing can be transferred
+    assert_eq!(
+        client.try_claim_reserves(&admin, &None, &recipient, &100),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+    assert_eq!(
+        client.try_disburse_revenue(&admin, &None, &recipient, &100),
+        Err(Ok(TreasuryError::InvalidAsset))
+    );
+    assert_eq!(client.get_reserve_balance(&None), 500);
+
+    client.set_native_asset_address(&admin, &token);
+    StellarAssetClient::new(&env, &token).mint(&contract_id, &500);
+
+    client.claim_reserves(&admin, &None, &recipient, &200);
+    assert_eq!(
+        client.disburse_revenue(&admin, &None, &recipient, &100),
+        200
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&recipient), 300);
+    assert_eq!(TokenClient::new(&env, &token).balance(&contract_id), 200);
+    assert_eq!(client.get_revenue_breakdown(&None).disbursed, 300);
+}
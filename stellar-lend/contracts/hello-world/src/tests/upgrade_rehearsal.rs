@@ -0,0 +1,136 @@
+//! Upgrade rehearsal harness. Loads a state fixture into a fresh `Env`, swaps
+//! the contract's code for the current build, runs the migration path and
+//! asserts post-migration invariants, so an upgrade can be rehearsed against
+//! real state before it reaches mainnet.
+//!
+//! Fixtures are ledger snapshots:
+//! - from a live deployment, `stellar snapshot create --address <contract>
+//!   --output json --out fixture.json`, loaded with `load_ledger_fixture_file`;
+//! - from a test deployment, `capture_fixture`, serialized with
+//!   `Snapshot::write` and loaded with `load_fixture`.
+//!
+//! Host values are bound to their `Env`, so addresses cross from the
+//! capturing `Env` to the fixture `Env` as strkeys (see `strkey`).
+
+extern crate std;
+
+use crate::config_export::ProtocolConfigExport;
+use crate::cross_asset::AssetKey;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Snapshot, Address, Env, Map};
+use std::string::String as StdString;
+use std::vec::Vec as StdVec;
+
+/// A fixture loaded and upgraded to the current build
+pub struct Rehearsal {
+    pub env: Env,
+    pub contract_id: Address,
+    /// Protocol configuration as stored before the migration ran
+    pub pre_upgrade: ProtocolConfigExport,
+}
+
+impl Rehearsal {
+    pub fn client(&self) -> HelloContractClient<'_> {
+        HelloContractClient::new(&self.env, &self.contract_id)
+    }
+}
+
+/// Capture the state of a test deployment as a serialized fixture
+pub fn capture_fixture(env: &Env) -> StdVec<u8> {
+    let mut fixture = StdVec::new();
+    env.to_snapshot().write(&mut fixture).unwrap();
+    fixture
+}
+
+/// Encode an address as a strkey, to rebuild it in another `Env`
+pub fn strkey(address: &Address) -> StdString {
+    let strkey = address.to_string();
+    let mut buf = StdVec::from([0u8; 56]);
+    strkey.copy_into_slice(&mut buf);
+    StdString::from_utf8(buf).unwrap()
+}
+
+/// Load a serialized fixture captured with `capture_fixture`
+pub fn load_fixture(fixture: &[u8]) -> Env {
+    Env::from_snapshot(Snapshot::read(fixture).unwrap())
+}
+
+/// Load a ledger snapshot file captured from a live deployment
+pub fn load_ledger_fixture_file(path: &str) -> Env {
+    Env::from_ledger_snapshot_file(path)
+}
+
+/// Rehearse an upgrade of the contract at strkey `contract_id` in a fixture `Env`
+///
+/// Installs the current build at `contract_id` (standing in for
+/// `update_current_contract_wasm`), records the configuration it reads from
+/// the fixture, then runs `migrate` against it.
+pub fn rehearse_upgrade<F>(env: Env, contract_id: &str, migrate: F) -> Rehearsal
+where
+    F: FnOnce(&HelloContractClient),
+{
+    env.mock_all_auths();
+    let contract_id = Address::from_str(&env, contract_id);
+    env.register_at(&contract_id, HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let pre_upgrade = client
+        .try_export_config()
+        .expect("fixture has no protocol configuration")
+        .unwrap();
+
+    migrate(&client);
+
+    Rehearsal {
+        env,
+        contract_id,
+        pre_upgrade,
+    }
+}
+
+/// Assert the migration left the protocol configuration untouched
+pub fn assert_config_preserved(rehearsal: &Rehearsal) {
+    assert_eq!(
+        rehearsal.client().export_config(),
+        rehearsal.pre_upgrade,
+        "configuration changed"
+    );
+}
+
+/// Assert the invariants every upgraded deployment must hold
+///
+/// - the admin is unchanged;
+/// - every reserve config and the risk and rate models still validate;
+/// - per reserve, total supply equals the sum of user collateral, and
+///   totals and positions are non-negative.
+pub fn assert_post_migration_invariants(rehearsal: &Rehearsal) {
+    let env = &rehearsal.env;
+    let config = rehearsal.client().export_config();
+    assert_eq!(config.admin, rehearsal.pre_upgrade.admin, "admin changed");
+
+    assert!(crate::risk_params::validate_risk_params(&config.risk_params).is_ok());
+    assert!(
+        crate::interest_rate::validate_interest_rate_config(&config.interest_rate_config).is_ok()
+    );
+
+    env.as_contract(&rehearsal.contract_id, || {
+        let mut collateral: Map<AssetKey, i128> = Map::new(env);
+        for (key, position) in crate::cross_asset::get_user_asset_positions(env).iter() {
+            assert!(position.collateral >= 0, "negative collateral");
+            assert!(position.debt_principal >= 0, "negative debt");
+            let total = collateral.get(key.asset.clone()).unwrap_or(0);
+            collateral.set(key.asset, total + position.collateral);
+        }
+
+        for reserve in config.reserves.iter() {
+            assert!(crate::cross_asset::require_valid_config(&reserve).is_ok());
+            let asset_key = AssetKey::from_option(reserve.asset.clone());
+            let total_supply = crate::cross_asset::get_total_supply(env, &asset_key);
+            assert!(crate::cross_asset::get_total_borrow(env, &asset_key) >= 0);
+            assert_eq!(
+                total_supply,
+                collateral.get(asset_key).unwrap_or(0),
+                "total supply out of sync with positions"
+            );
+        }
+    });
+}
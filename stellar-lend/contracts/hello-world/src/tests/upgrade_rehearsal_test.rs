@@ -0,0 +1,145 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::cross_asset::{AssetConfig, AssetKey};
+use crate::tests::upgrade_rehearsal::{
+    assert_config_preserved, assert_post_migration_invariants, capture_fixture, load_fixture,
+    load_ledger_fixture_file, rehearse_upgrade, strkey,
+};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Map, Symbol};
+use std::string::String;
+use std::vec::Vec;
+
+const PRICE_ONE: i128 = 10_000_000;
+
+fn asset_config(env: &Env, asset: Option<Address>) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7_500,
+        liquidation_threshold: 8_000,
+        reserve_factor: 1_000,
+        max_supply: 1_000_000,
+        max_borrow: 500_000,
+        can_collateralize: true,
+        can_borrow: true,
+        price: PRICE_ONE,
+        price_updated_at: env.ledger().timestamp(),
+    }
+}
+
+/// Deploy, configure and use a protocol, then capture it as a fixture
+///
+/// Returns the fixture and the strkeys of the contract, a borrower and the
+/// borrowed token.
+fn live_fixture() -> (Vec<u8>, String, String, String) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.initialize_ca(&admin);
+
+    let token = Address::generate(&env);
+    client.initialize_asset(&None, &asset_config(&env, None));
+    client.initialize_asset(
+        &Some(token.clone()),
+        &asset_config(&env, Some(token.clone())),
+    );
+    client.grant_role(
+        &admin,
+        &Symbol::new(&env, "keeper"),
+        &Address::generate(&env),
+    );
+
+    let user = Address::generate(&env);
+    client.cross_asset_deposit(&user, &None, &10_000);
+    client.cross_asset_borrow(&user, &Some(token.clone()), &4_000);
+
+    (
+        capture_fixture(&env),
+        strkey(&contract_id),
+        strkey(&user),
+        strkey(&token),
+    )
+}
+
+#[test]
+fn test_rehearse_upgrade_preserves_state() {
+    let (fixture, contract_id, user, token) = live_fixture();
+
+    let rehearsal = rehearse_upgrade(load_fixture(&fixture), &contract_id, |_| {});
+    assert_post_migration_invariants(&rehearsal);
+    assert_config_preserved(&rehearsal);
+    assert_eq!(rehearsal.pre_upgrade.reserves.len(), 2);
+    assert_eq!(rehearsal.pre_upgrade.roles.len(), 1);
+
+    // Positions survive the upgrade and keep working
+    let client = rehearsal.client();
+    let user = Address::from_str(&rehearsal.env, &user);
+    let token = Address::from_str(&rehearsal.env, &token);
+    let position = client.get_user_asset_position(&user, &Some(token.clone()));
+    assert_eq!(position.debt_principal, 4_000);
+    client.cross_asset_repay(&user, &Some(token.clone()), &1_000);
+    client.cross_asset_deposit(&user, &None, &5_000);
+    assert_post_migration_invariants(&rehearsal);
+}
+
+#[test]
+fn test_rehearse_upgrade_from_ledger_snapshot_file() {
+    let (fixture, contract_id, user, token) = live_fixture();
+
+    // Round-trip the fixture through the file format `stellar snapshot create`
+    // writes for live deployments
+    let path = std::env::temp_dir().join("stellarlend_upgrade_rehearsal_ledger.json");
+    let path = path.to_str().unwrap();
+    load_fixture(&fixture).to_ledger_snapshot_file(path);
+
+    let rehearsal = rehearse_upgrade(load_ledger_fixture_file(path), &contract_id, |_| {});
+    std::fs::remove_file(path).unwrap();
+    assert_post_migration_invariants(&rehearsal);
+    assert_config_preserved(&rehearsal);
+
+    let user = Address::from_str(&rehearsal.env, &user);
+    let token = Address::from_str(&rehearsal.env, &token);
+    let position = rehearsal
+        .client()
+        .get_user_asset_position(&user, &Some(token));
+    assert_eq!(position.debt_principal, 4_000);
+}
+
+#[test]
+fn test_rehearse_upgrade_runs_migration() {
+    let (fixture, contract_id, _, _) = live_fixture();
+
+    let rehearsal = rehearse_upgrade(load_fixture(&fixture), &contract_id, |client| {
+        let admin = client.export_config().admin.unwrap();
+        client.grant_role(
+            &admin,
+            &Symbol::new(&client.env, "guardian"),
+            &Address::generate(&client.env),
+        );
+    });
+    assert_post_migration_invariants(&rehearsal);
+    assert_eq!(rehearsal.client().export_config().roles.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "total supply out of sync with positions")]
+fn test_rehearsal_detects_broken_migration() {
+    let (fixture, contract_id, _, _) = live_fixture();
+
+    // A migration that resets the reserve totals but not the positions
+    let rehearsal = rehearse_upgrade(load_fixture(&fixture), &contract_id, |client| {
+        client.env.as_contract(&client.address, || {
+            let totals: Map<AssetKey, i128> = Map::new(&client.env);
+            client
+                .env
+                .storage()
+                .persistent()
+                .set(&symbol_short!("supplies"), &totals);
+        });
+    });
+    assert_post_migration_invariants(&rehearsal);
+}
@@ -0,0 +1,101 @@
+#![cfg(test)]
+
+use crate::admin::GUARDIAN_ROLE;
+use crate::param_timelock::PARAM_CHANGE_GRACE_PERIOD;
+use crate::upgrade::{UpgradeDataKey, UpgradeError, CONTRACT_VERSION};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Symbol,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn test_upgrade_waits_for_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = HelloContractClient::new(&env, &env.register(HelloContract, ()));
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let guardian = Address::generate(&env);
+    client.grant_role(&admin, &Symbol::new(&env, GUARDIAN_ROLE), &guardian);
+    client.set_param_timelock_delay(&admin, &DAY);
+
+    let hash = BytesN::from_array(&env, &[1; 32]);
+    let other = BytesN::from_array(&env, &[2; 32]);
+    assert_eq!(
+        client.try_upgrade(&admin, &hash),
+        Err(Ok(UpgradeError::UpgradeNotScheduled))
+    );
+    assert_eq!(
+        client.try_schedule_upgrade(&guardian, &hash),
+        Err(Ok(UpgradeError::Unauthorized))
+    );
+    let eta = client.schedule_upgrade(&admin, &hash);
+    assert_eq!(eta, env.ledger().timestamp() + DAY);
+    assert_eq!(client.get_scheduled_upgrade().unwrap().wasm_hash, hash);
+    assert_eq!(
+        client.try_schedule_upgrade(&admin, &other),
+        Err(Ok(UpgradeError::UpgradePending))
+    );
+
+    assert_eq!(
+        client.try_upgrade(&admin, &hash),
+        Err(Ok(UpgradeError::TimelockNotElapsed))
+    );
+    env.ledger().with_mut(|li| li.timestamp += DAY);
+    assert_eq!(
+        client.try_upgrade(&admin, &other),
+        Err(Ok(UpgradeError::HashMismatch))
+    );
+    assert_eq!(
+        client.try_upgrade(&guardian, &hash),
+        Err(Ok(UpgradeError::Unauthorized))
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp += PARAM_CHANGE_GRACE_PERIOD + 1);
+    assert_eq!(
+        client.try_upgrade(&admin, &hash),
+        Err(Ok(UpgradeError::UpgradeExpired))
+    );
+
+    // A guardian can cancel, which frees the slot for another upgrade
+    client.cancel_upgrade(&guardian);
+    assert!(client.get_scheduled_upgrade().is_none());
+    assert_eq!(
+        client.try_cancel_upgrade(&guardian),
+        Err(Ok(UpgradeError::UpgradeNotScheduled))
+    );
+    client.schedule_upgrade(&admin, &other);
+}
+
+#[test]
+fn test_version_and_storage_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(
+        client.try_migrate_storage(&admin),
+        Err(Ok(UpgradeError::AlreadyMigrated))
+    );
+
+    // A deployment from before version tracking
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .remove(&UpgradeDataKey::ContractVersion);
+    });
+    assert_eq!(client.version(), 0);
+    assert_eq!(
+        client.try_migrate_storage(&Address::generate(&env)),
+        Err(Ok(UpgradeError::Unauthorized))
+    );
+    assert_eq!(client.migrate_storage(&admin), CONTRACT_VERSION);
+    assert_eq!(client.version(), CONTRACT_VERSION);
+}
@@ -0,0 +1,61 @@
+#![cfg(test)]
+
+use crate::user_activity::MAX_USER_ACTIONS;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+fn setup(env: &Env) -> HelloContractClient<'_> {
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env));
+    client
+}
+
+#[test]
+fn test_user_activity_pages_newest_first() {
+    let env = Env::default();
+    let client = setup(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.deposit_collateral(&user, &None, &10_000);
+    client.deposit_collateral(&other, &None, &5_000);
+    client.borrow_asset(&user, &None, &1_000);
+    client.withdraw_collateral(&user, &None, &2_000);
+
+    let page = client.get_user_activity(&user, &None, &2);
+    assert_eq!(page.len(), 2);
+    let latest = page.get_unchecked(0);
+    assert_eq!(latest.id, 2);
+    assert_eq!(latest.action, Symbol::new(&env, "withdraw"));
+    assert_eq!(latest.amount, 2_000);
+    assert_eq!(latest.asset, None);
+    assert_eq!(page.get_unchecked(1).action, Symbol::new(&env, "borrow"));
+
+    // The next page continues below the last id
+    let cursor = page.get_unchecked(1).id;
+    let page = client.get_user_activity(&user, &Some(cursor), &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get_unchecked(0).id, 0);
+    assert_eq!(page.get_unchecked(0).action, Symbol::new(&env, "deposit"));
+    assert_eq!(client.get_user_activity(&user, &Some(0), &2).len(), 0);
+
+    assert_eq!(client.get_user_activity(&other, &None, &10).len(), 1);
+}
+
+#[test]
+fn test_user_activity_keeps_the_latest_actions() {
+    let env = Env::default();
+    let client = setup(&env);
+    let user = Address::generate(&env);
+
+    for _ in 0..MAX_USER_ACTIONS + 5 {
+        client.deposit_collateral(&user, &None, &100);
+    }
+
+    let page = client.get_user_activity(&user, &None, &100);
+    assert_eq!(page.len(), MAX_USER_ACTIONS);
+    assert_eq!(page.get_unchecked(0).id, (MAX_USER_ACTIONS + 4) as u64);
+    assert_eq!(page.last().unwrap().id, 5);
+}
@@ -5,6 +5,7 @@
 //! and edge cases (no debt, boundary health, risk getters).
 
 use crate::deposit::{DepositDataKey, Position, ProtocolAnalytics};
+use crate::interest_rate::to_rate_view;
 use crate::{HelloContract, HelloContractClient};
 use soroban_sdk::{testutils::Address as _, Address, Env};
 
@@ -97,11 +98,11 @@ fn test_get_user_report_after_repay() {
     let (env, contract_id, client, _admin, user, native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
     let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &native_asset);
-    token_client.mint(&user, &2500);
+    token_client.mint(&user, &5500);
+    token_client.approve(&user, &contract_id, &5500, &(env.ledger().sequence() + 100));
 
     client.deposit_collateral(&user, &None, &5000);
     client.borrow_asset(&user, &None, &2000);
-    token_client.approve(&user, &contract_id, &500, &(env.ledger().sequence() + 100));
     client.repay_debt(&user, &None, &500);
 
     let report = client.get_user_report(&user);
@@ -219,8 +220,8 @@ fn test_get_utilization_view() {
         env.storage().persistent().set(&key, &a);
     });
 
-    let util = client.get_utilization();
-    assert_eq!(util, 3000);
+    let util = client.get_utilization(&None);
+    assert_eq!(util, to_rate_view(3000));
 }
 
 #[test]
@@ -228,8 +229,8 @@ fn test_get_borrow_rate_and_supply_rate() {
     let env = create_test_env();
     let (_contract_id, _admin, client) = setup_contract_with_admin(&env);
 
-    let borrow_rate = client.get_borrow_rate();
-    let supply_rate = client.get_supply_rate();
+    let borrow_rate = client.get_borrow_rate(&None);
+    let supply_rate = client.get_supply_rate(&None);
     assert!(borrow_rate >= 0);
     assert!(supply_rate >= 0);
     assert!(supply_rate <= borrow_rate);
@@ -253,12 +254,17 @@ fn test_position_consistency_after_multiple_ops() {
     let (env, contract_id, client, _admin, user, native_asset) =
         crate::tests::test_helpers::setup_env_with_native_asset();
     let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &native_asset);
-    token_client.mint(&user, &2500);
+    token_client.mint(&user, &11_500);
+    token_client.approve(
+        &user,
+        &contract_id,
+        &11_500,
+        &(env.ledger().sequence() + 100),
+    );
 
     client.deposit_collateral(&user, &None, &10000);
     client.borrow_asset(&user, &None, &2000);
     client.deposit_collateral(&user, &None, &1000);
-    token_client.approve(&user, &contract_id, &500, &(env.ledger().sequence() + 100));
     client.repay_debt(&user, &None, &500);
 
     let report = client.get_user_report(&user);
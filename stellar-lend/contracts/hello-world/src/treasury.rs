@@ -0,0 +1,302 @@
+//! # Treasury Module
+//!
+//! Accounts for protocol revenue per asset and per source, on top of the
+//! per-asset protocol reserve (see `reserve`) that holds it.
+//!
+//! ## Revenue Sources
+//! - `ReserveFactor` — the reserve factor's share of repaid interest
+//! - `BorrowFee` — origination fees charged on borrows
+//! - `FlashLoanFee` — flash loan fees
+//! - `LiquidationFee` — the protocol's cut of liquidation bonuses
+//! - `AmmFee` — the protocol fee taken from swaps routed through the AMM
+//!   integration, set with `set_amm_protocol_fee`
+//!
+//! Every credit to a protocol reserve from one of these sources is recorded
+//! here as it happens, so the breakdown is cumulative and never decreases.
+//!
+//! ## Disbursement
+//! The admin pays revenue out of an asset's reserve with `disburse_revenue`.
+//! Reserve withdrawals to the treasury, reserve claims and safety module
+//! yield are counted as disbursed as well.
+//!
+//! ## Invariants
+//! - Disbursements never exceed the asset's protocol reserve.
+//! - The AMM protocol fee is at most `MAX_AMM_PROTOCOL_FEE_BPS`.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::deposit::DepositDataKey;
+use crate::events::{
+    emit_amm_protocol_fee_updated, emit_revenue_disbursed, AmmProtocolFeeUpdatedEvent,
+    RevenueDisbursedEvent,
+};
+use crate::reserve::BASIS_POINTS_SCALE;
+use stellarlend_amm::SwapParams;
+
+/// Highest AMM protocol fee (10%, in basis points)
+pub const MAX_AMM_PROTOCOL_FEE_BPS: i128 = 1_000;
+
+/// Errors that can occur during treasury operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TreasuryError {
+    /// Caller is not authorized (not admin)
+    Unauthorized = 1,
+    /// Amount must be greater than zero
+    InvalidAmount = 2,
+    /// Fee is outside `0..=MAX_AMM_PROTOCOL_FEE_BPS`
+    InvalidFee = 3,
+    /// Protocol reserve is too small to disburse the amount
+    InsufficientReserve = 4,
+    /// Native XLM has no configured token address
+    InvalidAsset = 5,
+}
+
+/// Where a piece of protocol revenue came from
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RevenueSource {
+    ReserveFactor,
+    BorrowFee,
+    FlashLoanFee,
+    LiquidationFee,
+    AmmFee,
+}
+
+/// Cumulative revenue of an asset by source
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RevenueBreakdown {
+    /// Reserve factor share of repaid interest
+    pub reserve_factor: i128,
+    /// Borrow origination fees
+    pub borrow_fees: i128,
+    /// Flash loan fees
+    pub flash_loan_fees: i128,
+    /// Protocol cut of liquidation bonuses
+    pub liquidation_fees: i128,
+    /// AMM protocol fees
+    pub amm_fees: i128,
+    /// Sum of all sources
+    pub total: i128,
+    /// Revenue paid out of the reserve
+    pub disbursed: i128,
+}
+
+/// Storage keys for treasury data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum TreasuryDataKey {
+    /// Cumulative revenue per asset: RevenueBreakdown
+    AssetRevenue(Option<Address>),
+    /// Assets that have ever earned revenue: Vec<Option<Address>>
+    RevenueAssets,
+    /// Protocol fee on AMM swaps, in basis points: i128
+    AmmProtocolFee,
+}
+
+/// Get the cumulative revenue of an asset by source
+pub fn get_revenue_breakdown(env: &Env, asset: &Option<Address>) -> RevenueBreakdown {
+    env.storage()
+        .persistent()
+        .get::<TreasuryDataKey, RevenueBreakdown>(&TreasuryDataKey::AssetRevenue(asset.clone()))
+        .unwrap_or_default()
+}
+
+/// Get the assets that have earned revenue
+pub fn get_revenue_assets(env: &Env) -> Vec<Option<Address>> {
+    env.storage()
+        .persistent()
+        .get::<TreasuryDataKey, Vec<Option<Address>>>(&TreasuryDataKey::RevenueAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_revenue_breakdown(env: &Env, asset: &Option<Address>, breakdown: &RevenueBreakdown) {
+    env.storage()
+        .persistent()
+        .set(&TreasuryDataKey::AssetRevenue(asset.clone()), breakdown);
+}
+
+/// Record revenue credited to an asset's protocol reserve
+pub(crate) fn record_revenue(
+    env: &Env,
+    asset: &Option<Address>,
+    source: RevenueSource,
+    amount: i128,
+) {
+    if amount <= 0 {
+        return;
+    }
+    let mut breakdown = get_revenue_breakdown(env, asset);
+    let bucket = match source {
+        RevenueSource::ReserveFactor => &mut breakdown.reserve_factor,
+        RevenueSource::BorrowFee => &mut breakdown.borrow_fees,
+        RevenueSource::FlashLoanFee => &mut breakdown.flash_loan_fees,
+        RevenueSource::LiquidationFee => &mut breakdown.liquidation_fees,
+        RevenueSource::AmmFee => &mut breakdown.amm_fees,
+    };
+    *bucket = bucket.saturating_add(amount);
+    breakdown.total = breakdown.total.saturating_add(amount);
+    set_revenue_breakdown(env, asset, &breakdown);
+
+    let mut assets = get_revenue_assets(env);
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        env.storage()
+            .persistent()
+            .set(&TreasuryDataKey::RevenueAssets, &assets);
+    }
+}
+
+/// Record revenue paid out of an asset's protocol reserve
+pub(crate) fn record_disbursement(env: &Env, asset: &Option<Address>, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let mut breakdown = get_revenue_breakdown(env, asset);
+    breakdown.disbursed = breakdown.disbursed.saturating_add(amount);
+    set_revenue_breakdown(env, asset, &breakdown);
+}
+
+/// Pay revenue out of an asset's protocol reserve (admin only)
+///
+/// # Arguments
+/// * `caller` - The caller address (must be admin)
+/// * `asset` - The reserve asset (None = native)
+/// * `to` - The recipient
+/// * `amount` - The amount to pay
+///
+/// # Returns
+/// Returns the asset's remaining protocol reserve
+///
+/// # Errors
+/// * `TreasuryError::Unauthorized` - If caller is not admin
+/// * `TreasuryError::InvalidAmount` - If amount is not positive
+/// * `TreasuryError::InsufficientReserve` - If the reserve is too small
+/// * `TreasuryError::InvalidAsset` - If the asset is native XLM and no
+///   native asset address is configured
+pub fn disburse_revenue(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    to: Address,
+    amount: i128,
+) -> Result<i128, TreasuryError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| TreasuryError::Unauthorized)?;
+    if amount <= 0 {
+        return Err(TreasuryError::InvalidAmount);
+    }
+    let reserve = crate::reserve::get_reserve_balance(env, asset.clone());
+    if amount > reserve {
+        return Err(TreasuryError::InsufficientReserve);
+    }
+
+    let token_addr =
+        crate::deposit::resolve_token_address(env, &asset).ok_or(TreasuryError::InvalidAsset)?;
+
+    let remaining = reserve - amount;
+    env.storage()
+        .persistent()
+        .set(&DepositDataKey::ProtocolReserve(asset.clone()), &remaining);
+    record_disbursement(env, &asset, amount);
+
+    soroban_sdk::token::Client::new(env, &token_addr).transfer(
+        &env.current_contract_address(),
+        &to,
+        &amount,
+    );
+
+    emit_revenue_disbursed(
+        env,
+        RevenueDisbursedEvent {
+            asset,
+            to,
+            amount,
+            remaining_reserve: remaining,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(remaining)
+}
+
+/// Get the protocol fee on AMM swaps, in basis points
+pub fn get_amm_protocol_fee(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<TreasuryDataKey, i128>(&TreasuryDataKey::AmmProtocolFee)
+        .unwrap_or(0)
+}
+
+/// Set the protocol fee on AMM swaps (admin only)
+///
+/// # Errors
+/// * `TreasuryError::Unauthorized` - If caller is not admin
+/// * `TreasuryError::InvalidFee` - If the fee is outside
+///   `0..=MAX_AMM_PROTOCOL_FEE_BPS`
+pub fn set_amm_protocol_fee(
+    env: &Env,
+    caller: Address,
+    fee_bps: i128,
+) -> Result<(), TreasuryError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| TreasuryError::Unauthorized)?;
+    if !(0..=MAX_AMM_PROTOCOL_FEE_BPS).contains(&fee_bps) {
+        return Err(TreasuryError::InvalidFee);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&TreasuryDataKey::AmmProtocolFee, &fee_bps);
+    emit_amm_protocol_fee_updated(
+        env,
+        AmmProtocolFeeUpdatedEvent {
+            admin: caller,
+            fee_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Protocol fee on a swap's input
+///
+/// Inputs without a token contract (native without a configured address)
+/// are not charged.
+pub(crate) fn amm_fee(env: &Env, params: &SwapParams) -> i128 {
+    let fee_bps = get_amm_protocol_fee(env);
+    if fee_bps == 0
+        || params.amount_in <= 0
+        || crate::deposit::resolve_token_address(env, &params.token_in).is_none()
+    {
+        return 0;
+    }
+    params.amount_in.saturating_mul(fee_bps) / BASIS_POINTS_SCALE
+}
+
+/// Collect a swap's protocol fee from the user (who must authorize) into the
+/// input token's protocol reserve
+pub(crate) fn collect_amm_fee(env: &Env, user: &Address, token_in: &Option<Address>, fee: i128) {
+    if fee <= 0 {
+        return;
+    }
+    let Some(token) = crate::deposit::resolve_token_address(env, token_in) else {
+        return;
+    };
+
+    user.require_auth();
+    soroban_sdk::token::Client::new(env, &token).transfer(
+        user,
+        env.current_contract_address(),
+        &fee,
+    );
+    let reserve = crate::reserve::get_reserve_balance(env, token_in.clone());
+    env.storage().persistent().set(
+        &DepositDataKey::ProtocolReserve(token_in.clone()),
+        &reserve.saturating_add(fee),
+    );
+    record_revenue(env, token_in, RevenueSource::AmmFee, fee);
+}
@@ -0,0 +1,267 @@
+//! # Contract Upgrade Module
+//!
+//! Replaces the contract's code in place through
+//! `env.deployer().update_current_contract_wasm`, behind the same delay as
+//! risk-parameter changes (see `param_timelock`).
+//!
+//! ## Lifecycle
+//! 1. The admin schedules the new code's hash with `schedule_upgrade`. It
+//!    becomes executable `get_param_timelock_delay` seconds later and stays
+//!    so for `PARAM_CHANGE_GRACE_PERIOD`.
+//! 2. The admin installs it with `upgrade(new_wasm_hash)`. The new code runs
+//!    from the next invocation on.
+//! 3. The admin runs `migrate_storage`, the post-upgrade hook that brings the
+//!    stored data to the layout the new code expects.
+//!
+//! The admin or a guardian can cancel a scheduled upgrade.
+//!
+//! ## Versioning
+//! `CONTRACT_VERSION` is the storage layout version of this build and
+//! `version()` the layout version of the stored data. `initialize` records
+//! the current version; deployments from before version tracking report 0.
+//! Each layout change bumps `CONTRACT_VERSION` and adds a step to
+//! `migrate_step`.
+//!
+//! ## Invariants
+//! - At most one upgrade is scheduled at a time.
+//! - Only the scheduled hash can be installed, and only inside its window.
+//! - The stored version never decreases, and each migration step runs once.
+
+#![allow(unused)]
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, Env};
+
+use crate::events::{
+    emit_contract_upgraded, emit_storage_migrated, emit_upgrade_cancelled, emit_upgrade_scheduled,
+    ContractUpgradedEvent, StorageMigratedEvent, UpgradeCancelledEvent, UpgradeScheduledEvent,
+};
+use crate::param_timelock::{get_param_timelock_delay, PARAM_CHANGE_GRACE_PERIOD};
+
+/// Storage layout version of this build
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Errors that can occur during contract upgrades
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum UpgradeError {
+    /// Caller lacks the required role
+    Unauthorized = 1,
+    /// An upgrade is already scheduled
+    UpgradePending = 2,
+    /// No upgrade is scheduled
+    UpgradeNotScheduled = 3,
+    /// The hash differs from the scheduled one
+    HashMismatch = 4,
+    /// The upgrade's ETA has not been reached
+    TimelockNotElapsed = 5,
+    /// The upgrade's grace period has passed
+    UpgradeExpired = 6,
+    /// The stored data already has the current layout
+    AlreadyMigrated = 7,
+}
+
+/// An upgrade waiting for its delay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledUpgrade {
+    pub wasm_hash: BytesN<32>,
+    pub proposer: Address,
+    pub scheduled_at: u64,
+    /// Earliest execution time
+    pub eta: u64,
+}
+
+/// Storage keys for upgrade data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum UpgradeDataKey {
+    /// Storage layout version of the stored data: u32
+    ContractVersion,
+    /// The scheduled upgrade: ScheduledUpgrade
+    ScheduledUpgrade,
+}
+
+/// Get the storage layout version of the stored data
+pub fn get_version(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<UpgradeDataKey, u32>(&UpgradeDataKey::ContractVersion)
+        .unwrap_or(0)
+}
+
+/// Record the current layout version on a fresh deployment
+pub(crate) fn initialize_version(env: &Env) {
+    env.storage()
+        .persistent()
+        .set(&UpgradeDataKey::ContractVersion, &CONTRACT_VERSION);
+}
+
+/// Get the scheduled upgrade
+pub fn get_scheduled_upgrade(env: &Env) -> Option<ScheduledUpgrade> {
+    env.storage()
+        .persistent()
+        .get::<UpgradeDataKey, ScheduledUpgrade>(&UpgradeDataKey::ScheduledUpgrade)
+}
+
+/// Schedule an upgrade to new code (admin only)
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `wasm_hash` - Hash of the uploaded code to install
+///
+/// # Returns
+/// Returns the earliest time the upgrade can be installed
+///
+/// # Errors
+/// * `UpgradeError::Unauthorized` - If caller is not the admin
+/// * `UpgradeError::UpgradePending` - If an upgrade is already scheduled
+pub fn schedule_upgrade(
+    env: &Env,
+    caller: Address,
+    wasm_hash: BytesN<32>,
+) -> Result<u64, UpgradeError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| UpgradeError::Unauthorized)?;
+    if get_scheduled_upgrade(env).is_some() {
+        return Err(UpgradeError::UpgradePending);
+    }
+
+    let now = env.ledger().timestamp();
+    let eta = now.saturating_add(get_param_timelock_delay(env));
+    let scheduled = ScheduledUpgrade {
+        wasm_hash: wasm_hash.clone(),
+        proposer: caller.clone(),
+        scheduled_at: now,
+        eta,
+    };
+    env.storage()
+        .persistent()
+        .set(&UpgradeDataKey::ScheduledUpgrade, &scheduled);
+
+    emit_upgrade_scheduled(
+        env,
+        UpgradeScheduledEvent {
+            wasm_hash,
+            proposer: caller,
+            eta,
+            timestamp: now,
+        },
+    );
+    Ok(eta)
+}
+
+/// Cancel the scheduled upgrade (admin or guardian)
+///
+/// # Errors
+/// * `UpgradeError::Unauthorized` - If caller is neither admin nor guardian
+/// * `UpgradeError::UpgradeNotScheduled` - If no upgrade is scheduled
+pub fn cancel_upgrade(env: &Env, caller: Address) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    crate::admin::require_guardian(env, &caller).map_err(|_| UpgradeError::Unauthorized)?;
+    let scheduled = get_scheduled_upgrade(env).ok_or(UpgradeError::UpgradeNotScheduled)?;
+    env.storage()
+        .persistent()
+        .remove(&UpgradeDataKey::ScheduledUpgrade);
+
+    emit_upgrade_cancelled(
+        env,
+        UpgradeCancelledEvent {
+            wasm_hash: scheduled.wasm_hash,
+            caller,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Install the scheduled code once its delay has elapsed (admin only)
+///
+/// The current invocation finishes on the old code; `migrate_storage` must
+/// be called afterwards if the new code changes the storage layout.
+///
+/// # Arguments
+/// * `caller` - The admin address
+/// * `new_wasm_hash` - Hash of the scheduled code
+///
+/// # Errors
+/// * `UpgradeError::Unauthorized` - If caller is not the admin
+/// * `UpgradeError::UpgradeNotScheduled` - If no upgrade is scheduled
+/// * `UpgradeError::HashMismatch` - If the hash is not the scheduled one
+/// * `UpgradeError::TimelockNotElapsed` - If the ETA has not been reached
+/// * `UpgradeError::UpgradeExpired` - If the grace period has passed
+pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), UpgradeError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| UpgradeError::Unauthorized)?;
+    let scheduled = get_scheduled_upgrade(env).ok_or(UpgradeError::UpgradeNotScheduled)?;
+    if scheduled.wasm_hash != new_wasm_hash {
+        return Err(UpgradeError::HashMismatch);
+    }
+
+    let now = env.ledger().timestamp();
+    if now < scheduled.eta {
+        return Err(UpgradeError::TimelockNotElapsed);
+    }
+    if now > scheduled.eta.saturating_add(PARAM_CHANGE_GRACE_PERIOD) {
+        return Err(UpgradeError::UpgradeExpired);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&UpgradeDataKey::ScheduledUpgrade);
+    emit_contract_upgraded(
+        env,
+        ContractUpgradedEvent {
+            wasm_hash: new_wasm_hash.clone(),
+            admin: caller,
+            from_version: get_version(env),
+            timestamp: now,
+        },
+    );
+    env.deployer().update_current_contract_wasm(new_wasm_hash);
+    Ok(())
+}
+
+/// Migrate the stored data from layout `from` to `from + 1`
+///
+/// Version 1 only starts tracking the version. Later layout changes add
+/// their step here, keyed by `from`.
+fn migrate_step(env: &Env, from: u32) {}
+
+/// Bring the stored data to the layout of this build (admin only)
+///
+/// Runs every migration step between the stored version and
+/// `CONTRACT_VERSION` in order.
+///
+/// # Returns
+/// Returns the new version
+///
+/// # Errors
+/// * `UpgradeError::Unauthorized` - If caller is not the admin
+/// * `UpgradeError::AlreadyMigrated` - If the data already has this layout
+pub fn migrate_storage(env: &Env, caller: Address) -> Result<u32, UpgradeError> {
+    caller.require_auth();
+    crate::admin::require_admin(env, &caller).map_err(|_| UpgradeError::Unauthorized)?;
+
+    let from_version = get_version(env);
+    if from_version >= CONTRACT_VERSION {
+        return Err(UpgradeError::AlreadyMigrated);
+    }
+    for version in from_version..CONTRACT_VERSION {
+        migrate_step(env, version);
+    }
+    env.storage()
+        .persistent()
+        .set(&UpgradeDataKey::ContractVersion, &CONTRACT_VERSION);
+
+    emit_storage_migrated(
+        env,
+        StorageMigratedEvent {
+            admin: caller,
+            from_version,
+            to_version: CONTRACT_VERSION,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(CONTRACT_VERSION)
+}
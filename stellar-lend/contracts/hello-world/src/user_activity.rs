@@ -0,0 +1,120 @@
+//! # User Activity Module
+//!
+//! Keeps a bounded, per-user history of core actions so wallets that cannot
+//! index `user_activity_tracked` events can still show a user's recent
+//! activity from a single read.
+//!
+//! ## Actions
+//! An action is recorded with every activity log entry of the core
+//! operations: deposit, withdraw, borrow, repay and liquidation (recorded
+//! for the borrower). Each action carries a per-user id that increases by
+//! one per action and is never reused.
+//!
+//! ## Pagination
+//! `get_user_activity` returns actions newest first. Passing no cursor
+//! starts at the most recent action; passing the id of the last action of a
+//! page continues with the actions before it. Ids stay valid as the buffer
+//! wraps, so a page is never shifted by actions recorded in between.
+//!
+//! ## Invariants
+//! - At most `MAX_USER_ACTIONS` actions are kept per user; the oldest is
+//!   dropped first.
+//! - Ids are strictly increasing within a user's history.
+
+#![allow(unused)]
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+/// Actions kept per user
+pub const MAX_USER_ACTIONS: u32 = 50;
+
+/// Maximum actions returned per activity page
+pub const MAX_ACTIVITY_PAGE: u32 = 50;
+
+/// Storage keys for user activity data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum UserActivityDataKey {
+    /// Recent actions of a user, oldest first: Vec<UserAction>
+    UserActivity(Address),
+}
+
+/// A core action of a user
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserAction {
+    /// Per-user sequence number of the action
+    pub id: u64,
+    /// Action type (e.g., "deposit", "withdraw", "borrow")
+    pub action: Symbol,
+    /// Asset address (None for native asset)
+    pub asset: Option<Address>,
+    /// Amount involved
+    pub amount: i128,
+    /// Ledger timestamp
+    pub timestamp: u64,
+}
+
+fn get_actions(env: &Env, user: &Address) -> Vec<UserAction> {
+    env.storage()
+        .persistent()
+        .get::<UserActivityDataKey, Vec<UserAction>>(&UserActivityDataKey::UserActivity(
+            user.clone(),
+        ))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Append an action to a user's history
+pub(crate) fn record_user_action(
+    env: &Env,
+    user: &Address,
+    action: Symbol,
+    asset: Option<Address>,
+    amount: i128,
+    timestamp: u64,
+) {
+    let mut actions = get_actions(env, user);
+    let id = actions.last().map(|last| last.id + 1).unwrap_or(0);
+    if actions.len() >= MAX_USER_ACTIONS {
+        actions.pop_front();
+    }
+    actions.push_back(UserAction {
+        id,
+        action,
+        asset,
+        amount,
+        timestamp,
+    });
+    env.storage()
+        .persistent()
+        .set(&UserActivityDataKey::UserActivity(user.clone()), &actions);
+}
+
+/// Get a page of a user's recent actions, newest first
+///
+/// # Arguments
+/// * `user` - The user address
+/// * `cursor` - Id of the last action of the previous page (None for the
+///   most recent actions)
+/// * `limit` - Maximum actions to return (capped at `MAX_ACTIVITY_PAGE`)
+///
+/// # Returns
+/// The actions with an id below `cursor`, newest first
+pub fn get_user_activity(
+    env: &Env,
+    user: &Address,
+    cursor: Option<u64>,
+    limit: u32,
+) -> Vec<UserAction> {
+    let limit = limit.min(MAX_ACTIVITY_PAGE);
+    let mut page = Vec::new(env);
+    for action in get_actions(env, user).iter().rev() {
+        if page.len() >= limit {
+            break;
+        }
+        if cursor.is_none_or(|cursor| action.id < cursor) {
+            page.push_back(action);
+        }
+    }
+    page
+}
@@ -1,3 +1,25 @@
+//! # Withdraw Module
+//!
+//! Handles collateral withdrawals for the lending protocol.
+//!
+//! Withdrawals are the mirror image of deposits: the collateral balance and
+//! position are reduced, the asset is transferred back to the user, and the
+//! same `withdraw`, `position_updated`, `analytics_updated` and
+//! `user_activity_tracked` events are emitted.
+//!
+//! ## Invariants
+//! - Withdraw amount must be strictly positive and cannot exceed the user's
+//!   collateral balance.
+//! - Interest is accrued on the user's debt before the withdrawal is checked,
+//!   so the resulting collateral ratio is measured against the current debt.
+//! - A withdrawal by a user with debt must leave the collateral ratio at or
+//!   above the minimum collateral ratio.
+//! - Withdrawals are rejected when the withdraw operation is paused.
+//! - Withdrawals to another address are authorized by the user and checked
+//!   against the user's position; only the receiver of the tokens changes.
+//! - Native XLM is paid out through the configured native asset contract;
+//!   withdrawing that contract's address is the same as withdrawing `None`.
+
 use soroban_sdk::{contracterror, Address, Env, Map, Symbol};
 
 use crate::deposit::{
@@ -5,7 +27,7 @@ use crate::deposit::{
     emit_user_activity_tracked_event, AssetParams, DepositDataKey, Position, ProtocolAnalytics,
     UserAnalytics,
 };
-use crate::events::{emit_withdrawal, WithdrawalEvent};
+use crate::events::{emit_withdraw_to, emit_withdrawal, WithdrawToEvent, WithdrawalEvent};
 
 /// Errors that can occur during withdraw operations
 #[contracterror]
@@ -28,11 +50,29 @@ pub enum WithdrawError {
     Reentrancy = 7,
     /// Position would become undercollateralized
     Undercollateralized = 8,
+    /// Recipient cannot receive the withdrawal
+    InvalidRecipient = 9,
+    /// Withdrawals are blocked for a few ledgers after a deposit
+    ActionDelayActive = 10,
 }
 
 // Minimum collateral ratio is now managed by the risk_params module
 // const MIN_COLLATERAL_RATIO_BPS: i128 = 15000; // 150% (Legacy)
 
+/// Accrue interest on a position
+///
+/// Books the interest accrued since the last accrual through the pool's
+/// borrow index (see `interest_index`).
+fn accrue_interest(
+    env: &Env,
+    user: &Address,
+    position: &mut Position,
+) -> Result<(), WithdrawError> {
+    crate::interest_index::accrue_position(env, user, position)
+        .map_err(|_| WithdrawError::Overflow)?;
+    Ok(())
+}
+
 /// Calculate collateral ratio
 /// Returns (collateral_value * collateral_factor) / (debt + interest)
 /// Returns None if debt is zero (infinite ratio)
@@ -56,21 +96,33 @@ fn calculate_collateral_ratio(
     collateral_value.checked_mul(10000)?.checked_div(total_debt)
 }
 
+/// Collateral factor of an asset in basis points
+/// Defaults to 100% for native XLM and for assets without parameters
+fn get_collateral_factor(env: &Env, asset: Option<&Address>) -> i128 {
+    if let Some(asset_addr) = asset {
+        let asset_params_key = DepositDataKey::AssetParams(asset_addr.clone());
+        if let Some(params) = env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, AssetParams>(&asset_params_key)
+        {
+            params.collateral_factor
+        } else {
+            10000 // Default 100% if not configured
+        }
+    } else {
+        10000 // Default 100% for native XLM
+    }
+}
+
 /// Check if withdrawal would violate minimum collateral ratio
 fn validate_collateral_ratio_after_withdraw(
     env: &Env,
     user: &Address,
+    position: &Position,
     withdraw_amount: i128,
     asset: Option<&Address>,
 ) -> Result<(), WithdrawError> {
-    // Get user position
-    let position_key = DepositDataKey::Position(user.clone());
-    let position = env
-        .storage()
-        .persistent()
-        .get::<DepositDataKey, Position>(&position_key)
-        .ok_or(WithdrawError::InsufficientCollateral)?;
-
     // If no debt, withdrawal is always allowed (as long as sufficient collateral)
     if position.debt == 0 && position.borrow_interest == 0 {
         return Ok(());
@@ -90,21 +142,7 @@ fn validate_collateral_ratio_after_withdraw(
         .ok_or(WithdrawError::InsufficientCollateral)?;
 
     // Get asset parameters for collateral factor
-    // Default collateral factor if asset params not found
-    let collateral_factor = if let Some(asset_addr) = asset {
-        let asset_params_key = DepositDataKey::AssetParams(asset_addr.clone());
-        if let Some(params) = env
-            .storage()
-            .persistent()
-            .get::<DepositDataKey, AssetParams>(&asset_params_key)
-        {
-            params.collateral_factor
-        } else {
-            10000 // Default 100% if not configured
-        }
-    } else {
-        10000 // Default 100% for native XLM
-    };
+    let collateral_factor = get_collateral_factor(env, asset);
 
     // Calculate total debt (debt + accrued interest)
     let _total_debt = position
@@ -137,7 +175,8 @@ fn validate_collateral_ratio_after_withdraw(
 /// Allows users to withdraw their deposited collateral, subject to:
 /// - Sufficient collateral balance
 /// - Minimum collateral ratio requirements
-/// - Pause switch checks
+/// - Pause switch checks, skipped for users without debt once the protocol
+///   is in emergency withdrawal mode
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
@@ -171,28 +210,143 @@ pub fn withdraw_collateral(
     asset: Option<Address>,
     amount: i128,
 ) -> Result<i128, WithdrawError> {
-    // Validate amount
-    if amount <= 0 {
-        return Err(WithdrawError::InvalidAmount);
+    withdraw_internal(env, user.clone(), asset, amount, &user, true)
+}
+
+/// Withdraw collateral to another address
+///
+/// Lets users send collateral straight to a cold wallet or another contract.
+/// The withdrawal is checked against the user's position exactly like
+/// `withdraw_collateral`; only the receiver of the tokens differs.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The address of the user withdrawing collateral (must authorize)
+/// * `asset` - The address of the asset contract to withdraw (None for native XLM)
+/// * `amount` - The amount to withdraw
+/// * `recipient` - The address receiving the tokens
+///
+/// # Returns
+/// Returns the updated collateral balance for the user
+///
+/// # Errors
+/// Same as `withdraw_collateral`, plus
+/// * `WithdrawError::InvalidRecipient` - If the recipient is the protocol itself
+pub fn withdraw_to(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+    recipient: Address,
+) -> Result<i128, WithdrawError> {
+    user.require_auth();
+
+    if recipient == env.current_contract_address() {
+        return Err(WithdrawError::InvalidRecipient);
     }
 
-    // Check if withdrawals are paused
-    let pause_switches_key = DepositDataKey::PauseSwitches;
-    if let Some(pause_map) = env
+    let new_collateral =
+        withdraw_internal(env, user.clone(), asset.clone(), amount, &recipient, true)?;
+    if recipient != user {
+        emit_withdraw_to(
+            env,
+            WithdrawToEvent {
+                user,
+                recipient,
+                asset,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+    Ok(new_collateral)
+}
+
+/// Whether a user owes principal or interest
+fn has_debt(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+        .map(|position| position.debt > 0 || position.borrow_interest > 0)
+        .unwrap_or(false)
+}
+
+/// Check that a user's position meets the minimum collateral ratio of
+/// withdrawing `asset`
+///
+/// Used after withdrawals made without their own check (see
+/// `withdraw_internal`).
+///
+/// # Errors
+/// * `WithdrawError::InsufficientCollateralRatio` - If the ratio is below the minimum
+pub(crate) fn check_collateral_ratio(
+    env: &Env,
+    user: &Address,
+    asset: Option<Address>,
+) -> Result<(), WithdrawError> {
+    let asset = crate::deposit::canonical_asset(env, asset);
+    match env
         .storage()
         .persistent()
-        .get::<DepositDataKey, Map<Symbol, bool>>(&pause_switches_key)
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
     {
-        if let Some(paused) = pause_map.get(Symbol::new(env, "pause_withdraw")) {
-            if paused {
-                return Err(WithdrawError::WithdrawPaused);
+        Some(position) => {
+            validate_collateral_ratio_after_withdraw(env, user, &position, 0, asset.as_ref())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Withdraw `amount` from `user`'s position and pay it to `recipient`
+///
+/// Without `check_ratio` the collateral ratio check is skipped; the caller
+/// must run `check_collateral_ratio` once it is done with the position.
+pub(crate) fn withdraw_internal(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+    recipient: &Address,
+    check_ratio: bool,
+) -> Result<i128, WithdrawError> {
+    // Validate amount
+    if amount <= 0 {
+        return Err(WithdrawError::InvalidAmount);
+    }
+
+    // Check if withdrawals are paused; in emergency withdrawal mode suppliers
+    // without debt can always exit
+    let emergency_exit =
+        crate::risk_management::is_emergency_withdrawal_mode(env) && !has_debt(env, &user);
+    if !emergency_exit {
+        let pause_switches_key = DepositDataKey::PauseSwitches;
+        if let Some(pause_map) = env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Map<Symbol, bool>>(&pause_switches_key)
+        {
+            if let Some(paused) = pause_map.get(Symbol::new(env, "pause_withdraw")) {
+                if paused {
+                    return Err(WithdrawError::WithdrawPaused);
+                }
             }
         }
+        if crate::risk_management::is_operation_paused(env, Symbol::new(env, "pause_withdraw")) {
+            return Err(WithdrawError::WithdrawPaused);
+        }
+    }
+
+    // No withdrawing collateral deposited within the action delay
+    if crate::action_delay::is_delayed(env, &user) {
+        return Err(WithdrawError::ActionDelayActive);
     }
 
     // Get current timestamp
     let timestamp = env.ledger().timestamp();
 
+    // Wrapped native XLM is booked as native XLM
+    let asset = crate::deposit::canonical_asset(env, asset);
+
     // Validate asset if provided
     if let Some(ref asset_addr) = asset {
         // Validate asset address - ensure it's not the contract itself
@@ -201,6 +355,9 @@ pub fn withdraw_collateral(
         }
     }
 
+    // Credit supply interest earned so far; it can be withdrawn too
+    crate::supply_interest::settle_supply_interest(env, &user);
+
     // Get current collateral balance
     let collateral_key = DepositDataKey::CollateralBalance(user.clone());
     let current_collateral = env
@@ -214,8 +371,19 @@ pub fn withdraw_collateral(
         return Err(WithdrawError::InsufficientCollateral);
     }
 
+    // Get user position and bring its interest up to date
+    let position_key = DepositDataKey::Position(user.clone());
+    let mut position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&position_key)
+        .ok_or(WithdrawError::InsufficientCollateral)?;
+    accrue_interest(env, &user, &mut position)?;
+
     // Validate collateral ratio after withdrawal
-    validate_collateral_ratio_after_withdraw(env, &user, amount, asset.as_ref())?;
+    if check_ratio {
+        validate_collateral_ratio_after_withdraw(env, &user, &position, amount, asset.as_ref())?;
+    }
 
     // Calculate new collateral balance
     let new_collateral = current_collateral
@@ -227,38 +395,19 @@ pub fn withdraw_collateral(
         .persistent()
         .set(&collateral_key, &new_collateral);
 
-    // Get or update user position
-    let position_key = DepositDataKey::Position(user.clone());
-    #[allow(clippy::unnecessary_lazy_evaluations)]
-    let mut position = env
-        .storage()
-        .persistent()
-        .get::<DepositDataKey, Position>(&position_key)
-        .unwrap_or_else(|| Position {
-            collateral: 0,
-            debt: 0,
-            borrow_interest: 0,
-            last_accrual_time: timestamp,
-        });
-
     // Update position
     position.collateral = new_collateral;
-    position.last_accrual_time = timestamp;
     env.storage().persistent().set(&position_key, &position);
 
-    // Handle asset transfer
-    if let Some(ref asset_addr) = asset {
-        // Transfer tokens from contract to user
-        let token_client = soroban_sdk::token::Client::new(env, asset_addr);
+    // Handle asset transfer; native XLM goes through its Stellar Asset Contract
+    if let Some(token_addr) = crate::deposit::resolve_token_address(env, &asset) {
+        // Transfer tokens from contract to recipient
+        let token_client = soroban_sdk::token::Client::new(env, &token_addr);
         token_client.transfer(
             &env.current_contract_address(), // from (this contract)
-            &user,                           // to (user)
+            recipient,                       // to (recipient)
             &amount,
         );
-    } else {
-        // Native XLM withdrawal - in Soroban, native assets are handled differently
-        // For now, we'll track it but actual XLM handling depends on Soroban's native asset support
-        // This is a placeholder for native asset handling
     }
 
     // Update user analytics
@@ -266,6 +415,9 @@ pub fn withdraw_collateral(
 
     // Update protocol analytics
     update_protocol_analytics_withdraw(env, amount)?;
+    crate::reserve::record_supply_change(env, &asset, -amount);
+    crate::storage_ttl::extend_position_ttl(env, &user);
+    crate::storage_ttl::extend_reserve_ttl(env, &asset);
 
     // Add to activity log
     add_activity_log(
@@ -280,6 +432,7 @@ pub fn withdraw_collateral(
         crate::deposit::DepositError::Overflow => WithdrawError::Overflow,
         _ => WithdrawError::Overflow,
     })?;
+    crate::health_history::record_health_snapshot(env, &user, "withdraw");
 
     // Emit withdraw event
     emit_withdrawal(
@@ -304,6 +457,86 @@ pub fn withdraw_collateral(
     Ok(new_collateral)
 }
 
+/// Get the largest amount a user can currently withdraw
+///
+/// Computes the withdrawal that leaves the collateral ratio exactly at the
+/// minimum collateral ratio (health factor 1.0 against the withdraw limit),
+/// with the debt including interest accrued up to now. A withdrawal of the
+/// returned amount passes the ratio check of `withdraw_collateral`; one unit
+/// more does not. Pause switches are not taken into account.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The address of the user
+/// * `asset` - The asset to withdraw (None for native XLM)
+///
+/// # Returns
+/// The maximum withdrawable amount; the full collateral balance when the user
+/// has no debt, and zero when the position is already at or below the minimum
+pub fn get_max_withdrawable(env: &Env, user: &Address, asset: Option<Address>) -> i128 {
+    let asset = crate::deposit::canonical_asset(env, asset);
+    let collateral = crate::supply_interest::get_user_supply_balance(env, user).max(0);
+
+    let mut position = match env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    {
+        Some(position) => position,
+        None => return collateral,
+    };
+    let pending = match crate::interest_index::pending_interest(env, user, &position) {
+        Ok(pending) => pending,
+        Err(_) => return 0,
+    };
+    position.borrow_interest = position.borrow_interest.saturating_add(pending);
+    let total_debt = position.debt.saturating_add(position.borrow_interest);
+    if total_debt <= 0 {
+        return collateral;
+    }
+
+    let collateral_factor = get_collateral_factor(env, asset.as_ref());
+    if collateral_factor <= 0 {
+        return 0;
+    }
+    let min_ratio = crate::risk_params::get_min_collateral_ratio(env).unwrap_or(15000);
+
+    // Smallest collateral whose ratio, rounded down as in the withdraw check,
+    // is still at or above the minimum
+    let min_collateral_value = ceil_div(min_ratio.saturating_mul(total_debt), 10000);
+    let min_collateral = ceil_div(
+        min_collateral_value.saturating_mul(10000),
+        collateral_factor,
+    );
+
+    collateral.saturating_sub(min_collateral).max(0)
+}
+
+fn ceil_div(numerator: i128, denominator: i128) -> i128 {
+    numerator.saturating_add(denominator - 1) / denominator
+}
+
+/// Withdraw a user's entire collateral balance
+///
+/// # Returns
+/// Returns the amount withdrawn
+///
+/// # Errors
+/// Same as `withdraw_collateral`; `WithdrawError::InsufficientCollateral` if
+/// the user has no collateral
+pub fn withdraw_all(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+) -> Result<i128, WithdrawError> {
+    let balance = crate::supply_interest::get_user_supply_balance(env, &user);
+    if balance <= 0 {
+        return Err(WithdrawError::InsufficientCollateral);
+    }
+    withdraw_collateral(env, user, asset, balance)?;
+    Ok(balance)
+}
+
 /// Update user analytics after withdrawal
 fn update_user_analytics_withdraw(
     env: &Env,
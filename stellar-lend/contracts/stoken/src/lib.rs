@@ -0,0 +1,294 @@
+//! # StellarLend Supply Token (sToken)
+//!
+//! Transferable receipt for a supplied position in one reserve of the
+//! lending pool. It implements the Soroban token interface (SEP-41), so
+//! wallets and other contracts can hold, move and compose supply positions.
+//!
+//! ## Balances
+//! The token keeps no balances of its own: `balance` reads the holder's
+//! supplied balance of the reserve from the pool, and transfers move that
+//! balance between positions inside the pool. The pool re-validates the
+//! sender's health factor when the reserve is used as collateral, so a
+//! transfer cannot leave the sender liquidatable. Minting and burning happen
+//! only through deposits and withdrawals on the pool.
+//!
+//! ## Invariants
+//! - The sum of all balances equals the pool's supply of the reserve.
+//! - Only the pool the token was initialized with is asked for balances.
+//! - `burn` and `burn_from` always fail; supply is withdrawn on the pool.
+//! - Allowances are kept by this contract and lapse after `expiration_ledger`.
+
+#![no_std]
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
+    panic_with_error, token::TokenInterface, Address, Env, MuxedAddress, String,
+};
+
+/// Errors raised by the supply token
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum STokenError {
+    /// The token was already initialized
+    AlreadyInitialized = 1,
+    /// The token has not been initialized
+    NotInitialized = 2,
+    /// Amount must not be negative
+    InvalidAmount = 3,
+    /// Amount exceeds the spender's allowance
+    InsufficientAllowance = 4,
+    /// Expiration ledger is in the past for a non-zero allowance
+    InvalidExpiration = 5,
+    /// Supply tokens are burned by withdrawing from the pool
+    BurnNotSupported = 6,
+}
+
+/// Pool entrypoints the supply token forwards balances and transfers to
+#[contractclient(name = "SupplyPoolClient")]
+pub trait SupplyPool {
+    /// Supplied balance of `user` in `asset`
+    fn supply_token_balance(env: Env, asset: Option<Address>, user: Address) -> i128;
+
+    /// Move `amount` of supplied `asset` from `from` to `to`
+    fn supply_token_transfer(
+        env: Env,
+        asset: Option<Address>,
+        from: Address,
+        to: Address,
+        amount: i128,
+    );
+}
+
+/// Static token configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    /// Lending pool holding the supplied balances
+    pub pool: Address,
+    /// Reserve asset of the pool (None for native XLM)
+    pub asset: Option<Address>,
+    pub decimals: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Allowance granted by an owner to a spender
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Storage keys of the supply token
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Token configuration: TokenMetadata
+    Metadata,
+    /// Allowance of (owner, spender): AllowanceValue
+    Allowance(Address, Address),
+}
+
+#[contractevent(data_format = "single-value")]
+#[derive(Clone, Debug)]
+pub struct Transfer {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractevent(data_format = "vec")]
+#[derive(Clone, Debug)]
+pub struct Approve {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+fn metadata(env: &Env) -> TokenMetadata {
+    env.storage()
+        .instance()
+        .get(&DataKey::Metadata)
+        .unwrap_or_else(|| panic_with_error!(env, STokenError::NotInitialized))
+}
+
+fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    match env
+        .storage()
+        .temporary()
+        .get::<DataKey, AllowanceValue>(&key)
+    {
+        Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+        _ => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
+}
+
+fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let allowance = read_allowance(env, from, spender);
+    if allowance.amount < amount {
+        panic_with_error!(env, STokenError::InsufficientAllowance);
+    }
+    if amount > 0 {
+        env.storage().temporary().set(
+            &DataKey::Allowance(from.clone(), spender.clone()),
+            &AllowanceValue {
+                amount: allowance.amount - amount,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+        );
+    }
+}
+
+fn move_balance(env: &Env, from: Address, to: Address, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(env, STokenError::InvalidAmount);
+    }
+    if amount > 0 {
+        let metadata = metadata(env);
+        SupplyPoolClient::new(env, &metadata.pool).supply_token_transfer(
+            &metadata.asset,
+            &from,
+            &to,
+            &amount,
+        );
+    }
+    Transfer { from, to, amount }.publish(env);
+}
+
+#[contract]
+pub struct SupplyToken;
+
+#[contractimpl]
+impl SupplyToken {
+    /// Bind the token to a pool reserve
+    ///
+    /// The pool admin registers the token with the pool afterwards, which
+    /// checks that it is bound to that pool and asset.
+    ///
+    /// # Arguments
+    /// * `pool` - The lending pool contract
+    /// * `asset` - The reserve asset (None for native XLM)
+    /// * `decimals` - Decimals of the reserve asset
+    /// * `name` - Token name
+    /// * `symbol` - Token symbol
+    pub fn initialize(
+        env: Env,
+        pool: Address,
+        asset: Option<Address>,
+        decimals: u32,
+        name: String,
+        symbol: String,
+    ) {
+        if env.storage().instance().has(&DataKey::Metadata) {
+            panic_with_error!(&env, STokenError::AlreadyInitialized);
+        }
+        env.storage().instance().set(
+            &DataKey::Metadata,
+            &TokenMetadata {
+                pool,
+                asset,
+                decimals,
+                name,
+                symbol,
+            },
+        );
+    }
+
+    /// Lending pool the token is bound to
+    pub fn pool(env: Env) -> Address {
+        metadata(&env).pool
+    }
+
+    /// Reserve asset the token represents (None for native XLM)
+    pub fn asset(env: Env) -> Option<Address> {
+        metadata(&env).asset
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for SupplyToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        read_allowance(&env, &from, &spender).amount
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+
+        if amount < 0 {
+            panic_with_error!(&env, STokenError::InvalidAmount);
+        }
+        let ledger = env.ledger().sequence();
+        if amount > 0 && expiration_ledger < ledger {
+            panic_with_error!(&env, STokenError::InvalidExpiration);
+        }
+
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().temporary().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+        if amount > 0 {
+            let live_for = expiration_ledger - ledger;
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, live_for, live_for);
+        }
+
+        Approve {
+            from,
+            spender,
+            amount,
+            expiration_ledger,
+        }
+        .publish(&env);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        let metadata = metadata(&env);
+        SupplyPoolClient::new(&env, &metadata.pool).supply_token_balance(&metadata.asset, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        from.require_auth();
+        move_balance(&env, from, to.address(), amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        spend_allowance(&env, &from, &spender, amount);
+        move_balance(&env, from, to, amount);
+    }
+
+    fn burn(env: Env, _from: Address, _amount: i128) {
+        panic_with_error!(&env, STokenError::BurnNotSupported);
+    }
+
+    fn burn_from(env: Env, _spender: Address, _from: Address, _amount: i128) {
+        panic_with_error!(&env, STokenError::BurnNotSupported);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        metadata(&env).decimals
+    }
+
+    fn name(env: Env) -> String {
+        metadata(&env).name
+    }
+
+    fn symbol(env: Env) -> String {
+        metadata(&env).symbol
+    }
+}